@@ -0,0 +1,134 @@
+//! measures the hot paths of the library: building the urls, the ascii conversion, parsing the JSON responses, serving
+//! the cached responses and requesting a local mock of EVDS.
+//!
+//! The benchmarks need the internals exposed by the `bench` feature, e.g. `cargo bench --features bench`.
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+use tcmb_evds_c::bench;
+use tcmb_evds_c::bench::mock_server::{self, generate_response};
+use tcmb_evds_c::evds_c::dataset_entities::TcmbEvdsJsonBackend;
+
+
+/// is the number of the observations of the responses parsed and served by the benchmarks.
+const OBSERVATION_COUNTS: [usize; 3] = [10, 1_000, 10_000];
+
+/// are the JSON backends the library is built with.
+#[cfg(feature = "simd_json")]
+const JSON_BACKENDS: [(&str, TcmbEvdsJsonBackend); 2] = [
+    ("serde_json", TcmbEvdsJsonBackend::SerdeJsonBackend),
+    ("simd_json", TcmbEvdsJsonBackend::SimdJsonBackend),
+];
+#[cfg(not(feature = "simd_json"))]
+const JSON_BACKENDS: [(&str, TcmbEvdsJsonBackend); 1] = [("serde_json", TcmbEvdsJsonBackend::SerdeJsonBackend)];
+
+
+fn bench_url_building(criterion: &mut Criterion) {
+
+    let mut group = criterion.benchmark_group("url");
+
+    for series in ["TP.DK.USD.S", "TP.DK.USD.S-TP.DK.EUR.S-TP.DK.GBP.S-TP.DK.CHF.S-TP.DK.JPY.S"] {
+        group.bench_with_input(BenchmarkId::new("build_data_url", series.len()), series, |bencher, series| {
+            bencher.iter(|| bench::build_data_url(black_box(series), "13-12-2011", "12-12-2012").unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_ascii_conversion(criterion: &mut Criterion) {
+
+    let text = "Tarih,TP_DK_USD_S\n(USD) ABD Doları (Döviz Alış),Türkiye Cumhuriyet Merkez Bankası\n".repeat(1_000);
+
+    let mut group = criterion.benchmark_group("ascii");
+
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("convert_to_ascii", |bencher| bencher.iter(|| bench::convert_to_ascii(black_box(&text))));
+    group.finish();
+}
+
+fn bench_parsing(criterion: &mut Criterion) {
+
+    let mut group = criterion.benchmark_group("parse");
+
+    for observation_count in OBSERVATION_COUNTS {
+        let response = generate_response(observation_count);
+
+        group.throughput(Throughput::Elements(observation_count as u64));
+        group.bench_with_input(BenchmarkId::new("from_json", observation_count), &response, |bencher, response| {
+            bencher.iter(|| bench::parse_json(black_box(response)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+/// compares the JSON backends on a data group sized response, e.g. `cargo bench --features bench,simd_json`.
+fn bench_json_backends(criterion: &mut Criterion) {
+
+    let response = generate_response(*OBSERVATION_COUNTS.last().unwrap());
+
+    let mut group = criterion.benchmark_group("json_backend");
+
+    group.throughput(Throughput::Bytes(response.len() as u64));
+
+    for (name, json_backend) in JSON_BACKENDS {
+        group.bench_with_input(BenchmarkId::new(name, response.len()), &response, |bencher, response| {
+            bencher.iter(|| bench::parse_json_value(black_box(response), json_backend).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_cache_path(criterion: &mut Criterion) {
+
+    let mut group = criterion.benchmark_group("cache");
+
+    for observation_count in OBSERVATION_COUNTS {
+        let series = format!("TP.BENCH.{}", observation_count);
+
+        bench::cache_response(&series, "01-01-2000", "31-12-2099", &generate_response(observation_count)).unwrap();
+
+        group.throughput(Throughput::Elements(observation_count as u64));
+        group.bench_with_input(BenchmarkId::new("get_data", observation_count), &series, |bencher, series| {
+            bencher.iter(|| bench::get_cached_data(black_box(series), "01-01-2000", "31-12-2099").unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+#[cfg(feature = "sync_mode")]
+fn bench_mock_server(criterion: &mut Criterion) {
+
+    let mut group = criterion.benchmark_group("mock_server");
+
+    for observation_count in OBSERVATION_COUNTS {
+        let response_length = generate_response(observation_count).len();
+
+        let url = format!("http://{}/series=TP.DK.USD.S&type=json", mock_server::start_mock_server(observation_count));
+
+        group.throughput(Throughput::Bytes(response_length as u64));
+        group.bench_with_input(BenchmarkId::new("request", observation_count), &url, |bencher, url| {
+            bencher.iter(|| bench::request(black_box(url)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+#[cfg(not(feature = "sync_mode"))]
+fn bench_mock_server(_criterion: &mut Criterion) {}
+
+
+criterion_group!(
+    benches,
+    bench_url_building,
+    bench_ascii_conversion,
+    bench_parsing,
+    bench_json_backends,
+    bench_cache_path,
+    bench_mock_server
+);
+criterion_main!(benches);
@@ -1,13 +1,240 @@
 extern crate cbindgen;
 
+use std::collections::HashMap;
 use std::env;
-use std::path::PathBuf;
-use cbindgen::{Config, Language};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use cbindgen::{Config, DocumentationStyle, ExportConfig, FunctionConfig, Language, PtrConfig};
+
+
+/// defines the annotations written into the header for Swift and Objective-C. They are empty for other compilers.
+const ANNOTATION_MACROS: &str = "\
+#if defined(__clang__)
+#define TCMB_EVDS_C_NULLABLE _Nullable
+#define TCMB_EVDS_C_NONNULL _Nonnull
+#define TCMB_EVDS_C_SWIFT_NAME(_name) __attribute__((swift_name(#_name)))
+#else
+#define TCMB_EVDS_C_NULLABLE
+#define TCMB_EVDS_C_NONNULL
+#define TCMB_EVDS_C_SWIFT_NAME(_name)
+#endif";
+
+/// are the features grouping the FFI functions with the macros declaring their functions in the header. The macros
+/// of the features enabled in the build are defined in the header, so the header declares only the built functions.
+const FUNCTION_GROUP_FEATURES: [(&str, &str); 5] = [
+  ("currency", "TCMB_EVDS_C_CURRENCY"),
+  ("catalog", "TCMB_EVDS_C_CATALOG"),
+  ("cache", "TCMB_EVDS_C_CACHE"),
+  ("parse", "TCMB_EVDS_C_PARSE"),
+  ("export", "TCMB_EVDS_C_EXPORT"),
+];
+
+/// is the module map written next to the header to import the library as the `CTcmbEvds` module in Swift and 
+/// Objective-C.
+const MODULE_MAP: &str = "\
+module CTcmbEvds {
+    header \"tcmb_evds_c.h\"
+    link \"tcmb_evds_c\"
+    export *
+}
+";
+
+/// is the beginning of the generated C# binding.
+const CSHARP_HEADER: &str = "\
+// is generated from src/evds_c/flat_entities.rs by the build script of tcmb_evds_c. Do not edit it.
+
+using System;
+using System.Runtime.InteropServices;
+using System.Text;
+
+namespace TcmbEvds
+{
+";
+
+/// is the part of the C# binding that wraps the imported functions with strings and exceptions.
+const CSHARP_WRAPPER: &str = r#"
+    public class EvdsException : Exception
+    {
+        public ReturnErrorC Error { get; }
+        public string Detail { get; }
+
+        public EvdsException(ReturnErrorC error, string message, string detail) : base(message)
+        {
+            Error = error;
+            Detail = detail;
+        }
+    }
+
+    public class Evds
+    {
+        private readonly byte[] apiKey;
+
+        public Evds(string apiKey)
+        {
+            this.apiKey = Encode(apiKey);
+        }
+
+        public string GetData(
+            string dataSeries,
+            string date,
+            TcmbEvdsReturnFormat format = TcmbEvdsReturnFormat.Json,
+            bool asciiMode = false)
+        {
+            byte[] series = Encode(dataSeries), dates = Encode(date);
+
+            return Read(NativeMethods.tcmb_evds_c_flat_get_data(
+                series, Length(series), dates, Length(dates), apiKey, Length(apiKey), (uint)format, asciiMode ? 1 : 0));
+        }
+
+        public string GetAdvancedData(
+            string currencySeries,
+            string date,
+            TcmbEvdsAggregationType aggregationType,
+            TcmbEvdsFormula formula,
+            TcmbEvdsDataFrequency dataFrequency,
+            TcmbEvdsReturnFormat format = TcmbEvdsReturnFormat.Json,
+            bool asciiMode = false)
+        {
+            byte[] series = Encode(currencySeries), dates = Encode(date);
+
+            return Read(NativeMethods.tcmb_evds_c_flat_get_advanced_data(
+                series, Length(series), dates, Length(dates), 
+                (uint)aggregationType, (uint)formula, (uint)dataFrequency, 
+                apiKey, Length(apiKey), (uint)format, asciiMode ? 1 : 0));
+        }
+
+        public string GetDataGroup(
+            string dataGroup,
+            string date,
+            TcmbEvdsReturnFormat format = TcmbEvdsReturnFormat.Json,
+            bool asciiMode = false)
+        {
+            byte[] group = Encode(dataGroup), dates = Encode(date);
+
+            return Read(NativeMethods.tcmb_evds_c_flat_get_data_group(
+                group, Length(group), dates, Length(dates), apiKey, Length(apiKey), (uint)format, asciiMode ? 1 : 0));
+        }
+
+        public string GetCategories(TcmbEvdsReturnFormat format = TcmbEvdsReturnFormat.Json, bool asciiMode = false)
+        {
+            return Read(NativeMethods.tcmb_evds_c_flat_get_categories(
+                apiKey, Length(apiKey), (uint)format, asciiMode ? 1 : 0));
+        }
+
+        public string GetSeriesList(
+            string code, TcmbEvdsReturnFormat format = TcmbEvdsReturnFormat.Json, bool asciiMode = false)
+        {
+            byte[] codes = Encode(code);
+
+            return Read(NativeMethods.tcmb_evds_c_flat_get_series_list(
+                codes, Length(codes), apiKey, Length(apiKey), (uint)format, asciiMode ? 1 : 0));
+        }
+
+        // the texts are followed by a NUL character, so that the arrays of empty texts are not passed as NULL.
+        private static byte[] Encode(string text) => Encoding.UTF8.GetBytes((text ?? "") + "\0");
+
+        private static UIntPtr Length(byte[] text) => (UIntPtr)(text.Length - 1);
+
+        private static string Read(IntPtr handle)
+        {
+            try
+            {
+                string text = Decode(
+                    NativeMethods.tcmb_evds_c_flat_handle_data(handle), 
+                    NativeMethods.tcmb_evds_c_flat_handle_len(handle));
+                ReturnErrorC error = NativeMethods.tcmb_evds_c_flat_handle_error(handle);
+
+                if (error != ReturnErrorC.NoError)
+                {
+                    string detail = Decode(
+                        NativeMethods.tcmb_evds_c_flat_handle_detail(handle), 
+                        NativeMethods.tcmb_evds_c_flat_handle_detail_len(handle));
+
+                    throw new EvdsException(error, text, detail);
+                }
+
+                return text;
+            }
+            finally
+            {
+                NativeMethods.tcmb_evds_c_flat_handle_free(handle);
+            }
+        }
+
+        private static string Decode(IntPtr text, UIntPtr length)
+        {
+            if (text == IntPtr.Zero) { return null; }
+
+            byte[] bytes = new byte[(int)length];
+            Marshal.Copy(text, bytes, 0, bytes.Length);
+
+            return Encoding.UTF8.GetString(bytes);
+        }
+    }
+"#;
+
+/// are the C enums whose values the functions receive as unsigned integers.
+const INPUT_ENUMS: [&str; 18] = [
+  "TcmbEvdsAggregationType", "TcmbEvdsFormula", "TcmbEvdsDataFrequency", "TcmbEvdsHugeRequestPolicy",
+  "TcmbEvdsReturnFormat", "TcmbEvdsAsciiMode", "TcmbEvdsHeaderLanguage", "TcmbEvdsCurrencyCode",
+  "TcmbEvdsExchangeSide", "TcmbEvdsDatePolicy", "TcmbEvdsErrorFormat", "TcmbEvdsJsonBackend", "TcmbEvdsLayout",
+  "TcmbEvdsOutlierMethod", "TcmbEvdsKeyRotation", "TcmbEvdsPostProcessor", "TcmbEvdsPriority",
+  "TcmbEvdsRecordingMode",
+];
+
+/// describes a table of the header mapping the values of a C enum into the texts that the crate sends to EVDS, which
+/// are read from the `as_str` function of the Rust enum the C enum is converted into.
+struct EnumStringTable {
+  table_name: &'static str,
+  c_enum: &'static str,
+  c_source: &'static str,
+  rust_enum: &'static str,
+  rust_source: &'static str,
+}
+
+/// are the tables of the C enums whose values have texts in the urls of EVDS.
+const ENUM_STRING_TABLES: [EnumStringTable; 4] = [
+  EnumStringTable {
+      table_name: "TCMB_EVDS_C_AGGREGATION_TYPE_STRINGS",
+      c_enum: "TcmbEvdsAggregationType",
+      c_source: "evds_c/advanced_entities.rs",
+      rust_enum: "AggregationType",
+      rust_source: "evds_currency/frequency_formulas.rs",
+  },
+  EnumStringTable {
+      table_name: "TCMB_EVDS_C_FORMULA_STRINGS",
+      c_enum: "TcmbEvdsFormula",
+      c_source: "evds_c/advanced_entities.rs",
+      rust_enum: "Formula",
+      rust_source: "evds_currency/frequency_formulas.rs",
+  },
+  EnumStringTable {
+      table_name: "TCMB_EVDS_C_DATA_FREQUENCY_STRINGS",
+      c_enum: "TcmbEvdsDataFrequency",
+      c_source: "evds_c/advanced_entities.rs",
+      rust_enum: "DataFrequency",
+      rust_source: "evds_currency/frequency_formulas.rs",
+  },
+  EnumStringTable {
+      table_name: "TCMB_EVDS_C_RETURN_FORMAT_STRINGS",
+      c_enum: "TcmbEvdsReturnFormat",
+      c_source: "evds_c/common_entities.rs",
+      rust_enum: "ReturnFormat",
+      rust_source: "common/mod.rs",
+  },
+];
 
 
 fn main() {
   let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
 
+  // the addon leaves the N-API functions to be resolved by the Node.js process loading it.
+  #[cfg(feature = "napi")]
+  napi_build::setup();
+
+  emit_build_metadata(&crate_dir);
+
   let package_name = env::var("CARGO_PKG_NAME").unwrap();
   let output_file = target_dir()
       .join(format!("{}.h", package_name))
@@ -18,12 +245,296 @@ fn main() {
       include_guard: Some("TCMB_EVDS_C_H".to_string()),
       line_length: 120,
       language: Language::C,
+      // the functions of optional features are declared only when the C code defines their macros.
+      defines: FUNCTION_GROUP_FEATURES
+          .iter()
+          .map(|(feature, macro_name)| (format!("feature = {}", feature), macro_name.to_string()))
+          .chain([
+              ("feature = tracing".to_string(), "TCMB_EVDS_C_TRACING".to_string()),
+              ("feature = seasonal".to_string(), "TCMB_EVDS_C_SEASONAL".to_string()),
+          ])
+          .collect(),
+      // the line comments keep the block comments of the C examples, and Xcode shows them as documentation.
+      documentation_style: DocumentationStyle::Cxx,
+      after_includes: Some(format!("{}\n{}", ANNOTATION_MACROS, generate_feature_macros())),
+      function: FunctionConfig { 
+          swift_name_macro: Some("TCMB_EVDS_C_SWIFT_NAME".to_string()), 
+          ..Default::default() 
+      },
+      pointer: PtrConfig { non_null_attribute: Some("TCMB_EVDS_C_NONNULL".to_string()) },
+      // the functions receive the input enums as unsigned integers, so the enums are exported on their own.
+      export: ExportConfig {
+          include: INPUT_ENUMS.iter().map(|input_enum| input_enum.to_string()).collect(),
+          ..Default::default()
+      },
       ..Default::default()
   };
+
+  let mut header = Vec::new();
                                                                                                                       
   cbindgen::generate_with_config(&crate_dir, config)
     .unwrap()
-    .write_to_file(&output_file);
+    .write(&mut header);
+
+  let header = annotate_nullable_pointers(&String::from_utf8(header).unwrap());
+  let enum_string_tables = generate_enum_string_tables(&PathBuf::from(&crate_dir).join("src"));
+  let header = insert_before_include_guard_end(&header, &enum_string_tables);
+
+  write_if_changed(&output_file, &header);
+  write_if_changed(&target_dir().join("module.modulemap").display().to_string(), MODULE_MAP);
+
+  let binding = generate_csharp_binding(&PathBuf::from(&crate_dir).join("src/evds_c"));
+
+  write_if_changed(&target_dir().join("TcmbEvds.cs").display().to_string(), &binding);
+}
+
+
+/// passes the metadata of the build into the crate to be read via `tcmb_evds_c_build_info`. The metadata does not 
+/// include the build time, so the same sources and features always produce the same metadata.
+fn emit_build_metadata(crate_dir: &str) {
+
+  // the builds from source archives without the git history may give the commit via `TCMB_EVDS_C_GIT_HASH`.
+  let git_hash = env::var("TCMB_EVDS_C_GIT_HASH").ok().or_else(|| read_git_hash(crate_dir)).unwrap_or_default();
+
+  let mut features = env::vars()
+      .filter_map(|(name, _)| Some(name.strip_prefix("CARGO_FEATURE_")?.to_lowercase()))
+      .collect::<Vec<String>>();
+
+  features.sort();
+
+  println!("cargo:rustc-env=TCMB_EVDS_C_GIT_HASH={}", git_hash);
+  println!("cargo:rustc-env=TCMB_EVDS_C_FEATURES={}", features.join(","));
+  println!("cargo:rustc-env=TCMB_EVDS_C_TARGET={}", env::var("TARGET").unwrap_or_default());
+  println!("cargo:rustc-env=TCMB_EVDS_C_PROFILE={}", env::var("PROFILE").unwrap_or_default());
+}
+
+/// reads the hash of the checked out commit, followed by "-dirty" when the sources have uncommitted changes. None is 
+/// returned when the sources are not in a git repository or git is not installed.
+fn read_git_hash(crate_dir: &str) -> Option<String> {
+
+  let run_git = |arguments: &[&str]| {
+      Command::new("git")
+          .args(arguments)
+          .current_dir(crate_dir)
+          .output()
+          .ok()
+          .filter(|output| output.status.success())
+          .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+  };
+
+  let git_hash = run_git(&["rev-parse", "HEAD"])?;
+
+  match run_git(&["status", "--porcelain", "--untracked-files=no"]) {
+      Some(changes) if !changes.is_empty() => Some(format!("{}-dirty", git_hash)),
+      _ => Some(git_hash),
+  }
+}
+
+/// defines the macros of the function groups enabled in the build, unless the C code already defines them.
+fn generate_feature_macros() -> String {
+  FUNCTION_GROUP_FEATURES
+      .iter()
+      .filter(|(feature, _)| env::var_os(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_some())
+      .map(|(_, macro_name)| format!("#ifndef {0}\n#define {0}\n#endif\n", macro_name))
+      .collect()
+}
+
+/// marks the raw pointers of the declarations as nullable since all of them accept or may return NULL. The pointers 
+/// required to be non-null are already marked by cbindgen. The comments and the directives are kept as they are.
+fn annotate_nullable_pointers(header: &str) -> String {
+  header
+      .lines()
+      .map(|line| {
+          let trimmed_line = line.trim_start();
+
+          if trimmed_line.starts_with("//") || trimmed_line.starts_with('#') {
+              return line.to_string();
+          }
+
+          line
+              .split('*')
+              .enumerate()
+              .map(|(index, part)| match index > 0 && !part.starts_with("TCMB_EVDS_C_NONNULL") {
+                  true => format!("TCMB_EVDS_C_NULLABLE {}", part.trim_start()),
+                  false => part.to_string(),
+              })
+              .collect::<Vec<String>>()
+              .join("*")
+      })
+      .map(|line| line + "\n")
+      .collect()
+}
+
+/// inserts the text before the end of the include guard, so that the text is also guarded against double inclusion.
+fn insert_before_include_guard_end(header: &str, text: &str) -> String {
+
+  let guard_end = header.rfind("#endif").unwrap();
+
+  format!("{}{}\n{}", &header[..guard_end], text, &header[guard_end..])
+}
+
+/// generates the tables of [`ENUM_STRING_TABLES`] indexed by the values of the C enums. The values that are not sent 
+/// to EVDS as they are, e.g. `SessionFormat`, are NULL.
+fn generate_enum_string_tables(source_dir: &Path) -> String {
+
+  let read_source = |file_name: &str| fs::read_to_string(source_dir.join(file_name)).unwrap();
+
+  let mut tables = String::new();
+
+  for table in &ENUM_STRING_TABLES {
+      let texts = read_as_str_texts(&read_source(table.rust_source), table.rust_enum);
+      let variants = read_enum_variants(&read_source(table.c_source), table.c_enum);
+
+      let entries = variants
+          .iter()
+          .map(|variant| match texts.get(variant) {
+              Some(text) => format!("    \"{}\", // {}\n", text, variant),
+              None => format!("    NULL, // {}\n", variant),
+          })
+          .collect::<String>();
+
+      tables += &format!(
+          "\n// maps the values of {} into the texts sent to EVDS,\n// e.g. {}[{}] is \"{}\".\n\
+          static const char *const {}[] = {{\n{}}};\n",
+          table.c_enum, table.table_name, variants[0], texts[&variants[0]], table.table_name, entries,
+      );
+  }
+
+  tables
+}
+
+/// reads the texts returned from the `as_str` function of the Rust enum for each of its variants.
+fn read_as_str_texts(source: &str, enum_name: &str) -> HashMap<String, String> {
+
+  let function_body = source
+      .split(&format!("impl {} {{", enum_name))
+      .skip(1)
+      .find_map(|implementation| implementation.split("\n}\n").next()?.split_once("fn as_str(&self)"))
+      .map(|(_, function_body)| function_body)
+      .unwrap_or_else(|| panic!("the as_str function of the {} enum is not found", enum_name));
+
+  let mut texts = HashMap::new();
+
+  for (variants, text) in function_body.lines().filter_map(|line| line.split_once("=>")) {
+      let text = text.trim().trim_end_matches(',').trim_matches('"');
+
+      for variant in variants.split('|') {
+          texts.insert(variant.trim().trim_start_matches("Self::").to_string(), text.to_string());
+      }
+  }
+
+  texts
+}
+
+/// generates the C# binding of the flattened functions in `flat_entities.rs` together with the C enums they use.
+fn generate_csharp_binding(source_dir: &Path) -> String {
+
+  let read_source = |file_name: &str| fs::read_to_string(source_dir.join(file_name)).unwrap();
+
+  let mut binding = String::from(CSHARP_HEADER);
+
+  binding += &generate_csharp_enums(&read_source("error_handling.rs"), &["ReturnErrorC"]);
+  binding += &generate_csharp_enums(&read_source("common_entities.rs"), &["TcmbEvdsReturnFormat"]);
+  binding += &generate_csharp_enums(
+      &read_source("advanced_entities.rs"), 
+      &["TcmbEvdsAggregationType", "TcmbEvdsFormula", "TcmbEvdsDataFrequency"],
+  );
+
+  binding += "    internal static class NativeMethods\n    {\n";
+  binding += "        private const string Library = \"tcmb_evds_c\";\n";
+
+  let flat_source = read_source("flat_entities.rs");
+
+  for declaration in flat_source.split("pub unsafe extern \"system\" fn ").skip(1) {
+      binding += &generate_csharp_import(declaration);
+  }
+
+  binding += "    }\n";
+  binding += CSHARP_WRAPPER;
+  binding += "}\n";
+
+  binding
+}
+
+/// generates a C# enum for each named C enum in the source. The values are the default ones in the declared order.
+fn generate_csharp_enums(source: &str, enum_names: &[&str]) -> String {
+
+  let mut enums = String::new();
+
+  for enum_name in enum_names {
+      let variants = read_enum_variants(source, enum_name)
+          .into_iter()
+          .map(|variant| format!("        {},\n", variant))
+          .collect::<String>();
+
+      enums += &format!("    public enum {}\n    {{\n{}    }}\n\n", enum_name, variants);
+  }
+
+  enums
+}
+
+/// reads the variants of the named C enum in the source in the declared order.
+fn read_enum_variants(source: &str, enum_name: &str) -> Vec<String> {
+
+  let enum_body = source
+      .split(&format!("pub enum {} {{", enum_name))
+      .nth(1)
+      .and_then(|rest| rest.split('}').next())
+      .unwrap_or_else(|| panic!("the {} enum is not found", enum_name));
+
+  enum_body
+      .lines()
+      .map(str::trim)
+      .filter(|line| !line.is_empty() && !line.starts_with("//") && !line.starts_with('#'))
+      .map(|line| line.trim_end_matches(',').to_string())
+      .collect()
+}
+
+/// generates the `DllImport` declaration of a flattened function from the part of its Rust declaration after `fn`.
+fn generate_csharp_import(declaration: &str) -> String {
+
+  let (function_name, rest) = declaration.split_once('(').unwrap();
+  let (parameters, rest) = rest.split_once(')').unwrap();
+
+  let return_type = match rest.split('{').next().unwrap().trim().strip_prefix("->") {
+      Some(return_type) => to_csharp_type(return_type.trim(), false),
+      None => "void",
+  };
+
+  let parameters = parameters
+      .split(',')
+      .filter_map(|parameter| parameter.split_once(':'))
+      .map(|(name, rust_type)| format!("{} {}", to_csharp_type(rust_type.trim(), true), name.trim()))
+      .collect::<Vec<String>>()
+      .join(", ");
+
+  format!(
+      "\n        [DllImport(Library, CallingConvention = CallingConvention.Winapi)]\n        \
+      internal static extern {} {}({});\n",
+      return_type, function_name, parameters,
+  )
+}
+
+/// maps a Rust type of the flattened functions into its C# type. The texts are passed as UTF-8 byte arrays.
+fn to_csharp_type(rust_type: &str, is_parameter: bool) -> &str {
+  match rust_type {
+      "*const c_char" if is_parameter => "byte[]",
+      "usize" => "UIntPtr",
+      "c_int" => "int",
+      "c_uint" => "uint",
+      "*const u8" | "*const TcmbEvdsHandle" | "*mut TcmbEvdsHandle" => "IntPtr",
+      "ReturnErrorC" => "ReturnErrorC",
+      other_type => panic!("the {} type is not mapped into a C# type", other_type),
+  }
+}
+
+/// writes the file unless it has the same content, so that the dependents of the file are not rebuilt.
+fn write_if_changed(path: &str, content: &str) {
+
+  if fs::read_to_string(path).is_ok_and(|old_content| old_content == content) { return; }
+
+  fs::create_dir_all(target_dir()).unwrap();
+  fs::write(path, content).unwrap();
 }
 
 
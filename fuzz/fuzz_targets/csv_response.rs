@@ -0,0 +1,10 @@
+//! parses EVDS CSV responses.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = tcmb_evds_c::fuzz::parse_csv(text);
+    }
+});
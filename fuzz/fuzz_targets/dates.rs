@@ -0,0 +1,11 @@
+//! checks the date data given to the data requests, e.g. "13-12-2011, 13-12-2021", "2023-Q2" and the date ranges
+//! separated by semicolons.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = tcmb_evds_c::fuzz::check_dates(text);
+    }
+});
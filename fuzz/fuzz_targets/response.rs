@@ -0,0 +1,10 @@
+//! parses responses of any return format recognized by their first character, including XML.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = tcmb_evds_c::fuzz::parse_response(text);
+    }
+});
@@ -0,0 +1,10 @@
+//! parses the currency series separated by dashes, e.g. "TP.DK.USD.A-TP.DK.EUR.S.YTL".
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = tcmb_evds_c::fuzz::parse_series(text);
+    }
+});
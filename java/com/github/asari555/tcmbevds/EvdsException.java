@@ -0,0 +1,8 @@
+package com.github.asari555.tcmbevds;
+
+/** is thrown by {@link TcmbEvds} for the errors of the library. The detail of a curl error follows the message. */
+public class EvdsException extends Exception {
+    public EvdsException(String message) {
+        super(message);
+    }
+}
@@ -0,0 +1,36 @@
+package com.github.asari555.tcmbevds;
+
+/**
+ * makes the EVDS requests via the tcmb_evds_c library built with the `jni` feature.
+ *
+ * The methods throw {@link EvdsException} with the message of the library when a request fails, and
+ * IllegalArgumentException when the return format is not one of the constants below.
+ */
+public final class TcmbEvds {
+    public static final int CSV = 0;
+    public static final int JSON = 1;
+    public static final int XML = 2;
+    public static final int AUTO = 3;
+    public static final int SESSION_FORMAT = 4;
+
+    static {
+        System.loadLibrary("tcmb_evds_c");
+    }
+
+    private TcmbEvds() {}
+
+    public static native String getData(
+        String dataSeries, String date, String apiKey, int returnFormat, boolean asciiMode) throws EvdsException;
+
+    /** gives the response of {@link #getData} in UTF-8 bytes. */
+    public static native byte[] getDataBytes(
+        String dataSeries, String date, String apiKey, int returnFormat, boolean asciiMode) throws EvdsException;
+
+    public static native String getDataGroup(
+        String dataGroup, String date, String apiKey, int returnFormat, boolean asciiMode) throws EvdsException;
+
+    public static native String getCategories(String apiKey, int returnFormat, boolean asciiMode) throws EvdsException;
+
+    public static native String getSeriesList(
+        String code, String apiKey, int returnFormat, boolean asciiMode) throws EvdsException;
+}
@@ -0,0 +1,18 @@
+/** is the return format of the responses. The session format of the library is used when it is omitted. */
+export type ReturnFormat = 'csv' | 'json' | 'xml' | 'auto';
+
+/**
+ * is the error of a rejected promise. The code is the error type of the library, e.g. `InvalidDate`, and the message
+ * is followed by the detail of the curl error if there is any.
+ */
+export interface EvdsError extends Error {
+  code: string;
+}
+
+export function getData(
+  dataSeries: string, date: string, apiKey: string, returnFormat?: ReturnFormat, asciiMode?: boolean
+): Promise<string>;
+
+export function getDataGroup(
+  dataGroup: string, date: string, apiKey: string, returnFormat?: ReturnFormat, asciiMode?: boolean
+): Promise<string>;
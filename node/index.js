@@ -0,0 +1,10 @@
+'use strict';
+
+const path = require('path');
+
+// the library is built via `cargo build --release --features napi` and copied as an addon, e.g.
+// `cp target/release/libtcmb_evds_c.so target/release/tcmb_evds_c.node`, or `libtcmb_evds_c.dylib` on macOS and
+// `tcmb_evds_c.dll` on Windows.
+const addonPath = process.env.TCMB_EVDS_C_ADDON || path.join(__dirname, '..', 'target', 'release', 'tcmb_evds_c.node');
+
+module.exports = require(addonPath);
@@ -0,0 +1,110 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+
+/// generates an EVDS JSON response of the series with the number of observations.
+pub fn generate_response(observation_count: usize) -> String {
+
+    let items: Vec<String> = (0..observation_count)
+        .map(|index| {
+            let (date, value) = generate_observation(index);
+
+            format!(
+                r#"{{"Tarih":"{}","TP_DK_USD_S":"{}","UNIXTIME":{{"$numberLong":"{}"}}}}"#,
+                date, value, 946_677_600 + index * 86_400
+            )
+        })
+        .collect();
+
+    format!(r#"{{"totalCount":{},"items":[{}]}}"#, observation_count, items.join(","))
+}
+
+/// generates an EVDS CSV response of the same observations with [`generate_response`].
+pub fn generate_csv_response(observation_count: usize) -> String {
+
+    let rows: String = (0..observation_count)
+        .map(|index| {
+            let (date, value) = generate_observation(index);
+
+            format!("{},{}\n", date, value)
+        })
+        .collect();
+
+    format!("Tarih,TP_DK_USD_S\n{}", rows)
+}
+
+/// starts a local mock of EVDS answering every request with the responses of the observations and keeping the
+/// connections alive as EVDS does, and gives its address. The requests of CSV are answered with
+/// [`generate_csv_response`] and the others with [`generate_response`].
+pub fn start_mock_server(observation_count: usize) -> SocketAddr {
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let json_response = generate_http_response("application/json", &generate_response(observation_count));
+    let csv_response = generate_http_response("text/csv", &generate_csv_response(observation_count));
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let (json_response, csv_response) = (json_response.clone(), csv_response.clone());
+
+            thread::spawn(move || serve_connection(stream, &json_response, &csv_response));
+        }
+    });
+
+    address
+}
+
+/// gives the date and the value of the observation at the index, e.g. "01-01-2000" and "0.0000".
+fn generate_observation(index: usize) -> (String, String) {
+
+    let (day, month, year) = (index % 28 + 1, index / 28 % 12 + 1, 2000 + index / 336);
+
+    (format!("{:02}-{:02}-{}", day, month, year), format!("{}.{:04}", index % 10, index % 10_000))
+}
+
+fn generate_http_response(content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: keep-alive\r\n\r\n{}",
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+fn serve_connection(stream: TcpStream, json_response: &str, csv_response: &str) {
+
+    // the response is written at once without waiting for the acknowledgement of the previous one.
+    if stream.set_nodelay(true).is_err() { return; }
+
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(_) => return,
+    };
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+
+    loop {
+        let mut is_csv_requested = None;
+
+        // the requests of the library are GET requests without a body, so a request ends with its first empty line.
+        loop {
+            line.clear();
+
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => return,
+                Ok(_) if line.trim_end().is_empty() => break,
+                // the first line is the request line including the url.
+                Ok(_) => { is_csv_requested.get_or_insert_with(|| line.contains("type=csv")); },
+            }
+        }
+
+        let http_response = match is_csv_requested {
+            Some(true) => csv_response,
+            _ => json_response,
+        };
+
+        if writer.write_all(http_response.as_bytes()).is_err() { return; }
+    }
+}
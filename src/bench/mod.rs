@@ -0,0 +1,139 @@
+use std::borrow::Cow;
+use std::env;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::common::{ApiKey, Evds, ReturnFormat};
+use crate::dataset::Dataset;
+use crate::dataset::cache::get_response_cache;
+use crate::dataset::json_backend::parse_json_with;
+use crate::date::{DatePreference, DateRange};
+use crate::error::ReturnError;
+use crate::evds_basic;
+use crate::evds_c;
+use crate::evds_c::dataset_entities::TcmbEvdsJsonBackend;
+use crate::evds_core::url::EVDS_URL_ROOT;
+
+/// serves a local mock of EVDS to the benchmarks and the soak test.
+pub mod mock_server;
+
+
+/// is the api key of the benchmarks, which is never verified via EVDS.
+const BENCH_API_KEY: &str = "BENCHMARKKEY";
+
+/// is the environment variable giving the url root of the mock to the processes that cannot call
+/// [`set_mock_url_root`], e.g. the C programs of the contract tests.
+pub const MOCK_URL_ROOT_VARIABLE: &str = "TCMB_EVDS_C_MOCK_URL_ROOT";
+
+/// is the url root replacing the root of EVDS in the requests, e.g. "http://127.0.0.1:8080/", set via 
+/// [`set_mock_url_root`].
+static MOCK_URL_ROOT: Mutex<Option<String>> = Mutex::new(None);
+
+
+/// builds the url of [`tcmb_evds_c_get_data`](crate::tcmb_evds_c_get_data) for the series and the dates, e.g.
+/// "13-12-2011" and "12-12-2012", in CSV.
+pub fn build_data_url(data_series: &str, start_date: &str, end_date: &str) -> Result<String, String> {
+
+    let date_preference = generate_date_preference(start_date, end_date)?;
+    let evds = generate_evds(ReturnFormat::Csv)?;
+
+    evds_basic::generate_data_url(data_series, &date_preference, &evds).map(|url| url.build()).map_err(to_message)
+}
+
+/// converts the Turkish characters of the text to English ones as done in ascii mode.
+pub fn convert_to_ascii(text: &str) -> String {
+
+    let mut text = text.to_string();
+
+    evds_c::convert_to_ascii(&mut text);
+
+    text
+}
+
+/// parses an EVDS JSON response and gives the number of its observations.
+pub fn parse_json(response: &str) -> Result<usize, String> {
+    Dataset::from_json(response).map(|dataset| dataset.observations.len()).map_err(to_message)
+}
+
+/// parses a JSON response via the backend without reading the observations, e.g. to compare the backends.
+pub fn parse_json_value(response: &str, json_backend: TcmbEvdsJsonBackend) -> Result<(), String> {
+    parse_json_with(response, json_backend).map(|_| ()).map_err(to_message)
+}
+
+/// enables the response cache and keeps the parsed JSON response as the response of the series in the dates, so that
+/// [`get_cached_data`] is served from the cache without requesting EVDS.
+pub fn cache_response(data_series: &str, start_date: &str, end_date: &str, response: &str) -> Result<(), String> {
+
+    let date_preference = generate_date_preference(start_date, end_date)?;
+    let evds = generate_evds(ReturnFormat::Json)?;
+
+    let url = evds_basic::generate_data_url(data_series, &date_preference, &evds).map_err(to_message)?;
+    let dataset = Dataset::from_json(response).map_err(to_message)?;
+
+    let mut response_cache = get_response_cache();
+
+    if !response_cache.is_enabled() { response_cache.set_capacity(1); }
+
+    response_cache.insert(url.build_cache_key(), dataset);
+
+    Ok(())
+}
+
+/// requests the series in the dates in CSV via the same path with
+/// [`tcmb_evds_c_get_data`](crate::tcmb_evds_c_get_data).
+pub fn get_cached_data(data_series: &str, start_date: &str, end_date: &str) -> Result<String, String> {
+
+    let date_preference = generate_date_preference(start_date, end_date)?;
+    let evds = generate_evds(ReturnFormat::Csv)?;
+
+    evds_basic::get_data(data_series, &date_preference, &evds).map_err(to_message)
+}
+
+/// requests the url through the whole request pipeline, e.g. the circuit breaker, the request coalescing and the rate
+/// limit retries.
+#[cfg(feature = "sync_mode")]
+pub fn request(url: &str) -> Result<String, String> {
+    crate::request_sync::do_request(url).map_err(to_message)
+}
+
+/// sends the later requests of the FFI functions to the url root, e.g. the root of
+/// [`start_mock_server`](mock_server::start_mock_server) as "http://127.0.0.1:8080/", instead of EVDS. None sends them
+/// to EVDS again. Only the requests made in `sync_mode` are redirected.
+pub fn set_mock_url_root(url_root: Option<&str>) {
+    *get_mock_url_root() = url_root.map(str::to_string);
+}
+
+/// replaces the root of EVDS in the url with the url root set via [`set_mock_url_root`], or with the one in
+/// [`MOCK_URL_ROOT_VARIABLE`] when it is not set.
+pub(crate) fn redirect_to_mock(url: &str) -> Cow<'_, str> {
+
+    let mock_url_root = get_mock_url_root().clone().or_else(|| env::var(MOCK_URL_ROOT_VARIABLE).ok());
+
+    match (mock_url_root.as_deref(), url.strip_prefix(EVDS_URL_ROOT)) {
+        (Some(mock_url_root), Some(query)) => Cow::Owned(format!("{}{}", mock_url_root, query)),
+        _ => Cow::Borrowed(url),
+    }
+}
+
+/// gives access to the mock url root. A poisoned lock is recovered since the root cannot be left half updated.
+fn get_mock_url_root() -> MutexGuard<'static, Option<String>> {
+    match MOCK_URL_ROOT.lock() {
+        Ok(mock_url_root) => mock_url_root,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn generate_date_preference(start_date: &str, end_date: &str) -> Result<DatePreference, String> {
+    DateRange::from(start_date, end_date).map(DatePreference::Multiple).map_err(to_message)
+}
+
+fn generate_evds(return_format: ReturnFormat) -> Result<Evds, String> {
+
+    let api_key = ApiKey::from_unverified(BENCH_API_KEY.to_string()).map_err(to_message)?;
+
+    Ok(Evds::from(api_key, return_format))
+}
+
+/// gives the message of the error, since the errors of the crate are not visible to the benchmarks.
+fn to_message(return_error: ReturnError) -> String {
+    return_error.to_string()
+}
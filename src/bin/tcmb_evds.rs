@@ -0,0 +1,394 @@
+//! is the `tcmb-evds` command line tool built on the FFI of the crate for quick checks and shell pipelines.
+//!
+//! The tool is built with the `cli` feature, e.g. `cargo build --release --features cli`, and makes its requests via
+//! the same FFI functions as C programs. Therefore, it also serves as an integration test harness of the library.
+
+use std::env;
+use std::ffi::CString;
+use std::process::ExitCode;
+use std::os::raw::c_uint;
+use std::slice;
+
+use tcmb_evds_c::evds_c::advanced_entities::{TcmbEvdsAggregationType, TcmbEvdsDataFrequency, TcmbEvdsFormula};
+use tcmb_evds_c::evds_c::common_entities::{TcmbEvdsInput, TcmbEvdsResult, TcmbEvdsReturnFormat};
+use tcmb_evds_c::evds_c::error_handling::ReturnErrorC;
+use tcmb_evds_c::evds_c::request_entities::*;
+use tcmb_evds_c::{tcmb_evds_c_execute, tcmb_evds_c_export_data_group, tcmb_evds_c_get_categories};
+use tcmb_evds_c::{tcmb_evds_c_get_series_list, tcmb_evds_c_list_series, tcmb_evds_c_result_free};
+
+
+/// is the environment variable read when the api key is not given via `--key`.
+const API_KEY_VARIABLE: &str = "TCMB_EVDS_API_KEY";
+
+const USAGE: &str = "\
+Usage: tcmb-evds <command> [arguments] [options]
+
+Commands:
+    get <series>               gets the data of a data series, e.g. TP.DK.USD.S
+    group <data group>         gets the data of a data group, e.g. bie_yssk
+    categories                 gets the categories list
+    search <text>              lists the categories, or the series of --group, containing the text
+    codes <data group> [prefix]
+                               lists the series codes of a data group starting with the prefix, one per line
+    export <data group>        exports each series of a data group into --dir as CSV files
+
+Options:
+    --date <date>              a date in any format accepted by the library, e.g. 13-12-2011,13-12-2012
+    --key <api key>            the api key, or else the TCMB_EVDS_API_KEY environment variable
+    --format <format>          Csv, Json, Xml or Auto (default: the session format)
+    --ascii                    converts the response into ASCII characters
+    --aggregation <type>       Average, Minimum, Maximum, Beginning, End or Cumulative
+    --formula <formula>        Level, PercentageChange, Difference, YearToYearPercentChange, ...
+    --frequency <frequency>    Daily, Business, WeeklyFriday, TwiceMonthly, Monthly, Quarterly, SemiAnnual or Annual
+    --group <data group>       the data group whose series are searched
+    --dir <directory>          the directory of the exported files
+";
+
+const RETURN_FORMATS: [(&str, TcmbEvdsReturnFormat); 4] = [
+    ("Csv", TcmbEvdsReturnFormat::Csv),
+    ("Json", TcmbEvdsReturnFormat::Json),
+    ("Xml", TcmbEvdsReturnFormat::Xml),
+    ("Auto", TcmbEvdsReturnFormat::Auto),
+];
+
+const AGGREGATION_TYPES: [(&str, TcmbEvdsAggregationType); 6] = [
+    ("Average", TcmbEvdsAggregationType::Average),
+    ("Minimum", TcmbEvdsAggregationType::Minimum),
+    ("Maximum", TcmbEvdsAggregationType::Maximum),
+    ("Beginning", TcmbEvdsAggregationType::Beginning),
+    ("End", TcmbEvdsAggregationType::End),
+    ("Cumulative", TcmbEvdsAggregationType::Cumulative),
+];
+
+const FORMULAS: [(&str, TcmbEvdsFormula); 9] = [
+    ("Level", TcmbEvdsFormula::Level),
+    ("PercentageChange", TcmbEvdsFormula::PercentageChange),
+    ("Difference", TcmbEvdsFormula::Difference),
+    ("YearToYearPercentChange", TcmbEvdsFormula::YearToYearPercentChange),
+    ("YearToYearDifferences", TcmbEvdsFormula::YearToYearDifferences),
+    ("PercentageChangeByEndOfPreviousYear", TcmbEvdsFormula::PercentageChangeByEndOfPreviousYear),
+    ("DifferenceByEndOfPreviousYear", TcmbEvdsFormula::DifferenceByEndOfPreviousYear),
+    ("MovingAverage", TcmbEvdsFormula::MovingAverage),
+    ("MovingSum", TcmbEvdsFormula::MovingSum),
+];
+
+const DATA_FREQUENCIES: [(&str, TcmbEvdsDataFrequency); 8] = [
+    ("Daily", TcmbEvdsDataFrequency::Daily),
+    ("Business", TcmbEvdsDataFrequency::Business),
+    ("WeeklyFriday", TcmbEvdsDataFrequency::WeeklyFriday),
+    ("TwiceMonthly", TcmbEvdsDataFrequency::TwiceMonthly),
+    ("Monthly", TcmbEvdsDataFrequency::Monthly),
+    ("Quarterly", TcmbEvdsDataFrequency::Quarterly),
+    ("SemiAnnual", TcmbEvdsDataFrequency::SemiAnnual),
+    ("Annual", TcmbEvdsDataFrequency::Annual),
+];
+
+
+/// is the command given to the tool with its argument.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Get(String),
+    Group(String),
+    Categories,
+    Search(String),
+    Codes(String, String),
+    Export(String),
+}
+
+/// contains the options given to the tool. The options that are not given keep the defaults of the library.
+#[derive(Debug, PartialEq)]
+struct Options {
+    date: String,
+    api_key: Option<String>,
+    return_format: TcmbEvdsReturnFormat,
+    ascii_mode: bool,
+    aggregation_type: Option<TcmbEvdsAggregationType>,
+    formula: Option<TcmbEvdsFormula>,
+    data_frequency: Option<TcmbEvdsDataFrequency>,
+    group: Option<String>,
+    directory: Option<String>,
+}
+
+/// is a failure of the tool with its exit code: 1 for the errors of the library and 2 for the usage errors.
+struct Failure {
+    exit_code: u8,
+    message: String,
+}
+
+impl Failure {
+    fn usage(message: String) -> Failure {
+        Failure { exit_code: 2, message: format!("{}\n\n{}", message, USAGE) }
+    }
+}
+
+/// owns the C strings of the inputs for as long as the FFI functions use them.
+struct Inputs(Vec<CString>);
+
+impl Inputs {
+    fn add(&mut self, text: &str) -> Result<TcmbEvdsInput, Failure> {
+
+        let text = CString::new(text)
+            .map_err(|_| Failure::usage("The arguments must not contain NUL characters.".to_string()))?;
+        let input = TcmbEvdsInput { input_ptr: text.as_ptr(), string_capacity: text.as_bytes().len() as _ };
+
+        self.0.push(text);
+
+        Ok(input)
+    }
+}
+
+
+fn main() -> ExitCode {
+
+    let arguments: Vec<String> = env::args().skip(1).collect();
+
+    let outcome = parse_arguments(&arguments).and_then(|(command, options)| run(command, options));
+
+    match outcome {
+        Ok(output) => {
+            println!("{}", output);
+
+            ExitCode::SUCCESS
+        },
+        Err(failure) => {
+            eprintln!("{}", failure.message);
+
+            ExitCode::from(failure.exit_code)
+        },
+    }
+}
+
+/// parses the command and the options of the tool.
+fn parse_arguments(arguments: &[String]) -> Result<(Command, Options), Failure> {
+
+    let mut options = Options {
+        date: String::new(),
+        api_key: None,
+        return_format: TcmbEvdsReturnFormat::SessionFormat,
+        ascii_mode: false,
+        aggregation_type: None,
+        formula: None,
+        data_frequency: None,
+        group: None,
+        directory: None,
+    };
+
+    let mut positionals = Vec::new();
+    let mut arguments = arguments.iter();
+
+    while let Some(argument) = arguments.next() {
+        if argument == "--ascii" {
+            options.ascii_mode = true;
+
+            continue;
+        }
+
+        if !argument.starts_with("--") {
+            positionals.push(argument.as_str());
+
+            continue;
+        }
+
+        let value = match arguments.next() {
+            Some(value) => value.as_str(),
+            None => return Err(Failure::usage(format!("The option {} requires a value.", argument))),
+        };
+
+        match argument.as_str() {
+            "--date" => options.date = value.to_string(),
+            "--key" => options.api_key = Some(value.to_string()),
+            "--format" => options.return_format = find_option(&RETURN_FORMATS, argument, value)?,
+            "--aggregation" => options.aggregation_type = Some(find_option(&AGGREGATION_TYPES, argument, value)?),
+            "--formula" => options.formula = Some(find_option(&FORMULAS, argument, value)?),
+            "--frequency" => options.data_frequency = Some(find_option(&DATA_FREQUENCIES, argument, value)?),
+            "--group" => options.group = Some(value.to_string()),
+            "--dir" => options.directory = Some(value.to_string()),
+            _ => return Err(Failure::usage(format!("Unknown option {}.", argument))),
+        }
+    }
+
+    let command = match positionals.as_slice() {
+        ["get", series] => Command::Get(series.to_string()),
+        ["group", data_group] => Command::Group(data_group.to_string()),
+        ["categories"] => Command::Categories,
+        ["search", text] => Command::Search(text.to_string()),
+        ["codes", data_group] => Command::Codes(data_group.to_string(), String::new()),
+        ["codes", data_group, prefix] => Command::Codes(data_group.to_string(), prefix.to_string()),
+        ["export", data_group] => Command::Export(data_group.to_string()),
+        [] => return Err(Failure::usage("A command is required.".to_string())),
+        _ => return Err(Failure::usage(format!("Invalid command: {}.", positionals.join(" ")))),
+    };
+
+    Ok((command, options))
+}
+
+/// finds the enum option with the given name ignoring the case.
+fn find_option<T: Copy>(options: &[(&str, T)], argument: &str, value: &str) -> Result<T, Failure> {
+    options
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(value))
+        .map(|(_, option)| *option)
+        .ok_or_else(|| Failure::usage(format!("Invalid value of {}: {}.", argument, value)))
+}
+
+/// runs the command via the FFI functions and gives the text of the result.
+fn run(command: Command, options: Options) -> Result<String, Failure> {
+
+    let api_key = match options.api_key.clone().or_else(|| env::var(API_KEY_VARIABLE).ok()) {
+        Some(api_key) => api_key,
+        None => return Err(Failure::usage(format!("An api key is required via --key or {}.", API_KEY_VARIABLE))),
+    };
+
+    let mut inputs = Inputs(Vec::new());
+    let api_key = inputs.add(&api_key)?;
+
+    match command {
+        Command::Get(series) => execute(&options, inputs.add(&series)?, api_key, true),
+        Command::Group(data_group) => execute(&options, inputs.add(&data_group)?, api_key, false),
+        Command::Categories => {
+            read_result(tcmb_evds_c_get_categories(api_key, options.return_format as c_uint, options.ascii_mode))
+        },
+        Command::Search(text) => {
+            let list = match &options.group {
+                Some(group) => {
+                    let group = inputs.add(group)?;
+
+                    tcmb_evds_c_get_series_list(group, api_key, TcmbEvdsReturnFormat::Csv as c_uint, options.ascii_mode)
+                },
+                None => tcmb_evds_c_get_categories(api_key, TcmbEvdsReturnFormat::Csv as c_uint, options.ascii_mode),
+            };
+
+            read_result(list).map(|list| search_lines(&list, &text))
+        },
+        Command::Codes(data_group, prefix) => {
+            let data_group = inputs.add(&data_group)?;
+
+            read_result(tcmb_evds_c_get_series_list(data_group, api_key, TcmbEvdsReturnFormat::Json as c_uint, false))?;
+
+            read_result(tcmb_evds_c_list_series(inputs.add(&prefix)?, TcmbEvdsReturnFormat::Csv as c_uint))
+        },
+        Command::Export(data_group) => {
+            let directory = match &options.directory {
+                Some(directory) => inputs.add(directory)?,
+                None => return Err(Failure::usage("The export command requires --dir.".to_string())),
+            };
+
+            let data_group = inputs.add(&data_group)?;
+            let date = inputs.add(&options.date)?;
+
+            read_result(tcmb_evds_c_export_data_group(data_group, date, directory, api_key))
+        },
+    }
+}
+
+/// makes the request of a data series or a data group via the request builder of the library.
+fn execute(options: &Options, code: TcmbEvdsInput, api_key: TcmbEvdsInput, is_series: bool) -> Result<String, Failure> {
+
+    let mut inputs = Inputs(Vec::new());
+    let date = inputs.add(&options.date)?;
+
+    let request = tcmb_evds_c_request_new();
+
+    let result = unsafe {
+        match is_series {
+            true => tcmb_evds_c_request_set_series(request, code),
+            false => tcmb_evds_c_request_set_data_group(request, code),
+        };
+
+        tcmb_evds_c_request_set_dates(request, date);
+        tcmb_evds_c_request_set_api_key(request, api_key);
+        tcmb_evds_c_request_set_return_format(request, options.return_format as c_uint);
+        tcmb_evds_c_request_set_ascii_mode(request, options.ascii_mode);
+
+        if let Some(aggregation_type) = options.aggregation_type {
+            tcmb_evds_c_request_set_aggregation_type(request, aggregation_type as c_uint);
+        }
+        if let Some(formula) = options.formula {
+            tcmb_evds_c_request_set_formula(request, formula as c_uint);
+        }
+        if let Some(data_frequency) = options.data_frequency {
+            tcmb_evds_c_request_set_frequency(request, data_frequency as c_uint);
+        }
+
+        let result = tcmb_evds_c_execute(request);
+
+        tcmb_evds_c_request_free(request);
+
+        result
+    };
+
+    read_result(result)
+}
+
+/// reads the text of the result and releases the result. The detail of an error is appended to its message.
+fn read_result(result: TcmbEvdsResult) -> Result<String, Failure> {
+
+    let read_text = |text_ptr: *mut u8, capacity| match text_ptr.is_null() {
+        true => String::new(),
+        false => String::from_utf8_lossy(unsafe { slice::from_raw_parts(text_ptr, capacity as usize) }).into_owned(),
+    };
+
+    let text = read_text(result.output_ptr, result.string_capacity);
+    let detail = read_text(result.detail_ptr, result.detail_capacity);
+    let is_error = !matches!(result.error_type, ReturnErrorC::NoError);
+
+    unsafe { tcmb_evds_c_result_free(result) };
+
+    match (is_error, detail.is_empty()) {
+        (false, _) => Ok(text),
+        (true, true) => Err(Failure { exit_code: 1, message: text }),
+        (true, false) => Err(Failure { exit_code: 1, message: format!("{}\nDetail: {}", text, detail) }),
+    }
+}
+
+/// keeps the header and the lines of a CSV list containing the text ignoring the case.
+fn search_lines(list: &str, text: &str) -> String {
+
+    let text = text.to_lowercase();
+    let mut lines = list.lines();
+
+    lines
+        .next()
+        .into_iter()
+        .chain(lines.filter(|line| line.to_lowercase().contains(&text)))
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_arguments(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn should_parse_arguments() {
+        let (command, options) =
+            parse_arguments(&to_arguments("get TP.DK.USD.S --date 13-12-2011 --format csv --formula percentagechange"))
+                .ok()
+                .unwrap();
+
+        assert_eq!(Command::Get("TP.DK.USD.S".to_string()), command);
+        assert_eq!("13-12-2011", options.date);
+        assert_eq!(TcmbEvdsReturnFormat::Csv, options.return_format);
+        assert_eq!(Some(TcmbEvdsFormula::PercentageChange), options.formula);
+
+        assert_eq!(
+            Command::Codes("bie_dkdovytl".to_string(), "TP.DK.".to_string()),
+            parse_arguments(&to_arguments("codes bie_dkdovytl TP.DK.")).ok().unwrap().0
+        );
+
+        assert_eq!(2, parse_arguments(&to_arguments("get")).err().unwrap().exit_code);
+        assert_eq!(2, parse_arguments(&to_arguments("categories --format yaml")).err().unwrap().exit_code);
+    }
+
+    #[test]
+    fn should_search_lines() {
+        let list = "CATEGORY_ID,TOPIC_TITLE_TR\n1,PIYASA VERILERI\n2,KURLAR\n3,Faiz Istatistikleri";
+
+        assert_eq!("CATEGORY_ID,TOPIC_TITLE_TR\n2,KURLAR", search_lines(list, "kur"));
+    }
+}
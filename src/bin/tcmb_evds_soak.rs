@@ -0,0 +1,365 @@
+//! is the `tcmb-evds-soak` test binary requesting a local mock of EVDS via the FFI functions in a loop for hours to
+//! catch the leaks in the lifecycle of the buffers handed to C before daemons adopt the library.
+//!
+//! Each iteration goes through the results, handles and datasets of the library and releases them as C programs do.
+//! The memory statistics of the library must show no live object after each iteration, and the resident memory of
+//! the process must not grow more than the limit after the warm-up. The binary needs the mock of the `bench` feature,
+//! e.g. `cargo run --release --features bench --bin tcmb-evds-soak -- --duration 7200`.
+
+use std::env;
+use std::ffi::CString;
+use std::os::raw::c_uint;
+use std::process::ExitCode;
+use std::ptr;
+use std::slice;
+use std::time::{Duration, Instant};
+
+use tcmb_evds_c::bench;
+use tcmb_evds_c::bench::mock_server::start_mock_server;
+use tcmb_evds_c::evds_c::common_entities::{TcmbEvdsInput, TcmbEvdsResult, TcmbEvdsReturnFormat};
+use tcmb_evds_c::evds_c::dataset_entities::{TcmbEvdsDataset, tcmb_evds_c_dataset_free};
+use tcmb_evds_c::evds_c::dataset_entities::tcmb_evds_c_dataset_observation_count;
+use tcmb_evds_c::evds_c::error_handling::ReturnErrorC;
+use tcmb_evds_c::evds_c::flat_entities::{tcmb_evds_c_flat_get_data, tcmb_evds_c_flat_handle_free};
+use tcmb_evds_c::evds_c::flat_entities::tcmb_evds_c_flat_handle_error;
+use tcmb_evds_c::evds_c::handle_entities::{tcmb_evds_c_handle_error, tcmb_evds_c_handle_free};
+use tcmb_evds_c::evds_c::lazy_entities::{tcmb_evds_c_lazy_dataset_free, tcmb_evds_c_lazy_dataset_from_result};
+use tcmb_evds_c::evds_c::lazy_entities::tcmb_evds_c_lazy_dataset_latest_value;
+use tcmb_evds_c::{tcmb_evds_c_get_data, tcmb_evds_c_get_data_handle, tcmb_evds_c_get_last_n};
+use tcmb_evds_c::{tcmb_evds_c_memory_stats, tcmb_evds_c_result_free};
+
+
+const USAGE: &str = "\
+Usage: tcmb-evds-soak [options]
+
+Options:
+    --duration <seconds>       how long the requests are repeated (default: 3600)
+    --report <seconds>         the interval of the progress reports (default: 60)
+    --observations <count>     the number of the observations of the mock responses (default: 1000)
+    --max-growth <megabytes>   the allowed growth of the resident memory after the first report (default: 64)
+";
+
+/// is the api key sent to the mock, which accepts any key.
+const SOAK_API_KEY: &str = "SOAKTESTKEY";
+
+const SOAK_SERIES: &str = "TP.DK.USD.S";
+
+const SOAK_DATES: &str = "01-01-2000,31-12-2011";
+
+/// is a date failing the validation, so that the results of the errors are released as well.
+const INVALID_DATE: &str = "32-13-2011";
+
+/// is the return format of the calls as the integer they receive.
+const CSV: c_uint = TcmbEvdsReturnFormat::Csv as c_uint;
+
+const JSON: c_uint = TcmbEvdsReturnFormat::Json as c_uint;
+
+
+/// contains the options given to the binary.
+#[derive(Debug, PartialEq)]
+struct Options {
+    duration: Duration,
+    report_interval: Duration,
+    observation_count: usize,
+    max_growth_bytes: u64,
+}
+
+/// owns the C strings of the inputs for as long as the FFI functions use them.
+struct Inputs {
+    series: CString,
+    dates: CString,
+    invalid_date: CString,
+    api_key: CString,
+}
+
+impl Inputs {
+    fn new() -> Inputs {
+        Inputs {
+            series: CString::new(SOAK_SERIES).unwrap(),
+            dates: CString::new(SOAK_DATES).unwrap(),
+            invalid_date: CString::new(INVALID_DATE).unwrap(),
+            api_key: CString::new(SOAK_API_KEY).unwrap(),
+        }
+    }
+}
+
+/// gives the input of the C string.
+fn to_input(text: &CString) -> TcmbEvdsInput {
+    TcmbEvdsInput { input_ptr: text.as_ptr(), string_capacity: text.as_bytes().len() as _ }
+}
+
+
+fn main() -> ExitCode {
+
+    let arguments: Vec<String> = env::args().skip(1).collect();
+
+    let options = match parse_arguments(&arguments) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+
+            return ExitCode::from(2);
+        },
+    };
+
+    match run(&options) {
+        Ok(iteration_count) => {
+            println!("Passed after {} iterations.", iteration_count);
+
+            ExitCode::SUCCESS
+        },
+        Err(message) => {
+            eprintln!("Failed: {}", message);
+
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// parses the options of the binary.
+fn parse_arguments(arguments: &[String]) -> Result<Options, String> {
+
+    let mut options = Options {
+        duration: Duration::from_secs(3600),
+        report_interval: Duration::from_secs(60),
+        observation_count: 1000,
+        max_growth_bytes: 64 << 20,
+    };
+
+    let mut arguments = arguments.iter();
+
+    while let Some(argument) = arguments.next() {
+        let value = match arguments.next().map(|value| value.parse::<u64>()) {
+            Some(Ok(value)) => value,
+            Some(Err(_)) => return Err(format!("The option {} requires a number.", argument)),
+            None => return Err(format!("The option {} requires a value.", argument)),
+        };
+
+        match argument.as_str() {
+            "--duration" => options.duration = Duration::from_secs(value),
+            "--report" => options.report_interval = Duration::from_secs(value.max(1)),
+            "--observations" => options.observation_count = value as usize,
+            "--max-growth" => options.max_growth_bytes = value << 20,
+            _ => return Err(format!("Unknown option {}.", argument)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// repeats the iterations against the mock until the duration passes and gives the number of the iterations.
+fn run(options: &Options) -> Result<u64, String> {
+
+    let address = start_mock_server(options.observation_count);
+
+    bench::set_mock_url_root(Some(&format!("http://{}/", address)));
+
+    let inputs = Inputs::new();
+
+    let start = Instant::now();
+    let mut next_report = start + options.report_interval;
+    let mut baseline_bytes = None;
+    let mut iteration_count = 0;
+
+    while start.elapsed() < options.duration {
+        run_iteration(&inputs, options.observation_count)?;
+        check_live_objects()?;
+
+        iteration_count += 1;
+
+        if Instant::now() < next_report { continue; }
+
+        next_report += options.report_interval;
+
+        let resident_bytes = read_resident_bytes();
+
+        println!(
+            "elapsed: {}s, iterations: {}, resident bytes: {}",
+            start.elapsed().as_secs(),
+            iteration_count,
+            resident_bytes.map_or("unknown".to_string(), |resident_bytes| resident_bytes.to_string())
+        );
+
+        // the first report ends the warm-up, in which the allocator and the connections of curl settle.
+        let (baseline_bytes, resident_bytes) = match (*baseline_bytes.get_or_insert(resident_bytes), resident_bytes) {
+            (Some(baseline_bytes), Some(resident_bytes)) => (baseline_bytes, resident_bytes),
+            _ => continue,
+        };
+
+        if resident_bytes > baseline_bytes + options.max_growth_bytes {
+            return Err(format!(
+                "the resident memory grew from {} to {} bytes after {} iterations",
+                baseline_bytes,
+                resident_bytes,
+                iteration_count
+            ));
+        }
+    }
+
+    Ok(iteration_count)
+}
+
+/// requests the mock via the results, handles and datasets of the library and releases each of them.
+fn run_iteration(inputs: &Inputs, observation_count: usize) -> Result<(), String> {
+
+    // the inputs are passed by value as C does, so each call is given its own copies.
+    let series = || to_input(&inputs.series);
+    let dates = || to_input(&inputs.dates);
+    let api_key = || to_input(&inputs.api_key);
+
+    unsafe {
+        let csv_result = tcmb_evds_c_get_data(series(), dates(), api_key(), CSV, false);
+
+        let csv_text = read_text(&csv_result);
+
+        tcmb_evds_c_result_free(csv_result);
+
+        csv_text?;
+
+        // the result is copied as C does since the lazy dataset does not release it.
+        let json_result = tcmb_evds_c_get_data(series(), dates(), api_key(), JSON, true);
+        let lazy_dataset = tcmb_evds_c_lazy_dataset_from_result(ptr::read(&json_result));
+        let mut latest_value = 0.0;
+
+        let has_latest_value = tcmb_evds_c_lazy_dataset_latest_value(lazy_dataset, series(), &mut latest_value);
+
+        tcmb_evds_c_lazy_dataset_free(lazy_dataset);
+        tcmb_evds_c_result_free(json_result);
+
+        if observation_count > 0 && !has_latest_value { return Err("the latest value is not read".to_string()); }
+
+        let mut dataset: *mut TcmbEvdsDataset = ptr::null_mut();
+        let last_result = tcmb_evds_c_get_last_n(series(), 5, api_key(), false, &mut dataset);
+
+        let last_text = read_text(&last_result);
+        let last_count = tcmb_evds_c_dataset_observation_count(dataset);
+
+        tcmb_evds_c_dataset_free(dataset);
+        tcmb_evds_c_result_free(last_result);
+
+        last_text?;
+
+        if last_count as usize != observation_count.min(5) {
+            return Err(format!("{} observations are read instead of the last 5", last_count));
+        }
+
+        let handle = tcmb_evds_c_get_data_handle(series(), dates(), api_key(), JSON, false);
+        let handle_error = tcmb_evds_c_handle_error(handle);
+
+        tcmb_evds_c_handle_free(handle);
+
+        check_error(handle_error)?;
+
+        let flat_handle = tcmb_evds_c_flat_get_data(
+            inputs.series.as_ptr(),
+            inputs.series.as_bytes().len(),
+            inputs.dates.as_ptr(),
+            inputs.dates.as_bytes().len(),
+            inputs.api_key.as_ptr(),
+            inputs.api_key.as_bytes().len(),
+            CSV,
+            0,
+        );
+        let flat_handle_error = tcmb_evds_c_flat_handle_error(flat_handle);
+
+        tcmb_evds_c_flat_handle_free(flat_handle);
+
+        check_error(flat_handle_error)?;
+
+        let invalid_date = to_input(&inputs.invalid_date);
+        let error_result = tcmb_evds_c_get_data(series(), invalid_date, api_key(), CSV, false);
+        let is_error = !matches!(error_result.error_type, ReturnErrorC::NoError);
+
+        tcmb_evds_c_result_free(error_result);
+
+        if !is_error { return Err("the invalid date is not rejected".to_string()); }
+    }
+
+    Ok(())
+}
+
+/// checks every result, handle and dataset handed to C is released.
+fn check_live_objects() -> Result<(), String> {
+
+    let memory_stats = tcmb_evds_c_memory_stats();
+
+    match memory_stats.live_results + memory_stats.live_handles + memory_stats.live_datasets {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{} results, {} handles and {} datasets of {} bytes are left alive",
+            memory_stats.live_results,
+            memory_stats.live_handles,
+            memory_stats.live_datasets,
+            memory_stats.live_bytes
+        )),
+    }
+}
+
+/// reads the text of the result, or its message when the result is an error.
+fn read_text(result: &TcmbEvdsResult) -> Result<String, String> {
+
+    let text = match result.output_ptr.is_null() {
+        true => String::new(),
+        false => {
+            let bytes = unsafe { slice::from_raw_parts(result.output_ptr, result.string_capacity as usize) };
+
+            String::from_utf8_lossy(bytes).into_owned()
+        },
+    };
+
+    match result.error_type {
+        ReturnErrorC::NoError => Ok(text),
+        _ => Err(text),
+    }
+}
+
+fn check_error(error: ReturnErrorC) -> Result<(), String> {
+    match error {
+        ReturnErrorC::NoError => Ok(()),
+        error => Err(format!("the request failed with {:?}", error)),
+    }
+}
+
+/// reads the resident memory of the process.
+#[cfg(target_os = "linux")]
+fn read_resident_bytes() -> Option<u64> {
+
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let resident_pages = statm.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+
+    if page_size <= 0 { return None; }
+
+    Some(resident_pages * page_size as u64)
+}
+
+/// gives None since the resident memory is read only on Linux, so only the memory statistics are checked.
+#[cfg(not(target_os = "linux"))]
+fn read_resident_bytes() -> Option<u64> {
+    None
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_arguments(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn should_parse_arguments() {
+        let options = parse_arguments(&to_arguments("--duration 60 --report 0 --max-growth 8")).unwrap();
+
+        assert_eq!(Duration::from_secs(60), options.duration);
+        assert_eq!(Duration::from_secs(1), options.report_interval);
+        assert_eq!(1000, options.observation_count);
+        assert_eq!(8 << 20, options.max_growth_bytes);
+
+        assert!(parse_arguments(&to_arguments("--duration")).is_err());
+        assert!(parse_arguments(&to_arguments("--duration hour")).is_err());
+        assert!(parse_arguments(&to_arguments("--threads 4")).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use serde_json::{Value, json};
+
+use crate::transport;
+
+
+/// is the commit the library is built from, followed by "-dirty" for uncommitted changes. It is empty when the
+/// sources are not in a git repository.
+const GIT_HASH: &str = env!("TCMB_EVDS_C_GIT_HASH");
+
+/// are the cargo features the library is built with, separated by commas in alphabetical order.
+const FEATURES: &str = env!("TCMB_EVDS_C_FEATURES");
+
+/// is the target triple the library is built for, e.g. "x86_64-unknown-linux-gnu".
+const TARGET: &str = env!("TCMB_EVDS_C_TARGET");
+
+/// is the cargo profile the library is built with, namely "release" or "debug".
+const PROFILE: &str = env!("TCMB_EVDS_C_PROFILE");
+
+
+/// gives the metadata of the build as a JSON object, e.g.
+/// `{"curl":{"sslVersion":"OpenSSL/3.0.2","static":false,"version":"7.81.0"},"features":["cache","sync_mode"],
+/// "gitHash":"d87578a..","httpBackend":"curl","profile":"release","target":"x86_64-unknown-linux-gnu",
+/// "version":"0.1.0"}`.
+pub(crate) fn build_info_json() -> String {
+
+    let features: Vec<&str> = FEATURES.split(',').filter(|feature| !feature.is_empty()).collect();
+
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "gitHash": GIT_HASH,
+        "features": features,
+        "target": TARGET,
+        "profile": PROFILE,
+        "httpBackend": transport::BACKEND_NAME,
+        "curl": curl_json(),
+    }).to_string()
+}
+
+/// gives the versions of the linked libcurl and its TLS library. They are read from the linked libcurl, so they 
+/// reflect the library loaded at run time rather than the one found while building.
+#[cfg(not(feature = "pure_rust_http"))]
+fn curl_json() -> Value {
+
+    let curl_version = curl::Version::get();
+
+    json!({
+        "version": curl_version.version(),
+        "sslVersion": curl_version.ssl_version(),
+        "static": cfg!(feature = "static_curl"),
+    })
+}
+
+/// gives null since libcurl is not linked when the library is built with the `pure_rust_http` feature.
+#[cfg(feature = "pure_rust_http")]
+fn curl_json() -> Value {
+    Value::Null
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_give_build_info() {
+        let build_info: Value = serde_json::from_str(&build_info_json()).unwrap();
+
+        assert_eq!(env!("CARGO_PKG_VERSION"), build_info["version"]);
+
+        let features = build_info["features"].as_array().unwrap();
+
+        assert_eq!(cfg!(feature = "sync_mode"), features.contains(&json!("sync_mode")));
+        assert!(!build_info["target"].as_str().unwrap().is_empty());
+        assert_eq!(transport::BACKEND_NAME, build_info["httpBackend"]);
+
+        #[cfg(not(feature = "pure_rust_http"))]
+        assert!(!build_info["curl"]["version"].as_str().unwrap().is_empty());
+    }
+}
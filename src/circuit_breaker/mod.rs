@@ -0,0 +1,180 @@
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::error::ReturnError;
+
+
+/// is the number of the consecutive transport failures opening the circuit.
+const FAILURE_THRESHOLD: u32 = 5;
+
+/// is the period the requests fail fast after the circuit opens.
+const COOL_DOWN: Duration = Duration::from_secs(30);
+
+static CIRCUIT_BREAKER: Mutex<CircuitBreaker> = Mutex::new(CircuitBreaker::new());
+
+
+/// is the state of the circuit breaker guarding the requests to EVDS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum CircuitState {
+    /// lets the requests through as usual.
+    Closed,
+    /// fails the requests fast with `ServiceUnavailable` error until the cool-down period is over.
+    Open,
+    /// lets the requests through after the cool-down period to probe EVDS. A failure opens the circuit again and a
+    /// success closes it.
+    HalfOpen,
+}
+
+/// is a snapshot of the circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Health {
+    pub(crate) state: CircuitState,
+    pub(crate) consecutive_failures: u32,
+    /// is zero unless the circuit is open.
+    pub(crate) remaining_cool_down: Duration,
+}
+
+/// counts the consecutive transport failures, namely the requests that could not reach EVDS, and opens the circuit
+/// after [`FAILURE_THRESHOLD`] failures so that an outage is not hammered with requests.
+pub(crate) struct CircuitBreaker {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub(crate) const fn new() -> CircuitBreaker {
+        CircuitBreaker { consecutive_failures: 0, opened_at: None }
+    }
+
+    fn get_state(&self, now: Instant) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if now < opened_at + COOL_DOWN => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// checks whether a request can be made or not.
+    ///
+    /// # Error
+    ///
+    /// This function returns `ServiceUnavailable` error while the circuit is open.
+    fn check(&self, now: Instant) -> Result<(), ReturnError> {
+
+        if self.get_state(now) == CircuitState::Open { return Err(ReturnError::ServiceUnavailable); }
+
+        Ok(())
+    }
+
+    /// records the outcome of a request. Any response, even an error message, means EVDS is reachable and closes the
+    /// circuit.
+    fn record(&mut self, is_transport_failure: bool, now: Instant) {
+
+        if !is_transport_failure {
+            self.consecutive_failures = 0;
+            self.opened_at = None;
+
+            return;
+        }
+
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+
+        if self.consecutive_failures >= FAILURE_THRESHOLD || self.opened_at.is_some() { self.opened_at = Some(now); }
+    }
+
+    fn get_health(&self, now: Instant) -> Health {
+
+        let remaining_cool_down = match self.opened_at {
+            Some(opened_at) => (opened_at + COOL_DOWN).saturating_duration_since(now),
+            None => Duration::ZERO,
+        };
+
+        Health { state: self.get_state(now), consecutive_failures: self.consecutive_failures, remaining_cool_down }
+    }
+}
+
+
+/// makes the request through the process wide circuit breaker. `FailedToApplyRequest`, `DnsFailure`, `TlsError` and 
+/// `Timeout` errors, which mean the request could not be performed, are counted as transport failures.
+///
+/// # Error
+///
+/// This function returns the errors of the request, and `ServiceUnavailable` error without making the request while
+/// the circuit is open.
+pub(crate) fn guard<F>(request: F) -> Result<String, ReturnError>
+where
+    F: FnOnce() -> Result<String, ReturnError>
+{
+
+    get_circuit_breaker().check(Instant::now())?;
+
+    let response = request();
+
+    let is_transport_failure = matches!(
+        response,
+        Err(
+            ReturnError::FailedToApplyRequest(_)
+            | ReturnError::DnsFailure(_)
+            | ReturnError::TlsError(_)
+            | ReturnError::Timeout(_)
+        )
+    );
+
+    get_circuit_breaker().record(is_transport_failure, Instant::now());
+
+    response
+}
+
+/// gives the current state of the process wide circuit breaker.
+pub(crate) fn get_health() -> Health {
+    get_circuit_breaker().get_health(Instant::now())
+}
+
+/// gives access to the circuit breaker. A poisoned lock is recovered since the counters cannot be left half updated.
+fn get_circuit_breaker() -> MutexGuard<'static, CircuitBreaker> {
+    match CIRCUIT_BREAKER.lock() {
+        Ok(circuit_breaker) => circuit_breaker,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_open_and_close_circuit() {
+        let mut circuit_breaker = CircuitBreaker::new();
+        let start = Instant::now();
+
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            circuit_breaker.record(true, start);
+        }
+
+        assert!(circuit_breaker.check(start).is_ok());
+
+        circuit_breaker.record(true, start);
+
+        let health = circuit_breaker.get_health(start + Duration::from_secs(10));
+
+        assert_eq!(CircuitState::Open, health.state);
+        assert_eq!(FAILURE_THRESHOLD, health.consecutive_failures);
+        assert_eq!(Duration::from_secs(20), health.remaining_cool_down);
+        assert_eq!(Err(ReturnError::ServiceUnavailable), circuit_breaker.check(start));
+
+        let after_cool_down = start + COOL_DOWN;
+
+        assert_eq!(CircuitState::HalfOpen, circuit_breaker.get_health(after_cool_down).state);
+        assert!(circuit_breaker.check(after_cool_down).is_ok());
+
+        circuit_breaker.record(true, after_cool_down);
+
+        assert_eq!(CircuitState::Open, circuit_breaker.get_health(after_cool_down).state);
+
+        circuit_breaker.record(false, after_cool_down + COOL_DOWN);
+
+        assert_eq!(CircuitState::Closed, circuit_breaker.get_health(after_cool_down + COOL_DOWN).state);
+        assert_eq!(0, circuit_breaker.get_health(after_cool_down + COOL_DOWN).consecutive_failures);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::error::ReturnError;
+
+
+/// are the media types of the return formats, namely JSON, CSV and XML, which EVDS responds with.
+const FORMAT_MEDIA_TYPES: [(&str, &[&str]); 3] = [
+    ("json", &["application/json", "text/json"]),
+    ("csv", &["text/csv", "application/csv"]),
+    ("xml", &["application/xml", "text/xml"]),
+];
+
+
+/// finds the media type in a `Content-Type` header line in lowercase without its parameters, e.g. "text/html" for 
+/// "Content-Type: text/html; charset=utf-8\r\n".
+pub(crate) fn find_content_type(header_line: &[u8]) -> Option<String> {
+
+    let header_line = std::str::from_utf8(header_line).ok()?;
+    let (name, value) = header_line.split_once(':')?;
+
+    if !name.trim().eq_ignore_ascii_case("content-type") { return None; }
+
+    let media_type = value.split(';').next()?.trim().to_lowercase();
+
+    (!media_type.is_empty()).then_some(media_type)
+}
+
+/// checks the content type of the response against the return format requested in the `type` parameter of the url, 
+/// so that an HTML page of EVDS, e.g. a login or a ban page, is not handed over as data.
+///
+/// A response without a content type is checked by its beginning. The content types not belonging to a return format,
+/// e.g. "text/plain", are accepted since they tell nothing about the response.
+///
+/// # Error
+///
+/// This function returns `UnexpectedContentType` error when the response is an HTML page or the content type belongs 
+/// to another return format.
+pub(crate) fn check_content_type(
+    url_format: &str,
+    content_type: Option<&str>,
+    response: &[u8]
+) -> Result<(), ReturnError> {
+
+    let requested_format = find_requested_format(url_format);
+
+    let content_type = match content_type {
+        Some(content_type) => content_type,
+        None if is_html_page(response) => "text/html",
+        None => return Ok(()),
+    };
+
+    let received_format = FORMAT_MEDIA_TYPES
+        .iter()
+        .find(|(_, media_types)| media_types.contains(&content_type))
+        .map(|(format, _)| *format);
+
+    let is_unexpected = match (received_format, requested_format) {
+        (Some(received_format), Some(requested_format)) => received_format != requested_format,
+        (None, _) => content_type == "text/html" || content_type == "application/xhtml+xml",
+        (Some(_), None) => false,
+    };
+
+    if !is_unexpected { return Ok(()); }
+
+    Err(ReturnError::UnexpectedContentType(match requested_format {
+        Some(requested_format) => format!("\"{}\" is received while {} is requested", content_type, requested_format),
+        None => format!("\"{}\" is received while data is requested", content_type),
+    }))
+}
+
+/// finds the return format in the `type` parameter of the url in lowercase, e.g. "csv". The parameters of EVDS urls 
+/// follow the path without a question mark, e.g. ".../series=TP.DK.USD.S&type=csv".
+fn find_requested_format(url_format: &str) -> Option<&'static str> {
+
+    let requested_format = url_format
+        .split(['/', '?', '&'])
+        .find_map(|parameter| parameter.strip_prefix("type="))?
+        .to_lowercase();
+
+    FORMAT_MEDIA_TYPES.iter().map(|(format, _)| *format).find(|format| *format == requested_format)
+}
+
+/// tells whether the response begins like an HTML page.
+fn is_html_page(response: &[u8]) -> bool {
+
+    let beginning = String::from_utf8_lossy(&response[..response.len().min(64)]).trim_start().to_lowercase();
+
+    beginning.starts_with("<!doctype html") || beginning.starts_with("<html")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_reject_html_pages_and_other_formats() {
+        let url = "https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S&startDate=13-12-2011&type=csv";
+
+        assert_eq!(Some("text/html".to_string()), find_content_type(b"Content-Type: text/HTML; charset=utf-8\r\n"));
+        assert_eq!(None, find_content_type(b"Content-Length: 1024\r\n"));
+
+        assert!(check_content_type(url, Some("text/csv"), b"Tarih,TP_DK_USD_S").is_ok());
+        assert!(check_content_type(url, Some("text/plain"), b"Tarih,TP_DK_USD_S").is_ok());
+        assert!(check_content_type(url, None, b"Tarih,TP_DK_USD_S").is_ok());
+        assert!(check_content_type("https://evds2.tcmb.gov.tr/service/evds/", Some("application/json"), b"").is_ok());
+
+        assert_eq!(
+            "Error: Unexpected content type, \"text/html\" is received while csv is requested.",
+            check_content_type(url, None, b"\n<!DOCTYPE html><html>").unwrap_err().to_string()
+        );
+        assert!(check_content_type(url, Some("application/json"), b"{}").is_err());
+        assert!(check_content_type("https://evds2.tcmb.gov.tr/service/evds/", Some("text/html"), b"").is_err());
+    }
+}
@@ -1,4 +1,14 @@
+/// assembles EVDS urls with a canonical parameter order and escaping.
+pub(crate) mod url_builder;
+/// receives the responses into single buffers that are handed over to C without copying.
+pub(crate) mod response_buffer;
+/// checks the content types of the responses against the requested return formats.
+pub(crate) mod content_type;
+
 use std::cmp;
+use std::fmt;
+
+use self::url_builder::{UrlBuilder, check_query_value};
 
 use crate::error::ReturnError;
 use crate::traits::{self, MakingUrlFormat};
@@ -20,15 +30,35 @@ pub(crate) enum ReturnFormat {
     Json,
     /// Extensible Markup Language format.
     Xml,
+    /// requests Java Script Object Notation format and converts the response into it when EVDS ignores the requested
+    /// format and responds with another one.
+    Auto,
 } 
 
-impl ToString for ReturnFormat {
-    /// returns stringified version of return format option that is appropriate for url usage. 
-    fn to_string(&self) -> String {
+impl ReturnFormat {
+    /// returns stringified version of return format option that is appropriate for url usage without allocating.
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
-            &Self::Csv => String::from("csv"),
-            &Self::Json => String::from("json"),
-            &Self::Xml => String::from("xml"),
+            Self::Csv => "csv",
+            Self::Json | Self::Auto => "json",
+            Self::Xml => "xml",
+        }
+    }
+}
+
+impl fmt::Display for ReturnFormat {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl ReturnFormat {
+    /// gives the url representation of the return format, which is a static text formatted once for all requests.
+    pub(crate) fn as_url_fragment(&self) -> &'static str {
+        match self {
+            Self::Csv => "type=csv",
+            Self::Json | Self::Auto => "type=json",
+            Self::Xml => "type=xml",
         }
     }
 }
@@ -36,7 +66,7 @@ impl ToString for ReturnFormat {
 impl traits::MakingUrlFormat for ReturnFormat {
     /// generates required url representation of return format.
     fn generate_url_format(&self) -> String {
-        format!("type={}", self.to_string())
+        self.as_url_fragment().to_string()
     }
 }
 
@@ -76,13 +106,15 @@ impl<'a> ApiKey {
     }
 
     fn is_api_key_valid(&self) -> Result<(), ReturnError> {
-        // The string below is divided into two due to the convention of horizontal width which is 120 characters. 
-        let reference_url = 
-        format!(
-            "https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S.YTL{}&key={}", 
-            "&startDate=13-12-2011&endDate=13-12-2011&type=json",
-            self.0,
-        );
+        check_query_value(&self.0)?;
+
+        let reference_url = UrlBuilder::new("")
+            .add_parameter("series", "TP.DK.USD.S.YTL")
+            .add_parameter("startDate", "13-12-2011")
+            .add_parameter("endDate", "13-12-2011")
+            .add_parameter("type", "json")
+            .add_parameter("key", &self.0)
+            .build();
     
         #[cfg(feature = "async_mode")]
         return ApiKey::check_api_key_validity_async(reference_url);
@@ -132,6 +164,18 @@ impl<'a> ApiKey {
 
         Ok(api_key)
     }
+
+    /// creates an api key without checking its validity via EVDS, so the internet connection is not required. It is 
+    /// only used to show the urls that would be requested.
+    ///
+    /// # Error
+    ///
+    /// The function will return error if given api key contains one of the characters separating query parameters.
+    pub(crate) fn from_unverified(key: String) -> Result<ApiKey, ReturnError> {
+        check_query_value(&key)?;
+
+        Ok(ApiKey(key))
+    }
 }
 
 impl cmp::PartialEq for ApiKey {
@@ -149,10 +193,14 @@ impl traits::MakingUrlFormat for ApiKey {
 
 /// is composed of created [`ApiKey`](struct@ApiKey) and [`ReturnFormat`](crate::common::ReturnFormat) variables.
 ///
-/// This struct is common for each function that this crate provides.
+/// This struct is common for each function that this crate provides. The url representation of the api key is formatted
+/// once when the key is set rather than for every request made with it.
 pub(crate) struct Evds {
+    // the key is read only to copy the object in another return format, which is done by the `parse` feature.
+    #[cfg_attr(not(feature = "parse"), allow(dead_code))]
     api_key: ApiKey,
     return_format: ReturnFormat,
+    api_key_as_url: String,
 }
 
 impl<'a> Evds {
@@ -186,6 +234,7 @@ impl<'a> Evds {
     /// ```
     pub(crate) fn from(api_key: ApiKey, return_format: ReturnFormat) -> Evds {
         Evds {
+            api_key_as_url: api_key.generate_url_format(),
             api_key,
             return_format,
         }
@@ -220,6 +269,7 @@ impl<'a> Evds {
     pub(crate) fn change_api_key(&mut self, api_key: &str) -> Result<(), ReturnError> {
 
         self.api_key.change(api_key)?;
+        self.api_key_as_url = self.api_key.generate_url_format();
 
         Ok(())
     }
@@ -250,14 +300,26 @@ impl<'a> Evds {
         self.return_format = return_format;
     }
 
-    /// generates url format of api key.
-    pub(crate) fn get_api_key_as_url(&self) -> String {
-        self.api_key.generate_url_format()
+    /// gives url format of api key.
+    pub(crate) fn get_api_key_as_url(&self) -> &str {
+        &self.api_key_as_url
+    }
+
+    /// gives url format of return format.
+    pub(crate) fn get_return_format_as_url(&self) -> &'static str {
+        self.return_format.as_url_fragment()
+    }
+
+    /// gives the return format of the requests.
+    pub(crate) fn get_return_format(&self) -> &ReturnFormat {
+        &self.return_format
     }
 
-    /// generates url format of return format.
-    pub(crate) fn get_return_format_as_url(&self) -> String {
-        self.return_format.generate_url_format()
+    /// gives a copy of the Evds object requesting in the given return format with the same api key, e.g. to parse the
+    /// series lists in JSON while the data is requested in CSV.
+    #[cfg(feature = "parse")]
+    pub(crate) fn with_return_format(&self, return_format: ReturnFormat) -> Evds {
+        Evds::from(ApiKey(self.api_key.0.clone()), return_format)
     }
 }
 
@@ -266,6 +328,8 @@ impl<'a> Evds {
 mod tests {
     use super::*;
 
+    use std::ptr;
+
     #[test]
     fn api_functionality_should_work() {
         let mut api_key = match ApiKey::from("abc".to_string()) {
@@ -332,4 +396,13 @@ mod tests {
         evds.return_format.generate_url_format(),
         evds.api_key.generate_url_format());
     }
+    #[test]
+    fn should_format_url_fragments_once() {
+        let api_key = ApiKey::from_unverified("users_key".to_string()).unwrap();
+
+        let evds = Evds::from(api_key, ReturnFormat::Auto);
+
+        assert_eq!(("key=users_key", "type=json"), (evds.get_api_key_as_url(), evds.get_return_format_as_url()));
+        assert!(ptr::eq(evds.get_api_key_as_url(), evds.get_api_key_as_url()));
+    }
 }
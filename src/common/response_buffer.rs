@@ -0,0 +1,58 @@
+/// is the largest buffer reserved for a response from its `Content-Length` header, so that a wrong length does not
+/// allocate more than a large data group response would need. Longer responses still grow their buffers.
+const MAX_RESERVED_LENGTH: usize = 64 * 1024 * 1024;
+
+
+/// finds the length of the response in a `Content-Length` header line, e.g. "Content-Length: 1024\r\n", limited to
+/// [`MAX_RESERVED_LENGTH`].
+pub(crate) fn find_content_length(header_line: &[u8]) -> Option<usize> {
+
+    let header_line = std::str::from_utf8(header_line).ok()?;
+    let (name, value) = header_line.split_once(':')?;
+
+    if !name.trim().eq_ignore_ascii_case("content-length") { return None; }
+
+    value.trim().parse::<usize>().ok().map(|length| length.min(MAX_RESERVED_LENGTH))
+}
+
+/// reserves the buffer of a response before its first data is received, so the response is received without growing
+/// the buffer when its length is known.
+pub(crate) fn reserve_response(buffer: &mut Vec<u8>, content_length: Option<usize>) {
+
+    if let (true, Some(content_length)) = (buffer.is_empty(), content_length) { buffer.reserve(content_length); }
+}
+
+/// turns the received bytes into the response text in place. Only a response containing invalid UTF-8 characters is
+/// copied to replace them.
+pub(crate) fn into_response_text(buffer: Vec<u8>) -> String {
+    match String::from_utf8(buffer) {
+        Ok(response) => response,
+        Err(error) => String::from_utf8_lossy(error.as_bytes()).into_owned(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_receive_response_into_reserved_buffer() {
+        assert_eq!(Some(1024), find_content_length(b"content-length: 1024\r\n"));
+        assert_eq!(Some(MAX_RESERVED_LENGTH), find_content_length(b"Content-Length: 99999999999\r\n"));
+        assert_eq!(None, find_content_length(b"Retry-After: 120\r\n"));
+
+        let mut buffer = Vec::new();
+
+        reserve_response(&mut buffer, Some(1024));
+
+        let reserved_buffer = buffer.as_ptr();
+
+        buffer.extend_from_slice("{\"items\":[]}".as_bytes());
+
+        let response = into_response_text(buffer);
+
+        assert_eq!((reserved_buffer, 1024), (response.as_ptr(), response.capacity()));
+        assert_eq!("a\u{FFFD}", into_response_text(vec![b'a', 0xFF]));
+    }
+}
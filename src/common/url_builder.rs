@@ -0,0 +1,235 @@
+use std::borrow::Cow;
+
+use crate::error::ReturnError;
+use crate::evds_core::url::{build_url, intern_name, is_query_value_safe};
+#[cfg(feature = "cache")]
+use crate::evds_core::url::{KEY_PARAMETER, TYPE_PARAMETER};
+
+pub(crate) use crate::evds_core::url::mask_api_key;
+
+
+/// is the name of the series parameter, whose value lists the series separated by dashes.
+const SERIES_PARAMETER: &str = "series";
+
+/// is the longest url sent to EVDS. The urls of longer series lists are split via [`UrlBuilder::split_series`] since 
+/// the server rejects them.
+pub(crate) const MAX_URL_LENGTH: usize = 2000;
+
+
+/// assembles EVDS urls deterministically regardless of the order the parameters are added in.
+///
+/// Parameter values are percent encoded except the characters that EVDS uses in series codes, dates and keys, namely
+/// letters, digits, `.`, `-`, `_` and `~`. As a result, the same request always produces the same url.
+///
+/// # Example
+///
+/// ```
+///     let url = UrlBuilder::new("categories/")
+///         .add_parameter("key", "users_key")
+///         .add_parameter("type", "json")
+///         .build();
+///
+///     assert_eq!("https://evds2.tcmb.gov.tr/service/evds/categories/type=json&key=users_key", url);
+/// ```
+#[derive(Debug, Clone)]
+pub(crate) struct UrlBuilder {
+    resource: &'static str,
+    /// are the parameters whose names are interned from
+    /// [`PARAMETER_ORDER`](crate::evds_core::url::PARAMETER_ORDER) when they are known.
+    parameters: Vec<(Cow<'static, str>, String)>,
+}
+
+impl UrlBuilder {
+    /// creates a builder for the given resource placed after the url root, e.g. `"categories/"`. The resource is empty
+    /// for series and data group requests.
+    pub(crate) fn new(resource: &'static str) -> UrlBuilder {
+        UrlBuilder {
+            resource,
+            parameters: Vec::new(),
+        }
+    }
+
+    /// adds a parameter. A parameter added again replaces the former value.
+    pub(crate) fn add_parameter(mut self, name: &str, value: &str) -> UrlBuilder {
+
+        self.parameters.retain(|(parameter_name, _)| parameter_name != name);
+        self.parameters.push((intern_name(name), value.to_string()));
+
+        self
+    }
+
+    /// adds the parameters of an already formatted url fragment such as `"startDate=13-12-2011&endDate=13-12-2011"`
+    /// generated via [`MakingUrlFormat`](crate::traits::MakingUrlFormat).
+    pub(crate) fn add_fragment(mut self, fragment: &str) -> UrlBuilder {
+
+        for pair in fragment.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = match pair.find('=') {
+                Some(index) => (&pair[..index], &pair[index + 1..]),
+                None => (pair, ""),
+            };
+
+            self = self.add_parameter(name, value);
+        }
+
+        self
+    }
+
+    /// builds the url with the parameters in the canonical order.
+    pub(crate) fn build(&self) -> String {
+        let _span = tracing::info_span!("build_url").entered();
+
+        self.build_with(&[], false)
+    }
+
+    /// builds the same url with [`build`](UrlBuilder::build) except that the value of the api key is masked, so the 
+    /// url can be shown or logged safely.
+    pub(crate) fn build_masked(&self) -> String {
+        self.build_with(&[], true)
+    }
+
+    /// builds the url without the api key and the return format to key the cached responses. The result is the same for 
+    /// the requests of the same data differing only in their keys or formats.
+    #[cfg(feature = "cache")]
+    pub(crate) fn build_cache_key(&self) -> String {
+        self.build_with(&[KEY_PARAMETER, TYPE_PARAMETER], false)
+    }
+
+    /// builds the url without the api key to key the responses cached as they are received, which differ by their 
+    /// return formats.
+    #[cfg(feature = "cache")]
+    pub(crate) fn build_text_cache_key(&self) -> String {
+        self.build_with(&[KEY_PARAMETER], false)
+    }
+
+    /// splits the series list into the fewest consecutive groups whose urls do not exceed [`MAX_URL_LENGTH`], keeping
+    /// the order of the series. The builder itself is the only group when its url is short enough, and a single series 
+    /// longer than the limit makes a group alone.
+    pub(crate) fn split_series(&self) -> Vec<UrlBuilder> {
+
+        let series = match self.parameters.iter().find(|(name, _)| name == SERIES_PARAMETER) {
+            Some((_, series)) if self.build().len() > MAX_URL_LENGTH => series.clone(),
+            _ => return vec![self.clone()],
+        };
+
+        let with_series = |group: &[&str]| self.clone().add_parameter(SERIES_PARAMETER, &group.join("-"));
+
+        let mut groups = Vec::new();
+        let mut group: Vec<&str> = Vec::new();
+
+        for series_code in series.split('-') {
+            group.push(series_code);
+
+            if group.len() > 1 && with_series(&group).build().len() > MAX_URL_LENGTH {
+                group.pop();
+                groups.push(with_series(&group));
+
+                group = vec![series_code];
+            }
+        }
+
+        groups.push(with_series(&group));
+
+        groups
+    }
+
+    fn build_with(&self, excluded_parameters: &[&str], is_key_masked: bool) -> String {
+        build_url(self.resource, &self.parameters, excluded_parameters, is_key_masked)
+    }
+}
+
+/// checks the user input does not contain any of the characters separating query parameters, namely `&`, `=` and `#`.
+///
+/// # Error
+///
+/// This function returns `UnsafeQueryCharacter` error if the input contains one of them.
+pub(crate) fn check_query_value(value: &str) -> Result<(), ReturnError> {
+
+    if !is_query_value_safe(value) { return Err(ReturnError::UnsafeQueryCharacter); }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_build_canonical_url() {
+        let former_url = UrlBuilder::new("")
+            .add_parameter("key", "users_key")
+            .add_fragment("type=json")
+            .add_fragment("startDate=13-12-2011&endDate=13-12-2012")
+            .add_parameter("series", "TP.DK.USD.S");
+
+        let latter_url = UrlBuilder::new("")
+            .add_parameter("series", "TP.DK.USD.S")
+            .add_fragment("startDate=13-12-2011&endDate=13-12-2012&type=json")
+            .add_parameter("key", "users_key");
+
+        assert_eq!(
+            "https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S&startDate=13-12-2011&endDate=13-12-2012\
+            &type=json&key=users_key",
+            former_url.build()
+        );
+        assert_eq!(former_url.build(), latter_url.build());
+        assert_eq!(
+            "https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S&startDate=13-12-2011&endDate=13-12-2012\
+            &type=json&key=********",
+            former_url.build_masked()
+        );
+        #[cfg(feature = "cache")]
+        assert_eq!(
+            "https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S&startDate=13-12-2011&endDate=13-12-2012",
+            former_url.build_cache_key()
+        );
+
+        let escaped_url = UrlBuilder::new("serieList/").add_parameter("code", "bie_yssk&key=other key").build();
+
+        assert_eq!("https://evds2.tcmb.gov.tr/service/evds/serieList/code=bie_yssk%26key%3Dother%20key", escaped_url);
+    }
+
+    #[test]
+    fn should_split_long_series_list() {
+        let series: Vec<String> = (0..300).map(|index| format!("TP.SERIES.{:03}", index)).collect();
+
+        let url = UrlBuilder::new("")
+            .add_parameter("series", &series.join("-"))
+            .add_fragment("startDate=13-12-2011&endDate=13-12-2012")
+            .add_parameter("key", "users_key");
+
+        let groups = url.split_series();
+
+        assert!(groups.len() > 1);
+        assert!(groups.iter().all(|group| group.build().len() <= MAX_URL_LENGTH));
+
+        let split_series: Vec<String> = groups
+            .iter()
+            .flat_map(|group| group.parameters.iter().find(|(name, _)| name == "series").unwrap().1.split('-'))
+            .map(str::to_string)
+            .collect();
+
+        assert_eq!(series, split_series);
+        assert_eq!(1, UrlBuilder::new("").add_parameter("series", "TP.DK.USD.S").split_series().len());
+    }
+
+    #[test]
+    fn should_mask_api_key() {
+        let url = UrlBuilder::new("")
+            .add_parameter("series", "TP.DK.USD.S")
+            .add_parameter("key", "users_key");
+
+        assert_eq!(url.build_masked(), mask_api_key(&url.build()));
+        assert_eq!(
+            "https://evds2.tcmb.gov.tr/service/evds/categories/key=********",
+            mask_api_key("https://evds2.tcmb.gov.tr/service/evds/categories/key=users_key")
+        );
+    }
+
+    #[test]
+    fn should_check_query_value() {
+        assert!(check_query_value("TP.DK.USD.S-TP.DK.EUR.S").is_ok());
+        assert_eq!(Err(ReturnError::UnsafeQueryCharacter), check_query_value("bie_yssk&key=other_key"));
+        assert_eq!(Err(ReturnError::UnsafeQueryCharacter), check_query_value("TP.DK.USD.S#"));
+    }
+}
@@ -0,0 +1,296 @@
+use std::cell::RefCell;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::Dataset;
+
+use crate::common::url_builder::UrlBuilder;
+use crate::error::ReturnError;
+use crate::evds_c::settings::get_settings;
+use crate::metrics::METRICS;
+
+
+thread_local! {
+    /// is the message of the error that made the call of the thread serve a stale response from the cache.
+    static STALE_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+
+/// is the default maximum age of the responses of the data endpoints, whose observations are published and revised
+/// during the day.
+const DEFAULT_DATA_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// is the default maximum age of the responses of the catalog endpoints, which rarely change.
+const DEFAULT_CATALOG_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+
+/// groups the EVDS endpoints by how long their responses stay fresh.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum CacheEndpoint {
+    /// is the data, advanced data and data group endpoints returning observations.
+    Data,
+    /// is the categories, advanced data group and series list endpoints describing the catalog of EVDS.
+    Catalog,
+}
+
+/// is a response kept in the cache, either parsed to be served in any return format or as it is received.
+enum CachedResponse {
+    Parsed(Dataset),
+    Text(String),
+}
+
+struct CacheEntry {
+    key: String,
+    response: CachedResponse,
+    endpoint: CacheEndpoint,
+    cached_at: Instant,
+}
+
+/// keeps the responses of the requests to serve the same request again without requesting EVDS.
+///
+/// The parsed responses of data requests are keyed by the normalized url of the request that excludes the api key and
+/// the return format, so a series fetched in CSV is served in JSON from the same entry. The other responses are kept 
+/// as they are received and keyed by the url excluding only the api key. The least recently used entry is dropped when
+/// the cache is full. A zero capacity disables the cache.
+///
+/// An entry older than the maximum age of its endpoint is expired: it is not served anymore, but it is kept to be 
+/// served as a stale response when the request fails. The data endpoints expire after an hour and the catalog endpoints
+/// after a day by default, and the entries of an endpoint without a maximum age never expire.
+pub(crate) struct ResponseCache {
+    capacity: usize,
+    data_max_age: Option<Duration>,
+    catalog_max_age: Option<Duration>,
+    /// is ordered from the least recently used entry to the most recently used one.
+    entries: Vec<CacheEntry>,
+}
+
+impl ResponseCache {
+    pub(crate) const fn new() -> ResponseCache {
+        ResponseCache {
+            capacity: 0,
+            data_max_age: Some(DEFAULT_DATA_MAX_AGE),
+            catalog_max_age: Some(DEFAULT_CATALOG_MAX_AGE),
+            entries: Vec::new(),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// changes the capacity and drops the least recently used entries exceeding it.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+
+        self.capacity = capacity;
+
+        let excess = self.entries.len().saturating_sub(capacity);
+
+        self.entries.drain(..excess);
+    }
+
+    /// changes the maximum age of the entries of the endpoint. None makes the entries never expire.
+    pub(crate) fn set_max_age(&mut self, endpoint: CacheEndpoint, max_age: Option<Duration>) {
+        match endpoint {
+            CacheEndpoint::Data => self.data_max_age = max_age,
+            CacheEndpoint::Catalog => self.catalog_max_age = max_age,
+        }
+    }
+
+    /// gives a copy of the dataset of the request unless it is expired, and marks it as the most recently used.
+    pub(crate) fn get(&mut self, key: &str) -> Option<Dataset> {
+        match self.find(key, false)? {
+            CachedResponse::Parsed(dataset) => Some(dataset.clone()),
+            CachedResponse::Text(_) => None,
+        }
+    }
+
+    /// gives a copy of the dataset of the request even if it is expired, and marks it as the most recently used.
+    pub(crate) fn get_stale(&mut self, key: &str) -> Option<Dataset> {
+        match self.find(key, true)? {
+            CachedResponse::Parsed(dataset) => Some(dataset.clone()),
+            CachedResponse::Text(_) => None,
+        }
+    }
+
+    /// gives a copy of the response kept as it is received unless it is expired, or even if it is expired when stale 
+    /// responses are asked, and marks it as the most recently used.
+    pub(crate) fn get_text(&mut self, key: &str, is_stale_allowed: bool) -> Option<String> {
+        match self.find(key, is_stale_allowed)? {
+            CachedResponse::Text(response) => Some(response.clone()),
+            CachedResponse::Parsed(_) => None,
+        }
+    }
+
+    fn find(&mut self, key: &str, is_stale_allowed: bool) -> Option<&CachedResponse> {
+
+        let index = self.entries.iter().position(|entry| entry.key == key)?;
+
+        let entry = self.entries.remove(index);
+
+        self.entries.push(entry);
+
+        let entry = self.entries.last()?;
+
+        let max_age = match entry.endpoint {
+            CacheEndpoint::Data => self.data_max_age,
+            CacheEndpoint::Catalog => self.catalog_max_age,
+        };
+
+        if !is_stale_allowed && max_age.is_some_and(|max_age| entry.cached_at.elapsed() >= max_age) { return None; }
+
+        Some(&entry.response)
+    }
+
+    /// adds or replaces the dataset of the request. Nothing is kept when the cache is disabled.
+    pub(crate) fn insert(&mut self, key: String, dataset: Dataset) {
+        self.insert_entry(key, CachedResponse::Parsed(dataset), CacheEndpoint::Data);
+    }
+
+    /// adds or replaces the response of the request as it is received. Nothing is kept when the cache is disabled.
+    pub(crate) fn insert_text(&mut self, key: String, response: String, endpoint: CacheEndpoint) {
+        self.insert_entry(key, CachedResponse::Text(response), endpoint);
+    }
+
+    fn insert_entry(&mut self, key: String, response: CachedResponse, endpoint: CacheEndpoint) {
+
+        if !self.is_enabled() { return; }
+
+        self.entries.retain(|entry| entry.key != key);
+
+        if self.entries.len() == self.capacity { self.entries.remove(0); }
+
+        self.entries.push(CacheEntry { key, response, endpoint, cached_at: Instant::now() });
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+static RESPONSE_CACHE: Mutex<ResponseCache> = Mutex::new(ResponseCache::new());
+
+
+/// gives access to the process wide response cache.
+///
+/// A poisoned lock is recovered because a panic cannot leave an entry half inserted.
+pub(crate) fn get_response_cache() -> MutexGuard<'static, ResponseCache> {
+    match RESPONSE_CACHE.lock() {
+        Ok(cache) => cache,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// makes the request via the response cache when the cache is enabled, keeping the response as it is received.
+///
+/// A request failing due to the network is served from its expired cached response when stale responses are enabled.
+/// Empty responses and errors are not cached.
+// the responses are read through only by the features requesting the catalog or the currencies.
+#[cfg_attr(
+    not(any(feature = "currency", feature = "catalog", feature = "parse", feature = "export")),
+    allow(dead_code)
+)]
+pub(crate) fn read_through<F>(url: &UrlBuilder, endpoint: CacheEndpoint, request: F) -> Result<String, ReturnError>
+where
+    F: FnOnce(&str) -> Result<String, ReturnError>
+{
+
+    if !get_response_cache().is_enabled() { return request(&url.build()); }
+
+    let cache_key = url.build_text_cache_key();
+
+    let cached_response = get_response_cache().get_text(&cache_key, false);
+
+    METRICS.record_cache_lookup(cached_response.is_some());
+
+    if let Some(response) = cached_response { return Ok(response); }
+
+    let return_error = match request(&url.build()) {
+        Ok(response) => {
+            if !response.is_empty() { get_response_cache().insert_text(cache_key, response.clone(), endpoint); }
+
+            return Ok(response);
+        },
+        Err(return_error) => return_error,
+    };
+
+    if !is_stale_served(&return_error) { return Err(return_error); }
+
+    let stale_response = match get_response_cache().get_text(&cache_key, true) {
+        Some(stale_response) => stale_response,
+        None => return Err(return_error),
+    };
+
+    mark_stale(&return_error.to_string());
+
+    Ok(stale_response)
+}
+
+/// tells whether a request failing with the error is served from its cached response, namely the error is due to the
+/// network and stale responses are enabled via 
+/// [`tcmb_evds_c_set_stale_if_error`](crate::tcmb_evds_c_set_stale_if_error).
+pub(crate) fn is_stale_served(return_error: &ReturnError) -> bool {
+    get_settings().stale_if_error && return_error.is_network_failure()
+}
+
+/// marks the call of the thread as served from the cache due to the error.
+pub(crate) fn mark_stale(error: &str) {
+    STALE_ERROR.with(|stale_error| *stale_error.borrow_mut() = Some(error.to_string()));
+}
+
+/// gives the error that made the call of the thread serve a stale response and clears the mark. None is returned when
+/// the response of the call is not stale.
+pub(crate) fn take_stale_error() -> Option<String> {
+    STALE_ERROR.with(|stale_error| stale_error.borrow_mut().take())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_dataset(code: &str) -> Dataset {
+        Dataset { series_codes: vec![code.to_string()], observations: Vec::new() }
+    }
+
+    #[test]
+    fn should_drop_least_recently_used() {
+        let mut cache = ResponseCache::new();
+
+        cache.insert("first".to_string(), generate_dataset("A"));
+        assert!(cache.get("first").is_none());
+
+        cache.set_capacity(2);
+        cache.insert("first".to_string(), generate_dataset("A"));
+        cache.insert("second".to_string(), generate_dataset("B"));
+
+        assert!(cache.get("first").is_some());
+
+        cache.insert("third".to_string(), generate_dataset("C"));
+
+        assert!(cache.get("second").is_none());
+        assert_eq!(generate_dataset("A"), cache.get("first").unwrap());
+
+        cache.set_capacity(1);
+
+        assert!(cache.get("third").is_none());
+        assert!(cache.get("first").is_some());
+
+        cache.set_capacity(2);
+        cache.insert_text("categories".to_string(), "[]".to_string(), CacheEndpoint::Catalog);
+        cache.set_max_age(CacheEndpoint::Data, Some(Duration::ZERO));
+
+        assert!(cache.get("first").is_none());
+        assert!(cache.get_stale("first").is_some());
+        assert!(cache.get("categories").is_none());
+        assert_eq!(Some("[]"), cache.get_text("categories", false).as_deref());
+
+        cache.set_max_age(CacheEndpoint::Catalog, Some(Duration::ZERO));
+
+        assert!(cache.get_text("categories", false).is_none());
+        assert!(cache.get_text("categories", true).is_some());
+
+        cache.clear();
+
+        assert!(cache.get_stale("first").is_none());
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Mutex, MutexGuard};
+
+use serde_json::Value;
+
+use super::frequency::parse_frequency_name;
+use super::unquote;
+
+use crate::date::calendar::CalendarDate;
+use crate::evds_currency::frequency_formulas::DataFrequency;
+
+
+/// is the key of the series codes in the series lists of EVDS.
+const SERIES_CODE_KEY: &str = "SERIE_CODE";
+
+/// is the key of the first dates of the series in the series lists of EVDS.
+const START_DATE_KEY: &str = "START_DATE";
+
+/// is the key of the data groups of the series in the series lists of EVDS.
+const DATA_GROUP_CODE_KEY: &str = "DATAGROUP_CODE";
+
+/// is the key of the frequencies of the series in the series lists of EVDS.
+const FREQUENCY_KEY: &str = "FREQUENCY_STR";
+
+
+/// keeps the codes of the series received from the series lists of EVDS, e.g. "TP.DK.USD.S", so that tools like shell
+/// completions can list the known series without requesting EVDS. The codes are kept in order and without duplicates.
+///
+/// The first dates of the series are kept as well, so that their full histories can be requested from their actual
+/// starts, and so are the series of each data group, so that the groups in the category tree show their series counts.
+/// The frequencies of the series are kept to estimate the number of the observations of a request before making it.
+pub(crate) struct SeriesCatalog {
+    codes: BTreeSet<String>,
+    start_dates: BTreeMap<String, CalendarDate>,
+    group_series: BTreeMap<String, BTreeSet<String>>,
+    frequencies: BTreeMap<String, DataFrequency>,
+}
+
+impl SeriesCatalog {
+    pub(crate) const fn new() -> SeriesCatalog {
+        SeriesCatalog {
+            codes: BTreeSet::new(),
+            start_dates: BTreeMap::new(),
+            group_series: BTreeMap::new(),
+            frequencies: BTreeMap::new(),
+        }
+    }
+
+    /// inserts the codes, the first dates, the frequencies and the data groups of the series in a series list response.
+    pub(crate) fn insert_series_list(&mut self, series_list: &str) {
+        self.insert(parse_series_codes(series_list));
+        self.start_dates.extend(parse_start_dates(series_list));
+        self.frequencies.extend(parse_frequencies(series_list));
+
+        for (group_code, code) in parse_data_groups(series_list) {
+            self.group_series.entry(group_code).or_default().insert(code);
+        }
+    }
+
+    /// gives the number of the series of the data group, e.g. "bie_dkdovytl", if its series list is received.
+    #[cfg(feature = "catalog")]
+    pub(crate) fn count_group_series(&self, group_code: &str) -> Option<usize> {
+        self.group_series.get(group_code).map(BTreeSet::len)
+    }
+
+    /// gives the first date of the series, e.g. "TP.DK.USD.S", if it is received in a series list.
+    pub(crate) fn find_start_date(&self, code: &str) -> Option<CalendarDate> {
+        self.start_dates.get(code).copied()
+    }
+
+    /// gives the frequency of the series, e.g. "TP.DK.USD.S", if it is received in a series list.
+    pub(crate) fn find_frequency(&self, code: &str) -> Option<DataFrequency> {
+        self.frequencies.get(code).copied()
+    }
+
+    /// gives the data group of the series, e.g. "bie_dkdovytl" for "TP.DK.USD.S", if its series list is received.
+    #[cfg(feature = "catalog")]
+    pub(crate) fn find_data_group(&self, code: &str) -> Option<&str> {
+        self.group_series
+            .iter()
+            .find(|(_, codes)| codes.contains(code))
+            .map(|(group_code, _)| group_code.as_str())
+    }
+
+    /// tells whether the series, e.g. "TP.DK.USD.S", is received in a series list.
+    #[cfg(feature = "catalog")]
+    pub(crate) fn contains(&self, code: &str) -> bool {
+        self.codes.contains(code)
+    }
+
+    pub(crate) fn insert<I>(&mut self, codes: I)
+    where
+        I: IntoIterator<Item = String>
+    {
+        self.codes.extend(codes);
+    }
+
+    /// gives the codes starting with the prefix in order. All of the codes are given for an empty prefix.
+    #[cfg(feature = "parse")]
+    pub(crate) fn find_codes(&self, prefix: &str) -> Vec<String> {
+        self.codes
+            .range(prefix.to_string()..)
+            .take_while(|code| code.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+}
+
+static SERIES_CATALOG: Mutex<SeriesCatalog> = Mutex::new(SeriesCatalog::new());
+
+
+/// gives access to the process wide series catalog.
+///
+/// A poisoned lock is recovered because a panic cannot leave a code half inserted.
+pub(crate) fn get_series_catalog() -> MutexGuard<'static, SeriesCatalog> {
+    match SERIES_CATALOG.lock() {
+        Ok(catalog) => catalog,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+/// parses the series codes from a JSON or CSV series list response. Nothing is parsed from the other responses, e.g.
+/// XML or error messages.
+pub(crate) fn parse_series_codes(series_list: &str) -> Vec<String> {
+
+    if let Ok(parsed_list) = serde_json::from_str::<Value>(series_list) {
+        return parsed_list
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|item| item.get(SERIES_CODE_KEY)?.as_str().map(str::to_string))
+            .collect();
+    }
+
+    let mut lines = series_list.lines();
+
+    let code_index = lines
+        .next()
+        .and_then(|header| header.split(',').position(|cell| unquote(cell) == SERIES_CODE_KEY));
+
+    let code_index = match code_index {
+        Some(code_index) => code_index,
+        None => return Vec::new(),
+    };
+
+    lines
+        .filter_map(|line| line.split(',').nth(code_index).map(unquote))
+        .filter(|code| !code.is_empty() && !code.contains(char::is_whitespace))
+        .collect()
+}
+
+/// parses the first dates of the series from a JSON series list response. The series without a valid date are skipped.
+fn parse_start_dates(series_list: &str) -> Vec<(String, CalendarDate)> {
+    parse_series_field(series_list, |item| CalendarDate::parse(item.get(START_DATE_KEY)?.as_str()?.trim()))
+}
+
+/// parses the frequencies of the series from a JSON series list response. The unknown frequencies are skipped.
+fn parse_frequencies(series_list: &str) -> Vec<(String, DataFrequency)> {
+    parse_series_field(series_list, |item| parse_frequency_name(item.get(FREQUENCY_KEY)?.as_str()?))
+}
+
+/// parses the data groups of the series from a JSON series list response as (data group code, series code) pairs.
+fn parse_data_groups(series_list: &str) -> Vec<(String, String)> {
+    parse_series_field(series_list, |item| Some(item.get(DATA_GROUP_CODE_KEY)?.as_str()?.to_string()))
+        .into_iter()
+        .map(|(code, group_code)| (group_code, code))
+        .collect()
+}
+
+/// parses a field of each series from a JSON series list response with the code of the series. The series without the
+/// field are skipped.
+fn parse_series_field<T, F>(series_list: &str, parse_field: F) -> Vec<(String, T)>
+where
+    F: Fn(&Value) -> Option<T>
+{
+
+    let parsed_list: Value = match serde_json::from_str(series_list) {
+        Ok(parsed_list) => parsed_list,
+        Err(_) => return Vec::new(),
+    };
+
+    parsed_list
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| Some((item.get(SERIES_CODE_KEY)?.as_str()?.to_string(), parse_field(item)?)))
+        .collect()
+}
+
+
+#[cfg(all(test, feature = "catalog", feature = "parse"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_codes_by_prefix() {
+        let mut catalog = SeriesCatalog::new();
+
+        catalog.insert(parse_series_codes(r#"[{"SERIE_CODE":"TP.DK.USD.S"},{"SERIE_CODE":"TP.DK.EUR.S"}]"#));
+        catalog.insert(parse_series_codes("SERIE_CODE,SERIE_NAME\nTP.DK.USD.A,\"(USD) ABD Doları\"\nTP.FG.J0,TÜFE"));
+        catalog.insert(parse_series_codes("<document/>"));
+
+        assert_eq!(vec!["TP.DK.EUR.S", "TP.DK.USD.A", "TP.DK.USD.S"], catalog.find_codes("TP.DK."));
+        assert_eq!(vec!["TP.FG.J0"], catalog.find_codes("TP.FG"));
+        assert_eq!(4, catalog.find_codes("").len());
+        assert!(catalog.find_codes("TP.X").is_empty());
+
+        catalog.insert_series_list(r#"[{"SERIE_CODE":"TP.DK.CHF.S","START_DATE":"02-01-1950"},{"SERIE_CODE":"TP.X"}]"#);
+
+        assert_eq!(CalendarDate::parse("02-01-1950"), catalog.find_start_date("TP.DK.CHF.S"));
+        assert_eq!(None, catalog.find_start_date("TP.X"));
+        assert_eq!(6, catalog.find_codes("").len());
+
+        catalog.insert_series_list(r#"[{"SERIE_CODE":"TP.DK.USD.A","DATAGROUP_CODE":"bie_dkdovytl"},
+            {"SERIE_CODE":"TP.DK.USD.S","DATAGROUP_CODE":"bie_dkdovytl"}]"#);
+
+        assert_eq!(Some(2), catalog.count_group_series("bie_dkdovytl"));
+        assert_eq!(Some("bie_dkdovytl"), catalog.find_data_group("TP.DK.USD.S"));
+        assert_eq!(None, catalog.find_data_group("TP.FG.J0"));
+        assert_eq!(None, catalog.count_group_series("bie_pyrepo"));
+
+        catalog.insert_series_list(r#"[{"SERIE_CODE":"TP.FG.J0","FREQUENCY_STR":"AYLIK"}]"#);
+
+        assert_eq!(Some(DataFrequency::Monthly), catalog.find_frequency("TP.FG.J0"));
+        assert_eq!(None, catalog.find_frequency("TP.DK.USD.A"));
+    }
+}
@@ -0,0 +1,190 @@
+use serde_json::{Value, json};
+
+use super::catalog::SeriesCatalog;
+use super::header::{HeaderLanguage, SeriesNames};
+
+
+/// is the key of the category ids in the categories and the data groups of EVDS.
+const CATEGORY_ID_KEY: &str = "CATEGORY_ID";
+
+
+/// is a category or a data group in the category tree.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TreeNode {
+    /// is the id of a category, e.g. "1", or the code of a data group, e.g. "bie_pyrepo".
+    pub(crate) code: String,
+    pub(crate) names: SeriesNames,
+    /// is the number of the series of a data group, which is None for a category or a data group whose series list is
+    /// not received yet.
+    pub(crate) series_count: Option<usize>,
+    pub(crate) first_child: Option<usize>,
+    pub(crate) next_sibling: Option<usize>,
+}
+
+/// is the catalog of EVDS as categories holding their data groups, parsed once so that user interfaces expand the nodes
+/// without requesting EVDS again.
+///
+/// The nodes are kept in a flat list and linked by the indexes of their first children and next siblings. The
+/// categories come first in the order of the categories response, and the data groups of each category follow in the
+/// order of the data groups response.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CategoryTree {
+    pub(crate) nodes: Vec<TreeNode>,
+}
+
+impl CategoryTree {
+    /// parses the JSON responses of the categories and all data groups. The series counts of the data groups are taken
+    /// from the series lists kept in the catalog. The data groups of unknown categories are skipped.
+    ///
+    /// None is returned when either response is not a JSON array, e.g. an error message.
+    pub(crate) fn parse(categories: &str, data_groups: &str, catalog: &SeriesCatalog) -> Option<CategoryTree> {
+
+        let categories: Vec<Value> = serde_json::from_str(categories).ok()?;
+        let data_groups: Vec<Value> = serde_json::from_str(data_groups).ok()?;
+
+        let mut nodes: Vec<TreeNode> = categories
+            .iter()
+            .filter_map(|category| {
+                Some(TreeNode {
+                    code: read_category_id(category)?,
+                    names: read_names(category, "TOPIC_TITLE_TR", "TOPIC_TITLE_ENG"),
+                    series_count: None,
+                    first_child: None,
+                    next_sibling: None,
+                })
+            })
+            .collect();
+
+        let category_count = nodes.len();
+
+        for index in 1..category_count { nodes[index - 1].next_sibling = Some(index); }
+
+        // is the index of the last data group of each category to link the next data group to it.
+        let mut last_children: Vec<Option<usize>> = vec![None; category_count];
+
+        for data_group in &data_groups {
+            let category_index = match read_category_id(data_group) {
+                Some(category_id) => nodes[..category_count].iter().position(|category| category.code == category_id),
+                None => None,
+            };
+
+            let (category_index, code) = match (category_index, data_group.get("DATAGROUP_CODE")) {
+                (Some(category_index), Some(Value::String(code))) => (category_index, code.clone()),
+                _ => continue,
+            };
+
+            let index = nodes.len();
+
+            nodes.push(TreeNode {
+                series_count: catalog.count_group_series(&code),
+                code,
+                names: read_names(data_group, "DATAGROUP_NAME", "DATAGROUP_NAME_ENG"),
+                first_child: None,
+                next_sibling: None,
+            });
+
+            match last_children[category_index] {
+                Some(last_child) => nodes[last_child].next_sibling = Some(index),
+                None => nodes[category_index].first_child = Some(index),
+            }
+
+            last_children[category_index] = Some(index);
+        }
+
+        Some(CategoryTree { nodes })
+    }
+
+    /// gives the index of the first category, which is None for an empty tree.
+    pub(crate) fn first_category(&self) -> Option<usize> {
+        match self.nodes.is_empty() {
+            true => None,
+            false => Some(0),
+        }
+    }
+
+    /// stringifies the tree as a JSON array of the categories with their data groups, e.g.
+    /// `[{"code":"1","name":"MARKET STATISTICS","groups":[{"code":"bie_pyrepo","name":"...","seriesCount":null}]}]`.
+    pub(crate) fn to_json(&self, language: HeaderLanguage) -> String {
+
+        let children = |first_child: Option<usize>| {
+            std::iter::successors(first_child, |index| self.nodes[*index].next_sibling).map(|index| &self.nodes[index])
+        };
+
+        let categories: Vec<Value> = children(self.first_category())
+            .map(|category| {
+                let groups: Vec<Value> = children(category.first_child)
+                    .map(|group| {
+                        let series_count = group.series_count;
+
+                        json!({ "code": group.code, "name": group.names.get(language), "seriesCount": series_count })
+                    })
+                    .collect();
+
+                json!({ "code": category.code, "name": category.names.get(language), "groups": groups })
+            })
+            .collect();
+
+        Value::from(categories).to_string()
+    }
+}
+
+/// reads the category id, which EVDS writes as a number in some responses and as a string in others.
+fn read_category_id(item: &Value) -> Option<String> {
+    match item.get(CATEGORY_ID_KEY)? {
+        Value::Number(category_id) => Some(category_id.to_string()),
+        Value::String(category_id) => Some(category_id.trim().to_string()),
+        _ => None,
+    }
+}
+
+fn read_names(item: &Value, turkish_key: &str, english_key: &str) -> SeriesNames {
+
+    let read_name = |key: &str| item.get(key).and_then(Value::as_str).unwrap_or_default().trim().to_string();
+
+    SeriesNames { turkish: read_name(turkish_key), english: read_name(english_key) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_category_tree() {
+        let categories = r#"[
+            {"CATEGORY_ID":1,"TOPIC_TITLE_TR":"PİYASA VERİLERİ","TOPIC_TITLE_ENG":"MARKET STATISTICS"},
+            {"CATEGORY_ID":2,"TOPIC_TITLE_TR":"KURLAR","TOPIC_TITLE_ENG":"EXCHANGE RATES"}
+        ]"#;
+        let data_groups = r#"[
+            {"CATEGORY_ID":2,"DATAGROUP_CODE":"bie_dkdovytl","DATAGROUP_NAME":"Kurlar","DATAGROUP_NAME_ENG":"Rates"},
+            {"CATEGORY_ID":1,"DATAGROUP_CODE":"bie_pyrepo","DATAGROUP_NAME":"Repo","DATAGROUP_NAME_ENG":""},
+            {"CATEGORY_ID":"2","DATAGROUP_CODE":"bie_dkdovizgn","DATAGROUP_NAME":"Efektif","DATAGROUP_NAME_ENG":""},
+            {"CATEGORY_ID":9,"DATAGROUP_CODE":"bie_unknown"}
+        ]"#;
+
+        let mut catalog = SeriesCatalog::new();
+        catalog.insert_series_list(r#"[{"SERIE_CODE":"TP.DK.USD.A","DATAGROUP_CODE":"bie_dkdovytl"}]"#);
+
+        let tree = CategoryTree::parse(categories, data_groups, &catalog).unwrap();
+
+        assert_eq!(5, tree.nodes.len());
+        assert_eq!((Some(1), Some(2)), (tree.nodes[0].next_sibling, tree.nodes[1].first_child));
+        assert_eq!(Some(4), tree.nodes[2].next_sibling);
+        assert_eq!("bie_dkdovizgn", tree.nodes[4].code);
+        assert_eq!((Some(1), None), (tree.nodes[2].series_count, tree.nodes[4].series_count));
+        assert_eq!(
+            json!([
+                {"code": "1", "name": "MARKET STATISTICS", "groups": [
+                    {"code": "bie_pyrepo", "name": "Repo", "seriesCount": null}
+                ]},
+                {"code": "2", "name": "EXCHANGE RATES", "groups": [
+                    {"code": "bie_dkdovytl", "name": "Rates", "seriesCount": 1},
+                    {"code": "bie_dkdovizgn", "name": "Efektif", "seriesCount": null}
+                ]}
+            ]),
+            serde_json::from_str::<Value>(&tree.to_json(HeaderLanguage::English)).unwrap()
+        );
+        assert!(CategoryTree::parse("Error: Invalid api key.", data_groups, &catalog).is_none());
+        assert_eq!(None, CategoryTree::parse("[]", "[]", &catalog).unwrap().first_category());
+    }
+}
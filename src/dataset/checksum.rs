@@ -0,0 +1,50 @@
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "parse")]
+use super::Dataset;
+
+
+/// computes the SHA-256 checksum of the canonical form of the dataset as a lowercase hexadecimal string.
+///
+/// The canonical form is the normalized CSV of [`Dataset::to_csv`], so the same data gives the same checksum whichever
+/// return format it is fetched in, and the metadata such as `UNIXTIME` does not affect it.
+#[cfg(feature = "parse")]
+pub(crate) fn compute_checksum(dataset: &Dataset) -> String {
+
+    sha256(dataset.to_csv().as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// computes the SHA-256 digest of the data as defined in FIPS 180-4.
+pub(crate) fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+
+#[cfg(all(test, feature = "parse"))]
+mod tests {
+    use super::*;
+
+    fn to_hex(digest: [u8; 32]) -> String {
+        digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn should_compute_checksum() {
+        assert_eq!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855", to_hex(sha256(b"")));
+        assert_eq!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad", to_hex(sha256(b"abc")));
+        assert_eq!(
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            to_hex(sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"))
+        );
+
+        let json_dataset = 
+            Dataset::from_response(r#"{"items":[{"Tarih":"13-12-2011","TP_DK_USD_S":"1.8673"}]}"#).unwrap();
+        let csv_dataset = Dataset::from_response("Tarih,TP_DK_USD_S,UNIXTIME\n13-12-2011,1.8673,1323727200").unwrap();
+
+        assert_eq!(compute_checksum(&json_dataset), compute_checksum(&csv_dataset));
+        assert_eq!(64, compute_checksum(&json_dataset).len());
+    }
+}
@@ -0,0 +1,51 @@
+use std::borrow::Cow;
+
+use crate::evds_c::settings::get_settings;
+
+
+/// are the first characters making spreadsheet applications read a cell as a formula.
+const FORMULA_TRIGGERS: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+
+/// tells whether the text cells of the CSV written by the library are neutralized, which is set via
+/// [`tcmb_evds_c_set_csv_injection_protection`](crate::tcmb_evds_c_set_csv_injection_protection).
+pub(crate) fn is_protection_enabled() -> bool {
+    get_settings().csv_injection_protection
+}
+
+/// neutralizes a CSV cell that a spreadsheet application would run as a formula, e.g. "=HYPERLINK(..)" becomes 
+/// "'=HYPERLINK(..)", by prefixing it with a single quote. Numbers such as "-1.5" are kept since they are not run.
+pub(crate) fn neutralize_formula(cell: &str) -> Cow<'_, str> {
+
+    let is_formula = cell.starts_with(FORMULA_TRIGGERS) && cell.trim().parse::<f64>().is_err();
+
+    match is_formula {
+        true => Cow::Owned(format!("'{}", cell)),
+        false => Cow::Borrowed(cell),
+    }
+}
+
+/// neutralizes the cell when the protection is enabled.
+pub(crate) fn protect_cell(cell: &str, is_protected: bool) -> Cow<'_, str> {
+    match is_protected {
+        true => neutralize_formula(cell),
+        false => Cow::Borrowed(cell),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_neutralize_formulas() {
+        assert_eq!("'=1+2", neutralize_formula("=1+2"));
+        assert_eq!("'@SUM(A1:A2)", neutralize_formula("@SUM(A1:A2)"));
+        assert_eq!("'+90 (Bin TL)", neutralize_formula("+90 (Bin TL)"));
+        assert_eq!("'-", neutralize_formula("-"));
+        assert_eq!("-1.8534", neutralize_formula("-1.8534"));
+        assert_eq!("(USD) ABD Doları", neutralize_formula("(USD) ABD Doları"));
+        assert_eq!("=1+2", protect_cell("=1+2", false));
+    }
+}
@@ -0,0 +1,65 @@
+use super::{Dataset, Observation};
+
+use crate::error::ReturnError;
+
+
+/// is appended to the code of a nominal series to name its real series, e.g. `TP_KREDI_TOPLAM_REAL`.
+const REAL_SERIES_SUFFIX: &str = "_REAL";
+
+
+/// converts the nominal series, namely the first series of the dataset, into a real series in the prices of the base
+/// period by dividing it by the price index, namely the second series, and multiplying by the index at the base period.
+///
+/// The base period is written like the dates of EVDS, e.g. "2003-1" for a month. A value is missing in the real series
+/// when the nominal value or the index is missing at its date.
+///
+/// # Error
+///
+/// This function returns `InvalidDate` error when the base period is not a date of the dataset or the price index has
+/// no positive value at it, and `UnparsableResponse` when the dataset does not have two series.
+pub(crate) fn deflate(dataset: &Dataset, base_period: &str) -> Result<Dataset, ReturnError> {
+
+    if dataset.series_codes.len() != 2 { return Err(ReturnError::UnparsableResponse); }
+
+    let base_index = dataset
+        .find_observation(base_period)
+        .and_then(|observation| observation.values[1])
+        .filter(|base_index| *base_index > 0.0)
+        .ok_or(ReturnError::InvalidDate)?;
+
+    let observations = dataset.observations
+        .iter()
+        .map(|observation| {
+            let real_value = match (observation.values[0], observation.values[1]) {
+                (Some(nominal_value), Some(index)) if index != 0.0 => Some(nominal_value / index * base_index),
+                _ => None,
+            };
+
+            Observation { date: observation.date.clone(), values: vec![real_value], unix_time: observation.unix_time }
+        })
+        .collect();
+
+    Ok(Dataset { series_codes: vec![dataset.series_codes[0].clone() + REAL_SERIES_SUFFIX], observations })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_deflate_nominal_series() {
+        let dataset = Dataset::from_csv(
+            "Tarih,TP_KREDI_TOPLAM,TP_FG_J0\n2020-1,100,50\n2020-2,110,55\n2020-3,,60\n2020-4,150,75"
+        ).unwrap();
+
+        let real_dataset = deflate(&dataset, "2020-02").unwrap();
+
+        assert_eq!(vec!["TP_KREDI_TOPLAM_REAL".to_string()], real_dataset.series_codes);
+        assert_eq!(
+            vec![Some(110.0), Some(110.0), None, Some(110.0)],
+            real_dataset.observations.iter().map(|observation| observation.values[0]).collect::<Vec<Option<f64>>>()
+        );
+        assert!(matches!(deflate(&dataset, "2019-12"), Err(ReturnError::InvalidDate)));
+    }
+}
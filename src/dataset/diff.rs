@@ -0,0 +1,157 @@
+use serde_json::{json, Value};
+
+use super::Dataset;
+
+
+/// specifies how the value of a series at a date differs between two fetches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ChangeKind {
+    /// is a value published in the new fetch that is missing in the old one.
+    Added,
+    /// is a value revised between the fetches.
+    Changed,
+    /// is a value of the old fetch that is missing in the new one.
+    Removed,
+}
+
+/// is a difference of the value of a series at a date.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ObservationChange {
+    pub(crate) kind: ChangeKind,
+    pub(crate) date: String,
+    pub(crate) series_code: String,
+    pub(crate) old_value: Option<f64>,
+    pub(crate) new_value: Option<f64>,
+}
+
+
+/// compares the values of each series at each date of two fetches of the same data.
+///
+/// The observations are matched by their dates and the series by their codes. A missing value and a missing observation
+/// are treated the same. The changes are ordered by the dates of the new dataset followed by the dates only found in
+/// the old one, and by the series codes in the same way.
+pub(crate) fn diff_datasets(old_dataset: &Dataset, new_dataset: &Dataset) -> Vec<ObservationChange> {
+
+    let dates = merge_keys(
+        new_dataset.observations.iter().map(|observation| &observation.date),
+        old_dataset.observations.iter().map(|observation| &observation.date),
+    );
+
+    let series_codes = merge_keys(new_dataset.series_codes.iter(), old_dataset.series_codes.iter());
+
+    let mut changes = Vec::new();
+
+    for date in &dates {
+        for series_code in &series_codes {
+            let old_value = find_value(old_dataset, date, series_code);
+            let new_value = find_value(new_dataset, date, series_code);
+
+            let kind = match (old_value, new_value) {
+                (None, Some(_)) => ChangeKind::Added,
+                (Some(_), None) => ChangeKind::Removed,
+                (Some(old), Some(new)) if old != new => ChangeKind::Changed,
+                _ => continue,
+            };
+
+            changes.push(ObservationChange {
+                kind,
+                date: date.clone(),
+                series_code: series_code.clone(),
+                old_value,
+                new_value,
+            });
+        }
+    }
+
+    changes
+}
+
+/// stringifies the changes as a JSON object grouping them as `added`, `changed` and `removed`, e.g.
+/// `{"added":[{"date":"14-12-2011","series":"TP_DK_USD_S","oldValue":null,"newValue":1.87}],"changed":[],"removed":[]}`.
+pub(crate) fn changes_to_json(changes: &[ObservationChange]) -> String {
+
+    let generate_group = |kind: ChangeKind| {
+        changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .map(|change| {
+                json!({
+                    "date": change.date,
+                    "series": change.series_code,
+                    "oldValue": change.old_value,
+                    "newValue": change.new_value,
+                })
+            })
+            .collect::<Vec<Value>>()
+    };
+
+    json!({
+        "added": generate_group(ChangeKind::Added),
+        "changed": generate_group(ChangeKind::Changed),
+        "removed": generate_group(ChangeKind::Removed),
+    })
+    .to_string()
+}
+
+/// collects the keys of the first iterator followed by the keys only found in the second one.
+fn merge_keys<'a, F, S>(first_keys: F, second_keys: S) -> Vec<String>
+where
+    F: Iterator<Item = &'a String>,
+    S: Iterator<Item = &'a String>,
+{
+
+    let mut keys: Vec<String> = Vec::new();
+
+    for key in first_keys.chain(second_keys) {
+        if !keys.contains(key) { keys.push(key.clone()); }
+    }
+
+    keys
+}
+
+fn find_value(dataset: &Dataset, date: &str, series_code: &str) -> Option<f64> {
+
+    let column = dataset.series_codes.iter().position(|code| code == series_code)?;
+
+    dataset.observations
+        .iter()
+        .find(|observation| observation.date == date)
+        .and_then(|observation| observation.values.get(column).copied().flatten())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_revisions() {
+        let old_dataset = Dataset::from_json(r#"{"items":[
+            {"Tarih":"09-12-2011","TP_DK_USD_S":"1.8412"},
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8534"},
+            {"Tarih":"13-12-2011","TP_DK_USD_S":"1.8673"}
+        ]}"#).unwrap();
+
+        let new_dataset = Dataset::from_json(r#"{"items":[
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8535"},
+            {"Tarih":"13-12-2011","TP_DK_USD_S":"1.8673"},
+            {"Tarih":"14-12-2011","TP_DK_USD_S":"1.8701"}
+        ]}"#).unwrap();
+
+        let changes = diff_datasets(&old_dataset, &new_dataset);
+
+        assert_eq!(3, changes.len());
+        assert_eq!(ChangeKind::Changed, changes[0].kind);
+        assert_eq!((Some(1.8534), Some(1.8535)), (changes[0].old_value, changes[0].new_value));
+        assert_eq!(ChangeKind::Added, changes[1].kind);
+        assert_eq!("14-12-2011", changes[1].date);
+        assert_eq!(ChangeKind::Removed, changes[2].kind);
+        assert_eq!("09-12-2011", changes[2].date);
+
+        let changes: Value = serde_json::from_str(&changes_to_json(&changes)).unwrap();
+
+        assert_eq!("TP_DK_USD_S", changes["added"][0]["series"]);
+        assert_eq!(1.8412, changes["removed"][0]["oldValue"]);
+        assert!(diff_datasets(&new_dataset, &new_dataset).is_empty());
+    }
+}
@@ -0,0 +1,397 @@
+use super::{Dataset, Observation};
+
+use crate::error::ReturnError;
+
+
+/// is an arithmetic operation of two operands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    fn apply(&self, left: f64, right: f64) -> Option<f64> {
+        match self {
+            Operator::Add => Some(left + right),
+            Operator::Subtract => Some(left - right),
+            Operator::Multiply => Some(left * right),
+            Operator::Divide if right == 0.0 => None,
+            Operator::Divide => Some(left / right),
+        }
+    }
+}
+
+/// is a parsed expression over the series of a dataset, e.g. `(TP.DK.EUR.S - TP.DK.USD.S) / TP.DK.USD.S * 100`.
+///
+/// The series are referred by their codes given to EVDS, e.g. "TP.DK.USD.S", and the operators follow the usual
+/// precedence. The functions work along the observations:
+///
+/// > **log(x):** the natural logarithm of each value. <br />
+/// > **diff(x):** the change of each value from the previous observation. <br />
+/// > **lag(x, n):** the value `n` observations before.
+///
+/// The expression is evaluated for all observations at once, and a missing operand, a division by zero, the logarithm
+/// of a non-positive value or a value before the first observation gives a missing value rather than an error.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expression {
+    Number(f64),
+    Series(String),
+    Negate(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Log(Box<Expression>),
+    Diff(Box<Expression>),
+    Lag(Box<Expression>, usize),
+}
+
+impl Expression {
+    /// parses the text of an expression.
+    ///
+    /// # Error
+    ///
+    /// This function returns `InvalidExpression` error with the position of the first unexpected character.
+    pub(crate) fn parse(text: &str) -> Result<Expression, ReturnError> {
+
+        let mut parser = Parser { text, position: 0 };
+
+        let expression = parser.parse_sum()?;
+
+        parser.skip_whitespaces();
+
+        if parser.position != text.len() { return Err(parser.error("an operator is expected")); }
+
+        Ok(expression)
+    }
+
+    /// gives the codes of the series the expression refers to in their first order without duplicates.
+    pub(crate) fn series_codes(&self) -> Vec<String> {
+
+        let mut series_codes = Vec::new();
+
+        self.collect_series_codes(&mut series_codes);
+
+        series_codes
+    }
+
+    fn collect_series_codes(&self, series_codes: &mut Vec<String>) {
+        match self {
+            Expression::Number(_) => (),
+            Expression::Series(code) => if !series_codes.contains(code) { series_codes.push(code.clone()); },
+            Expression::Negate(operand) 
+            | Expression::Log(operand) 
+            | Expression::Diff(operand) 
+            | Expression::Lag(operand, _) => operand.collect_series_codes(series_codes),
+            Expression::Binary(_, left, right) => {
+                left.collect_series_codes(series_codes);
+                right.collect_series_codes(series_codes);
+            },
+        }
+    }
+
+    /// evaluates the expression for each observation of the dataset. The series are matched by their codes where the
+    /// dots are underscores in the dataset.
+    ///
+    /// # Error
+    ///
+    /// This function returns `InvalidExpression` error when a series of the expression is not in the dataset.
+    pub(crate) fn evaluate(&self, dataset: &Dataset) -> Result<Vec<Option<f64>>, ReturnError> {
+        match self {
+            Expression::Number(number) => Ok(vec![Some(*number); dataset.observations.len()]),
+            Expression::Series(code) => {
+                let column_code = code.replace('.', "_");
+
+                let column = match dataset.series_codes.iter().position(|series_code| *series_code == column_code) {
+                    Some(column) => column,
+                    None => {
+                        return Err(ReturnError::InvalidExpression(format!("the series {} is not received", code)));
+                    },
+                };
+
+                Ok(dataset.observations.iter().map(|observation| observation.values[column]).collect())
+            },
+            Expression::Negate(operand) => {
+                Ok(operand.evaluate(dataset)?.into_iter().map(|value| value.map(|value| -value)).collect())
+            },
+            Expression::Binary(operator, left, right) => {
+                let values = left.evaluate(dataset)?
+                    .into_iter()
+                    .zip(right.evaluate(dataset)?)
+                    .map(|values| match values {
+                        (Some(left), Some(right)) => operator.apply(left, right),
+                        _ => None,
+                    })
+                    .collect();
+
+                Ok(values)
+            },
+            Expression::Log(operand) => {
+                let values = operand.evaluate(dataset)?
+                    .into_iter()
+                    .map(|value| value.filter(|value| *value > 0.0).map(f64::ln))
+                    .collect();
+
+                Ok(values)
+            },
+            Expression::Diff(operand) => {
+                let values = operand.evaluate(dataset)?;
+
+                let differences = (0..values.len())
+                    .map(|index| match (index.checked_sub(1).and_then(|previous| values[previous]), values[index]) {
+                        (Some(previous), Some(current)) => Some(current - previous),
+                        _ => None,
+                    })
+                    .collect();
+
+                Ok(differences)
+            },
+            Expression::Lag(operand, lag) => {
+                let values = operand.evaluate(dataset)?;
+
+                Ok((0..values.len()).map(|index| index.checked_sub(*lag).and_then(|lagged| values[lagged])).collect())
+            },
+        }
+    }
+
+    /// evaluates the expression into a dataset of a single series with the given code and the dates of the dataset.
+    ///
+    /// # Error
+    ///
+    /// This function returns `InvalidExpression` error when a series of the expression is not in the dataset.
+    pub(crate) fn evaluate_into_dataset(&self, series_code: &str, dataset: &Dataset) -> Result<Dataset, ReturnError> {
+
+        let values = self.evaluate(dataset)?;
+
+        let observations = dataset.observations
+            .iter()
+            .zip(values)
+            .map(|(observation, value)| Observation { values: vec![value], ..observation.clone() })
+            .collect();
+
+        Ok(Dataset { series_codes: vec![series_code.to_string()], observations })
+    }
+}
+
+
+/// checks the character whether it may be a part of a series code, e.g. "TP.DK.USD.S", or not.
+pub(crate) fn is_series_character(character: char) -> bool {
+    character.is_ascii_alphanumeric() || character == '.' || character == '_'
+}
+
+
+/// parses an expression by recursive descent, one precedence level per method.
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, expectation: &str) -> ReturnError {
+        ReturnError::InvalidExpression(format!("{} at position {}", expectation, self.position + 1))
+    }
+
+    fn skip_whitespaces(&mut self) {
+        let rest = &self.text[self.position..];
+
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespaces();
+
+        self.text[self.position..].chars().next()
+    }
+
+    /// takes the longest text at the position whose characters match the condition.
+    fn take_while<F>(&mut self, condition: F) -> &'a str
+    where
+        F: Fn(char) -> bool
+    {
+        let rest = &self.text[self.position..];
+        let length = rest.find(|character| !condition(character)).unwrap_or(rest.len());
+
+        self.position += length;
+
+        &rest[..length]
+    }
+
+    fn parse_sum(&mut self) -> Result<Expression, ReturnError> {
+
+        let mut expression = self.parse_product()?;
+
+        loop {
+            let operator = match self.peek() {
+                Some('+') => Operator::Add,
+                Some('-') => Operator::Subtract,
+                _ => return Ok(expression),
+            };
+
+            self.position += 1;
+
+            expression = Expression::Binary(operator, Box::new(expression), Box::new(self.parse_product()?));
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<Expression, ReturnError> {
+
+        let mut expression = self.parse_operand()?;
+
+        loop {
+            let operator = match self.peek() {
+                Some('*') => Operator::Multiply,
+                Some('/') => Operator::Divide,
+                _ => return Ok(expression),
+            };
+
+            self.position += 1;
+
+            expression = Expression::Binary(operator, Box::new(expression), Box::new(self.parse_operand()?));
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Expression, ReturnError> {
+        match self.peek() {
+            Some('-') => {
+                self.position += 1;
+
+                Ok(Expression::Negate(Box::new(self.parse_operand()?)))
+            },
+            Some('(') => {
+                self.position += 1;
+
+                let expression = self.parse_sum()?;
+
+                if self.peek() != Some(')') { return Err(self.error("a closing parenthesis is expected")); }
+
+                self.position += 1;
+
+                Ok(expression)
+            },
+            Some(character) if character.is_ascii_digit() => {
+                let start = self.position;
+                let number = self.take_while(|character| character.is_ascii_digit() || character == '.');
+
+                match number.parse::<f64>() {
+                    Ok(number) => Ok(Expression::Number(number)),
+                    Err(_) => {
+                        self.position = start;
+
+                        Err(self.error("a valid number is expected"))
+                    },
+                }
+            },
+            Some(character) if character.is_ascii_alphabetic() => {
+                let start = self.position;
+                let name = self.take_while(is_series_character);
+
+                if self.peek() != Some('(') { return Ok(Expression::Series(name.to_string())); }
+
+                self.position += 1;
+
+                let operand = Box::new(self.parse_sum()?);
+
+                let function = match name {
+                    "log" => Expression::Log(operand),
+                    "diff" => Expression::Diff(operand),
+                    "lag" => Expression::Lag(operand, self.parse_lag()?),
+                    _ => {
+                        self.position = start;
+
+                        return Err(self.error("one of the log, diff and lag functions is expected"));
+                    },
+                };
+
+                if self.peek() != Some(')') { return Err(self.error("a closing parenthesis is expected")); }
+
+                self.position += 1;
+
+                Ok(function)
+            },
+            _ => Err(self.error("a series, a number or a parenthesis is expected")),
+        }
+    }
+
+    /// parses the second argument of the lag function, which is the number of observations to shift.
+    fn parse_lag(&mut self) -> Result<usize, ReturnError> {
+
+        if self.peek() != Some(',') { return Err(self.error("a comma is expected")); }
+
+        self.position += 1;
+        self.skip_whitespaces();
+
+        match self.take_while(|character| character.is_ascii_digit()).parse::<usize>() {
+            Ok(lag) => Ok(lag),
+            Err(_) => Err(self.error("the number of observations is expected")),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_and_evaluate_expression() {
+        let expression = Expression::parse(" (TP.DK.EUR.S - TP.DK.USD.S) / TP.DK.USD.S * 100").unwrap();
+
+        assert_eq!(vec!["TP.DK.EUR.S".to_string(), "TP.DK.USD.S".to_string()], expression.series_codes());
+
+        let dataset = Dataset::from_json(r#"{"totalCount":3,"items":[
+            {"Tarih":"09-12-2011","TP_DK_USD_S":"2","TP_DK_EUR_S":"3"},
+            {"Tarih":"10-12-2011","TP_DK_USD_S":null,"TP_DK_EUR_S":"3"},
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"0","TP_DK_EUR_S":"1"}
+        ]}"#).unwrap();
+
+        let spread = expression.evaluate_into_dataset("SPREAD", &dataset).unwrap();
+
+        assert_eq!(vec!["SPREAD".to_string()], spread.series_codes);
+        assert_eq!("10-12-2011", spread.observations[1].date);
+        assert_eq!(
+            vec![Some(50.0), None, None],
+            spread.observations.iter().map(|observation| observation.values[0]).collect::<Vec<_>>()
+        );
+
+        assert_eq!(vec![Some(-1.0); 3], Expression::parse("-(1 + -2) * -1").unwrap().evaluate(&dataset).unwrap());
+        assert!(Expression::parse("TP.DK.GBP.S").unwrap().evaluate(&dataset).is_err());
+    }
+
+    #[test]
+    fn should_evaluate_functions() {
+        let dataset = Dataset::from_json(r#"{"totalCount":4,"items":[
+            {"Tarih":"2011-9","TP_X":"1"},
+            {"Tarih":"2011-10","TP_X":"2"},
+            {"Tarih":"2011-11","TP_X":null},
+            {"Tarih":"2011-12","TP_X":"8"}
+        ]}"#).unwrap();
+
+        let evaluate = |text: &str| Expression::parse(text).unwrap().evaluate(&dataset).unwrap();
+
+        assert_eq!(vec![None, Some(1.0), None, None], evaluate("diff(TP.X)"));
+        assert_eq!(vec![None, None, Some(1.0), Some(2.0)], evaluate("lag(TP.X, 2)"));
+        assert_eq!(vec![Some(0.0), Some(1.0), None, Some(3.0)], evaluate("log(TP.X) / log(2)"));
+        assert_eq!(vec![None, Some(-1.0), None, None], evaluate("-diff(lag(TP_X, 0))"));
+        assert_eq!(vec!["TP.X".to_string()], Expression::parse("log(TP.X) - lag(TP.X, 1)").unwrap().series_codes());
+    }
+
+    #[test]
+    fn should_report_invalid_expression() {
+        let invalid_expressions = [
+            ("TP.DK.USD.S -", "at position 14"),
+            ("(TP.DK.USD.S", "a closing parenthesis"),
+            ("TP.DK.USD.S TP.DK.EUR.S", "at position 13"),
+            ("1.2.3", "a valid number"),
+            ("mean(TP.DK.USD.S)", "at position 1"),
+            ("lag(TP.DK.USD.S)", "a comma"),
+            ("lag(TP.DK.USD.S, -1)", "the number of observations"),
+        ];
+
+        for (text, expected_message) in invalid_expressions {
+            match Expression::parse(text) {
+                Err(ReturnError::InvalidExpression(message)) => assert!(message.contains(expected_message), "{}", text),
+                _ => panic!("{} should be invalid", text),
+            }
+        }
+    }
+}
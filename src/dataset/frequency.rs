@@ -0,0 +1,325 @@
+#[cfg(feature = "parse")]
+use super::Dataset;
+
+#[cfg(any(feature = "currency", feature = "parse"))]
+use crate::date::calendar::CalendarDate;
+use crate::evds_currency::frequency_formulas::DataFrequency;
+
+
+/// are the data frequencies from the finest to the coarsest.
+const FREQUENCIES: [DataFrequency; 8] = [
+    DataFrequency::Daily,
+    DataFrequency::Business,
+    DataFrequency::WeeklyFriday,
+    DataFrequency::TwiceMonthly,
+    DataFrequency::Monthly,
+    DataFrequency::Quarterly,
+    DataFrequency::SemiAnnual,
+    DataFrequency::Annual,
+];
+
+
+/// is the frequency of a dataset inferred from the dates of its observations together with its gap statistics.
+#[cfg(feature = "parse")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FrequencyReport {
+    /// is None when the dates are not recognized or there are less than two observations with values.
+    pub(crate) frequency: Option<DataFrequency>,
+    pub(crate) observation_count: usize,
+    /// is the number of the observations without any value, e.g. weekends of a daily series.
+    pub(crate) missing_value_count: usize,
+    /// is the number of the intervals between observations with values that are longer than the frequency expects.
+    pub(crate) gap_count: usize,
+    /// is the length of the longest gap in days, or zero when there is no gap.
+    pub(crate) largest_gap_days: usize,
+}
+
+
+/// infers the frequency of the dataset from the most common interval between the observations including values.
+///
+/// Daily dates such as "13-12-2011" are compared in days, and the period dates of EVDS such as "2011-12" for months,
+/// "2011-Q4" for quarters, "2011-S2" for half years and "2011" for years are compared from the first days of their
+/// periods. A daily series without any value on weekends is a `Business` series. The intervals of a business series
+/// are counted in business days, so weekends are not gaps but holidays are.
+#[cfg(feature = "parse")]
+pub(crate) fn infer_frequency(dataset: &Dataset) -> FrequencyReport {
+
+    let observation_count = dataset.observations.len();
+    let missing_value_count = dataset.observations.iter().filter(|observation| !observation.has_value()).count();
+
+    let mut report = FrequencyReport {
+        frequency: None,
+        observation_count,
+        missing_value_count,
+        gap_count: 0,
+        largest_gap_days: 0,
+    };
+
+    let dates: Option<Vec<CalendarDate>> = dataset.observations
+        .iter()
+        .filter(|observation| observation.has_value())
+        .map(|observation| parse_period_start(&observation.date))
+        .collect();
+
+    let mut dates = match dates {
+        Some(dates) if dates.len() >= 2 => dates,
+        _ => return report,
+    };
+
+    dates.sort();
+    dates.dedup();
+
+    let intervals: Vec<i64> = dates.windows(2).map(|pair| pair[1].to_days() - pair[0].to_days()).collect();
+
+    let frequency = match find_most_common(&intervals) {
+        1 if dates.iter().any(|date| date.is_weekend()) => DataFrequency::Daily,
+        1..=3 => DataFrequency::Business,
+        4..=10 => DataFrequency::WeeklyFriday,
+        11..=20 => DataFrequency::TwiceMonthly,
+        21..=45 => DataFrequency::Monthly,
+        46..=135 => DataFrequency::Quarterly,
+        136..=270 => DataFrequency::SemiAnnual,
+        _ => DataFrequency::Annual,
+    };
+
+    for (pair, interval) in dates.windows(2).zip(intervals.iter()) {
+        let is_gap = match frequency {
+            DataFrequency::Business => count_business_days(pair[0], pair[1]) > 1,
+            _ => *interval > find_longest_interval(&frequency),
+        };
+
+        if !is_gap { continue; }
+
+        report.gap_count += 1;
+        report.largest_gap_days = report.largest_gap_days.max(*interval as usize);
+    }
+
+    report.frequency = Some(frequency);
+
+    report
+}
+
+/// parses the frequency of a series written in the series lists of EVDS, e.g. "İŞ GÜNLÜK", "AYLIK" or "3 AYLIK", and
+/// the English names of the frequencies, e.g. "MONTHLY". None is returned for an unknown frequency.
+pub(crate) fn parse_frequency_name(name: &str) -> Option<DataFrequency> {
+
+    let name: String = name
+        .chars()
+        .map(|character| match character {
+            'İ' | 'ı' => 'I',
+            'Ş' | 'ş' => 'S',
+            'Ğ' | 'ğ' => 'G',
+            'Ü' | 'ü' => 'U',
+            'Ö' | 'ö' => 'O',
+            'Ç' | 'ç' => 'C',
+            character => character.to_ascii_uppercase(),
+        })
+        .collect();
+
+    let contains_any = |parts: &[&str]| parts.iter().any(|part| name.contains(part));
+
+    let frequency = match () {
+        _ if contains_any(&["IS GUN", "ISGUN", "BUSINESS"]) => DataFrequency::Business,
+        _ if contains_any(&["GUNLUK", "DAILY"]) => DataFrequency::Daily,
+        _ if contains_any(&["HAFTA", "WEEK"]) => DataFrequency::WeeklyFriday,
+        _ if contains_any(&["2 KEZ", "IKI KEZ", "TWICE"]) => DataFrequency::TwiceMonthly,
+        _ if contains_any(&["3 AY", "UC AY", "QUARTER"]) => DataFrequency::Quarterly,
+        _ if contains_any(&["6 AY", "ALTI AY", "SEMI"]) => DataFrequency::SemiAnnual,
+        _ if contains_any(&["AYLIK", "MONTH"]) => DataFrequency::Monthly,
+        _ if contains_any(&["YIL", "ANNUAL", "YEAR"]) => DataFrequency::Annual,
+        _ => return None,
+    };
+
+    Some(frequency)
+}
+
+/// estimates the number of the observations of the series of the frequencies in a date range of the given days when 
+/// they are requested in the data frequency. A series keeps its own frequency when the data frequency is None or 
+/// finer than it.
+pub(crate) fn estimate_observations(
+    frequencies: &[DataFrequency], 
+    data_frequency: Option<DataFrequency>, 
+    span_days: i64
+) -> u64 {
+
+    let data_frequency_index = data_frequency.map_or(0, find_frequency_index);
+
+    frequencies
+        .iter()
+        .map(|frequency| {
+            let frequency = FREQUENCIES[find_frequency_index(*frequency).max(data_frequency_index)];
+
+            let days_per_observation = match frequency {
+                DataFrequency::Daily => 1.0,
+                DataFrequency::Business => 7.0 / 5.0,
+                DataFrequency::WeeklyFriday => 7.0,
+                DataFrequency::TwiceMonthly => 365.25 / 24.0,
+                DataFrequency::Monthly => 365.25 / 12.0,
+                DataFrequency::Quarterly => 365.25 / 4.0,
+                DataFrequency::SemiAnnual => 365.25 / 2.0,
+                DataFrequency::Annual => 365.25,
+            };
+
+            (span_days as f64 / days_per_observation).floor() as u64 + 1
+        })
+        .sum()
+}
+
+/// finds the finest data frequency coarser than the finest of the frequencies in which the estimated observations do 
+/// not exceed the limit. None is returned when even annual data exceeds it or the frequencies are already annual.
+pub(crate) fn find_coarser_frequency(
+    frequencies: &[DataFrequency], 
+    span_days: i64, 
+    max_observations: u64
+) -> Option<DataFrequency> {
+
+    let finest_index = frequencies.iter().copied().map(find_frequency_index).min()?;
+
+    FREQUENCIES[finest_index + 1..]
+        .iter()
+        .copied()
+        .find(|frequency| estimate_observations(frequencies, Some(*frequency), span_days) <= max_observations)
+}
+
+fn find_frequency_index(frequency: DataFrequency) -> usize {
+    FREQUENCIES.iter().position(|other| *other == frequency).unwrap_or_default()
+}
+
+/// finds the first day of the period of an EVDS date, e.g. "2011-Q4" starts at "01-10-2011".
+#[cfg(any(feature = "currency", feature = "parse"))]
+pub(crate) fn parse_period_start(date: &str) -> Option<CalendarDate> {
+
+    if let Some(calendar_date) = CalendarDate::parse(date) { return Some(calendar_date); }
+
+    let mut parts = date.trim().split('-');
+
+    let year = parts.next()?.parse::<i64>().ok()?;
+
+    let parse_number = |text: &str, maximum: u32| {
+        text.parse::<u32>().ok().filter(|number| (1..=maximum).contains(number))
+    };
+
+    let month = match parts.next() {
+        None => 1,
+        Some(period) if period.starts_with('Q') => parse_number(&period[1..], 4)? * 3 - 2,
+        Some(period) if period.starts_with('S') => parse_number(&period[1..], 2)? * 6 - 5,
+        Some(period) => parse_number(period, 12)?,
+    };
+
+    if parts.next().is_some() { return None; }
+
+    CalendarDate::from(1, month, year)
+}
+
+/// finds the most common value. The smaller value wins a tie.
+#[cfg(feature = "parse")]
+fn find_most_common(values: &[i64]) -> i64 {
+
+    let mut sorted_values = values.to_vec();
+    sorted_values.sort_unstable();
+
+    let mut most_common = (0, 0);
+    let mut index = 0;
+
+    while index < sorted_values.len() {
+        let value = sorted_values[index];
+        let count = sorted_values[index..].iter().take_while(|other| **other == value).count();
+
+        if count > most_common.1 { most_common = (value, count); }
+
+        index += count;
+    }
+
+    most_common.0
+}
+
+/// finds the longest interval in days that is not a gap for the frequency.
+#[cfg(feature = "parse")]
+fn find_longest_interval(frequency: &DataFrequency) -> i64 {
+    match frequency {
+        DataFrequency::Daily | DataFrequency::Business => 1,
+        DataFrequency::WeeklyFriday => 7,
+        DataFrequency::TwiceMonthly => 16,
+        DataFrequency::Monthly => 31,
+        DataFrequency::Quarterly => 92,
+        DataFrequency::SemiAnnual => 184,
+        DataFrequency::Annual => 366,
+    }
+}
+
+/// counts the business days after the start date until the end date including the end date.
+#[cfg(feature = "parse")]
+fn count_business_days(start: CalendarDate, end: CalendarDate) -> i64 {
+    (start.to_days() + 1..=end.to_days())
+        .filter(|days| !CalendarDate::from_days(*days).is_weekend())
+        .count() as i64
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "parse")]
+    use crate::dataset::Observation;
+
+    #[cfg(feature = "parse")]
+    fn generate_dataset(dates: &[&str]) -> Dataset {
+        Dataset {
+            series_codes: vec!["TP_DK_USD_S".to_string()],
+            observations: dates
+                .iter()
+                .map(|date| Observation::new(date.to_string(), vec![Some(1.0)]))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn should_estimate_observations() {
+        assert_eq!(Some(DataFrequency::Business), parse_frequency_name("İŞ GÜNLÜK"));
+        assert_eq!(Some(DataFrequency::Quarterly), parse_frequency_name("3 AYLIK"));
+        assert_eq!(Some(DataFrequency::Monthly), parse_frequency_name("Monthly"));
+        assert_eq!(Some(DataFrequency::TwiceMonthly), parse_frequency_name("AYDA 2 KEZ"));
+        assert_eq!(None, parse_frequency_name("-"));
+
+        let frequencies = [DataFrequency::Daily, DataFrequency::Monthly];
+
+        assert_eq!(366 + 12, estimate_observations(&frequencies, None, 365));
+        assert_eq!(12 + 12, estimate_observations(&frequencies, Some(DataFrequency::Monthly), 365));
+        assert_eq!(Some(DataFrequency::TwiceMonthly), find_coarser_frequency(&frequencies, 365, 40));
+        assert_eq!(Some(DataFrequency::Annual), find_coarser_frequency(&frequencies, 3652, 20));
+        assert_eq!(None, find_coarser_frequency(&frequencies, 3652, 10));
+    }
+
+    #[test]
+    #[cfg(feature = "parse")]
+    fn should_infer_frequency() {
+        // Monday to Friday with the missing weekend and a holiday on 23-04-2021.
+        let mut business_dataset = generate_dataset(&[
+            "19-04-2021", "20-04-2021", "21-04-2021", "22-04-2021", "26-04-2021", "27-04-2021",
+        ]);
+        business_dataset.observations.push(Observation::new("24-04-2021".to_string(), vec![None]));
+
+        let report = infer_frequency(&business_dataset);
+
+        assert!(matches!(report.frequency, Some(DataFrequency::Business)));
+        assert_eq!((7, 1), (report.observation_count, report.missing_value_count));
+        assert_eq!((1, 4), (report.gap_count, report.largest_gap_days));
+
+        let daily_report = infer_frequency(&generate_dataset(&["16-04-2021", "17-04-2021", "18-04-2021"]));
+        assert!(matches!(daily_report.frequency, Some(DataFrequency::Daily)));
+
+        let monthly_report = infer_frequency(&generate_dataset(&["2021-1", "2021-2", "2021-3", "2021-6"]));
+        assert!(matches!(monthly_report.frequency, Some(DataFrequency::Monthly)));
+        assert_eq!((1, 92), (monthly_report.gap_count, monthly_report.largest_gap_days));
+
+        let quarterly_report = infer_frequency(&generate_dataset(&["2020-Q3", "2020-Q4", "2021-Q1"]));
+        assert!(matches!(quarterly_report.frequency, Some(DataFrequency::Quarterly)));
+
+        let annual_report = infer_frequency(&generate_dataset(&["2019", "2020", "2021"]));
+        assert!(matches!(annual_report.frequency, Some(DataFrequency::Annual)));
+
+        assert!(infer_frequency(&generate_dataset(&["2021"])).frequency.is_none());
+        assert!(infer_frequency(&generate_dataset(&["2021-W1", "2021-W2"])).frequency.is_none());
+    }
+}
@@ -0,0 +1,242 @@
+#[cfg(feature = "parse")]
+use std::sync::{Mutex, MutexGuard};
+
+#[cfg(feature = "parse")]
+use serde_json::{Map, Value};
+
+#[cfg(feature = "parse")]
+use super::{csv_injection, unquote};
+
+#[cfg(feature = "parse")]
+use crate::common::ReturnFormat;
+#[cfg(feature = "parse")]
+use crate::error::ReturnError;
+
+
+/// specifies the language of the descriptive series names written into the headers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum HeaderLanguage {
+    English,
+    Turkish,
+}
+
+/// is the Turkish and English names of a series in EVDS metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SeriesNames {
+    pub(crate) turkish: String,
+    pub(crate) english: String,
+}
+
+impl SeriesNames {
+    /// gives the name in the language. The other name is used when the name in the language is empty.
+    pub(crate) fn get(&self, language: HeaderLanguage) -> &str {
+
+        let (name, other_name) = match language {
+            HeaderLanguage::English => (&self.english, &self.turkish),
+            HeaderLanguage::Turkish => (&self.turkish, &self.english),
+        };
+
+        if name.is_empty() { other_name } else { name }
+    }
+}
+
+
+/// keeps the names of the series received from the series lists of EVDS, so the headers of later requests are
+/// translated without requesting the metadata again. The names are keyed by the series codes whose dots are
+/// underscores as in the headers, e.g. "TP_DK_USD_S".
+#[cfg(feature = "parse")]
+pub(crate) struct SeriesNameCache {
+    entries: Vec<(String, SeriesNames)>,
+}
+
+#[cfg(feature = "parse")]
+impl SeriesNameCache {
+    pub(crate) const fn new() -> SeriesNameCache {
+        SeriesNameCache { entries: Vec::new() }
+    }
+
+    pub(crate) fn get(&self, series_code: &str) -> Option<SeriesNames> {
+        self.entries
+            .iter()
+            .find(|(code, _)| code == series_code)
+            .map(|(_, names)| names.clone())
+    }
+
+    pub(crate) fn insert(&mut self, series_code: String, names: SeriesNames) {
+
+        self.entries.retain(|(code, _)| *code != series_code);
+
+        self.entries.push((series_code, names));
+    }
+}
+
+#[cfg(feature = "parse")]
+static SERIES_NAME_CACHE: Mutex<SeriesNameCache> = Mutex::new(SeriesNameCache::new());
+
+
+/// gives access to the process wide series name cache.
+///
+/// A poisoned lock is recovered because a panic cannot leave an entry half inserted.
+#[cfg(feature = "parse")]
+pub(crate) fn get_series_name_cache() -> MutexGuard<'static, SeriesNameCache> {
+    match SERIES_NAME_CACHE.lock() {
+        Ok(cache) => cache,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+/// parses the codes and the names of the series from a JSON series list response. The codes are written with
+/// underscores as in the headers of the data responses.
+#[cfg(feature = "parse")]
+pub(crate) fn parse_series_names(series_list: &str) -> Vec<(String, SeriesNames)> {
+
+    let parsed_list: Value = match serde_json::from_str(series_list) {
+        Ok(parsed_list) => parsed_list,
+        Err(_) => return Vec::new(),
+    };
+
+    let read_text = |item: &Value, key: &str| item.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+
+    parsed_list
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| {
+            let code = item.get("SERIE_CODE")?.as_str()?.replace('.', "_");
+
+            let names = SeriesNames {
+                turkish: read_text(item, "SERIE_NAME"),
+                english: read_text(item, "SERIE_NAME_ENG"),
+            };
+
+            Some((code, names))
+        })
+        .collect()
+}
+
+/// rewrites the series codes in the headers of the response with the given names, e.g. "TP_DK_USD_S" becomes
+/// "(USD) ABD Doları (Döviz Satış)". The date and metadata columns and the codes without a name are kept.
+///
+/// A CSV header cell including a comma or a quote is quoted, and a name that spreadsheet applications would run as a
+/// formula, e.g. "=...", is neutralized unless the CSV injection protection is disabled. JSON keys are renamed in each
+/// item.
+///
+/// # Error
+///
+/// This function returns `UnsupportedReturnFormat` error for XML since the names cannot be element names, and
+/// `UnparsableResponse` error when a JSON response does not include an `items` array.
+#[cfg(feature = "parse")]
+pub(crate) fn translate_headers(
+    response: &str,
+    return_format: &ReturnFormat,
+    names: &[(String, String)]
+) -> Result<String, ReturnError> {
+
+    let find_name = |code: &str| names.iter().find(|(name_code, _)| name_code == code).map(|(_, name)| name);
+
+    match return_format {
+        ReturnFormat::Csv => {
+            let is_protected = csv_injection::is_protection_enabled();
+
+            let (header, rest) = response.split_once('\n').unwrap_or((response, ""));
+
+            let translated_header: Vec<String> = header
+                .trim_end_matches('\r')
+                .split(',')
+                .map(|cell| match find_name(&unquote(cell)) {
+                    Some(name) => quote_csv_cell(&csv_injection::protect_cell(name, is_protected)),
+                    None => cell.to_string(),
+                })
+                .collect();
+
+            if response.contains('\n') {
+                Ok(format!("{}\n{}", translated_header.join(","), rest))
+            } else {
+                Ok(translated_header.join(","))
+            }
+        },
+        ReturnFormat::Json | ReturnFormat::Auto => {
+            let mut parsed_response: Value = match serde_json::from_str(response) {
+                Ok(parsed_response) => parsed_response,
+                Err(_) => return Err(ReturnError::UnparsableResponse),
+            };
+
+            let items = match parsed_response.get_mut("items").and_then(Value::as_array_mut) {
+                Some(items) => items,
+                None => return Err(ReturnError::UnparsableResponse),
+            };
+
+            for item in items.iter_mut() {
+                if let Some(item) = item.as_object_mut() {
+                    let translated_item: Map<String, Value> = std::mem::take(item)
+                        .into_iter()
+                        .map(|(key, value)| (find_name(&key).cloned().unwrap_or(key), value))
+                        .collect();
+
+                    *item = translated_item;
+                }
+            }
+
+            Ok(parsed_response.to_string())
+        },
+        ReturnFormat::Xml => Err(ReturnError::UnsupportedReturnFormat),
+    }
+}
+
+#[cfg(feature = "parse")]
+fn quote_csv_cell(cell: &str) -> String {
+
+    if !cell.contains(',') && !cell.contains('"') { return cell.to_string(); }
+
+    format!("\"{}\"", cell.replace('"', "\"\""))
+}
+
+
+#[cfg(all(test, feature = "parse"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_translate_headers() {
+        let series_list = r#"[
+            {
+                "SERIE_CODE":"TP.DK.USD.S",
+                "SERIE_NAME":"(USD) ABD Doları (Döviz Satış)",
+                "SERIE_NAME_ENG":"(USD) US Dollar, Selling"
+            },
+            {"SERIE_CODE":"TP.DK.EUR.S","SERIE_NAME":"(EUR) Euro (Döviz Satış)"}
+        ]"#;
+
+        let series_names = parse_series_names(series_list);
+
+        assert_eq!("TP_DK_USD_S", series_names[0].0);
+        assert_eq!("(EUR) Euro (Döviz Satış)", series_names[1].1.get(HeaderLanguage::English));
+
+        let names: Vec<(String, String)> = series_names
+            .iter()
+            .map(|(code, names)| (code.clone(), names.get(HeaderLanguage::English).to_string()))
+            .collect();
+
+        assert_eq!(
+            "Tarih,\"(USD) US Dollar, Selling\",TP_DK_GBP_S\n13-12-2011,1.8673,2.9138",
+            translate_headers("Tarih,TP_DK_USD_S,TP_DK_GBP_S\n13-12-2011,1.8673,2.9138", &ReturnFormat::Csv, &names)
+                .unwrap()
+        );
+
+        let translated_json = translate_headers(
+            r#"{"totalCount":1,"items":[{"Tarih":"13-12-2011","TP_DK_EUR_S":"2.4361"}]}"#,
+            &ReturnFormat::Json,
+            &names
+        ).unwrap();
+        let translated_json: Value = serde_json::from_str(&translated_json).unwrap();
+
+        assert_eq!("2.4361", translated_json["items"][0]["(EUR) Euro (Döviz Satış)"]);
+        assert_eq!("13-12-2011", translated_json["items"][0]["Tarih"]);
+        assert_eq!(
+            Err(ReturnError::UnsupportedReturnFormat),
+            translate_headers("<document/>", &ReturnFormat::Xml, &names)
+        );
+    }
+}
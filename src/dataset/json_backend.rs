@@ -0,0 +1,77 @@
+use serde_json::Value;
+
+use crate::error::ReturnError;
+#[cfg(feature = "parse")]
+use crate::evds_c::dataset_entities::TcmbEvdsJsonBackend;
+#[cfg(feature = "parse")]
+use crate::evds_c::settings::get_settings;
+
+
+/// tells whether the library is built with the `simd_json` feature, namely whether
+/// [`TcmbEvdsJsonBackend::SimdJsonBackend`] can be chosen.
+#[cfg(feature = "parse")]
+pub(crate) const IS_SIMD_JSON_AVAILABLE: bool = cfg!(feature = "simd_json");
+
+
+/// parses a JSON response via the backend chosen via
+/// [`tcmb_evds_c_set_json_backend`](crate::tcmb_evds_c_set_json_backend).
+///
+/// # Error
+///
+/// This function returns `UnparsableResponse` error when the response is not a valid JSON text.
+#[cfg(feature = "parse")]
+pub(crate) fn parse_json(response: &str) -> Result<Value, ReturnError> {
+
+    let json_backend = get_settings().json_backend;
+
+    parse_json_with(response, json_backend)
+}
+
+/// parses a JSON response via serde_json, since the backend cannot be chosen without the `parse` feature.
+///
+/// # Error
+///
+/// This function returns `UnparsableResponse` error when the response is not a valid JSON text.
+#[cfg(not(feature = "parse"))]
+pub(crate) fn parse_json(response: &str) -> Result<Value, ReturnError> {
+    serde_json::from_str(response).map_err(|_| ReturnError::UnparsableResponse)
+}
+
+/// parses a JSON response via the given backend. serde_json is used when the library is built without the backend.
+#[cfg(feature = "parse")]
+pub(crate) fn parse_json_with(response: &str, json_backend: TcmbEvdsJsonBackend) -> Result<Value, ReturnError> {
+    match json_backend {
+        #[cfg(feature = "simd_json")]
+        TcmbEvdsJsonBackend::SimdJsonBackend => parse_with_simd_json(response),
+        _ => serde_json::from_str(response).map_err(|_| ReturnError::UnparsableResponse),
+    }
+}
+
+/// parses the response via simd-json, which parses its input in place, so a copy of the response is parsed.
+#[cfg(feature = "simd_json")]
+fn parse_with_simd_json(response: &str) -> Result<Value, ReturnError> {
+
+    let mut response = response.as_bytes().to_vec();
+
+    simd_json::serde::from_slice(&mut response).map_err(|_| ReturnError::UnparsableResponse)
+}
+
+
+#[cfg(all(test, feature = "parse"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_json_with_each_backend() {
+        let response = r#"{"totalCount":1,"items":[{"Tarih":"13-12-2011","TP_DK_USD_S":"1.8673","UNIXTIME":null}]}"#;
+
+        let parsed_response = parse_json_with(response, TcmbEvdsJsonBackend::SerdeJsonBackend).unwrap();
+
+        assert_eq!("1.8673", parsed_response["items"][0]["TP_DK_USD_S"]);
+        assert_eq!(parsed_response, parse_json_with(response, TcmbEvdsJsonBackend::SimdJsonBackend).unwrap());
+
+        for json_backend in [TcmbEvdsJsonBackend::SerdeJsonBackend, TcmbEvdsJsonBackend::SimdJsonBackend] {
+            assert!(matches!(parse_json_with("Error", json_backend), Err(ReturnError::UnparsableResponse)));
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::ops::Range;
+
+use serde_json::{Map, Value};
+
+use super::{Dataset, collect_series_codes, parse_item, parse_value};
+use crate::error::ReturnError;
+
+
+/// is an EVDS JSON response whose items are indexed once and parsed only when they are read.
+///
+/// Indexing only finds where each item of the `items` array starts and ends without building any JSON value, so
+/// reading a single value, e.g. the latest value of a series, out of a large response parses a single item rather
+/// than the whole response. The items are validated as they are parsed.
+#[derive(Debug)]
+pub(crate) struct LazyDataset {
+    response: String,
+    /// are the byte ranges of the items in the response.
+    items: Vec<Range<usize>>,
+}
+
+impl LazyDataset {
+    /// indexes the items of an EVDS JSON response having the `{"totalCount": .., "items": [..]}` layout.
+    ///
+    /// # Error
+    ///
+    /// This function returns `UnparsableResponse` error when the response is not a JSON object including an `items`
+    /// array of objects.
+    pub(crate) fn index(response: String) -> Result<LazyDataset, ReturnError> {
+        let _span = tracing::info_span!("index").entered();
+
+        match index_items(response.as_bytes()) {
+            Some(items) => Ok(LazyDataset { response, items }),
+            None => Err(ReturnError::UnparsableResponse),
+        }
+    }
+
+    /// gives the number of the items, namely observations, including the ones without values.
+    pub(crate) fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// gives the approximate bytes of the response and its index.
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        self.response.capacity() + self.items.capacity() * std::mem::size_of::<Range<usize>>()
+    }
+
+    /// gives the value of the series at the given row, which is None when the row is out of range, the value is
+    /// missing or the item could not be parsed. The series code is written as in the responses, e.g. "TP_DK_USD_S".
+    pub(crate) fn value(&self, row: usize, series_code: &str) -> Option<f64> {
+        parse_value(self.read_item(row).ok()?.get(series_code))
+    }
+
+    /// gives the date and the value of the last observation having a value of the series by parsing the items from
+    /// the end of the response until the value is found. None is returned when the series has no value.
+    pub(crate) fn latest_value(&self, series_code: &str) -> Option<(String, f64)> {
+        (0..self.len()).rev().find_map(|row| {
+            let item = self.read_item(row).ok()?;
+            let observation = parse_item(&item, &[series_code.to_string()]).ok()?;
+
+            observation.values[0].map(|value| (observation.date, value))
+        })
+    }
+
+    /// parses the given rows into a dataset having only the given series, or the series found in the rows when no
+    /// series is given. The rows out of range are skipped.
+    ///
+    /// # Error
+    ///
+    /// This function returns `UnparsableResponse` error when one of the rows is not a valid JSON object or does not
+    /// have a date.
+    pub(crate) fn materialize(&self, series_codes: &[String], rows: Range<usize>) -> Result<Dataset, ReturnError> {
+        let _span = tracing::info_span!("parse").entered();
+
+        let rows = rows.start.min(self.len())..rows.end.min(self.len());
+
+        let items = rows.map(|row| self.read_item(row)).collect::<Result<Vec<Map<String, Value>>, ReturnError>>()?;
+
+        let series_codes = match series_codes.is_empty() {
+            true => collect_series_codes(&items.iter().collect::<Vec<&Map<String, Value>>>()),
+            false => series_codes.to_vec(),
+        };
+
+        let observations = items
+            .iter()
+            .map(|item| parse_item(item, &series_codes))
+            .collect::<Result<_, ReturnError>>()?;
+
+        Ok(Dataset { series_codes, observations })
+    }
+
+    /// parses the item at the given row.
+    fn read_item(&self, row: usize) -> Result<Map<String, Value>, ReturnError> {
+
+        let item = self.items.get(row).ok_or(ReturnError::UnparsableResponse)?;
+
+        serde_json::from_str(&self.response[item.clone()]).map_err(|_| ReturnError::UnparsableResponse)
+    }
+}
+
+/// finds the byte ranges of the objects in the `items` array of the response without parsing them.
+fn index_items(bytes: &[u8]) -> Option<Vec<Range<usize>>> {
+
+    let mut items = Vec::new();
+    let mut position = skip_whitespace(bytes, find_items_array(bytes)? + 1);
+
+    if bytes.get(position) == Some(&b']') { return Some(items); }
+
+    loop {
+        if bytes.get(position) != Some(&b'{') { return None; }
+
+        let item_end = skip_value(bytes, position)?;
+
+        items.push(position..item_end);
+        position = skip_whitespace(bytes, item_end);
+
+        match bytes.get(position)? {
+            b',' => position = skip_whitespace(bytes, position + 1),
+            b']' => return Some(items),
+            _ => return None,
+        }
+    }
+}
+
+/// finds the position of the opening bracket of the `items` array among the members of the top level object.
+fn find_items_array(bytes: &[u8]) -> Option<usize> {
+
+    let mut position = skip_whitespace(bytes, 0);
+
+    if bytes.get(position) != Some(&b'{') { return None; }
+
+    loop {
+        position = skip_whitespace(bytes, position + 1);
+
+        if bytes.get(position) != Some(&b'"') { return None; }
+
+        let key_end = skip_string(bytes, position)?;
+        let key = &bytes[position + 1..key_end - 1];
+
+        position = skip_whitespace(bytes, key_end);
+
+        if bytes.get(position) != Some(&b':') { return None; }
+
+        position = skip_whitespace(bytes, position + 1);
+
+        if key == b"items" { return (bytes.get(position) == Some(&b'[')).then_some(position); }
+
+        position = skip_whitespace(bytes, skip_value(bytes, position)?);
+
+        if bytes.get(position) != Some(&b',') { return None; }
+    }
+}
+
+/// gives the position after the JSON value starting at the given position.
+fn skip_value(bytes: &[u8], start: usize) -> Option<usize> {
+    match bytes.get(start)? {
+        b'"' => skip_string(bytes, start),
+        b'{' | b'[' => skip_container(bytes, start),
+        _ => {
+            let length = bytes[start..].iter().position(|byte| matches!(byte, b',' | b'}' | b']'));
+
+            Some(start + length.unwrap_or(bytes.len() - start))
+        },
+    }
+}
+
+/// gives the position after the closing quote of the string starting at the given position.
+fn skip_string(bytes: &[u8], start: usize) -> Option<usize> {
+
+    let mut position = start + 1;
+
+    while let Some(byte) = bytes.get(position) {
+        match byte {
+            b'\\' => position += 2,
+            b'"' => return Some(position + 1),
+            _ => position += 1,
+        }
+    }
+
+    None
+}
+
+/// gives the position after the closing bracket of the object or array starting at the given position.
+fn skip_container(bytes: &[u8], start: usize) -> Option<usize> {
+
+    let mut depth = 0_usize;
+    let mut position = start;
+
+    while let Some(byte) = bytes.get(position) {
+        match byte {
+            b'"' => {
+                position = skip_string(bytes, position)?;
+                continue;
+            },
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                depth -= 1;
+
+                if depth == 0 { return Some(position + 1); }
+            },
+            _ => {},
+        }
+
+        position += 1;
+    }
+
+    None
+}
+
+/// gives the position of the first byte other than whitespaces from the given position.
+fn skip_whitespace(bytes: &[u8], start: usize) -> usize {
+    start + bytes.get(start..).map_or(0, |rest| rest.iter().take_while(|byte| byte.is_ascii_whitespace()).count())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_values_without_parsing_whole_response() {
+        let response = r#"{"totalCount":3, "items" : [
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8534","TP_DK_EUR_S":"2.4361","UNIXTIME":{"$numberLong":"1"}},
+            {"Tarih":"13-12-2011","TP_DK_USD_S":"1.8673","TP_DK_EUR_S":"a \"]}"},
+            {"Tarih":"14-12-2011","TP_DK_USD_S":null}
+        ]}"#;
+
+        let lazy_dataset = LazyDataset::index(response.to_string()).unwrap();
+
+        assert_eq!(3, lazy_dataset.len());
+        assert_eq!(Some(1.8673), lazy_dataset.value(1, "TP_DK_USD_S"));
+        assert_eq!(None, lazy_dataset.value(3, "TP_DK_USD_S"));
+        assert_eq!(Some(("13-12-2011".to_string(), 1.8673)), lazy_dataset.latest_value("TP_DK_USD_S"));
+        assert_eq!(Some(("12-12-2011".to_string(), 2.4361)), lazy_dataset.latest_value("TP_DK_EUR_S"));
+
+        let dataset = Dataset::from_json(response).unwrap();
+
+        assert_eq!(dataset, lazy_dataset.materialize(&[], 0..10).unwrap());
+
+        let usd_dataset = lazy_dataset.materialize(&["TP_DK_USD_S".to_string()], 1..2).unwrap();
+
+        assert_eq!(vec![Some(1.8673)], usd_dataset.observations[0].values);
+        assert_eq!("13-12-2011", usd_dataset.observations[0].date);
+
+        assert_eq!(0, LazyDataset::index(r#"{"items":[]}"#.to_string()).unwrap().len());
+        assert!(LazyDataset::index(r#"{"totalCount":1}"#.to_string()).is_err());
+        assert!(LazyDataset::index(r#"{"items":[{"Tarih":"13-12-2011"}"#.to_string()).is_err());
+        assert!(LazyDataset::index("Tarih,TP_DK_USD_S".to_string()).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::{Dataset, Observation};
+#[cfg(feature = "parse")]
+use super::frequency::parse_period_start;
+use crate::common::ReturnFormat;
+use crate::error::ReturnError;
+
+
+/// merges the responses of the same request made for different date ranges into a single response.
+///
+/// The responses are merged in the given order without sorting and the layout of the first response is kept.
+///
+/// > **Csv:** The header of the first response is kept and the headers of the rest are dropped. <br />
+/// > **Json:** Items are appended and `totalCount` is updated. <br />
+/// > **Xml:** `items` elements are appended and `totalCount` is updated.
+///
+/// # Error
+///
+/// This function returns `UnparsableResponse` error when a Json or Xml response does not have the expected layout.
+pub(crate) fn merge_responses(responses: Vec<String>, return_format: &ReturnFormat) -> Result<String, ReturnError> {
+
+    if responses.len() == 1 { return Ok(responses.into_iter().next().unwrap()); }
+
+    match return_format {
+        ReturnFormat::Csv => Ok(merge_csv_responses(responses)),
+        ReturnFormat::Json | ReturnFormat::Auto => merge_json_responses(responses),
+        ReturnFormat::Xml => merge_xml_responses(responses),
+    }
+}
+
+fn merge_csv_responses(responses: Vec<String>) -> String {
+
+    let mut merged_response = String::new();
+
+    for (index, response) in responses.iter().enumerate() {
+        let rows = match (index, response.find('\n')) {
+            (0, _) => response.as_str(),
+            (_, Some(header_end)) => &response[header_end + 1..],
+            (_, None) => "",
+        };
+
+        if rows.is_empty() { continue; }
+
+        if !merged_response.is_empty() && !merged_response.ends_with('\n') { merged_response.push('\n'); }
+
+        merged_response.push_str(rows);
+    }
+
+    merged_response
+}
+
+fn merge_json_responses(responses: Vec<String>) -> Result<String, ReturnError> {
+
+    let mut merged_items = Vec::new();
+    let mut merged_document = None;
+
+    for response in responses {
+        let mut document: Value = match serde_json::from_str(&response) {
+            Ok(document) => document,
+            Err(_) => return Err(ReturnError::UnparsableResponse),
+        };
+
+        match document.get_mut("items").map(Value::take) {
+            Some(Value::Array(items)) => merged_items.extend(items),
+            Some(Value::Null) | None => (),
+            Some(_) => return Err(ReturnError::UnparsableResponse),
+        }
+
+        if merged_document.is_none() { merged_document = Some(document); }
+    }
+
+    let mut merged_document = match merged_document {
+        Some(Value::Object(document)) => document,
+        _ => return Err(ReturnError::UnparsableResponse),
+    };
+
+    merged_document.insert("totalCount".to_string(), Value::from(merged_items.len()));
+    merged_document.insert("items".to_string(), Value::Array(merged_items));
+
+    Ok(Value::Object(merged_document).to_string())
+}
+
+fn merge_xml_responses(responses: Vec<String>) -> Result<String, ReturnError> {
+
+    let mut merged_items = String::new();
+    let mut item_count = 0;
+
+    for response in &responses {
+        if let Some((start, end)) = find_xml_items(response) {
+            merged_items.push_str(&response[start..end]);
+            item_count += response[start..end].matches("<items>").count();
+        }
+    }
+
+    // the first response including items is used as the frame of the merged response.
+    let frame = responses.iter().find(|response| find_xml_items(response).is_some());
+
+    let frame = match frame {
+        Some(frame) => frame,
+        None => return Ok(responses.into_iter().next().unwrap()),
+    };
+
+    let (start, end) = find_xml_items(frame).unwrap();
+
+    let mut merged_response = format!("{}{}{}", &frame[..start], merged_items, &frame[end..]);
+
+    let total_count_start = merged_response.find("<totalCount>");
+    let total_count_end = merged_response.find("</totalCount>");
+
+    if let (Some(total_count_start), Some(total_count_end)) = (total_count_start, total_count_end) {
+        let value_start = total_count_start + "<totalCount>".len();
+
+        if value_start > total_count_end { return Err(ReturnError::UnparsableResponse); }
+
+        merged_response.replace_range(value_start..total_count_end, &item_count.to_string());
+    }
+
+    Ok(merged_response)
+}
+
+/// joins the series of the datasets requested for the same dates into a single dataset, e.g. the groups of a series 
+/// list split into several requests.
+///
+/// The observations are matched by their dates and placed in the order their dates are first seen. A series missing
+/// at a date of another series has a missing value there.
+pub(crate) fn join_series(datasets: Vec<Dataset>) -> Dataset {
+
+    let mut joined_dataset = Dataset::default();
+    let mut rows: HashMap<String, usize> = HashMap::new();
+
+    for dataset in datasets {
+        let column_start = joined_dataset.series_codes.len();
+        let column_count = column_start + dataset.series_codes.len();
+
+        joined_dataset.series_codes.extend(dataset.series_codes);
+
+        for observation in dataset.observations {
+            let row = *rows.entry(observation.date.clone()).or_insert_with(|| {
+                joined_dataset.observations.push(Observation { values: Vec::new(), ..observation.clone() });
+
+                joined_dataset.observations.len() - 1
+            });
+
+            let joined_observation = &mut joined_dataset.observations[row];
+
+            joined_observation.values.resize(column_start, None);
+            joined_observation.values.extend(observation.values);
+        }
+
+        for joined_observation in joined_dataset.observations.iter_mut() {
+            joined_observation.values.resize(column_count, None);
+        }
+    }
+
+    joined_dataset
+}
+
+/// merges the datasets fetched for different, possibly overlapping, date ranges into a single dataset whose dates are 
+/// unique and sorted.
+///
+/// The series are the union of the series of the datasets in the order they are first seen. When a date is in more 
+/// than one dataset, its observations are combined and a value of a later dataset replaces the value of an earlier 
+/// one unless it is missing, so the latest revision wins and overlapping windows are not counted twice. The dates are
+/// sorted chronologically, and the unrecognized dates are placed after them in their order.
+#[cfg(feature = "parse")]
+pub(crate) fn merge_datasets(datasets: &[&Dataset]) -> Dataset {
+
+    let mut merged_dataset = Dataset::default();
+
+    for dataset in datasets {
+        for series_code in &dataset.series_codes {
+            if merged_dataset.series_codes.contains(series_code) { continue; }
+
+            merged_dataset.series_codes.push(series_code.clone());
+        }
+    }
+
+    let series_count = merged_dataset.series_codes.len();
+    let mut rows: HashMap<&str, usize> = HashMap::new();
+
+    for dataset in datasets {
+        let columns: Vec<usize> = dataset.series_codes
+            .iter()
+            .map(|series_code| merged_dataset.series_codes.iter().position(|code| code == series_code).unwrap())
+            .collect();
+
+        for observation in &dataset.observations {
+            let row = *rows.entry(observation.date.as_str()).or_insert_with(|| {
+                merged_dataset.observations.push(Observation {
+                    values: vec![None; series_count],
+                    ..observation.clone()
+                });
+
+                merged_dataset.observations.len() - 1
+            });
+
+            let merged_observation = &mut merged_dataset.observations[row];
+
+            for (value, column) in observation.values.iter().zip(columns.iter()) {
+                if value.is_some() { merged_observation.values[*column] = *value; }
+            }
+
+            if observation.unix_time.is_some() { merged_observation.unix_time = observation.unix_time; }
+        }
+    }
+
+    merged_dataset.observations.sort_by_key(|observation| {
+        let period_start = parse_period_start(&observation.date);
+
+        (period_start.is_none(), period_start)
+    });
+
+    merged_dataset
+}
+
+/// finds the part of a Xml response from the first `items` element to the end of the last one.
+fn find_xml_items(response: &str) -> Option<(usize, usize)> {
+
+    let start = response.find("<items>")?;
+    let end = response.rfind("</items>")? + "</items>".len();
+
+    if end <= start { return None; }
+
+    Some((start, end))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_merge_responses() {
+        let csv_responses = vec![
+            "Tarih,TP_DK_USD_S\n02-01-2020,5.9281\n".to_string(),
+            "Tarih,TP_DK_USD_S\n04-01-2021,7.3405".to_string(),
+        ];
+
+        assert_eq!(
+            "Tarih,TP_DK_USD_S\n02-01-2020,5.9281\n04-01-2021,7.3405",
+            merge_responses(csv_responses, &ReturnFormat::Csv).unwrap()
+        );
+
+        let json_responses = vec![
+            r#"{"totalCount":1,"items":[{"Tarih":"02-01-2020","TP_DK_USD_S":"5.9281"}]}"#.to_string(),
+            r#"{"totalCount":1,"items":[{"Tarih":"04-01-2021","TP_DK_USD_S":"7.3405"}]}"#.to_string(),
+        ];
+
+        let merged_json = merge_responses(json_responses, &ReturnFormat::Json).unwrap();
+        let merged_json: Value = serde_json::from_str(&merged_json).unwrap();
+
+        assert_eq!(2, merged_json["totalCount"]);
+        assert_eq!("04-01-2021", merged_json["items"][1]["Tarih"]);
+
+        let xml_responses = vec![
+            "<document><totalCount>1</totalCount><items><Tarih>02-01-2020</Tarih></items></document>".to_string(),
+            "<document><totalCount>1</totalCount><items><Tarih>04-01-2021</Tarih></items></document>".to_string(),
+        ];
+
+        assert_eq!(
+            "<document><totalCount>2</totalCount><items><Tarih>02-01-2020</Tarih></items>\
+            <items><Tarih>04-01-2021</Tarih></items></document>",
+            merge_responses(xml_responses, &ReturnFormat::Xml).unwrap()
+        );
+
+        assert!(merge_responses(vec!["{}".to_string(), "<>".to_string()], &ReturnFormat::Json).is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "parse", feature = "export"))]
+    fn should_join_series() {
+        let usd_dataset = Dataset::from_csv("Tarih,TP_DK_USD_S\n02-01-2020,5.9281\n03-01-2020,5.9712").unwrap();
+        let eur_dataset = Dataset::from_csv("Tarih,TP_DK_EUR_S\n03-01-2020,6.6545\n06-01-2020,6.6821").unwrap();
+
+        let joined_dataset = join_series(vec![usd_dataset, eur_dataset]);
+
+        assert_eq!(
+            "Tarih,TP_DK_USD_S,TP_DK_EUR_S\n02-01-2020,5.9281,\n03-01-2020,5.9712,6.6545\n06-01-2020,,6.6821",
+            joined_dataset.to_csv().trim_end()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "parse")]
+    fn should_merge_datasets() {
+        let later_dataset = Dataset::from_csv("Tarih,TP_DK_USD_S\n03-01-2020,5.9800\n06-01-2020,5.9846").unwrap();
+        let earlier_dataset = Dataset::from_csv("Tarih,TP_DK_USD_S\n02-01-2020,5.9281\n03-01-2020,5.9712").unwrap();
+        let eur_dataset = Dataset::from_csv("Tarih,TP_DK_EUR_S\n03-01-2020,6.6545").unwrap();
+
+        let merged_dataset = merge_datasets(&[&earlier_dataset, &later_dataset, &eur_dataset]);
+
+        assert_eq!(
+            "Tarih,TP_DK_USD_S,TP_DK_EUR_S\n02-01-2020,5.9281,\n03-01-2020,5.98,6.6545\n06-01-2020,5.9846,",
+            merged_dataset.to_csv().trim_end()
+        );
+    }
+}
@@ -0,0 +1,604 @@
+#[cfg(feature = "cache")]
+pub(crate) mod cache;
+pub(crate) mod catalog;
+#[cfg(feature = "catalog")]
+pub(crate) mod category_tree;
+pub(crate) mod checksum;
+pub(crate) mod csv_injection;
+#[cfg(feature = "parse")]
+pub(crate) mod deflate;
+#[cfg(feature = "parse")]
+pub(crate) mod diff;
+#[cfg(feature = "parse")]
+pub(crate) mod expression;
+pub(crate) mod frequency;
+#[cfg(any(feature = "catalog", feature = "parse"))]
+pub(crate) mod header;
+pub(crate) mod json_backend;
+#[cfg(feature = "parse")]
+pub(crate) mod lazy;
+pub(crate) mod merge;
+#[cfg(feature = "parse")]
+pub(crate) mod outlier;
+pub(crate) mod precision;
+#[cfg(feature = "parse")]
+pub(crate) mod reshape;
+pub(crate) mod schema;
+#[cfg(feature = "catalog")]
+pub(crate) mod series_manifest;
+#[cfg(feature = "seasonal")]
+pub(crate) mod seasonal;
+pub(crate) mod stats;
+pub(crate) mod sniff;
+#[cfg(feature = "cache")]
+pub(crate) mod store;
+#[cfg(feature = "parse")]
+pub(crate) mod unit;
+#[cfg(feature = "parse")]
+pub(crate) mod virtual_series;
+
+use serde_json::{Map, Value};
+
+use crate::common::ReturnFormat;
+use crate::date::calendar::CalendarDate;
+use crate::error::ReturnError;
+
+
+/// is the key of the date column in EVDS JSON responses.
+pub(crate) const DATE_KEY: &str = "Tarih";
+
+/// is the key of the start of the date in epoch seconds in EVDS responses.
+const UNIX_TIME_KEY: &str = "UNIXTIME";
+
+/// are the keys accompanying the observations in EVDS JSON responses that do not belong to any series.
+const METADATA_KEYS: [&str; 3] = [DATE_KEY, UNIX_TIME_KEY, "YEARWEEK"];
+
+
+/// is a row of a parsed EVDS response that contains a date and the values of each series at that date.
+///
+/// Values are placed in the same order with [`Dataset::series_codes`]. A missing value, which EVDS returns as `null`
+/// for holidays or unpublished periods, is `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Observation {
+    pub(crate) date: String,
+    pub(crate) values: Vec<Option<f64>>,
+    /// is the start of the date on Turkish time in seconds since 01-01-1970 00:00 UTC, namely `UNIXTIME` of EVDS. It is
+    /// None when the response does not include it and the date is not a daily date such as "13-12-2011".
+    pub(crate) unix_time: Option<i64>,
+}
+
+impl Observation {
+    /// creates the observation whose unix time is calculated from its date.
+    pub(crate) fn new(date: String, values: Vec<Option<f64>>) -> Observation {
+
+        let unix_time = CalendarDate::parse(&date).map(CalendarDate::to_epoch_seconds);
+
+        Observation { date, values, unix_time }
+    }
+
+    /// replaces the calculated unix time with the one received from EVDS when it exists.
+    fn with_unix_time(mut self, unix_time: Option<i64>) -> Observation {
+
+        if unix_time.is_some() { self.unix_time = unix_time; }
+
+        self
+    }
+
+    /// checks the observation whether includes at least one value or not.
+    #[cfg(feature = "parse")]
+    pub(crate) fn has_value(&self) -> bool {
+        self.values.iter().any(Option::is_some)
+    }
+}
+
+
+/// is the parsed version of an EVDS data response.
+///
+/// The dataset is built from JSON responses having the `{"totalCount": .., "items": [..]}` layout. Each item becomes an
+/// [`Observation`] and each key other than the date and metadata keys becomes a series column.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Dataset {
+    pub(crate) series_codes: Vec<String>,
+    pub(crate) observations: Vec<Observation>,
+}
+
+impl Dataset {
+    /// parses an EVDS JSON response into a dataset.
+    ///
+    /// # Error
+    ///
+    /// This function returns `UnparsableResponse` error when the response is not a JSON object including an `items`
+    /// array or an item does not have a date.
+    pub(crate) fn from_json(response: &str) -> Result<Dataset, ReturnError> {
+        let _span = tracing::info_span!("parse").entered();
+
+        let parsed_response = json_backend::parse_json(response)?;
+
+        let items = match parsed_response.get("items").and_then(Value::as_array) {
+            Some(items) => items,
+            None => return Err(ReturnError::UnparsableResponse),
+        };
+
+        let items = match items.iter().map(Value::as_object).collect::<Option<Vec<&Map<String, Value>>>>() {
+            Some(items) => items,
+            None => return Err(ReturnError::UnparsableResponse),
+        };
+
+        let series_codes = collect_series_codes(&items);
+
+        let observations = items
+            .iter()
+            .map(|item| parse_item(item, &series_codes))
+            .collect::<Result<Vec<Observation>, ReturnError>>()?;
+
+        Ok(Dataset { series_codes, observations })
+    }
+
+    /// parses an EVDS CSV response, e.g. "Tarih,TP_DK_USD_S\n13-12-2011,1.8673", into a dataset.
+    ///
+    /// # Error
+    ///
+    /// This function returns `UnparsableResponse` error when the header does not start with the date column.
+    pub(crate) fn from_csv(response: &str) -> Result<Dataset, ReturnError> {
+        let _span = tracing::info_span!("parse").entered();
+
+        let mut lines = response.lines().filter(|line| !line.trim().is_empty());
+
+        let header: Vec<String> = match lines.next() {
+            Some(header) => header.split(',').map(unquote).collect(),
+            None => return Err(ReturnError::UnparsableResponse),
+        };
+
+        if header.first().map(String::as_str) != Some(DATE_KEY) { return Err(ReturnError::UnparsableResponse); }
+
+        let unix_time_column = header.iter().position(|code| code == UNIX_TIME_KEY);
+
+        // the columns of the series are kept with their places in the header to skip the metadata columns.
+        let series_columns: Vec<(usize, String)> = header
+            .into_iter()
+            .enumerate()
+            .filter(|(_, code)| !METADATA_KEYS.contains(&code.as_str()))
+            .collect();
+
+        let mut observations = Vec::new();
+
+        for line in lines {
+            let cells: Vec<String> = line.split(',').map(unquote).collect();
+
+            let values = series_columns
+                .iter()
+                .map(|(column, _)| cells.get(*column).and_then(|cell| cell.parse::<f64>().ok()))
+                .collect();
+
+            let unix_time = unix_time_column
+                .and_then(|column| cells.get(column))
+                .and_then(|cell| cell.parse::<i64>().ok());
+
+            observations.push(Observation::new(cells[0].clone(), values).with_unix_time(unix_time));
+        }
+
+        let series_codes = series_columns.into_iter().map(|(_, code)| code).collect();
+
+        Ok(Dataset { series_codes, observations })
+    }
+
+    /// parses an EVDS XML response having `<items>` elements that include the date and series elements into a dataset.
+    ///
+    /// # Error
+    ///
+    /// This function returns `UnparsableResponse` error when an element is not closed or an item does not have a date.
+    pub(crate) fn from_xml(response: &str) -> Result<Dataset, ReturnError> {
+        let _span = tracing::info_span!("parse").entered();
+
+        let mut series_codes: Vec<String> = Vec::new();
+        let mut items = Vec::new();
+
+        let mut rest = response;
+
+        while let Some(items_start) = rest.find("<items>") {
+            let item_text = &rest[items_start + "<items>".len()..];
+
+            let items_end = match item_text.find("</items>") {
+                Some(items_end) => items_end,
+                None => return Err(ReturnError::UnparsableResponse),
+            };
+
+            let elements = parse_xml_elements(&item_text[..items_end])?;
+
+            for (name, _) in &elements {
+                if METADATA_KEYS.contains(&name.as_str()) || series_codes.contains(name) { continue; }
+
+                series_codes.push(name.to_string());
+            }
+
+            items.push(elements);
+            rest = &item_text[items_end + "</items>".len()..];
+        }
+
+        let mut observations = Vec::with_capacity(items.len());
+
+        for elements in items {
+            let date = match elements.iter().find(|(name, _)| name == DATE_KEY) {
+                Some((_, date)) => date.to_string(),
+                None => return Err(ReturnError::UnparsableResponse),
+            };
+
+            let values = series_codes
+                .iter()
+                .map(|code| {
+                    elements
+                        .iter()
+                        .find(|(name, _)| name == code)
+                        .and_then(|(_, value)| value.trim().parse::<f64>().ok())
+                })
+                .collect();
+
+            let unix_time = elements
+                .iter()
+                .find(|(name, _)| name == UNIX_TIME_KEY)
+                .and_then(|(_, value)| value.trim().parse::<i64>().ok());
+
+            observations.push(Observation::new(date, values).with_unix_time(unix_time));
+        }
+
+        Ok(Dataset { series_codes, observations })
+    }
+
+    /// parses a response of any return format into a dataset. The format is recognized by the first character, and a
+    /// CSV in the long layout of [`reshape::to_long_csv`] is recognized by its header.
+    ///
+    /// # Error
+    ///
+    /// This function returns `UnparsableResponse` error when the response does not have the layout of its format.
+    pub(crate) fn from_response(response: &str) -> Result<Dataset, ReturnError> {
+        match response.trim_start().chars().next() {
+            Some('{') => Dataset::from_json(response),
+            Some('<') => Dataset::from_xml(response),
+            #[cfg(feature = "parse")]
+            _ if response.lines().next().is_some_and(reshape::is_long_csv_header) => reshape::from_long_csv(response),
+            _ => Dataset::from_csv(response),
+        }
+    }
+
+    /// stringifies the dataset in the same JSON layout with EVDS responses.
+    ///
+    /// Values are written as strings as EVDS does and missing values become `null`.
+    pub(crate) fn to_json(&self) -> String {
+        self.write_json(None)
+    }
+
+    /// writes the JSON of [`Dataset::to_json`] with the values in their shortest forms or with the decimal places.
+    fn write_json(&self, decimal_places: Option<u32>) -> String {
+
+        let items = self.observations
+            .iter()
+            .map(|observation| {
+                let mut item = Map::new();
+
+                item.insert(DATE_KEY.to_string(), Value::String(observation.date.clone()));
+
+                for (code, value) in self.series_codes.iter().zip(observation.values.iter()) {
+                    let json_value = match value {
+                        Some(value) => Value::String(precision::format_value(*value, decimal_places)),
+                        None => Value::Null,
+                    };
+
+                    item.insert(code.to_string(), json_value);
+                }
+
+                Value::Object(item)
+            })
+            .collect::<Vec<Value>>();
+
+        let mut document = Map::new();
+
+        document.insert("totalCount".to_string(), Value::from(self.observations.len()));
+        document.insert("items".to_string(), Value::Array(items));
+
+        Value::Object(document).to_string()
+    }
+
+    /// stringifies the dataset in the same CSV layout with EVDS responses. Missing values become empty cells.
+    ///
+    /// The series codes and the dates that spreadsheet applications would run as formulas are neutralized unless the
+    /// CSV injection protection is disabled.
+    #[cfg(any(feature = "parse", feature = "export"))]
+    pub(crate) fn to_csv(&self) -> String {
+        self.write_csv(None)
+    }
+
+    /// writes the CSV of [`Dataset::to_csv`] with the values in their shortest forms or with the decimal places.
+    fn write_csv(&self, decimal_places: Option<u32>) -> String {
+
+        let is_protected = csv_injection::is_protection_enabled();
+
+        let mut csv = String::from(DATE_KEY);
+
+        for code in &self.series_codes {
+            csv.push(',');
+            csv.push_str(&csv_injection::protect_cell(code, is_protected));
+        }
+
+        for observation in &self.observations {
+            csv.push('\n');
+            csv.push_str(&csv_injection::protect_cell(&observation.date, is_protected));
+
+            for value in &observation.values {
+                csv.push(',');
+
+                if let Some(value) = value { csv.push_str(&precision::format_value(*value, decimal_places)); }
+            }
+        }
+
+        csv
+    }
+
+    /// writes the dataset in the same XML layout with EVDS responses with the values in their shortest forms or with
+    /// the decimal places. Missing values become empty elements.
+    fn write_xml(&self, decimal_places: Option<u32>) -> String {
+
+        let mut xml = format!("<document><totalCount>{}</totalCount>", self.observations.len());
+
+        for observation in &self.observations {
+            xml.push_str(&format!("<items><{0}>{1}</{0}>", DATE_KEY, observation.date));
+
+            for (code, value) in self.series_codes.iter().zip(observation.values.iter()) {
+                let value = match value {
+                    Some(value) => precision::format_value(*value, decimal_places),
+                    None => String::new(),
+                };
+
+                xml.push_str(&format!("<{0}>{1}</{0}>", code, value));
+            }
+
+            xml.push_str("</items>");
+        }
+
+        xml.push_str("</document>");
+
+        xml
+    }
+
+    /// stringifies the dataset in the given return format.
+    pub(crate) fn serialize(&self, return_format: &ReturnFormat) -> String {
+        self.serialize_with_decimal_places(return_format, None)
+    }
+
+    /// stringifies the dataset in the given return format with each value rounded to exactly the given decimal places,
+    /// e.g. "1.8530" with 4 decimal places, or in its shortest form when no decimal places are given.
+    pub(crate) fn serialize_with_decimal_places(
+        &self,
+        return_format: &ReturnFormat,
+        decimal_places: Option<u32>
+    ) -> String {
+
+        let _span = tracing::info_span!("convert").entered();
+
+        match return_format {
+            ReturnFormat::Csv => self.write_csv(decimal_places),
+            ReturnFormat::Json | ReturnFormat::Auto => self.write_json(decimal_places),
+            ReturnFormat::Xml => self.write_xml(decimal_places),
+        }
+    }
+
+    /// rounds each value to the given decimal places half away from zero, so that the values read from the dataset do
+    /// not carry the excessive precision of some responses.
+    #[cfg(feature = "parse")]
+    pub(crate) fn round_values(&mut self, decimal_places: u32) {
+        for value in self.observations.iter_mut().flat_map(|observation| observation.values.iter_mut()).flatten() {
+            *value = precision::round_value(*value, decimal_places);
+        }
+    }
+
+    /// counts the observations including at least one value.
+    #[cfg(feature = "parse")]
+    pub(crate) fn count_observations(&self) -> usize {
+        self.observations.iter().filter(|observation| observation.has_value()).count()
+    }
+
+    /// drops the observations without any value and keeps only the last `count` observations.
+    #[cfg(feature = "parse")]
+    pub(crate) fn keep_last(&mut self, count: usize) {
+
+        self.observations.retain(Observation::has_value);
+
+        let excess = self.observations.len().saturating_sub(count);
+
+        self.observations.drain(..excess);
+    }
+
+    /// finds the observation at the date written like the dates of EVDS, e.g. "2003-1" or "2003-01" for a month and
+    /// "13-12-2011" for a day.
+    #[cfg(any(feature = "currency", feature = "parse"))]
+    pub(crate) fn find_observation(&self, date: &str) -> Option<&Observation> {
+
+        let period_start = frequency::parse_period_start(date);
+
+        self.observations.iter().find(|observation| match period_start {
+            Some(period_start) => frequency::parse_period_start(&observation.date) == Some(period_start),
+            None => observation.date.trim() == date.trim(),
+        })
+    }
+}
+
+/// collects the keys of the JSON items other than the date and metadata keys as series codes in the order they are
+/// first seen.
+fn collect_series_codes(items: &[&Map<String, Value>]) -> Vec<String> {
+
+    let mut series_codes: Vec<String> = Vec::new();
+
+    for key in items.iter().flat_map(|item| item.keys()) {
+        if METADATA_KEYS.contains(&key.as_str()) || series_codes.contains(key) { continue; }
+
+        series_codes.push(key.to_string());
+    }
+
+    series_codes
+}
+
+/// converts a JSON item of EVDS into an observation having the values of the given series.
+///
+/// # Error
+///
+/// This function returns `UnparsableResponse` error when the item does not have a date.
+fn parse_item(item: &Map<String, Value>, series_codes: &[String]) -> Result<Observation, ReturnError> {
+
+    let date = match item.get(DATE_KEY).and_then(Value::as_str) {
+        Some(date) => date.to_string(),
+        None => return Err(ReturnError::UnparsableResponse),
+    };
+
+    let values = series_codes
+        .iter()
+        .map(|code| parse_value(item.get(code)))
+        .collect();
+
+    let unix_time = parse_unix_time(item.get(UNIX_TIME_KEY));
+
+    Ok(Observation::new(date, values).with_unix_time(unix_time))
+}
+
+/// removes the surrounding whitespaces and quotes of a CSV cell.
+fn unquote(cell: &str) -> String {
+    cell.trim().trim_matches('"').to_string()
+}
+
+/// parses the `<name>value</name>` and `<name/>` elements placed one after another as (name, value) pairs.
+fn parse_xml_elements(text: &str) -> Result<Vec<(String, String)>, ReturnError> {
+
+    let mut elements = Vec::new();
+    let mut rest = text.trim_start();
+
+    while !rest.is_empty() {
+        let tag_end = match (rest.starts_with('<'), rest.find('>')) {
+            (true, Some(tag_end)) => tag_end,
+            _ => return Err(ReturnError::UnparsableResponse),
+        };
+
+        let tag = &rest[1..tag_end];
+
+        if let Some(name) = tag.strip_suffix('/') {
+            elements.push((name.trim().to_string(), String::new()));
+            rest = rest[tag_end + 1..].trim_start();
+
+            continue;
+        }
+
+        let closing_tag = format!("</{}>", tag);
+        let content = &rest[tag_end + 1..];
+
+        let content_end = match content.find(&closing_tag) {
+            Some(content_end) => content_end,
+            None => return Err(ReturnError::UnparsableResponse),
+        };
+
+        elements.push((tag.to_string(), content[..content_end].to_string()));
+        rest = content[content_end + closing_tag.len()..].trim_start();
+    }
+
+    Ok(elements)
+}
+
+/// converts a JSON value of EVDS into a number. EVDS mostly sends numbers as strings.
+fn parse_value(value: Option<&Value>) -> Option<f64> {
+    match value? {
+        Value::String(text) => text.trim().parse::<f64>().ok(),
+        Value::Number(number) => number.as_f64(),
+        _ => None,
+    }
+}
+
+/// converts the `UNIXTIME` of EVDS into seconds. EVDS sends it as `{"$numberLong":"1323727200"}` in JSON.
+fn parse_unix_time(unix_time: Option<&Value>) -> Option<i64> {
+    match unix_time? {
+        Value::Object(object) => object.get("$numberLong").and_then(|number| parse_unix_time(Some(number))),
+        Value::String(text) => text.trim().parse::<i64>().ok(),
+        Value::Number(number) => number.as_i64(),
+        _ => None,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"{"totalCount":4,"items":[
+        {"Tarih":"09-12-2011","TP_DK_USD_S":"1.8412","UNIXTIME":{"$numberLong":"1323381600"}},
+        {"Tarih":"10-12-2011","TP_DK_USD_S":null,"UNIXTIME":{"$numberLong":"1323468000"}},
+        {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8534","UNIXTIME":{"$numberLong":"1323640800"}},
+        {"Tarih":"13-12-2011","TP_DK_USD_S":"1.8673","UNIXTIME":{"$numberLong":"1323727200"}}
+    ]}"#;
+
+    #[test]
+    #[cfg(feature = "parse")]
+    fn should_parse_and_keep_last() {
+        let mut dataset = Dataset::from_json(RESPONSE).unwrap();
+
+        assert_eq!(vec!["TP_DK_USD_S".to_string()], dataset.series_codes);
+        assert_eq!(4, dataset.observations.len());
+        assert_eq!(3, dataset.count_observations());
+        assert_eq!(None, dataset.observations[1].values[0]);
+
+        dataset.keep_last(2);
+
+        assert_eq!(2, dataset.observations.len());
+        assert_eq!("12-12-2011", dataset.observations[0].date);
+        assert_eq!(Some(1.8673), dataset.observations[1].values[0]);
+
+        let reparsed_dataset = Dataset::from_json(&dataset.to_json()).unwrap();
+
+        assert_eq!(dataset, reparsed_dataset);
+
+        assert!(Dataset::from_json("<document></document>").is_err());
+    }
+
+    #[test]
+    fn should_parse_each_format() {
+        let json_dataset = Dataset::from_json(RESPONSE).unwrap();
+
+        let csv_dataset = Dataset::from_response(
+            "Tarih,TP_DK_USD_S,UNIXTIME\n09-12-2011,1.8412,1323381600\n10-12-2011,,1323468000\n\
+            12-12-2011,1.8534,1323640800\n13-12-2011,\"1.8673\",1323727200\n"
+        ).unwrap();
+
+        assert_eq!(json_dataset, csv_dataset);
+        assert_eq!(Some(1323727200), json_dataset.observations[3].unix_time);
+
+        let monthly_dataset = Dataset::from_csv("Tarih,TP_X,UNIXTIME
+2011-12,1.8,1322690400
+2012-1,1.9,").unwrap();
+
+        assert_eq!(Some(1322690400), monthly_dataset.observations[0].unix_time);
+        assert_eq!(None, monthly_dataset.observations[1].unix_time);
+
+        let xml_dataset = Dataset::from_response(&json_dataset.serialize(&ReturnFormat::Xml)).unwrap();
+
+        assert_eq!(json_dataset, xml_dataset);
+
+        let xml_dataset = Dataset::from_xml(
+            "<document><totalCount>1</totalCount><items><Tarih>10-12-2011</Tarih><TP_DK_USD_S/>\
+            <UNIXTIME>1323468000</UNIXTIME></items></document>"
+        ).unwrap();
+
+        assert_eq!(vec![None], xml_dataset.observations[0].values);
+        assert!(Dataset::from_csv("TP_DK_USD_S\n1.8412").is_err());
+        assert!(Dataset::from_xml("<items><Tarih>10-12-2011</items>").is_err());
+    }
+
+    #[test]
+    fn should_serialize_in_each_format() {
+        let dataset = Dataset::from_json(RESPONSE).unwrap();
+
+        assert_eq!(
+            "Tarih,TP_DK_USD_S\n09-12-2011,1.8412\n10-12-2011,\n12-12-2011,1.8534\n13-12-2011,1.8673",
+            dataset.serialize(&ReturnFormat::Csv)
+        );
+
+        let xml = dataset.serialize(&ReturnFormat::Xml);
+
+        assert!(xml.starts_with("<document><totalCount>4</totalCount><items><Tarih>09-12-2011</Tarih>"));
+        assert!(xml.contains("<items><Tarih>10-12-2011</Tarih><TP_DK_USD_S></TP_DK_USD_S></items>"));
+        assert!(xml.ends_with("<TP_DK_USD_S>1.8673</TP_DK_USD_S></items></document>"));
+    }
+}
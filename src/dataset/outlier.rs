@@ -0,0 +1,110 @@
+use serde_json::{Map, Value};
+
+use super::Dataset;
+
+
+/// finds the observations of each series whose values are further than `threshold` standard deviations from the mean
+/// of the series. A series without any spread has no outliers.
+///
+/// The indexes of the observations are returned for each series in the order of [`Dataset::series_codes`].
+pub(crate) fn find_z_score_outliers(dataset: &Dataset, threshold: f64) -> Vec<Vec<usize>> {
+    find_outliers(dataset, |values| {
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let deviation = (values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / count).sqrt();
+
+        match deviation > 0.0 {
+            true => (mean - threshold * deviation, mean + threshold * deviation),
+            false => (f64::NEG_INFINITY, f64::INFINITY),
+        }
+    })
+}
+
+/// finds the observations of each series whose values are further than `threshold` interquartile ranges below the
+/// first quartile or above the third quartile of the series, e.g. 1.5 for the fences of Tukey.
+///
+/// The indexes of the observations are returned for each series in the order of [`Dataset::series_codes`].
+pub(crate) fn find_interquartile_range_outliers(dataset: &Dataset, threshold: f64) -> Vec<Vec<usize>> {
+    find_outliers(dataset, |values| {
+        let mut sorted_values = values.to_vec();
+        sorted_values.sort_by(f64::total_cmp);
+
+        let first_quartile = find_quantile(&sorted_values, 0.25);
+        let third_quartile = find_quantile(&sorted_values, 0.75);
+        let range = third_quartile - first_quartile;
+
+        (first_quartile - threshold * range, third_quartile + threshold * range)
+    })
+}
+
+/// stringifies the indexes of the outliers of each series as a JSON object, e.g. `{"TP_DK_USD_S":[3,17]}`.
+pub(crate) fn outliers_to_json(series_codes: &[String], outliers: &[Vec<usize>]) -> String {
+
+    let outliers_of_series: Map<String, Value> = series_codes
+        .iter()
+        .zip(outliers.iter())
+        .map(|(series_code, indexes)| (series_code.clone(), Value::from(indexes.clone())))
+        .collect();
+
+    Value::Object(outliers_of_series).to_string()
+}
+
+/// flags the values of each series outside of the bounds found from the present values of the series. Missing values
+/// are never flagged.
+fn find_outliers<F>(dataset: &Dataset, find_bounds: F) -> Vec<Vec<usize>>
+    where F: Fn(&[f64]) -> (f64, f64)
+{
+    (0..dataset.series_codes.len())
+        .map(|column| {
+            let indexed_values: Vec<(usize, f64)> = dataset.observations
+                .iter()
+                .enumerate()
+                .filter_map(|(index, observation)| Some((index, observation.values.get(column).copied().flatten()?)))
+                .collect();
+
+            if indexed_values.is_empty() { return Vec::new(); }
+
+            let values: Vec<f64> = indexed_values.iter().map(|(_, value)| *value).collect();
+
+            let (lower_bound, upper_bound) = find_bounds(&values);
+
+            indexed_values
+                .iter()
+                .filter(|(_, value)| *value < lower_bound || *value > upper_bound)
+                .map(|(index, _)| *index)
+                .collect()
+        })
+        .collect()
+}
+
+/// finds the quantile of the sorted values by interpolating linearly between the closest ranks.
+fn find_quantile(sorted_values: &[f64], quantile: f64) -> f64 {
+
+    let rank = quantile * (sorted_values.len() - 1) as f64;
+    let lower_rank = rank.floor() as usize;
+    let upper_rank = rank.ceil() as usize;
+
+    sorted_values[lower_rank] + (rank - lower_rank as f64) * (sorted_values[upper_rank] - sorted_values[lower_rank])
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_outliers() {
+        let dataset = Dataset::from_csv(
+            "Tarih,TP_DK_USD_S,TP_DK_EUR_S\n\
+            01-12-2011,1.84,2.45\n02-12-2011,1.85,\n05-12-2011,18.5,2.44\n06-12-2011,1.86,2.46\n\
+            07-12-2011,1.85,2.45\n08-12-2011,1.84,2.45\n09-12-2011,1.86,2.44\n12-12-2011,1.85,2.46"
+        ).unwrap();
+
+        assert_eq!(vec![vec![2], vec![]], find_z_score_outliers(&dataset, 2.0));
+        assert_eq!(vec![vec![2], vec![]], find_interquartile_range_outliers(&dataset, 1.5));
+        assert_eq!(
+            r#"{"TP_DK_EUR_S":[],"TP_DK_USD_S":[2]}"#,
+            outliers_to_json(&dataset.series_codes, &find_z_score_outliers(&dataset, 2.0))
+        );
+    }
+}
@@ -0,0 +1,93 @@
+/// is the most decimal places that the values are rounded to, beyond which a double has no more precision.
+pub(crate) const MAX_DECIMAL_PLACES: u32 = 15;
+
+
+/// rounds the value to the given decimal places half away from zero, e.g. 1.23456 becomes 1.235 with 3 decimal places.
+/// The decimal places are limited to [`MAX_DECIMAL_PLACES`].
+#[cfg(feature = "parse")]
+pub(crate) fn round_value(value: f64, decimal_places: u32) -> f64 {
+    format_value(value, Some(decimal_places)).parse::<f64>().unwrap_or(value)
+}
+
+/// writes the value in its shortest form, e.g. "1.8534", or rounded to exactly the given decimal places, e.g. "1.8530"
+/// with 4 decimal places, so that the values have the same number of digits after the dot.
+///
+/// The value is rounded from its shortest form, namely the form written by EVDS, so that 1.005 becomes 1.01 with 2
+/// decimal places although the closest double of 1.005 is slightly less than it.
+pub(crate) fn format_value(value: f64, decimal_places: Option<u32>) -> String {
+
+    let text = value.to_string();
+
+    match decimal_places {
+        Some(decimal_places) if value.is_finite() => round_decimal_text(&text, decimal_places.min(MAX_DECIMAL_PLACES)),
+        _ => text,
+    }
+}
+
+/// rounds a decimal written without an exponent, e.g. "-12.345", to the given decimal places half away from zero.
+fn round_decimal_text(text: &str, decimal_places: u32) -> String {
+
+    let decimal_places = decimal_places as usize;
+
+    let (is_negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+
+    let (integer_part, fraction_part) = text.split_once('.').unwrap_or((text, ""));
+
+    let mut digits: Vec<u8> = integer_part.bytes().collect();
+
+    digits.extend(fraction_part.bytes().chain(std::iter::repeat(b'0')).take(decimal_places));
+
+    if fraction_part.as_bytes().get(decimal_places).is_some_and(|digit| *digit >= b'5') {
+        // carries the rounding up through the nines, e.g. "9.99" becomes "10.0" with 1 decimal place.
+        match digits.iter().rposition(|digit| *digit != b'9') {
+            Some(position) => {
+                digits[position] += 1;
+                digits[position + 1..].fill(b'0');
+            },
+            None => {
+                digits.fill(b'0');
+                digits.insert(0, b'1');
+            },
+        }
+    }
+
+    let is_zero = digits.iter().all(|digit| *digit == b'0');
+    let integer_length = digits.len() - decimal_places;
+
+    let mut rounded_text = String::with_capacity(digits.len() + 2);
+
+    // avoids writing small negative values as "-0.00".
+    if is_negative && !is_zero { rounded_text.push('-'); }
+
+    rounded_text.push_str(&String::from_utf8_lossy(&digits[..integer_length]));
+
+    if decimal_places > 0 {
+        rounded_text.push('.');
+        rounded_text.push_str(&String::from_utf8_lossy(&digits[integer_length..]));
+    }
+
+    rounded_text
+}
+
+
+#[cfg(all(test, feature = "parse"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_round_values_half_away_from_zero() {
+        assert_eq!(1.235, round_value(1.23456, 3));
+        assert_eq!(1.01, round_value(1.005, 2));
+        assert_eq!(-3.0, round_value(-2.5, 0));
+        assert_eq!(0.0, round_value(-0.0001, 2));
+
+        assert_eq!("1.8530", format_value(1.853, Some(4)));
+        assert_eq!("10.0", format_value(9.96, Some(1)));
+        assert_eq!("0.00", format_value(-0.0001, Some(2)));
+        assert_eq!("2", format_value(1.5, Some(0)));
+        assert_eq!("1.853", format_value(1.853, None));
+    }
+}
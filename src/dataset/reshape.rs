@@ -0,0 +1,170 @@
+use super::{DATE_KEY, Dataset, Observation, csv_injection};
+
+use crate::error::ReturnError;
+
+
+/// is the header of the long CSV layout whose rows are `date,series,value`.
+pub(crate) const LONG_CSV_HEADER: &str = "Tarih,SERIE_CODE,VALUE";
+
+
+/// is a single value of a series at a date, namely a row of the long layout.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LongObservation {
+    pub(crate) date: String,
+    pub(crate) series_code: String,
+    pub(crate) value: f64,
+}
+
+
+/// converts the wide dataset, which has a column for each series, into the long layout which has a row for each value.
+///
+/// The rows are ordered by the dates and by the series of each date. Missing values are not written since a missing
+/// row already means a missing value in the long layout.
+pub(crate) fn to_long(dataset: &Dataset) -> Vec<LongObservation> {
+
+    let mut long_observations = Vec::with_capacity(dataset.observations.len() * dataset.series_codes.len());
+
+    for observation in &dataset.observations {
+        for (series_code, value) in dataset.series_codes.iter().zip(observation.values.iter()) {
+            if let Some(value) = value {
+                long_observations.push(LongObservation {
+                    date: observation.date.clone(),
+                    series_code: series_code.clone(),
+                    value: *value,
+                });
+            }
+        }
+    }
+
+    long_observations
+}
+
+/// converts the long layout back into a wide dataset. The dates and the series are placed in the order they first
+/// appear, and a date without a row for a series has a missing value. A repeated row overrides the former one.
+pub(crate) fn from_long(long_observations: &[LongObservation]) -> Dataset {
+
+    let mut dataset = Dataset::default();
+
+    for long_observation in long_observations {
+        let column = match dataset.series_codes.iter().position(|code| *code == long_observation.series_code) {
+            Some(column) => column,
+            None => {
+                dataset.series_codes.push(long_observation.series_code.clone());
+                dataset.observations.iter_mut().for_each(|observation| observation.values.push(None));
+
+                dataset.series_codes.len() - 1
+            },
+        };
+
+        let row = match dataset.observations.iter().position(|observation| observation.date == long_observation.date) {
+            Some(row) => row,
+            None => {
+                let values = vec![None; dataset.series_codes.len()];
+                dataset.observations.push(Observation::new(long_observation.date.clone(), values));
+
+                dataset.observations.len() - 1
+            },
+        };
+
+        dataset.observations[row].values[column] = Some(long_observation.value);
+    }
+
+    dataset
+}
+
+/// stringifies the dataset in the long CSV layout, e.g. "Tarih,SERIE_CODE,VALUE\n13-12-2011,TP_DK_USD_S,1.8673".
+///
+/// The dates and the series codes that spreadsheet applications would run as formulas are neutralized unless the CSV
+/// injection protection is disabled.
+pub(crate) fn to_long_csv(dataset: &Dataset) -> String {
+
+    let is_protected = csv_injection::is_protection_enabled();
+
+    let mut csv = LONG_CSV_HEADER.to_string();
+
+    for long_observation in to_long(dataset) {
+        csv.push_str(&format!(
+            "\n{},{},{}",
+            csv_injection::protect_cell(&long_observation.date, is_protected),
+            csv_injection::protect_cell(&long_observation.series_code, is_protected),
+            long_observation.value
+        ));
+    }
+
+    csv
+}
+
+/// parses a CSV in the long layout written by [`to_long_csv`] into a wide dataset.
+///
+/// # Error
+///
+/// This function returns `UnparsableResponse` error when the header is not the long layout header or a row does not
+/// have a date, a series code and a number.
+pub(crate) fn from_long_csv(csv: &str) -> Result<Dataset, ReturnError> {
+
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+
+    if !lines.next().is_some_and(is_long_csv_header) { return Err(ReturnError::UnparsableResponse); }
+
+    let mut long_observations = Vec::new();
+
+    for line in lines {
+        let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+
+        let value = match cells.as_slice() {
+            [date, series_code, value] if !date.is_empty() && !series_code.is_empty() => value.parse::<f64>().ok(),
+            _ => None,
+        };
+
+        match value {
+            Some(value) => long_observations.push(LongObservation {
+                date: cells[0].to_string(),
+                series_code: cells[1].to_string(),
+                value,
+            }),
+            None => return Err(ReturnError::UnparsableResponse),
+        }
+    }
+
+    Ok(from_long(&long_observations))
+}
+
+/// checks the first line of a CSV whether it is the long layout header or not.
+pub(crate) fn is_long_csv_header(line: &str) -> bool {
+
+    let mut columns = line.split(',').map(str::trim);
+
+    columns.next() == Some(DATE_KEY) && columns.next() == Some("SERIE_CODE") && columns.next() == Some("VALUE")
+        && columns.next().is_none()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_reshape_between_wide_and_long() {
+        let wide_dataset = Dataset::from_csv(
+            "Tarih,TP_DK_USD_S,TP_DK_EUR_S\n12-12-2011,1.8534,\n13-12-2011,1.8673,2.4361"
+        ).unwrap();
+
+        let long_observations = to_long(&wide_dataset);
+
+        assert_eq!(3, long_observations.len());
+        assert_eq!("TP_DK_EUR_S", long_observations[2].series_code);
+
+        let long_csv = to_long_csv(&wide_dataset);
+
+        assert_eq!(
+            "Tarih,SERIE_CODE,VALUE\n12-12-2011,TP_DK_USD_S,1.8534\n13-12-2011,TP_DK_USD_S,1.8673\n\
+            13-12-2011,TP_DK_EUR_S,2.4361",
+            long_csv
+        );
+        assert_eq!(wide_dataset, from_long_csv(&long_csv).unwrap());
+        assert_eq!(wide_dataset, from_long(&long_observations));
+
+        assert_eq!(Err(ReturnError::UnparsableResponse), from_long_csv("Tarih,TP_DK_USD_S\n13-12-2011,1.8673"));
+        assert_eq!(Err(ReturnError::UnparsableResponse), from_long_csv("Tarih,SERIE_CODE,VALUE\n13-12-2011,TP,x"));
+    }
+}
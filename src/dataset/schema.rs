@@ -0,0 +1,107 @@
+use serde_json::Value;
+
+use super::DATE_KEY;
+
+use crate::error::ReturnError;
+
+
+/// checks a JSON data response against the schema of EVDS, which is an object with a numeric `totalCount` and an
+/// `items` array of objects. Each item must have a string date and a key for each of the expected series codes whose
+/// value is a string, a number or `null`. `totalCount` must be the number of the items.
+///
+/// The series codes are written with underscores as in the responses, e.g. "TP_DK_USD_S". No series is checked when
+/// the codes are empty, e.g. for data groups whose series are not known before the request.
+///
+/// # Error
+///
+/// This function returns `UnexpectedSchema` error including the first discrepancy, e.g. "items[3] is missing
+/// TP_DK_USD_S".
+pub(crate) fn validate_schema(response: &str, series_codes: &[String]) -> Result<(), ReturnError> {
+
+    let discrepancy = |message: String| Err(ReturnError::UnexpectedSchema(message));
+
+    let parsed_response: Value = match serde_json::from_str(response) {
+        Ok(parsed_response) => parsed_response,
+        Err(_) => return discrepancy("the response is not JSON".to_string()),
+    };
+
+    let document = match parsed_response.as_object() {
+        Some(document) => document,
+        None => return discrepancy("the response is not a JSON object".to_string()),
+    };
+
+    let items = match document.get("items") {
+        Some(Value::Array(items)) => items,
+        Some(_) => return discrepancy("items is not an array".to_string()),
+        None => return discrepancy("items is missing".to_string()),
+    };
+
+    match document.get("totalCount").map(Value::as_u64) {
+        Some(Some(total_count)) if total_count == items.len() as u64 => (),
+        Some(Some(total_count)) => {
+            return discrepancy(format!("totalCount is {} but there are {} items", total_count, items.len()));
+        },
+        Some(None) => return discrepancy("totalCount is not a number".to_string()),
+        None => return discrepancy("totalCount is missing".to_string()),
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        let item = match item.as_object() {
+            Some(item) => item,
+            None => return discrepancy(format!("items[{}] is not an object", index)),
+        };
+
+        if !item.get(DATE_KEY).is_some_and(Value::is_string) {
+            return discrepancy(format!("items[{}] does not have a {} string", index, DATE_KEY));
+        }
+
+        for series_code in series_codes {
+            match item.get(series_code) {
+                Some(Value::String(_)) | Some(Value::Number(_)) | Some(Value::Null) => (),
+                Some(_) => return discrepancy(format!("items[{}].{} is not a value", index, series_code)),
+                None => return discrepancy(format!("items[{}] is missing {}", index, series_code)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_validate_schema() {
+        let series_codes = vec!["TP_DK_USD_S".to_string()];
+
+        let valid_response = r#"{"totalCount":2,"items":[
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8534","UNIXTIME":{"$numberLong":"1323640800"}},
+            {"Tarih":"13-12-2011","TP_DK_USD_S":null}
+        ]}"#;
+
+        assert!(validate_schema(valid_response, &series_codes).is_ok());
+        assert!(validate_schema(r#"{"totalCount":0,"items":[]}"#, &[]).is_ok());
+
+        let assert_discrepancy = |response: &str, message: &str| {
+            assert_eq!(
+                Err(ReturnError::UnexpectedSchema(message.to_string())),
+                validate_schema(response, &series_codes)
+            );
+        };
+
+        assert_discrepancy("Tarih,TP_DK_USD_S", "the response is not JSON");
+        assert_discrepancy(r#"{"totalCount":0}"#, "items is missing");
+        assert_discrepancy(r#"{"items":[]}"#, "totalCount is missing");
+        assert_discrepancy(r#"{"totalCount":3,"items":[]}"#, "totalCount is 3 but there are 0 items");
+        assert_discrepancy(
+            r#"{"totalCount":1,"items":[{"TP_DK_USD_S":"1"}]}"#,
+            "items[0] does not have a Tarih string"
+        );
+        assert_discrepancy(
+            r#"{"totalCount":1,"items":[{"Tarih":"13-12-2011","TP_DK_USD":"1"}]}"#,
+            "items[0] is missing TP_DK_USD_S"
+        );
+    }
+}
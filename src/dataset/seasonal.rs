@@ -0,0 +1,245 @@
+use serde_json::{Map, Value, json};
+
+use super::Dataset;
+use super::frequency::{infer_frequency, parse_period_start};
+
+use crate::error::ReturnError;
+use crate::evds_currency::frequency_formulas::DataFrequency;
+
+
+/// is the least number of full years a series must cover, so that each season is estimated from at least two years
+/// after the ends lost to the moving average.
+const MINIMUM_YEARS: usize = 3;
+
+
+/// specifies how the seasonal component is combined with the rest of a series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SeasonalModel {
+    /// is used for series with only positive values, whose seasonal swings grow with their level, e.g. prices.
+    Multiplicative,
+    /// is used for series including zero or negative values, e.g. balances.
+    Additive,
+}
+
+impl SeasonalModel {
+    fn to_str(self) -> &'static str {
+        match self {
+            SeasonalModel::Multiplicative => "multiplicative",
+            SeasonalModel::Additive => "additive",
+        }
+    }
+}
+
+/// is the seasonal component estimated for a series, which has a factor for each month or quarter starting from
+/// January or the first quarter.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SeasonalFactors {
+    pub(crate) model: SeasonalModel,
+    pub(crate) factors: Vec<f64>,
+}
+
+
+/// removes the seasonal component of each series of a monthly or a quarterly dataset in place and returns the
+/// factors removed from each series.
+///
+/// The decomposition is the classical one based on moving averages: the trend is the centered moving average over a
+/// year, the ratios or the differences of the values to the trend are averaged for each month or quarter, and the
+/// averages normalized to one or zero are the seasonal factors. It is much simpler than X-13, so the adjusted series
+/// are not expected to match the seasonally adjusted series published by EVDS.
+///
+/// # Error
+///
+/// This function returns `SeasonalAdjustmentFailed` error when the dataset is not monthly or quarterly, its dates
+/// are not consecutive periods, or a series covers less than three years. The dataset is left unchanged in the case
+/// of an error.
+pub(crate) fn seasonally_adjust(dataset: &mut Dataset) -> Result<Vec<SeasonalFactors>, ReturnError> {
+
+    let failure = |reason: &str| ReturnError::SeasonalAdjustmentFailed(reason.to_string());
+
+    let period = match infer_frequency(dataset).frequency {
+        Some(DataFrequency::Monthly) => 12,
+        Some(DataFrequency::Quarterly) => 4,
+        _ => return Err(failure("only monthly and quarterly series are adjusted")),
+    };
+
+    let seasons = find_seasons(dataset, period)
+        .ok_or_else(|| failure("the dates must be consecutive months or quarters"))?;
+
+    let mut all_factors = Vec::with_capacity(dataset.series_codes.len());
+
+    for column in 0..dataset.series_codes.len() {
+        let values: Vec<Option<f64>> = dataset.observations
+            .iter()
+            .map(|observation| observation.values.get(column).copied().flatten())
+            .collect();
+
+        let factors = estimate_factors(&values, &seasons, period).ok_or_else(|| {
+            ReturnError::SeasonalAdjustmentFailed(format!(
+                "the series {} does not cover {} years", dataset.series_codes[column], MINIMUM_YEARS
+            ))
+        })?;
+
+        all_factors.push(factors);
+    }
+
+    for (column, factors) in all_factors.iter().enumerate() {
+        for (observation, season) in dataset.observations.iter_mut().zip(seasons.iter()) {
+            let value = match observation.values.get_mut(column) {
+                Some(Some(value)) => value,
+                _ => continue,
+            };
+
+            match factors.model {
+                SeasonalModel::Multiplicative => *value /= factors.factors[*season],
+                SeasonalModel::Additive => *value -= factors.factors[*season],
+            }
+        }
+    }
+
+    Ok(all_factors)
+}
+
+/// stringifies the factors of each series as a JSON object, e.g.
+/// `{"TP_FG_J0":{"model":"multiplicative","factors":[0.98,1.01,...]}}`.
+pub(crate) fn factors_to_json(series_codes: &[String], all_factors: &[SeasonalFactors]) -> String {
+
+    let factors_of_series: Map<String, Value> = series_codes
+        .iter()
+        .zip(all_factors.iter())
+        .map(|(series_code, factors)| {
+            (series_code.clone(), json!({ "model": factors.model.to_str(), "factors": factors.factors }))
+        })
+        .collect();
+
+    Value::Object(factors_of_series).to_string()
+}
+
+/// finds the season of each observation, namely its month or quarter counted from zero. None is returned when the
+/// observations are not consecutive periods.
+fn find_seasons(dataset: &Dataset, period: usize) -> Option<Vec<usize>> {
+
+    let months_per_season = 12 / period as i64;
+
+    let period_indexes: Vec<i64> = dataset.observations
+        .iter()
+        .map(|observation| {
+            let start = parse_period_start(&observation.date)?;
+
+            Some((start.year * 12 + start.month as i64 - 1) / months_per_season)
+        })
+        .collect::<Option<Vec<i64>>>()?;
+
+    if period_indexes.windows(2).any(|pair| pair[1] - pair[0] != 1) { return None; }
+
+    Some(period_indexes.iter().map(|index| index.rem_euclid(period as i64) as usize).collect())
+}
+
+/// estimates the seasonal factors of a series. None is returned when a season has less than two estimates.
+fn estimate_factors(values: &[Option<f64>], seasons: &[usize], period: usize) -> Option<SeasonalFactors> {
+
+    if values.iter().flatten().count() < MINIMUM_YEARS * period { return None; }
+
+    let model = match values.iter().flatten().all(|value| *value > 0.0) {
+        true => SeasonalModel::Multiplicative,
+        false => SeasonalModel::Additive,
+    };
+
+    let trend = find_centered_moving_average(values, period);
+
+    let mut sums = vec![0.0; period];
+    let mut counts = vec![0; period];
+
+    for ((value, trend), season) in values.iter().zip(trend.iter()).zip(seasons.iter()) {
+        let (value, trend) = match (value, trend) {
+            (Some(value), Some(trend)) => (*value, *trend),
+            _ => continue,
+        };
+
+        sums[*season] += match model {
+            SeasonalModel::Multiplicative => value / trend,
+            SeasonalModel::Additive => value - trend,
+        };
+        counts[*season] += 1;
+    }
+
+    if counts.iter().any(|count| *count < MINIMUM_YEARS - 1) { return None; }
+
+    let mut factors: Vec<f64> = sums.iter().zip(counts.iter()).map(|(sum, count)| sum / *count as f64).collect();
+
+    let mean = factors.iter().sum::<f64>() / period as f64;
+
+    for factor in factors.iter_mut() {
+        match model {
+            SeasonalModel::Multiplicative => *factor /= mean,
+            SeasonalModel::Additive => *factor -= mean,
+        }
+    }
+
+    Some(SeasonalFactors { model, factors })
+}
+
+/// finds the centered moving average over a year at each observation. Since the period is even, the window has one
+/// more value than the period and its ends are weighted by half. The averages near the ends of the series and the
+/// averages of the windows with a missing value are None.
+fn find_centered_moving_average(values: &[Option<f64>], period: usize) -> Vec<Option<f64>> {
+
+    let half_period = period / 2;
+
+    (0..values.len())
+        .map(|index| {
+            if index < half_period || index + half_period >= values.len() { return None; }
+
+            let window = &values[index - half_period..=index + half_period];
+
+            let sum = window
+                .iter()
+                .enumerate()
+                .map(|(position, value)| match position == 0 || position == period {
+                    true => value.map(|value| value / 2.0),
+                    false => *value,
+                })
+                .sum::<Option<f64>>()?;
+
+            Some(sum / period as f64)
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataset::Observation;
+
+    #[test]
+    fn should_seasonally_adjust_quarterly_series() {
+        let seasonal_pattern = [0.9, 1.1, 1.2, 0.8];
+
+        let observations = (0..16)
+            .map(|index| Observation::new(
+                format!("{}-Q{}", 2010 + index / 4, index % 4 + 1),
+                vec![Some((100.0 + index as f64) * seasonal_pattern[index % 4])],
+            ))
+            .collect();
+
+        let mut dataset = Dataset { series_codes: vec!["TP_GSYIH01_GY_CF".to_string()], observations };
+
+        let all_factors = seasonally_adjust(&mut dataset).unwrap();
+
+        assert_eq!(SeasonalModel::Multiplicative, all_factors[0].model);
+
+        for (factor, expected_factor) in all_factors[0].factors.iter().zip(seasonal_pattern.iter()) {
+            assert!((factor - expected_factor).abs() < 0.01);
+        }
+        for (index, observation) in dataset.observations.iter().enumerate() {
+            assert!((observation.values[0].unwrap() - (100.0 + index as f64)).abs() < 1.0);
+        }
+
+        let mut short_dataset = Dataset {
+            series_codes: dataset.series_codes.clone(),
+            observations: dataset.observations[..8].to_vec(),
+        };
+
+        assert!(seasonally_adjust(&mut short_dataset).is_err());
+    }
+}
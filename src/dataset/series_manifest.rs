@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value, json};
+
+use crate::error::ReturnError;
+
+
+/// are the fields of the series lists of EVDS written into the manifests with their names in the manifests. The end
+/// dates and the links are left out since they change without any change in what a pipeline consumes.
+const MANIFEST_FIELDS: [(&str, &str); 7] = [
+    ("SERIE_NAME", "name"),
+    ("SERIE_NAME_ENG", "nameEng"),
+    ("FREQUENCY_STR", "frequency"),
+    ("DEFAULT_AGG_METHOD", "aggregationMethod"),
+    ("START_DATE", "startDate"),
+    ("DATASOURCE_ENG", "dataSource"),
+    ("TAG_ENG", "tag"),
+];
+
+
+/// generates the manifest of the series of the data group from its JSON series list, e.g.
+/// `{"dataGroup":"bie_dkdovytl","series":[{"code":"TP.DK.USD.A","name":"...","frequency":"IS GUNLUK",...}]}`.
+///
+/// The manifest is meant to be kept in version control as a contract of the series a pipeline consumes, so the series
+/// are ordered by their codes and the manifest is pretty printed to keep the diffs of the files small.
+///
+/// # Error
+///
+/// This function returns `UnparsableResponse` error when the series list is not a JSON array of series.
+pub(crate) fn generate_manifest(data_group: &str, series_list: &str) -> Result<String, ReturnError> {
+
+    let items: Vec<Value> = serde_json::from_str(series_list).map_err(|_| ReturnError::UnparsableResponse)?;
+
+    let mut series = BTreeMap::new();
+
+    for item in &items {
+        let code = item.get("SERIE_CODE").and_then(Value::as_str).ok_or(ReturnError::UnparsableResponse)?;
+
+        let mut fields = Map::new();
+
+        fields.insert("code".to_string(), Value::from(code));
+
+        for (field, manifest_field) in MANIFEST_FIELDS {
+            let value = match item.get(field) {
+                Some(Value::String(value)) => Value::from(value.trim()),
+                Some(value) => value.clone(),
+                None => Value::Null,
+            };
+
+            fields.insert(manifest_field.to_string(), value);
+        }
+
+        series.insert(code.to_string(), Value::Object(fields));
+    }
+
+    let manifest = json!({ "dataGroup": data_group, "series": series.into_values().collect::<Vec<Value>>() });
+
+    serde_json::to_string_pretty(&manifest).map_err(|_| ReturnError::UnparsableResponse)
+}
+
+/// compares two manifests of [`generate_manifest`] and stringifies the differences as a JSON object of the added and
+/// removed series codes and the changed fields of the rest, e.g. `{"added":["TP.DK.CHF.A"],"removed":[],"changed":
+/// [{"code":"TP.DK.USD.A","field":"frequency","oldValue":"IS GUNLUK","newValue":"GUNLUK"}]}`.
+///
+/// # Error
+///
+/// This function returns `UnparsableResponse` error when either manifest could not be parsed.
+pub(crate) fn diff_manifests(old_manifest: &str, new_manifest: &str) -> Result<String, ReturnError> {
+
+    let old_series = parse_manifest(old_manifest)?;
+    let new_series = parse_manifest(new_manifest)?;
+
+    let added: Vec<&String> = new_series.keys().filter(|code| !old_series.contains_key(*code)).collect();
+    let removed: Vec<&String> = old_series.keys().filter(|code| !new_series.contains_key(*code)).collect();
+
+    let mut changed = Vec::new();
+
+    for (code, new_fields) in &new_series {
+        let old_fields = match old_series.get(code) {
+            Some(old_fields) => old_fields,
+            None => continue,
+        };
+
+        let mut field_names: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+        field_names.sort();
+        field_names.dedup();
+
+        for field in field_names {
+            let old_value = old_fields.get(field).unwrap_or(&Value::Null);
+            let new_value = new_fields.get(field).unwrap_or(&Value::Null);
+
+            if old_value == new_value { continue; }
+
+            changed.push(json!({ "code": code, "field": field, "oldValue": old_value, "newValue": new_value }));
+        }
+    }
+
+    Ok(json!({ "added": added, "removed": removed, "changed": changed }).to_string())
+}
+
+/// parses the series of a manifest keyed by their codes.
+fn parse_manifest(manifest: &str) -> Result<BTreeMap<String, Map<String, Value>>, ReturnError> {
+
+    let parsed_manifest: Value = serde_json::from_str(manifest).map_err(|_| ReturnError::UnparsableResponse)?;
+
+    parsed_manifest
+        .get("series")
+        .and_then(Value::as_array)
+        .ok_or(ReturnError::UnparsableResponse)?
+        .iter()
+        .map(|series| {
+            let fields = series.as_object().ok_or(ReturnError::UnparsableResponse)?;
+            let code = fields.get("code").and_then(Value::as_str).ok_or(ReturnError::UnparsableResponse)?;
+
+            Ok((code.to_string(), fields.clone()))
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_generate_and_diff_manifests() {
+        let old_series_list = r#"[
+            {"SERIE_CODE":"TP.DK.USD.A","SERIE_NAME":"(USD) ABD Doları (Döviz Alış) ","FREQUENCY_STR":"IS GUNLUK",
+                "START_DATE":"02-01-1950","END_DATE":"13-12-2011"},
+            {"SERIE_CODE":"TP.DK.EUR.A","SERIE_NAME":"(EUR) Euro (Döviz Alış)","FREQUENCY_STR":"IS GUNLUK"}
+        ]"#;
+        let new_series_list = r#"[
+            {"SERIE_CODE":"TP.DK.USD.A","SERIE_NAME":"(USD) ABD Doları (Döviz Alış)","FREQUENCY_STR":"GUNLUK",
+                "START_DATE":"02-01-1950","END_DATE":"14-12-2011"},
+            {"SERIE_CODE":"TP.DK.CHF.A","SERIE_NAME":"(CHF) İsviçre Frangı (Döviz Alış)"}
+        ]"#;
+
+        let old_manifest = generate_manifest("bie_dkdovytl", old_series_list).unwrap();
+        let new_manifest = generate_manifest("bie_dkdovytl", new_series_list).unwrap();
+
+        let parsed_manifest: Value = serde_json::from_str(&old_manifest).unwrap();
+
+        assert_eq!("bie_dkdovytl", parsed_manifest["dataGroup"]);
+        assert_eq!("TP.DK.EUR.A", parsed_manifest["series"][0]["code"]);
+        assert_eq!("(USD) ABD Doları (Döviz Alış)", parsed_manifest["series"][1]["name"]);
+        assert!(parsed_manifest["series"][1].get("endDate").is_none());
+        assert_eq!(
+            json!({
+                "added": ["TP.DK.CHF.A"],
+                "removed": ["TP.DK.EUR.A"],
+                "changed": [
+                    {"code": "TP.DK.USD.A", "field": "frequency", "oldValue": "IS GUNLUK", "newValue": "GUNLUK"}
+                ],
+            }),
+            serde_json::from_str::<Value>(&diff_manifests(&old_manifest, &new_manifest).unwrap()).unwrap()
+        );
+        assert!(matches!(generate_manifest("bie_dkdovytl", "Error"), Err(ReturnError::UnparsableResponse)));
+        assert!(matches!(diff_manifests("{}", &new_manifest), Err(ReturnError::UnparsableResponse)));
+    }
+}
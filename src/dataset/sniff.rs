@@ -0,0 +1,75 @@
+use super::Dataset;
+
+use crate::common::ReturnFormat;
+
+
+/// finds the format of a response from its first characters. A response starting with `{` or `[` is JSON, one
+/// starting with `<` is XML, and one whose first line includes a comma is CSV.
+///
+/// None is returned when the response does not look like any of them, e.g. an empty response or a plain text message.
+pub(crate) fn sniff_format(response: &str) -> Option<ReturnFormat> {
+
+    let response = response.trim_start_matches(|character: char| character.is_whitespace() || character == '\u{feff}');
+
+    match response.chars().next()? {
+        '{' | '[' => Some(ReturnFormat::Json),
+        '<' => Some(ReturnFormat::Xml),
+        _ if response.lines().next().is_some_and(|line| line.contains(',')) => Some(ReturnFormat::Csv),
+        _ => None,
+    }
+}
+
+/// converts the response into JSON when the return format is `Auto` and EVDS responded with another format although
+/// JSON was requested.
+///
+/// The response is returned as it is for the other return formats, for JSON responses, and for the responses that
+/// cannot be parsed as a dataset such as category lists or error messages.
+pub(crate) fn negotiate_response(response: String, return_format: &ReturnFormat) -> String {
+
+    if !matches!(return_format, ReturnFormat::Auto) { return response; }
+
+    let _span = tracing::info_span!("convert").entered();
+
+    match sniff_format(&response) {
+        Some(ReturnFormat::Json) | None => response,
+        Some(_) => match Dataset::from_response(response.trim_start_matches('\u{feff}')) {
+            Ok(dataset) => dataset.to_json(),
+            Err(_) => response,
+        },
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_negotiate_response() {
+        assert!(matches!(sniff_format("\u{feff} {\"items\":[]}"), Some(ReturnFormat::Json)));
+        assert!(matches!(sniff_format("<document/>"), Some(ReturnFormat::Xml)));
+        assert!(matches!(sniff_format("Tarih,TP_DK_USD_S\n13-12-2011,1.8673"), Some(ReturnFormat::Csv)));
+        assert!(sniff_format("").is_none());
+        assert!(sniff_format("Service Unavailable").is_none());
+
+        let csv_response = "Tarih,TP_DK_USD_S\n13-12-2011,1.8673".to_string();
+
+        assert_eq!(csv_response, negotiate_response(csv_response.clone(), &ReturnFormat::Csv));
+
+        let negotiated_response = negotiate_response(csv_response.clone(), &ReturnFormat::Auto);
+
+        assert!(negotiated_response.starts_with('{'));
+        assert_eq!(Dataset::from_response(&csv_response), Dataset::from_response(&negotiated_response));
+
+        let xml_response = concat!(
+            "<document><items><Tarih>13-12-2011</Tarih>",
+            "<TP_DK_USD_S>1.8673</TP_DK_USD_S></items></document>"
+        );
+
+        assert_eq!(
+            Dataset::from_response(&csv_response).unwrap(),
+            Dataset::from_response(&negotiate_response(xml_response.to_string(), &ReturnFormat::Auto)).unwrap()
+        );
+        assert_eq!("Service Unavailable", negotiate_response("Service Unavailable".to_string(), &ReturnFormat::Auto));
+    }
+}
@@ -0,0 +1,91 @@
+use serde_json::Value;
+
+use super::Dataset;
+
+
+/// is the summary of a data response to sanity check it without reading the observations one by one.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ResponseStats {
+    /// is the `totalCount` field of JSON and XML responses. CSV responses do not include it.
+    pub(crate) total_count: Option<u64>,
+    pub(crate) row_count: usize,
+    /// is the number of the missing values of all series, e.g. `null` values of holidays.
+    pub(crate) null_count: usize,
+    pub(crate) first_date: Option<String>,
+    pub(crate) last_date: Option<String>,
+}
+
+
+/// computes the statistics of a data response in any return format. The dates are the first and the last dates in
+/// the order of the response.
+///
+/// None is returned when the response cannot be parsed as a dataset, e.g. a category list or an error message.
+pub(crate) fn compute_stats(response: &str) -> Option<ResponseStats> {
+
+    let dataset = Dataset::from_response(response).ok()?;
+
+    let null_count = dataset.observations
+        .iter()
+        .map(|observation| observation.values.iter().filter(|value| value.is_none()).count())
+        .sum();
+
+    Some(ResponseStats {
+        total_count: find_total_count(response),
+        row_count: dataset.observations.len(),
+        null_count,
+        first_date: dataset.observations.first().map(|observation| observation.date.clone()),
+        last_date: dataset.observations.last().map(|observation| observation.date.clone()),
+    })
+}
+
+/// finds the `totalCount` field of a JSON or an XML response.
+fn find_total_count(response: &str) -> Option<u64> {
+
+    match response.trim_start().chars().next()? {
+        '{' => {
+            let parsed_response: Value = serde_json::from_str(response).ok()?;
+
+            parsed_response.get("totalCount").and_then(Value::as_u64)
+        },
+        '<' => {
+            let value_start = response.find("<totalCount>")? + "<totalCount>".len();
+            let value_end = value_start + response[value_start..].find("</totalCount>")?;
+
+            response[value_start..value_end].trim().parse::<u64>().ok()
+        },
+        _ => None,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compute_stats() {
+        let json_stats = compute_stats(r#"{"totalCount":3,"items":[
+            {"Tarih":"09-12-2011","TP_DK_USD_S":"1.8412","TP_DK_EUR_S":null},
+            {"Tarih":"10-12-2011","TP_DK_USD_S":null,"TP_DK_EUR_S":null},
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8534","TP_DK_EUR_S":"2.4432"}
+        ]}"#).unwrap();
+
+        assert_eq!(Some(3), json_stats.total_count);
+        assert_eq!((3, 3), (json_stats.row_count, json_stats.null_count));
+        assert_eq!(Some("09-12-2011"), json_stats.first_date.as_deref());
+        assert_eq!(Some("12-12-2011"), json_stats.last_date.as_deref());
+
+        let xml_stats = compute_stats(
+            "<document><totalCount>1</totalCount><items><Tarih>13-12-2011</Tarih><TP_DK_USD_S>1.8673</TP_DK_USD_S>\
+            </items></document>"
+        ).unwrap();
+
+        assert_eq!((Some(1), 1, 0), (xml_stats.total_count, xml_stats.row_count, xml_stats.null_count));
+
+        let csv_stats = compute_stats("Tarih,TP_DK_USD_S\n13-12-2011,1.8673").unwrap();
+
+        assert_eq!(None, csv_stats.total_count);
+        assert_eq!(Some("13-12-2011"), csv_stats.last_date.as_deref());
+        assert!(compute_stats("Error: Invalid date.").is_none());
+    }
+}
@@ -0,0 +1,361 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use super::{Dataset, Observation};
+
+use crate::date::calendar::CalendarDate;
+
+
+/// is the extension of the segment files. Other files in the series directories are ignored.
+const SEGMENT_EXTENSION: &str = "seg";
+
+/// is the number of the segments of a series after which they are compacted into a single segment.
+const MAX_SEGMENTS: usize = 8;
+
+/// starts the block of a date window inside of a segment.
+const WINDOW_TAG: &str = "window";
+
+
+/// is the date window of a stored request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct StoreWindow {
+    pub(crate) start: CalendarDate,
+    pub(crate) end: CalendarDate,
+}
+
+impl StoreWindow {
+    fn contains(&self, other: &StoreWindow) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+/// is the observations of a series received for a date window.
+#[derive(Debug, Clone, PartialEq)]
+struct WindowBlock {
+    window: StoreWindow,
+    observations: Vec<(String, Option<f64>)>,
+}
+
+impl WindowBlock {
+    /// gives the observations inside of the window, or None when the dates of the observations cannot be compared
+    /// with the window. Only the daily dates, e.g. "13-12-2011", can be compared.
+    fn find_observations(&self, window: &StoreWindow) -> Option<Vec<(String, Option<f64>)>> {
+
+        if self.window == *window { return Some(self.observations.clone()); }
+
+        if !self.window.contains(window) { return None; }
+
+        let mut observations = Vec::new();
+
+        for (date, value) in &self.observations {
+            let calendar_date = CalendarDate::parse(date)?;
+
+            if window.start <= calendar_date && calendar_date <= window.end { observations.push((date.clone(), *value)); }
+        }
+
+        Some(observations)
+    }
+}
+
+
+/// keeps the historical observations of each series on disk so that repeated windows are served without requesting
+/// EVDS, also in later sessions.
+///
+/// Each series has a directory including append-only segment files. Each write adds a new segment with the received
+/// window and its observations, and the segments are compacted into a single one when they exceed
+/// [`MAX_SEGMENTS`]. The store is best effort: file errors never fail a request, they only cause a miss.
+pub(crate) struct ObservationStore {
+    directory: PathBuf,
+}
+
+impl ObservationStore {
+    /// opens the store in the given directory and creates the directory if it does not exist.
+    pub(crate) fn open(directory: &str) -> Option<ObservationStore> {
+
+        if fs::create_dir_all(directory).is_err() { return None; }
+
+        Some(ObservationStore { directory: PathBuf::from(directory) })
+    }
+
+    /// reads the observations of the series in the window. All of the series must have a window covering it.
+    pub(crate) fn read(&self, series_codes: &[String], window: &StoreWindow) -> Option<Dataset> {
+
+        let mut series_observations = Vec::with_capacity(series_codes.len());
+
+        for series_code in series_codes {
+            let blocks = self.read_blocks(series_code);
+
+            let observations = blocks
+                .iter()
+                .rev()
+                .find_map(|block| block.find_observations(window))?;
+
+            series_observations.push(observations);
+        }
+
+        let mut dates: Vec<String> = Vec::new();
+
+        for (date, _) in series_observations.iter().flatten() {
+            if !dates.contains(date) { dates.push(date.clone()); }
+        }
+
+        if dates.iter().all(|date| CalendarDate::parse(date).is_some()) {
+            dates.sort_by_key(|date| CalendarDate::parse(date).unwrap());
+        }
+
+        let observations = dates
+            .into_iter()
+            .map(|date| {
+                let values = series_observations
+                    .iter()
+                    .map(|observations| {
+                        observations
+                            .iter()
+                            .find(|(observed_date, _)| *observed_date == date)
+                            .and_then(|(_, value)| *value)
+                    })
+                    .collect();
+
+                Observation::new(date, values)
+            })
+            .collect();
+
+        Some(Dataset { series_codes: series_codes.to_vec(), observations })
+    }
+
+    /// appends the observations of each series of the dataset received for the window as a new segment.
+    pub(crate) fn write(&self, window: &StoreWindow, dataset: &Dataset) {
+
+        for (index, series_code) in dataset.series_codes.iter().enumerate() {
+            let observations = dataset.observations
+                .iter()
+                .map(|observation| (observation.date.clone(), observation.values[index]))
+                .collect();
+
+            let block = WindowBlock { window: *window, observations };
+
+            let series_directory = self.find_series_directory(series_code);
+
+            if fs::create_dir_all(&series_directory).is_err() { continue; }
+
+            let segments = find_segments(&series_directory);
+            let next_number = segments.last().map_or(0, |(number, _)| number + 1);
+
+            if write_segment(&series_directory, next_number, &[block]).is_err() { continue; }
+
+            if segments.len() + 1 > MAX_SEGMENTS { self.compact(series_code); }
+        }
+    }
+
+    /// rewrites the segments of the series as a single segment. The blocks of the same window are reduced to the latest
+    /// one.
+    pub(crate) fn compact(&self, series_code: &str) {
+
+        let series_directory = self.find_series_directory(series_code);
+        let segments = find_segments(&series_directory);
+
+        let next_number = match segments.last() {
+            Some((number, _)) => number + 1,
+            None => return,
+        };
+
+        let mut blocks: Vec<WindowBlock> = Vec::new();
+
+        for block in self.read_blocks(series_code) {
+            blocks.retain(|former_block| former_block.window != block.window);
+            blocks.push(block);
+        }
+
+        if write_segment(&series_directory, next_number, &blocks).is_err() { return; }
+
+        for (_, segment_path) in segments {
+            let _ = fs::remove_file(segment_path);
+        }
+    }
+
+    /// reads the blocks of all segments of the series from the oldest to the newest.
+    fn read_blocks(&self, series_code: &str) -> Vec<WindowBlock> {
+
+        let series_directory = self.find_series_directory(series_code);
+
+        find_segments(&series_directory)
+            .iter()
+            .filter_map(|(_, segment_path)| fs::read_to_string(segment_path).ok())
+            .flat_map(|segment| parse_segment(&segment))
+            .collect()
+    }
+
+    fn find_series_directory(&self, series_code: &str) -> PathBuf {
+
+        let directory_name: String = series_code
+            .chars()
+            .map(|character| if character.is_ascii_alphanumeric() { character } else { '_' })
+            .collect();
+
+        self.directory.join(directory_name)
+    }
+}
+
+/// finds the segment files of a series directory ordered by their numbers.
+fn find_segments(series_directory: &Path) -> Vec<(u64, PathBuf)> {
+
+    let entries = match fs::read_dir(series_directory) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut segments: Vec<(u64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == SEGMENT_EXTENSION))
+        .filter_map(|path| {
+            let number = path.file_stem()?.to_str()?.parse::<u64>().ok()?;
+
+            Some((number, path))
+        })
+        .collect();
+
+    segments.sort_by_key(|(number, _)| *number);
+
+    segments
+}
+
+/// writes the blocks into a temporary file first and renames it, so a segment is either complete or missing.
+fn write_segment(series_directory: &Path, number: u64, blocks: &[WindowBlock]) -> std::io::Result<()> {
+
+    let segment_path = series_directory.join(format!("{}.{}", number, SEGMENT_EXTENSION));
+    let temporary_path = series_directory.join(format!("{}.tmp", number));
+
+    let mut file = fs::File::create(&temporary_path)?;
+
+    file.write_all(format_segment(blocks).as_bytes())?;
+    file.sync_all()?;
+
+    fs::rename(temporary_path, segment_path)
+}
+
+fn format_segment(blocks: &[WindowBlock]) -> String {
+
+    let mut segment = String::new();
+
+    for block in blocks {
+        segment.push_str(&format!("{},{},{}\n", WINDOW_TAG, block.window.start.format(), block.window.end.format()));
+
+        for (date, value) in &block.observations {
+            let value = match value {
+                Some(value) => value.to_string(),
+                None => String::new(),
+            };
+
+            segment.push_str(&format!("{},{}\n", date, value));
+        }
+    }
+
+    segment
+}
+
+/// parses the blocks of a segment. The lines that do not belong to any valid window are skipped.
+fn parse_segment(segment: &str) -> Vec<WindowBlock> {
+
+    let mut blocks: Vec<WindowBlock> = Vec::new();
+    let mut is_in_valid_block = false;
+
+    for line in segment.lines() {
+        let mut parts = line.split(',');
+
+        let first_part = parts.next().unwrap_or_default();
+        let second_part = parts.next();
+
+        if first_part == WINDOW_TAG {
+            let start = second_part.and_then(CalendarDate::parse);
+            let end = parts.next().and_then(CalendarDate::parse);
+
+            is_in_valid_block = match (start, end) {
+                (Some(start), Some(end)) => {
+                    blocks.push(WindowBlock { window: StoreWindow { start, end }, observations: Vec::new() });
+
+                    true
+                },
+                _ => false,
+            };
+
+            continue;
+        }
+
+        if !is_in_valid_block || first_part.is_empty() { continue; }
+
+        let value = second_part.and_then(|value| value.parse::<f64>().ok());
+
+        blocks.last_mut().unwrap().observations.push((first_part.to_string(), value));
+    }
+
+    blocks
+}
+
+static OBSERVATION_STORE: Mutex<Option<ObservationStore>> = Mutex::new(None);
+
+
+/// gives access to the process wide observation store which is None unless a directory is set.
+///
+/// A poisoned lock is recovered because the store keeps only its directory in memory.
+pub(crate) fn get_observation_store() -> MutexGuard<'static, Option<ObservationStore>> {
+    match OBSERVATION_STORE.lock() {
+        Ok(store) => store,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_window(start: &str, end: &str) -> StoreWindow {
+        StoreWindow { start: CalendarDate::parse(start).unwrap(), end: CalendarDate::parse(end).unwrap() }
+    }
+
+    fn generate_dataset(observations: &[(&str, Option<f64>)]) -> Dataset {
+        Dataset {
+            series_codes: vec!["TP_DK_USD_S".to_string()],
+            observations: observations
+                .iter()
+                .map(|(date, value)| Observation::new(date.to_string(), vec![*value]))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn should_store_and_compact_windows() {
+        let directory = std::env::temp_dir().join(format!("tcmb_evds_c_store_{}", std::process::id()));
+        let store = ObservationStore::open(directory.to_str().unwrap()).unwrap();
+
+        let series_codes = vec!["TP_DK_USD_S".to_string()];
+        let window = generate_window("09-12-2011", "13-12-2011");
+        let dataset = generate_dataset(&[("09-12-2011", Some(1.8412)), ("12-12-2011", None), ("13-12-2011", Some(1.8673))]);
+
+        assert!(store.read(&series_codes, &window).is_none());
+
+        store.write(&window, &dataset);
+
+        assert_eq!(Some(dataset.clone()), store.read(&series_codes, &window));
+        assert_eq!(
+            Some(generate_dataset(&[("12-12-2011", None), ("13-12-2011", Some(1.8673))])),
+            store.read(&series_codes, &generate_window("12-12-2011", "13-12-2011"))
+        );
+        assert!(store.read(&series_codes, &generate_window("12-12-2011", "14-12-2011")).is_none());
+
+        for _ in 0..MAX_SEGMENTS {
+            store.write(&window, &dataset);
+        }
+
+        let series_directory = store.find_series_directory("TP_DK_USD_S");
+
+        assert_eq!(1, find_segments(&series_directory).len());
+        assert_eq!(1, store.read_blocks("TP_DK_USD_S").len());
+        assert_eq!(Some(dataset), store.read(&series_codes, &window));
+
+        let _ = fs::remove_dir_all(directory);
+    }
+}
@@ -0,0 +1,275 @@
+use serde_json::Value;
+
+use super::Dataset;
+
+use crate::error::ReturnError;
+
+
+/// are the words of the magnitudes in Turkish and English series names.
+const MAGNITUDE_WORDS: [(&str, Magnitude); 8] = [
+    ("bin", Magnitude::Thousand),
+    ("thousand", Magnitude::Thousand),
+    ("milyon", Magnitude::Million),
+    ("million", Magnitude::Million),
+    ("mn", Magnitude::Million),
+    ("milyar", Magnitude::Billion),
+    ("billion", Magnitude::Billion),
+    ("bn", Magnitude::Billion),
+];
+
+/// are the names of the currencies in Turkish and English series names and their codes.
+const CURRENCY_NAMES: [(&str, &str); 12] = [
+    ("tl", "TRY"),
+    ("try", "TRY"),
+    ("türk lirası", "TRY"),
+    ("turkish lira", "TRY"),
+    ("usd", "USD"),
+    ("abd doları", "USD"),
+    ("us dollar", "USD"),
+    ("us dollars", "USD"),
+    ("eur", "EUR"),
+    ("euro", "EUR"),
+    ("avro", "EUR"),
+    ("euros", "EUR"),
+];
+
+/// are the words of the percentages in Turkish and English series names.
+const PERCENT_WORDS: [&str; 3] = ["%", "yüzde", "percent"];
+
+
+/// is the multiplier of the values of a series, e.g. `Thousand` for "Bin TL".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Magnitude {
+    One,
+    Thousand,
+    Million,
+    Billion,
+}
+
+impl Magnitude {
+    fn factor(self) -> f64 {
+        match self {
+            Magnitude::One => 1.0,
+            Magnitude::Thousand => 1_000.0,
+            Magnitude::Million => 1_000_000.0,
+            Magnitude::Billion => 1_000_000_000.0,
+        }
+    }
+
+    fn to_str(self) -> &'static str {
+        match self {
+            Magnitude::One => "",
+            Magnitude::Thousand => "thousand",
+            Magnitude::Million => "million",
+            Magnitude::Billion => "billion",
+        }
+    }
+}
+
+/// is what the values of a series measure.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Measure {
+    /// is an amount of a currency such as "TRY" or "USD".
+    Currency(String),
+    /// is an index whose base year is 100, e.g. 2003 for "2003=100".
+    Index(u32),
+    Percent,
+}
+
+/// is the unit of a series parsed from its name, e.g. "Toplam Mevduat (Bin TL)" is in thousand TRY.
+///
+/// A unit without a measure only specifies a magnitude, e.g. "(Milyon)", and is also used as a target unit to rescale
+/// the magnitudes without checking the measures.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SeriesUnit {
+    pub(crate) magnitude: Magnitude,
+    pub(crate) measure: Option<Measure>,
+}
+
+impl SeriesUnit {
+    /// parses a unit written as an optional magnitude followed by an optional measure, e.g. "thousand TRY",
+    /// "Milyon ABD Doları", "million", "TL", "%" or "2003=100".
+    ///
+    /// A foreign currency without a magnitude is not a unit since series names such as "(USD) ABD Doları" name the
+    /// currency of an exchange rate whose values are in TRY.
+    pub(crate) fn parse(text: &str) -> Option<SeriesUnit> {
+
+        let text = text.trim().to_lowercase();
+
+        if PERCENT_WORDS.contains(&text.as_str()) {
+            return Some(SeriesUnit { magnitude: Magnitude::One, measure: Some(Measure::Percent) });
+        }
+
+        if let Some(base_year) = parse_index_base(&text) {
+            return Some(SeriesUnit { magnitude: Magnitude::One, measure: Some(Measure::Index(base_year)) });
+        }
+
+        let (magnitude, currency_name) = match text.split_once(' ') {
+            Some((first_word, rest)) => match find_magnitude(first_word) {
+                Some(magnitude) => (Some(magnitude), rest.trim()),
+                None => (None, text.as_str()),
+            },
+            None => match find_magnitude(&text) {
+                Some(magnitude) => (Some(magnitude), ""),
+                None => (None, text.as_str()),
+            },
+        };
+
+        let currency_code = CURRENCY_NAMES
+            .iter()
+            .find(|(name, _)| *name == currency_name)
+            .map(|(_, code)| code.to_string());
+
+        match (magnitude, currency_code) {
+            (Some(magnitude), None) if currency_name.is_empty() => Some(SeriesUnit { magnitude, measure: None }),
+            (Some(magnitude), Some(code)) => Some(SeriesUnit { magnitude, measure: Some(Measure::Currency(code)) }),
+            (None, Some(code)) if code == "TRY" => {
+                Some(SeriesUnit { magnitude: Magnitude::One, measure: Some(Measure::Currency(code)) })
+            },
+            _ => None,
+        }
+    }
+
+    /// parses the unit from the first parenthesized part of a series name that is a unit.
+    pub(crate) fn from_series_name(series_name: &str) -> Option<SeriesUnit> {
+        series_name
+            .split('(')
+            .skip(1)
+            .filter_map(|part| part.split_once(')'))
+            .find_map(|(part, _)| SeriesUnit::parse(part))
+    }
+
+    /// stringifies the unit in English, e.g. "thousand TRY", "index 2003=100" or "percent".
+    pub(crate) fn to_label(&self) -> String {
+
+        let measure = match &self.measure {
+            Some(Measure::Currency(code)) => code.clone(),
+            Some(Measure::Index(base_year)) => format!("index {}=100", base_year),
+            Some(Measure::Percent) => "percent".to_string(),
+            None => String::new(),
+        };
+
+        match (self.magnitude, measure.is_empty()) {
+            (Magnitude::One, _) => measure,
+            (magnitude, true) => magnitude.to_str().to_string(),
+            (magnitude, false) => format!("{} {}", magnitude.to_str(), measure),
+        }
+    }
+}
+
+
+/// finds the unit of a series from a JSON series list response of
+/// [`get_series_list`](crate::evds_basic::get_series_list). The English name is preferred over the Turkish one.
+pub(crate) fn find_series_unit(series_list: &str) -> Option<SeriesUnit> {
+
+    let parsed_list: Value = serde_json::from_str(series_list).ok()?;
+    let item = parsed_list.as_array()?.first()?;
+
+    ["SERIE_NAME_ENG", "SERIE_NAME"]
+        .iter()
+        .filter_map(|key| item.get(*key).and_then(Value::as_str))
+        .find_map(SeriesUnit::from_series_name)
+}
+
+/// rescales the values of each series of the dataset from its unit to the magnitude of the target unit, and updates
+/// the units.
+///
+/// The dataset is left unchanged when any of the series cannot be rescaled.
+///
+/// # Error
+///
+/// This function returns `IncompatibleUnits` error when the unit of a series is unknown, its measure differs from the
+/// measure of the target unit, or a percentage or an index is rescaled to another magnitude.
+pub(crate) fn rescale_dataset(
+    dataset: &mut Dataset,
+    units: &mut [Option<SeriesUnit>],
+    target_unit: &SeriesUnit
+) -> Result<(), ReturnError> {
+
+    let mut factors = Vec::with_capacity(units.len());
+
+    for unit in units.iter() {
+        let unit = match unit {
+            Some(unit) => unit,
+            None => return Err(ReturnError::IncompatibleUnits),
+        };
+
+        let is_measure_compatible = target_unit.measure.is_none() || target_unit.measure == unit.measure;
+        let is_scalable = !matches!(unit.measure, Some(Measure::Index(_)) | Some(Measure::Percent));
+
+        if !is_measure_compatible { return Err(ReturnError::IncompatibleUnits); }
+        if !is_scalable && target_unit.magnitude != unit.magnitude { return Err(ReturnError::IncompatibleUnits); }
+
+        factors.push(unit.magnitude.factor() / target_unit.magnitude.factor());
+    }
+
+    for observation in dataset.observations.iter_mut() {
+        for (value, factor) in observation.values.iter_mut().zip(factors.iter()) {
+            if let Some(value) = value { *value *= factor; }
+        }
+    }
+
+    for unit in units.iter_mut().flatten() {
+        unit.magnitude = target_unit.magnitude;
+    }
+
+    Ok(())
+}
+
+fn find_magnitude(word: &str) -> Option<Magnitude> {
+    MAGNITUDE_WORDS
+        .iter()
+        .find(|(magnitude_word, _)| *magnitude_word == word)
+        .map(|(_, magnitude)| *magnitude)
+}
+
+/// parses the base year of an index such as "2003=100".
+fn parse_index_base(text: &str) -> Option<u32> {
+
+    let (base_year, hundred) = text.split_once('=')?;
+
+    if hundred.trim() != "100" { return None; }
+
+    base_year.trim().parse::<u32>().ok().filter(|base_year| (1900..=2100).contains(base_year))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dataset::Observation;
+
+    #[test]
+    fn should_parse_and_rescale_units() {
+        let thousand_try = SeriesUnit::from_series_name("Toplam Mevduat (Bin TL)").unwrap();
+
+        assert_eq!("thousand TRY", thousand_try.to_label());
+        assert_eq!("million USD", SeriesUnit::from_series_name("Reserves (Million US Dollars)").unwrap().to_label());
+        assert_eq!("index 2003=100", SeriesUnit::from_series_name("TÜFE (2003=100)").unwrap().to_label());
+        assert_eq!("percent", SeriesUnit::from_series_name("Politika Faizi (%)").unwrap().to_label());
+        assert!(SeriesUnit::from_series_name("(USD) ABD Doları (Döviz Alış)").is_none());
+
+        let series_list = r#"[{"SERIE_CODE":"TP.X","SERIE_NAME":"X (Bin TL)","SERIE_NAME_ENG":"X (Thousand TRY)"}]"#;
+        assert_eq!(Some(thousand_try.clone()), find_series_unit(series_list));
+
+        let mut dataset = Dataset {
+            series_codes: vec!["TP_X".to_string(), "TP_Y".to_string()],
+            observations: vec![Observation::new("2021-1".to_string(), vec![Some(2500.0), None])],
+        };
+        let mut units = vec![Some(thousand_try.clone()), SeriesUnit::parse("milyon TL")];
+
+        rescale_dataset(&mut dataset, &mut units, &SeriesUnit::parse("million TRY").unwrap()).unwrap();
+
+        assert_eq!(Some(2.5), dataset.observations[0].values[0]);
+        assert_eq!(Some("million TRY".to_string()), units[0].as_ref().map(SeriesUnit::to_label));
+
+        let mut index_units = vec![SeriesUnit::parse("2003=100"), None];
+
+        assert_eq!(
+            Err(ReturnError::IncompatibleUnits),
+            rescale_dataset(&mut dataset, &mut index_units, &SeriesUnit::parse("million").unwrap())
+        );
+        assert_eq!(Some(2.5), dataset.observations[0].values[0]);
+    }
+}
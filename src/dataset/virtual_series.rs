@@ -0,0 +1,100 @@
+use std::sync::{Mutex, MutexGuard};
+
+use super::expression::{Expression, is_series_character};
+
+use crate::error::ReturnError;
+
+
+/// keeps the virtual series registered by users, which are defined as expressions over real series, e.g.
+/// `SPREAD = TP.DK.EUR.S - TP.DK.USD.S`, and requested like real series by their names.
+pub(crate) struct VirtualSeriesRegistry {
+    definitions: Vec<(String, Expression)>,
+}
+
+impl VirtualSeriesRegistry {
+    pub(crate) const fn new() -> VirtualSeriesRegistry {
+        VirtualSeriesRegistry { definitions: Vec::new() }
+    }
+
+    /// adds or replaces the definition of the virtual series.
+    pub(crate) fn define(&mut self, name: String, expression: Expression) {
+
+        self.definitions.retain(|(defined_name, _)| *defined_name != name);
+
+        self.definitions.push((name, expression));
+    }
+
+    /// removes the virtual series. False is returned when it is not defined.
+    pub(crate) fn remove(&mut self, name: &str) -> bool {
+
+        let definition_count = self.definitions.len();
+
+        self.definitions.retain(|(defined_name, _)| defined_name != name);
+
+        self.definitions.len() != definition_count
+    }
+
+    /// gives a copy of the expression of the virtual series.
+    pub(crate) fn get(&self, name: &str) -> Option<Expression> {
+        self.definitions
+            .iter()
+            .find(|(defined_name, _)| defined_name == name)
+            .map(|(_, expression)| expression.clone())
+    }
+}
+
+static VIRTUAL_SERIES: Mutex<VirtualSeriesRegistry> = Mutex::new(VirtualSeriesRegistry::new());
+
+
+/// gives access to the process wide virtual series.
+///
+/// A poisoned lock is recovered because a panic cannot leave a definition half added.
+pub(crate) fn get_virtual_series() -> MutexGuard<'static, VirtualSeriesRegistry> {
+    match VIRTUAL_SERIES.lock() {
+        Ok(virtual_series) => virtual_series,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+/// checks the name of a virtual series, which must start with a letter and include only the characters of the series
+/// codes, so that it is not confused with a list of series separated by dashes.
+///
+/// # Error
+///
+/// This function returns `InvalidExpression` error when the name is empty or has another character.
+pub(crate) fn check_virtual_series_name(name: &str) -> Result<(), ReturnError> {
+
+    let starts_with_letter = name.chars().next().is_some_and(|character| character.is_ascii_alphabetic());
+
+    if starts_with_letter && name.chars().all(is_series_character) { return Ok(()); }
+
+    Err(ReturnError::InvalidExpression(format!("the name \"{}\" is not a valid series name", name)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_define_and_remove_virtual_series() {
+        let mut registry = VirtualSeriesRegistry::new();
+
+        registry.define("SPREAD".to_string(), Expression::parse("TP.DK.EUR.S - TP.DK.USD.S").unwrap());
+        registry.define("SPREAD".to_string(), Expression::parse("TP.DK.EUR.A - TP.DK.USD.A").unwrap());
+
+        assert_eq!(
+            vec!["TP.DK.EUR.A".to_string(), "TP.DK.USD.A".to_string()],
+            registry.get("SPREAD").unwrap().series_codes()
+        );
+        assert!(registry.remove("SPREAD"));
+        assert!(!registry.remove("SPREAD"));
+        assert!(registry.get("SPREAD").is_none());
+
+        assert!(check_virtual_series_name("EUR_USD.SPREAD").is_ok());
+        assert!(check_virtual_series_name("EUR-USD").is_err());
+        assert!(check_virtual_series_name("1SPREAD").is_err());
+        assert!(check_virtual_series_name("").is_err());
+    }
+}
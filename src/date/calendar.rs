@@ -0,0 +1,238 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+
+/// is the offset of Europe/Istanbul from UTC. Turkey stays at UTC+03:00 all year since 2016.
+pub(crate) const ISTANBUL_UTC_OFFSET_MINUTES: i32 = 180;
+
+/// is the offset of Europe/Istanbul from UTC in winter until 2016.
+const ISTANBUL_WINTER_UTC_OFFSET_MINUTES: i32 = 120;
+
+/// is the first year that Turkey stays on summer time all year. The summer time started in March 2016 never ended.
+const PERMANENT_SUMMER_TIME_YEAR: i64 = 2016;
+
+/// are the summer time periods of Turkey that do not follow the last Sundays of March and October, as (year, the day
+/// in March the summer time starts, the day and month it ends).
+const SHIFTED_SUMMER_TIMES: [(i64, u32, (u32, u32)); 3] = [
+    (2011, 28, (30, 10)),
+    (2014, 31, (26, 10)),
+    (2015, 29, (8, 11)),
+];
+
+/// is the westernmost offset from UTC used around the world, namely UTC-12:00.
+pub(crate) const MIN_UTC_OFFSET_MINUTES: i32 = -720;
+
+/// is the easternmost offset from UTC used around the world, namely UTC+14:00.
+pub(crate) const MAX_UTC_OFFSET_MINUTES: i32 = 840;
+
+/// is the last year written in "dd-mm-yyyy" format, which also keeps the day arithmetic of the dates from overflowing.
+const MAX_YEAR: i64 = 9999;
+
+
+/// is a day of the proleptic Gregorian calendar used for date arithmetic such as shifting a date by some days.
+///
+/// The conversion between days and civil dates follows the well known *days from civil* algorithm so that no external
+/// date library is needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct CalendarDate {
+    pub(crate) year: i64,
+    pub(crate) month: u32,
+    pub(crate) day: u32,
+}
+
+impl CalendarDate {
+    /// creates a calendar date if the given values point an existing day.
+    pub(crate) fn from(day: u32, month: u32, year: i64) -> Option<CalendarDate> {
+        if year > MAX_YEAR { return None; }
+        if !(1..=12).contains(&month) { return None; }
+        if day == 0 || day > days_in_month(month, year) { return None; }
+
+        Some(CalendarDate { year, month, day })
+    }
+
+    /// parses a date in "dd-mm-yyyy" format. Returns None if the text is not a date of the calendar.
+    pub(crate) fn parse(date: &str) -> Option<CalendarDate> {
+        let mut parts = date.split('-');
+
+        let day = parts.next()?.parse::<u32>().ok()?;
+        let month = parts.next()?.parse::<u32>().ok()?;
+        let year = parts.next()?.parse::<i64>().ok()?;
+
+        if parts.next().is_some() { return None; }
+
+        CalendarDate::from(day, month, year)
+    }
+
+    /// creates the calendar date from the number of days passed since 01-01-1970.
+    pub(crate) fn from_days(days: i64) -> CalendarDate {
+        let shifted_days = days + 719_468;
+        let era = shifted_days.div_euclid(146_097);
+        let day_of_era = shifted_days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        CalendarDate { year, month, day }
+    }
+
+    /// gives the number of days passed since 01-01-1970.
+    pub(crate) fn to_days(self) -> i64 {
+        let year = if self.month <= 2 { self.year - 1 } else { self.year };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = self.month as i64;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// gives today at the given offset from UTC in minutes regardless of the local time zone of the machine.
+    ///
+    /// CBRT publishes data on Turkish time, so [`ISTANBUL_UTC_OFFSET_MINUTES`] is the offset used by default.
+    pub(crate) fn today(utc_offset_minutes: i32) -> CalendarDate {
+        let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() as i64,
+            Err(_) => 0,
+        };
+
+        let local_seconds = seconds + utc_offset_minutes as i64 * 60;
+
+        CalendarDate::from_days(local_seconds.div_euclid(86_400))
+    }
+
+    /// shifts the date by the given number of days. Negative values move the date backwards.
+    pub(crate) fn add_days(self, days: i64) -> CalendarDate {
+        CalendarDate::from_days(self.to_days() + days)
+    }
+
+    /// gives the day of the week starting from Monday as 0 to Sunday as 6.
+    pub(crate) fn weekday(self) -> u32 {
+        // 01-01-1970 is Thursday.
+        (self.to_days() + 3).rem_euclid(7) as u32
+    }
+
+    /// checks the date is whether Saturday or Sunday.
+    pub(crate) fn is_weekend(self) -> bool {
+        self.weekday() >= 5
+    }
+
+    /// gives the seconds passed since 01-01-1970 00:00 UTC until the start of the date on Turkish time, which is the
+    /// `UNIXTIME` of the date in EVDS responses, e.g. 1323727200 for "13-12-2011".
+    pub(crate) fn to_epoch_seconds(self) -> i64 {
+        self.to_days() * 86_400 - istanbul_utc_offset_minutes(self) as i64 * 60
+    }
+
+    /// gives the date on Turkish time of the given seconds passed since 01-01-1970 00:00 UTC.
+    pub(crate) fn from_epoch_seconds(epoch_seconds: i64) -> CalendarDate {
+
+        // the largest offset gives the latest possible date, which is one day later at most.
+        let latest_date = CalendarDate::from_days(
+            (epoch_seconds + ISTANBUL_UTC_OFFSET_MINUTES as i64 * 60).div_euclid(86_400)
+        );
+
+        if latest_date.to_epoch_seconds() > epoch_seconds { latest_date.add_days(-1) } else { latest_date }
+    }
+
+    /// stringifies the date in "dd-mm-yyyy" format that EVDS expects.
+    pub(crate) fn format(self) -> String {
+        format!("{:02}-{:02}-{:04}", self.day, self.month, self.year)
+    }
+}
+
+/// gives the offset of Europe/Istanbul from UTC at the start of the date.
+///
+/// Until 2016, Turkey was on UTC+02:00 in winter and on UTC+03:00 in summer. The summer time starts at 03:00 on the
+/// last Sunday of March and ends at 04:00 on the last Sunday of October except [`SHIFTED_SUMMER_TIMES`], so the start
+/// of the first day is still in winter and the start of the last day is still in summer.
+pub(crate) fn istanbul_utc_offset_minutes(date: CalendarDate) -> i32 {
+
+    if date.year > PERMANENT_SUMMER_TIME_YEAR { return ISTANBUL_UTC_OFFSET_MINUTES; }
+
+    let shifted_summer_time = SHIFTED_SUMMER_TIMES.iter().find(|(year, _, _)| *year == date.year);
+
+    let (start_day, (end_day, end_month)) = match shifted_summer_time {
+        Some((_, start_day, end)) => (*start_day, *end),
+        None => (find_last_sunday(3, date.year), (find_last_sunday(10, date.year), 10)),
+    };
+
+    let start = CalendarDate { year: date.year, month: 3, day: start_day };
+    let end = CalendarDate { year: date.year, month: end_month, day: end_day };
+
+    let is_summer_time = start < date && (date.year == PERMANENT_SUMMER_TIME_YEAR || date <= end);
+
+    if is_summer_time { ISTANBUL_UTC_OFFSET_MINUTES } else { ISTANBUL_WINTER_UTC_OFFSET_MINUTES }
+}
+
+fn find_last_sunday(month: u32, year: i64) -> u32 {
+
+    let last_day = days_in_month(month, year);
+    let weekday = CalendarDate { year, month, day: last_day }.weekday();
+
+    last_day - (weekday + 1) % 7
+}
+
+/// checks the year is whether leap year or not.
+pub(crate) fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// gives the number of days of the given month.
+pub(crate) fn days_in_month(month: u32, year: i64) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_days() {
+        let date = CalendarDate::parse("13-12-2011").unwrap();
+
+        assert_eq!(date, CalendarDate::from_days(date.to_days()));
+        assert_eq!(0, CalendarDate::parse("01-01-1970").unwrap().to_days());
+        assert_eq!("01-03-2020", CalendarDate::parse("28-02-2020").unwrap().add_days(2).format());
+        assert_eq!("31-12-2019", CalendarDate::parse("01-01-2020").unwrap().add_days(-1).format());
+
+        let utc_today = CalendarDate::today(0).to_days();
+        let istanbul_today = CalendarDate::today(ISTANBUL_UTC_OFFSET_MINUTES).to_days();
+
+        assert!(istanbul_today - utc_today == 0 || istanbul_today - utc_today == 1);
+        assert!(CalendarDate::today(MIN_UTC_OFFSET_MINUTES) < CalendarDate::today(MAX_UTC_OFFSET_MINUTES));
+
+        // 13-12-2011 is Tuesday.
+        assert_eq!(1, date.weekday());
+        assert!(CalendarDate::parse("17-12-2011").unwrap().is_weekend());
+
+        assert_eq!(Some(date), CalendarDate::from(13, 12, 2011));
+        assert!(CalendarDate::parse("29-02-2021").is_none());
+        assert!(CalendarDate::parse("29-02-2020").is_some());
+        assert!(CalendarDate::parse("13-12").is_none());
+        assert!(CalendarDate::parse("01-01-77777777777777770").is_none());
+
+        assert_eq!(1323727200, date.to_epoch_seconds());
+        assert_eq!(date, CalendarDate::from_epoch_seconds(1323727200));
+        assert_eq!(date, CalendarDate::from_epoch_seconds(1323727200 + 86_399));
+        assert_eq!(120, istanbul_utc_offset_minutes(CalendarDate::parse("28-03-2011").unwrap()));
+        assert_eq!(180, istanbul_utc_offset_minutes(CalendarDate::parse("29-03-2011").unwrap()));
+        assert_eq!(120, istanbul_utc_offset_minutes(CalendarDate::parse("27-03-2016").unwrap()));
+        assert_eq!(180, istanbul_utc_offset_minutes(CalendarDate::parse("13-12-2016").unwrap()));
+        assert_eq!(180, istanbul_utc_offset_minutes(CalendarDate::parse("08-11-2015").unwrap()));
+        assert_eq!(120, istanbul_utc_offset_minutes(CalendarDate::parse("09-11-2015").unwrap()));
+        assert_eq!(180, istanbul_utc_offset_minutes(CalendarDate::parse("13-12-2021").unwrap()));
+        assert_eq!(1639342800, CalendarDate::parse("13-12-2021").unwrap().to_epoch_seconds());
+
+        assert_eq!(29, days_in_month(2, 2020));
+        assert_eq!(28, days_in_month(2, 2021));
+    }
+}
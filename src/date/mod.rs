@@ -1,9 +1,11 @@
 use std::str;
 
-mod date_validation_basics;
+pub(crate) mod calendar;
+pub(crate) mod publication_calendar;
 
-use self::date_validation_basics::*;
+use self::calendar::CalendarDate;
 
+use crate::evds_core::date::{is_alignment_valid, is_each_value_valid};
 use crate::traits::{self, HavingDateValidation};
 use crate::error::ReturnError;
 
@@ -217,6 +219,7 @@ pub(crate) enum DatePreference {
 
 impl DatePreference {
     /// checks date preference is wether single or not.
+    #[cfg(any(feature = "currency", feature = "parse", feature = "export"))]
     pub(crate) fn is_single(&self) -> Result<(), ReturnError> {
         if let DatePreference::Multiple(_) = self {
             return Err(ReturnError::SingleDateExpected);
@@ -226,6 +229,7 @@ impl DatePreference {
     }
 
     /// checks date preference is wether single or not.
+    #[cfg(any(feature = "currency", feature = "parse", feature = "export"))]
     pub(crate) fn is_multiple(&self) -> Result<(), ReturnError> {
         if let DatePreference::Single(_) = self {
             return Err(ReturnError::MultipleDateExpected);
@@ -234,6 +238,14 @@ impl DatePreference {
         Ok(())
     }
 
+    /// gives the number of days from the start date to the end date, which is zero for a *Single* date. None is 
+    /// returned when a date is not a calendar date.
+    pub(crate) fn count_days(&self) -> Option<i64> {
+        let (start_date, end_date) = self.get_dates();
+
+        Some(CalendarDate::parse(end_date)?.to_days() - CalendarDate::parse(start_date)?.to_days())
+    }
+
     /// gives the same dates in a tuple if date preference is *Single* or 
     /// start and end dates if date preference is *Multiple*.
     pub(crate) fn get_dates(&self) -> (&str, &str) {
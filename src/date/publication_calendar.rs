@@ -0,0 +1,118 @@
+use super::calendar::CalendarDate;
+
+
+/// are the national holidays having the same day each year as (month, day, first year of the holiday).
+///
+/// 15 July became a national holiday in 2017.
+const FIXED_HOLIDAYS: [(u32, u32, i64); 7] = [
+    (1, 1, 0),
+    (4, 23, 0),
+    (5, 1, 0),
+    (5, 19, 0),
+    (7, 15, 2017),
+    (8, 30, 0),
+    (10, 29, 0),
+];
+
+/// is a day of a year as (month, day).
+type MonthDay = (u32, u32);
+
+/// are the first days of the religious holidays as (year, (month, day) of Ramazan Bayramı, (month, day) of Kurban
+/// Bayramı) according to the calendar of the Presidency of Religious Affairs.
+///
+/// Ramazan Bayramı lasts three days and Kurban Bayramı lasts four days. The religious holidays of the years out of the
+/// table are unknown.
+const RELIGIOUS_HOLIDAYS: [(i64, MonthDay, MonthDay); 13] = [
+    (2015, (7, 17), (9, 24)),
+    (2016, (7, 5), (9, 12)),
+    (2017, (6, 25), (9, 1)),
+    (2018, (6, 15), (8, 21)),
+    (2019, (6, 4), (8, 11)),
+    (2020, (5, 24), (7, 31)),
+    (2021, (5, 13), (7, 20)),
+    (2022, (5, 2), (7, 9)),
+    (2023, (4, 21), (6, 28)),
+    (2024, (4, 10), (6, 16)),
+    (2025, (3, 30), (6, 6)),
+    (2026, (3, 20), (5, 27)),
+    (2027, (3, 9), (5, 16)),
+];
+
+const RAMAZAN_BAYRAMI_LENGTH: i64 = 3;
+const KURBAN_BAYRAMI_LENGTH: i64 = 4;
+
+
+/// checks the date is whether a national or religious holiday in Turkey or not.
+///
+/// The eves of the holidays are half days and CBRT still publishes exchange rates on them, so they are not holidays.
+/// Returns None if the date is not a national holiday and its year is out of [`RELIGIOUS_HOLIDAYS`], namely before
+/// 2015 or after 2027, since whether it is a religious holiday is unknown.
+pub(crate) fn is_holiday(date: CalendarDate) -> Option<bool> {
+
+    let is_fixed_holiday = FIXED_HOLIDAYS
+        .iter()
+        .any(|&(month, day, first_year)| date.month == month && date.day == day && date.year >= first_year);
+
+    if is_fixed_holiday { return Some(true); }
+
+    let &(_, ramazan_bayrami, kurban_bayrami) = RELIGIOUS_HOLIDAYS.iter().find(|&&(year, _, _)| year == date.year)?;
+
+    let is_in_holiday = |(month, day): MonthDay, length: i64| {
+        let first_day = CalendarDate { year: date.year, month, day }.to_days();
+        let days_passed = date.to_days() - first_day;
+
+        (0..length).contains(&days_passed)
+    };
+
+    Some(is_in_holiday(ramazan_bayrami, RAMAZAN_BAYRAMI_LENGTH) || is_in_holiday(kurban_bayrami, KURBAN_BAYRAMI_LENGTH))
+}
+
+/// checks whether CBRT publishes exchange rates at the date, namely the date is neither a weekend nor a holiday.
+///
+/// Returns None if it is unknown, see [`is_holiday`].
+pub(crate) fn is_publication_day(date: CalendarDate) -> Option<bool> {
+
+    if date.is_weekend() { return Some(false); }
+
+    is_holiday(date).map(|is_holiday| !is_holiday)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(date: &str) -> Option<bool> {
+        is_publication_day(CalendarDate::parse(date).unwrap())
+    }
+
+    #[test]
+    fn should_find_publication_days() {
+        // Tuesday.
+        assert_eq!(Some(true), check("13-12-2016"));
+        // Saturday.
+        assert_eq!(Some(false), check("17-12-2016"));
+        // Republic Day on Monday.
+        assert_eq!(Some(false), check("29-10-2018"));
+        // 15 July is a holiday since 2017.
+        assert_eq!(Some(true), check("15-07-2016"));
+        assert_eq!(Some(false), check("15-07-2019"));
+        // Kurban Bayramı of 2020 continues into August.
+        assert_eq!(Some(false), check("03-08-2020"));
+        assert_eq!(Some(true), check("04-08-2020"));
+        // The eve of Ramazan Bayramı of 2024.
+        assert_eq!(Some(true), check("09-04-2024"));
+        assert_eq!(Some(false), check("10-04-2024"));
+    }
+
+    #[test]
+    fn should_not_know_religious_holidays_out_of_table() {
+        // Ramazan Bayramı of 2011, which is out of the table.
+        assert_eq!(None, check("31-08-2011"));
+        assert_eq!(None, check("13-12-2011"));
+        assert_eq!(None, check("13-12-2028"));
+        // Weekends and national holidays are known in any year.
+        assert_eq!(Some(false), check("17-12-2011"));
+        assert_eq!(Some(false), check("29-10-2030"));
+    }
+}
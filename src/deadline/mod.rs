@@ -0,0 +1,221 @@
+use std::cell::Cell;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::error::ReturnError;
+
+
+static LIMITS: Mutex<Limits> = Mutex::new(Limits { call_deadline: None, attempt_timeout: None });
+
+thread_local! {
+    /// is the deadline of the FFI call the thread is in.
+    static CALL_DEADLINE: Cell<Option<Deadline>> = const { Cell::new(None) };
+}
+
+
+/// are the process wide time limits of the calls. None means no limit.
+struct Limits {
+    /// is the overall budget of an FFI call including all of its attempts, retries and waits.
+    call_deadline: Option<Duration>,
+    /// is the budget of a single attempt of a request.
+    attempt_timeout: Option<Duration>,
+}
+
+/// is the time budget of a call started at a moment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Deadline {
+    start: Instant,
+    budget: Duration,
+}
+
+/// ends the deadline of the call when dropped.
+pub(crate) struct CallGuard {
+    is_outermost: bool,
+}
+
+
+impl Deadline {
+    pub(crate) fn new(start: Instant, budget: Duration) -> Deadline {
+        Deadline { start, budget }
+    }
+
+    /// gives the time left until the deadline.
+    ///
+    /// # Error
+    ///
+    /// This function returns `DeadlineExceeded` error with the elapsed time when there is no time left.
+    pub(crate) fn get_remaining(&self, now: Instant) -> Result<Duration, ReturnError> {
+
+        let elapsed = now.saturating_duration_since(self.start);
+
+        match self.budget.checked_sub(elapsed) {
+            Some(remaining) if !remaining.is_zero() => Ok(remaining),
+            _ => Err(ReturnError::DeadlineExceeded(elapsed.as_millis() as u64)),
+        }
+    }
+
+    /// checks whether the call can wait for the given duration without exceeding the deadline.
+    ///
+    /// # Error
+    ///
+    /// This function returns `DeadlineExceeded` error with the elapsed time instead of waiting in vain when the wait
+    /// would end after the deadline.
+    pub(crate) fn check_wait(&self, wait: Duration, now: Instant) -> Result<(), ReturnError> {
+
+        if wait >= self.get_remaining(now)? {
+            return Err(ReturnError::DeadlineExceeded(now.saturating_duration_since(self.start).as_millis() as u64));
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        if self.is_outermost { CALL_DEADLINE.with(|call_deadline| call_deadline.set(None)); }
+    }
+}
+
+
+/// sets the overall deadline of each FFI call. Zero removes the deadline.
+pub(crate) fn set_call_deadline(call_deadline: Duration) {
+    get_limits().call_deadline = Some(call_deadline).filter(|call_deadline| !call_deadline.is_zero());
+}
+
+/// sets the timeout of each attempt of a request. Zero removes the timeout.
+pub(crate) fn set_attempt_timeout(attempt_timeout: Duration) {
+    get_limits().attempt_timeout = Some(attempt_timeout).filter(|attempt_timeout| !attempt_timeout.is_zero());
+}
+
+/// starts the deadline of an FFI call on the thread. A call made inside of another call keeps the deadline of the
+/// outer call, so the outer budget is never extended.
+pub(crate) fn start_call() -> CallGuard {
+
+    let call_deadline = get_limits().call_deadline;
+
+    CALL_DEADLINE.with(|current_deadline| {
+        if current_deadline.get().is_some() { return CallGuard { is_outermost: false }; }
+
+        current_deadline.set(call_deadline.map(|budget| Deadline::new(Instant::now(), budget)));
+
+        CallGuard { is_outermost: true }
+    })
+}
+
+/// gives the deadline of the call the thread is in, so that the threads working for the call can keep it via
+/// [`inherit_call`].
+#[cfg(feature = "export")]
+pub(crate) fn get_call_deadline() -> Option<Deadline> {
+    CALL_DEADLINE.with(Cell::get)
+}
+
+/// starts the given deadline of a call on a thread working for the call, e.g. a worker of a parallel export. A thread
+/// already in a call keeps its own deadline as with [`start_call`].
+#[cfg(feature = "export")]
+pub(crate) fn inherit_call(call_deadline: Option<Deadline>) -> CallGuard {
+
+    CALL_DEADLINE.with(|current_deadline| {
+        if current_deadline.get().is_some() { return CallGuard { is_outermost: false }; }
+
+        current_deadline.set(call_deadline);
+
+        CallGuard { is_outermost: true }
+    })
+}
+
+/// gives the timeout of the next attempt of a request, which is the attempt timeout or the time left until the
+/// deadline of the call, whichever is shorter. None means the attempt is not limited.
+///
+/// # Error
+///
+/// This function returns `DeadlineExceeded` error when the deadline of the call has passed.
+pub(crate) fn get_attempt_timeout() -> Result<Option<Duration>, ReturnError> {
+
+    let attempt_timeout = get_limits().attempt_timeout;
+    let remaining = get_remaining()?;
+
+    Ok(match (attempt_timeout, remaining) {
+        (Some(attempt_timeout), Some(remaining)) => Some(attempt_timeout.min(remaining)),
+        (attempt_timeout, remaining) => attempt_timeout.or(remaining),
+    })
+}
+
+/// gives the time left until the deadline of the call. None means the call has no deadline.
+///
+/// # Error
+///
+/// This function returns `DeadlineExceeded` error when the deadline of the call has passed.
+pub(crate) fn get_remaining() -> Result<Option<Duration>, ReturnError> {
+    CALL_DEADLINE.with(Cell::get).map(|deadline| deadline.get_remaining(Instant::now())).transpose()
+}
+
+/// checks that the deadline of the call has not passed.
+///
+/// # Error
+///
+/// This function returns `DeadlineExceeded` error when the deadline of the call has passed.
+pub(crate) fn check() -> Result<(), ReturnError> {
+    get_attempt_timeout().map(|_| ())
+}
+
+/// checks that a retry or a backoff can wait for the given duration within the deadline of the call.
+///
+/// # Error
+///
+/// This function returns `DeadlineExceeded` error when the wait would end after the deadline.
+pub(crate) fn check_wait(wait: Duration) -> Result<(), ReturnError> {
+    match CALL_DEADLINE.with(Cell::get) {
+        Some(deadline) => deadline.check_wait(wait, Instant::now()),
+        None => Ok(()),
+    }
+}
+
+/// gives access to the limits. A poisoned lock is recovered since the limits cannot be left half updated.
+fn get_limits() -> MutexGuard<'static, Limits> {
+    match LIMITS.lock() {
+        Ok(limits) => limits,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_limit_waits_to_deadline() {
+        let start = Instant::now();
+        let deadline = Deadline::new(start, Duration::from_millis(500));
+
+        assert_eq!(Ok(Duration::from_millis(300)), deadline.get_remaining(start + Duration::from_millis(200)));
+        assert!(deadline.check_wait(Duration::from_millis(100), start + Duration::from_millis(200)).is_ok());
+
+        assert_eq!(
+            Err(ReturnError::DeadlineExceeded(200)),
+            deadline.check_wait(Duration::from_millis(300), start + Duration::from_millis(200))
+        );
+        assert_eq!(
+            Err(ReturnError::DeadlineExceeded(750)),
+            deadline.get_remaining(start + Duration::from_millis(750))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "export")]
+    fn should_keep_deadline_in_worker_threads() {
+        let call_deadline = Some(Deadline::new(Instant::now(), Duration::from_secs(60)));
+
+        std::thread::spawn(move || {
+            {
+                let _call = inherit_call(call_deadline);
+
+                assert_eq!(call_deadline, get_call_deadline());
+            }
+
+            assert_eq!(None, get_call_deadline());
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(feature = "debug_ffi")]
+use std::collections::{HashMap, VecDeque};
+#[cfg(feature = "debug_ffi")]
+use std::sync::{Mutex, MutexGuard};
+
+use crate::evds_c::memory_entities::AllocationKind;
+
+
+/// is the number of the freed pointers remembered to report double frees. Older pointers are reported as unknown.
+#[cfg(feature = "debug_ffi")]
+const MAX_FREED_POINTERS: usize = 4096;
+
+#[cfg(feature = "debug_ffi")]
+static POINTER_REGISTRY: Mutex<PointerRegistry> = Mutex::new(PointerRegistry::new());
+
+
+/// is the state of a pointer handed to C.
+#[cfg(feature = "debug_ffi")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PointerState {
+    Live(AllocationKind),
+    Freed(AllocationKind),
+}
+
+/// is a wrong use of a pointer by C.
+#[cfg(feature = "debug_ffi")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PointerMisuse {
+    Unknown,
+    AlreadyFreed,
+    WrongKind(AllocationKind),
+}
+
+/// tracks the live and the recently freed pointers handed to C by their addresses.
+#[cfg(feature = "debug_ffi")]
+struct PointerRegistry {
+    states: Option<HashMap<usize, PointerState>>,
+    freed_order: VecDeque<usize>,
+}
+
+#[cfg(feature = "debug_ffi")]
+impl PointerRegistry {
+    const fn new() -> PointerRegistry {
+        PointerRegistry { states: None, freed_order: VecDeque::new() }
+    }
+
+    fn register(&mut self, kind: AllocationKind, address: usize) {
+        self.states.get_or_insert_with(HashMap::new).insert(address, PointerState::Live(kind));
+    }
+
+    /// checks that the pointer is live and of the kind.
+    fn check(&self, kind: AllocationKind, address: usize) -> Result<(), PointerMisuse> {
+        match self.states.as_ref().and_then(|states| states.get(&address)) {
+            Some(PointerState::Live(live_kind)) if *live_kind == kind => Ok(()),
+            Some(PointerState::Live(live_kind)) => Err(PointerMisuse::WrongKind(*live_kind)),
+            Some(PointerState::Freed(_)) => Err(PointerMisuse::AlreadyFreed),
+            None => Err(PointerMisuse::Unknown),
+        }
+    }
+
+    /// marks the live pointer as freed after checking it.
+    fn release(&mut self, kind: AllocationKind, address: usize) -> Result<(), PointerMisuse> {
+        self.check(kind, address)?;
+
+        self.states.get_or_insert_with(HashMap::new).insert(address, PointerState::Freed(kind));
+        self.freed_order.push_back(address);
+
+        if self.freed_order.len() > MAX_FREED_POINTERS {
+            let forgotten_address = self.freed_order.pop_front();
+
+            if let (Some(states), Some(forgotten_address)) = (self.states.as_mut(), forgotten_address) {
+                // the address may be live again after being reused by the allocator.
+                if let Some(PointerState::Freed(_)) = states.get(&forgotten_address) {
+                    states.remove(&forgotten_address);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "debug_ffi")]
+fn get_pointer_registry() -> MutexGuard<'static, PointerRegistry> {
+    match POINTER_REGISTRY.lock() {
+        Ok(registry) => registry,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// writes the diagnostic of the misuse and aborts the process before the heap is corrupted.
+#[cfg(feature = "debug_ffi")]
+fn abort_with_diagnostic(misuse: PointerMisuse, kind: AllocationKind, address: usize, function_name: &str) -> ! {
+
+    let reason = match misuse {
+        PointerMisuse::Unknown => "was not returned from this library".to_string(),
+        PointerMisuse::AlreadyFreed => "is already freed".to_string(),
+        PointerMisuse::WrongKind(live_kind) => format!("is a {:?}, not a {:?}", live_kind, kind),
+    };
+
+    eprintln!(
+        "tcmb_evds_c: {} is called with the {:?} pointer {:#x} that {}. Each object returned from the library must be \
+        freed exactly once via its own free function and must not be used after it is freed.",
+        function_name, kind, address, reason,
+    );
+
+    std::process::abort();
+}
+
+
+/// registers a pointer handed to C when the crate is built with the `debug_ffi` feature. NULL pointers are ignored.
+#[cfg(feature = "debug_ffi")]
+pub(crate) fn register<T>(kind: AllocationKind, pointer: *const T) {
+
+    if pointer.is_null() { return; }
+
+    get_pointer_registry().register(kind, pointer as usize);
+}
+
+#[cfg(not(feature = "debug_ffi"))]
+pub(crate) fn register<T>(_kind: AllocationKind, _pointer: *const T) {}
+
+/// aborts the process when a pointer received from C is not live, e.g. it is already freed, when the crate is built
+/// with the `debug_ffi` feature. NULL pointers are ignored.
+#[cfg(feature = "debug_ffi")]
+pub(crate) fn check_live<T>(kind: AllocationKind, pointer: *const T, function_name: &str) {
+
+    if pointer.is_null() { return; }
+
+    if let Err(misuse) = get_pointer_registry().check(kind, pointer as usize) {
+        abort_with_diagnostic(misuse, kind, pointer as usize, function_name);
+    }
+}
+
+#[cfg(not(feature = "debug_ffi"))]
+pub(crate) fn check_live<T>(_kind: AllocationKind, _pointer: *const T, _function_name: &str) {}
+
+/// marks a pointer received from C as freed, and aborts the process when the pointer is unknown or already freed
+/// instead of corrupting the heap, when the crate is built with the `debug_ffi` feature. NULL pointers are ignored.
+#[cfg(feature = "debug_ffi")]
+pub(crate) fn release<T>(kind: AllocationKind, pointer: *const T, function_name: &str) {
+
+    if pointer.is_null() { return; }
+
+    let released = get_pointer_registry().release(kind, pointer as usize);
+
+    if let Err(misuse) = released {
+        abort_with_diagnostic(misuse, kind, pointer as usize, function_name);
+    }
+}
+
+#[cfg(not(feature = "debug_ffi"))]
+pub(crate) fn release<T>(_kind: AllocationKind, _pointer: *const T, _function_name: &str) {}
+
+
+#[cfg(all(test, feature = "debug_ffi"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_detect_misused_pointers() {
+        let mut registry = PointerRegistry::new();
+
+        registry.register(AllocationKind::Handle, 0x10);
+        registry.register(AllocationKind::Dataset, 0x20);
+
+        assert_eq!(Ok(()), registry.check(AllocationKind::Handle, 0x10));
+        assert_eq!(
+            Err(PointerMisuse::WrongKind(AllocationKind::Dataset)), 
+            registry.check(AllocationKind::Handle, 0x20)
+        );
+        assert_eq!(Err(PointerMisuse::Unknown), registry.release(AllocationKind::Handle, 0x30));
+
+        assert_eq!(Ok(()), registry.release(AllocationKind::Handle, 0x10));
+        assert_eq!(Err(PointerMisuse::AlreadyFreed), registry.release(AllocationKind::Handle, 0x10));
+
+        registry.register(AllocationKind::Handle, 0x10);
+
+        assert_eq!(Ok(()), registry.check(AllocationKind::Handle, 0x10));
+    }
+}
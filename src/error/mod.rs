@@ -1,19 +1,31 @@
 use std::{cmp, error, fmt};
 
+use crate::evds_currency::frequency_formulas::DataFrequency;
+
 
 /// contains specified error options returned from various [`tcmb_evds_c`](crate) operations.
 ///
 /// This enum is returned with an error option to specify what goes wrong.
 ///
 /// Only **ResponseError** option of this enum contains an error message which is a returned response 
-/// against incorrect request.
-#[derive(Debug)]
+/// against incorrect request, **UnexpectedSchema** option contains the first discrepancy of a response from the 
+/// schema of EVDS, **RateLimitedByServer** option contains the wait in seconds suggested by the server, 
+/// **DeadlineExceeded** option contains the elapsed milliseconds of the call, **InvalidSeries** option contains the
+/// constraint of the series syntax failed by the series, **RequestVetoed** option contains the
+/// message of the pre-request hook vetoing the request, **InvalidExpression** option contains the problem of an
+/// expression, **SeasonalAdjustmentFailed** option contains the reason a dataset cannot be seasonally adjusted,
+/// **DateRangeTooLarge** option contains the maximum date span in days, and **HugeRequest** option contains the 
+/// estimated number of the observations with the suggested data frequency. **UnexpectedContentType** option contains
+/// the received content type with the requested return format.
+/// **FailedToApplyRequest**, **DnsFailure**, **TlsError** and **Timeout** options contain the code and the message of
+/// the underlying error of the HTTP backend, e.g. curl, as the detail.
+#[derive(Debug, Clone)]
 pub(crate) enum ReturnError {
     InvalidApiKeyOrBadInternetConnection,
     BadInternetConnection,
     BadInternetConnectionOrInvalidUrl,
     InvalidUrl,
-    InvalidSeries,
+    InvalidSeries(String),
     EmptyParameter,
     InvalidDate,
     EmptyExchangeType,
@@ -25,40 +37,66 @@ pub(crate) enum ReturnError {
     NotFound,
     UnableToRequest,
     UnableToSetUrl,
-    FailedToApplyRequest,
+    FailedToApplyRequest(String),
     FailedToSaveReceivedData,
     ResponseError(String),
     EmptyResponse,
     ForbiddenRequest,
+    UnparsableResponse,
+    InvalidEnumValue,
+    UnsafeQueryCharacter,
+    FailedToWriteFile,
+    IncompatibleUnits,
+    UnsupportedReturnFormat,
+    UnexpectedSchema(String),
+    RateLimitedByServer(u64),
+    ServiceUnavailable,
+    DeadlineExceeded(u64),
+    DnsFailure(String),
+    TlsError(String),
+    Timeout(String),
+    RecordingNotFound,
+    RequestVetoed(String),
+    InvalidExpression(String),
+    SeasonalAdjustmentFailed(String),
+    QueueFull,
+    DateRangeTooLarge(u32),
+    HugeRequest(u64, DataFrequency),
+    UnexpectedContentType(String),
 }
 
 impl ReturnError {
-    /// stringifies returned error in a standard format.
-    pub(crate) fn to_string(&self) -> String {
+    /// tells whether the error is caused by EVDS being unreachable or overloaded rather than by the request, so that 
+    /// a former response of the same request is still worth returning.
+    #[cfg(feature = "cache")]
+    pub(crate) fn is_network_failure(&self) -> bool {
+        matches!(
+            self,
+            ReturnError::InvalidApiKeyOrBadInternetConnection
+            | ReturnError::BadInternetConnection
+            | ReturnError::BadInternetConnectionOrInvalidUrl
+            | ReturnError::FailedToApplyRequest(_)
+            | ReturnError::RateLimitedByServer(_)
+            | ReturnError::ServiceUnavailable
+            | ReturnError::DeadlineExceeded(_)
+            | ReturnError::DnsFailure(_)
+            | ReturnError::TlsError(_)
+            | ReturnError::Timeout(_)
+        )
+    }
+}
+
+impl ReturnError {
+    /// gives the detail of the underlying error, e.g. the code and the message of curl or the failed constraint of an
+    /// invalid series. None is returned when the error has no detail.
+    pub(crate) fn get_detail(&self) -> Option<&str> {
         match self {
-            ReturnError::InvalidApiKeyOrBadInternetConnection => return "Error: Invalid api key or bad internet connection.".to_string(),
-            ReturnError::BadInternetConnection => return "Error: Bad internet connection.".to_string(),
-            ReturnError::BadInternetConnectionOrInvalidUrl => return "Error: Bad internet connection or invalid url.".to_string(),
-            ReturnError::InvalidUrl => return "Error: Invalid url.".to_string(),
-            ReturnError::InvalidSeries => return "Error: Invalid series.".to_string(),
-            ReturnError::EmptyParameter => return "Error: Empty parameter.".to_string(),
-            ReturnError::InvalidDate => return "Error: Invalid date.".to_string(),
-            ReturnError::EmptyExchangeType => return "Error: Empty exchange type.".to_string(),
-            ReturnError::EmptyCurrencyCodes => return "Error: Empty currency codes.".to_string(),
-            ReturnError::SingleExchangeTypeExpected => return "Error: Single exchange type expected.".to_string(),
-            ReturnError::SingleDateExpected => return "Error: Single date expected.".to_string(),
-            ReturnError::MultipleDateExpected => return "Error: Multiple date expected.".to_string(),
-            ReturnError::RequestDenied => return "Error: Request denied.".to_string(),
-            ReturnError::NotFound => return "Error: 404 not found.".to_string(),
-            ReturnError::UnableToRequest => return "Error: Unable to ask for a HTTP GET request.".to_string(),
-            ReturnError::UnableToSetUrl => return "Error: Unable to appropriately set url.".to_string(),
-            ReturnError::FailedToApplyRequest => return "Error: Failed to apply HTTP request.
-            \nHelp: please check the internet connection or the validity of given url.".to_string(),
-            ReturnError::FailedToSaveReceivedData => return "Error: Failed to save received data.".to_string(),
-            ReturnError::ResponseError(message) => return message.to_owned(),
-            ReturnError::EmptyResponse => return "Error: Empty page returned.".to_string(),
-            ReturnError::ForbiddenRequest => return "Error: The request is forbidden.
-            \nHelp: please check given data series is wether single or not.".to_string(),
+            ReturnError::FailedToApplyRequest(detail) 
+            | ReturnError::DnsFailure(detail) 
+            | ReturnError::TlsError(detail) 
+            | ReturnError::Timeout(detail)
+            | ReturnError::InvalidSeries(detail) => Some(detail),
+            _ => None,
         }
     }
 }
@@ -70,9 +108,112 @@ impl cmp::PartialEq for ReturnError {
 }
 
 impl fmt::Display for ReturnError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {         
-        write!(f, "{}", self.to_string())
+    /// writes the error in a standard format.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReturnError::InvalidApiKeyOrBadInternetConnection => {
+                f.write_str("Error: Invalid api key or bad internet connection.")
+            },
+            ReturnError::BadInternetConnection => f.write_str("Error: Bad internet connection."),
+            ReturnError::BadInternetConnectionOrInvalidUrl => {
+                f.write_str("Error: Bad internet connection or invalid url.")
+            },
+            ReturnError::InvalidUrl => f.write_str("Error: Invalid url."),
+            ReturnError::InvalidSeries(constraint) => write!(f, "Error: Invalid series, {}.", constraint),
+            ReturnError::EmptyParameter => f.write_str("Error: Empty parameter."),
+            ReturnError::InvalidDate => f.write_str("Error: Invalid date."),
+            ReturnError::EmptyExchangeType => f.write_str("Error: Empty exchange type."),
+            ReturnError::EmptyCurrencyCodes => f.write_str("Error: Empty currency codes."),
+            ReturnError::SingleExchangeTypeExpected => f.write_str("Error: Single exchange type expected."),
+            ReturnError::SingleDateExpected => f.write_str("Error: Single date expected."),
+            ReturnError::MultipleDateExpected => f.write_str("Error: Multiple date expected."),
+            ReturnError::RequestDenied => f.write_str("Error: Request denied."),
+            ReturnError::NotFound => f.write_str("Error: 404 not found."),
+            ReturnError::UnableToRequest => f.write_str("Error: Unable to ask for a HTTP GET request."),
+            ReturnError::UnableToSetUrl => f.write_str("Error: Unable to appropriately set url."),
+            ReturnError::FailedToApplyRequest(_) => f.write_str("Error: Failed to apply HTTP request.
+            \nHelp: please check the internet connection or the validity of given url."),
+            ReturnError::FailedToSaveReceivedData => f.write_str("Error: Failed to save received data."),
+            ReturnError::ResponseError(message) => f.write_str(message),
+            ReturnError::EmptyResponse => f.write_str("Error: Empty page returned."),
+            ReturnError::ForbiddenRequest => f.write_str("Error: The request is forbidden.
+            \nHelp: please check given data series is wether single or not."),
+            ReturnError::UnparsableResponse => f.write_str("Error: The response could not be parsed."),
+            ReturnError::InvalidEnumValue => f.write_str("Error: The given enum value is out of range."),
+            ReturnError::UnsafeQueryCharacter => {
+                f.write_str("Error: Parameters must not contain '&', '=' or '#' characters.")
+            },
+            ReturnError::FailedToWriteFile => f.write_str("Error: Failed to write into the given directory."),
+            ReturnError::IncompatibleUnits => {
+                f.write_str("Error: Units of the series are unknown or incompatible with the target unit.")
+            },
+            ReturnError::UnsupportedReturnFormat => {
+                f.write_str("Error: The operation is not supported for the requested return format.")
+            },
+            ReturnError::UnexpectedSchema(discrepancy) => {
+                write!(f, "Error: Unexpected response schema, {}.", discrepancy)
+            },
+            ReturnError::RateLimitedByServer(seconds) => {
+                write!(f, "Error: Rate limited by the server, retry after {} seconds.", seconds)
+            },
+            ReturnError::ServiceUnavailable => {
+                f.write_str("Error: EVDS is unavailable after repeated failures, requests are paused for a while.")
+            },
+            ReturnError::DeadlineExceeded(milliseconds) => {
+                write!(f, "Error: Deadline of the call is exceeded after {} milliseconds.", milliseconds)
+            },
+            ReturnError::DnsFailure(_) => f.write_str("Error: The host name of EVDS could not be resolved."),
+            ReturnError::TlsError(_) => {
+                f.write_str("Error: The secure connection to EVDS could not be established.")
+            },
+            ReturnError::Timeout(_) => f.write_str("Error: The request to EVDS timed out."),
+            ReturnError::RecordingNotFound => {
+                f.write_str("Error: No recording is found for the request in replay mode.")
+            },
+            ReturnError::RequestVetoed(message) => {
+                write!(f, "Error: The request is vetoed by the pre-request hook: {}.", message)
+            },
+            ReturnError::InvalidExpression(problem) => write!(f, "Error: Invalid expression, {}.", problem),
+            ReturnError::SeasonalAdjustmentFailed(reason) => {
+                write!(f, "Error: The dataset cannot be seasonally adjusted, {}.", reason)
+            },
+            ReturnError::QueueFull => f.write_str("Error: The queue of bulk requests is full."),
+            ReturnError::DateRangeTooLarge(max_span_days) => {
+                write!(
+                    f,
+                    "Error: The date range is longer than the maximum span of {} days, fetch it in chunks separated \
+                    by semicolons instead.", 
+                    max_span_days
+                )
+            },
+            ReturnError::HugeRequest(observation_count, data_frequency) => {
+                write!(
+                    f,
+                    "Error: The request would return about {} observations, request it in {:?} data frequency \
+                    instead.", 
+                    observation_count, 
+                    data_frequency
+                )
+            },
+            ReturnError::UnexpectedContentType(mismatch) => {
+                write!(f, "Error: Unexpected content type, {}.", mismatch)
+            },
+        }
     }
 }
 
 impl error::Error for ReturnError {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_give_details() {
+        let timeout = ReturnError::Timeout("curl error 28: Timeout was reached".to_string());
+
+        assert_eq!(Some("curl error 28: Timeout was reached"), timeout.get_detail());
+        assert_eq!(None, ReturnError::InvalidDate.get_detail());
+    }
+}
@@ -8,7 +8,9 @@ use crate::request_sync;
 /// provides function options to divert the flow of [`check_response`](fn@check_response) in the specific make 
 /// request functions only utilized for ['get_data_group'](fn@get_data_group) and 
 /// ['get_series_list'](fn@get_series_list) functions. 
+#[derive(Clone, Copy)]
 pub(crate) enum Function{
+    #[cfg(feature = "catalog")]
     GetDataGroup,
     GetSeriesList,
     OneOfOtherFunctions,
@@ -34,6 +36,7 @@ pub(crate) fn check_emptiness(data: &str) -> Result<(), ReturnError> {
 /// # Error
 ///
 /// This function returns an error if the response message contains known error.
+#[cfg(feature = "catalog")]
 fn handle_response_error(response_message: &str) -> Result<(), ReturnError> {
     
     let mut words: Vec<&str> = response_message.split(' ').collect();
@@ -72,6 +75,7 @@ fn handle_response_error(response_message: &str) -> Result<(), ReturnError> {
 /// This function returns an error if the response message contains known and specified error. 
 pub(crate) fn check_response(response: &str, function: Function) -> Result<(), ReturnError> {
     match function {
+        #[cfg(feature = "catalog")]
         Function::GetDataGroup => { return handle_response_error(&response); },
         Function::GetSeriesList => { 
             // This part returns error if response contains xml_empty or json_empty given below.
@@ -0,0 +1,268 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Instant;
+
+use serde_json::{json, Value};
+
+use crate::common;
+use crate::dataset::Dataset;
+use crate::date::DatePreference;
+use crate::deadline;
+use crate::error::ReturnError;
+use crate::scheduler::{self, MAX_PARALLEL_REQUESTS, Priority, wait_for_turn};
+
+
+/// is the name of the manifest file written next to the CSV files.
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+
+/// is a series of a data group found in the series list.
+#[derive(Debug, Clone, PartialEq)]
+struct GroupSeries {
+    code: String,
+    name: String,
+}
+
+/// is the outcome of exporting a series.
+struct ExportedSeries {
+    series: GroupSeries,
+    result: Result<(String, usize), ReturnError>,
+}
+
+
+/// exports each series of the data group in the date ranges into a CSV file in the directory, and writes a manifest.
+///
+/// The series are listed via [`get_series_list`](super::get_series_list) and fetched in parallel with a rate limit.
+/// Each CSV file is named after the series code, e.g. `TP_DK_USD_A.csv`, and has the normalized layout of
+/// [`Dataset::to_csv`]. A series that could not be fetched does not stop the export and is reported in the manifest.
+///
+/// *The* **evds** *argument must use the Json return format since the responses are parsed.*
+///
+/// # Error
+///
+/// This function returns an error if the series list could not be received or parsed, or the directory or the
+/// manifest could not be written.
+///
+/// Returns the manifest which is also written into `manifest.json`.
+pub(crate) fn export_data_group(
+    data_group: &str,
+    date_preferences: &[DatePreference],
+    directory: &str,
+    evds: &common::Evds,
+) -> Result<String, ReturnError> {
+
+    let series_list = super::get_series_list(data_group, evds)?;
+    let group_series = parse_series_list(&series_list)?;
+
+    if fs::create_dir_all(directory).is_err() { return Err(ReturnError::FailedToWriteFile); }
+
+    let next_series = AtomicUsize::new(0);
+    let last_request = Mutex::new(None);
+    let exported_series = Mutex::new(Vec::with_capacity(group_series.len()));
+    let call_deadline = deadline::get_call_deadline();
+
+    thread::scope(|scope| {
+        for _ in 0..MAX_PARALLEL_REQUESTS.min(group_series.len()) {
+            scope.spawn(|| {
+                let _call = deadline::inherit_call(call_deadline);
+
+                scheduler::set_priority(Priority::Bulk);
+
+                loop {
+                    let index = next_series.fetch_add(1, Ordering::Relaxed);
+
+                    let series = match group_series.get(index) {
+                        Some(series) => series,
+                        None => break,
+                    };
+
+                    let result = export_series(series, date_preferences, directory, evds, &last_request);
+
+                    let exported = ExportedSeries { series: series.clone(), result };
+
+                    match exported_series.lock() {
+                        Ok(mut exported_series) => exported_series.push((index, exported)),
+                        Err(poisoned) => poisoned.into_inner().push((index, exported)),
+                    }
+                }
+            });
+        }
+    });
+
+    let mut exported_series = match exported_series.into_inner() {
+        Ok(exported_series) => exported_series,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    exported_series.sort_by_key(|(index, _)| *index);
+
+    let manifest = generate_manifest(data_group, date_preferences, exported_series.iter().map(|(_, exported)| exported));
+
+    if fs::write(Path::new(directory).join(MANIFEST_FILE_NAME), &manifest).is_err() {
+        return Err(ReturnError::FailedToWriteFile);
+    }
+
+    Ok(manifest)
+}
+
+/// fetches the series in each date range and writes it into its CSV file. Returns the file name and the number of the
+/// observations.
+fn export_series(
+    series: &GroupSeries,
+    date_preferences: &[DatePreference],
+    directory: &str,
+    evds: &common::Evds,
+    last_request: &Mutex<Option<Instant>>,
+) -> Result<(String, usize), ReturnError> {
+
+    let mut dataset = Dataset::default();
+
+    for date_preference in date_preferences {
+        wait_for_turn(last_request);
+
+        let response = super::get_data(&series.code, date_preference, evds)?;
+        let range_dataset = Dataset::from_json(&response)?;
+
+        if dataset.series_codes.is_empty() { dataset.series_codes = range_dataset.series_codes; }
+
+        dataset.observations.extend(range_dataset.observations);
+    }
+
+    let file_name = generate_file_name(&series.code);
+
+    if fs::write(Path::new(directory).join(&file_name), dataset.to_csv()).is_err() {
+        return Err(ReturnError::FailedToWriteFile);
+    }
+
+    Ok((file_name, dataset.observations.len()))
+}
+
+/// generates the name of the CSV file of a series from its code received from EVDS, where the characters other than
+/// ASCII letters and digits are replaced by underscores, e.g. `TP_DK_USD_A.csv`, so that the file stays in the
+/// directory.
+fn generate_file_name(series_code: &str) -> String {
+
+    let file_stem: String = series_code
+        .chars()
+        .map(|character| if character.is_ascii_alphanumeric() { character } else { '_' })
+        .collect();
+
+    format!("{}.csv", file_stem)
+}
+
+/// parses the codes and names of the series from a JSON series list response.
+fn parse_series_list(series_list: &str) -> Result<Vec<GroupSeries>, ReturnError> {
+
+    let parsed_list: Value = match serde_json::from_str(series_list) {
+        Ok(value) => value,
+        Err(_) => return Err(ReturnError::UnparsableResponse),
+    };
+
+    let items = match parsed_list.as_array() {
+        Some(items) => items,
+        None => return Err(ReturnError::UnparsableResponse),
+    };
+
+    let mut group_series = Vec::with_capacity(items.len());
+
+    for item in items {
+        let code = match item.get("SERIE_CODE").and_then(Value::as_str) {
+            Some(code) => code.to_string(),
+            None => return Err(ReturnError::UnparsableResponse),
+        };
+
+        let name = item.get("SERIE_NAME").and_then(Value::as_str).unwrap_or_default().to_string();
+
+        group_series.push(GroupSeries { code, name });
+    }
+
+    Ok(group_series)
+}
+
+fn generate_manifest<'a, I>(data_group: &str, date_preferences: &[DatePreference], exported_series: I) -> String
+where
+    I: Iterator<Item = &'a ExportedSeries>
+{
+
+    let dates: Vec<Value> = date_preferences
+        .iter()
+        .map(|date_preference| {
+            let (start_date, end_date) = date_preference.get_dates();
+
+            json!({ "startDate": start_date, "endDate": end_date })
+        })
+        .collect();
+
+    let series: Vec<Value> = exported_series
+        .map(|exported| {
+            match &exported.result {
+                Ok((file_name, observation_count)) => json!({
+                    "code": exported.series.code,
+                    "name": exported.series.name,
+                    "file": file_name,
+                    "observations": observation_count,
+                    "error": Value::Null,
+                }),
+                Err(return_error) => json!({
+                    "code": exported.series.code,
+                    "name": exported.series.name,
+                    "file": Value::Null,
+                    "observations": 0,
+                    "error": return_error.to_string(),
+                }),
+            }
+        })
+        .collect();
+
+    let failed_count = series.iter().filter(|series| !series["error"].is_null()).count();
+
+    json!({
+        "dataGroup": data_group,
+        "dates": dates,
+        "exportedCount": series.len() - failed_count,
+        "failedCount": failed_count,
+        "series": series,
+    })
+    .to_string()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_series_list_and_generate_manifest() {
+        let series_list = r#"[
+            {"SERIE_CODE":"TP.DK.USD.A","DATAGROUP_CODE":"bie_dkdovytl","SERIE_NAME":"(USD) ABD Doları (Döviz Alış)"},
+            {"SERIE_CODE":"TP.DK.USD.S","DATAGROUP_CODE":"bie_dkdovytl"}
+        ]"#;
+
+        let group_series = parse_series_list(series_list).unwrap();
+
+        assert_eq!(2, group_series.len());
+        assert_eq!("TP.DK.USD.A", group_series[0].code);
+        assert_eq!("", group_series[1].name);
+        assert_eq!(Err(ReturnError::UnparsableResponse), parse_series_list(r#"{"items":[]}"#));
+
+        assert_eq!("TP_DK_USD_A.csv", generate_file_name(&group_series[0].code));
+        assert_eq!("______etc_passwd.csv", generate_file_name("../../etc/passwd"));
+        assert_eq!("C__Windows_x.csv", generate_file_name("C:\\Windows\\x"));
+
+        let exported_series = [
+            ExportedSeries { series: group_series[0].clone(), result: Ok(("TP_DK_USD_A.csv".to_string(), 3)) },
+            ExportedSeries { series: group_series[1].clone(), result: Err(ReturnError::EmptyResponse) },
+        ];
+
+        let manifest = generate_manifest("bie_dkdovytl", &[], exported_series.iter());
+        let manifest: Value = serde_json::from_str(&manifest).unwrap();
+
+        assert_eq!(1, manifest["exportedCount"]);
+        assert_eq!(1, manifest["failedCount"]);
+        assert_eq!("TP_DK_USD_A.csv", manifest["series"][0]["file"]);
+        assert_eq!("Error: Empty page returned.", manifest["series"][1]["error"]);
+    }
+}
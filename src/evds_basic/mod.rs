@@ -1,9 +1,52 @@
 /// provides specific make request function for basic operations.
 mod basic;
+/// exports the series of a data group into a directory of CSV files.
+#[cfg(feature = "export")]
+pub(crate) mod export;
+/// pre-populates the response cache and the observation store from a manifest of series.
+#[cfg(feature = "cache")]
+pub(crate) mod warm_up;
+/// runs the post-processors registered for the session on the data responses.
+#[cfg(feature = "parse")]
+pub(crate) mod post_processing;
 
-use crate::date;
-use crate::common;
+use crate::date::{self, calendar::CalendarDate};
+use crate::common::{self, ReturnFormat, url_builder::{UrlBuilder, check_query_value}};
+use crate::dataset::{Dataset, sniff::negotiate_response};
+#[cfg(feature = "cache")]
+use crate::dataset::cache::{self, get_response_cache};
+#[cfg(all(feature = "cache", any(feature = "catalog", feature = "parse", feature = "export")))]
+use crate::dataset::cache::CacheEndpoint;
+use crate::dataset::catalog::{SeriesCatalog, get_series_catalog};
+use crate::dataset::frequency::{estimate_observations, find_coarser_frequency};
+#[cfg(feature = "catalog")]
+use crate::dataset::category_tree::CategoryTree;
+#[cfg(feature = "cache")]
+use crate::dataset::store::{StoreWindow, get_observation_store};
+use crate::dataset::schema::validate_schema;
+#[cfg(feature = "catalog")]
+use crate::dataset::series_manifest::generate_manifest;
+#[cfg(feature = "parse")]
+use crate::dataset::header::{HeaderLanguage, get_series_name_cache, parse_series_names, translate_headers};
+#[cfg(feature = "parse")]
+use crate::dataset::unit::{SeriesUnit, find_series_unit};
+#[cfg(feature = "parse")]
+use crate::dataset::deflate::deflate;
+#[cfg(feature = "parse")]
+use crate::dataset::expression::Expression;
+use crate::dataset::merge::join_series;
+#[cfg(feature = "parse")]
+use crate::dataset::virtual_series::get_virtual_series;
+use crate::evds_c::advanced_entities::TcmbEvdsHugeRequestPolicy;
+use crate::evds_c::settings::{FULL_HISTORY_START_DATE, get_settings};
+#[cfg(feature = "cache")]
+use crate::evds_c::settings::get_today;
+use crate::evds_currency::frequency_formulas::DataFrequency;
+#[cfg(feature = "currency")]
+use crate::evds_currency::basket::CurrencyBasket;
 use crate::error::ReturnError;
+#[cfg(feature = "cache")]
+use crate::metrics::METRICS;
 use crate::traits::MakingUrlFormat;
 
 /// returns data about requested data series.
@@ -66,22 +109,498 @@ pub(crate) fn get_data<'a>(
     evds: &common::Evds,
 ) -> Result<String, ReturnError> {
 
+    #[cfg(feature = "parse")]
+    if let Some(expression) = get_virtual_series().get(data_series.trim()) {
+        return get_virtual_data(data_series.trim(), &expression, date_preference, evds);
+    }
+
+    let narrowed_date_preference = narrow_full_history(data_series, date_preference, evds);
+    let date_preference = narrowed_date_preference.as_ref().unwrap_or(date_preference);
+
+    let coarser_frequency = apply_huge_request_policy(data_series, date_preference, evds)?;
+
+    let mut url = generate_data_url(data_series, date_preference, evds)?;
+
+    if let Some(coarser_frequency) = &coarser_frequency {
+        url = url.add_fragment(&coarser_frequency.generate_url_format());
+    }
+
+    let split_urls = url.split_series();
+
+    if split_urls.len() > 1 { return get_split_data(split_urls, evds); }
+
+    #[cfg(feature = "cache")]
+    let response = {
+        // the observations of a coarser frequency are not the observations of the series, so they are not stored.
+        let store_window = match coarser_frequency {
+            Some(_) => None,
+            None => find_store_window(date_preference),
+        };
+
+        if let Some(window) = &store_window {
+            let series_codes = generate_series_codes(data_series);
+
+            let stored_dataset = get_observation_store().as_ref().and_then(|store| store.read(&series_codes, window));
+
+            if let Some(dataset) = stored_dataset {
+                METRICS.record_cache_lookup(true);
+
+                return Ok(dataset.serialize(evds.get_return_format()));
+            }
+        }
+
+        make_cached_request(url, evds, basic::Function::OneOfOtherFunctions, store_window)?
+    };
+
+    #[cfg(not(feature = "cache"))]
+    let response = make_uncached_request(&url, evds, basic::Function::OneOfOtherFunctions)?;
+
+    check_schema(response, &generate_series_codes(data_series), evds)
+}
+
+/// narrows a date range starting at [`FULL_HISTORY_START_DATE`], e.g. the expansion of the "ALL" date, to start at the
+/// first date of the earliest data series, so that the full histories are requested without guessing their starts.
+///
+/// The first dates are taken from the series catalog, and the series lists of the series not in it are requested in
+/// JSON. None is returned when the date preference is not a full history or a first date could not be found.
+fn narrow_full_history(
+    data_series: &str,
+    date_preference: &date::DatePreference,
+    evds: &common::Evds
+) -> Option<date::DatePreference> {
+
+    let (start_date, end_date) = date_preference.get_dates();
+
+    if start_date != FULL_HISTORY_START_DATE { return None; }
+
+    let mut earliest_start_date: Option<CalendarDate> = None;
+
+    for code in data_series.split('-').map(str::trim) {
+        let series_start_date = find_in_catalog(code, evds, SeriesCatalog::find_start_date)?;
+
+        earliest_start_date = Some(earliest_start_date.map_or(series_start_date, |earliest_start_date| {
+            earliest_start_date.min(series_start_date)
+        }));
+    }
+
+    let earliest_start_date = earliest_start_date?;
+
+    if Some(earliest_start_date) > CalendarDate::parse(end_date) { return None; }
+
+    let narrowed_range = date::DateRange::from(&earliest_start_date.format(), end_date).ok()?;
+
+    Some(date::DatePreference::Multiple(narrowed_range))
+}
+
+/// estimates the number of the observations of the data series in the date range from their frequencies and applies 
+/// the policy set via [`tcmb_evds_c_set_huge_request_policy`](crate::tcmb_evds_c_set_huge_request_policy) when the 
+/// estimate is above its limit, so that a program does not freeze while receiving and parsing a huge response.
+///
+/// The frequencies are taken from the series catalog, and the series lists of the series not in it are requested in 
+/// JSON. The series whose frequencies could not be found are not counted. The coarser data frequency in which the data
+/// should be requested is returned when the request is downgraded.
+///
+/// # Error
+///
+/// This function returns `HugeRequest` error suggesting a coarser data frequency when huge requests are rejected.
+fn apply_huge_request_policy(
+    data_series: &str,
+    date_preference: &date::DatePreference,
+    evds: &common::Evds
+) -> Result<Option<DataFrequency>, ReturnError> {
+
+    let (policy, max_observations) = {
+        let settings = get_settings();
+
+        (settings.huge_request_policy, settings.max_estimated_observations)
+    };
+
+    if policy == TcmbEvdsHugeRequestPolicy::AllowHugeRequests { return Ok(None); }
+
+    let span_days = match date_preference.count_days() {
+        Some(span_days) => span_days,
+        None => return Ok(None),
+    };
+
+    let frequencies: Vec<DataFrequency> = data_series
+        .split('-')
+        .filter_map(|code| find_in_catalog(code.trim(), evds, SeriesCatalog::find_frequency))
+        .collect();
+
+    let observation_count = estimate_observations(&frequencies, None, span_days);
+
+    if observation_count <= max_observations { return Ok(None); }
+
+    let coarser_frequency = find_coarser_frequency(&frequencies, span_days, max_observations)
+        .unwrap_or(DataFrequency::Annual);
+
+    match policy {
+        TcmbEvdsHugeRequestPolicy::DowngradeHugeRequests => Ok(Some(coarser_frequency)),
+        _ => Err(ReturnError::HugeRequest(observation_count, coarser_frequency)),
+    }
+}
+
+/// finds a field of the series, e.g. its first date, in the series catalog. The series list of the series is requested
+/// in JSON and kept in the catalog when the field is not found.
+fn find_in_catalog<T, F>(code: &str, evds: &common::Evds, find: F) -> Option<T>
+where
+    F: Fn(&SeriesCatalog, &str) -> Option<T>
+{
+
+    if let Some(value) = find(&get_series_catalog(), code) { return Some(value); }
+
+    let url = generate_series_list_url(code, evds).ok()?.add_parameter("type", &ReturnFormat::Json.to_string());
+
+    if let Ok(series_list) = basic::make_request(&url.build(), basic::Function::GetSeriesList) {
+        get_series_catalog().insert_series_list(&series_list);
+    }
+
+    find(&get_series_catalog(), code)
+}
+
+/// returns the data of a series list too long for a single url, which is split into groups via 
+/// [`UrlBuilder::split_series`].
+///
+/// The groups are requested one by one in JSON, each waiting for the rate limits like any other request, and their
+/// series are joined by date into a single response in the return format of the evds.
+fn get_split_data(urls: Vec<UrlBuilder>, evds: &common::Evds) -> Result<String, ReturnError> {
+
+    let datasets = urls
+        .into_iter()
+        .map(|url| {
+            let json_url = url.add_parameter("type", &ReturnFormat::Json.to_string()).build();
+
+            let json_response = basic::make_request(&json_url, basic::Function::OneOfOtherFunctions)?;
+
+            Dataset::from_json(&json_response)
+        })
+        .collect::<Result<Vec<Dataset>, ReturnError>>()?;
+
+    Ok(join_series(datasets).serialize(evds.get_return_format()))
+}
+
+/// returns the data of a virtual series registered via 
+/// [`tcmb_evds_c_define_series`](crate::tcmb_evds_c_define_series).
+///
+/// The real series of its expression are requested together in JSON, so that EVDS aligns their observations by date.
+/// Then, the expression is evaluated at each date and the virtual series is returned in the return format of the evds
+/// argument under its own name.
+///
+/// # Error
+///
+/// This function returns the errors of [`get_data`](fn@get_data), and `InvalidExpression` error when a series of the
+/// expression is not in the response.
+#[cfg(feature = "parse")]
+fn get_virtual_data(
+    name: &str,
+    expression: &Expression,
+    date_preference: &date::DatePreference,
+    evds: &common::Evds,
+) -> Result<String, ReturnError> {
+
+    let data_series = expression.series_codes().join("-");
+
+    let json_url = generate_data_url(&data_series, date_preference, evds)?
+        .add_parameter("type", &ReturnFormat::Json.to_string())
+        .build();
+
+    let json_response = basic::make_request(&json_url, basic::Function::OneOfOtherFunctions)?;
+
+    let dataset = Dataset::from_json(&json_response)?;
+
+    Ok(expression.evaluate_into_dataset(name, &dataset)?.serialize(evds.get_return_format()))
+}
+
+/// returns the nominal data series divided by the price index and rebased to the prices of the base period, namely 
+/// the "real" series, e.g. the loans in the prices of 2003 via the consumer price index `TP.FG.J0`.
+///
+/// Both series are requested together in JSON, so that EVDS aligns their observations by date, and the real series is
+/// returned in the return format of the evds. The series should have the same frequency.
+///
+/// # Error
+///
+/// This function returns the errors of [`get_data`](fn@get_data), and `InvalidDate` error when the base period is not
+/// within the dates or the price index has no value at it.
+#[cfg(feature = "parse")]
+pub(crate) fn get_real_data(
+    data_series: &str,
+    price_index: &str,
+    base_period: &str,
+    date_preferences: &[date::DatePreference],
+    evds: &common::Evds,
+) -> Result<String, ReturnError> {
+
+    let series = format!("{}-{}", data_series.trim(), price_index.trim());
+
+    let nominal_dataset = get_aligned_dataset(&series, date_preferences, evds)?;
+
+    Ok(deflate(&nominal_dataset, base_period)?.serialize(evds.get_return_format()))
+}
+
+/// returns the value of the currency basket in TRY, or its index which is 100 at the base date when it is given, at 
+/// each date of the date preferences.
+///
+/// The selling rates of the currencies are requested together in JSON, so that EVDS aligns them by date, and the 
+/// basket is returned as a single series in the return format of the evds.
+///
+/// # Error
+///
+/// This function returns the errors of [`get_data`](fn@get_data), and `InvalidDate` error when the base date is not
+/// within the dates or a rate is missing at it.
+#[cfg(feature = "currency")]
+pub(crate) fn get_basket_index(
+    basket: &CurrencyBasket,
+    base_date: Option<&str>,
+    date_preferences: &[date::DatePreference],
+    evds: &common::Evds,
+) -> Result<String, ReturnError> {
+
+    let rates_dataset = get_aligned_dataset(&basket.generate_series(), date_preferences, evds)?;
+
+    Ok(basket.compute(&rates_dataset, base_date)?.serialize(evds.get_return_format()))
+}
+
+/// requests the series together in JSON for each date preference, so that EVDS aligns their observations by date, and
+/// joins the observations of the date preferences into a dataset.
+#[cfg(any(feature = "currency", feature = "parse"))]
+fn get_aligned_dataset(
+    data_series: &str,
+    date_preferences: &[date::DatePreference],
+    evds: &common::Evds,
+) -> Result<Dataset, ReturnError> {
+
+    let mut aligned_dataset: Option<Dataset> = None;
+
+    for date_preference in date_preferences {
+        let json_url = generate_data_url(data_series, date_preference, evds)?
+            .add_parameter("type", &ReturnFormat::Json.to_string())
+            .build();
+
+        let json_response = basic::make_request(&json_url, basic::Function::OneOfOtherFunctions)?;
+
+        let dataset = Dataset::from_json(&json_response)?;
+
+        match &mut aligned_dataset {
+            Some(aligned_dataset) => aligned_dataset.observations.extend(dataset.observations),
+            None => aligned_dataset = Some(dataset),
+        }
+    }
+
+    aligned_dataset.ok_or(ReturnError::InvalidDate)
+}
+
+/// generates the url requested by [`get_data`](fn@get_data) without making the request.
+///
+/// # Error
+///
+/// This function returns error when the data series is empty or contains one of `&`, `=` and `#` characters.
+pub(crate) fn generate_data_url(
+    data_series: &str, 
+    date_preference: &date::DatePreference, 
+    evds: &common::Evds,
+) -> Result<UrlBuilder, ReturnError> {
+
     let dates_as_url = date_preference.generate_url_format();
     let return_format_as_url = evds.get_return_format_as_url();
     let api_key_as_url = evds.get_api_key_as_url();
 
     basic::check_emptiness(data_series)?;
+    check_query_value(data_series)?;
 
-    let url = 
-    format!(
-        "https://evds2.tcmb.gov.tr/service/evds/series={}&{}&{}&{}", 
-        data_series, 
-        dates_as_url,
-        return_format_as_url, 
-        api_key_as_url,
-    );
-    
-    basic::make_request(&url, basic::Function::OneOfOtherFunctions)
+    let url = UrlBuilder::new("")
+        .add_parameter("series", data_series)
+        .add_fragment(&dates_as_url)
+        .add_fragment(return_format_as_url)
+        .add_fragment(api_key_as_url);
+
+    Ok(url)
+}
+
+/// makes the request in the return format of the evds argument, which is negotiated when it is `Auto`.
+fn make_uncached_request(
+    url: &UrlBuilder,
+    evds: &common::Evds,
+    function: basic::Function,
+) -> Result<String, ReturnError> {
+
+    let response = basic::make_request(&url.build(), function)?;
+
+    Ok(negotiate_response(response, evds.get_return_format()))
+}
+
+/// makes the request via the response cache when the cache is enabled.
+///
+/// A cached request is served in the format of the evds argument by converting the cached dataset. Otherwise, the data
+/// is requested in JSON to be parsed and cached, and then converted into the return format as well, so that a request 
+/// gives the same response whether it is cached or not. Responses that could not be parsed, e.g. error messages, are 
+/// not cached and the request is made in the return format as usual.
+///
+/// The parsed response is also written into the observation store when a store window is given. A request failing due
+/// to the network is served from its expired cached response when stale responses are enabled.
+#[cfg(feature = "cache")]
+fn make_cached_request(
+    url: UrlBuilder, 
+    evds: &common::Evds, 
+    function: basic::Function,
+    store_window: Option<StoreWindow>,
+) -> Result<String, ReturnError> {
+
+    if !get_response_cache().is_enabled() && store_window.is_none() {
+        return make_uncached_request(&url, evds, function);
+    }
+
+    let cache_key = url.build_cache_key();
+    let return_format = evds.get_return_format();
+
+    let cached_dataset = get_response_cache().get(&cache_key);
+
+    METRICS.record_cache_lookup(cached_dataset.is_some());
+
+    if let Some(dataset) = cached_dataset { return Ok(dataset.serialize(return_format)); }
+
+    let json_url = url.clone().add_parameter("type", &ReturnFormat::Json.to_string()).build();
+
+    let json_response = match basic::make_request(&json_url, function) {
+        Ok(json_response) => json_response,
+        Err(return_error) => return serve_stale(&cache_key, return_error, return_format),
+    };
+
+    let dataset = match Dataset::from_json(&json_response) {
+        Ok(dataset) => dataset,
+        Err(_) if matches!(return_format, ReturnFormat::Json) => return Ok(json_response),
+        Err(_) if matches!(return_format, ReturnFormat::Auto) => {
+            return Ok(negotiate_response(json_response, return_format));
+        },
+        Err(_) => return basic::make_request(&url.build(), function),
+    };
+
+    get_response_cache().insert(cache_key, dataset.clone());
+
+    if let (Some(window), Some(store)) = (store_window, get_observation_store().as_ref()) { store.write(&window, &dataset); }
+
+    Ok(dataset.serialize(return_format))
+}
+
+/// makes the request of a catalog endpoint via the response cache, which keeps the response as it is received.
+#[cfg(all(feature = "cache", any(feature = "catalog", feature = "parse", feature = "export")))]
+fn make_catalog_request(url: &UrlBuilder, function: basic::Function) -> Result<String, ReturnError> {
+    cache::read_through(url, CacheEndpoint::Catalog, |url| basic::make_request(url, function))
+}
+
+/// makes the request of a catalog endpoint as it is, since the responses are not cached without the `cache` feature.
+#[cfg(all(not(feature = "cache"), any(feature = "catalog", feature = "parse", feature = "export")))]
+fn make_catalog_request(url: &UrlBuilder, function: basic::Function) -> Result<String, ReturnError> {
+    basic::make_request(&url.build(), function)
+}
+
+/// returns the cached response of the request, even an expired one, flagged as stale when the request failed due to the
+/// network and stale responses are enabled via 
+/// [`tcmb_evds_c_set_stale_if_error`](crate::tcmb_evds_c_set_stale_if_error). The error is returned otherwise.
+#[cfg(feature = "cache")]
+fn serve_stale(
+    cache_key: &str,
+    return_error: ReturnError,
+    return_format: &ReturnFormat
+) -> Result<String, ReturnError> {
+
+    if !cache::is_stale_served(&return_error) { return Err(return_error); }
+
+    let stale_dataset = match get_response_cache().get_stale(cache_key) {
+        Some(stale_dataset) => stale_dataset,
+        None => return Err(return_error),
+    };
+
+    cache::mark_stale(&return_error.to_string());
+
+    Ok(stale_dataset.serialize(return_format))
+}
+
+/// finds the window of the date preference when the observation store is open and the window is historical, namely it 
+/// ends before today. The observations of the current day may still be published or revised, so they are not stored.
+#[cfg(feature = "cache")]
+fn find_store_window(date_preference: &date::DatePreference) -> Option<StoreWindow> {
+
+    if get_observation_store().is_none() { return None; }
+
+    let (start_date, end_date) = date_preference.get_dates();
+
+    let window = StoreWindow { start: CalendarDate::parse(start_date)?, end: CalendarDate::parse(end_date)? };
+
+    if window.end >= get_today() { return None; }
+
+    Some(window)
+}
+
+/// validates a JSON response against the schema of EVDS when strict schema validation is enabled via 
+/// [`tcmb_evds_c_set_strict_schema`](crate::tcmb_evds_c_set_strict_schema). CSV and XML responses are not validated.
+fn check_schema(response: String, series_codes: &[String], evds: &common::Evds) -> Result<String, ReturnError> {
+
+    let is_json = matches!(evds.get_return_format(), ReturnFormat::Json | ReturnFormat::Auto);
+
+    if !get_settings().strict_schema || !is_json { return Ok(response); }
+
+    validate_schema(&response, series_codes)?;
+
+    Ok(response)
+}
+
+/// generates the codes of the series in the responses from the requested data series, e.g. "TP.DK.USD.S-TP.DK.EUR.S" 
+/// becomes "TP_DK_USD_S" and "TP_DK_EUR_S".
+fn generate_series_codes(data_series: &str) -> Vec<String> {
+    data_series.split('-').map(|series| series.trim().replace('.', "_")).collect()
+}
+
+/// returns the last `count` observations up to `end_date`, which is mostly today, of the requested data series as a 
+/// parsed dataset.
+///
+/// The frequency of the series is not known before requesting it. Therefore, the function starts with a date window
+/// ending at `end_date` that is adequate for a daily series and widens the window until the response includes enough 
+/// observations or the window reaches the earliest date EVDS serves. Observations without any value, e.g. holidays,
+/// are not counted. As a result, the dataset contains exactly `count` observations unless the series is shorter.
+///
+/// *The* **evds** *argument must use the Json return format since the response is parsed.*
+///
+/// # Error
+///
+/// This function returns an error if the data series is empty, the internet connection is poor, the request is 
+/// invalid or the response could not be parsed.
+#[cfg(feature = "parse")]
+pub(crate) fn get_last_observations(
+    data_series: &str,
+    count: usize,
+    end_date: CalendarDate,
+    evds: &common::Evds,
+) -> Result<Dataset, ReturnError> {
+
+    basic::check_emptiness(data_series)?;
+
+    let earliest_date = CalendarDate { year: 1950, month: 1, day: 1 };
+
+    // A daily series publishes five observations a week, so twice the count plus a margin for holidays is enough for 
+    // the first attempt. Each further attempt widens the window four times.
+    let mut window_days = count as i64 * 2 + 10;
+
+    loop {
+        let mut start_date = end_date.add_days(-window_days);
+
+        let is_last_window = start_date <= earliest_date;
+        if is_last_window { start_date = earliest_date; }
+
+        let date_range = date::DateRange::from(&start_date.format(), &end_date.format())?;
+        let date_preference = date::DatePreference::Multiple(date_range);
+
+        let response = get_data(data_series, &date_preference, evds)?;
+
+        let mut dataset = Dataset::from_json(&response)?;
+
+        if dataset.count_observations() >= count || is_last_window {
+            dataset.keep_last(count);
+
+            return Ok(dataset);
+        }
+
+        window_days *= 4;
+    }
 }
 
 /// returns requested data group.
@@ -141,28 +660,49 @@ pub(crate) fn get_data<'a>(
 ///         Ok(data) => data,     
 ///     };
 /// ```
+#[cfg(feature = "catalog")]
 pub(crate) fn get_data_group(
     data_group: &str, 
     date_preference: &date::DatePreference, 
     evds: &common::Evds,
 ) -> Result<String, ReturnError> {
 
+    let url = generate_data_group_url(data_group, date_preference, evds)?;
+
+    #[cfg(feature = "cache")]
+    let response = make_cached_request(url, evds, basic::Function::GetDataGroup, None)?;
+    #[cfg(not(feature = "cache"))]
+    let response = make_uncached_request(&url, evds, basic::Function::GetDataGroup)?;
+
+    check_schema(response, &[], evds)
+}
+
+/// generates the url requested by [`get_data_group`](fn@get_data_group) without making the request.
+///
+/// # Error
+///
+/// This function returns error when the data group is empty or contains one of `&`, `=` and `#` characters.
+#[cfg(feature = "catalog")]
+pub(crate) fn generate_data_group_url(
+    data_group: &str, 
+    date_preference: &date::DatePreference, 
+    evds: &common::Evds,
+) -> Result<UrlBuilder, ReturnError> {
+
     let dates_as_url = date_preference.generate_url_format();
     let return_format_as_url = evds.get_return_format_as_url();
     let api_key_as_url = evds.get_api_key_as_url();
 
     basic::check_emptiness(data_group)?;
+    check_query_value(data_group)?;
 
-    let url = 
-    format!(
-        "https://evds2.tcmb.gov.tr/service/evds/datagroup={}&{}&{}&{}", 
-        data_group, 
-        dates_as_url,
-        return_format_as_url, 
-        api_key_as_url,
-    );
+    let url = UrlBuilder::new("")
+        .add_parameter("datagroup", data_group)
+        .add_fragment(&dates_as_url)
+        .add_fragment(return_format_as_url)
+        .add_fragment(api_key_as_url);
 
-    basic::make_request(&url, basic::Function::GetDataGroup)
+    Ok(url)
 }
 
 /// returns all requested categories of EVDS.
@@ -202,19 +742,42 @@ pub(crate) fn get_data_group(
 ///         Ok(data) => data,     
 ///     };
 /// ```
+#[cfg(feature = "catalog")]
 pub(crate) fn get_categories(evds: &common::Evds) -> Result<String, ReturnError> {
 
     let return_format_as_url = evds.get_return_format_as_url();
     let api_key_as_url = evds.get_api_key_as_url();
 
-    let url = 
-    format!{
-        "https://evds2.tcmb.gov.tr/service/evds/categories/{}&{}",
-        api_key_as_url,
-        return_format_as_url,
-    };
+    let url = UrlBuilder::new("categories/")
+        .add_fragment(api_key_as_url)
+        .add_fragment(return_format_as_url);
+
+    let response = make_catalog_request(&url, basic::Function::OneOfOtherFunctions)?;
+
+    Ok(negotiate_response(response, evds.get_return_format()))
+}
 
-    basic::make_request(&url, basic::Function::OneOfOtherFunctions)
+/// returns the tree of the categories of EVDS holding their data groups, which is parsed from the categories and all 
+/// data groups requested in the return format of the evds argument. The series counts of the data groups are known for
+/// the data groups whose series lists are requested before.
+///
+/// # Error
+///
+/// This function returns the errors of [`get_categories`], and `UnparsableResponse` error when the return format is not
+/// JSON or the responses could not be parsed.
+#[cfg(feature = "catalog")]
+pub(crate) fn get_category_tree(evds: &common::Evds) -> Result<CategoryTree, ReturnError> {
+
+    let categories = get_categories(evds)?;
+
+    let url = UrlBuilder::new("datagroups/")
+        .add_fragment(evds.get_api_key_as_url())
+        .add_parameter("mode", "0")
+        .add_fragment(evds.get_return_format_as_url());
+
+    let data_groups = make_catalog_request(&url, basic::Function::OneOfOtherFunctions)?;
+
+    CategoryTree::parse(&categories, &data_groups, &get_series_catalog()).ok_or(ReturnError::UnparsableResponse)
 }
 
 /// returns required data groups.
@@ -263,6 +826,7 @@ pub(crate) fn get_categories(evds: &common::Evds) -> Result<String, ReturnError>
 ///         Ok(data) => data,     
 ///     };
 /// ```
+#[cfg(feature = "catalog")]
 pub(crate) fn get_advanced_data_group(
     mode: u32, 
     code: &str, 
@@ -273,22 +837,24 @@ pub(crate) fn get_advanced_data_group(
     let api_key_as_url = evds.get_api_key_as_url();
     
     basic::check_emptiness(code)?;
+    check_query_value(code)?;
     
-    let url = 
-    format!(
-        "https://evds2.tcmb.gov.tr/service/evds/datagroups/{}&mode={}&code={}&{}", 
-        api_key_as_url,
-        mode, 
-        code, 
-        return_format_as_url, 
-    );
-
-    basic::make_request(&url, basic::Function::OneOfOtherFunctions)
+    let url = UrlBuilder::new("datagroups/")
+        .add_fragment(api_key_as_url)
+        .add_parameter("mode", &mode.to_string())
+        .add_parameter("code", code)
+        .add_fragment(return_format_as_url);
+
+    let response = make_catalog_request(&url, basic::Function::OneOfOtherFunctions)?;
+
+    Ok(negotiate_response(response, evds.get_return_format()))
 }
 
 /// returns all usable series list.
 ///
-/// Data should be detached from the result to have the list of series.
+/// Data should be detached from the result to have the list of series. The codes of the series in JSON and CSV lists 
+/// are also kept in the process wide series catalog listed by 
+/// [`tcmb_evds_c_list_series`](crate::tcmb_evds_c_list_series).
 /// 
 /// It is because code parameter can be a string parameter that this parameter is required as string slice.
 ///
@@ -331,23 +897,147 @@ pub(crate) fn get_advanced_data_group(
 ///         Ok(data) => data,     
 ///     };
 /// ```
+#[cfg(any(feature = "catalog", feature = "parse", feature = "export"))]
 pub(crate) fn get_series_list(
     code: &str, 
     evds: &common::Evds
 ) -> Result<String, ReturnError> {
+
+    let url = generate_series_list_url(code, evds)?;
+
+    let response = make_catalog_request(&url, basic::Function::GetSeriesList)?;
+
+    get_series_catalog().insert_series_list(&response);
+
+    Ok(negotiate_response(response, evds.get_return_format()))
+}
+
+/// returns the manifest of the series of the data group with their metadata, which is generated from the series list
+/// of the data group via [`generate_manifest`].
+///
+/// *The* **evds** *argument must use the Json return format since the series list is parsed.*
+///
+/// # Error
+///
+/// This function returns the errors of [`get_series_list`], and `UnparsableResponse` error when the series list could
+/// not be parsed.
+#[cfg(feature = "catalog")]
+pub(crate) fn get_series_manifest(data_group: &str, evds: &common::Evds) -> Result<String, ReturnError> {
+
+    let series_list = get_series_list(data_group, evds)?;
+
+    generate_manifest(data_group, &series_list)
+}
+
+/// rewrites the series codes in the headers of a data response of the data series with their descriptive names in the
+/// language, e.g. "TP_DK_USD_S" becomes "(USD) US Dollar (Selling)".
+///
+/// The names are taken from the series lists of EVDS and cached for the process, so each series costs a request only
+/// once. A series whose name could not be received keeps its code.
+///
+/// # Error
+///
+/// This function returns an error if the response is in XML or could not be parsed.
+#[cfg(feature = "parse")]
+pub(crate) fn translate_data_headers(
+    response: &str,
+    data_series: &str,
+    language: HeaderLanguage,
+    evds: &common::Evds
+) -> Result<String, ReturnError> {
+
+    let mut names = Vec::new();
+
+    for code in data_series.split('-') {
+        let series_code = code.replace('.', "_");
+
+        let cached_names = get_series_name_cache().get(&series_code);
+
+        let series_names = match cached_names {
+            Some(series_names) => Some(series_names),
+            None => {
+                let url = generate_series_list_url(code, evds)?.add_parameter("type", &ReturnFormat::Json.to_string());
+
+                let received_names = match basic::make_request(&url.build(), basic::Function::GetSeriesList) {
+                    Ok(series_list) => {
+                        get_series_catalog().insert_series_list(&series_list);
+
+                        parse_series_names(&series_list)
+                    },
+                    Err(_) => Vec::new(),
+                };
+
+                let mut series_name_cache = get_series_name_cache();
+
+                for (received_code, received_series_names) in received_names {
+                    series_name_cache.insert(received_code, received_series_names);
+                }
+
+                series_name_cache.get(&series_code)
+            },
+        };
+
+        if let Some(series_names) = series_names {
+            names.push((series_code, series_names.get(language).to_string()));
+        }
+    }
+
+    translate_headers(response, evds.get_return_format(), &names)
+}
+
+/// finds the unit of each series of the dataset received for the data series from the names in its series list.
+///
+/// The series are matched by their codes where the dots of the data series are underscores in the dataset. A unit is
+/// None when the series list could not be received or the series name does not include a known unit.
+///
+/// *The* **evds** *argument must use the Json return format since the series lists are parsed.*
+#[cfg(feature = "parse")]
+pub(crate) fn get_series_units(
+    data_series: &str,
+    dataset: &Dataset,
+    evds: &common::Evds
+) -> Vec<Option<SeriesUnit>> {
+
+    let found_units: Vec<(String, Option<SeriesUnit>)> = data_series
+        .split('-')
+        .map(|code| {
+            let unit = get_series_list(code, evds).ok().and_then(|series_list| find_series_unit(&series_list));
+
+            (code.replace('.', "_"), unit)
+        })
+        .collect();
+
+    dataset.series_codes
+        .iter()
+        .map(|series_code| {
+            found_units
+                .iter()
+                .find(|(code, _)| code == series_code)
+                .and_then(|(_, unit)| unit.clone())
+        })
+        .collect()
+}
+
+/// generates the url requested by [`get_series_list`](fn@get_series_list) without making the request.
+///
+/// # Error
+///
+/// This function returns error when the code is empty or contains one of `&`, `=` and `#` characters.
+pub(crate) fn generate_series_list_url(
+    code: &str, 
+    evds: &common::Evds
+) -> Result<UrlBuilder, ReturnError> {
     
     if code.is_empty() { return Err(ReturnError::EmptyParameter); }
+    check_query_value(code)?;
 
     let return_format_as_url = evds.get_return_format_as_url();
     let api_key_as_url = evds.get_api_key_as_url();
 
-    let url = 
-    format!(
-        "https://evds2.tcmb.gov.tr/service/evds/serieList/{}&{}&code={}",
-        api_key_as_url,
-        return_format_as_url, 
-        code, 
-    );
+    let url = UrlBuilder::new("serieList/")
+        .add_fragment(api_key_as_url)
+        .add_fragment(return_format_as_url)
+        .add_parameter("code", code);
 
-    basic::make_request(&url, basic::Function::GetSeriesList)
+    Ok(url)
 }
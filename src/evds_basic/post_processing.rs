@@ -0,0 +1,120 @@
+use crate::common::{self, ReturnFormat};
+use crate::dataset::Dataset;
+use crate::dataset::header::HeaderLanguage;
+use crate::dataset::unit::{SeriesUnit, rescale_dataset};
+use crate::error::ReturnError;
+use crate::evds_c::convert_to_ascii;
+
+use super::{get_series_units, translate_data_headers};
+
+
+/// is a built-in step run on the data responses of the session, so that the features shaping a response are chosen 
+/// once rather than passed to each call.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PostProcessor {
+    /// converts the Turkish characters into English ones and the other non-ascii characters into asterisks.
+    Ascii,
+    /// renames the series codes in the headers with the names of the series in the language.
+    HeaderTranslation(HeaderLanguage),
+    /// rewrites the values in their shortest decimal form with a dot, e.g. "1.85340" becomes "1.8534".
+    DecimalNormalization,
+    /// rescales the values of each series from its unit to the magnitude of the unit.
+    UnitRescale(SeriesUnit),
+    /// rounds the values to exactly the decimal places, e.g. "1.853400" becomes "1.85" with 2 decimal places.
+    Rounding(u32),
+}
+
+/// runs the post-processors on the data response in the given order.
+///
+/// The series of the response are found from its headers, so the steps reading the series, namely the unit rescale
+/// and the header translation, must come before a header translation. The decimal normalization, the unit rescale and
+/// the rounding rewrite the response from its observations, so the columns other than the date and the series are
+/// dropped. The values are kept as they are received unless one of them is run.
+///
+/// # Error
+///
+/// This function returns the error of the first failing step, e.g. `IncompatibleUnits` error when a series cannot be
+/// rescaled or `UnparsableResponse` error when the response is not a dataset.
+pub(crate) fn run_post_processors(
+    mut response: String,
+    post_processors: &[PostProcessor],
+    evds: &common::Evds
+) -> Result<String, ReturnError> {
+
+    let _span = tracing::info_span!("post_process").entered();
+
+    for post_processor in post_processors {
+        response = match post_processor {
+            PostProcessor::Ascii => {
+                convert_to_ascii(&mut response);
+
+                response
+            },
+            PostProcessor::HeaderTranslation(language) => {
+                let data_series = find_data_series(&Dataset::from_response(&response)?);
+
+                translate_data_headers(&response, &data_series, *language, evds)?
+            },
+            PostProcessor::DecimalNormalization => {
+                Dataset::from_response(&response)?.serialize(evds.get_return_format())
+            },
+            PostProcessor::UnitRescale(target_unit) => {
+                let mut dataset = Dataset::from_response(&response)?;
+
+                let json_evds = evds.with_return_format(ReturnFormat::Json);
+                let mut units = get_series_units(&find_data_series(&dataset), &dataset, &json_evds);
+
+                rescale_dataset(&mut dataset, &mut units, target_unit)?;
+
+                dataset.serialize(evds.get_return_format())
+            },
+            PostProcessor::Rounding(decimal_places) => {
+                let dataset = Dataset::from_response(&response)?;
+
+                dataset.serialize_with_decimal_places(evds.get_return_format(), Some(*decimal_places))
+            },
+        };
+    }
+
+    Ok(response)
+}
+
+/// writes the series of the dataset as the data series of the requests, e.g. "TP.DK.USD.A-TP.DK.EUR.A", from the 
+/// codes in its headers whose dots are underscores.
+fn find_data_series(dataset: &Dataset) -> String {
+    dataset.series_codes
+        .iter()
+        .map(|series_code| series_code.replace('_', "."))
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::common::ApiKey;
+
+    #[test]
+    fn should_run_post_processors_in_order() {
+        let evds = common::Evds::from(ApiKey::from_unverified("key".to_string()).unwrap(), ReturnFormat::Csv);
+        let response = "Tarih,TP_DK_USD_S,UNIXTIME\n12-12-2011,1.85340,1323640800\n13-12-2011,,1323727200".to_string();
+
+        let normalized_response = run_post_processors(response.clone(), &[PostProcessor::DecimalNormalization], &evds);
+
+        assert_eq!(Ok("Tarih,TP_DK_USD_S\n12-12-2011,1.8534\n13-12-2011,".to_string()), normalized_response);
+        assert_eq!(
+            Ok("Tarih,TP_DK_USD_S\n12-12-2011,1.853\n13-12-2011,".to_string()),
+            run_post_processors(response.clone(), &[PostProcessor::Rounding(3)], &evds)
+        );
+        assert_eq!(Ok(response.clone()), run_post_processors(response, &[], &evds));
+
+        let turkish_response = "Tarih,Açıklama\n12-12-2011,Döviz".to_string();
+
+        assert_eq!(
+            Ok("Tarih,Aciklama\n12-12-2011,Doviz".to_string()),
+            run_post_processors(turkish_response, &[PostProcessor::Ascii], &evds)
+        );
+    }
+}
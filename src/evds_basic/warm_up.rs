@@ -0,0 +1,199 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use serde_json::{json, Value};
+
+use crate::common;
+use crate::date::{Date, DateRange, DatePreference};
+use crate::error::ReturnError;
+use crate::scheduler::{self, MAX_PARALLEL_REQUESTS, Priority, wait_for_turn};
+
+
+/// is a series and its dates listed in a warm-up manifest.
+struct WarmUpEntry {
+    code: String,
+    date_preference: DatePreference,
+}
+
+
+/// requests each series of the manifest in its dates in parallel with a rate limit, so that the responses are kept in
+/// the response cache and the observation store before they are needed, e.g. right after a deployment.
+///
+/// The manifest lists the series with their `code`, `startDate` and optional `endDate` either in JSON, e.g.
+/// `{"series":[{"code":"TP.DK.USD.S","startDate":"01-01-2020","endDate":"31-12-2020"}]}`, or in TOML with a
+/// `[[series]]` table for each series whose dates are strings. The responses are kept only by the enabled cache or the
+/// open store. A series that could not be fetched does not stop the warm-up and is reported in the returned JSON
+/// object.
+///
+/// # Error
+///
+/// This function returns `UnparsableResponse` error when the manifest could not be parsed, and `InvalidDate` when a
+/// date of the manifest is invalid.
+pub(crate) fn warm_cache(manifest: &str, evds: &common::Evds) -> Result<String, ReturnError> {
+
+    let entries = parse_manifest(manifest)?;
+
+    let next_entry = AtomicUsize::new(0);
+    let last_request = Mutex::new(None);
+    let outcomes = Mutex::new(Vec::with_capacity(entries.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..MAX_PARALLEL_REQUESTS.min(entries.len()) {
+            scope.spawn(|| {
+                scheduler::set_priority(Priority::Bulk);
+
+                loop {
+                    let index = next_entry.fetch_add(1, Ordering::Relaxed);
+
+                    let entry = match entries.get(index) {
+                        Some(entry) => entry,
+                        None => break,
+                    };
+
+                    wait_for_turn(&last_request);
+
+                    let result = super::get_data(&entry.code, &entry.date_preference, evds).map(|_| ());
+
+                    match outcomes.lock() {
+                        Ok(mut outcomes) => outcomes.push((index, result)),
+                        Err(poisoned) => poisoned.into_inner().push((index, result)),
+                    }
+                }
+            });
+        }
+    });
+
+    let mut outcomes = match outcomes.into_inner() {
+        Ok(outcomes) => outcomes,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    outcomes.sort_by_key(|(index, _)| *index);
+
+    Ok(generate_report(&entries, outcomes.iter().map(|(_, result)| result)))
+}
+
+/// parses a JSON manifest when it starts with `{` and a TOML manifest otherwise.
+fn parse_manifest(manifest: &str) -> Result<Vec<WarmUpEntry>, ReturnError> {
+
+    let parsed_manifest: Option<Value> = match manifest.trim_start().starts_with('{') {
+        true => serde_json::from_str(manifest).ok(),
+        false => toml::from_str(manifest).ok(),
+    };
+
+    parsed_manifest
+        .as_ref()
+        .and_then(read_series_fields)
+        .ok_or(ReturnError::UnparsableResponse)?
+        .iter()
+        .map(|fields| {
+            let find_field = |name: &str| {
+                fields.iter().find(|(field_name, _)| field_name == name).map(|(_, value)| value.trim())
+            };
+
+            let code = find_field("code").filter(|code| !code.is_empty()).ok_or(ReturnError::UnparsableResponse)?;
+            let start_date = find_field("startDate").ok_or(ReturnError::InvalidDate)?;
+
+            let date_preference = match find_field("endDate") {
+                Some(end_date) => DatePreference::Multiple(DateRange::from(start_date, end_date)?),
+                None => DatePreference::Single(Date::from(start_date)?),
+            };
+
+            Ok(WarmUpEntry { code: code.to_string(), date_preference })
+        })
+        .collect()
+}
+
+/// reads the string fields of each object in the `series` array of a parsed manifest, namely of each `[[series]]`
+/// table of a TOML manifest.
+fn read_series_fields(parsed_manifest: &Value) -> Option<Vec<Vec<(String, String)>>> {
+
+    parsed_manifest
+        .get("series")?
+        .as_array()?
+        .iter()
+        .map(|entry| {
+            let fields = entry
+                .as_object()?
+                .iter()
+                .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+                .collect();
+
+            Some(fields)
+        })
+        .collect()
+}
+
+fn generate_report<'a, I>(entries: &[WarmUpEntry], results: I) -> String
+where
+    I: Iterator<Item = &'a Result<(), ReturnError>>
+{
+
+    let series: Vec<Value> = entries
+        .iter()
+        .zip(results)
+        .map(|(entry, result)| {
+            let (start_date, end_date) = entry.date_preference.get_dates();
+
+            let error = match result {
+                Ok(()) => Value::Null,
+                Err(return_error) => Value::from(return_error.to_string()),
+            };
+
+            json!({ "code": entry.code, "startDate": start_date, "endDate": end_date, "error": error })
+        })
+        .collect();
+
+    let failed_count = series.iter().filter(|series| !series["error"].is_null()).count();
+
+    json!({
+        "warmedCount": series.len() - failed_count,
+        "failedCount": failed_count,
+        "series": series,
+    })
+    .to_string()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_manifest_and_generate_report() {
+        let json_manifest = r#"{"series":[
+            {"code":"TP.DK.USD.S","startDate":"01-01-2020","endDate":"31-12-2020"},
+            {"code":"TP.DK.EUR.S","startDate":"13-12-2011"}
+        ]}"#;
+        let toml_manifest = "# exchange rates\n\
+            [[series]]\ncode = \"TP.DK.USD.S\"\nstartDate = \"01-01-2020\"\nendDate = \"31-12-2020\"\n\n\
+            [[series]]\ncode = 'TP.DK.EUR.S' # euro\nstartDate = \"13-12-2011\"\n";
+
+        for manifest in [json_manifest, toml_manifest] {
+            let entries = parse_manifest(manifest).unwrap();
+
+            assert_eq!(2, entries.len());
+            assert_eq!("TP.DK.EUR.S", entries[1].code);
+            assert_eq!(("01-01-2020", "31-12-2020"), entries[0].date_preference.get_dates());
+        }
+
+        assert!(matches!(parse_manifest("code = \"TP.DK.USD.S\""), Err(ReturnError::UnparsableResponse)));
+        assert!(matches!(parse_manifest("[[series]\ncode = \"TP.DK.USD.S\""), Err(ReturnError::UnparsableResponse)));
+        assert!(matches!(
+            parse_manifest("[[series]]\ncode = \"TP.DK.USD.S\"\nstartDate = 2020-01-01"),
+            Err(ReturnError::InvalidDate)
+        ));
+        assert!(matches!(
+            parse_manifest(r#"{"series":[{"code":"TP.DK.USD.S","startDate":"32-01-2020"}]}"#),
+            Err(ReturnError::InvalidDate)
+        ));
+
+        let entries = parse_manifest(json_manifest).unwrap();
+        let report = generate_report(&entries, [Ok(()), Err(ReturnError::EmptyResponse)].iter());
+        let report: Value = serde_json::from_str(&report).unwrap();
+
+        assert_eq!(1, report["warmedCount"]);
+        assert_eq!("Error: Empty page returned.", report["series"][1]["error"]);
+    }
+}
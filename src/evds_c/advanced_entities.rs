@@ -3,6 +3,7 @@ use crate::traits::{converting_to_rust_enum::*, enum_specific::*};
 
 
 /// supplies an aggregation type option to [`tcmb_evds_c_get_advanced_data`](crate::tcmb_evds_c_get_advanced_data).
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub enum TcmbEvdsAggregationType {
     Average,
@@ -14,6 +15,7 @@ pub enum TcmbEvdsAggregationType {
 }
 
 /// supplies a formula option to [`tcmb_evds_c_get_advanced_data`](crate::tcmb_evds_c_get_advanced_data).
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub enum TcmbEvdsFormula {
     Level, 
@@ -28,6 +30,7 @@ pub enum TcmbEvdsFormula {
 }
 
 /// supplies a data frequency option to [`tcmb_evds_c_get_advanced_data`](crate::tcmb_evds_c_get_advanced_data).
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub enum TcmbEvdsDataFrequency {
     Daily, 
@@ -40,18 +43,49 @@ pub enum TcmbEvdsDataFrequency {
     Annual,
 }
 
+/// specifies what happens to a data request whose estimated number of observations is above the limit set via
+/// [`tcmb_evds_c_set_huge_request_policy`](crate::tcmb_evds_c_set_huge_request_policy).
+///
+/// `AllowHugeRequests` makes the requests as they are. `RejectHugeRequests` returns `HugeRequest` error suggesting the 
+/// finest coarser data frequency fitting the limit, and `DowngradeHugeRequests` requests the data in that frequency 
+/// instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsHugeRequestPolicy {
+    AllowHugeRequests,
+    RejectHugeRequests,
+    DowngradeHugeRequests,
+}
+
+impl TcmbEvdsDataFrequency {
+    /// converts a frequency inferred in Rust back to its C option.
+    #[cfg(feature = "parse")]
+    pub(crate) fn from(data_frequency: DataFrequency) -> TcmbEvdsDataFrequency {
+        match data_frequency {
+            DataFrequency::Daily => TcmbEvdsDataFrequency::Daily,
+            DataFrequency::Business => TcmbEvdsDataFrequency::Business,
+            DataFrequency::WeeklyFriday => TcmbEvdsDataFrequency::WeeklyFriday,
+            DataFrequency::TwiceMonthly => TcmbEvdsDataFrequency::TwiceMonthly,
+            DataFrequency::Monthly => TcmbEvdsDataFrequency::Monthly,
+            DataFrequency::Quarterly => TcmbEvdsDataFrequency::Quarterly,
+            DataFrequency::SemiAnnual => TcmbEvdsDataFrequency::SemiAnnual,
+            DataFrequency::Annual => TcmbEvdsDataFrequency::Annual,
+        }
+    }
+}
+
 impl ConvertingToRustEnum<DataFrequency> for TcmbEvdsDataFrequency {
     /// returns `Daily` option by default.
     fn convert(&self) -> DataFrequency {
         match self {
-            TcmbEvdsDataFrequency::Business => return DataFrequency::Business,
-            TcmbEvdsDataFrequency::WeeklyFriday => return DataFrequency::WeeklyFriday,
-            TcmbEvdsDataFrequency::TwiceMonthly => return DataFrequency::TwiceMonthly,
-            TcmbEvdsDataFrequency::Monthly => return DataFrequency::Monthly,
-            TcmbEvdsDataFrequency::Quarterly => return DataFrequency::Quarterly,
-            TcmbEvdsDataFrequency::SemiAnnual => return DataFrequency::SemiAnnual,
-            TcmbEvdsDataFrequency::Annual => return DataFrequency::Annual,
-            _ => return DataFrequency::Daily,
+            TcmbEvdsDataFrequency::Business => DataFrequency::Business,
+            TcmbEvdsDataFrequency::WeeklyFriday => DataFrequency::WeeklyFriday,
+            TcmbEvdsDataFrequency::TwiceMonthly => DataFrequency::TwiceMonthly,
+            TcmbEvdsDataFrequency::Monthly => DataFrequency::Monthly,
+            TcmbEvdsDataFrequency::Quarterly => DataFrequency::Quarterly,
+            TcmbEvdsDataFrequency::SemiAnnual => DataFrequency::SemiAnnual,
+            TcmbEvdsDataFrequency::Annual => DataFrequency::Annual,
+            _ => DataFrequency::Daily,
         }
     }
 }
@@ -60,15 +94,15 @@ impl ConvertingToRustEnum<Formula> for TcmbEvdsFormula {
     /// returns `Level` option by default.
     fn convert(&self) -> Formula {
         match self {
-            TcmbEvdsFormula::PercentageChange => return Formula::PercentageChange,
-            TcmbEvdsFormula::Difference => return Formula::Difference,
-            TcmbEvdsFormula::YearToYearPercentChange => return Formula::YearToYearPercentChange,
-            TcmbEvdsFormula::YearToYearDifferences => return Formula::YearToYearDifferences,
-            TcmbEvdsFormula::PercentageChangeByEndOfPreviousYear => return Formula::PercentageChangeByEndOfPreviousYear,
-            TcmbEvdsFormula::DifferenceByEndOfPreviousYear => return Formula::DifferenceByEndOfPreviousYear,
-            TcmbEvdsFormula::MovingAverage => return Formula::MovingAverage,
-            TcmbEvdsFormula::MovingSum => return Formula::MovingSum,
-            _ => return Formula::Level,
+            TcmbEvdsFormula::PercentageChange => Formula::PercentageChange,
+            TcmbEvdsFormula::Difference => Formula::Difference,
+            TcmbEvdsFormula::YearToYearPercentChange => Formula::YearToYearPercentChange,
+            TcmbEvdsFormula::YearToYearDifferences => Formula::YearToYearDifferences,
+            TcmbEvdsFormula::PercentageChangeByEndOfPreviousYear => Formula::PercentageChangeByEndOfPreviousYear,
+            TcmbEvdsFormula::DifferenceByEndOfPreviousYear => Formula::DifferenceByEndOfPreviousYear,
+            TcmbEvdsFormula::MovingAverage => Formula::MovingAverage,
+            TcmbEvdsFormula::MovingSum => Formula::MovingSum,
+            _ => Formula::Level,
         }
     }
 }
@@ -77,16 +111,70 @@ impl ConvertingToRustEnum<AggregationType> for TcmbEvdsAggregationType {
     /// returns `Average` option by default.
     fn convert(&self) -> AggregationType {
         match self {
-            TcmbEvdsAggregationType::Minimum => return AggregationType::Minimum,
-            TcmbEvdsAggregationType::Maximum => return AggregationType::Maximum,
-            TcmbEvdsAggregationType::Beginning => return AggregationType::Beginning,
-            TcmbEvdsAggregationType::End => return AggregationType::End,
-            TcmbEvdsAggregationType::Cumulative => return AggregationType::Cumulative,
-            _ => return AggregationType::Average,
+            TcmbEvdsAggregationType::Minimum => AggregationType::Minimum,
+            TcmbEvdsAggregationType::Maximum => AggregationType::Maximum,
+            TcmbEvdsAggregationType::Beginning => AggregationType::Beginning,
+            TcmbEvdsAggregationType::End => AggregationType::End,
+            TcmbEvdsAggregationType::Cumulative => AggregationType::Cumulative,
+            _ => AggregationType::Average,
         }
     }
 }
 
+impl ReadingCEnum for TcmbEvdsAggregationType {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsAggregationType::Average,
+        TcmbEvdsAggregationType::Minimum,
+        TcmbEvdsAggregationType::Maximum,
+        TcmbEvdsAggregationType::Beginning,
+        TcmbEvdsAggregationType::End,
+        TcmbEvdsAggregationType::Cumulative,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsAggregationType::Average;
+}
+
+impl ReadingCEnum for TcmbEvdsFormula {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsFormula::Level,
+        TcmbEvdsFormula::PercentageChange,
+        TcmbEvdsFormula::Difference,
+        TcmbEvdsFormula::YearToYearPercentChange,
+        TcmbEvdsFormula::YearToYearDifferences,
+        TcmbEvdsFormula::PercentageChangeByEndOfPreviousYear,
+        TcmbEvdsFormula::DifferenceByEndOfPreviousYear,
+        TcmbEvdsFormula::MovingAverage,
+        TcmbEvdsFormula::MovingSum,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsFormula::Level;
+}
+
+impl ReadingCEnum for TcmbEvdsDataFrequency {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsDataFrequency::Daily,
+        TcmbEvdsDataFrequency::Business,
+        TcmbEvdsDataFrequency::WeeklyFriday,
+        TcmbEvdsDataFrequency::TwiceMonthly,
+        TcmbEvdsDataFrequency::Monthly,
+        TcmbEvdsDataFrequency::Quarterly,
+        TcmbEvdsDataFrequency::SemiAnnual,
+        TcmbEvdsDataFrequency::Annual,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsDataFrequency::Daily;
+}
+
+impl ReadingCEnum for TcmbEvdsHugeRequestPolicy {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsHugeRequestPolicy::AllowHugeRequests,
+        TcmbEvdsHugeRequestPolicy::RejectHugeRequests,
+        TcmbEvdsHugeRequestPolicy::DowngradeHugeRequests,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsHugeRequestPolicy::AllowHugeRequests;
+}
+
 impl EnumSpecific for DataFrequency {}
 impl EnumSpecific for Formula {}
 impl EnumSpecific for AggregationType {}
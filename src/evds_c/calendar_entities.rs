@@ -0,0 +1,24 @@
+/// tells whether CBRT publishes exchange rates at a date, returned by
+/// [`tcmb_evds_c_is_publication_day`](crate::tcmb_evds_c_is_publication_day).
+///
+/// `UnknownPublicationDay` is returned for the weekdays out of the embedded years of the religious holidays, namely
+/// before 2015 or after 2027, which are neither weekends nor national holidays. `InvalidPublicationDate` is returned
+/// when the date is NULL or not a date of the calendar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsPublicationDay {
+    NoPublicationDay,
+    PublicationDay,
+    UnknownPublicationDay,
+    InvalidPublicationDate,
+}
+
+impl TcmbEvdsPublicationDay {
+    pub(crate) fn from(is_publication_day: Option<bool>) -> TcmbEvdsPublicationDay {
+        match is_publication_day {
+            Some(true) => TcmbEvdsPublicationDay::PublicationDay,
+            Some(false) => TcmbEvdsPublicationDay::NoPublicationDay,
+            None => TcmbEvdsPublicationDay::UnknownPublicationDay,
+        }
+    }
+}
@@ -1,23 +1,44 @@
-use std::{os::raw::c_char, ffi::CStr};
+use std::{cell::Cell, ffi::CString, mem::ManuallyDrop, os::raw::c_char, ptr, slice, str};
+use std::sync::Mutex;
 
 use libc::{c_uchar, c_ulong};
 
-use super::error_handling::ReturnErrorC;
-use crate::traits::{converting_to_rust_enum::ConvertingToRustEnum, enum_specific::EnumSpecific};
+use super::error_handling::{ReturnErrorC, TcmbEvdsErrorFormat, format_error_as_json};
+use super::memory_entities::{AllocationKind, MEMORY_COUNTERS};
+use super::settings::get_settings;
+use crate::traits::{converting_to_rust_enum::{ConvertingToRustEnum, ReadingCEnum}, enum_specific::EnumSpecific};
 use crate::common::ReturnFormat;
+#[cfg(any(feature = "catalog", feature = "parse"))]
+use crate::dataset::header::HeaderLanguage;
+use crate::metrics::METRICS;
+use crate::debug_ffi;
+
+thread_local! {
+    /// is true while the checks of a validation are collected, so their errors are neither formatted nor counted.
+    static IS_COLLECTING_PROBLEMS: Cell<bool> = const { Cell::new(false) };
+}
 
 /// contains the text of the response to the submitted request or information about an error that should be easily read 
 /// and handled in C language. 
 ///
-/// To read the response text the string capacity should be used.
+/// To read the response text the string capacity should be used. The buffer capacity is the size of the buffer holding
+/// the text, which may be larger than the text since the received response is handed over without copying it into a
+/// buffer of its length. It must not be changed, so that the output is released correctly.
 ///
 /// The error type becomes `ReturnErrorC::NoError` when there is no error. Otherwise, it returns a related error type 
-/// with the given error.
+/// with the given error. A failed transfer additionally has the detail of the underlying curl error, e.g. 
+/// "curl error 6: Couldn't resolve host name", which is released with the result via 
+/// [`tcmb_evds_c_result_free`](crate::tcmb_evds_c_result_free).
 #[repr(C)]
 pub struct TcmbEvdsResult {
     pub output_ptr: *mut c_uchar,
     pub string_capacity: c_ulong,
     pub error_type: ReturnErrorC,
+    /// is the detail of the underlying error, or NULL when the error has no detail.
+    pub detail_ptr: *mut c_uchar,
+    pub detail_capacity: c_ulong,
+    /// is the size of the buffer of the output in bytes, which is at least the string capacity.
+    pub buffer_capacity: c_ulong,
 }
 
 impl TcmbEvdsResult {
@@ -29,19 +50,153 @@ impl TcmbEvdsResult {
     /// message.
     pub(crate) fn generate_result(request_result: String, error_type: ReturnErrorC) -> TcmbEvdsResult {
         
+        TcmbEvdsResult::generate_formatted_result(request_result, error_type, None)
+    }
+
+    /// generates tcmb evds result type result for an error caused by the given parameter.
+    ///
+    /// The parameter name only appears in the result when the error format is 
+    /// [`TcmbEvdsErrorFormat::JsonErrors`](crate::evds_c::error_handling::TcmbEvdsErrorFormat::JsonErrors).
+    pub(crate) fn generate_parameter_result(
+        error_message: String, 
+        error_type: ReturnErrorC, 
+        parameter_name: &str
+    ) -> TcmbEvdsResult {
+
+        TcmbEvdsResult::generate_formatted_result(error_message, error_type, Some(parameter_name))
+    }
+
+    /// writes error messages in the error format chosen via 
+    /// [`tcmb_evds_c_set_error_format`](crate::tcmb_evds_c_set_error_format) and then generates the result.
+    fn generate_formatted_result(
+        mut request_result: String, 
+        error_type: ReturnErrorC, 
+        parameter_name: Option<&str>
+    ) -> TcmbEvdsResult {
+
+        let is_reported_error = !matches!(error_type, ReturnErrorC::NoError) && !IS_COLLECTING_PROBLEMS.with(Cell::get);
+
+        if is_reported_error { METRICS.record_error(&format!("{:?}", error_type)); }
+
+        if is_reported_error && get_settings().error_format == TcmbEvdsErrorFormat::JsonErrors {
+            request_result = format_error_as_json(&error_type, parameter_name, &request_result);
+        }
+
+        TcmbEvdsResult::generate_raw_result(request_result, error_type)
+    }
+
+    /// generates the result from a text already written in the chosen error format without counting the error in the 
+    /// metrics.
+    pub(crate) fn generate_raw_result(request_result: String, error_type: ReturnErrorC) -> TcmbEvdsResult {
+
         let error_message_length = request_result.len();
-            
-        let boxed_error = request_result.into_boxed_str();
-        let sendable_error = Box::leak(boxed_error).as_mut_ptr();
+
+        MEMORY_COUNTERS.record_allocation(AllocationKind::Result, error_message_length);
+
+        // the buffer of the text is handed over as it is instead of being copied into a buffer of its length.
+        let mut sendable_result = ManuallyDrop::new(request_result);
             
         let result = TcmbEvdsResult { 
-            output_ptr: sendable_error,
+            output_ptr: sendable_result.as_mut_ptr(),
             string_capacity: error_message_length as c_ulong,
             error_type,
+            detail_ptr: ptr::null_mut(),
+            detail_capacity: 0,
+            buffer_capacity: sendable_result.capacity() as c_ulong,
         };
+
+        // the outputs of empty texts may be dangling pointers shared by all of them, so they are not tracked.
+        if error_message_length > 0 { debug_ffi::register(AllocationKind::Result, result.output_ptr); }
         
         return result;
     }
+
+    /// attaches the detail of the underlying error to the result.
+    pub(crate) fn with_detail(mut self, detail: Option<String>) -> TcmbEvdsResult {
+
+        if let Some(detail) = detail {
+            MEMORY_COUNTERS.record_growth(detail.len());
+
+            self.detail_capacity = detail.len() as c_ulong;
+            self.detail_ptr = Box::leak(detail.into_boxed_str()).as_mut_ptr();
+        }
+
+        self
+    }
+
+    /// reads the response text of a result. None is returned when the result contains an error.
+    ///
+    /// # Safety
+    ///
+    /// The result must be returned from this library and its output must not be freed yet.
+    #[cfg(feature = "parse")]
+    pub(crate) unsafe fn read_data(&self) -> Option<String> {
+
+        if !matches!(self.error_type, ReturnErrorC::NoError) || self.output_ptr.is_null() { return None; }
+
+        if self.string_capacity > 0 {
+            debug_ffi::check_live(AllocationKind::Result, self.output_ptr, "the function reading the result");
+        }
+
+        let output = slice::from_raw_parts(self.output_ptr as *const u8, self.string_capacity as usize);
+
+        Some(String::from_utf8_lossy(output).into_owned())
+    }
+
+    /// takes the error type, the text and the detail of the result back and marks them as released in the memory 
+    /// statistics.
+    pub(crate) fn into_parts(self) -> (ReturnErrorC, String, Option<String>) {
+
+        if self.string_capacity > 0 {
+            debug_ffi::release(AllocationKind::Result, self.output_ptr, "tcmb_evds_c_result_free");
+        }
+
+        // the output of a result is a leaked `String` and the detail is a leaked `Box<str>`, both created by 
+        // `TcmbEvdsResult`.
+        let text = match self.output_ptr.is_null() {
+            true => String::new(),
+            false => unsafe {
+                String::from_raw_parts(self.output_ptr, self.string_capacity as usize, self.buffer_capacity as usize)
+            },
+        };
+        let detail = match self.detail_ptr.is_null() {
+            true => None,
+            false => {
+                let detail = ptr::slice_from_raw_parts_mut(self.detail_ptr, self.detail_capacity as usize);
+
+                Some(unsafe { Box::from_raw(detail as *mut str) }.into_string())
+            },
+        };
+
+        if !self.output_ptr.is_null() {
+            let released_bytes = text.len() + detail.as_ref().map_or(0, String::len);
+
+            MEMORY_COUNTERS.record_release(AllocationKind::Result, released_bytes);
+        }
+
+        (self.error_type, text, detail)
+    }
+}
+
+/// runs a check of a validation and gives the error type and the plain error message of the result returned when the 
+/// check fails. The message is not formatted as JSON and not counted in the metrics since the error is not returned to 
+/// C as it is.
+pub(crate) fn collect_problem<T, F>(check: F) -> Result<T, (ReturnErrorC, String)>
+where
+    F: FnOnce() -> Result<T, TcmbEvdsResult>
+{
+
+    IS_COLLECTING_PROBLEMS.with(|is_collecting| is_collecting.set(true));
+
+    let checked = check();
+
+    IS_COLLECTING_PROBLEMS.with(|is_collecting| is_collecting.set(false));
+
+    checked.map_err(|result| {
+        let (error_type, text, _) = result.into_parts();
+
+        (error_type, text)
+    })
 }
 
 /// includes an input string pointer and its size to easily read an input string by Rust language.
@@ -51,59 +206,278 @@ pub struct TcmbEvdsInput {
     pub string_capacity: c_ulong,
 }
 
+/// is the maximum accepted length of an input string in bytes. Longer inputs are rejected without being read entirely.
+pub(crate) const MAX_INPUT_LENGTH: usize = 65_536;
+
 impl TcmbEvdsInput {
     /// generates Rust string with given c_char pointer and its length.
     ///
+    /// The pointer and the capacity given by C are not trusted. The string is never read beyond its NUL terminator or 
+    /// [`MAX_INPUT_LENGTH`], and a capacity shorter than the string takes only the first `string_capacity` bytes.
+    ///
     /// # Error
     /// 
-    /// This function returns an error string and error state true in a tuple structure when the string taken from 
-    /// C could not be converted to Rust string slice. 
+    /// This function returns an error string and error state true in a tuple structure when the pointer is NULL, the 
+    /// string is longer than [`MAX_INPUT_LENGTH`], the capacity exceeds the length of the string, namely the string 
+    /// contains a NUL character before the capacity, or the string could not be converted to Rust string slice. 
     ///
     /// Error message contains the `parameter name` as an error indicator.
     pub(crate) fn get_input(&self, parameter_name: &str) -> (String, bool) {
-        let c_data_series = unsafe { CStr::from_ptr(self.input_ptr) };
-
-        let result_string;
 
-        let error_state;
+        if self.input_ptr.is_null() {
+            return (format!("Error: The given {} parameter is NULL.", parameter_name), true);
+        }
 
-        match  c_data_series.to_str() {
-            Ok(series) => {
-                let rust_string = &series[..self.string_capacity as usize];
+        if self.string_capacity > MAX_INPUT_LENGTH as c_ulong {
+            return (
+                format!(
+                    "Error: The string capacity of the given {} parameter is more than {} bytes.", 
+                    parameter_name,
+                    MAX_INPUT_LENGTH,
+                ),
+                true,
+            );
+        }
 
-                result_string = String::from(rust_string);
+        let capacity = self.string_capacity as usize;
 
-                error_state = false;
-            }
-            _ => {
-                result_string = format!("Error: There is a problem with given {} parameter.", parameter_name);
+        // the string is read until the NUL character or one byte after the capacity, whichever comes first.
+        let string_length = unsafe { libc::strnlen(self.input_ptr, capacity + 1) };
 
-                error_state = true;
-            }
+        if capacity > string_length {
+            return (
+                format!(
+                    "Error: The string capacity ({}) of the given {} parameter exceeds the length of the string ({}).
+                    \nHelp: Please check the capacity and make sure the string does not contain NUL characters.", 
+                    capacity,
+                    parameter_name,
+                    string_length,
+                ),
+                true,
+            );
         }
 
-        (result_string, error_state)
+        let bytes = unsafe { slice::from_raw_parts(self.input_ptr as *const u8, capacity) };
+
+        match str::from_utf8(bytes) {
+            Ok(input) => (String::from(input), false),
+            Err(_) => (format!("Error: There is a problem with given {} parameter.", parameter_name), true),
+        }
     }
 }
 
 
 /// is used to specify the return format of the required response.
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub enum TcmbEvdsReturnFormat {
     Csv,
     Json,
     Xml,
+    /// requests `Json` and verifies the format of the response, which is converted into `Json` when EVDS responds
+    /// with another format.
+    Auto,
+    /// uses the return format of the session set via 
+    /// [`tcmb_evds_c_set_session_defaults`](crate::tcmb_evds_c_set_session_defaults), which is `Json` unless set.
+    SessionFormat,
 }
 
 impl ConvertingToRustEnum<ReturnFormat> for TcmbEvdsReturnFormat {
     /// returns `Json` option by default.
     fn convert(&self) -> ReturnFormat {
         match self {
-            TcmbEvdsReturnFormat::Csv => return ReturnFormat::Csv,
-            TcmbEvdsReturnFormat::Xml => return ReturnFormat::Xml,
-            _ => return ReturnFormat::Json,
+            TcmbEvdsReturnFormat::Csv => ReturnFormat::Csv,
+            TcmbEvdsReturnFormat::Xml => ReturnFormat::Xml,
+            TcmbEvdsReturnFormat::Auto => ReturnFormat::Auto,
+            _ => ReturnFormat::Json,
         }
     }
 }
 
+impl ReadingCEnum for TcmbEvdsReturnFormat {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsReturnFormat::Csv,
+        TcmbEvdsReturnFormat::Json,
+        TcmbEvdsReturnFormat::Xml,
+        TcmbEvdsReturnFormat::Auto,
+        TcmbEvdsReturnFormat::SessionFormat,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsReturnFormat::Json;
+}
+
 impl EnumSpecific for ReturnFormat {}
+
+/// converts a Rust string into a C string by dropping interior NUL characters that C strings cannot carry.
+pub(crate) fn to_c_string(text: &str) -> CString {
+    match CString::new(text) {
+        Ok(c_string) => c_string,
+        Err(_) => CString::new(text.replace('\0', "")).unwrap_or_default(),
+    }
+}
+
+/// gives a NUL terminated copy of a static text, e.g. the text of an enum in the urls. Each text is copied once and 
+/// lives until the process ends, so the returned pointer must not be freed.
+pub(crate) fn to_static_c_string(text: &'static str) -> *const c_char {
+
+    static C_TEXTS: Mutex<Vec<(&str, CString)>> = Mutex::new(Vec::new());
+
+    // the texts cannot be left half updated, so a poisoned lock is recovered.
+    let mut c_texts = match C_TEXTS.lock() {
+        Ok(c_texts) => c_texts,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    if let Some((_, c_text)) = c_texts.iter().find(|(static_text, _)| *static_text == text) { return c_text.as_ptr(); }
+
+    // the buffer of the copy does not move when the vector grows.
+    let c_text = CString::new(text).unwrap_or_default();
+    let c_text_ptr = c_text.as_ptr();
+
+    c_texts.push((text, c_text));
+
+    c_text_ptr
+}
+
+/// specifies the process wide ascii mode set via [`tcmb_evds_c_set_ascii_mode`](crate::tcmb_evds_c_set_ascii_mode).
+///
+/// `AsciiModeDefault` uses the `ascii_mode` argument of each call. `AsciiModeForceOn` and `AsciiModeForceOff` ignore
+/// the argument and always or never convert the responses into ASCII characters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsAsciiMode {
+    AsciiModeDefault,
+    AsciiModeForceOn,
+    AsciiModeForceOff,
+}
+
+impl TcmbEvdsAsciiMode {
+    /// decides whether the response of a call is converted into ASCII characters.
+    pub(crate) fn resolve(&self, ascii_mode: bool) -> bool {
+        match self {
+            TcmbEvdsAsciiMode::AsciiModeForceOn => true,
+            TcmbEvdsAsciiMode::AsciiModeForceOff => false,
+            TcmbEvdsAsciiMode::AsciiModeDefault => ascii_mode,
+        }
+    }
+}
+
+impl ReadingCEnum for TcmbEvdsAsciiMode {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsAsciiMode::AsciiModeDefault,
+        TcmbEvdsAsciiMode::AsciiModeForceOn,
+        TcmbEvdsAsciiMode::AsciiModeForceOff,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsAsciiMode::AsciiModeDefault;
+}
+
+/// specifies the headers of the series columns in a data response.
+///
+/// `CodeHeaders` keeps the series codes of EVDS, e.g. `TP_DK_USD_S`. `EnglishHeaders` and `TurkishHeaders` rewrite them
+/// with the descriptive names of the series in the related language.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsHeaderLanguage {
+    CodeHeaders,
+    EnglishHeaders,
+    TurkishHeaders,
+}
+
+impl TcmbEvdsHeaderLanguage {
+    /// returns None for `CodeHeaders` since the headers are not translated.
+    #[cfg(any(feature = "catalog", feature = "parse"))]
+    pub(crate) fn to_language(self) -> Option<HeaderLanguage> {
+        match self {
+            TcmbEvdsHeaderLanguage::EnglishHeaders => Some(HeaderLanguage::English),
+            TcmbEvdsHeaderLanguage::TurkishHeaders => Some(HeaderLanguage::Turkish),
+            TcmbEvdsHeaderLanguage::CodeHeaders => None,
+        }
+    }
+}
+
+impl ReadingCEnum for TcmbEvdsHeaderLanguage {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsHeaderLanguage::CodeHeaders,
+        TcmbEvdsHeaderLanguage::EnglishHeaders,
+        TcmbEvdsHeaderLanguage::TurkishHeaders,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsHeaderLanguage::CodeHeaders;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_input(text: &[u8], string_capacity: usize) -> TcmbEvdsInput {
+        TcmbEvdsInput { input_ptr: text.as_ptr() as *const c_char, string_capacity: string_capacity as c_ulong }
+    }
+
+    #[test]
+    fn should_check_input() {
+        let text = b"13-12-2011\0";
+
+        assert_eq!(("13-12-2011".to_string(), false), generate_input(text, 10).get_input("date"));
+        assert_eq!(("13-12".to_string(), false), generate_input(text, 5).get_input("date"));
+        assert!(generate_input(text, 11).get_input("date").1);
+        assert!(generate_input(b"13-12\x002011\0", 10).get_input("date").1);
+        assert!(generate_input(b"\xff\xfe\0", 2).get_input("date").1);
+        assert!(generate_input(text, MAX_INPUT_LENGTH + 1).get_input("date").1);
+
+        let null_input = TcmbEvdsInput { input_ptr: std::ptr::null(), string_capacity: 10 };
+
+        assert_eq!(
+            ("Error: The given date parameter is NULL.".to_string(), true), 
+            null_input.get_input("date")
+        );
+
+        // a capacity in the middle of a multi byte character must not panic.
+        assert!(generate_input("ğ\0".as_bytes(), 1).get_input("date").1);
+    }
+
+    #[test]
+    fn should_survive_random_inputs() {
+        // a simple linear congruential generator keeps the test deterministic without extra dependencies.
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next_random = || {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as usize
+        };
+
+        for _ in 0..10_000 {
+            let length = next_random() % 64;
+
+            let mut text = vec![0; length + 1];
+            text[..length].iter_mut().for_each(|byte| *byte = next_random() as u8);
+
+            let string_capacity = match next_random() % 4 {
+                0 => next_random(),
+                1 => length + next_random() % 4,
+                _ => next_random() % (length + 1),
+            };
+
+            let (input, error_state) = generate_input(&text, string_capacity).get_input("fuzz");
+
+            if !error_state { assert!(input.len() <= length); }
+        }
+    }
+
+    #[test]
+    fn should_resolve_ascii_mode() {
+        assert!(TcmbEvdsAsciiMode::AsciiModeDefault.resolve(true));
+        assert!(!TcmbEvdsAsciiMode::AsciiModeDefault.resolve(false));
+        assert!(TcmbEvdsAsciiMode::AsciiModeForceOn.resolve(false));
+        assert!(!TcmbEvdsAsciiMode::AsciiModeForceOff.resolve(true));
+    }
+
+    #[test]
+    fn should_give_static_texts_of_enums() {
+        let json_text = to_static_c_string(TcmbEvdsReturnFormat::Auto.convert().as_str());
+
+        assert_eq!("json", unsafe { std::ffi::CStr::from_ptr(json_text) }.to_str().unwrap());
+        assert_eq!(json_text, to_static_c_string(ReturnFormat::Json.as_str()));
+        assert_ne!(json_text, to_static_c_string(ReturnFormat::Xml.as_str()));
+    }
+}
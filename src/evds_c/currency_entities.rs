@@ -0,0 +1,151 @@
+use crate::evds_currency::{CurrencyCode, ExchangeType};
+use crate::traits::{converting_to_rust_enum::*, enum_specific::*};
+
+
+/// supplies a currency option to [`tcmb_evds_c_build_currency_series`](crate::tcmb_evds_c_build_currency_series).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsCurrencyCode {
+    Usd,
+    Aud,
+    Dkk,
+    Eur,
+    Gbp,
+    Chf,
+    Sek,
+    Cad,
+    Kwd,
+    Nok,
+    Sar,
+    Jpy,
+    Bgn,
+    Ron,
+    Rub,
+    Irr,
+    Cny,
+    Pkr,
+    Qar,
+}
+
+/// supplies the exchange type of the series built via
+/// [`tcmb_evds_c_build_currency_series`](crate::tcmb_evds_c_build_currency_series).
+///
+/// `BothSides` builds the buying and the selling series of the currency together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsExchangeSide {
+    SellingSide,
+    BuyingSide,
+    BothSides,
+}
+
+impl ConvertingToRustEnum<CurrencyCode> for TcmbEvdsCurrencyCode {
+    /// returns `Usd` option by default.
+    fn convert(&self) -> CurrencyCode {
+        match self {
+            TcmbEvdsCurrencyCode::Aud => CurrencyCode::Aud,
+            TcmbEvdsCurrencyCode::Dkk => CurrencyCode::Dkk,
+            TcmbEvdsCurrencyCode::Eur => CurrencyCode::Eur,
+            TcmbEvdsCurrencyCode::Gbp => CurrencyCode::Gbp,
+            TcmbEvdsCurrencyCode::Chf => CurrencyCode::Chf,
+            TcmbEvdsCurrencyCode::Sek => CurrencyCode::Sek,
+            TcmbEvdsCurrencyCode::Cad => CurrencyCode::Cad,
+            TcmbEvdsCurrencyCode::Kwd => CurrencyCode::Kwd,
+            TcmbEvdsCurrencyCode::Nok => CurrencyCode::Nok,
+            TcmbEvdsCurrencyCode::Sar => CurrencyCode::Sar,
+            TcmbEvdsCurrencyCode::Jpy => CurrencyCode::Jpy,
+            TcmbEvdsCurrencyCode::Bgn => CurrencyCode::Bgn,
+            TcmbEvdsCurrencyCode::Ron => CurrencyCode::Ron,
+            TcmbEvdsCurrencyCode::Rub => CurrencyCode::Rub,
+            TcmbEvdsCurrencyCode::Irr => CurrencyCode::Irr,
+            TcmbEvdsCurrencyCode::Cny => CurrencyCode::Cny,
+            TcmbEvdsCurrencyCode::Pkr => CurrencyCode::Pkr,
+            TcmbEvdsCurrencyCode::Qar => CurrencyCode::Qar,
+            _ => CurrencyCode::Usd,
+        }
+    }
+}
+
+impl ConvertingToRustEnum<ExchangeType> for TcmbEvdsExchangeSide {
+    /// returns the selling type by default.
+    fn convert(&self) -> ExchangeType {
+        match self {
+            TcmbEvdsExchangeSide::BuyingSide => ExchangeType::from(true, false),
+            TcmbEvdsExchangeSide::BothSides => ExchangeType::from(true, true),
+            _ => ExchangeType::from(false, true),
+        }
+    }
+}
+
+impl ReadingCEnum for TcmbEvdsCurrencyCode {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsCurrencyCode::Usd,
+        TcmbEvdsCurrencyCode::Aud,
+        TcmbEvdsCurrencyCode::Dkk,
+        TcmbEvdsCurrencyCode::Eur,
+        TcmbEvdsCurrencyCode::Gbp,
+        TcmbEvdsCurrencyCode::Chf,
+        TcmbEvdsCurrencyCode::Sek,
+        TcmbEvdsCurrencyCode::Cad,
+        TcmbEvdsCurrencyCode::Kwd,
+        TcmbEvdsCurrencyCode::Nok,
+        TcmbEvdsCurrencyCode::Sar,
+        TcmbEvdsCurrencyCode::Jpy,
+        TcmbEvdsCurrencyCode::Bgn,
+        TcmbEvdsCurrencyCode::Ron,
+        TcmbEvdsCurrencyCode::Rub,
+        TcmbEvdsCurrencyCode::Irr,
+        TcmbEvdsCurrencyCode::Cny,
+        TcmbEvdsCurrencyCode::Pkr,
+        TcmbEvdsCurrencyCode::Qar,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsCurrencyCode::Usd;
+}
+
+impl ReadingCEnum for TcmbEvdsExchangeSide {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsExchangeSide::SellingSide,
+        TcmbEvdsExchangeSide::BuyingSide,
+        TcmbEvdsExchangeSide::BothSides,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsExchangeSide::SellingSide;
+}
+
+impl EnumSpecific for ExchangeType {}
+
+
+#[cfg(all(test, feature = "parse"))]
+mod tests {
+    use super::*;
+
+    use libc::c_uint;
+
+    use crate::tcmb_evds_c_build_currency_series;
+
+    use TcmbEvdsCurrencyCode::{Qar, Usd};
+    use TcmbEvdsExchangeSide::{BothSides, BuyingSide, SellingSide};
+
+    #[test]
+    fn should_build_currency_series_from_c_options() {
+        let usd_series = tcmb_evds_c_build_currency_series(Usd as c_uint, SellingSide as c_uint, true);
+        let qar_series = tcmb_evds_c_build_currency_series(Qar as c_uint, BothSides as c_uint, false);
+        // the out of range values fall back to the default options since strict enums are disabled.
+        let unknown_series = tcmb_evds_c_build_currency_series(Qar as c_uint + 1, 3, true);
+
+        unsafe {
+            assert_eq!(Some("TP.DK.USD.S.YTL".to_string()), usd_series.read_data());
+            assert_eq!(Some("TP.DK.QAR.A-TP.DK.QAR.S".to_string()), qar_series.read_data());
+            assert_eq!(Some("TP.DK.USD.S.YTL".to_string()), unknown_series.read_data());
+        }
+
+        usd_series.into_parts();
+        qar_series.into_parts();
+        unknown_series.into_parts();
+
+        let exchange_type: ExchangeType = BuyingSide.convert_strictly().unwrap();
+
+        assert!(exchange_type.is_buying_type() && !exchange_type.is_selling_type());
+    }
+}
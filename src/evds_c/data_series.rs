@@ -1,10 +1,20 @@
+#[cfg(feature = "catalog")]
+use serde_json::json;
+
+#[cfg(feature = "catalog")]
+use crate::dataset::catalog::SeriesCatalog;
+#[cfg(feature = "catalog")]
+use crate::date::calendar::CalendarDate;
 use crate::error::ReturnError;
+use crate::evds_core::series::split_currency_series;
 use crate::evds_currency::{ExchangeType, CurrencyCode};
-use crate::traits::ConvertingToRustEnum;
+use crate::traits::converting_to_rust_enum::ConvertingToRustEnum;
 
 
 /// has required variables to crate `CurrencySeries`. 
+// the parts are read only by the `currency` and `catalog` features, and the series are merely validated otherwise.
 #[derive(Debug)]
+#[cfg_attr(not(any(feature = "currency", feature = "catalog")), allow(dead_code))]
 pub(crate) struct DataSeriesParts {
     pub exchange_type: ExchangeType,
     pub currency_code: CurrencyCode,
@@ -15,10 +25,7 @@ pub(crate) struct DataSeriesParts {
 impl std::cmp::PartialEq for DataSeriesParts {
     fn eq(&self, other: &Self) -> bool {
         
-        let exchange_type_comparison = 
-            self.exchange_type.is_buying_type() == other.exchange_type.is_buying_type() &&
-            self.exchange_type.is_selling_type() == other.exchange_type.is_selling_type() &&
-            self.exchange_type.are_both_types() == other.exchange_type.are_both_types();
+        let exchange_type_comparison = self.exchange_type == other.exchange_type;
 
         let currency_code_comparison = self.currency_code.to_string() == other.currency_code.to_string();
 
@@ -33,75 +40,109 @@ impl std::cmp::PartialEq for DataSeriesParts {
 
 /// parses data series into currency unit, exchange type and ytl_mode. 
 ///
-/// An instance for data series is `TP.DK.USD.S.YTL`.
+/// An instance for data series is `TP.DK.USD.S.YTL`. The whitespaces around the data series are ignored.
+///
+/// # Error
+///
+/// This function returns `InvalidSeries` error holding the failed constraint, namely the length, the dot count, an 
+/// unknown currency, an exchange type other than "A" and "S" or a suffix other than "YTL".
 pub(crate) fn parse_series(data_series: &str) -> Result<DataSeriesParts, ReturnError> {
 
-    // Expected values.
-    let min_series_length = 11;
-    let max_series_length = 15;
-    let min_dot_number = 3;
-    let max_dot_number = 4;
-    let min_separated_parts_number = 4;
-    let max_separated_parts_number = 5;
-
-    // Checking general validity.
-    let mut out_of_range;
-
-
-    let length = data_series.len();
-
-    out_of_range = length < min_series_length || length > max_series_length;
-
-    if out_of_range { return Err(ReturnError::InvalidSeries); }
-
-
-    let mut dot_number: u8 = 0;
-    for character in data_series.chars() {
-        if character == '.' { dot_number += 1; }
-    }
-
-    out_of_range = dot_number < min_dot_number || dot_number > max_dot_number;
-
-    if out_of_range { return Err(ReturnError::InvalidSeries); }
-
-    
-    let separated_data_series: Vec<&str> = data_series.split('.').collect();
-
-    let vector_size = separated_data_series.len();
-
-    out_of_range = vector_size < min_separated_parts_number || vector_size > max_separated_parts_number;
-
-    if out_of_range { return Err(ReturnError::InvalidSeries); }
-    
-
-    // Making the required data series part via separated data series.
-    let mut separated_parts = separated_data_series.iter();
-
-    let mut ytl_mode = false;
-
-
-    // ytl_mode occurs at only max separated parts number. 
-    if vector_size == max_separated_parts_number {
-        let ytl_mode_part = separated_parts.next_back().unwrap().to_ascii_lowercase();
-
-        if ytl_mode_part != "ytl" { return Err(ReturnError::InvalidSeries); }
-
-        ytl_mode = true;
-    }
-
+    let series_parts = split_currency_series(data_series)
+        .map_err(|constraint| ReturnError::InvalidSeries(constraint.to_string()))?;
 
     let mut exchange_type = ExchangeType::new();
 
-    let exchange_type_part = separated_parts.next_back().unwrap().to_ascii_lowercase();
+    if series_parts.is_buying { exchange_type.select_buying_type(); }
 
-    if &*exchange_type_part == "a" { exchange_type.select_buying_type(); }
+    let currency_code = match series_parts.currency_code.convert_strictly() {
+        Ok(currency_code) => currency_code,
+        Err(_) => {
+            return Err(ReturnError::InvalidSeries(format!(
+                "the currency \"{}\" is unknown",
+                series_parts.currency_code
+            )));
+        },
+    };
 
-    let currency_code_part = *separated_parts.next_back().unwrap();
 
-    let currency_code = currency_code_part.convert();
+    Ok(DataSeriesParts { exchange_type, currency_code, ytl_mode: series_parts.ytl_mode })
+}
 
+/// parses data series separated by dashes, e.g. `TP.DK.USD.A-TP.DK.EUR.A`, into the parts of each series in order.
+///
+/// # Error
+///
+/// This function returns the error of [`parse_series`] for the first invalid series, whose order is added to the failed
+/// constraint when more than one series is given.
+pub(crate) fn parse_multiple_series(data_series: &str) -> Result<Vec<DataSeriesParts>, ReturnError> {
+
+    let separated_series: Vec<&str> = data_series.split('-').collect();
+
+    if separated_series.len() == 1 { return Ok(vec![parse_series(data_series)?]); }
+
+    separated_series
+        .iter()
+        .enumerate()
+        .map(|(index, series)| {
+            parse_series(series).map_err(|return_error| match return_error {
+                ReturnError::InvalidSeries(constraint) => {
+                    ReturnError::InvalidSeries(format!("{} in the series {}", constraint, index + 1))
+                },
+                return_error => return_error,
+            })
+        })
+        .collect()
+}
 
-    return Ok(DataSeriesParts { exchange_type, currency_code, ytl_mode });
+/// describes how a series code is read as a JSON object, e.g.
+/// `{"code":"TP.DK.USD.S.YTL","currency":"USD","kind":"currency","side":"selling","ytlMode":true}` for a currency
+/// series.
+///
+/// The other codes are described via the series catalog, e.g.
+/// `{"code":"TP.FG.J0","dataGroup":"bie_tukfiy4","frequency":"Monthly","kind":"catalog","startDate":"01-01-2003"}`,
+/// where the fields not received in the series lists are null.
+///
+/// # Error
+///
+/// This function returns the error of [`parse_series`] when the code is neither a currency series nor a series in the
+/// catalog.
+#[cfg(feature = "catalog")]
+pub(crate) fn describe_series(data_series: &str, catalog: &SeriesCatalog) -> Result<String, ReturnError> {
+
+    let data_series = data_series.trim();
+
+    let parse_error = match parse_series(data_series) {
+        Ok(data_series_parts) => {
+            let side = match data_series_parts.exchange_type.is_buying_type() {
+                true => "buying",
+                false => "selling",
+            };
+
+            let description = json!({
+                "code": data_series,
+                "kind": "currency",
+                "currency": data_series_parts.currency_code.as_str(),
+                "side": side,
+                "ytlMode": data_series_parts.ytl_mode,
+            });
+
+            return Ok(description.to_string());
+        },
+        Err(return_error) => return_error,
+    };
+
+    if !catalog.contains(data_series) { return Err(parse_error); }
+
+    let description = json!({
+        "code": data_series,
+        "kind": "catalog",
+        "dataGroup": catalog.find_data_group(data_series),
+        "frequency": catalog.find_frequency(data_series).map(|frequency| format!("{:?}", frequency)),
+        "startDate": catalog.find_start_date(data_series).map(CalendarDate::format),
+    });
+
+    Ok(description.to_string())
 }
 
 
@@ -1325,4 +1366,77 @@ mod tests {
 
         assert_eq!(DataSeriesParts { exchange_type, currency_code, ytl_mode }, data_series_parts);
     }
+
+    #[test]
+    #[cfg(feature = "catalog")]
+    fn should_describe_series() {
+        let mut catalog = SeriesCatalog::new();
+
+        catalog.insert_series_list(r#"[{"SERIE_CODE":"TP.FG.J0","DATAGROUP_CODE":"bie_tukfiy4","FREQUENCY_STR":"AYLIK"},
+            {"SERIE_CODE":"TP.X"}]"#);
+
+        let currency_description = describe_series(" TP.DK.USD.S.YTL ", &catalog).unwrap();
+        let catalog_description = describe_series("TP.FG.J0", &catalog).unwrap();
+
+        assert_eq!(
+            r#"{"code":"TP.DK.USD.S.YTL","currency":"USD","kind":"currency","side":"selling","ytlMode":true}"#,
+            currency_description
+        );
+        assert_eq!(
+            r#"{"code":"TP.FG.J0","dataGroup":"bie_tukfiy4","frequency":"Monthly","kind":"catalog","startDate":null}"#,
+            catalog_description
+        );
+        assert_eq!(
+            Ok(r#"{"code":"TP.X","dataGroup":null,"frequency":null,"kind":"catalog","startDate":null}"#.to_string()),
+            describe_series("TP.X", &catalog)
+        );
+        assert!(matches!(describe_series("TP.DK.USD", &catalog), Err(ReturnError::InvalidSeries(_))));
+    }
+
+    #[test]
+    fn should_report_failed_series_constraint() {
+        let failed_constraint = |data_series: &str| match parse_series(data_series) {
+            Err(ReturnError::InvalidSeries(constraint)) => constraint,
+            _ => panic!("{} is expected to be invalid", data_series),
+        };
+
+        assert_eq!("the length is 9 characters while 11 to 15 characters are expected", failed_constraint("TP.DK.USD"));
+        assert_eq!("2 dots are found while 3 or 4 dots are expected", failed_constraint("TP.DK.USD_S"));
+        assert_eq!("the currency \"XYZ\" is unknown", failed_constraint("TP.DK.XYZ.S"));
+        assert_eq!(
+            "the exchange type \"B\" is neither \"A\" for buying nor \"S\" for selling",
+            failed_constraint("TP.DK.USD.B.YTL")
+        );
+        assert_eq!("the suffix \"TRY\" is not \"YTL\"", failed_constraint("TP.DK.USD.S.TRY"));
+
+        let data_series_parts = parse_series("  TP.DK.EUR.A.YTL\n").unwrap();
+        let mut buying_type = ExchangeType::new();
+        buying_type.select_buying_type();
+
+        assert!(data_series_parts.exchange_type == buying_type && data_series_parts.ytl_mode);
+        assert_eq!("EUR", data_series_parts.currency_code.as_str());
+    }
+
+    #[test]
+    fn should_parse_multiple_series() {
+        let data_series_parts = parse_multiple_series("TP.DK.USD.A-TP.DK.EUR.S.YTL").unwrap();
+        let mut buying_type = ExchangeType::new();
+        buying_type.select_buying_type();
+
+        assert_eq!(2, data_series_parts.len());
+        assert_eq!("USD", data_series_parts[0].currency_code.as_str());
+        assert!(data_series_parts[0].exchange_type == buying_type && !data_series_parts[0].ytl_mode);
+        assert_eq!("EUR", data_series_parts[1].currency_code.as_str());
+        assert!(data_series_parts[1].exchange_type == ExchangeType::new() && data_series_parts[1].ytl_mode);
+
+        assert_eq!(1, parse_multiple_series(" TP.DK.USD.A ").unwrap().len());
+        assert_eq!(
+            Err(ReturnError::InvalidSeries("the currency \"XYZ\" is unknown in the series 2".to_string())),
+            parse_multiple_series("TP.DK.USD.A-TP.DK.XYZ.A").map(|_| ())
+        );
+        assert_eq!(
+            Err(ReturnError::InvalidSeries("the currency \"XYZ\" is unknown".to_string())),
+            parse_multiple_series("TP.DK.XYZ.A").map(|_| ())
+        );
+    }
 }
@@ -0,0 +1,1108 @@
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::{mem, ptr};
+
+use libc::{c_double, c_uint, c_ulong};
+
+use super::advanced_entities::TcmbEvdsDataFrequency;
+use super::common_entities::{TcmbEvdsInput, TcmbEvdsResult, to_c_string};
+use super::error_handling::{ParameterErrors, ReturnErrorC, handle_parameter_return_error, handle_return_error};
+use super::error_handling::{shield_or, shield_result};
+use super::memory_entities::{AllocationKind, MEMORY_COUNTERS};
+use super::read_enum;
+use crate::dataset::Dataset;
+use crate::dataset::diff::{changes_to_json, diff_datasets};
+use crate::dataset::expression::Expression;
+use crate::dataset::frequency::infer_frequency;
+use crate::dataset::merge::merge_datasets;
+use crate::dataset::outlier::{find_interquartile_range_outliers, find_z_score_outliers, outliers_to_json};
+use crate::dataset::precision::MAX_DECIMAL_PLACES;
+use crate::dataset::reshape::to_long_csv;
+#[cfg(feature = "seasonal")]
+use crate::dataset::seasonal::{factors_to_json, seasonally_adjust};
+use crate::dataset::unit::{SeriesUnit, rescale_dataset};
+use crate::debug_ffi;
+use crate::traits::ReadingCEnum;
+
+
+/// is an opaque handle of a parsed EVDS dataset that is created by the functions returning parsed data such as
+/// [`tcmb_evds_c_get_last_n`](crate::tcmb_evds_c_get_last_n).
+///
+/// The dataset is composed of observations, namely rows, and series, namely columns. The content is read via the
+/// `tcmb_evds_c_dataset_*` functions and the handle must be released via
+/// [`tcmb_evds_c_dataset_free`](crate::evds_c::dataset_entities::tcmb_evds_c_dataset_free).
+pub struct TcmbEvdsDataset {
+    dataset: Dataset,
+    dates: Vec<CString>,
+    series_codes: Vec<CString>,
+    /// are the units of the series which are None when unknown.
+    units: Vec<Option<SeriesUnit>>,
+    unit_labels: Vec<Option<CString>>,
+    /// is the approximate size of the dataset reported in the memory statistics.
+    allocated_bytes: usize,
+}
+
+/// specifies the CSV layout of a dataset.
+///
+/// `WideLayout` is the layout of EVDS having a column for each series, e.g. `Tarih,TP_DK_USD_S,TP_DK_EUR_S`. 
+/// `LongLayout` has a row for each value with `Tarih,SERIE_CODE,VALUE` columns as databases usually expect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsLayout {
+    WideLayout,
+    LongLayout,
+}
+
+/// specifies the parser of the JSON responses.
+///
+/// `SerdeJsonBackend` is the default parser. `SimdJsonBackend` parses the responses via the SIMD instructions of the 
+/// CPU and is available when the library is built with the `simd_json` feature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsJsonBackend {
+    SerdeJsonBackend,
+    SimdJsonBackend,
+}
+
+impl TcmbEvdsLayout {
+    /// stringifies the dataset as CSV in the layout.
+    pub(crate) fn write_csv(&self, dataset: &Dataset) -> String {
+        match self {
+            TcmbEvdsLayout::WideLayout => dataset.to_csv(),
+            TcmbEvdsLayout::LongLayout => to_long_csv(dataset),
+        }
+    }
+}
+
+impl ReadingCEnum for TcmbEvdsLayout {
+    const OPTIONS: &'static [Self] = &[TcmbEvdsLayout::WideLayout, TcmbEvdsLayout::LongLayout];
+
+    const FALLBACK: Self = TcmbEvdsLayout::WideLayout;
+}
+
+impl ReadingCEnum for TcmbEvdsJsonBackend {
+    const OPTIONS: &'static [Self] = &[TcmbEvdsJsonBackend::SerdeJsonBackend, TcmbEvdsJsonBackend::SimdJsonBackend];
+
+    const FALLBACK: Self = TcmbEvdsJsonBackend::SerdeJsonBackend;
+}
+
+/// specifies how the outliers of a series are detected.
+///
+/// `ZScore` flags the values further than the threshold in standard deviations from the mean of the series, e.g. 3.
+/// `InterquartileRange` flags the values further than the threshold in interquartile ranges below the first quartile or
+/// above the third quartile, e.g. 1.5, which is robust against the outliers themselves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsOutlierMethod {
+    ZScore,
+    InterquartileRange,
+}
+
+impl TcmbEvdsOutlierMethod {
+    /// finds the indexes of the outlier observations of each series of the dataset.
+    pub(crate) fn find_outliers(&self, dataset: &Dataset, threshold: f64) -> Vec<Vec<usize>> {
+        match self {
+            TcmbEvdsOutlierMethod::ZScore => find_z_score_outliers(dataset, threshold),
+            TcmbEvdsOutlierMethod::InterquartileRange => find_interquartile_range_outliers(dataset, threshold),
+        }
+    }
+}
+
+impl ReadingCEnum for TcmbEvdsOutlierMethod {
+    const OPTIONS: &'static [Self] = &[TcmbEvdsOutlierMethod::ZScore, TcmbEvdsOutlierMethod::InterquartileRange];
+
+    const FALLBACK: Self = TcmbEvdsOutlierMethod::ZScore;
+}
+
+/// is the frequency and the gap statistics of a dataset returned from
+/// [`tcmb_evds_c_infer_frequency`](crate::evds_c::dataset_entities::tcmb_evds_c_infer_frequency).
+///
+/// The frequency is meaningful only when `is_inferred` is true.
+#[repr(C)]
+pub struct TcmbEvdsFrequencyInfo {
+    pub is_inferred: bool,
+    pub frequency: TcmbEvdsDataFrequency,
+    /// is the number of the observations, namely rows, including the ones without values.
+    pub observation_count: c_ulong,
+    /// is the number of the observations without any value, e.g. weekends of a daily series.
+    pub missing_value_count: c_ulong,
+    /// is the number of the intervals between observations that are longer than the frequency expects, e.g. holidays
+    /// of a business series.
+    pub gap_count: c_ulong,
+    /// is the length of the longest gap in days, or zero when there is no gap.
+    pub largest_gap_days: c_ulong,
+}
+
+impl TcmbEvdsFrequencyInfo {
+    fn not_inferred() -> TcmbEvdsFrequencyInfo {
+        TcmbEvdsFrequencyInfo {
+            is_inferred: false,
+            frequency: TcmbEvdsDataFrequency::Daily,
+            observation_count: 0,
+            missing_value_count: 0,
+            gap_count: 0,
+            largest_gap_days: 0,
+        }
+    }
+}
+
+impl TcmbEvdsDataset {
+    /// prepares the C strings of the dataset once to hand them out without allocating for each access.
+    pub(crate) fn from(dataset: Dataset) -> TcmbEvdsDataset {
+        let dates: Vec<CString> = dataset.observations
+            .iter()
+            .map(|observation| to_c_string(&observation.date))
+            .collect();
+
+        let series_codes: Vec<CString> = dataset.series_codes
+            .iter()
+            .map(|code| to_c_string(code))
+            .collect();
+
+        let allocated_bytes = estimate_bytes(&dataset, &dates, &series_codes);
+
+        MEMORY_COUNTERS.record_allocation(AllocationKind::Dataset, allocated_bytes);
+
+        let units = vec![None; dataset.series_codes.len()];
+        let unit_labels = vec![None; dataset.series_codes.len()];
+
+        TcmbEvdsDataset { dataset, dates, series_codes, units, unit_labels, allocated_bytes }
+    }
+
+    /// attaches the units of the series placed in the same order with the series codes.
+    pub(crate) fn with_units(mut self, units: Vec<Option<SeriesUnit>>) -> TcmbEvdsDataset {
+        self.units = units;
+        self.update_unit_labels();
+
+        self
+    }
+
+    fn update_unit_labels(&mut self) {
+        self.unit_labels = self.units
+            .iter()
+            .map(|unit| unit.as_ref().map(|unit| to_c_string(&unit.to_label())))
+            .collect();
+    }
+
+    /// gives the number of series, namely columns, of the dataset.
+    pub(super) fn series_count(&self) -> usize {
+        self.series_codes.len()
+    }
+
+    /// gives the date of the row together with the value of the row and column, which is None when the value is 
+    /// missing in EVDS. None is returned when the row or column is out of range.
+    pub(super) fn read_cell(&self, row: usize, column: usize) -> Option<(&CString, Option<f64>)> {
+        let date = self.dates.get(row)?;
+        let value = self.dataset.observations.get(row)?.values.get(column)?;
+
+        Some((date, *value))
+    }
+
+    /// moves the dataset to the heap and gives its ownership to C.
+    pub(crate) fn into_raw(self) -> *mut TcmbEvdsDataset {
+        let dataset = Box::into_raw(Box::new(self));
+
+        debug_ffi::register(AllocationKind::Dataset, dataset);
+
+        dataset
+    }
+}
+
+/// reads a dataset received from C. The process is aborted with a diagnostic when the dataset is already freed and 
+/// the crate is built with the `debug_ffi` feature.
+unsafe fn read_dataset<'a>(dataset: *const TcmbEvdsDataset, function_name: &str) -> Option<&'a TcmbEvdsDataset> {
+
+    debug_ffi::check_live(AllocationKind::Dataset, dataset, function_name);
+
+    dataset.as_ref()
+}
+
+/// estimates the bytes of the values and strings of the dataset.
+fn estimate_bytes(dataset: &Dataset, dates: &[CString], series_codes: &[CString]) -> usize {
+
+    let values_bytes = dataset.observations.len() * dataset.series_codes.len() * mem::size_of::<Option<f64>>();
+
+    let strings_bytes: usize = dates
+        .iter()
+        .chain(series_codes.iter())
+        .map(|text| text.as_bytes_with_nul().len() * 2)
+        .sum();
+
+    mem::size_of::<TcmbEvdsDataset>() + values_bytes + strings_bytes
+}
+
+
+/// gives the number of observations, namely rows, of the dataset.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_observation_count(dataset: *const TcmbEvdsDataset) -> c_ulong {
+
+    shield_or(0, || {
+        match read_dataset(dataset, "tcmb_evds_c_dataset_observation_count") {
+            Some(dataset) => dataset.dataset.observations.len() as c_ulong,
+            None => 0,
+        }
+    })
+}
+
+/// gives the number of series, namely columns, of the dataset.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_series_count(dataset: *const TcmbEvdsDataset) -> c_ulong {
+
+    shield_or(0, || {
+        match read_dataset(dataset, "tcmb_evds_c_dataset_series_count") {
+            Some(dataset) => dataset.dataset.series_codes.len() as c_ulong,
+            None => 0,
+        }
+    })
+}
+
+/// gives the series code of the given column as a NUL terminated string, e.g. "TP_DK_USD_S".
+///
+/// The returned string belongs to the dataset and remains valid until the dataset is freed. NULL is returned when the
+/// column is out of range.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_series_code(
+    dataset: *const TcmbEvdsDataset,
+    column: c_ulong
+) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        let series_code = read_dataset(dataset, "tcmb_evds_c_dataset_series_code")
+            .and_then(|dataset| dataset.series_codes.get(column as usize));
+
+        match series_code {
+            Some(series_code) => series_code.as_ptr(),
+            None => ptr::null(),
+        }
+    })
+}
+
+/// gives the date of the given observation as a NUL terminated string, e.g. "13-12-2011".
+///
+/// The returned string belongs to the dataset and remains valid until the dataset is freed. NULL is returned when the
+/// row is out of range.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_date(dataset: *const TcmbEvdsDataset, row: c_ulong) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        let date = read_dataset(dataset, "tcmb_evds_c_dataset_date")
+            .and_then(|dataset| dataset.dates.get(row as usize));
+
+        match date {
+            Some(date) => date.as_ptr(),
+            None => ptr::null(),
+        }
+    })
+}
+
+/// writes the value of the given observation and series into `value`.
+///
+/// Returns false without writing when the row or column is out of range or the value is missing in EVDS.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet. The value must be NULL or
+/// a valid pointer to a double.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_value(
+    dataset: *const TcmbEvdsDataset,
+    row: c_ulong,
+    column: c_ulong,
+    value: *mut f64
+) -> bool {
+
+    shield_or(false, || {
+        let found_value = read_dataset(dataset, "tcmb_evds_c_dataset_value")
+            .and_then(|dataset| dataset.dataset.observations.get(row as usize))
+            .and_then(|observation| observation.values.get(column as usize).copied().flatten());
+
+        match (found_value, value.as_mut()) {
+            (Some(found_value), Some(value)) => {
+                *value = found_value;
+                true
+            },
+            _ => false,
+        }
+    })
+}
+
+/// writes the `UNIXTIME` of the given observation, namely the start of its date on Turkish time in seconds since 
+/// 01-01-1970 00:00 UTC, into the value, e.g. 1323727200 for "13-12-2011".
+///
+/// The unix time is taken from the response when EVDS sends it, and calculated from the date otherwise.
+///
+/// Returns false without writing when the row is out of range or the unix time is unknown, e.g. a monthly date without
+/// `UNIXTIME` in the response.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet. The value must be NULL or
+/// a valid pointer to a 64 bit integer.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_unix_time(
+    dataset: *const TcmbEvdsDataset,
+    row: c_ulong,
+    value: *mut i64
+) -> bool {
+
+    shield_or(false, || {
+        let found_unix_time = read_dataset(dataset, "tcmb_evds_c_dataset_unix_time")
+            .and_then(|dataset| dataset.dataset.observations.get(row as usize))
+            .and_then(|observation| observation.unix_time);
+
+        match (found_unix_time, value.as_mut()) {
+            (Some(found_unix_time), Some(value)) => {
+                *value = found_unix_time;
+                true
+            },
+            _ => false,
+        }
+    })
+}
+
+/// gives the unit of the series of the given column as a NUL terminated string, e.g. "thousand TRY", "million USD",
+/// "index 2003=100" or "percent".
+///
+/// The units are parsed from the series names in EVDS metadata, e.g. "(Bin TL)". The returned string belongs to the
+/// dataset and remains valid until the dataset is freed or rescaled. NULL is returned when the column is out of range
+/// or the unit is unknown.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_unit(dataset: *const TcmbEvdsDataset, column: c_ulong) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        let unit_label = read_dataset(dataset, "tcmb_evds_c_dataset_unit")
+            .and_then(|dataset| dataset.unit_labels.get(column as usize))
+            .and_then(Option::as_ref);
+
+        match unit_label {
+            Some(unit_label) => unit_label.as_ptr(),
+            None => ptr::null(),
+        }
+    })
+}
+
+/// writes the column of the series with the given code into `column` and returns true, so that the values of joined or
+/// wide datasets are read without knowing the layout of the EVDS response.
+///
+/// The code is written with dots as in the requests, e.g. "TP.DK.USD.S", or with underscores as in the responses, e.g.
+/// "TP_DK_USD_S". Returns false without writing when the code is not found or invalid.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet. The column must be NULL
+/// or a valid pointer to an unsigned long.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_find_column(
+    dataset: *const TcmbEvdsDataset,
+    series_code: TcmbEvdsInput,
+    column: *mut c_ulong
+) -> bool {
+
+    shield_or(false, || {
+        let (rust_series_code, series_code_error_state) = series_code.get_input("series_code");
+
+        if series_code_error_state { return false; }
+
+        let rust_series_code = rust_series_code.trim().replace('.', "_");
+
+        let found_column = read_dataset(dataset, "tcmb_evds_c_dataset_find_column")
+            .and_then(|dataset| dataset.dataset.series_codes.iter().position(|code| *code == rust_series_code));
+
+        match (found_column, column.as_mut()) {
+            (Some(found_column), Some(column)) => {
+                *column = found_column as c_ulong;
+                true
+            },
+            _ => false,
+        }
+    })
+}
+
+/// gives the number of the values of the given column, which is the number of observations, or zero when the column
+/// is out of range.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_column_len(dataset: *const TcmbEvdsDataset, column: c_ulong) -> c_ulong {
+
+    shield_or(0, || {
+        match read_dataset(dataset, "tcmb_evds_c_dataset_column_len") {
+            Some(dataset) if (column as usize) < dataset.series_count() => {
+                dataset.dataset.observations.len() as c_ulong
+            },
+            _ => 0,
+        }
+    })
+}
+
+/// copies the values of the given column into the buffer in the order of the observations and returns the number of
+/// the copied values.
+///
+/// At most `capacity` values are copied, so the length of the column is read via 
+/// [`tcmb_evds_c_dataset_column_len`](crate::evds_c::dataset_entities::tcmb_evds_c_dataset_column_len) to copy all of
+/// them. A missing value in EVDS, e.g. of a holiday, is copied as NaN. Zero is returned when the column is out of range
+/// or the buffer is NULL.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet. The buffer must be NULL 
+/// or a valid pointer to at least `capacity` doubles.
+///
+/// # Example
+///
+/// ```C
+///     unsigned long column;
+///
+///     if (tcmb_evds_c_dataset_find_column(dataset, series_code, &column)) {
+///         unsigned long length = tcmb_evds_c_dataset_column_len(dataset, column);
+///         double* values = malloc(length * sizeof(double));
+///
+///         tcmb_evds_c_dataset_copy_column(dataset, column, values, length);
+///     }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_copy_column(
+    dataset: *const TcmbEvdsDataset,
+    column: c_ulong,
+    buffer: *mut f64,
+    capacity: c_ulong
+) -> c_ulong {
+
+    shield_or(0, || {
+        let dataset = match read_dataset(dataset, "tcmb_evds_c_dataset_copy_column") {
+            Some(dataset) if !buffer.is_null() && (column as usize) < dataset.series_count() => dataset,
+            _ => return 0,
+        };
+
+        let values = dataset.dataset.observations
+            .iter()
+            .take(capacity as usize)
+            .map(|observation| observation.values.get(column as usize).copied().flatten().unwrap_or(f64::NAN));
+
+        let mut copied_count = 0;
+
+        for (index, value) in values.enumerate() {
+            *buffer.add(index) = value;
+            copied_count += 1;
+        }
+
+        copied_count
+    })
+}
+
+/// rescales the values of each series of the dataset to the magnitude of the target unit, so that series in mixed 
+/// units such as thousand TRY and million TRY can be joined safely.
+///
+/// The target unit is written as an optional magnitude followed by an optional measure, e.g. "million TRY", 
+/// "thousand USD" or only "million". A target without a measure rescales each series in its own measure. Currencies are
+/// not converted into each other. The new unit of each series is read via
+/// [`tcmb_evds_c_dataset_unit`](crate::evds_c::dataset_entities::tcmb_evds_c_dataset_unit), and the result includes the
+/// label of the target unit.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the dataset is NULL or the target unit is not recognized, and 
+/// `IncompatibleUnits` when the unit of a series is unknown or differs from the measure of the target unit. The dataset
+/// is left unchanged in the case of an error.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     target_unit.input_ptr = "million TRY";
+///     target_unit.string_capacity = strlen(target_unit.input_ptr);
+///
+///     TcmbEvdsResult rescale_result = tcmb_evds_c_rescale(dataset, target_unit);
+///
+///     if (rescale_result.error_type == IncompatibleUnits) { /* A Process */ };
+///
+///     tcmb_evds_c_result_free(rescale_result);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_rescale(
+    dataset: *mut TcmbEvdsDataset,
+    target_unit: TcmbEvdsInput
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_rescale", || {
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        debug_ffi::check_live(AllocationKind::Dataset, dataset, "tcmb_evds_c_rescale");
+
+        let dataset = match dataset.as_mut() {
+            Some(dataset) => dataset,
+            None => {
+                let error_message = "Error: The dataset is NULL.".to_string();
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, parameter_error, "dataset");
+            },
+        };
+
+        let (rust_target_unit, target_unit_error_state) = target_unit.get_input("target_unit");
+
+        if target_unit_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_target_unit, parameter_error, "target_unit");
+        }
+
+        let target_unit = match SeriesUnit::parse(&rust_target_unit) {
+            Some(target_unit) => target_unit,
+            None => {
+                let error_message = format!("Error: The target unit \"{}\" is not recognized.", rust_target_unit);
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, parameter_error, "target_unit");
+            },
+        };
+
+        if let Err(return_error) = rescale_dataset(&mut dataset.dataset, &mut dataset.units, &target_unit) {
+            return handle_return_error(return_error);
+        }
+
+        dataset.update_unit_labels();
+
+        TcmbEvdsResult::generate_result(target_unit.to_label(), ReturnErrorC::NoError)
+    })
+}
+
+/// rounds each value of the dataset in place to the given decimal places half away from zero, since EVDS sometimes 
+/// returns more digits than the consumers of the values expect.
+///
+/// Returns false and leaves the dataset unchanged when the dataset is NULL or the decimal places are more than 15.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     // 1.853449 becomes 1.8534.
+///     if (!tcmb_evds_c_dataset_round(dataset, 4)) { /* A Process */ };
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_round(dataset: *mut TcmbEvdsDataset, decimal_places: c_uint) -> bool {
+
+    shield_or(false, || {
+        debug_ffi::check_live(AllocationKind::Dataset, dataset, "tcmb_evds_c_dataset_round");
+
+        match dataset.as_mut() {
+            Some(dataset) if decimal_places <= MAX_DECIMAL_PLACES => {
+                dataset.dataset.round_values(decimal_places);
+                true
+            },
+            _ => false,
+        }
+    })
+}
+
+/// removes the seasonal component of each series of a monthly or a quarterly dataset in place, since EVDS publishes
+/// only some series in seasonally adjusted form.
+///
+/// The decomposition is based on moving averages and uses seasonal factors multiplying the series when all of its
+/// values are positive, or added to the series otherwise. The result includes the removed factors of each series for
+/// each month or quarter as a JSON object, e.g. `{"TP_FG_J0":{"model":"multiplicative","factors":[0.98,1.01,...]}}`.
+/// This function is available when the library is built with the `seasonal` feature.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the dataset is NULL, and `SeasonalAdjustmentFailed` when the dataset is
+/// not monthly or quarterly, its dates have gaps or a series covers less than three years. The dataset is left 
+/// unchanged in the case of an error.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult adjustment_result = tcmb_evds_c_seasonally_adjust(dataset);
+///
+///     if (adjustment_result.error_type == SeasonalAdjustmentFailed) { /* A Process */ };
+///
+///     tcmb_evds_c_result_free(adjustment_result);
+/// ```
+#[cfg(feature = "seasonal")]
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_seasonally_adjust(dataset: *mut TcmbEvdsDataset) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_seasonally_adjust", || {
+        debug_ffi::check_live(AllocationKind::Dataset, dataset, "tcmb_evds_c_seasonally_adjust");
+
+        let dataset = match dataset.as_mut() {
+            Some(dataset) => dataset,
+            None => {
+                let error_message = "Error: The dataset is NULL.".to_string();
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, ReturnErrorC::ParameterError, "dataset");
+            },
+        };
+
+        match seasonally_adjust(&mut dataset.dataset) {
+            Ok(all_factors) => {
+                let factors = factors_to_json(&dataset.dataset.series_codes, &all_factors);
+
+                TcmbEvdsResult::generate_result(factors, ReturnErrorC::NoError)
+            },
+            Err(return_error) => handle_return_error(return_error),
+        }
+    })
+}
+
+/// detects the suspicious values of each series of the dataset, e.g. to validate the revisions of CBRT data, and 
+/// returns the indexes of their observations as a JSON object, e.g. `{"TP_DK_USD_S":[3,17]}`.
+///
+/// The indexes are the ones used by [`tcmb_evds_c_dataset_value`]. Missing values are never flagged and the series 
+/// without outliers have empty arrays.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the dataset is NULL or the threshold is not a positive number.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult outliers = tcmb_evds_c_detect_outliers(dataset, InterquartileRange, 1.5);
+///
+///     if (outliers.error_type == NoError) { /* A Process */ };
+///
+///     tcmb_evds_c_result_free(outliers);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_detect_outliers(
+    dataset: *const TcmbEvdsDataset,
+    method: c_uint,
+    threshold: c_double
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_detect_outliers", || {
+        let mut parameter_errors = ParameterErrors::new();
+
+        let dataset = read_dataset(dataset, "tcmb_evds_c_detect_outliers");
+        let method: TcmbEvdsOutlierMethod = parameter_errors.read_enum("method", method);
+
+        if dataset.is_none() {
+            parameter_errors.add(ReturnErrorC::ParameterError, "dataset", "Error: The dataset is NULL.".to_string());
+        }
+        if !(threshold.is_finite() && threshold > 0.0) {
+            let error_message = "Error: The threshold must be a positive number.".to_string();
+
+            parameter_errors.add(ReturnErrorC::ParameterError, "threshold", error_message);
+        }
+
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
+
+        // the dataset is checked above.
+        let dataset = dataset.unwrap();
+
+        let outliers = method.find_outliers(&dataset.dataset, threshold);
+
+        TcmbEvdsResult::generate_result(
+            outliers_to_json(&dataset.dataset.series_codes, &outliers), 
+            ReturnErrorC::NoError
+        )
+    })
+}
+
+/// stringifies the dataset as CSV in the given layout.
+///
+/// Missing values are empty cells in the wide layout and are not written in the long layout, e.g. 
+/// `Tarih,SERIE_CODE,VALUE\n13-12-2011,TP_DK_USD_S,1.8673\n13-12-2011,TP_DK_EUR_S,2.4361`.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the dataset is NULL.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult long_csv = tcmb_evds_c_dataset_to_csv(dataset, LongLayout);
+///
+///     fwrite(long_csv.output_ptr, long_csv.string_capacity, 1, database_import_file);
+///
+///     tcmb_evds_c_result_free(long_csv);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_to_csv(
+    dataset: *const TcmbEvdsDataset,
+    layout: c_uint
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_dataset_to_csv", || {
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        let layout: TcmbEvdsLayout = match read_enum(layout) {
+            Ok(layout) => layout,
+            Err(return_error) => return handle_parameter_return_error(return_error, "layout"),
+        };
+
+        let dataset = match read_dataset(dataset, "tcmb_evds_c_dataset_to_csv") {
+            Some(dataset) => dataset,
+            None => {
+                let error_message = "Error: The dataset is NULL.".to_string();
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, parameter_error, "dataset");
+            },
+        };
+
+        TcmbEvdsResult::generate_result(layout.write_csv(&dataset.dataset), ReturnErrorC::NoError)
+    })
+}
+
+/// compares two fetches of the same data and returns the added, changed and removed values as a JSON object, so 
+/// revisions of CBRT to previously published figures can be detected.
+///
+/// The observations are matched by their dates and the series by their codes. Each change includes the date, the series
+/// code, the old value and the new value where a missing value is `null`, e.g. 
+/// `{"added":[],"changed":[{"date":"12-12-2011","series":"TP_DK_USD_S","oldValue":1.8534,"newValue":1.8535}],
+/// "removed":[]}`.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when one of the datasets is NULL.
+///
+/// # Safety
+///
+/// The datasets must be NULL or valid pointers returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult diff_result = tcmb_evds_c_diff(yesterdays_dataset, todays_dataset);
+///
+///     fwrite(diff_result.output_ptr, diff_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(diff_result);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_diff(
+    dataset_old: *const TcmbEvdsDataset,
+    dataset_new: *const TcmbEvdsDataset
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_diff", || {
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        let old_dataset = match read_dataset(dataset_old, "tcmb_evds_c_diff") {
+            Some(old_dataset) => old_dataset,
+            None => {
+                let error_message = "Error: The dataset_old is NULL.".to_string();
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, parameter_error, "dataset_old");
+            },
+        };
+
+        let new_dataset = match read_dataset(dataset_new, "tcmb_evds_c_diff") {
+            Some(new_dataset) => new_dataset,
+            None => {
+                let error_message = "Error: The dataset_new is NULL.".to_string();
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, parameter_error, "dataset_new");
+            },
+        };
+
+        let changes = diff_datasets(&old_dataset.dataset, &new_dataset.dataset);
+
+        TcmbEvdsResult::generate_result(changes_to_json(&changes), ReturnErrorC::NoError)
+    })
+}
+
+/// infers the frequency of the dataset from the intervals between its dates, and counts its missing values and gaps,
+/// so that the correct frequency and aggregation options can be picked for
+/// [`tcmb_evds_c_get_advanced_data`](crate::tcmb_evds_c_get_advanced_data).
+///
+/// Daily dates, e.g. "13-12-2011", and the period dates of EVDS, e.g. "2011-12", "2011-Q4", "2011-S2" and "2011", are
+/// recognized. A daily dataset without any value on weekends is inferred as `Business`, and its holidays are counted
+/// as gaps.
+///
+/// `is_inferred` is false when the dataset is NULL, its dates are not recognized or it has less than two observations
+/// with values. The counts are still filled for a non NULL dataset.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsFrequencyInfo frequency_info = tcmb_evds_c_infer_frequency(dataset);
+///
+///     if (frequency_info.is_inferred && frequency_info.frequency == Business)
+///         printf("%lu holidays found.\n", frequency_info.gap_count);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_infer_frequency(dataset: *const TcmbEvdsDataset) -> TcmbEvdsFrequencyInfo {
+
+    shield_or(TcmbEvdsFrequencyInfo::not_inferred(), || {
+        let dataset = match read_dataset(dataset, "tcmb_evds_c_infer_frequency") {
+            Some(dataset) => dataset,
+            None => return TcmbEvdsFrequencyInfo::not_inferred(),
+        };
+
+        let report = infer_frequency(&dataset.dataset);
+
+        TcmbEvdsFrequencyInfo {
+            is_inferred: report.frequency.is_some(),
+            frequency: report.frequency.map_or(TcmbEvdsDataFrequency::Daily, TcmbEvdsDataFrequency::from),
+            observation_count: report.observation_count as c_ulong,
+            missing_value_count: report.missing_value_count as c_ulong,
+            gap_count: report.gap_count as c_ulong,
+            largest_gap_days: report.largest_gap_days as c_ulong,
+        }
+    })
+}
+
+/// evaluates an expression over the series of the bindings dataset and returns the result as a single series in the 
+/// EVDS JSON layout, so that one-off transforms are applied to fetched data without new requests.
+///
+/// The expression consists of the series codes of the dataset, e.g. `TP.DK.USD.S` or `TP_DK_USD_S`, numbers, `+`, `-`,
+/// `*`, `/`, parentheses and the `log(x)`, `diff(x)` and `lag(x, n)` functions working along the observations. The 
+/// series of the result is named after the expression and has the dates of the dataset. A missing value, a division by 
+/// zero, the logarithm of a non-positive value or a value before the first observation gives a missing value.
+///
+/// # Error
+///
+/// This function returns `InvalidExpression` error when the expression is invalid or refers to a series that is not in 
+/// the dataset, and `ParameterError` when the expression cannot be read or the dataset is NULL.
+///
+/// # Safety
+///
+/// The bindings must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput expression = { "diff(log(TP.DK.USD.S)) * 100", 28 };
+///
+///     TcmbEvdsResult growth_result = tcmb_evds_c_eval(expression, dataset);
+///
+///     fwrite(growth_result.output_ptr, growth_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(growth_result);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_eval(
+    expression: TcmbEvdsInput,
+    bindings: *const TcmbEvdsDataset
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_eval", || {
+        let mut parameter_errors = ParameterErrors::new();
+
+        let rust_expression = parameter_errors.read_input(&expression, "expression");
+
+        let parsed_expression = match &rust_expression {
+            Some(rust_expression) => parameter_errors.check("expression", Expression::parse(rust_expression)),
+            None => None,
+        };
+
+        let bindings = read_dataset(bindings, "tcmb_evds_c_eval");
+
+        if bindings.is_none() {
+            parameter_errors.add(ReturnErrorC::ParameterError, "bindings", "Error: The dataset is NULL.".to_string());
+        }
+
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
+
+        // all of the parameters are checked above.
+        let (rust_expression, parsed_expression, bindings) = 
+            (rust_expression.unwrap(), parsed_expression.unwrap(), bindings.unwrap());
+
+        match parsed_expression.evaluate_into_dataset(rust_expression.trim(), &bindings.dataset) {
+            Ok(evaluated_dataset) => TcmbEvdsResult::generate_result(evaluated_dataset.to_json(), ReturnErrorC::NoError),
+            Err(return_error) => handle_parameter_return_error(return_error, "expression"),
+        }
+    })
+}
+
+/// merges the datasets fetched for different date ranges, e.g. by chunking a long history, into a single dataset 
+/// whose dates are unique and sorted, so that overlapping windows do not count an observation twice.
+///
+/// The series of the merged dataset are the union of the series of the datasets. A date found in more than one 
+/// dataset takes the values of the later dataset in the array unless they are missing, so the latest revision wins. 
+/// The merged dataset is returned in the EVDS JSON layout. Additionally, when `merged` is not NULL, a new dataset 
+/// handle is written into it and must be released with `tcmb_evds_c_dataset_free`. The given datasets are not changed.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the array is NULL, `n` is zero or one of the datasets is NULL.
+///
+/// # Safety
+///
+/// The array must be NULL or point to `n` pointers, each of which must be NULL or a valid dataset returned from this 
+/// library and not freed yet. The `merged` must be NULL or a valid pointer to a `TcmbEvdsDataset` pointer.
+///
+/// # Example
+///
+/// ```C
+///     const TcmbEvdsDataset* chunks[] = { first_chunk, second_chunk };
+///     TcmbEvdsDataset* merged = NULL;
+///
+///     TcmbEvdsResult merge_result = tcmb_evds_c_merge(chunks, 2, &merged);
+///
+///     if (merge_result.error_type == NoError) { /* A Process */ };
+///
+///     tcmb_evds_c_result_free(merge_result);
+///     tcmb_evds_c_dataset_free(merged);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_merge(
+    datasets: *const *const TcmbEvdsDataset,
+    n: c_ulong,
+    merged: *mut *mut TcmbEvdsDataset
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_merge", || {
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        if datasets.is_null() || n == 0 {
+            let error_message = "Error: At least one dataset should be given.".to_string();
+
+            return TcmbEvdsResult::generate_parameter_result(error_message, parameter_error, "datasets");
+        }
+
+        let given_datasets: Option<Vec<&Dataset>> = (0..n as usize)
+            .map(|index| read_dataset(*datasets.add(index), "tcmb_evds_c_merge").map(|dataset| &dataset.dataset))
+            .collect();
+
+        let given_datasets = match given_datasets {
+            Some(given_datasets) => given_datasets,
+            None => {
+                let error_message = "Error: One of the datasets is NULL.".to_string();
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, parameter_error, "datasets");
+            },
+        };
+
+        let merged_dataset = merge_datasets(&given_datasets);
+
+        let response = merged_dataset.to_json();
+
+        if let Some(merged) = merged.as_mut() { *merged = TcmbEvdsDataset::from(merged_dataset).into_raw(); }
+
+        TcmbEvdsResult::generate_result(response, ReturnErrorC::NoError)
+    })
+}
+
+/// releases the dataset. Passing NULL is allowed and does nothing.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_dataset_free(dataset: *mut TcmbEvdsDataset) {
+
+    shield_or((), || {
+        if dataset.is_null() { return; }
+
+        debug_ffi::release(AllocationKind::Dataset, dataset, "tcmb_evds_c_dataset_free");
+
+        let dataset = Box::from_raw(dataset);
+
+        MEMORY_COUNTERS.record_release(AllocationKind::Dataset, dataset.allocated_bytes);
+
+        drop(dataset);
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_copy_columns_by_series_code() {
+        let response = r#"{"totalCount":2,"items":[
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8534","TP_DK_EUR_S":"2.4361"},
+            {"Tarih":"13-12-2011","TP_DK_USD_S":"1.8673","TP_DK_EUR_S":null}]}"#;
+
+        let dataset = TcmbEvdsDataset::from(Dataset::from_json(response).unwrap()).into_raw();
+        let series_code = CString::new("TP.DK.EUR.S").unwrap();
+        let series_code = TcmbEvdsInput { input_ptr: series_code.as_ptr(), string_capacity: 11 };
+
+        unsafe {
+            let mut column = 0;
+
+            assert!(tcmb_evds_c_dataset_find_column(dataset, series_code, &mut column));
+            assert_eq!(2, tcmb_evds_c_dataset_column_len(dataset, column));
+            assert_eq!(0, tcmb_evds_c_dataset_column_len(dataset, 2));
+
+            let mut values = [0.0; 3];
+
+            assert_eq!(2, tcmb_evds_c_dataset_copy_column(dataset, column, values.as_mut_ptr(), 3));
+            assert_eq!(2.4361, values[0]);
+            assert!(values[1].is_nan());
+            assert_eq!(1, tcmb_evds_c_dataset_copy_column(dataset, column, values.as_mut_ptr(), 1));
+            assert_eq!(0, tcmb_evds_c_dataset_copy_column(dataset, column, ptr::null_mut(), 3));
+
+            assert!(!tcmb_evds_c_dataset_round(dataset, 16));
+            assert!(tcmb_evds_c_dataset_round(dataset, 2));
+            assert_eq!(2, tcmb_evds_c_dataset_copy_column(dataset, column, values.as_mut_ptr(), 3));
+            assert_eq!(2.44, values[0]);
+
+            tcmb_evds_c_dataset_free(dataset);
+        }
+    }
+
+    #[test]
+    fn should_evaluate_expression_over_dataset() {
+        let response = r#"{"totalCount":2,"items":[
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.5","TP_DK_EUR_S":"2.5"},
+            {"Tarih":"13-12-2011","TP_DK_USD_S":"2","TP_DK_EUR_S":null}]}"#;
+
+        let dataset = TcmbEvdsDataset::from(Dataset::from_json(response).unwrap()).into_raw();
+
+        let evaluate = |text: &str| {
+            let expression = CString::new(text).unwrap();
+            let expression = TcmbEvdsInput { input_ptr: expression.as_ptr(), string_capacity: text.len() as c_ulong };
+
+            unsafe { tcmb_evds_c_eval(expression, dataset).into_parts() }
+        };
+
+        let (error_type, evaluated, _) = evaluate("TP_DK_EUR_S - TP.DK.USD.S");
+
+        assert!(matches!(error_type, ReturnErrorC::NoError));
+
+        let evaluated = Dataset::from_json(&evaluated).unwrap();
+
+        assert_eq!(vec!["TP_DK_EUR_S - TP.DK.USD.S".to_string()], evaluated.series_codes);
+        assert_eq!(vec![Some(1.0)], evaluated.observations[0].values);
+        assert_eq!(vec![None], evaluated.observations[1].values);
+        assert!(matches!(evaluate("TP.DK.GBP.S").0, ReturnErrorC::InvalidExpression));
+        assert!(matches!(evaluate("lag(TP.DK.USD.S)").0, ReturnErrorC::InvalidExpression));
+
+        unsafe { tcmb_evds_c_dataset_free(dataset); }
+    }
+}
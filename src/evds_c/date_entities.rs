@@ -1,3 +1,4 @@
+use crate::date::calendar::days_in_month;
 use crate::evds_c::common_entities::*;
 use crate::evds_c::error_handling::ReturnErrorC;
 use crate::traits::checking_string_character::CheckingStringCharacter;
@@ -11,25 +12,28 @@ pub(crate) fn check_date_string_validity(
 
     if !valid_amount_number {
         return Err(
-            TcmbEvdsResult::generate_result(
+            TcmbEvdsResult::generate_parameter_result(
                 "Error: There is not enough amount of required number in the given date data.".to_string(),
                 ReturnErrorC::MissingNumberInDateData,
+                "date",
             )
         );
     }
     if !valid_amount_dash {
         return Err(
-            TcmbEvdsResult::generate_result(
+            TcmbEvdsResult::generate_parameter_result(
                 "Error: There are some problems with dashes in the given date data.".to_string(),
                 ReturnErrorC::MissingDashInDateData,
+                "date",
             )
         );
     }
     if !valid_amount_comma {
         return Err(
-            TcmbEvdsResult::generate_result(
+            TcmbEvdsResult::generate_parameter_result(
                 "Error: There is no comma in the given date data.".to_string(),
                 ReturnErrorC::MissingCommaInDateData,
+                "date",
             )
         );
     }
@@ -38,29 +42,33 @@ pub(crate) fn check_date_string_validity(
 }
 
 
+/// checks the length of the date data to find its format type. The spaces are not counted, so any number of spaces
+/// may surround the comma of a multiple date data, e.g. "13-12-2011,  13-12-2021".
 pub(crate) fn check_date_data_string_length(date_string: &str) -> Result<DateFormatType, TcmbEvdsResult> {
     
     let single_date_length = 10;
-    let multiple_date_max_length = 22;
-    let multiple_date_min_length = 21;
+    let multiple_date_length = 21;
 
-    let length = date_string.len();
+    let length = date_string.len() - date_string.chars().filter(|character| character.is_space()).count();
 
+    if is_shorthand(date_string) { return Ok(DateFormatType::Shorthand); }
 
-    if length > multiple_date_max_length { 
+    if length > multiple_date_length { 
         return Err(
-            TcmbEvdsResult::generate_result(
+            TcmbEvdsResult::generate_parameter_result(
                 "Error: Length of the given date data is more than expected.".to_string(),
                 ReturnErrorC::DateDataExceedingLengthLimit,
+                "date",
             )
         );
     };
-    if length > single_date_length && length < multiple_date_min_length {
+    if length > single_date_length && length < multiple_date_length {
         return Err(
-            TcmbEvdsResult::generate_result(
+            TcmbEvdsResult::generate_parameter_result(
                 "Error: Undefined date data format.
                 \nHelp: Please prefer one of the date data format types single and multiple.".to_string(),
                 ReturnErrorC::UndefinedDateDataFormat,
+                "date",
             )
         );
     }
@@ -72,6 +80,26 @@ pub(crate) fn check_date_data_string_length(date_string: &str) -> Result<DateFor
 
 
 
+/// checks the date data has one of the shorthand patterns, namely *"yyyy"*, *"yyyy-Qq"* and *"mm-yyyy"*, whose values
+/// are checked while they are expanded. The other date data, e.g. *"1-1-20"*, are checked as single and multiple dates.
+fn is_shorthand(date_string: &str) -> bool {
+
+    let is_digits = |text: &str, length: usize| text.len() == length && text.bytes().all(|byte| byte.is_ascii_digit());
+
+    let mut parts = date_string.split('-');
+
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(year), None, None) => is_digits(year, 4),
+        (Some(year), Some(quarter), None) if quarter.starts_with(&['Q', 'q'][..]) => {
+            is_digits(year, 4) && is_digits(&quarter[1..], 1)
+        },
+        (Some(month), Some(year), None) => is_digits(month, 2) && is_digits(year, 4),
+        _ => false,
+    }
+}
+
+
+
 impl CheckingStringCharacter for char {
     fn is_dash(&self) -> bool {
         if *self != '-' { return false; }
@@ -95,6 +123,7 @@ impl CheckingStringCharacter for char {
 pub(crate) enum DateFormatType {
     Single,
     Multiple,
+    Shorthand,
 }
 
 /// checks compatibility of the given date data formats which are single and multiple dates.
@@ -105,14 +134,23 @@ pub(crate) enum DateFormatType {
 /// >> "dd-mm-yyyy" -> "13-12-2011"
 ///
 /// > **Multiple:**
-/// >> One comma and/or an empty space. <br />
+/// >> One comma and/or empty spaces around it. <br />
 /// >> *"dd-mm-yyyy,dd-mm-yyyy"* -> *"13-12-2011,13-12-2021"* <br />
 /// >> *"dd-mm-yyyy, dd-mm-yyyy"* -> *"13-12-2011, 13-12-2021"*
+///
+/// > **Shorthand:**
+/// >> A year, a quarter of a year or a month of a year. <br />
+/// >> *"yyyy"* -> *"2023"* <br />
+/// >> *"yyyy-Qq"* -> *"2023-Q2"* <br />
+/// >> *"mm-yyyy"* -> *"05-2024"*
 pub(crate) fn check_date_format(date_string: &str) -> Result<DateFormatType, TcmbEvdsResult> {
 
     // checking part of the correctness of the both date format types.
     let date_format_type = check_date_data_string_length(date_string)?;
 
+    // shorthand dates are checked while they are expanded.
+    if let DateFormatType::Shorthand = date_format_type { return Ok(date_format_type); }
+
     let count_numeric = date_string.chars().filter(|character| character.is_numeric()).count();
     let count_dash = date_string.chars().filter(|character| character.is_dash()).count();
 
@@ -147,19 +185,223 @@ pub(crate) fn check_date_format(date_string: &str) -> Result<DateFormatType, Tcm
     Ok(date_format_type)
 }
 
-/// divides dates data into two separated date data.
-pub(crate) fn parse_dates(dates: &str) -> (&str, &str) {
+/// divides dates data into two separated date data. The whitespaces around each date are ignored, e.g.
+/// "13-12-2011,  13-12-2021".
+///
+/// # Error
+///
+/// This function returns `MissingCommaInDateData` error when there is no comma between the dates, 
+/// `UndefinedDateDataFormat` error for more than two dates and `MissingDateInDateData` error when one of the dates is
+/// empty, e.g. "13-12-2011,".
+pub(crate) fn parse_dates(dates: &str) -> Result<(&str, &str), TcmbEvdsResult> {
+
+    let mut split_dates = dates.split(',').map(str::trim);
+
+    let (first_date, second_date) = match (split_dates.next(), split_dates.next(), split_dates.next()) {
+        (Some(first_date), Some(second_date), None) => (first_date, second_date),
+        (_, None, _) => {
+            return Err(
+                TcmbEvdsResult::generate_parameter_result(
+                    "Error: There is no comma in the given date data.".to_string(),
+                    ReturnErrorC::MissingCommaInDateData,
+                    "date",
+                )
+            );
+        },
+        _ => {
+            return Err(
+                TcmbEvdsResult::generate_parameter_result(
+                    "Error: There are more than two dates in the given date data.".to_string(),
+                    ReturnErrorC::UndefinedDateDataFormat,
+                    "date",
+                )
+            );
+        },
+    };
+
+    if first_date.is_empty() || second_date.is_empty() {
+        let missing_date = if first_date.is_empty() { "start" } else { "end" };
+
+        return Err(
+            TcmbEvdsResult::generate_parameter_result(
+                format!("Error: The {} date is missing in the given date data.", missing_date),
+                ReturnErrorC::MissingDateInDateData,
+                "date",
+            )
+        );
+    }
+
+    Ok((first_date, second_date))
+}
+
+/// expands a shorthand date into the first and the last days of the period it represents.
+///
+/// # The shorthand formats
+///
+/// > *"2023"* -> *"01-01-2023"*, *"31-12-2023"* <br />
+/// > *"2023-Q2"* -> *"01-04-2023"*, *"30-06-2023"* <br />
+/// > *"05-2024"* -> *"01-05-2024"*, *"31-05-2024"*
+///
+/// # Error
+///
+/// This function returns a specific error for malformed year, quarter and month shorthands.
+pub(crate) fn expand_shorthand(shorthand: &str) -> Result<(String, String), TcmbEvdsResult> {
+
+    let is_digits = |text: &str| !text.is_empty() && text.chars().all(|character| character.is_ascii_digit());
+
+    let year_error = || generate_shorthand_error(
+        "Error: Invalid year shorthand.\nHelp: Please give a four digit year such as \"2023\".",
+        ReturnErrorC::InvalidYearShorthand,
+    );
+    let quarter_error = || generate_shorthand_error(
+        "Error: Invalid quarter shorthand.\nHelp: Please give a year and a quarter between 1 and 4 such as \"2023-Q2\".",
+        ReturnErrorC::InvalidQuarterShorthand,
+    );
+    let month_error = || generate_shorthand_error(
+        "Error: Invalid month shorthand.\nHelp: Please give a month between 1 and 12 and a year such as \"05-2024\".",
+        ReturnErrorC::InvalidMonthShorthand,
+    );
+
+    let parse_year = |year: &str| -> Option<i64> {
+        if year.len() != 4 || !is_digits(year) { return None; }
+
+        let year = year.parse::<i64>().ok()?;
+
+        if year < 1000 { return None; }
+
+        Some(year)
+    };
+
+    // Quarter shorthand: "yyyy-Qq".
+    if shorthand.contains(&['Q', 'q'][..]) {
+        let mut parts = shorthand.split('-');
+
+        let (year, quarter) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(year), Some(quarter), None) => (year, quarter),
+            _ => return Err(quarter_error()),
+        };
+
+        let year = match parse_year(year) {
+            Some(year) => year,
+            None => return Err(quarter_error()),
+        };
+
+        let quarter = match quarter.get(1..).filter(|number| is_digits(number)) {
+            Some(number) => number.parse::<u32>().unwrap_or(0),
+            None => return Err(quarter_error()),
+        };
+
+        if !(1..=4).contains(&quarter) { return Err(quarter_error()); }
+
+        let first_month = quarter * 3 - 2;
+        let last_month = quarter * 3;
+
+        return Ok((
+            format!("01-{:02}-{}", first_month, year),
+            format!("{:02}-{:02}-{}", days_in_month(last_month, year), last_month, year),
+        ));
+    }
+
+    // Month shorthand: "mm-yyyy".
+    if shorthand.contains('-') {
+        let mut parts = shorthand.split('-');
+
+        let (month, year) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(month), Some(year), None) => (month, year),
+            _ => return Err(month_error()),
+        };
+
+        if month.len() != 2 || !is_digits(month) { return Err(month_error()); }
+
+        let month = month.parse::<u32>().unwrap_or(0);
+
+        if !(1..=12).contains(&month) { return Err(month_error()); }
+
+        let year = match parse_year(year) {
+            Some(year) => year,
+            None => return Err(month_error()),
+        };
+
+        return Ok((
+            format!("01-{:02}-{}", month, year),
+            format!("{:02}-{:02}-{}", days_in_month(month, year), month, year),
+        ));
+    }
+
+    // Year shorthand: "yyyy".
+    let year = match parse_year(shorthand) {
+        Some(year) => year,
+        None => return Err(year_error()),
+    };
+
+    Ok((format!("01-01-{}", year), format!("31-12-{}", year)))
+}
+
+fn generate_shorthand_error(message: &str, error_type: ReturnErrorC) -> TcmbEvdsResult {
+    TcmbEvdsResult::generate_parameter_result(message.to_string(), error_type, "date")
+}
 
-    let date_format_length = 10;
 
-    let mut split_dates = dates.split(&[','][..]).into_iter();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let first_date = split_dates.next().unwrap();
+    #[test]
+    fn should_expand_shorthand() {
+        let expand = |shorthand: &str| expand_shorthand(shorthand).ok();
 
-    let mut second_date = split_dates.next().unwrap();
+        assert_eq!(Some(("01-01-2023".to_string(), "31-12-2023".to_string())), expand("2023"));
+        assert_eq!(Some(("01-04-2023".to_string(), "30-06-2023".to_string())), expand("2023-Q2"));
+        assert_eq!(Some(("01-10-2023".to_string(), "31-12-2023".to_string())), expand("2023-q4"));
+        assert_eq!(Some(("01-02-2024".to_string(), "29-02-2024".to_string())), expand("02-2024"));
 
-    // ignores if there is an empty space between two dates.
-    if second_date.len() > date_format_length { second_date = &second_date[1..]; }
+        let error_type = |shorthand: &str| expand_shorthand(shorthand).err().map(|result| result.error_type);
 
-    (first_date, second_date)
+        assert!(matches!(error_type("2023-Q5"), Some(ReturnErrorC::InvalidQuarterShorthand)));
+        assert!(matches!(error_type("13-2024"), Some(ReturnErrorC::InvalidMonthShorthand)));
+        assert!(matches!(error_type("0999"), Some(ReturnErrorC::InvalidYearShorthand)));
+        assert!(matches!(error_type("20x3"), Some(ReturnErrorC::InvalidYearShorthand)));
+    }
+
+    #[test]
+    fn should_check_shorthand_patterns() {
+        let format_type = |date_data: &str| collect_problem(|| check_date_format(date_data));
+
+        assert!(matches!(format_type("2023"), Ok(DateFormatType::Shorthand)));
+        assert!(matches!(format_type("2023-Q5"), Ok(DateFormatType::Shorthand)));
+        assert!(matches!(format_type("13-2024"), Ok(DateFormatType::Shorthand)));
+
+        assert!(matches!(format_type(""), Err((ReturnErrorC::MissingNumberInDateData, _))));
+        assert!(matches!(format_type("1-1-20"), Err((ReturnErrorC::MissingNumberInDateData, _))));
+        assert!(matches!(format_type("13-122011"), Err((ReturnErrorC::MissingDashInDateData, _))));
+    }
+
+    #[test]
+    fn should_parse_dates() {
+        fn parse(dates: &str) -> Result<(&str, &str), (ReturnErrorC, String)> {
+            collect_problem(|| parse_dates(dates))
+        }
+
+        assert_eq!(Some(("13-12-2011", "13-12-2021")), parse("13-12-2011,13-12-2021").ok());
+        assert_eq!(Some(("13-12-2011", "13-12-2021")), parse("13-12-2011, 13-12-2021").ok());
+        assert_eq!(Some(("13-12-2011", "13-12-2021")), parse("13-12-2011 ,  13-12-2021").ok());
+        assert_eq!(Some(("01-01-2000", "\u{660}1-01-2000")), parse("01-01-2000,\u{660}1-01-2000").ok());
+
+        let error_type = |dates: &str| parse(dates).err().map(|(error_type, _)| error_type);
+
+        assert!(matches!(error_type("13-12-2011"), Some(ReturnErrorC::MissingCommaInDateData)));
+        assert!(matches!(error_type("13-12-2011,"), Some(ReturnErrorC::MissingDateInDateData)));
+        assert!(matches!(error_type(" , 13-12-2021"), Some(ReturnErrorC::MissingDateInDateData)));
+        assert!(matches!(error_type("13-12-2011,13-12-2021,"), Some(ReturnErrorC::UndefinedDateDataFormat)));
+    }
+
+    #[test]
+    fn should_tolerate_spaces_around_comma() {
+        let format_type = |date_data: &str| collect_problem(|| check_date_format(date_data)).ok();
+
+        assert!(matches!(format_type("13-12-2011,13-12-2021"), Some(DateFormatType::Multiple)));
+        assert!(matches!(format_type("13-12-2011,  13-12-2021"), Some(DateFormatType::Multiple)));
+        assert!(matches!(format_type("13-12-2011  ,   13-12-2021"), Some(DateFormatType::Multiple)));
+        assert!(format_type("13-12-2011,x13-12-2021").is_none());
+    }
 }
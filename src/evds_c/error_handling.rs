@@ -1,9 +1,23 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+use libc::c_uint;
+
+#[cfg(feature = "cache")]
+use crate::dataset::cache;
+use crate::deadline;
 use crate::error::ReturnError;
-use super::common_entities::TcmbEvdsResult;
+use crate::spans;
+use crate::metrics::METRICS;
+use crate::traits::ReadingCEnum;
+use super::read_enum;
+use super::common_entities::{TcmbEvdsInput, TcmbEvdsResult, collect_problem};
+use super::memory_entities::MEMORY_COUNTERS;
+use super::settings::get_settings;
 
 /// There is a **'C'** letter at the end of the enum name. This comes from C language. The name means that 
 /// `ReturnError` for C.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 #[repr(C)]
 pub enum ReturnErrorC {
     NoError,
@@ -34,6 +48,51 @@ pub enum ReturnErrorC {
     DateDataExceedingLengthLimit,
     UndefinedDateDataFormat,
     ParameterError,
+    UnparsableResponse,
+    InvalidYearShorthand,
+    InvalidQuarterShorthand,
+    InvalidMonthShorthand,
+    InvalidEnumValue,
+    InternalError,
+    MemoryBudgetExceeded,
+    UnsafeQueryCharacter,
+    FailedToWriteFile,
+    IncompatibleUnits,
+    UnsupportedReturnFormat,
+    UnexpectedSchema,
+    RateLimitedByServer,
+    ServiceUnavailable,
+    DeadlineExceeded,
+    DnsFailure,
+    TlsError,
+    Timeout,
+    RecordingNotFound,
+    RequestVetoed,
+    InvalidExpression,
+    SeasonalAdjustmentFailed,
+    QueueFull,
+    DateRangeTooLarge,
+    HugeRequest,
+    UnexpectedContentType,
+    MissingDateInDateData,
+}
+
+/// specifies how the error message inside of `TcmbEvdsResult` is written when an error occurs.
+///
+/// `PlainTextErrors` keeps the standard error message. `JsonErrors` writes a small JSON object including the error type, 
+/// the related parameter if it is known and the standard error message, e.g. 
+/// `{"error":"InvalidDate","param":"date","message":"Error: Invalid date."}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsErrorFormat {
+    PlainTextErrors,
+    JsonErrors,
+}
+
+impl ReadingCEnum for TcmbEvdsErrorFormat {
+    const OPTIONS: &'static [Self] = &[TcmbEvdsErrorFormat::PlainTextErrors, TcmbEvdsErrorFormat::JsonErrors];
+
+    const FALLBACK: Self = TcmbEvdsErrorFormat::PlainTextErrors;
 }
 
 /// converts `error::ReturnError` into `error_handling::ReturnErrorC` with error message.
@@ -67,11 +126,11 @@ fn convert_return_error(return_error: ReturnError) -> (ReturnErrorC, String) {
 
             error_message = ReturnError::InvalidUrl.to_string();
         },
-        ReturnError::InvalidSeries => {
+        ReturnError::InvalidSeries(constraint) => {
 
             error = ReturnErrorC::InvalidSeries;
 
-            error_message = ReturnError::InvalidSeries.to_string();
+            error_message = ReturnError::InvalidSeries(constraint).to_string();
         },
         ReturnError::EmptyParameter => {
 
@@ -139,11 +198,11 @@ fn convert_return_error(return_error: ReturnError) -> (ReturnErrorC, String) {
 
             error_message = ReturnError::UnableToSetUrl.to_string();
         },
-        ReturnError::FailedToApplyRequest => {
+        ReturnError::FailedToApplyRequest(detail) => {
 
             error = ReturnErrorC::FailedToApplyRequest;
 
-            error_message = ReturnError::FailedToApplyRequest.to_string();
+            error_message = ReturnError::FailedToApplyRequest(detail).to_string();
         },
         ReturnError::FailedToSaveReceivedData => {
 
@@ -169,6 +228,132 @@ fn convert_return_error(return_error: ReturnError) -> (ReturnErrorC, String) {
 
             error_message = ReturnError::ForbiddenRequest.to_string();
         },
+        ReturnError::UnparsableResponse => {
+
+            error = ReturnErrorC::UnparsableResponse;
+
+            error_message = ReturnError::UnparsableResponse.to_string();
+        },
+        ReturnError::InvalidEnumValue => {
+
+            error = ReturnErrorC::InvalidEnumValue;
+
+            error_message = ReturnError::InvalidEnumValue.to_string();
+        },
+        ReturnError::UnsafeQueryCharacter => {
+
+            error = ReturnErrorC::UnsafeQueryCharacter;
+
+            error_message = ReturnError::UnsafeQueryCharacter.to_string();
+        },
+        ReturnError::FailedToWriteFile => {
+
+            error = ReturnErrorC::FailedToWriteFile;
+
+            error_message = ReturnError::FailedToWriteFile.to_string();
+        },
+        ReturnError::IncompatibleUnits => {
+
+            error = ReturnErrorC::IncompatibleUnits;
+
+            error_message = ReturnError::IncompatibleUnits.to_string();
+        },
+        ReturnError::UnsupportedReturnFormat => {
+
+            error = ReturnErrorC::UnsupportedReturnFormat;
+
+            error_message = ReturnError::UnsupportedReturnFormat.to_string();
+        },
+        ReturnError::UnexpectedSchema(discrepancy) => {
+
+            error = ReturnErrorC::UnexpectedSchema;
+
+            error_message = ReturnError::UnexpectedSchema(discrepancy).to_string();
+        },
+        ReturnError::RateLimitedByServer(seconds) => {
+
+            error = ReturnErrorC::RateLimitedByServer;
+
+            error_message = ReturnError::RateLimitedByServer(seconds).to_string();
+        },
+        ReturnError::ServiceUnavailable => {
+
+            error = ReturnErrorC::ServiceUnavailable;
+
+            error_message = ReturnError::ServiceUnavailable.to_string();
+        },
+        ReturnError::DeadlineExceeded(milliseconds) => {
+
+            error = ReturnErrorC::DeadlineExceeded;
+
+            error_message = ReturnError::DeadlineExceeded(milliseconds).to_string();
+        },
+        ReturnError::DnsFailure(detail) => {
+
+            error = ReturnErrorC::DnsFailure;
+
+            error_message = ReturnError::DnsFailure(detail).to_string();
+        },
+        ReturnError::TlsError(detail) => {
+
+            error = ReturnErrorC::TlsError;
+
+            error_message = ReturnError::TlsError(detail).to_string();
+        },
+        ReturnError::Timeout(detail) => {
+
+            error = ReturnErrorC::Timeout;
+
+            error_message = ReturnError::Timeout(detail).to_string();
+        },
+        ReturnError::RecordingNotFound => {
+
+            error = ReturnErrorC::RecordingNotFound;
+
+            error_message = ReturnError::RecordingNotFound.to_string();
+        },
+        ReturnError::RequestVetoed(message) => {
+
+            error = ReturnErrorC::RequestVetoed;
+
+            error_message = ReturnError::RequestVetoed(message).to_string();
+        },
+        ReturnError::InvalidExpression(problem) => {
+
+            error = ReturnErrorC::InvalidExpression;
+
+            error_message = ReturnError::InvalidExpression(problem).to_string();
+        },
+        ReturnError::SeasonalAdjustmentFailed(reason) => {
+
+            error = ReturnErrorC::SeasonalAdjustmentFailed;
+
+            error_message = ReturnError::SeasonalAdjustmentFailed(reason).to_string();
+        },
+        ReturnError::QueueFull => {
+
+            error = ReturnErrorC::QueueFull;
+
+            error_message = ReturnError::QueueFull.to_string();
+        },
+        ReturnError::DateRangeTooLarge(max_span_days) => {
+
+            error = ReturnErrorC::DateRangeTooLarge;
+
+            error_message = ReturnError::DateRangeTooLarge(max_span_days).to_string();
+        },
+        ReturnError::HugeRequest(observation_count, data_frequency) => {
+
+            error = ReturnErrorC::HugeRequest;
+
+            error_message = ReturnError::HugeRequest(observation_count, data_frequency).to_string();
+        },
+        ReturnError::UnexpectedContentType(mismatch) => {
+
+            error = ReturnErrorC::UnexpectedContentType;
+
+            error_message = ReturnError::UnexpectedContentType(mismatch).to_string();
+        },
     }
 
     (error, error_message)
@@ -176,7 +361,288 @@ fn convert_return_error(return_error: ReturnError) -> (ReturnErrorC, String) {
 
 pub(crate) fn handle_return_error(return_error: ReturnError) -> TcmbEvdsResult {
 
+    let detail = return_error.get_detail().map(str::to_string);
+
+    let (error_type, error_message) = convert_return_error(return_error);
+
+    TcmbEvdsResult::generate_result(error_message, error_type).with_detail(detail)
+}
+
+/// does the same thing with [`handle_return_error`] and additionally marks the parameter causing the error.
+pub(crate) fn handle_parameter_return_error(return_error: ReturnError, parameter_name: &str) -> TcmbEvdsResult {
+
+    let detail = return_error.get_detail().map(str::to_string);
+
     let (error_type, error_message) = convert_return_error(return_error);
 
-    TcmbEvdsResult::generate_result(error_message, error_type)
+    TcmbEvdsResult::generate_parameter_result(error_message, error_type, parameter_name).with_detail(detail)
+}
+
+/// runs the body of an FFI function returning `TcmbEvdsResult` and converts a panic into an `InternalError` result.
+///
+/// An unwinding panic crossing the FFI boundary is undefined behavior, so every FFI function is shielded. The body is 
+/// not run and `MemoryBudgetExceeded` error is returned when the memory budget is exceeded.
+pub(crate) fn shield_result<F>(function_name: &str, body: F) -> TcmbEvdsResult
+where
+    F: FnOnce() -> TcmbEvdsResult
+{
+
+    let memory_budget_bytes = get_settings().memory_budget_bytes;
+
+    if MEMORY_COUNTERS.is_budget_exceeded(memory_budget_bytes) {
+        return TcmbEvdsResult::generate_result(
+            "Error: The memory budget is exceeded by the live results, handles and datasets.
+            \nHelp: Please free the results, handles and datasets that are not used anymore.".to_string(),
+            ReturnErrorC::MemoryBudgetExceeded,
+        );
+    }
+
+    let _span = spans::enter_function(function_name);
+    let _call = deadline::start_call();
+
+    // a stale mark left by a former call of the thread must not flag the response of this call.
+    #[cfg(feature = "cache")]
+    cache::take_stale_error();
+
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let error_message = format!(
+                "Error: Internal error in {}: {}.\nHelp: Please report the issue with the given arguments.",
+                function_name,
+                extract_panic_message(payload.as_ref()),
+            );
+
+            TcmbEvdsResult::generate_result(error_message, ReturnErrorC::InternalError)
+        },
+    }
+}
+
+/// runs the body of an FFI function not returning `TcmbEvdsResult` and returns the fallback value on a panic.
+pub(crate) fn shield_or<T, F>(fallback: T, body: F) -> T
+where
+    F: FnOnce() -> T
+{
+
+    let _call = deadline::start_call();
+
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(value) => value,
+        Err(_) => fallback,
+    }
+}
+
+/// collects the parameter errors of a call, so that all of them are returned together rather than only the first one.
+///
+/// A single error is returned exactly as before. Several errors are returned as a `ParameterError` whose message lists 
+/// them one per line as `- parameter: message`, or whose JSON object lists them in its `errors` array when the error 
+/// format is [`TcmbEvdsErrorFormat::JsonErrors`].
+#[derive(Default)]
+pub(crate) struct ParameterErrors {
+    errors: Vec<(ReturnErrorC, String, String)>,
+}
+
+impl ParameterErrors {
+    pub(crate) fn new() -> ParameterErrors {
+        ParameterErrors::default()
+    }
+
+    pub(crate) fn add(&mut self, error_type: ReturnErrorC, parameter_name: &str, error_message: String) {
+        self.errors.push((error_type, parameter_name.to_string(), error_message));
+    }
+
+    /// reads the input and records its error. None is returned for an invalid input.
+    pub(crate) fn read_input(&mut self, input: &TcmbEvdsInput, parameter_name: &str) -> Option<String> {
+
+        let (text, error_state) = input.get_input(parameter_name);
+
+        if !error_state { return Some(text); }
+
+        self.add(ReturnErrorC::ParameterError, parameter_name, text);
+
+        None
+    }
+
+    /// records the error of the checked value of the parameter. None is returned for an error.
+    pub(crate) fn check<T>(&mut self, parameter_name: &str, checked: Result<T, ReturnError>) -> Option<T> {
+        match checked {
+            Ok(value) => Some(value),
+            Err(return_error) => {
+                let (error_type, error_message) = convert_return_error(return_error);
+
+                self.add(error_type, parameter_name, error_message);
+
+                None
+            },
+        }
+    }
+
+    /// reads a C enum received as an integer and records its error. The default option of the enum is returned for an 
+    /// error.
+    pub(crate) fn read_enum<E: ReadingCEnum>(&mut self, parameter_name: &str, value: c_uint) -> E {
+        self.check(parameter_name, read_enum(value)).unwrap_or(E::FALLBACK)
+    }
+
+    /// runs the check returning a result for its error and records the error. None is returned for an error.
+    pub(crate) fn collect<T, F>(&mut self, parameter_name: &str, check: F) -> Option<T>
+    where
+        F: FnOnce() -> Result<T, TcmbEvdsResult>
+    {
+        match collect_problem(check) {
+            Ok(value) => Some(value),
+            Err((error_type, error_message)) => {
+                self.add(error_type, parameter_name, error_message);
+
+                None
+            },
+        }
+    }
+
+    /// gives the result of the collected errors. Nothing is returned when there is no error.
+    pub(crate) fn into_result(self) -> Result<(), TcmbEvdsResult> {
+
+        let mut errors = self.errors;
+
+        if errors.len() <= 1 {
+            return match errors.pop() {
+                Some((error_type, parameter_name, error_message)) => {
+                    Err(TcmbEvdsResult::generate_parameter_result(error_message, error_type, &parameter_name))
+                },
+                None => Ok(()),
+            };
+        }
+
+        let error_type = ReturnErrorC::ParameterError;
+
+        METRICS.record_error(&format!("{:?}", error_type));
+
+        let summary = format!("Error: {} parameter errors are found.", errors.len());
+
+        let error_message = match get_settings().error_format {
+            TcmbEvdsErrorFormat::JsonErrors => {
+                let json_errors: Vec<String> = errors.iter()
+                    .map(|(error_type, parameter_name, message)| {
+                        format_error_as_json(error_type, Some(parameter_name), message)
+                    })
+                    .collect();
+
+                let json_summary = format_error_as_json(&error_type, None, &summary);
+
+                format!("{},\"errors\":[{}]}}", &json_summary[..json_summary.len() - 1], json_errors.join(","))
+            },
+            TcmbEvdsErrorFormat::PlainTextErrors => {
+                let lines: Vec<String> = errors.iter()
+                    .map(|(_, parameter_name, message)| format!("- {}: {}", parameter_name, message))
+                    .collect();
+
+                format!("{}\n{}", summary, lines.join("\n"))
+            },
+        };
+
+        Err(TcmbEvdsResult::generate_raw_result(error_message, error_type))
+    }
+}
+
+/// gives the message of a panic, which is a `&str` or a `String` for the panics of the standard library.
+fn extract_panic_message(payload: &(dyn Any + Send)) -> &str {
+
+    if let Some(message) = payload.downcast_ref::<&str>() { return message; }
+    if let Some(message) = payload.downcast_ref::<String>() { return message; }
+
+    "unknown panic"
+}
+
+/// escapes the given text to be placed between the quotes of a JSON string.
+pub(crate) fn escape_json_string(text: &str) -> String {
+
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if (character as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", character as u32)),
+            character => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+/// writes the error as a JSON object for [`TcmbEvdsErrorFormat::JsonErrors`].
+///
+/// The `param` field becomes `null` when the parameter causing the error is unknown.
+pub(crate) fn format_error_as_json(error_type: &ReturnErrorC, parameter_name: Option<&str>, message: &str) -> String {
+
+    let parameter = match parameter_name {
+        Some(name) => format!("\"{}\"", escape_json_string(name)),
+        None => "null".to_string(),
+    };
+
+    format!(
+        "{{\"error\":\"{:?}\",\"param\":{},\"message\":\"{}\"}}",
+        error_type,
+        parameter,
+        escape_json_string(message),
+    )
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_format_error_as_json() {
+        let json = format_error_as_json(&ReturnErrorC::InvalidDate, Some("date"), "Error: Invalid date.");
+
+        assert_eq!(r#"{"error":"InvalidDate","param":"date","message":"Error: Invalid date."}"#, json);
+
+        let json = format_error_as_json(&ReturnErrorC::ResponseError, None, "Error: \"quoted\"\n\tline.");
+
+        assert_eq!(r#"{"error":"ResponseError","param":null,"message":"Error: \"quoted\"\n\tline."}"#, json);
+    }
+
+    #[test]
+    fn should_shield_panics() {
+        let result = shield_result("tcmb_evds_c_test", || panic!("unexpected state"));
+
+        assert!(matches!(result.error_type, ReturnErrorC::InternalError));
+
+        let message = unsafe {
+            std::slice::from_raw_parts(result.output_ptr as *const u8, result.string_capacity as usize)
+        };
+        let message = std::str::from_utf8(message).unwrap();
+
+        assert!(message.contains("tcmb_evds_c_test") && message.contains("unexpected state"));
+
+        assert_eq!(3, shield_or(3, || -> i32 { panic!("unexpected state") }));
+        assert_eq!(5, shield_or(3, || 5));
+    }
+
+    #[test]
+    fn should_collect_parameter_errors() {
+        let mut parameter_errors = ParameterErrors::new();
+
+        assert!(parameter_errors.check("date", Ok::<_, ReturnError>(3)).is_some());
+
+        assert!(ParameterErrors::new().into_result().is_ok());
+
+        parameter_errors.check::<()>("data_series", Err(ReturnError::EmptyParameter));
+        parameter_errors.add(ReturnErrorC::InvalidDate, "date", "Error: Invalid date.".to_string());
+
+        let (error_type, message) = collect_problem(|| parameter_errors.into_result()).unwrap_err();
+
+        assert!(matches!(error_type, ReturnErrorC::ParameterError));
+        assert_eq!(
+            format!(
+                "Error: 2 parameter errors are found.\n- data_series: {}\n- date: Error: Invalid date.",
+                ReturnError::EmptyParameter
+            ),
+            message
+        );
+    }
 }
@@ -0,0 +1,397 @@
+use std::convert::TryFrom;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use std::slice;
+
+use libc::{c_uint, c_ulong};
+
+use super::common_entities::{MAX_INPUT_LENGTH, TcmbEvdsInput, TcmbEvdsResult};
+use super::error_handling::{ReturnErrorC, shield_or, shield_result};
+use super::handle_entities::{
+    TcmbEvdsHandle,
+    tcmb_evds_c_handle_data_ptr,
+    tcmb_evds_c_handle_detail,
+    tcmb_evds_c_handle_error,
+    tcmb_evds_c_handle_free,
+    tcmb_evds_c_handle_len,
+};
+
+
+/// is a copy of a UTF-8 text given as a pointer and a length. The copy is followed by a NUL character, so that the
+/// text is read as an input without reading past the buffer of the caller, which is not NUL terminated, e.g. a .NET
+/// `byte[]`.
+struct FlatInput {
+    text: Option<Vec<u8>>,
+    string_capacity: c_ulong,
+}
+
+impl FlatInput {
+    /// copies the text. A NULL pointer is kept as a NULL input whose error is reported by the called function.
+    ///
+    /// # Error
+    ///
+    /// This function returns an error message without reading the text when the length is more than
+    /// [`MAX_INPUT_LENGTH`] bytes or cannot be given as the capacity of an input.
+    unsafe fn from(text_ptr: *const c_char, length: usize, parameter_name: &str) -> Result<FlatInput, String> {
+
+        if text_ptr.is_null() { return Ok(FlatInput { text: None, string_capacity: 0 }); }
+
+        let string_capacity = match c_ulong::try_from(length) {
+            Ok(string_capacity) if length <= MAX_INPUT_LENGTH => string_capacity,
+            _ => {
+                return Err(
+                    format!(
+                        "Error: The length of the given {} parameter is more than {} bytes.",
+                        parameter_name,
+                        MAX_INPUT_LENGTH,
+                    )
+                );
+            },
+        };
+
+        let mut text = slice::from_raw_parts(text_ptr as *const u8, length).to_vec();
+        text.push(0);
+
+        Ok(FlatInput { text: Some(text), string_capacity })
+    }
+
+    /// gives the input borrowing the copy, which must outlive the call.
+    fn as_input(&self) -> TcmbEvdsInput {
+        TcmbEvdsInput {
+            input_ptr: self.text.as_ref().map_or(ptr::null(), |text| text.as_ptr() as *const c_char),
+            string_capacity: self.string_capacity,
+        }
+    }
+}
+
+
+/// copies the flat inputs and calls the function with them, which are valid during the call.
+///
+/// # Error
+///
+/// This function returns `ParameterError` for the first input that could not be copied.
+unsafe fn with_flat_inputs<F, const N: usize>(inputs: [(*const c_char, usize, &str); N], call: F) -> TcmbEvdsResult
+where
+    F: FnOnce([TcmbEvdsInput; N]) -> TcmbEvdsResult
+{
+
+    let mut flat_inputs = Vec::with_capacity(N);
+
+    for (text_ptr, length, parameter_name) in inputs.iter() {
+        match FlatInput::from(*text_ptr, *length, parameter_name) {
+            Ok(flat_input) => flat_inputs.push(flat_input),
+            Err(error_message) => {
+                return TcmbEvdsResult::generate_parameter_result(
+                    error_message,
+                    ReturnErrorC::ParameterError,
+                    parameter_name,
+                );
+            },
+        }
+    }
+
+    let mut c_inputs = [(); N].map(|_| TcmbEvdsInput { input_ptr: ptr::null(), string_capacity: 0 });
+
+    for (c_input, flat_input) in c_inputs.iter_mut().zip(flat_inputs.iter()) {
+        *c_input = flat_input.as_input();
+    }
+
+    call(c_inputs)
+}
+
+
+/// does the same thing with [`tcmb_evds_c_get_data`](crate::tcmb_evds_c_get_data) and returns the result as a
+/// [`TcmbEvdsHandle`].
+///
+/// # Safety
+///
+/// Each text pointer must be NULL or point to at least the given number of bytes. A nonzero `ascii_mode` is true.
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_get_data(
+    data_series_ptr: *const c_char,
+    data_series_len: usize,
+    date_ptr: *const c_char,
+    date_len: usize,
+    api_key_ptr: *const c_char,
+    api_key_len: usize,
+    return_format: c_uint,
+    ascii_mode: c_int
+) -> *mut TcmbEvdsHandle {
+
+    let result = shield_result("tcmb_evds_c_flat_get_data", || {
+        let inputs = [
+            (data_series_ptr, data_series_len, "data_series"),
+            (date_ptr, date_len, "date"),
+            (api_key_ptr, api_key_len, "api_key"),
+        ];
+
+        with_flat_inputs(inputs, |[data_series, date, api_key]| {
+            crate::tcmb_evds_c_get_data(data_series, date, api_key, return_format, ascii_mode != 0)
+        })
+    });
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_advanced_data`](crate::tcmb_evds_c_get_advanced_data) and returns the
+/// result as a [`TcmbEvdsHandle`].
+///
+/// # Safety
+///
+/// Each text pointer must be NULL or point to at least the given number of bytes. A nonzero `ascii_mode` is true.
+#[cfg(feature = "currency")]
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "system" fn tcmb_evds_c_flat_get_advanced_data(
+    currency_series_ptr: *const c_char,
+    currency_series_len: usize,
+    date_ptr: *const c_char,
+    date_len: usize,
+    aggregation_type: c_uint,
+    formula: c_uint,
+    data_frequency: c_uint,
+    api_key_ptr: *const c_char,
+    api_key_len: usize,
+    return_format: c_uint,
+    ascii_mode: c_int
+) -> *mut TcmbEvdsHandle {
+
+    let result = shield_result("tcmb_evds_c_flat_get_advanced_data", || {
+        let inputs = [
+            (currency_series_ptr, currency_series_len, "currency_series"),
+            (date_ptr, date_len, "date"),
+            (api_key_ptr, api_key_len, "api_key"),
+        ];
+
+        with_flat_inputs(inputs, |[currency_series, date, api_key]| {
+            crate::tcmb_evds_c_get_advanced_data(
+                currency_series,
+                date,
+                aggregation_type,
+                formula,
+                data_frequency,
+                api_key,
+                return_format,
+                ascii_mode != 0
+            )
+        })
+    });
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_data_group`](crate::tcmb_evds_c_get_data_group) and returns the result
+/// as a [`TcmbEvdsHandle`].
+///
+/// # Safety
+///
+/// Each text pointer must be NULL or point to at least the given number of bytes. A nonzero `ascii_mode` is true.
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_get_data_group(
+    data_group_ptr: *const c_char,
+    data_group_len: usize,
+    date_ptr: *const c_char,
+    date_len: usize,
+    api_key_ptr: *const c_char,
+    api_key_len: usize,
+    return_format: c_uint,
+    ascii_mode: c_int
+) -> *mut TcmbEvdsHandle {
+
+    let result = shield_result("tcmb_evds_c_flat_get_data_group", || {
+        let inputs = [
+            (data_group_ptr, data_group_len, "data_group"),
+            (date_ptr, date_len, "date"),
+            (api_key_ptr, api_key_len, "api_key"),
+        ];
+
+        with_flat_inputs(inputs, |[data_group, date, api_key]| {
+            crate::tcmb_evds_c_get_data_group(data_group, date, api_key, return_format, ascii_mode != 0)
+        })
+    });
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_categories`](crate::tcmb_evds_c_get_categories) and returns the result
+/// as a [`TcmbEvdsHandle`].
+///
+/// # Safety
+///
+/// The api key pointer must be NULL or point to at least the given number of bytes. A nonzero `ascii_mode` is true.
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_get_categories(
+    api_key_ptr: *const c_char,
+    api_key_len: usize,
+    return_format: c_uint,
+    ascii_mode: c_int
+) -> *mut TcmbEvdsHandle {
+
+    let result = shield_result("tcmb_evds_c_flat_get_categories", || {
+        let inputs = [(api_key_ptr, api_key_len, "api_key")];
+
+        with_flat_inputs(inputs, |[api_key]| {
+            crate::tcmb_evds_c_get_categories(api_key, return_format, ascii_mode != 0)
+        })
+    });
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_series_list`](crate::tcmb_evds_c_get_series_list) and returns the
+/// result as a [`TcmbEvdsHandle`].
+///
+/// # Safety
+///
+/// Each text pointer must be NULL or point to at least the given number of bytes. A nonzero `ascii_mode` is true.
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_get_series_list(
+    code_ptr: *const c_char,
+    code_len: usize,
+    api_key_ptr: *const c_char,
+    api_key_len: usize,
+    return_format: c_uint,
+    ascii_mode: c_int
+) -> *mut TcmbEvdsHandle {
+
+    let result = shield_result("tcmb_evds_c_flat_get_series_list", || {
+        let inputs = [(code_ptr, code_len, "code"), (api_key_ptr, api_key_len, "api_key")];
+
+        with_flat_inputs(inputs, |[code, api_key]| {
+            crate::tcmb_evds_c_get_series_list(code, api_key, return_format, ascii_mode != 0)
+        })
+    });
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// gives the response text or the error message of the handle as UTF-8 bytes whose length is given by
+/// [`tcmb_evds_c_flat_handle_len`]. The bytes belong to the handle and NULL is returned for a NULL handle.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_handle_data(handle: *const TcmbEvdsHandle) -> *const u8 {
+    tcmb_evds_c_handle_data_ptr(handle) as *const u8
+}
+
+/// gives the length of the response text or the error message of the handle in bytes.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_handle_len(handle: *const TcmbEvdsHandle) -> usize {
+    tcmb_evds_c_handle_len(handle) as usize
+}
+
+/// gives the error type of the handle. `NoError` means the handle contains the response text.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_handle_error(handle: *const TcmbEvdsHandle) -> ReturnErrorC {
+    tcmb_evds_c_handle_error(handle)
+}
+
+/// gives the detail of the error of the handle as UTF-8 bytes whose length is given by
+/// [`tcmb_evds_c_flat_handle_detail_len`]. NULL is returned when the handle has no detail.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_handle_detail(handle: *const TcmbEvdsHandle) -> *const u8 {
+    tcmb_evds_c_handle_detail(handle) as *const u8
+}
+
+/// gives the length of the detail of the error of the handle in bytes. Zero is returned when there is no detail.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_handle_detail_len(handle: *const TcmbEvdsHandle) -> usize {
+    read_length(tcmb_evds_c_handle_detail(handle))
+}
+
+/// frees the handle. NULL handles are ignored.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and must not be used after this call.
+#[no_mangle]
+pub unsafe extern "system" fn tcmb_evds_c_flat_handle_free(handle: *mut TcmbEvdsHandle) {
+    tcmb_evds_c_handle_free(handle)
+}
+
+/// gives the length of a NUL terminated detail of a handle.
+unsafe fn read_length(text: *const c_char) -> usize {
+    shield_or(0, || if text.is_null() { 0 } else { libc::strlen(text) })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::evds_c::common_entities::TcmbEvdsReturnFormat::Json;
+
+    #[test]
+    fn should_read_flat_inputs_and_handles() {
+        unsafe {
+            let series = b"TP.DK.USD.S";
+            let api_key = b"users_key";
+
+            let handle = tcmb_evds_c_flat_get_data(
+                series.as_ptr() as *const c_char,
+                series.len(),
+                ptr::null(),
+                0,
+                api_key.as_ptr() as *const c_char,
+                api_key.len(),
+                Json as c_uint,
+                0
+            );
+
+            assert!(matches!(tcmb_evds_c_flat_handle_error(handle), ReturnErrorC::ParameterError));
+
+            let message_len = tcmb_evds_c_flat_handle_len(handle);
+            let message = slice::from_raw_parts(tcmb_evds_c_flat_handle_data(handle), message_len);
+
+            assert!(std::str::from_utf8(message).unwrap().contains("date"));
+            assert!(tcmb_evds_c_flat_handle_detail(handle).is_null());
+            assert_eq!(0, tcmb_evds_c_flat_handle_detail_len(handle));
+
+            tcmb_evds_c_flat_handle_free(handle);
+
+            // the length is rejected before the text is read.
+            let handle = tcmb_evds_c_flat_get_data(
+                series.as_ptr() as *const c_char,
+                series.len(),
+                series.as_ptr() as *const c_char,
+                usize::MAX,
+                api_key.as_ptr() as *const c_char,
+                api_key.len(),
+                Json as c_uint,
+                0
+            );
+
+            let message_len = tcmb_evds_c_flat_handle_len(handle);
+            let message = slice::from_raw_parts(tcmb_evds_c_flat_handle_data(handle), message_len);
+
+            assert!(matches!(tcmb_evds_c_flat_handle_error(handle), ReturnErrorC::ParameterError));
+            assert!(std::str::from_utf8(message).unwrap().contains("date parameter is more than"));
+
+            tcmb_evds_c_flat_handle_free(handle);
+
+            assert_eq!(0, tcmb_evds_c_flat_handle_len(ptr::null()));
+            assert!(matches!(tcmb_evds_c_flat_handle_error(ptr::null()), ReturnErrorC::InternalError));
+        }
+    }
+}
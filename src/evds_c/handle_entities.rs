@@ -0,0 +1,393 @@
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+use std::sync::OnceLock;
+
+use libc::c_ulong;
+
+use super::common_entities::{TcmbEvdsResult, to_c_string};
+use super::error_handling::{ReturnErrorC, shield_or};
+use super::memory_entities::{AllocationKind, MEMORY_COUNTERS};
+
+use crate::dataset::stats::{ResponseStats, compute_stats};
+use crate::debug_ffi;
+
+
+/// is an opaque handle of the result of an operation returned by the `*_handle` versions of the operational functions,
+/// e.g. [`tcmb_evds_c_get_data_handle`](crate::tcmb_evds_c_get_data_handle).
+///
+/// Unlike [`TcmbEvdsResult`], the layout of the handle is not a part of the C ABI. The content is read via the
+/// `tcmb_evds_c_handle_*` functions, so new fields can be added without breaking existing binaries. The handle must be
+/// released via [`tcmb_evds_c_handle_free`](crate::evds_c::handle_entities::tcmb_evds_c_handle_free).
+pub struct TcmbEvdsHandle {
+    /// is the response text or the error message followed by a NUL character.
+    data: Box<[u8]>,
+    error_type: ReturnErrorC,
+    /// is the detail of a failed transfer, e.g. the curl error, or None otherwise.
+    detail: Option<CString>,
+    /// is computed on the first access to the statistics. It is None when the handle does not contain a data response.
+    stats: OnceLock<Option<HandleStats>>,
+}
+
+/// keeps the statistics of the response of a handle with the C strings of its dates.
+struct HandleStats {
+    stats: ResponseStats,
+    first_date: Option<CString>,
+    last_date: Option<CString>,
+}
+
+impl TcmbEvdsHandle {
+    /// takes the ownership of the text of the result to move it into the handle.
+    pub(crate) fn from(result: TcmbEvdsResult) -> TcmbEvdsHandle {
+
+        let (error_type, text, detail) = result.into_parts();
+
+        let mut data = text.into_bytes();
+        data.push(0);
+
+        let handle = TcmbEvdsHandle {
+            data: data.into_boxed_slice(),
+            error_type,
+            detail: detail.as_deref().map(to_c_string),
+            stats: OnceLock::new(),
+        };
+
+        MEMORY_COUNTERS.record_allocation(AllocationKind::Handle, handle.count_bytes());
+
+        handle
+    }
+
+    /// counts the bytes of the text and the detail including their NUL characters.
+    fn count_bytes(&self) -> usize {
+        self.data.len() + self.detail.as_ref().map_or(0, |detail| detail.as_bytes_with_nul().len())
+    }
+
+    /// gives the statistics of the response by parsing it on the first call.
+    fn get_stats(&self) -> Option<&HandleStats> {
+        self.stats
+            .get_or_init(|| {
+                if !matches!(self.error_type, ReturnErrorC::NoError) { return None; }
+
+                let text = std::str::from_utf8(&self.data[..self.data.len() - 1]).ok()?;
+                let stats = compute_stats(text)?;
+
+                Some(HandleStats {
+                    first_date: stats.first_date.as_deref().map(to_c_string),
+                    last_date: stats.last_date.as_deref().map(to_c_string),
+                    stats,
+                })
+            })
+            .as_ref()
+    }
+
+    /// moves the handle to the heap and gives its ownership to C.
+    pub(crate) fn into_raw(self) -> *mut TcmbEvdsHandle {
+        let handle = Box::into_raw(Box::new(self));
+
+        debug_ffi::register(AllocationKind::Handle, handle);
+
+        handle
+    }
+}
+
+/// reads a handle received from C. The process is aborted with a diagnostic when the handle is already freed and the
+/// crate is built with the `debug_ffi` feature.
+unsafe fn read_handle<'a>(handle: *const TcmbEvdsHandle, function_name: &str) -> Option<&'a TcmbEvdsHandle> {
+
+    debug_ffi::check_live(AllocationKind::Handle, handle, function_name);
+
+    handle.as_ref()
+}
+
+
+/// gives the response text or the error message of the handle as a NUL terminated string.
+///
+/// The returned string belongs to the handle and remains valid until the handle is freed. NULL is returned for a NULL
+/// handle.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_data_ptr(handle: *const TcmbEvdsHandle) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        match read_handle(handle, "tcmb_evds_c_handle_data_ptr") {
+            Some(handle) => handle.data.as_ptr() as *const c_char,
+            None => ptr::null(),
+        }
+    })
+}
+
+/// gives the length of the response text or the error message of the handle in bytes excluding the NUL character.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_len(handle: *const TcmbEvdsHandle) -> c_ulong {
+
+    shield_or(0, || {
+        match read_handle(handle, "tcmb_evds_c_handle_len") {
+            Some(handle) => (handle.data.len() - 1) as c_ulong,
+            None => 0,
+        }
+    })
+}
+
+/// gives the error type of the handle. `NoError` means the handle contains the response text.
+///
+/// `InternalError` is returned for a NULL handle.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_error(handle: *const TcmbEvdsHandle) -> ReturnErrorC {
+
+    shield_or(ReturnErrorC::InternalError, || {
+        match read_handle(handle, "tcmb_evds_c_handle_error") {
+            Some(handle) => handle.error_type,
+            None => ReturnErrorC::InternalError,
+        }
+    })
+}
+
+/// gives the detail of the error of the handle as a NUL terminated string, e.g. the curl error of a failed transfer.
+///
+/// The returned string belongs to the handle and remains valid until the handle is freed. NULL is returned when the
+/// handle is NULL or has no detail.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_detail(handle: *const TcmbEvdsHandle) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        let detail = read_handle(handle, "tcmb_evds_c_handle_detail").and_then(|handle| handle.detail.as_ref());
+
+        match detail {
+            Some(detail) => detail.as_ptr(),
+            None => ptr::null(),
+        }
+    })
+}
+
+/// gives the `totalCount` field of the JSON or XML response of the handle through the `total_count` pointer.
+///
+/// The response is parsed once on the first call to any of the statistics functions and the statistics are kept in 
+/// the handle, so the rest of the calls are cheap. 
+///
+/// False is returned and nothing is written when the handle is NULL or an error, the response is in CSV, which does 
+/// not include `totalCount`, or the response is not a data response.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsHandle* data_handle = tcmb_evds_c_get_data_handle(data_series, date, api_key, Json, ascii_mode);
+///
+///     unsigned long total_count;
+///
+///     if (tcmb_evds_c_handle_total_count(data_handle, &total_count)) {
+///         printf("%lu observations from %s to %s with %lu missing values\n", 
+///             total_count,
+///             tcmb_evds_c_handle_first_date(data_handle),
+///             tcmb_evds_c_handle_last_date(data_handle),
+///             tcmb_evds_c_handle_null_count(data_handle));
+///     }
+///
+///     tcmb_evds_c_handle_free(data_handle);
+/// ```
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet. The `total_count` pointer
+/// must be NULL or writable.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_total_count(
+    handle: *const TcmbEvdsHandle,
+    total_count: *mut c_ulong
+) -> bool {
+
+    shield_or(false, || {
+        let found_total_count = read_handle(handle, "tcmb_evds_c_handle_total_count")
+            .and_then(TcmbEvdsHandle::get_stats)
+            .and_then(|stats| stats.stats.total_count);
+
+        match (found_total_count, total_count.as_mut()) {
+            (Some(found_total_count), Some(total_count)) => {
+                *total_count = found_total_count as c_ulong;
+
+                true
+            },
+            _ => false,
+        }
+    })
+}
+
+/// gives the number of the observations, namely rows, in the data response of the handle. Zero is returned when the 
+/// handle is NULL or an error, or the response is not a data response.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_row_count(handle: *const TcmbEvdsHandle) -> c_ulong {
+
+    shield_or(0, || {
+        match read_handle(handle, "tcmb_evds_c_handle_row_count").and_then(TcmbEvdsHandle::get_stats) {
+            Some(stats) => stats.stats.row_count as c_ulong,
+            None => 0,
+        }
+    })
+}
+
+/// gives the number of the missing values of all series in the data response of the handle, e.g. `null` values of 
+/// holidays. Zero is returned when the handle is NULL or an error, or the response is not a data response.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_null_count(handle: *const TcmbEvdsHandle) -> c_ulong {
+
+    shield_or(0, || {
+        match read_handle(handle, "tcmb_evds_c_handle_null_count").and_then(TcmbEvdsHandle::get_stats) {
+            Some(stats) => stats.stats.null_count as c_ulong,
+            None => 0,
+        }
+    })
+}
+
+/// gives the date of the first observation in the data response of the handle as a NUL terminated string.
+///
+/// The returned string belongs to the handle and remains valid until the handle is freed. NULL is returned when the 
+/// handle is NULL or an error, or the response does not have any observation.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_first_date(handle: *const TcmbEvdsHandle) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        let first_date = read_handle(handle, "tcmb_evds_c_handle_first_date")
+            .and_then(TcmbEvdsHandle::get_stats)
+            .and_then(|stats| stats.first_date.as_ref());
+
+        match first_date {
+            Some(first_date) => first_date.as_ptr(),
+            None => ptr::null(),
+        }
+    })
+}
+
+/// gives the date of the last observation in the data response of the handle like 
+/// [`tcmb_evds_c_handle_first_date`].
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_last_date(handle: *const TcmbEvdsHandle) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        let last_date = read_handle(handle, "tcmb_evds_c_handle_last_date")
+            .and_then(TcmbEvdsHandle::get_stats)
+            .and_then(|stats| stats.last_date.as_ref());
+
+        match last_date {
+            Some(last_date) => last_date.as_ptr(),
+            None => ptr::null(),
+        }
+    })
+}
+
+/// releases the handle. Passing NULL is allowed and does nothing.
+///
+/// # Safety
+///
+/// The handle must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_handle_free(handle: *mut TcmbEvdsHandle) {
+
+    shield_or((), || {
+        if handle.is_null() { return; }
+
+        debug_ffi::release(AllocationKind::Handle, handle, "tcmb_evds_c_handle_free");
+
+        let handle = Box::from_raw(handle);
+
+        MEMORY_COUNTERS.record_release(AllocationKind::Handle, handle.count_bytes());
+
+        drop(handle);
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CStr;
+
+    #[test]
+    fn should_read_handle() {
+        let result = TcmbEvdsResult::generate_result("Tarih,TP_DK_USD_S".to_string(), ReturnErrorC::NoError);
+
+        let handle = TcmbEvdsHandle::from(result).into_raw();
+
+        unsafe {
+            assert_eq!(17, tcmb_evds_c_handle_len(handle));
+            assert_eq!("Tarih,TP_DK_USD_S", CStr::from_ptr(tcmb_evds_c_handle_data_ptr(handle)).to_str().unwrap());
+            assert!(matches!(tcmb_evds_c_handle_error(handle), ReturnErrorC::NoError));
+
+            tcmb_evds_c_handle_free(handle);
+
+            assert!(tcmb_evds_c_handle_data_ptr(ptr::null()).is_null());
+            assert!(matches!(tcmb_evds_c_handle_error(ptr::null()), ReturnErrorC::InternalError));
+
+            let error_result = TcmbEvdsResult::generate_result("Error: Timeout.".to_string(), ReturnErrorC::Timeout)
+                .with_detail(Some("curl error 28: Timeout was reached".to_string()));
+            let error_handle = TcmbEvdsHandle::from(error_result).into_raw();
+
+            let detail = CStr::from_ptr(tcmb_evds_c_handle_detail(error_handle));
+
+            assert_eq!("curl error 28: Timeout was reached", detail.to_str().unwrap());
+
+            tcmb_evds_c_handle_free(error_handle);
+        }
+    }
+
+    #[test]
+    fn should_read_handle_stats() {
+        let response = r#"{"totalCount":2,"items":[
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8534"},{"Tarih":"13-12-2011","TP_DK_USD_S":null}
+        ]}"#;
+        let result = TcmbEvdsResult::generate_result(response.to_string(), ReturnErrorC::NoError);
+
+        let handle = TcmbEvdsHandle::from(result).into_raw();
+
+        unsafe {
+            let mut total_count = 0;
+
+            assert!(tcmb_evds_c_handle_total_count(handle, &mut total_count));
+            assert_eq!(2, total_count);
+            assert_eq!((2, 1), (tcmb_evds_c_handle_row_count(handle), tcmb_evds_c_handle_null_count(handle)));
+            assert_eq!("12-12-2011", CStr::from_ptr(tcmb_evds_c_handle_first_date(handle)).to_str().unwrap());
+            assert_eq!("13-12-2011", CStr::from_ptr(tcmb_evds_c_handle_last_date(handle)).to_str().unwrap());
+
+            tcmb_evds_c_handle_free(handle);
+
+            let error_message = "Error: Invalid date.".to_string();
+            let error_result = TcmbEvdsResult::generate_result(error_message, ReturnErrorC::InvalidDate);
+            let error_handle = TcmbEvdsHandle::from(error_result).into_raw();
+
+            assert!(!tcmb_evds_c_handle_total_count(error_handle, &mut total_count));
+            assert!(tcmb_evds_c_handle_first_date(error_handle).is_null());
+
+            tcmb_evds_c_handle_free(error_handle);
+        }
+    }
+}
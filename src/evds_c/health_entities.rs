@@ -0,0 +1,45 @@
+use libc::{c_uint, c_ulong};
+
+use crate::circuit_breaker::{CircuitState, Health};
+
+
+/// is the state of the circuit breaker guarding the requests to EVDS.
+///
+/// `CircuitClosed` lets the requests through. `CircuitOpen` makes them fail fast with `ServiceUnavailable` error during
+/// the cool-down period after repeated transport failures. `CircuitHalfOpen` lets them through after the cool-down
+/// period to probe EVDS; a failure opens the circuit again and a success closes it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsCircuitState {
+    CircuitClosed,
+    CircuitOpen,
+    CircuitHalfOpen,
+}
+
+/// reports the state of the circuit breaker returned by [`tcmb_evds_c_health`](crate::tcmb_evds_c_health).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct TcmbEvdsHealth {
+    pub circuit_state: TcmbEvdsCircuitState,
+    /// is the number of the last requests in a row that could not reach EVDS.
+    pub consecutive_failures: c_uint,
+    /// is the remaining cool-down period of the open circuit in milliseconds, or zero otherwise.
+    pub remaining_cool_down_ms: c_ulong,
+}
+
+impl TcmbEvdsHealth {
+    pub(crate) fn from(health: Health) -> TcmbEvdsHealth {
+
+        let circuit_state = match health.state {
+            CircuitState::Closed => TcmbEvdsCircuitState::CircuitClosed,
+            CircuitState::Open => TcmbEvdsCircuitState::CircuitOpen,
+            CircuitState::HalfOpen => TcmbEvdsCircuitState::CircuitHalfOpen,
+        };
+
+        TcmbEvdsHealth {
+            circuit_state,
+            consecutive_failures: health.consecutive_failures as c_uint,
+            remaining_cool_down_ms: health.remaining_cool_down.as_millis() as c_ulong,
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::ffi::{CStr, CString, c_void};
+use std::sync::{Mutex, MutexGuard};
+
+use libc::c_char;
+
+#[cfg(feature = "parse")]
+use super::dataset_entities::{TcmbEvdsDataset, tcmb_evds_c_dataset_free};
+
+use crate::common::url_builder::mask_api_key;
+#[cfg(feature = "parse")]
+use crate::dataset::Dataset;
+use crate::error::ReturnError;
+
+
+/// is a function called with the parsed dataset of each data response before the response is returned, e.g. to
+/// archive everything fetched. It is registered via
+/// [`tcmb_evds_c_set_response_hook`](crate::tcmb_evds_c_set_response_hook).
+///
+/// The dataset belongs to the library and is valid only during the call, so the hook must not free it. The user data
+/// is the pointer given together with the hook.
+#[cfg(feature = "parse")]
+pub type TcmbEvdsResponseHook = Option<unsafe extern "C" fn(dataset: *const TcmbEvdsDataset, user_data: *mut c_void)>;
+
+/// keeps the hook with its user data, which is stored as an address to share it among the threads.
+#[cfg(feature = "parse")]
+#[derive(Clone, Copy)]
+pub(crate) struct ResponseHook {
+    hook: unsafe extern "C" fn(*const TcmbEvdsDataset, *mut c_void),
+    user_data: usize,
+}
+
+#[cfg(feature = "parse")]
+impl ResponseHook {
+    pub(crate) fn new(
+        hook: unsafe extern "C" fn(*const TcmbEvdsDataset, *mut c_void),
+        user_data: *mut c_void,
+    ) -> ResponseHook {
+        ResponseHook { hook, user_data: user_data as usize }
+    }
+}
+
+/// describes a request about to be sent to EVDS to the pre-request hook.
+///
+/// The `url` is the url of the request whose api key is masked and is valid only during the call of the hook. The hook
+/// may annotate the request by pointing `annotation` to a header line such as `"X-Team: research"`, which is sent with
+/// the request, e.g. for the quota accounting of a proxy. The annotation must outlive the call of the hook, e.g. a 
+/// string literal, since it is copied after the hook returns.
+#[repr(C)]
+pub struct TcmbEvdsRequestInfo {
+    pub url: *const c_char,
+    pub annotation: *const c_char,
+}
+
+/// is a function called before each request is sent to EVDS, e.g. to enforce organizational policies. It is registered
+/// via [`tcmb_evds_c_set_request_hook`](crate::tcmb_evds_c_set_request_hook).
+///
+/// The hook returns NULL to let the request be sent, or a message to veto it. A vetoed request is not sent and the call
+/// returns `RequestVetoed` error with the message, which must outlive the call of the hook like the annotation.
+pub type TcmbEvdsRequestHook = 
+    Option<unsafe extern "C" fn(request: *mut TcmbEvdsRequestInfo, user_data: *mut c_void) -> *const c_char>;
+
+/// keeps the pre-request hook with its user data, which is stored as an address to share it among the threads.
+#[derive(Clone, Copy)]
+pub(crate) struct RequestHook {
+    hook: unsafe extern "C" fn(*mut TcmbEvdsRequestInfo, *mut c_void) -> *const c_char,
+    user_data: usize,
+}
+
+impl RequestHook {
+    pub(crate) fn new(
+        hook: unsafe extern "C" fn(*mut TcmbEvdsRequestInfo, *mut c_void) -> *const c_char,
+        user_data: *mut c_void,
+    ) -> RequestHook {
+        RequestHook { hook, user_data: user_data as usize }
+    }
+}
+
+#[cfg(feature = "parse")]
+static RESPONSE_HOOK: Mutex<Option<ResponseHook>> = Mutex::new(None);
+
+static REQUEST_HOOK: Mutex<Option<RequestHook>> = Mutex::new(None);
+
+
+/// gives access to the process wide response hook.
+///
+/// A poisoned lock is recovered because a panic cannot leave the hook half set.
+#[cfg(feature = "parse")]
+pub(crate) fn get_response_hook() -> MutexGuard<'static, Option<ResponseHook>> {
+    match RESPONSE_HOOK.lock() {
+        Ok(response_hook) => response_hook,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// calls the response hook with the parsed dataset of the response if a hook is set. The responses that are not data,
+/// e.g. categories, are not parsed into datasets and do not call the hook.
+///
+/// The hook is called without holding the lock, so it may call the functions of the library.
+#[cfg(feature = "parse")]
+pub(crate) fn run_response_hook(response: &str) {
+
+    let response_hook = match *get_response_hook() {
+        Some(response_hook) => response_hook,
+        None => return,
+    };
+
+    let dataset = match Dataset::from_response(response) {
+        Ok(dataset) => TcmbEvdsDataset::from(dataset).into_raw(),
+        Err(_) => return,
+    };
+
+    unsafe {
+        (response_hook.hook)(dataset, response_hook.user_data as *mut c_void);
+
+        tcmb_evds_c_dataset_free(dataset);
+    }
+}
+
+/// gives access to the process wide pre-request hook.
+///
+/// A poisoned lock is recovered because a panic cannot leave the hook half set.
+pub(crate) fn get_request_hook() -> MutexGuard<'static, Option<RequestHook>> {
+    match REQUEST_HOOK.lock() {
+        Ok(request_hook) => request_hook,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// calls the pre-request hook with the request of the url if a hook is set, and gives the header line the hook 
+/// annotated the request with. None is returned when there is no hook or annotation.
+///
+/// The hook is called without holding the lock, so it may call the functions of the library.
+///
+/// # Error
+///
+/// This function returns `RequestVetoed` error with the message of the hook when the hook vetoes the request.
+pub(crate) fn run_request_hook(url: &str) -> Result<Option<String>, ReturnError> {
+
+    let request_hook = match *get_request_hook() {
+        Some(request_hook) => request_hook,
+        None => return Ok(None),
+    };
+
+    // the masked url cannot contain a NUL character since the parameters are taken as texts.
+    let masked_url = CString::new(mask_api_key(url)).unwrap_or_default();
+
+    let mut request = TcmbEvdsRequestInfo { url: masked_url.as_ptr(), annotation: std::ptr::null() };
+
+    let veto_message = unsafe { (request_hook.hook)(&mut request, request_hook.user_data as *mut c_void) };
+
+    if !veto_message.is_null() {
+        let veto_message = unsafe { CStr::from_ptr(veto_message) }.to_string_lossy().into_owned();
+
+        return Err(ReturnError::RequestVetoed(veto_message));
+    }
+
+    match request.annotation.is_null() {
+        true => Ok(None),
+        false => Ok(Some(unsafe { CStr::from_ptr(request.annotation) }.to_string_lossy().into_owned())),
+    }
+}
+
+
+#[cfg(all(test, feature = "parse"))]
+mod tests {
+    use super::*;
+
+    use std::ffi::CStr;
+
+    use crate::evds_c::dataset_entities::{tcmb_evds_c_dataset_observation_count, tcmb_evds_c_dataset_series_code};
+
+    /// counts the observations of the test series only, since the other tests may return responses at the same time.
+    unsafe extern "C" fn count_observations(dataset: *const TcmbEvdsDataset, user_data: *mut c_void) {
+        let series_code = tcmb_evds_c_dataset_series_code(dataset, 0);
+
+        if series_code.is_null() || CStr::from_ptr(series_code).to_bytes() != b"TP_HOOK_TEST" { return; }
+
+        *(user_data as *mut u64) += tcmb_evds_c_dataset_observation_count(dataset) as u64;
+    }
+
+    #[test]
+    fn should_run_response_hook() {
+        let mut observation_count: u64 = 0;
+
+        *get_response_hook() = Some(ResponseHook::new(count_observations, &mut observation_count as *mut u64 as _));
+
+        run_response_hook(r#"{"totalCount":2,"items":[{"Tarih":"12-12-2011","TP_HOOK_TEST":"1.8534"},
+            {"Tarih":"13-12-2011","TP_HOOK_TEST":"1.8673"}]}"#);
+        run_response_hook("not a dataset");
+
+        *get_response_hook() = None;
+
+        run_response_hook(r#"{"totalCount":1,"items":[{"Tarih":"12-12-2011","TP_HOOK_TEST":"1.8534"}]}"#);
+
+        assert_eq!(2, observation_count);
+    }
+
+    /// vetoes the requests of the test series and annotates the others with the team of the user data.
+    unsafe extern "C" fn apply_policy(request: *mut TcmbEvdsRequestInfo, user_data: *mut c_void) -> *const c_char {
+        let url = CStr::from_ptr((*request).url).to_string_lossy();
+
+        if url.contains("TP_VETO_TEST") { return b"outside business hours\0".as_ptr() as *const c_char; }
+
+        (*request).annotation = user_data as *const c_char;
+
+        std::ptr::null()
+    }
+
+    #[test]
+    fn should_run_request_hook() {
+        let team = b"X-Team: research\0";
+
+        *get_request_hook() = Some(RequestHook::new(apply_policy, team.as_ptr() as *mut c_void));
+
+        let vetoed = run_request_hook("https://evds2.tcmb.gov.tr/service/evds/series=TP_VETO_TEST&key=users_key");
+        let annotated = run_request_hook("https://evds2.tcmb.gov.tr/service/evds/series=TP_DK_USD_S&key=users_key");
+
+        *get_request_hook() = None;
+
+        assert!(matches!(vetoed, Err(ReturnError::RequestVetoed(message)) if message == "outside business hours"));
+        assert_eq!(Some("X-Team: research".to_string()), annotated.unwrap());
+        assert_eq!(None, run_request_hook("https://evds2.tcmb.gov.tr/service/evds/series=TP_VETO_TEST").unwrap());
+    }
+}
@@ -0,0 +1,170 @@
+use std::os::raw::c_char;
+use std::ptr;
+
+use super::dataset_entities::TcmbEvdsDataset;
+use super::error_handling::shield_or;
+
+
+/// is an opaque iterator walking the values of a [`TcmbEvdsDataset`] observation by observation, created by
+/// [`tcmb_evds_c_iter_new`] and released via [`tcmb_evds_c_iter_free`].
+///
+/// The values of an observation are given in the order of the series before the next observation, so each value of a
+/// single series dataset is a separate observation. The iterator borrows the dataset, which must not be freed or
+/// rescaled before the iterator is freed.
+pub struct TcmbEvdsIterator {
+    dataset: *const TcmbEvdsDataset,
+    row: usize,
+    column: usize,
+}
+
+impl TcmbEvdsIterator {
+    /// gives the date and the value at the position and moves to the next position. None is returned after the last
+    /// value.
+    fn advance(&mut self, dataset: &TcmbEvdsDataset) -> Option<(*const c_char, Option<f64>)> {
+
+        if dataset.series_count() == 0 { return None; }
+
+        let (date, value) = dataset.read_cell(self.row, self.column)?;
+
+        self.column += 1;
+
+        if self.column == dataset.series_count() {
+            self.column = 0;
+            self.row += 1;
+        }
+
+        Some((date.as_ptr(), value))
+    }
+}
+
+
+/// creates an iterator over the values of the dataset starting from its first observation.
+///
+/// NULL is returned when the dataset is NULL.
+///
+/// # Safety
+///
+/// The dataset must be NULL or a valid pointer returned from this library, and it must outlive the iterator.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsIterator* iterator = tcmb_evds_c_iter_new(dataset);
+///
+///     const char* date;
+///     double value;
+///
+///     while (tcmb_evds_c_iter_next(iterator, &date, &value)) {
+///         if (!isnan(value)) { printf("%s: %f\n", date, value); }
+///     }
+///
+///     tcmb_evds_c_iter_free(iterator);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_iter_new(dataset: *const TcmbEvdsDataset) -> *mut TcmbEvdsIterator {
+
+    shield_or(ptr::null_mut(), || {
+        if dataset.is_null() { return ptr::null_mut(); }
+
+        Box::into_raw(Box::new(TcmbEvdsIterator { dataset, row: 0, column: 0 }))
+    })
+}
+
+/// writes the date and the value of the next observation into `date` and `value` and returns true, or returns false
+/// without writing after the last value.
+///
+/// The date belongs to the dataset and remains valid until the dataset is freed. A missing value in EVDS, e.g. of a
+/// holiday, is written as NaN. Either of the output pointers can be NULL to skip it.
+///
+/// # Safety
+///
+/// The iterator must be NULL or a valid pointer returned from this library and not freed yet. The date and the value
+/// must be NULL or valid pointers to a string pointer and a double.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_iter_next(
+    iterator: *mut TcmbEvdsIterator,
+    date: *mut *const c_char,
+    value: *mut f64
+) -> bool {
+
+    shield_or(false, || {
+        let iterator = match iterator.as_mut() {
+            Some(iterator) => iterator,
+            None => return false,
+        };
+
+        let next_value = match iterator.dataset.as_ref() {
+            Some(dataset) => iterator.advance(dataset),
+            None => None,
+        };
+
+        let (next_date, next_value) = match next_value {
+            Some(next_value) => next_value,
+            None => return false,
+        };
+
+        if let Some(date) = date.as_mut() { *date = next_date; }
+        if let Some(value) = value.as_mut() { *value = next_value.unwrap_or(f64::NAN); }
+
+        true
+    })
+}
+
+/// releases the iterator without releasing its dataset. Passing NULL is allowed and does nothing.
+///
+/// # Safety
+///
+/// The iterator must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_iter_free(iterator: *mut TcmbEvdsIterator) {
+
+    shield_or((), || {
+        if iterator.is_null() { return; }
+
+        drop(Box::from_raw(iterator));
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CStr;
+
+    use crate::dataset::Dataset;
+    use crate::evds_c::dataset_entities::tcmb_evds_c_dataset_free;
+
+    #[test]
+    fn should_iterate_observations() {
+        let response = r#"{"totalCount":2,"items":[
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8534","TP_DK_EUR_S":"2.4361"},
+            {"Tarih":"13-12-2011","TP_DK_USD_S":"1.8673","TP_DK_EUR_S":null}]}"#;
+
+        let dataset = TcmbEvdsDataset::from(Dataset::from_json(response).unwrap()).into_raw();
+
+        unsafe {
+            let iterator = tcmb_evds_c_iter_new(dataset);
+            let mut date = ptr::null();
+            let mut value = 0.0;
+            let mut values = Vec::new();
+
+            while tcmb_evds_c_iter_next(iterator, &mut date, &mut value) {
+                values.push((CStr::from_ptr(date).to_str().unwrap().to_string(), value));
+            }
+
+            assert_eq!(4, values.len());
+            assert_eq!(("12-12-2011".to_string(), 2.4361), values[0]);
+            assert_eq!(("12-12-2011".to_string(), 1.8534), values[1]);
+            assert!(values[2].1.is_nan());
+            assert_eq!(("13-12-2011".to_string(), 1.8673), values[3]);
+            assert!(!tcmb_evds_c_iter_next(iterator, ptr::null_mut(), ptr::null_mut()));
+
+            tcmb_evds_c_iter_free(iterator);
+            tcmb_evds_c_dataset_free(dataset);
+
+            assert!(tcmb_evds_c_iter_new(ptr::null()).is_null());
+            assert!(!tcmb_evds_c_iter_next(ptr::null_mut(), &mut date, &mut value));
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::key_pool::KeyRotation;
+use crate::traits::ReadingCEnum;
+
+
+/// specifies when the calls given an empty api key move on to the next key of the pool set via
+/// [`tcmb_evds_c_set_api_keys`](crate::tcmb_evds_c_set_api_keys).
+///
+/// `RoundRobinRotation` gives each call the next key in turn. `QuotaErrorRotation` keeps giving the same key until
+/// EVDS rate limits a request made with it. Both skip a key right after it is rate limited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsKeyRotation {
+    RoundRobinRotation,
+    QuotaErrorRotation,
+}
+
+impl TcmbEvdsKeyRotation {
+    pub(crate) fn to_rotation(self) -> KeyRotation {
+        match self {
+            TcmbEvdsKeyRotation::RoundRobinRotation => KeyRotation::RoundRobin,
+            TcmbEvdsKeyRotation::QuotaErrorRotation => KeyRotation::OnQuotaError,
+        }
+    }
+}
+
+impl ReadingCEnum for TcmbEvdsKeyRotation {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsKeyRotation::RoundRobinRotation,
+        TcmbEvdsKeyRotation::QuotaErrorRotation,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsKeyRotation::RoundRobinRotation;
+}
@@ -0,0 +1,300 @@
+use std::ptr;
+
+use libc::c_ulong;
+
+use super::common_entities::{TcmbEvdsInput, TcmbEvdsResult};
+use super::dataset_entities::TcmbEvdsDataset;
+use super::error_handling::shield_or;
+use super::memory_entities::{AllocationKind, MEMORY_COUNTERS};
+use crate::dataset::lazy::LazyDataset;
+use crate::debug_ffi;
+
+
+/// is an opaque handle of an EVDS JSON response whose observations are parsed only when they are read, created by
+/// [`tcmb_evds_c_lazy_dataset_from_result`] and released via [`tcmb_evds_c_lazy_dataset_free`].
+///
+/// The handle suits reading a few values, e.g. the latest value of a series, out of a large response. The rows and
+/// series read more than once are better materialized into a [`TcmbEvdsDataset`] via
+/// [`tcmb_evds_c_lazy_dataset_materialize`].
+pub struct TcmbEvdsLazyDataset {
+    lazy_dataset: LazyDataset,
+    /// is the approximate size of the response and its index reported in the memory statistics.
+    allocated_bytes: usize,
+}
+
+/// reads a lazy dataset received from C. The process is aborted with a diagnostic when the lazy dataset is already
+/// freed and the crate is built with the `debug_ffi` feature.
+unsafe fn read_lazy_dataset<'a>(
+    lazy_dataset: *const TcmbEvdsLazyDataset,
+    function_name: &str
+) -> Option<&'a LazyDataset> {
+
+    debug_ffi::check_live(AllocationKind::Dataset, lazy_dataset, function_name);
+
+    lazy_dataset.as_ref().map(|lazy_dataset| &lazy_dataset.lazy_dataset)
+}
+
+/// reads a series code written with dots as in the requests, e.g. "TP.DK.USD.S", or with underscores as in the
+/// responses. None is returned when the code is invalid.
+fn read_series_code(series_code: &TcmbEvdsInput) -> Option<String> {
+
+    let (rust_series_code, series_code_error_state) = series_code.get_input("series_code");
+
+    if series_code_error_state { return None; }
+
+    Some(rust_series_code.trim().replace('.', "_"))
+}
+
+
+/// indexes the JSON data inside of the result, so that its values are read without parsing the whole response.
+///
+/// Only the places of the observations are found while indexing, and each observation is parsed when it is read. The
+/// given result is not freed. NULL is returned when the result contains an error or its data is not a JSON response
+/// of EVDS data, e.g. a CSV response.
+///
+/// # Safety
+///
+/// The result must be returned from this library and its output must not be freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Json, false);
+///
+///     TcmbEvdsLazyDataset* lazy_dataset = tcmb_evds_c_lazy_dataset_from_result(data_result);
+///
+///     double value;
+///
+///     if (tcmb_evds_c_lazy_dataset_latest_value(lazy_dataset, data_series, &value)) { printf("%f\n", value); }
+///
+///     tcmb_evds_c_lazy_dataset_free(lazy_dataset);
+///     tcmb_evds_c_result_free(data_result);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_lazy_dataset_from_result(result: TcmbEvdsResult) -> *mut TcmbEvdsLazyDataset {
+
+    shield_or(ptr::null_mut(), || {
+        let lazy_dataset = match result.read_data().map(LazyDataset::index) {
+            Some(Ok(lazy_dataset)) => lazy_dataset,
+            _ => return ptr::null_mut(),
+        };
+
+        let allocated_bytes = lazy_dataset.allocated_bytes();
+
+        MEMORY_COUNTERS.record_allocation(AllocationKind::Dataset, allocated_bytes);
+
+        let lazy_dataset = Box::into_raw(Box::new(TcmbEvdsLazyDataset { lazy_dataset, allocated_bytes }));
+
+        debug_ffi::register(AllocationKind::Dataset, lazy_dataset);
+
+        lazy_dataset
+    })
+}
+
+/// gives the number of observations, namely rows, of the lazy dataset including the ones without values.
+///
+/// # Safety
+///
+/// The lazy dataset must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_lazy_dataset_observation_count(
+    lazy_dataset: *const TcmbEvdsLazyDataset
+) -> c_ulong {
+
+    shield_or(0, || {
+        match read_lazy_dataset(lazy_dataset, "tcmb_evds_c_lazy_dataset_observation_count") {
+            Some(lazy_dataset) => lazy_dataset.len() as c_ulong,
+            None => 0,
+        }
+    })
+}
+
+/// writes the value of the series at the given observation into `value` by parsing only that observation.
+///
+/// The series code is written with dots as in the requests, e.g. "TP.DK.USD.S", or with underscores as in the
+/// responses. Returns false without writing when the row is out of range, the series code is invalid or the value is
+/// missing in EVDS.
+///
+/// # Safety
+///
+/// The lazy dataset must be NULL or a valid pointer returned from this library and not freed yet. The value must be
+/// NULL or a valid pointer to a double.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_lazy_dataset_value(
+    lazy_dataset: *const TcmbEvdsLazyDataset,
+    row: c_ulong,
+    series_code: TcmbEvdsInput,
+    value: *mut f64
+) -> bool {
+
+    shield_or(false, || {
+        let found_value = read_lazy_dataset(lazy_dataset, "tcmb_evds_c_lazy_dataset_value")
+            .zip(read_series_code(&series_code))
+            .and_then(|(lazy_dataset, series_code)| lazy_dataset.value(row as usize, &series_code));
+
+        match (found_value, value.as_mut()) {
+            (Some(found_value), Some(value)) => {
+                *value = found_value;
+                true
+            },
+            _ => false,
+        }
+    })
+}
+
+/// writes the value of the last observation having a value of the series into `value`, parsing the observations from
+/// the end of the response until the value is found.
+///
+/// The series code is written with dots as in the requests, e.g. "TP.DK.USD.S", or with underscores as in the
+/// responses. Returns false without writing when the series code is invalid or the series has no value.
+///
+/// # Safety
+///
+/// The lazy dataset must be NULL or a valid pointer returned from this library and not freed yet. The value must be
+/// NULL or a valid pointer to a double.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_lazy_dataset_latest_value(
+    lazy_dataset: *const TcmbEvdsLazyDataset,
+    series_code: TcmbEvdsInput,
+    value: *mut f64
+) -> bool {
+
+    shield_or(false, || {
+        let found_value = read_lazy_dataset(lazy_dataset, "tcmb_evds_c_lazy_dataset_latest_value")
+            .zip(read_series_code(&series_code))
+            .and_then(|(lazy_dataset, series_code)| lazy_dataset.latest_value(&series_code));
+
+        match (found_value, value.as_mut()) {
+            (Some((_, found_value)), Some(value)) => {
+                *value = found_value;
+                true
+            },
+            _ => false,
+        }
+    })
+}
+
+/// parses `row_count` observations starting from `first_row` into a dataset having only the given series, which must
+/// be released with `tcmb_evds_c_dataset_free`.
+///
+/// The series are separated by dashes as in the requests, e.g. "TP.DK.USD.S-TP.DK.EUR.S", and an empty series list
+/// keeps each series found in the observations. The rows out of range are skipped. NULL is returned when the lazy
+/// dataset is NULL, the series list is invalid or one of the observations could not be parsed.
+///
+/// # Safety
+///
+/// The lazy dataset must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     unsigned long count = tcmb_evds_c_lazy_dataset_observation_count(lazy_dataset);
+///
+///     // the last 10 observations.
+///     TcmbEvdsDataset* dataset =
+///         tcmb_evds_c_lazy_dataset_materialize(lazy_dataset, data_series, count < 10 ? 0 : count - 10, 10);
+///
+///     tcmb_evds_c_dataset_free(dataset);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_lazy_dataset_materialize(
+    lazy_dataset: *const TcmbEvdsLazyDataset,
+    series_codes: TcmbEvdsInput,
+    first_row: c_ulong,
+    row_count: c_ulong
+) -> *mut TcmbEvdsDataset {
+
+    shield_or(ptr::null_mut(), || {
+        let lazy_dataset = match read_lazy_dataset(lazy_dataset, "tcmb_evds_c_lazy_dataset_materialize") {
+            Some(lazy_dataset) => lazy_dataset,
+            None => return ptr::null_mut(),
+        };
+
+        let (rust_series_codes, series_codes_error_state) = series_codes.get_input("series_codes");
+
+        if series_codes_error_state { return ptr::null_mut(); }
+
+        let rust_series_codes: Vec<String> = rust_series_codes
+            .split('-')
+            .map(|series_code| series_code.trim().replace('.', "_"))
+            .filter(|series_code| !series_code.is_empty())
+            .collect();
+
+        let first_row = first_row as usize;
+        let rows = first_row..first_row.saturating_add(row_count as usize);
+
+        match lazy_dataset.materialize(&rust_series_codes, rows) {
+            Ok(dataset) => TcmbEvdsDataset::from(dataset).into_raw(),
+            Err(_) => ptr::null_mut(),
+        }
+    })
+}
+
+/// releases the lazy dataset. Passing NULL is allowed and does nothing. The datasets materialized from it are not
+/// released.
+///
+/// # Safety
+///
+/// The lazy dataset must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_lazy_dataset_free(lazy_dataset: *mut TcmbEvdsLazyDataset) {
+
+    shield_or((), || {
+        if lazy_dataset.is_null() { return; }
+
+        debug_ffi::release(AllocationKind::Dataset, lazy_dataset, "tcmb_evds_c_lazy_dataset_free");
+
+        let lazy_dataset = Box::from_raw(lazy_dataset);
+
+        MEMORY_COUNTERS.record_release(AllocationKind::Dataset, lazy_dataset.allocated_bytes);
+
+        drop(lazy_dataset);
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CString;
+
+    use super::super::dataset_entities::{tcmb_evds_c_dataset_free, tcmb_evds_c_dataset_observation_count};
+    use super::super::error_handling::ReturnErrorC;
+
+    #[test]
+    fn should_read_latest_value_lazily() {
+        let response = r#"{"totalCount":3,"items":[
+            {"Tarih":"12-12-2011","TP_DK_USD_S":"1.8534","TP_DK_EUR_S":"2.4361"},
+            {"Tarih":"13-12-2011","TP_DK_USD_S":"1.8673","TP_DK_EUR_S":null},
+            {"Tarih":"14-12-2011","TP_DK_USD_S":null,"TP_DK_EUR_S":null}]}"#;
+
+        let result = TcmbEvdsResult::generate_result(response.to_string(), ReturnErrorC::NoError);
+        let series_code = CString::new("TP.DK.EUR.S").unwrap();
+        let series_code = || TcmbEvdsInput { input_ptr: series_code.as_ptr(), string_capacity: 11 };
+
+        unsafe {
+            // C passes a copy of the result, which stays to be freed by the caller.
+            let lazy_dataset = tcmb_evds_c_lazy_dataset_from_result(ptr::read(&result));
+            let mut value = 0.0;
+
+            assert_eq!(3, tcmb_evds_c_lazy_dataset_observation_count(lazy_dataset));
+            assert!(tcmb_evds_c_lazy_dataset_latest_value(lazy_dataset, series_code(), &mut value));
+            assert_eq!(2.4361, value);
+            assert!(!tcmb_evds_c_lazy_dataset_value(lazy_dataset, 1, series_code(), &mut value));
+
+            let dataset = tcmb_evds_c_lazy_dataset_materialize(lazy_dataset, series_code(), 1, 5);
+
+            assert_eq!(2, tcmb_evds_c_dataset_observation_count(dataset));
+
+            tcmb_evds_c_dataset_free(dataset);
+            tcmb_evds_c_lazy_dataset_free(lazy_dataset);
+
+            let csv_result = TcmbEvdsResult::generate_result("Tarih,TP_DK_USD_S".to_string(), ReturnErrorC::NoError);
+
+            assert!(tcmb_evds_c_lazy_dataset_from_result(csv_result).is_null());
+        }
+
+        result.into_parts();
+    }
+}
@@ -0,0 +1,147 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use libc::c_ulong;
+
+
+/// reports the live allocations that are made for the objects handed to C and not released yet.
+///
+/// Results are only counted as released when they are freed via
+/// [`tcmb_evds_c_result_free`](crate::tcmb_evds_c_result_free) or moved into a handle. Results released via `free`
+/// stay counted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct TcmbEvdsMemoryStats {
+    pub live_results: c_ulong,
+    pub live_handles: c_ulong,
+    pub live_datasets: c_ulong,
+    /// is the sum of the bytes of the live results, handles and datasets.
+    pub live_bytes: c_ulong,
+    /// is the budget set via [`tcmb_evds_c_set_memory_budget`](crate::tcmb_evds_c_set_memory_budget). Zero means no
+    /// budget.
+    pub budget_bytes: c_ulong,
+}
+
+/// specifies the kind of the object allocated for C.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum AllocationKind {
+    Result,
+    Handle,
+    Dataset,
+}
+
+/// counts the live objects of each kind and their total bytes.
+pub(crate) struct MemoryCounters {
+    live_objects: [AtomicUsize; 3],
+    live_bytes: AtomicUsize,
+}
+
+impl MemoryCounters {
+    pub(crate) const fn new() -> MemoryCounters {
+        MemoryCounters {
+            live_objects: [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)],
+            live_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    /// records an object allocated for C.
+    pub(crate) fn record_allocation(&self, kind: AllocationKind, bytes: usize) {
+        self.live_objects[kind as usize].fetch_add(1, Ordering::Relaxed);
+        self.live_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// records the bytes added to an object already allocated for C.
+    pub(crate) fn record_growth(&self, bytes: usize) {
+        self.live_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// records an object released by C. The counters never fall below zero.
+    pub(crate) fn record_release(&self, kind: AllocationKind, bytes: usize) {
+        let saturating_subtract = |counter: &AtomicUsize, amount: usize| {
+            let _ = counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |value| {
+                Some(value.saturating_sub(amount))
+            });
+        };
+
+        saturating_subtract(&self.live_objects[kind as usize], 1);
+        saturating_subtract(&self.live_bytes, bytes);
+    }
+
+    /// gives the number of the live objects of the given kind.
+    pub(crate) fn get_live_objects(&self, kind: AllocationKind) -> usize {
+        self.live_objects[kind as usize].load(Ordering::Relaxed)
+    }
+
+    /// gives the total bytes of the live objects.
+    pub(crate) fn get_live_bytes(&self) -> usize {
+        self.live_bytes.load(Ordering::Relaxed)
+    }
+
+    /// checks whether the live bytes reach the given budget. Zero means no budget.
+    ///
+    /// The results released via `free` instead of [`tcmb_evds_c_result_free`](crate::tcmb_evds_c_result_free) stay
+    /// counted, so under a budget they must be freed via the latter for the live bytes to fall below the budget again.
+    pub(crate) fn is_budget_exceeded(&self, budget_bytes: usize) -> bool {
+        budget_bytes != 0 && self.get_live_bytes() >= budget_bytes
+    }
+
+    /// generates the statistics with the given budget.
+    pub(crate) fn generate_stats(&self, budget_bytes: usize) -> TcmbEvdsMemoryStats {
+        TcmbEvdsMemoryStats {
+            live_results: self.get_live_objects(AllocationKind::Result) as c_ulong,
+            live_handles: self.get_live_objects(AllocationKind::Handle) as c_ulong,
+            live_datasets: self.get_live_objects(AllocationKind::Dataset) as c_ulong,
+            live_bytes: self.get_live_bytes() as c_ulong,
+            budget_bytes: budget_bytes as c_ulong,
+        }
+    }
+}
+
+/// is the process wide counters of the objects handed to C.
+pub(crate) static MEMORY_COUNTERS: MemoryCounters = MemoryCounters::new();
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_count_allocations() {
+        let counters = MemoryCounters::new();
+
+        counters.record_allocation(AllocationKind::Result, 100);
+        counters.record_allocation(AllocationKind::Handle, 20);
+        counters.record_release(AllocationKind::Result, 100);
+
+        assert_eq!(
+            TcmbEvdsMemoryStats { live_results: 0, live_handles: 1, live_datasets: 0, live_bytes: 20, budget_bytes: 64 },
+            counters.generate_stats(64)
+        );
+
+        counters.record_release(AllocationKind::Dataset, 50);
+
+        assert_eq!(0, counters.get_live_objects(AllocationKind::Dataset));
+        assert_eq!(0, counters.get_live_bytes());
+    }
+
+    #[test]
+    fn should_allocate_again_after_release_under_budget() {
+        let counters = MemoryCounters::new();
+
+        counters.record_allocation(AllocationKind::Result, 100);
+
+        assert!(!counters.is_budget_exceeded(0));
+        assert!(!counters.is_budget_exceeded(150));
+
+        counters.record_allocation(AllocationKind::Result, 100);
+
+        assert!(counters.is_budget_exceeded(150));
+
+        counters.record_release(AllocationKind::Result, 100);
+
+        assert!(!counters.is_budget_exceeded(150));
+
+        counters.record_allocation(AllocationKind::Result, 40);
+
+        assert!(!counters.is_budget_exceeded(150));
+    }
+}
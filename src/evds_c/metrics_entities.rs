@@ -0,0 +1,59 @@
+use libc::c_ulong;
+
+use crate::metrics::MetricsSnapshot;
+
+
+/// reports the process wide counters returned by [`tcmb_evds_c_metrics_snapshot`](crate::tcmb_evds_c_metrics_snapshot).
+///
+/// The counters start from zero when the library is loaded and only increase. The latency percentiles are computed
+/// from the latest 1024 requests and are zero until a request is made.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct TcmbEvdsMetrics {
+    /// is the number of the requests sent to EVDS. A retried request is counted once.
+    pub requests: c_ulong,
+    pub received_bytes: c_ulong,
+    /// is the number of the errors of all types returned to C. The count of a type is read via
+    /// [`tcmb_evds_c_metrics_error_count`](crate::tcmb_evds_c_metrics_error_count).
+    pub errors: c_ulong,
+    /// is the number of the requests served from the response cache or the observation store.
+    pub cache_hits: c_ulong,
+    pub cache_misses: c_ulong,
+    /// is the number of the attempts repeated after a failed attempt or a rate limited response.
+    pub retries: c_ulong,
+    pub latency_p50_ms: f64,
+    pub latency_p95_ms: f64,
+}
+
+impl TcmbEvdsMetrics {
+    pub(crate) fn from(snapshot: &MetricsSnapshot) -> TcmbEvdsMetrics {
+
+        let to_milliseconds = |latency: Option<std::time::Duration>| {
+            latency.map_or(0.0, |latency| latency.as_secs_f64() * 1_000.0)
+        };
+
+        TcmbEvdsMetrics {
+            requests: snapshot.requests as c_ulong,
+            received_bytes: snapshot.received_bytes as c_ulong,
+            errors: snapshot.count_errors() as c_ulong,
+            cache_hits: snapshot.cache_hits as c_ulong,
+            cache_misses: snapshot.cache_misses as c_ulong,
+            retries: snapshot.retries as c_ulong,
+            latency_p50_ms: to_milliseconds(snapshot.latency_p50),
+            latency_p95_ms: to_milliseconds(snapshot.latency_p95),
+        }
+    }
+
+    pub(crate) fn zero() -> TcmbEvdsMetrics {
+        TcmbEvdsMetrics {
+            requests: 0,
+            received_bytes: 0,
+            errors: 0,
+            cache_hits: 0,
+            cache_misses: 0,
+            retries: 0,
+            latency_p50_ms: 0.0,
+            latency_p95_ms: 0.0,
+        }
+    }
+}
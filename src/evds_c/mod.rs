@@ -106,55 +106,353 @@ pub mod common_entities;
 ///     if (advanced_data_result.error_type == InvalidApiKeyOrBadInternetConnection) { /* A Process */ };
 /// ```
 pub mod error_handling;
-mod date_entities;
+/// provides an opaque dataset handle and its accessor functions to read parsed EVDS responses in C language.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsDataset* dataset = NULL;
+///
+///     TcmbEvdsResult last_result = tcmb_evds_c_get_last_n(data_series, 5, api_key, ascii_mode, &dataset);
+///
+///
+///     // reading the parsed observations.
+///     for (unsigned long row = 0; row < tcmb_evds_c_dataset_observation_count(dataset); row++) {
+///         double value;
+///
+///         if (tcmb_evds_c_dataset_value(dataset, row, 0, &value)) {
+///             printf("%s: %f\n", tcmb_evds_c_dataset_date(dataset, row), value);
+///         }
+///     }
+///
+///
+///     // dataset must be freed.
+///     tcmb_evds_c_dataset_free(dataset);
+/// ```
+#[cfg(feature = "parse")]
+pub mod dataset_entities;
+/// provides the currency and exchange side options to build the currency series codes expected by EVDS.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult series_result = tcmb_evds_c_build_currency_series(Usd, SellingSide, true);
+///
+///     // "TP.DK.USD.S.YTL"
+///     fwrite(series_result.output_ptr, series_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(series_result);
+/// ```
+#[cfg(feature = "currency")]
+pub mod currency_entities;
+/// provides an opaque handle of a JSON response that is indexed once and parsed only for the observations read from 
+/// it, e.g. to read the latest value of a series out of a large response.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsLazyDataset* lazy_dataset = tcmb_evds_c_lazy_dataset_from_result(data_result);
+///
+///     double value;
+///
+///     if (tcmb_evds_c_lazy_dataset_latest_value(lazy_dataset, data_series, &value)) { printf("%f\n", value); }
+///
+///
+///     // lazy dataset must be freed.
+///     tcmb_evds_c_lazy_dataset_free(lazy_dataset);
+/// ```
+#[cfg(feature = "parse")]
+pub mod lazy_entities;
+/// provides an opaque iterator to walk the observations of a dataset in C language without indexing into its rows and
+/// columns.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsIterator* iterator = tcmb_evds_c_iter_new(dataset);
+///
+///     const char* date;
+///     double value;
+///
+///     while (tcmb_evds_c_iter_next(iterator, &date, &value)) { write_to_sink(date, value); }
+///
+///
+///     // iterator must be freed before its dataset.
+///     tcmb_evds_c_iter_free(iterator);
+///     tcmb_evds_c_dataset_free(dataset);
+/// ```
+#[cfg(feature = "parse")]
+pub mod iterator_entities;
+/// provides an opaque result handle and its accessor functions as an alternative to `TcmbEvdsResult` whose layout is a
+/// part of the C ABI.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsHandle* data_handle = 
+///         tcmb_evds_c_get_data_handle(data_series, date, api_key, return_format, ascii_mode);
+///
+///
+///     // error checking and printing the response.
+///     if (tcmb_evds_c_handle_error(data_handle) == NoError) { 
+///         printf("%s\n", tcmb_evds_c_handle_data_ptr(data_handle));
+///     };
+///
+///
+///     // handle must be freed.
+///     tcmb_evds_c_handle_free(data_handle);
+/// ```
+pub mod handle_entities;
+/// provides a flattened export set for .NET P/Invoke and the other FFI layers that cannot marshal structures by value.
+///
+/// The functions take each text as a UTF-8 pointer and a length, take `ascii_mode` as an `int`, return a
+/// [`TcmbEvdsHandle`](handle_entities::TcmbEvdsHandle) and use the `stdcall` convention on 32-bit Windows. They are not
+/// declared in the C header but in the `TcmbEvds.cs` binding file generated next to it.
+///
+/// # Example
+///
+/// ```C#
+///     var evds = new TcmbEvds.Evds("VALID_API_KEY");
+///
+///     string response = evds.GetData("TP.DK.USD.S", "13-12-2011", TcmbEvdsReturnFormat.Json);
+/// ```
+pub mod flat_entities;
+/// provides the variants of the operational functions taking wide strings, e.g. `wchar_t` strings of Windows 
+/// applications, which are converted into UTF-8 internally.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsWideInput data_group = { L"bie_dkdovytl", wcslen(L"bie_dkdovytl") };
+///
+///     TcmbEvdsResult data_group_result = 
+///         tcmb_evds_c_get_data_group_w(data_group, date, api_key, return_format, ascii_mode);
+/// ```
+#[cfg(feature = "parse")]
+pub mod wide_entities;
+/// provides the state of the circuit breaker that makes the requests fail fast during EVDS outages.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsHealth health = tcmb_evds_c_health();
+///
+///     if (health.circuit_state == CircuitOpen) {
+///         printf("EVDS is paused for %lu ms after %u failures\n", 
+///             health.remaining_cool_down_ms, 
+///             health.consecutive_failures);
+///     }
+/// ```
+pub mod health_entities;
+/// provides the process wide counters of the requests, errors and cache lookups for the users embedding the library in
+/// services.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsMetrics metrics = tcmb_evds_c_metrics_snapshot();
+///
+///     printf("%lu requests, p95 latency: %.1f ms\n", metrics.requests, metrics.latency_p95_ms);
+/// ```
+pub mod metrics_entities;
+/// provides the rotation of the api keys of the key pool shared by the calls given an empty api key.
+///
+/// # Example
+///
+/// ```C
+///     api_keys.input_ptr = "FIRST_API_KEY,SECOND_API_KEY";
+///     api_keys.string_capacity = strlen(api_keys.input_ptr);
+///
+///     tcmb_evds_c_set_api_keys(api_keys, RoundRobinRotation);
+/// ```
+pub mod key_entities;
+/// provides the memory statistics of the objects handed to C to detect the missing free calls.
+///
+/// # Example
+///
+/// ```C
+///     // requests fail fast with MemoryBudgetExceeded error after 64 MiB of live results.
+///     tcmb_evds_c_set_memory_budget(64 * 1024 * 1024);
+///
+///     TcmbEvdsMemoryStats memory_stats = tcmb_evds_c_memory_stats();
+///
+///     printf("%lu live results of %lu bytes\n", memory_stats.live_results, memory_stats.live_bytes);
+/// ```
+pub mod memory_entities;
+/// provides the answer of the publication calendar, which may be unknown for the years out of its religious holidays.
+///
+/// # Example
+///
+/// ```C
+///     if (tcmb_evds_c_is_publication_day(date) == UnknownPublicationDay) { /* A Process */ };
+/// ```
+pub mod calendar_entities;
+/// provides the defaults of the session that the calls use for their empty dates and `SessionFormat` return format.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsSessionDefaults defaults;
+///
+///     defaults.date.input_ptr = "";
+///     defaults.date.string_capacity = 0;
+///     defaults.trailing_days = 0;
+///     defaults.date_policy = YearToDate;
+///     defaults.return_format = Json;
+///     defaults.ascii_mode = AsciiModeForceOn;
+///
+///     tcmb_evds_c_set_session_defaults(defaults);
+/// ```
+pub mod session_entities;
+/// provides the builder-style request made via [`tcmb_evds_c_execute`](crate::tcmb_evds_c_execute) instead of the 
+/// positional arguments of the operational functions.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsRequest* request = tcmb_evds_c_request_new();
+///
+///     tcmb_evds_c_request_set_series(request, data_series);
+///     tcmb_evds_c_request_set_dates(request, date);
+///     tcmb_evds_c_request_set_api_key(request, api_key);
+///     tcmb_evds_c_request_set_formula(request, PercentageChange);
+///
+///     TcmbEvdsResult result = tcmb_evds_c_execute(request);
+///
+///     tcmb_evds_c_request_free(request);
+/// ```
+pub mod request_entities;
+/// provides the mode of recording the responses of EVDS into a directory and replaying them to reproduce bug reports.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_recording(RecordResponses, directory);
+///
+///     /* The calls to be reproduced */
+///
+///     tcmb_evds_c_set_recording(ReplayResponses, directory);
+/// ```
+pub mod recording_entities;
+/// provides the hook called with the parsed dataset of each data response, e.g. to archive the fetched data, and the 
+/// hook called before each request that may veto or annotate it.
+///
+/// # Example
+///
+/// ```C
+///     void archive(const TcmbEvdsDataset* dataset, void* user_data) { /* A Process */ }
+///     const char* apply_policy(TcmbEvdsRequestInfo* request, void* user_data) { return NULL; }
+///
+///     tcmb_evds_c_set_response_hook(archive, NULL);
+///     tcmb_evds_c_set_request_hook(apply_policy, NULL);
+/// ```
+pub mod hook_entities;
+/// provides the priorities of the requests, so that the requests waited by users preempt the bulk requests, e.g. the 
+/// requests of a nightly catalog sync.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_thread_priority(BulkPriority);
+///
+///     /* The requests of the catalog sync */
+/// ```
+pub mod scheduler_entities;
+/// provides the tree of the categories of EVDS holding their data groups, walked by the indexes of its nodes.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsCategoryTree* tree = NULL;
+///
+///     TcmbEvdsResult tree_result = tcmb_evds_c_get_category_tree(api_key, EnglishHeaders, &tree);
+///
+///     long category = tcmb_evds_c_tree_first_category(tree);
+///     long group = tcmb_evds_c_tree_first_child(tree, category);
+///
+///     tcmb_evds_c_tree_free(tree);
+/// ```
+#[cfg(feature = "catalog")]
+pub mod tree_entities;
+/// provides the built-in post-processors run on each data response of the session.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsPostProcessor post_processors[] = { DecimalPostProcessor, AsciiPostProcessor };
+///
+///     TcmbEvdsInput no_unit;
+///
+///     no_unit.input_ptr = "";
+///     no_unit.string_capacity = 0;
+///
+///     if (!tcmb_evds_c_set_post_processors(post_processors, 2, no_unit, 0)) { /* A Process */ };
+/// ```
+#[cfg(feature = "parse")]
+pub mod pipeline_entities;
+pub(crate) mod date_entities;
 pub(crate) mod data_series;
+pub(crate) mod settings;
 
-use self::error_handling::{ReturnErrorC, handle_return_error};
+use std::cell::RefCell;
+
+use self::error_handling::{ParameterErrors, ReturnErrorC, handle_return_error, handle_parameter_return_error};
 use self::common_entities::*;
 use self::date_entities::*;
 
-use crate::common;
+use crate::common::{self, url_builder::{UrlBuilder, check_query_value}};
+#[cfg(feature = "cache")]
+use crate::dataset::cache;
+use crate::dataset::merge::merge_responses;
 use crate::date::{self, DatePreference};
 use crate::error::ReturnError;
-use crate::traits::ConvertingToRustEnum;
+#[cfg(feature = "parse")]
+use crate::evds_basic::post_processing::run_post_processors;
+use crate::evds_currency::frequency_formulas::DataFrequency;
+use crate::key_pool;
+use crate::traits::{ConvertingToRustEnum, EnumSpecific, ReadingCEnum};
 
 
-pub(crate) fn convert_to_ascii(text: &mut String) {
+thread_local! {
+    /// is the api key of the prepared request the thread is executing, which is already verified via EVDS.
+    static VERIFIED_API_KEY: RefCell<Option<String>> = const { RefCell::new(None) };
+}
 
-    let english_characters = 
-        [('Ç','C'), 
-        ('ç', 'c'), 
-        ('Ğ', 'G'), 
-        ('ğ', 'g'), 
-        ('İ', 'I'), 
-        ('ı', 'i'), 
-        ('Ö', 'O'), 
-        ('ö', 'o'), 
-        ('Ş', 'S'), 
-        ('ş', 's'), 
-        ('Ü', 'U'), 
-        ('ü', 'u')];
-
-
-    let a = text.chars()
-        .map(|character| { 
-            let result = english_characters.iter().find(|chars| chars.0 == character);
-        
-            let result_char = match result {
-                Some(chars) => chars.1,
-                None => character,
-            };
-
-            result_char
-        })
-        .map(|character|  {
-            if !character.is_ascii() { return '*'; }
-        
-            character
-        });
 
+/// converts the Turkish characters of the text to English ones and the other non-ascii characters to asterisks.
+///
+/// The text is left as it is when it is already ascii, and is converted into a single buffer of its length otherwise
+/// since every character is replaced by an ascii character.
+pub(crate) fn convert_to_ascii(text: &mut String) {
 
-    *text = a.collect::<String>();
+    let _span = tracing::info_span!("convert").entered();
+
+    if text.is_ascii() { return; }
+
+    let mut converted_text = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        let converted_character = match character {
+            'Ç' => 'C',
+            'ç' => 'c',
+            'Ğ' => 'G',
+            'ğ' => 'g',
+            'İ' => 'I',
+            'ı' => 'i',
+            'Ö' => 'O',
+            'ö' => 'o',
+            'Ş' => 'S',
+            'ş' => 's',
+            'Ü' => 'U',
+            'ü' => 'u',
+            character if character.is_ascii() => character,
+            _ => '*',
+        };
+
+        converted_text.push(converted_character);
+    }
+
+    *text = converted_text;
 }
 
 pub(crate) fn generate_date_preference(date_data: &str) -> Result<DatePreference, TcmbEvdsResult> {
@@ -167,16 +465,25 @@ pub(crate) fn generate_date_preference(date_data: &str) -> Result<DatePreference
         DateFormatType::Single => {
 
             let converted_date = date::Date::from(&date_data);
-            if let Err(return_error) = converted_date { return Err(handle_return_error(return_error)); }
+            if let Err(return_error) = converted_date { return Err(handle_parameter_return_error(return_error, "date")); }
 
             date_preference = date::DatePreference::Single(converted_date.unwrap());
         },
         DateFormatType::Multiple => {
 
-            let (rust_start_date, rust_end_date) = parse_dates(&date_data);
+            let (rust_start_date, rust_end_date) = parse_dates(date_data)?;
+
+            let converted_dates = date::DateRange::from(rust_start_date, rust_end_date);
+            if let Err(return_error) = converted_dates { return Err(handle_parameter_return_error(return_error, "date")); }
+
+            date_preference = date::DatePreference::Multiple(converted_dates.unwrap());
+        },
+        DateFormatType::Shorthand => {
+
+            let (rust_start_date, rust_end_date) = expand_shorthand(date_data)?;
 
             let converted_dates = date::DateRange::from(&rust_start_date, &rust_end_date);
-            if let Err(return_error) = converted_dates { return Err(handle_return_error(return_error)); }
+            if let Err(return_error) = converted_dates { return Err(handle_parameter_return_error(return_error, "date")); }
 
             date_preference = date::DatePreference::Multiple(converted_dates.unwrap());
         }
@@ -185,27 +492,331 @@ pub(crate) fn generate_date_preference(date_data: &str) -> Result<DatePreference
     Ok(date_preference)
 }
 
+/// generates a date preference for each date range separated by semicolons, e.g. 
+/// "01-01-2020,31-03-2020;01-01-2021,31-03-2021".
+///
+/// A date data without any semicolon generates only one date preference. An empty date data uses the date of the 
+/// session if it is set via [`tcmb_evds_c_set_session_defaults`](crate::tcmb_evds_c_set_session_defaults).
+///
+/// A date range written as [`FULL_HISTORY_DATE`](settings::FULL_HISTORY_DATE) is the full history ending today, which 
+/// the data requests narrow to the first date of the series.
+pub(crate) fn generate_date_preferences(date_data: &str) -> Result<Vec<DatePreference>, TcmbEvdsResult> {
+
+    let session_date = match date_data.trim().is_empty() {
+        true => settings::get_settings().session_date.clone(),
+        false => None,
+    };
+
+    let date_data = match session_date {
+        Some(session_date) => session_date.resolve(settings::get_today()),
+        None => date_data.to_string(),
+    };
+
+    let date_data = normalize_date_separators(date_data, settings::get_settings().strict_date_separators);
+
+    let mut date_preferences = Vec::new();
+
+    for date_range in date_data.split(';') {
+        let date_range = date_range.trim();
+
+        if date_range.is_empty() {
+            return Err(
+                TcmbEvdsResult::generate_parameter_result(
+                    "Error: One of the date ranges separated by semicolons is empty.".to_string(),
+                    ReturnErrorC::UndefinedDateDataFormat,
+                    "date",
+                )
+            );
+        }
+
+        if date_range.eq_ignore_ascii_case(settings::FULL_HISTORY_DATE) {
+            let full_history = settings::SessionDate::FullHistory.resolve(settings::get_today());
+
+            date_preferences.push(generate_date_preference(&full_history)?);
+
+            continue;
+        }
+
+        date_preferences.push(generate_date_preference(date_range)?);
+    }
+
+    Ok(date_preferences)
+}
+
+/// replaces the dots and the slashes separating the days, the months and the years with dashes, e.g. "13.12.2011" and
+/// "13/12/2011" with "13-12-2011", as Turkish users habitually write dates with dots. The date data is kept as it is 
+/// when the separators are strict, namely strict date separators are enabled via 
+/// [`tcmb_evds_c_set_strict_date_separators`](crate::tcmb_evds_c_set_strict_date_separators), so such a date data 
+/// fails the validation.
+pub(crate) fn normalize_date_separators(date_data: String, is_strict: bool) -> String {
+
+    if is_strict { return date_data; }
+
+    if !date_data.contains(&['.', '/'][..]) { return date_data; }
+
+    date_data.replace(&['.', '/'][..], "-")
+}
+
+/// is the kind of dates that a parameter of a function needs.
+#[cfg(any(feature = "currency", feature = "parse", feature = "export"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum DateKind {
+    /// is a single date, e.g. the base date of an index.
+    // the only single date is the base date of a basket, which is given by the `currency` feature.
+    #[cfg_attr(not(feature = "currency"), allow(dead_code))]
+    Single,
+    /// is a date range, e.g. the dates of a series which is deflated or exported.
+    Range,
+}
+
+/// checks that the date preferences are of the kind the parameter needs when strict dates are enabled via
+/// [`tcmb_evds_c_set_strict_dates`](crate::tcmb_evds_c_set_strict_dates). Otherwise, a single date is used as a range 
+/// starting and ending at it, so the date preferences are given back as they are.
+///
+/// # Error
+///
+/// This function returns `MultipleDateExpected` error for a single date given for a range and `SingleDateExpected` for
+/// a range given for a single date when strict dates are enabled.
+#[cfg(any(feature = "currency", feature = "parse", feature = "export"))]
+pub(crate) fn check_date_kind(
+    date_preferences: Vec<DatePreference>, 
+    date_kind: DateKind, 
+    parameter_name: &str
+) -> Result<Vec<DatePreference>, TcmbEvdsResult> {
+
+    if !settings::get_settings().strict_dates { return Ok(date_preferences); }
+
+    match find_date_kind_error(&date_preferences, date_kind) {
+        Some(return_error) => Err(handle_parameter_return_error(return_error, parameter_name)),
+        None => Ok(date_preferences),
+    }
+}
+
+#[cfg(any(feature = "currency", feature = "parse", feature = "export"))]
+fn find_date_kind_error(date_preferences: &[DatePreference], date_kind: DateKind) -> Option<ReturnError> {
+    date_preferences
+        .iter()
+        .map(|date_preference| match date_kind {
+            DateKind::Single => date_preference.is_single(),
+            DateKind::Range => date_preference.is_multiple(),
+        })
+        .find_map(Result::err)
+}
+
+/// checks that none of the date ranges is longer than the maximum date span set via 
+/// [`tcmb_evds_c_set_max_date_span`](crate::tcmb_evds_c_set_max_date_span), so that a daily series is not pulled for 
+/// decades by accident. The data requested in a frequency coarser than business days is not checked since it has few 
+/// observations even in long date ranges.
+///
+/// # Error
+///
+/// This function returns `DateRangeTooLarge` error when a date range is longer than the maximum date span.
+pub(crate) fn check_date_span(
+    date_preferences: Vec<DatePreference>, 
+    data_frequency: Option<DataFrequency>
+) -> Result<Vec<DatePreference>, TcmbEvdsResult> {
+
+    let max_span_days = settings::get_settings().max_date_span_days;
+
+    let is_daily = matches!(data_frequency, None | Some(DataFrequency::Daily) | Some(DataFrequency::Business));
+
+    if max_span_days == 0 || !is_daily { return Ok(date_preferences); }
+
+    match find_too_large_span(&date_preferences, max_span_days) {
+        Some(return_error) => Err(handle_parameter_return_error(return_error, "date")),
+        None => Ok(date_preferences),
+    }
+}
+
+fn find_too_large_span(date_preferences: &[DatePreference], max_span_days: u32) -> Option<ReturnError> {
+
+    let is_too_large = date_preferences
+        .iter()
+        .any(|date_preference| date_preference.count_days().is_some_and(|days| days > i64::from(max_span_days)));
+
+    match is_too_large {
+        true => Some(ReturnError::DateRangeTooLarge(max_span_days)),
+        false => None,
+    }
+}
+
+/// makes the request for each date preference and merges the responses in the given order.
+///
+/// The first error stops the requests and is returned.
+pub(crate) fn request_for_each_date_preference<F>(
+    date_preferences: Vec<DatePreference>, 
+    evds: &common::Evds, 
+    mut request: F,
+) -> Result<String, ReturnError> 
+where 
+    F: FnMut(DatePreference) -> Result<String, ReturnError>
+{
+
+    let mut responses = Vec::with_capacity(date_preferences.len());
+
+    for date_preference in date_preferences {
+        responses.push(request(date_preference)?);
+    }
+
+    merge_responses(responses, evds.get_return_format())
+}
+
+/// reads a C enum received as an integer, e.g. the `return_format` argument of a call, into its option.
+///
+/// Out of range values fall back to the default options unless strict enums are enabled via 
+/// [`tcmb_evds_c_set_strict_enums`](crate::tcmb_evds_c_set_strict_enums).
+///
+/// # Error
+///
+/// This function returns `InvalidEnumValue` error for out of range values when strict enums are enabled.
+pub(crate) fn read_enum<E: ReadingCEnum>(value: c_uint) -> Result<E, ReturnError> {
+
+    if let Some(option) = E::from_value(value) { return Ok(option); }
+
+    match settings::get_settings().strict_enums {
+        true => Err(ReturnError::InvalidEnumValue),
+        false => Ok(E::FALLBACK),
+    }
+}
+
+/// reads a C enum received as an integer via [`read_enum`] and converts it into the related Rust enum.
+///
+/// # Error
+///
+/// This function returns `InvalidEnumValue` error for out of range values when strict enums are enabled.
+pub(crate) fn convert_enum<C, T>(value: c_uint) -> Result<T, ReturnError>
+where
+    C: ReadingCEnum + ConvertingToRustEnum<T>,
+    T: EnumSpecific,
+{
+    read_enum::<C>(value).map(|option| option.convert())
+}
+
+/// generates an Evds object after checking the validity of the api key via EVDS unless the key is verified for the 
+/// prepared request the thread is executing.
 pub(crate) fn generate_evds(api_key: TcmbEvdsInput, return_format: TcmbEvdsReturnFormat) -> Result<common::Evds, TcmbEvdsResult> {
+    generate_evds_with(api_key, return_format, |api_key| {
+        let is_verified = VERIFIED_API_KEY.with(|verified_api_key| {
+            verified_api_key.borrow().as_deref() == Some(api_key.as_str())
+        });
+
+        match is_verified {
+            true => common::ApiKey::from_unverified(api_key),
+            false => common::ApiKey::from(api_key),
+        }
+    })
+}
+
+/// reads the return format and checks the format of the api key without verifying the key via EVDS, so that their 
+/// errors are collected together with the errors of the other parameters before any request is made. The read return 
+/// format is returned, which is its default option when its error is recorded.
+pub(crate) fn check_evds_parameters(
+    parameter_errors: &mut ParameterErrors,
+    api_key: &TcmbEvdsInput,
+    return_format: c_uint,
+) -> TcmbEvdsReturnFormat {
+
+    let return_format = parameter_errors.read_enum("return_format", return_format);
+
+    if let Some(rust_api_key) = parameter_errors.read_input(api_key, "api_key") {
+        parameter_errors.check("api_key", common::ApiKey::from_unverified(rust_api_key));
+    }
+
+    return_format
+}
+
+/// checks that the text of a data series, a data group or an api key is not empty and does not contain one of the 
+/// characters separating query parameters.
+pub(crate) fn check_parameter_text(text: &str) -> Result<(), ReturnError> {
+
+    if text.is_empty() { return Err(ReturnError::EmptyParameter); }
+
+    check_query_value(text)
+}
+
+/// runs the body with the api key treated as verified, so that the calls made by a prepared request do not check 
+/// the validity of the same key via EVDS again.
+pub(crate) fn with_verified_api_key<T, F>(api_key: &str, body: F) -> T
+where
+    F: FnOnce() -> T
+{
+
+    VERIFIED_API_KEY.with(|verified_api_key| *verified_api_key.borrow_mut() = Some(api_key.to_string()));
+
+    let value = body();
+
+    VERIFIED_API_KEY.with(|verified_api_key| *verified_api_key.borrow_mut() = None);
+
+    value
+}
+
+/// generates an Evds object without checking the validity of the api key via EVDS. It is only used to audit the urls.
+pub(crate) fn generate_unverified_evds(
+    api_key: TcmbEvdsInput, 
+    return_format: TcmbEvdsReturnFormat,
+) -> Result<common::Evds, TcmbEvdsResult> {
+    generate_evds_with(api_key, return_format, common::ApiKey::from_unverified)
+}
+
+fn generate_evds_with<F>(
+    api_key: TcmbEvdsInput, 
+    return_format: TcmbEvdsReturnFormat, 
+    create_api_key: F,
+) -> Result<common::Evds, TcmbEvdsResult> 
+where 
+    F: Fn(String) -> Result<common::ApiKey, ReturnError>
+{
 
     let (rust_api_key, api_key_error_state) = api_key.get_input("api_key");
+
+    let return_format = match return_format {
+        TcmbEvdsReturnFormat::SessionFormat => settings::get_settings().session_return_format,
+        return_format => return_format,
+    };
+
     let rust_return_format = return_format.convert();
 
     if api_key_error_state {
         return Err(
-            TcmbEvdsResult::generate_result(
+            TcmbEvdsResult::generate_parameter_result(
                 rust_api_key,
                 ReturnErrorC::ParameterError,
+                "api_key",
             )
         );
     }
 
-    let handled_api_key = common::ApiKey::from(rust_api_key);
-    if let Err(return_error) = handled_api_key { return Err(handle_return_error(return_error)); }
+    // an empty api key is replaced with the next key of the key pool if the pool is used.
+    let rust_api_key = match rust_api_key.trim().is_empty() {
+        true => key_pool::get_key_pool().select_key(key_pool::now_seconds()).unwrap_or(rust_api_key),
+        false => rust_api_key,
+    };
+
+    let handled_api_key = create_api_key(rust_api_key);
+    if let Err(return_error) = handled_api_key { return Err(handle_parameter_return_error(return_error, "api_key")); }
     let valid_api_key = handled_api_key.unwrap();
 
     Ok(common::Evds::from(valid_api_key, rust_return_format))
 }
 
+/// generates the masked url of each date preference, one url per line, to audit what would be requested.
+pub(crate) fn audit_each_date_preference<F>(
+    date_preferences: Vec<DatePreference>, 
+    mut generate_url: F,
+) -> Result<String, ReturnError> 
+where 
+    F: FnMut(DatePreference) -> Result<UrlBuilder, ReturnError>
+{
+
+    let mut urls = Vec::with_capacity(date_preferences.len());
+
+    for date_preference in date_preferences {
+        urls.push(generate_url(date_preference)?.build_masked());
+    }
+
+    Ok(urls.join("\n"))
+}
+
 pub(crate) fn handle_request(request_response: Result<String, ReturnError>) -> TcmbEvdsResult {
 
     if let Err(return_error) = request_response { return handle_return_error(return_error); } 
@@ -217,13 +828,53 @@ pub(crate) fn handle_request(request_response: Result<String, ReturnError>) -> T
     TcmbEvdsResult::generate_result(request_result, error_type)
 }
 
+/// runs the post-processors set for the session via 
+/// [`tcmb_evds_c_set_post_processors`](crate::tcmb_evds_c_set_post_processors) on the data response. The response 
+/// is returned as it is when it is an error or no post-processor is set.
+#[cfg(feature = "parse")]
+pub(crate) fn post_process_response(
+    response: Result<String, ReturnError>,
+    evds: &common::Evds
+) -> Result<String, ReturnError> {
+
+    // the post-processors are copied so that the settings are not locked while the headers and units are requested.
+    let post_processors = settings::get_settings().post_processors.clone();
+
+    if post_processors.is_empty() { return response; }
+
+    run_post_processors(response?, &post_processors, evds)
+}
+
+/// returns the data response as it is, since the post-processors cannot be set without the `parse` feature.
+#[cfg(not(feature = "parse"))]
+pub(crate) fn post_process_response(
+    response: Result<String, ReturnError>,
+    _evds: &common::Evds
+) -> Result<String, ReturnError> {
+    response
+}
+
+/// generates the result of the response after calling the response hook. The response is converted into ASCII 
+/// characters according to the `ascii_mode` argument unless the process wide ascii mode forces it on or off.
+///
+/// A stale response served from the cache after a failed request has the error of the request as its detail.
 pub(crate) fn return_response(mut response: Result<String, ReturnError>, ascii_mode: bool) -> TcmbEvdsResult {
 
-    if !ascii_mode || response.is_err() { return handle_request(response); } 
+    #[cfg(feature = "cache")]
+    let stale_error = cache::take_stale_error().filter(|_| response.is_ok());
+    #[cfg(not(feature = "cache"))]
+    let stale_error: Option<String> = None;
+
+    #[cfg(feature = "parse")]
+    if let Ok(response) = &response { hook_entities::run_response_hook(response); }
 
-    if let Ok(response) = &mut response { convert_to_ascii(response); }
+    let ascii_mode = settings::get_settings().ascii_mode.resolve(ascii_mode);
+
+    if ascii_mode {
+        if let Ok(response) = &mut response { convert_to_ascii(response); }
+    }
 
-    handle_request(response)
+    handle_request(response).with_detail(stale_error.map(|stale_error| format!("Stale: {}", stale_error)))
 }
 
 
@@ -237,6 +888,126 @@ mod tests {
 
         convert_to_ascii(&mut string);
 
-        println!("{}", string);
+        assert_eq!("IouGOU *this *this and *this", string);
+    }
+
+    #[test]
+    fn should_convert_strictly() {
+        use crate::evds_currency::CurrencyCode;
+        use self::advanced_entities::TcmbEvdsFormula;
+
+        assert!(matches!("qar".convert_strictly(), Ok(CurrencyCode::Qar)));
+        assert!(matches!("UsD".convert_strictly(), Ok(CurrencyCode::Usd)));
+
+        let unknown_currency: Result<CurrencyCode, ReturnError> = "xyz".convert_strictly();
+        assert!(matches!(unknown_currency, Err(ReturnError::InvalidEnumValue)));
+
+        assert!(TcmbEvdsFormula::MovingSum.convert_strictly().is_ok());
+        assert!(TcmbEvdsReturnFormat::Xml.convert_strictly().is_ok());
+    }
+
+    #[test]
+    fn should_read_enums() {
+        use std::fmt::Debug;
+
+        use self::advanced_entities::*;
+        #[cfg(feature = "currency")]
+        use self::currency_entities::*;
+        #[cfg(feature = "parse")]
+        use self::dataset_entities::*;
+        use self::error_handling::TcmbEvdsErrorFormat;
+        use self::key_entities::TcmbEvdsKeyRotation;
+        #[cfg(feature = "parse")]
+        use self::pipeline_entities::TcmbEvdsPostProcessor;
+        use self::recording_entities::TcmbEvdsRecordingMode;
+        use self::scheduler_entities::TcmbEvdsPriority;
+        use self::session_entities::TcmbEvdsDatePolicy;
+
+        // the options must be listed in the order of their values, so that each value is read as its own option.
+        fn check_options<E: ReadingCEnum + Debug + PartialEq>(to_value: fn(E) -> c_uint) {
+            for (value, option) in E::OPTIONS.iter().enumerate() {
+                assert_eq!(value as c_uint, to_value(*option));
+                assert_eq!(Some(*option), E::from_value(value as c_uint));
+            }
+
+            assert_eq!(None, E::from_value(E::OPTIONS.len() as c_uint));
+            assert_eq!(None, E::from_value(c_uint::MAX));
+        }
+
+        check_options(|option: TcmbEvdsReturnFormat| option as c_uint);
+        check_options(|option: TcmbEvdsAsciiMode| option as c_uint);
+        check_options(|option: TcmbEvdsHeaderLanguage| option as c_uint);
+        check_options(|option: TcmbEvdsAggregationType| option as c_uint);
+        check_options(|option: TcmbEvdsFormula| option as c_uint);
+        check_options(|option: TcmbEvdsDataFrequency| option as c_uint);
+        check_options(|option: TcmbEvdsHugeRequestPolicy| option as c_uint);
+        #[cfg(feature = "currency")]
+        check_options(|option: TcmbEvdsCurrencyCode| option as c_uint);
+        #[cfg(feature = "currency")]
+        check_options(|option: TcmbEvdsExchangeSide| option as c_uint);
+        #[cfg(feature = "parse")]
+        check_options(|option: TcmbEvdsLayout| option as c_uint);
+        #[cfg(feature = "parse")]
+        check_options(|option: TcmbEvdsJsonBackend| option as c_uint);
+        #[cfg(feature = "parse")]
+        check_options(|option: TcmbEvdsOutlierMethod| option as c_uint);
+        check_options(|option: TcmbEvdsErrorFormat| option as c_uint);
+        check_options(|option: TcmbEvdsKeyRotation| option as c_uint);
+        #[cfg(feature = "parse")]
+        check_options(|option: TcmbEvdsPostProcessor| option as c_uint);
+        check_options(|option: TcmbEvdsRecordingMode| option as c_uint);
+        check_options(|option: TcmbEvdsPriority| option as c_uint);
+        check_options(|option: TcmbEvdsDatePolicy| option as c_uint);
+
+        assert!(matches!(read_enum(TcmbEvdsFormula::MovingSum as c_uint), Ok(TcmbEvdsFormula::MovingSum)));
+        assert!(matches!(read_enum(42), Ok(TcmbEvdsFormula::Level)));
+    }
+
+    #[test]
+    #[cfg(any(feature = "currency", feature = "parse", feature = "export"))]
+    fn should_find_date_kind_errors() {
+        let date_preferences = generate_date_preferences("01-01-2020;01-01-2020,31-12-2020").ok().unwrap();
+
+        assert!(matches!(
+            find_date_kind_error(&date_preferences, DateKind::Range), 
+            Some(ReturnError::MultipleDateExpected)
+        ));
+        assert!(matches!(
+            find_date_kind_error(&date_preferences[1..], DateKind::Single), 
+            Some(ReturnError::SingleDateExpected)
+        ));
+        assert!(find_date_kind_error(&date_preferences[..1], DateKind::Single).is_none());
+        assert!(find_date_kind_error(&generate_date_preferences("2020").ok().unwrap(), DateKind::Range).is_none());
+    }
+
+    #[test]
+    fn should_normalize_date_separators() {
+        assert_eq!("13-12-2011", normalize_date_separators("13.12.2011".to_string(), false));
+        assert_eq!("13-12-2011, 13-12-2021", normalize_date_separators("13/12/2011, 13.12.2021".to_string(), false));
+        assert_eq!(1, generate_date_preferences("01.01.2020,31/12/2020").ok().unwrap().len());
+        assert_eq!("13.12.2011", normalize_date_separators("13.12.2011".to_string(), true));
+    }
+
+    #[test]
+    fn should_find_too_large_spans() {
+        let date_preferences = generate_date_preferences("01-01-2020,31-12-2020;01-01-1950,31-12-2020").ok().unwrap();
+
+        assert_eq!(Some(365), date_preferences[0].count_days());
+        assert!(find_too_large_span(&date_preferences[..1], 366).is_none());
+        assert!(matches!(
+            find_too_large_span(&date_preferences, 3653), 
+            Some(ReturnError::DateRangeTooLarge(3653))
+        ));
+    }
+
+    #[test]
+    fn should_skip_verification_of_verified_api_key() {
+        let api_key = std::ffi::CString::new("users_key").unwrap();
+        let api_key_input = TcmbEvdsInput { input_ptr: api_key.as_ptr(), string_capacity: 9 };
+
+        let evds = with_verified_api_key("users_key", || generate_evds(api_key_input, TcmbEvdsReturnFormat::Csv));
+
+        assert_eq!("key=users_key", evds.ok().unwrap().get_api_key_as_url());
+        assert!(VERIFIED_API_KEY.with(|verified_api_key| verified_api_key.borrow().is_none()));
     }
 }
@@ -0,0 +1,118 @@
+use libc::c_uint;
+
+use crate::dataset::header::HeaderLanguage;
+use crate::dataset::precision::MAX_DECIMAL_PLACES;
+use crate::dataset::unit::SeriesUnit;
+use crate::evds_basic::post_processing::PostProcessor;
+use crate::traits::ReadingCEnum;
+
+
+/// supplies a built-in step to the post-processing pipeline set via
+/// [`tcmb_evds_c_set_post_processors`](crate::tcmb_evds_c_set_post_processors).
+///
+/// `AsciiPostProcessor` converts the response into ASCII characters. `EnglishHeadersPostProcessor` and
+/// `TurkishHeadersPostProcessor` rename the series codes in the headers with the names of the series.
+/// `DecimalPostProcessor` rewrites the values in their shortest decimal form. `UnitRescalePostProcessor` rescales the
+/// values into the target unit of the pipeline. `RoundingPostProcessor` rounds the values to the decimal places of the
+/// pipeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsPostProcessor {
+    AsciiPostProcessor,
+    EnglishHeadersPostProcessor,
+    TurkishHeadersPostProcessor,
+    DecimalPostProcessor,
+    UnitRescalePostProcessor,
+    RoundingPostProcessor,
+}
+
+impl TcmbEvdsPostProcessor {
+    /// converts the option into the post-processor rescaling into the given unit or rounding to the given decimal 
+    /// places. None is returned for `UnitRescalePostProcessor` option without a target unit and for 
+    /// `RoundingPostProcessor` option with more than [`MAX_DECIMAL_PLACES`].
+    pub(crate) fn to_post_processor(
+        self,
+        target_unit: Option<&SeriesUnit>,
+        decimal_places: u32
+    ) -> Option<PostProcessor> {
+
+        match self {
+            TcmbEvdsPostProcessor::AsciiPostProcessor => Some(PostProcessor::Ascii),
+            TcmbEvdsPostProcessor::EnglishHeadersPostProcessor => {
+                Some(PostProcessor::HeaderTranslation(HeaderLanguage::English))
+            },
+            TcmbEvdsPostProcessor::TurkishHeadersPostProcessor => {
+                Some(PostProcessor::HeaderTranslation(HeaderLanguage::Turkish))
+            },
+            TcmbEvdsPostProcessor::DecimalPostProcessor => Some(PostProcessor::DecimalNormalization),
+            TcmbEvdsPostProcessor::UnitRescalePostProcessor => target_unit.cloned().map(PostProcessor::UnitRescale),
+            TcmbEvdsPostProcessor::RoundingPostProcessor => {
+                (decimal_places <= MAX_DECIMAL_PLACES).then_some(PostProcessor::Rounding(decimal_places))
+            },
+        }
+    }
+}
+
+impl ReadingCEnum for TcmbEvdsPostProcessor {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsPostProcessor::AsciiPostProcessor,
+        TcmbEvdsPostProcessor::EnglishHeadersPostProcessor,
+        TcmbEvdsPostProcessor::TurkishHeadersPostProcessor,
+        TcmbEvdsPostProcessor::DecimalPostProcessor,
+        TcmbEvdsPostProcessor::UnitRescalePostProcessor,
+        TcmbEvdsPostProcessor::RoundingPostProcessor,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsPostProcessor::AsciiPostProcessor;
+}
+
+/// reads the post-processors in the given order from their C values. None is returned if a value is not an option of
+/// [`TcmbEvdsPostProcessor`] or it cannot be converted into a post-processor, see
+/// [`TcmbEvdsPostProcessor::to_post_processor`].
+pub(crate) fn read_post_processors(
+    c_post_processors: &[c_uint],
+    target_unit: Option<&SeriesUnit>,
+    decimal_places: u32
+) -> Option<Vec<PostProcessor>> {
+
+    c_post_processors
+        .iter()
+        .map(|post_processor| {
+            let post_processor = TcmbEvdsPostProcessor::from_value(*post_processor)?;
+
+            post_processor.to_post_processor(target_unit, decimal_places)
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use TcmbEvdsPostProcessor::*;
+
+    #[test]
+    fn should_read_post_processors_in_order() {
+        let target_unit = SeriesUnit::parse("Thousand TRY").unwrap();
+
+        let post_processors =
+            [DecimalPostProcessor, UnitRescalePostProcessor, AsciiPostProcessor, RoundingPostProcessor];
+        let post_processors = post_processors.map(|post_processor| post_processor as c_uint);
+        let unknown_post_processors = [DecimalPostProcessor as c_uint, 6];
+
+        assert_eq!(None, read_post_processors(&unknown_post_processors, Some(&target_unit), 2));
+        assert_eq!(None, read_post_processors(&post_processors, None, 2));
+        assert_eq!(None, read_post_processors(&post_processors, Some(&target_unit), 16));
+
+        let expected_post_processors = vec![
+            PostProcessor::DecimalNormalization,
+            PostProcessor::UnitRescale(target_unit.clone()),
+            PostProcessor::Ascii,
+            PostProcessor::Rounding(2),
+        ];
+
+        assert_eq!(Some(expected_post_processors), read_post_processors(&post_processors, Some(&target_unit), 2));
+        assert_eq!(Some(vec![]), read_post_processors(&[], None, 0));
+    }
+}
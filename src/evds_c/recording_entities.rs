@@ -0,0 +1,37 @@
+use crate::recording::RecordingMode;
+use crate::traits::ReadingCEnum;
+
+
+/// specifies whether the responses of EVDS are recorded or replayed via
+/// [`tcmb_evds_c_set_recording`](crate::tcmb_evds_c_set_recording).
+///
+/// `RecordResponses` saves every request and response pair into the given directory, and `ReplayResponses` serves the
+/// later calls from those recordings without requesting EVDS. `RecordingOff` makes the calls request EVDS as usual.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsRecordingMode {
+    RecordingOff,
+    RecordResponses,
+    ReplayResponses,
+}
+
+impl TcmbEvdsRecordingMode {
+    /// returns None for `RecordingOff` since the responses are neither recorded nor replayed.
+    pub(crate) fn to_mode(self) -> Option<RecordingMode> {
+        match self {
+            TcmbEvdsRecordingMode::RecordingOff => None,
+            TcmbEvdsRecordingMode::RecordResponses => Some(RecordingMode::Record),
+            TcmbEvdsRecordingMode::ReplayResponses => Some(RecordingMode::Replay),
+        }
+    }
+}
+
+impl ReadingCEnum for TcmbEvdsRecordingMode {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsRecordingMode::RecordingOff,
+        TcmbEvdsRecordingMode::RecordResponses,
+        TcmbEvdsRecordingMode::ReplayResponses,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsRecordingMode::RecordingOff;
+}
@@ -0,0 +1,393 @@
+use std::ffi::CString;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use libc::{c_uint, c_ulong};
+
+use super::advanced_entities::{TcmbEvdsAggregationType, TcmbEvdsDataFrequency, TcmbEvdsFormula};
+use super::common_entities::{TcmbEvdsInput, TcmbEvdsReturnFormat};
+use super::data_series::parse_multiple_series;
+use super::error_handling::{ParameterErrors, ReturnErrorC, shield_or};
+use super::{check_date_span, check_parameter_text, generate_date_preferences};
+use crate::error::ReturnError;
+use crate::evds_currency::frequency_formulas::DataFrequency;
+use crate::traits::{ConvertingToRustEnum, ReadingCEnum};
+
+
+/// is an opaque request built via the `tcmb_evds_c_request_set_*` functions and made via
+/// [`tcmb_evds_c_execute`](crate::tcmb_evds_c_execute).
+///
+/// The parameters that are not set keep their defaults: an empty date and the `SessionFormat` return format, which use
+/// the session defaults, no ascii conversion, and the first options of the frequency formulas. Since the layout of
+/// the request is not a part of the C ABI, new parameters are added as new setter functions without breaking the
+/// existing callers. The enums are kept as the integers given by C until the request is checked or made. The request 
+/// must be released via [`tcmb_evds_c_request_free`].
+pub struct TcmbEvdsRequest {
+    pub(crate) series: Option<CString>,
+    pub(crate) data_group: Option<CString>,
+    pub(crate) dates: CString,
+    pub(crate) api_key: CString,
+    pub(crate) return_format: c_uint,
+    pub(crate) ascii_mode: bool,
+    pub(crate) aggregation_type: Option<c_uint>,
+    pub(crate) formula: Option<c_uint>,
+    pub(crate) frequency: Option<c_uint>,
+    /// becomes true after the api key is verified via EVDS, so the later executions do not verify it again.
+    is_api_key_verified: AtomicBool,
+}
+
+impl TcmbEvdsRequest {
+    fn new() -> TcmbEvdsRequest {
+        TcmbEvdsRequest {
+            series: None,
+            data_group: None,
+            dates: CString::default(),
+            api_key: CString::default(),
+            return_format: TcmbEvdsReturnFormat::SessionFormat as c_uint,
+            ascii_mode: false,
+            aggregation_type: None,
+            formula: None,
+            frequency: None,
+            is_api_key_verified: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn is_api_key_verified(&self) -> bool {
+        self.is_api_key_verified.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn mark_api_key_verified(&self) {
+        self.is_api_key_verified.store(true, Ordering::Relaxed);
+    }
+
+    /// checks whether any of the frequency formulas is set, which makes the request an advanced data request.
+    pub(crate) fn has_frequency_formulas(&self) -> bool {
+        self.aggregation_type.is_some() || self.formula.is_some() || self.frequency.is_some()
+    }
+
+    /// runs all client-side checks of the request without making it and collects every error found. No error means 
+    /// that the request is valid up to the checks made via EVDS, e.g. the validity of the api key and the existence of 
+    /// the data series.
+    pub(crate) fn find_problems(&self) -> ParameterErrors {
+
+        let mut errors = ParameterErrors::new();
+
+        let request_error = ReturnErrorC::ParameterError;
+
+        match (&self.series, &self.data_group) {
+            (Some(series), None) if self.has_frequency_formulas() => {
+                errors.check("data_series", parse_multiple_series(&series.to_string_lossy()));
+            },
+            (Some(series), None) => {
+                errors.check("data_series", check_parameter_text(&series.to_string_lossy()));
+            },
+            (None, Some(_)) if self.has_frequency_formulas() => {
+                errors.add(
+                    request_error,
+                    "request",
+                    "Error: Frequency formulas are only supported for data series, not for data groups.".to_string(),
+                );
+            },
+            (None, Some(data_group)) => {
+                errors.check("data_group", check_parameter_text(&data_group.to_string_lossy()));
+            },
+            (Some(_), Some(_)) => {
+                errors.add(
+                    request_error,
+                    "request",
+                    "Error: The request has both a data series and a data group.".to_string(),
+                );
+            },
+            (None, None) => {
+                errors.add(
+                    request_error,
+                    "request",
+                    "Error: The request has neither a data series nor a data group.".to_string(),
+                );
+            },
+        }
+
+        let dates = self.dates.to_string_lossy();
+
+        let data_frequency: Option<DataFrequency> = self.frequency
+            .and_then(TcmbEvdsDataFrequency::from_value)
+            .map(|data_frequency| data_frequency.convert());
+
+        errors.collect("date", || check_date_span(generate_date_preferences(&dates)?, data_frequency));
+
+        errors.check("return_format", check_enum::<TcmbEvdsReturnFormat>(Some(self.return_format)));
+        errors.check("aggregation_type", check_enum::<TcmbEvdsAggregationType>(self.aggregation_type));
+        errors.check("formula", check_enum::<TcmbEvdsFormula>(self.formula));
+        errors.check("data_frequency", check_enum::<TcmbEvdsDataFrequency>(self.frequency));
+        errors.check("api_key", check_parameter_text(&self.api_key.to_string_lossy()));
+
+        errors
+    }
+}
+
+/// checks that the enum value, if set, is one of the values known by this library even when strict enums are disabled.
+fn check_enum<E: ReadingCEnum>(value: Option<c_uint>) -> Result<(), ReturnError> {
+    match value.map(E::from_value) {
+        Some(None) => Err(ReturnError::InvalidEnumValue),
+        _ => Ok(()),
+    }
+}
+
+/// borrows the stored string as an input of the operational functions. The string lives as long as the request.
+pub(crate) fn to_input(text: &CString) -> TcmbEvdsInput {
+    TcmbEvdsInput { input_ptr: text.as_ptr(), string_capacity: text.as_bytes().len() as c_ulong }
+}
+
+/// reads the input into a C string to be kept in the request. None is returned for an invalid input.
+fn read_input(input: &TcmbEvdsInput, parameter_name: &str) -> Option<CString> {
+
+    let (text, error_state) = input.get_input(parameter_name);
+
+    if error_state { return None; }
+
+    // a valid input never contains a NUL character.
+    CString::new(text).ok()
+}
+
+/// applies the setter to the request. False is returned for a NULL request or when the setter fails.
+unsafe fn set<F>(request: *mut TcmbEvdsRequest, setter: F) -> bool
+where
+    F: FnOnce(&mut TcmbEvdsRequest) -> bool
+{
+    shield_or(false, || {
+        match request.as_mut() {
+            Some(request) => setter(request),
+            None => false,
+        }
+    })
+}
+
+
+/// creates an empty request to be released via [`tcmb_evds_c_request_free`].
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsRequest* request = tcmb_evds_c_request_new();
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_request_new() -> *mut TcmbEvdsRequest {
+    shield_or(std::ptr::null_mut(), || Box::into_raw(Box::new(TcmbEvdsRequest::new())))
+}
+
+/// sets the data series of the request, e.g. "TP.DK.USD.S". A data series request cannot have a data group.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_set_series(request: *mut TcmbEvdsRequest, series: TcmbEvdsInput) -> bool {
+    set(request, |request| {
+        request.series = read_input(&series, "data_series");
+        request.series.is_some()
+    })
+}
+
+/// sets the data group of the request, e.g. "bie_yssk". A data group request cannot have a data series.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_set_data_group(
+    request: *mut TcmbEvdsRequest,
+    data_group: TcmbEvdsInput
+) -> bool {
+    set(request, |request| {
+        request.data_group = read_input(&data_group, "data_group");
+        request.data_group.is_some()
+    })
+}
+
+/// sets the dates of the request in any format accepted by the operational functions, e.g. "13-12-2011,13-12-2012".
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_set_dates(request: *mut TcmbEvdsRequest, dates: TcmbEvdsInput) -> bool {
+    set(request, |request| {
+        match read_input(&dates, "date") {
+            Some(dates) => request.dates = dates,
+            None => return false,
+        }
+
+        true
+    })
+}
+
+/// sets the api key of the request. The key is verified via EVDS on the next execution.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_set_api_key(
+    request: *mut TcmbEvdsRequest,
+    api_key: TcmbEvdsInput
+) -> bool {
+    set(request, |request| {
+        match read_input(&api_key, "api_key") {
+            Some(api_key) => request.api_key = api_key,
+            None => return false,
+        }
+
+        request.is_api_key_verified = AtomicBool::new(false);
+
+        true
+    })
+}
+
+/// sets the return format of the request.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_set_return_format(
+    request: *mut TcmbEvdsRequest,
+    return_format: c_uint
+) -> bool {
+    set(request, |request| {
+        request.return_format = return_format;
+        true
+    })
+}
+
+/// sets whether the response of the request is converted into ASCII characters.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_set_ascii_mode(request: *mut TcmbEvdsRequest, ascii_mode: bool) -> bool {
+    set(request, |request| {
+        request.ascii_mode = ascii_mode;
+        true
+    })
+}
+
+/// sets the aggregation type of the request, which makes it an advanced data request.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_set_aggregation_type(
+    request: *mut TcmbEvdsRequest,
+    aggregation_type: c_uint
+) -> bool {
+    set(request, |request| {
+        request.aggregation_type = Some(aggregation_type);
+        true
+    })
+}
+
+/// sets the formula of the request, which makes it an advanced data request.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_set_formula(
+    request: *mut TcmbEvdsRequest,
+    formula: c_uint
+) -> bool {
+    set(request, |request| {
+        request.formula = Some(formula);
+        true
+    })
+}
+
+/// sets the data frequency of the request, which makes it an advanced data request.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_set_frequency(
+    request: *mut TcmbEvdsRequest,
+    frequency: c_uint
+) -> bool {
+    set(request, |request| {
+        request.frequency = Some(frequency);
+        true
+    })
+}
+
+/// releases the request. Passing NULL is allowed and does nothing.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_free(request: *mut TcmbEvdsRequest) {
+
+    shield_or((), || {
+        if request.is_null() { return; }
+
+        drop(Box::from_raw(request));
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evds_c::common_entities::collect_problem;
+
+    #[test]
+    fn should_build_request() {
+        let series = CString::new("TP.DK.USD.S").unwrap();
+        let request = tcmb_evds_c_request_new();
+
+        unsafe {
+            assert!(tcmb_evds_c_request_set_series(request, to_input(&series)));
+            assert!(tcmb_evds_c_request_set_formula(request, TcmbEvdsFormula::PercentageChange as c_uint));
+
+            let built_request = request.as_ref().unwrap();
+
+            assert_eq!(Some("TP.DK.USD.S"), built_request.series.as_ref().and_then(|series| series.to_str().ok()));
+            assert_eq!(TcmbEvdsReturnFormat::SessionFormat as c_uint, built_request.return_format);
+            assert!(built_request.has_frequency_formulas());
+
+            let null_input = TcmbEvdsInput { input_ptr: std::ptr::null(), string_capacity: 0 };
+
+            assert!(!tcmb_evds_c_request_set_dates(request, null_input));
+            assert!(!tcmb_evds_c_request_set_ascii_mode(std::ptr::null_mut(), true));
+
+            tcmb_evds_c_request_free(request);
+        }
+    }
+
+    #[test]
+    fn should_find_every_problem_of_request() {
+        let mut request = TcmbEvdsRequest::new();
+
+        request.series = CString::new("TP.DK.USD.S&").ok();
+        request.dates = CString::new("32-13-2020").unwrap();
+
+        let (_, message) = collect_problem(|| request.find_problems().into_result()).unwrap_err();
+
+        let parameter_names: Vec<&str> =
+            message.lines().skip(1).filter_map(|line| line[2..].split(':').next()).collect();
+
+        assert_eq!(vec!["data_series", "date", "api_key"], parameter_names);
+
+        request.series = CString::new("TP.DK.USD.S").ok();
+        request.dates = CString::new("13-12-2011").unwrap();
+        request.api_key = CString::new("AbCdEfGhIj").unwrap();
+
+        assert!(request.find_problems().into_result().is_ok());
+
+        request.formula = Some(TcmbEvdsFormula::MovingSum as c_uint + 1);
+
+        let (error_type, _) = collect_problem(|| request.find_problems().into_result()).unwrap_err();
+
+        assert!(matches!(error_type, ReturnErrorC::InvalidEnumValue));
+    }
+}
@@ -0,0 +1,30 @@
+use crate::scheduler::Priority;
+use crate::traits::ReadingCEnum;
+
+
+/// specifies whether the requests of the calling thread are made right away or give way to the others via
+/// [`tcmb_evds_c_set_thread_priority`](crate::tcmb_evds_c_set_thread_priority).
+///
+/// `InteractivePriority` is the default of each thread. The requests of a thread with `BulkPriority` wait while an
+/// interactive request is being made, and so do the requests of the batch operations, e.g. exports and warm-ups.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsPriority {
+    InteractivePriority,
+    BulkPriority,
+}
+
+impl TcmbEvdsPriority {
+    pub(crate) fn to_priority(self) -> Priority {
+        match self {
+            TcmbEvdsPriority::InteractivePriority => Priority::Interactive,
+            TcmbEvdsPriority::BulkPriority => Priority::Bulk,
+        }
+    }
+}
+
+impl ReadingCEnum for TcmbEvdsPriority {
+    const OPTIONS: &'static [Self] = &[TcmbEvdsPriority::InteractivePriority, TcmbEvdsPriority::BulkPriority];
+
+    const FALLBACK: Self = TcmbEvdsPriority::InteractivePriority;
+}
@@ -0,0 +1,52 @@
+use libc::c_uint;
+
+use super::common_entities::TcmbEvdsInput;
+use crate::traits::ReadingCEnum;
+
+
+/// contains the defaults of the session set via
+/// [`tcmb_evds_c_set_session_defaults`](crate::tcmb_evds_c_set_session_defaults).
+///
+/// The calls given an empty date use `date` if it is not empty, or else the range of `trailing_days` ending today if
+/// it is not zero, or else the range of `date_policy`. The calls given `SessionFormat` use `return_format`, and 
+/// `ascii_mode` is applied to all calls like [`tcmb_evds_c_set_ascii_mode`](crate::tcmb_evds_c_set_ascii_mode). The 
+/// enums are held as integers, so that the values assigned by C are checked before they are read.
+#[repr(C)]
+pub struct TcmbEvdsSessionDefaults {
+    /// is a date in any format accepted by the calls, e.g. "2023" or "01-01-2020,31-12-2020", or an empty string.
+    pub date: TcmbEvdsInput,
+    /// is the number of days ending today used when `date` is empty, e.g. 365 for the last year.
+    pub trailing_days: c_uint,
+    /// is a `TcmbEvdsDatePolicy` option giving the range used when `date` is empty and `trailing_days` is zero.
+    pub date_policy: c_uint,
+    /// is a `TcmbEvdsReturnFormat` option.
+    pub return_format: c_uint,
+    /// is a `TcmbEvdsAsciiMode` option.
+    pub ascii_mode: c_uint,
+}
+
+/// specifies the date range used by the calls given an empty date when the session has neither a date nor trailing
+/// days.
+///
+/// `NoDatePolicy` keeps the empty dates as errors. `LastThirtyDays` is the thirty days ending today, `YearToDate` is
+/// the range from the first day of this year to today, and `FullHistory` is the range from 01-01-1950, before the
+/// first observations of EVDS, to today. The ranges move forward as the days pass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum TcmbEvdsDatePolicy {
+    NoDatePolicy,
+    LastThirtyDays,
+    YearToDate,
+    FullHistory,
+}
+
+impl ReadingCEnum for TcmbEvdsDatePolicy {
+    const OPTIONS: &'static [Self] = &[
+        TcmbEvdsDatePolicy::NoDatePolicy,
+        TcmbEvdsDatePolicy::LastThirtyDays,
+        TcmbEvdsDatePolicy::YearToDate,
+        TcmbEvdsDatePolicy::FullHistory,
+    ];
+
+    const FALLBACK: Self = TcmbEvdsDatePolicy::NoDatePolicy;
+}
@@ -0,0 +1,146 @@
+use std::sync::{Mutex, MutexGuard};
+
+use super::advanced_entities::TcmbEvdsHugeRequestPolicy;
+use super::common_entities::{TcmbEvdsAsciiMode, TcmbEvdsReturnFormat};
+#[cfg(feature = "parse")]
+use super::dataset_entities::TcmbEvdsJsonBackend;
+use super::error_handling::TcmbEvdsErrorFormat;
+
+use crate::date::calendar::{CalendarDate, ISTANBUL_UTC_OFFSET_MINUTES};
+#[cfg(feature = "parse")]
+use crate::evds_basic::post_processing::PostProcessor;
+
+
+/// contains process wide preferences that change how the FFI functions shape their results.
+///
+/// The preferences are changed via dedicated FFI functions and read by the result generation part of the module.
+pub(crate) struct Settings {
+    pub(crate) error_format: TcmbEvdsErrorFormat,
+    /// is the offset from UTC in minutes used to find today. It is Europe/Istanbul unless overridden.
+    pub(crate) today_utc_offset_minutes: i32,
+    /// makes out of range enums and unknown codes return an error instead of falling back to the default options.
+    pub(crate) strict_enums: bool,
+    /// is the limit of the live bytes handed to C after which the requests fail fast. Zero means no limit.
+    pub(crate) memory_budget_bytes: usize,
+    /// overrides the `ascii_mode` argument of the calls unless it is the default.
+    pub(crate) ascii_mode: TcmbEvdsAsciiMode,
+    /// makes JSON data responses fail with `UnexpectedSchema` error when they do not match the schema of EVDS.
+    pub(crate) strict_schema: bool,
+    /// is the date used when a call is given an empty date.
+    pub(crate) session_date: Option<SessionDate>,
+    /// is the return format used when a call is given `SessionFormat`.
+    pub(crate) session_return_format: TcmbEvdsReturnFormat,
+    /// makes the failed requests return their cached responses, even the expired ones, flagged as stale.
+    #[cfg(feature = "cache")]
+    pub(crate) stale_if_error: bool,
+    /// makes the functions needing date ranges reject single dates and the ones needing single dates reject ranges.
+    pub(crate) strict_dates: bool,
+    /// makes the date data written with dots or slashes, e.g. "13.12.2011", fail instead of being read with dashes.
+    pub(crate) strict_date_separators: bool,
+    /// is the longest date range of a data request in days. Zero means no limit.
+    pub(crate) max_date_span_days: u32,
+    /// is what happens to the data requests whose estimated observations are more than `max_estimated_observations`.
+    pub(crate) huge_request_policy: TcmbEvdsHugeRequestPolicy,
+    pub(crate) max_estimated_observations: u64,
+    /// is the parser of the JSON responses.
+    #[cfg(feature = "parse")]
+    pub(crate) json_backend: TcmbEvdsJsonBackend,
+    /// are the steps run in order on each data response of the session.
+    #[cfg(feature = "parse")]
+    pub(crate) post_processors: Vec<PostProcessor>,
+    /// makes the CSV written by the library neutralize the text cells that spreadsheet applications run as formulas.
+    pub(crate) csv_injection_protection: bool,
+}
+
+/// is the default date of the session set via 
+/// [`tcmb_evds_c_set_session_defaults`](crate::tcmb_evds_c_set_session_defaults).
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SessionDate {
+    /// is a date text in any format accepted by the calls, e.g. "01-01-2020,31-12-2020".
+    Fixed(String),
+    /// is the given number of days ending today, so the range moves forward as the days pass.
+    Trailing(u32),
+    /// is the range from the first day of the current year to today.
+    YearToDate,
+    /// is the range from [`FULL_HISTORY_START_DATE`] to today.
+    FullHistory,
+}
+
+/// is the start of the full history, which is before the first observations of EVDS.
+pub(crate) const FULL_HISTORY_START_DATE: &str = "01-01-1950";
+
+/// is the date that the calls expand into the full history, e.g. "ALL" or "all".
+pub(crate) const FULL_HISTORY_DATE: &str = "ALL";
+
+static SETTINGS: Mutex<Settings> = Mutex::new(Settings {
+    error_format: TcmbEvdsErrorFormat::PlainTextErrors,
+    today_utc_offset_minutes: ISTANBUL_UTC_OFFSET_MINUTES,
+    strict_enums: false,
+    memory_budget_bytes: 0,
+    ascii_mode: TcmbEvdsAsciiMode::AsciiModeDefault,
+    strict_schema: false,
+    session_date: None,
+    session_return_format: TcmbEvdsReturnFormat::Json,
+    #[cfg(feature = "cache")]
+    stale_if_error: false,
+    strict_dates: false,
+    strict_date_separators: false,
+    max_date_span_days: 0,
+    huge_request_policy: TcmbEvdsHugeRequestPolicy::AllowHugeRequests,
+    max_estimated_observations: 0,
+    #[cfg(feature = "parse")]
+    json_backend: TcmbEvdsJsonBackend::SerdeJsonBackend,
+    #[cfg(feature = "parse")]
+    post_processors: Vec::new(),
+    csv_injection_protection: true,
+});
+
+
+/// gives access to the process wide settings.
+///
+/// A poisoned lock is recovered because the settings only contain plain values that cannot be left half updated.
+pub(crate) fn get_settings() -> MutexGuard<'static, Settings> {
+    match SETTINGS.lock() {
+        Ok(settings) => settings,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// gives today on Turkish time, which CBRT publishes on, or at the overridden offset from UTC.
+pub(crate) fn get_today() -> CalendarDate {
+    let utc_offset_minutes = get_settings().today_utc_offset_minutes;
+
+    CalendarDate::today(utc_offset_minutes)
+}
+
+impl SessionDate {
+    /// gives the date text of the session date on the given day, e.g. "14-12-2010,13-12-2011" for 365 trailing days 
+    /// on 13-12-2011.
+    pub(crate) fn resolve(&self, today: CalendarDate) -> String {
+        match self {
+            SessionDate::Fixed(date) => date.clone(),
+            SessionDate::Trailing(days) => {
+                format!("{},{}", today.add_days(1 - i64::from(*days)).format(), today.format())
+            },
+            SessionDate::YearToDate => format!("01-01-{:04},{}", today.year, today.format()),
+            SessionDate::FullHistory => format!("{},{}", FULL_HISTORY_START_DATE, today.format()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_resolve_session_date() {
+        let today = CalendarDate::parse("13-12-2011").unwrap();
+
+        assert_eq!("14-12-2010,13-12-2011", SessionDate::Trailing(365).resolve(today));
+        assert_eq!("13-12-2011,13-12-2011", SessionDate::Trailing(1).resolve(today));
+        assert_eq!("2011", SessionDate::Fixed("2011".to_string()).resolve(today));
+        assert_eq!("01-01-2011,13-12-2011", SessionDate::YearToDate.resolve(today));
+        assert_eq!("01-01-1950,13-12-2011", SessionDate::FullHistory.resolve(today));
+    }
+}
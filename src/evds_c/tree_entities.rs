@@ -0,0 +1,265 @@
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+
+use libc::{c_long, c_uint};
+
+use super::common_entities::{TcmbEvdsHeaderLanguage, to_c_string};
+use super::error_handling::shield_or;
+use super::read_enum;
+
+use crate::dataset::category_tree::{CategoryTree, TreeNode};
+use crate::dataset::header::HeaderLanguage;
+
+
+/// is the index given instead of a node when there is no such node, e.g. the next sibling of the last category.
+const NO_NODE: c_long = -1;
+
+
+/// is an opaque handle of the tree of the categories of EVDS holding their data groups, created by
+/// [`tcmb_evds_c_get_category_tree`](crate::tcmb_evds_c_get_category_tree) and released via [`tcmb_evds_c_tree_free`].
+///
+/// The nodes are addressed by their indexes. The first category is found via [`tcmb_evds_c_tree_first_category`], and
+/// the rest of the tree is walked via [`tcmb_evds_c_tree_first_child`] and [`tcmb_evds_c_tree_next_sibling`], which
+/// give -1 when there is no such node. The whole tree is parsed at once, so the nodes are expanded without requesting
+/// EVDS again.
+pub struct TcmbEvdsCategoryTree {
+    tree: CategoryTree,
+    codes: Vec<CString>,
+    turkish_names: Vec<CString>,
+    english_names: Vec<CString>,
+}
+
+impl TcmbEvdsCategoryTree {
+    pub(crate) fn from(tree: CategoryTree) -> TcmbEvdsCategoryTree {
+
+        let to_c_strings = |read_text: fn(&TreeNode) -> &str| {
+            tree.nodes.iter().map(read_text).map(to_c_string).collect()
+        };
+
+        TcmbEvdsCategoryTree {
+            codes: to_c_strings(|node| &node.code),
+            turkish_names: to_c_strings(|node| node.names.get(HeaderLanguage::Turkish)),
+            english_names: to_c_strings(|node| node.names.get(HeaderLanguage::English)),
+            tree,
+        }
+    }
+
+    /// moves the tree to the heap and gives its ownership to C.
+    pub(crate) fn into_raw(self) -> *mut TcmbEvdsCategoryTree {
+        Box::into_raw(Box::new(self))
+    }
+
+    fn get_node(&self, node: c_long) -> Option<&TreeNode> {
+        usize::try_from(node).ok().and_then(|node| self.tree.nodes.get(node))
+    }
+}
+
+fn to_index(node: Option<usize>) -> c_long {
+    node.map_or(NO_NODE, |node| node as c_long)
+}
+
+
+/// gives the index of the first category of the tree. -1 is returned when the tree is NULL or empty.
+///
+/// # Example
+///
+/// ```C
+///     for (long category = tcmb_evds_c_tree_first_category(tree);
+///          category != -1;
+///          category = tcmb_evds_c_tree_next_sibling(tree, category)) {
+///         printf("%s\n", tcmb_evds_c_tree_node_name(tree, category, EnglishHeaders));
+///
+///         for (long group = tcmb_evds_c_tree_first_child(tree, category);
+///              group != -1;
+///              group = tcmb_evds_c_tree_next_sibling(tree, group)) {
+///             printf("  %s: %ld series\n",
+///                 tcmb_evds_c_tree_node_code(tree, group),
+///                 tcmb_evds_c_tree_series_count(tree, group));
+///         }
+///     }
+/// ```
+///
+/// # Safety
+///
+/// The tree must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_tree_first_category(tree: *const TcmbEvdsCategoryTree) -> c_long {
+
+    shield_or(NO_NODE, || {
+        match tree.as_ref() {
+            Some(tree) => to_index(tree.tree.first_category()),
+            None => NO_NODE,
+        }
+    })
+}
+
+/// gives the index of the first data group of a category. -1 is returned for a data group, a category without data
+/// groups, an out of range node or a NULL tree.
+///
+/// # Safety
+///
+/// The tree must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_tree_first_child(tree: *const TcmbEvdsCategoryTree, node: c_long) -> c_long {
+
+    shield_or(NO_NODE, || {
+        to_index(tree.as_ref().and_then(|tree| tree.get_node(node)).and_then(|node| node.first_child))
+    })
+}
+
+/// gives the index of the next category of a category or the next data group of a data group in the same category.
+/// -1 is returned after the last one, for an out of range node or a NULL tree.
+///
+/// # Safety
+///
+/// The tree must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_tree_next_sibling(tree: *const TcmbEvdsCategoryTree, node: c_long) -> c_long {
+
+    shield_or(NO_NODE, || {
+        to_index(tree.as_ref().and_then(|tree| tree.get_node(node)).and_then(|node| node.next_sibling))
+    })
+}
+
+/// gives the id of a category, e.g. "1", or the code of a data group, e.g. "bie_pyrepo", as a NUL terminated string.
+///
+/// The returned string belongs to the tree and remains valid until the tree is freed. NULL is returned for an out of
+/// range node, a NULL tree or an out of range language when strict enums are enabled.
+///
+/// # Safety
+///
+/// The tree must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_tree_node_code(tree: *const TcmbEvdsCategoryTree, node: c_long) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        let code = tree.as_ref().and_then(|tree| tree.codes.get(usize::try_from(node).ok()?));
+
+        match code {
+            Some(code) => code.as_ptr(),
+            None => ptr::null(),
+        }
+    })
+}
+
+/// gives the name of a category or a data group in the language as a NUL terminated string. The name in the other
+/// language is given when the name is missing in the language, and the English name is given for `CodeHeaders`.
+///
+/// The returned string belongs to the tree and remains valid until the tree is freed. NULL is returned for an out of
+/// range node or a NULL tree.
+///
+/// # Safety
+///
+/// The tree must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_tree_node_name(
+    tree: *const TcmbEvdsCategoryTree,
+    node: c_long,
+    language: c_uint
+) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        let language: TcmbEvdsHeaderLanguage = match read_enum(language) {
+            Ok(language) => language,
+            Err(_) => return ptr::null(),
+        };
+
+        let name = tree.as_ref().and_then(|tree| {
+            let names = match language.to_language() {
+                Some(HeaderLanguage::Turkish) => &tree.turkish_names,
+                _ => &tree.english_names,
+            };
+
+            names.get(usize::try_from(node).ok()?)
+        });
+
+        match name {
+            Some(name) => name.as_ptr(),
+            None => ptr::null(),
+        }
+    })
+}
+
+/// gives the number of the series of a data group. -1 is returned for a category, a data group whose series list is
+/// not requested before the tree, an out of range node or a NULL tree.
+///
+/// # Safety
+///
+/// The tree must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_tree_series_count(tree: *const TcmbEvdsCategoryTree, node: c_long) -> c_long {
+
+    shield_or(-1, || {
+        match tree.as_ref().and_then(|tree| tree.get_node(node)).and_then(|node| node.series_count) {
+            Some(series_count) => series_count as c_long,
+            None => -1,
+        }
+    })
+}
+
+/// releases the tree. Passing NULL is allowed and does nothing.
+///
+/// # Safety
+///
+/// The tree must be NULL or a valid pointer returned from this library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_tree_free(tree: *mut TcmbEvdsCategoryTree) {
+
+    shield_or((), || {
+        if tree.is_null() { return; }
+
+        drop(Box::from_raw(tree));
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CStr;
+
+    use crate::dataset::catalog::SeriesCatalog;
+
+    use TcmbEvdsHeaderLanguage::{EnglishHeaders, TurkishHeaders};
+
+    #[test]
+    fn should_walk_category_tree() {
+        let categories = r#"[{"CATEGORY_ID":1,"TOPIC_TITLE_TR":"PİYASA VERİLERİ","TOPIC_TITLE_ENG":"MARKET STATISTICS"}]"#;
+        let data_groups = r#"[{"CATEGORY_ID":1,"DATAGROUP_CODE":"bie_pyrepo","DATAGROUP_NAME":"Repo"}]"#;
+
+        let category_tree = CategoryTree::parse(categories, data_groups, &SeriesCatalog::new()).unwrap();
+        let tree = TcmbEvdsCategoryTree::from(category_tree).into_raw();
+
+        unsafe {
+            let category = tcmb_evds_c_tree_first_category(tree);
+            let group = tcmb_evds_c_tree_first_child(tree, category);
+
+            assert_eq!((0, 1), (category, group));
+            assert_eq!(NO_NODE, tcmb_evds_c_tree_next_sibling(tree, category));
+            assert_eq!(NO_NODE, tcmb_evds_c_tree_first_child(tree, group));
+            assert_eq!("bie_pyrepo", CStr::from_ptr(tcmb_evds_c_tree_node_code(tree, group)).to_str().unwrap());
+            assert_eq!(
+                "PİYASA VERİLERİ",
+                CStr::from_ptr(tcmb_evds_c_tree_node_name(tree, category, TurkishHeaders as c_uint))
+                    .to_str()
+                    .unwrap()
+            );
+            assert_eq!(
+                "Repo",
+                CStr::from_ptr(tcmb_evds_c_tree_node_name(tree, group, EnglishHeaders as c_uint))
+                    .to_str()
+                    .unwrap()
+            );
+            assert_eq!(-1, tcmb_evds_c_tree_series_count(tree, group));
+            assert!(tcmb_evds_c_tree_node_code(tree, 2).is_null());
+            assert!(tcmb_evds_c_tree_node_code(tree, NO_NODE).is_null());
+
+            tcmb_evds_c_tree_free(tree);
+
+            assert_eq!(NO_NODE, tcmb_evds_c_tree_first_category(ptr::null()));
+        }
+    }
+}
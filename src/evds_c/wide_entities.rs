@@ -0,0 +1,281 @@
+use std::{mem, ptr};
+use std::os::raw::c_char;
+
+use libc::{c_uint, c_ulong, wchar_t};
+
+use super::common_entities::{MAX_INPUT_LENGTH, TcmbEvdsInput, TcmbEvdsResult};
+use super::error_handling::{ReturnErrorC, shield_result};
+
+
+/// includes a wide string pointer and its length in `wchar_t` units, e.g. `L"TP.DK.USD.S"` and `wcslen` of it.
+///
+/// The wide strings are UTF-16 on Windows and UTF-32 on the other systems as their `wchar_t` is, and they are
+/// converted into UTF-8 before calling the related functions taking [`TcmbEvdsInput`].
+#[repr(C)]
+pub struct TcmbEvdsWideInput {
+    pub input_ptr: *const wchar_t,
+    pub string_capacity: c_ulong,
+}
+
+impl TcmbEvdsWideInput {
+    /// converts the wide string into UTF-8 bytes followed by a NUL character. None is returned for a NULL pointer.
+    ///
+    /// The string is never read beyond its NUL terminator or [`MAX_INPUT_LENGTH`] units, and a capacity shorter than
+    /// the string takes only the first `string_capacity` units like [`TcmbEvdsInput`] does.
+    ///
+    /// # Error
+    ///
+    /// This function returns an error message when the string is too long, contains a NUL character before the
+    /// capacity or is not a valid wide string.
+    fn to_utf8(&self, parameter_name: &str) -> Result<Option<Vec<u8>>, String> {
+
+        if self.input_ptr.is_null() { return Ok(None); }
+
+        if self.string_capacity > MAX_INPUT_LENGTH as c_ulong {
+            return Err(
+                format!(
+                    "Error: The string capacity of the given {} parameter is more than {} characters.",
+                    parameter_name,
+                    MAX_INPUT_LENGTH,
+                )
+            );
+        }
+
+        let capacity = self.string_capacity as usize;
+        let mut units = Vec::with_capacity(capacity);
+
+        // the string is read until the NUL character or the capacity, whichever comes first.
+        while units.len() < capacity {
+            let unit = unsafe { *self.input_ptr.add(units.len()) };
+
+            if unit == 0 { break; }
+
+            units.push(unit);
+        }
+
+        if units.len() < capacity {
+            return Err(
+                format!(
+                    "Error: The string capacity ({}) of the given {} parameter exceeds the length of the string ({}).",
+                    capacity,
+                    parameter_name,
+                    units.len(),
+                )
+            );
+        }
+
+        match decode_wide_string(&units) {
+            Some(text) => Ok(Some(text.into_bytes())),
+            None => Err(format!("Error: The given {} parameter is not a valid wide string.", parameter_name)),
+        }
+    }
+}
+
+/// decodes the units of a UTF-16 or UTF-32 wide string according to the size of `wchar_t`.
+fn decode_wide_string(units: &[wchar_t]) -> Option<String> {
+
+    if mem::size_of::<wchar_t>() == 2 {
+        let units: Vec<u16> = units.iter().map(|unit| *unit as u16).collect();
+
+        return String::from_utf16(&units).ok();
+    }
+
+    units.iter().map(|unit| char::from_u32(*unit as u32)).collect()
+}
+
+/// converts the wide inputs and calls the function with their UTF-8 versions, which are valid during the call. The
+/// NULL inputs are passed as NULL, so that the function reports them like its other errors.
+///
+/// # Error
+///
+/// This function returns `ParameterError` for the first input that could not be converted.
+fn with_utf8_inputs<F, const N: usize>(inputs: [(&TcmbEvdsWideInput, &str); N], call: F) -> TcmbEvdsResult
+where
+    F: FnOnce([TcmbEvdsInput; N]) -> TcmbEvdsResult
+{
+
+    let mut converted_inputs = Vec::with_capacity(N);
+
+    for (input, parameter_name) in inputs.iter() {
+        match input.to_utf8(parameter_name) {
+            Ok(converted_input) => converted_inputs.push(converted_input),
+            Err(error_message) => {
+                return TcmbEvdsResult::generate_parameter_result(
+                    error_message,
+                    ReturnErrorC::ParameterError,
+                    parameter_name,
+                );
+            },
+        }
+    }
+
+    let mut utf8_inputs = [(); N].map(|_| TcmbEvdsInput { input_ptr: ptr::null(), string_capacity: 0 });
+
+    for (utf8_input, converted_input) in utf8_inputs.iter_mut().zip(converted_inputs.iter_mut()) {
+        if let Some(text) = converted_input {
+            utf8_input.string_capacity = text.len() as c_ulong;
+
+            text.push(0);
+            utf8_input.input_ptr = text.as_ptr() as *const c_char;
+        }
+    }
+
+    call(utf8_inputs)
+}
+
+
+/// does the same thing with [`tcmb_evds_c_get_data`](crate::tcmb_evds_c_get_data) for wide string inputs.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsWideInput data_series = { L"TP.DK.USD.S", wcslen(L"TP.DK.USD.S") };
+///     TcmbEvdsWideInput date = { L"13-12-2011", wcslen(L"13-12-2011") };
+///     TcmbEvdsWideInput api_key = { api_key_text, wcslen(api_key_text) };
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data_w(data_series, date, api_key, Json, false);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_data_w(
+    data_series: TcmbEvdsWideInput,
+    date: TcmbEvdsWideInput,
+    api_key: TcmbEvdsWideInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_data_w", || {
+        let inputs = [(&data_series, "data_series"), (&date, "date"), (&api_key, "api_key")];
+
+        with_utf8_inputs(inputs, |[data_series, date, api_key]| {
+            crate::tcmb_evds_c_get_data(data_series, date, api_key, return_format, ascii_mode)
+        })
+    })
+}
+
+/// does the same thing with [`tcmb_evds_c_get_advanced_data`](crate::tcmb_evds_c_get_advanced_data) for wide string
+/// inputs.
+#[cfg(feature = "currency")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_advanced_data_w(
+    currency_series: TcmbEvdsWideInput,
+    date: TcmbEvdsWideInput,
+    aggregation_type: c_uint,
+    formula: c_uint,
+    data_frequency: c_uint,
+    api_key: TcmbEvdsWideInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_advanced_data_w", || {
+        let inputs = [(&currency_series, "currency_series"), (&date, "date"), (&api_key, "api_key")];
+
+        with_utf8_inputs(inputs, |[currency_series, date, api_key]| {
+            crate::tcmb_evds_c_get_advanced_data(
+                currency_series,
+                date,
+                aggregation_type,
+                formula,
+                data_frequency,
+                api_key,
+                return_format,
+                ascii_mode
+            )
+        })
+    })
+}
+
+/// does the same thing with [`tcmb_evds_c_get_data_group`](crate::tcmb_evds_c_get_data_group) for wide string inputs.
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_data_group_w(
+    data_group: TcmbEvdsWideInput,
+    date: TcmbEvdsWideInput,
+    api_key: TcmbEvdsWideInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_data_group_w", || {
+        let inputs = [(&data_group, "data_group"), (&date, "date"), (&api_key, "api_key")];
+
+        with_utf8_inputs(inputs, |[data_group, date, api_key]| {
+            crate::tcmb_evds_c_get_data_group(data_group, date, api_key, return_format, ascii_mode)
+        })
+    })
+}
+
+/// does the same thing with [`tcmb_evds_c_get_categories`](crate::tcmb_evds_c_get_categories) for a wide string api
+/// key.
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_categories_w(
+    api_key: TcmbEvdsWideInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_categories_w", || {
+        with_utf8_inputs([(&api_key, "api_key")], |[api_key]| {
+            crate::tcmb_evds_c_get_categories(api_key, return_format, ascii_mode)
+        })
+    })
+}
+
+/// does the same thing with [`tcmb_evds_c_get_series_list`](crate::tcmb_evds_c_get_series_list) for wide string
+/// inputs.
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_series_list_w(
+    code: TcmbEvdsWideInput,
+    api_key: TcmbEvdsWideInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_series_list_w", || {
+        with_utf8_inputs([(&code, "code"), (&api_key, "api_key")], |[code, api_key]| {
+            crate::tcmb_evds_c_get_series_list(code, api_key, return_format, ascii_mode)
+        })
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::evds_c::common_entities::TcmbEvdsReturnFormat::Json;
+
+    fn to_wide_units(text: &str) -> Vec<wchar_t> {
+        match mem::size_of::<wchar_t>() {
+            2 => text.encode_utf16().map(|unit| unit as wchar_t).collect(),
+            _ => text.chars().map(|character| character as wchar_t).collect(),
+        }
+    }
+
+    #[test]
+    fn should_convert_wide_inputs() {
+        let units = to_wide_units("TÜFE 2003=100");
+        let input = TcmbEvdsWideInput { input_ptr: units.as_ptr(), string_capacity: units.len() as c_ulong };
+
+        assert_eq!(Ok(Some("TÜFE 2003=100".as_bytes().to_vec())), input.to_utf8("code"));
+
+        let short_input = TcmbEvdsWideInput { input_ptr: units.as_ptr(), string_capacity: 4 };
+        assert_eq!(Ok(Some("TÜFE".as_bytes().to_vec())), short_input.to_utf8("code"));
+
+        let null_terminated_units = to_wide_units("TP\0");
+        let long_capacity = TcmbEvdsWideInput { input_ptr: null_terminated_units.as_ptr(), string_capacity: 5 };
+        assert!(long_capacity.to_utf8("code").is_err());
+
+        let null_input = TcmbEvdsWideInput { input_ptr: ptr::null(), string_capacity: 0 };
+        assert_eq!(Ok(None), null_input.to_utf8("code"));
+
+        let result = tcmb_evds_c_get_data_w(null_input, input, short_input, Json as c_uint, false);
+        let (error_type, message, _) = result.into_parts();
+
+        assert!(matches!(error_type, ReturnErrorC::ParameterError));
+        assert!(message.contains("NULL"));
+    }
+}
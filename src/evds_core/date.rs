@@ -0,0 +1,92 @@
+/// is the length of the dates in "day-month-year" format, e.g. "13-12-2011".
+pub const DATE_LENGTH: usize = 10;
+
+
+/// checks the date is in "day-month-year" format, e.g. "13-12-2011", with a day up to 31, a month up to 12 and a four
+/// digit year.
+///
+/// The date is not checked against the calendar, so "31-02-2011" is valid as EVDS accepts it.
+pub fn is_date_valid(date: &str) -> bool {
+    date.len() == DATE_LENGTH && is_each_value_valid(date) && is_alignment_valid(date)
+}
+
+/// checks day, month and year values are whether valid or not.
+pub fn is_each_value_valid(date: &str) -> bool {
+
+    let max_day_number = 31;
+    let max_month_number = 12;
+    let min_year_number = 1000;
+    let max_year_number = 9999;
+
+
+    let string_parts = date.split('-');
+
+    if string_parts.count() != 3 { return false; }
+
+    let string_parts = date.split('-');
+
+    let mut count_numbers = 0;
+
+    for part in string_parts {
+        let value = match part.parse::<u16>() {
+            Ok(value) => value,
+            Err(_) => return false,
+        };
+
+        count_numbers += 1;
+
+        if count_numbers == 1 && value > max_day_number { return false; }
+        if count_numbers == 2 && value > max_month_number { return false; }
+        if count_numbers == 3 && !(min_year_number..=max_year_number).contains(&value) { return false; }
+    }
+
+
+    true
+}
+
+/// checks format of the given date string is whether valid or not.
+pub fn is_alignment_valid(date: &str) -> bool {
+
+    let dash_initial_index = 2;
+    let dash_final_index = 5;
+    let min_line_number = 2;
+    let max_line_number = 8;
+
+
+    let mut count_lines = 0;
+    let mut count_numbers = 0;
+
+    let char_indices = date.char_indices();
+
+    for (index, character) in char_indices {
+        if character == '-' {
+            count_lines += 1;
+
+            if index != dash_initial_index && index != dash_final_index { return false; }
+        }
+        if character.is_ascii_digit() { count_numbers += 1; }
+    }
+
+    if count_lines != min_line_number || count_numbers != max_line_number { return false; }
+
+
+    true
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_validate_dates() {
+        assert!(is_date_valid("13-12-2011"));
+        assert!(is_date_valid("31-02-2011"));
+        assert!(!is_date_valid("32-12-2011"));
+        assert!(!is_date_valid("13-13-2011"));
+        assert!(!is_date_valid("13-12-0999"));
+        assert!(!is_date_valid("1-12-20111"));
+        assert!(!is_date_valid("13-12-2011 "));
+        assert!(!is_date_valid(""));
+    }
+}
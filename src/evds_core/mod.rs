@@ -0,0 +1,9 @@
+// the core is checked to use only `core` and `alloc`, so that it builds in `no_std` environments.
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc, clippy::alloc_instead_of_core)]
+
+/// validates the dates in "day-month-year" format.
+pub mod date;
+/// splits the currency series codes into their parts.
+pub mod series;
+/// builds the canonical EVDS urls and checks the query values.
+pub mod url;
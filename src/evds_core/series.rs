@@ -0,0 +1,136 @@
+use core::fmt;
+
+
+/// is the shortest currency series code, e.g. "TP.DK.USD.S".
+const MIN_SERIES_LENGTH: usize = 11;
+
+/// is the longest currency series code, e.g. "TP.DK.USD.S.YTL".
+const MAX_SERIES_LENGTH: usize = 15;
+
+/// is the number of the dots of the currency series codes without the "YTL" suffix.
+const MIN_DOT_NUMBER: usize = 3;
+
+/// is the number of the dots of the currency series codes with the "YTL" suffix.
+const MAX_DOT_NUMBER: usize = 4;
+
+
+/// is the constraint that a currency series code fails, which is written as a sentence fragment, e.g. "the suffix
+/// \"TL\" is not \"YTL\"".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesConstraint<'a> {
+    /// holds the length of the code in characters.
+    Length(usize),
+    /// holds the number of the dots in the code.
+    DotCount(usize),
+    /// holds the suffix written instead of "YTL".
+    Suffix(&'a str),
+    /// holds the exchange type written instead of "A" or "S".
+    ExchangeType(&'a str),
+}
+
+impl fmt::Display for SeriesConstraint<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeriesConstraint::Length(length) => write!(
+                formatter,
+                "the length is {} characters while {} to {} characters are expected",
+                length,
+                MIN_SERIES_LENGTH,
+                MAX_SERIES_LENGTH
+            ),
+            SeriesConstraint::DotCount(dot_number) => write!(
+                formatter,
+                "{} dots are found while {} or {} dots are expected",
+                dot_number,
+                MIN_DOT_NUMBER,
+                MAX_DOT_NUMBER
+            ),
+            SeriesConstraint::Suffix(suffix) => write!(formatter, "the suffix \"{}\" is not \"YTL\"", suffix),
+            SeriesConstraint::ExchangeType(exchange_type) => write!(
+                formatter,
+                "the exchange type \"{}\" is neither \"A\" for buying nor \"S\" for selling",
+                exchange_type
+            ),
+        }
+    }
+}
+
+/// is a currency series code split into its parts, e.g. "USD", selling and ytl mode for "TP.DK.USD.S.YTL".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrencySeriesParts<'a> {
+    /// is the currency code as it is written, which is not checked against the known currencies.
+    pub currency_code: &'a str,
+    pub is_buying: bool,
+    pub ytl_mode: bool,
+}
+
+
+/// splits a currency series code, e.g. `TP.DK.USD.S.YTL`, into its parts. The whitespaces around the code are
+/// ignored.
+///
+/// # Error
+///
+/// This function returns the failed constraint, namely the length, the dot count, an exchange type other than "A" and
+/// "S" or a suffix other than "YTL".
+pub fn split_currency_series(data_series: &str) -> Result<CurrencySeriesParts<'_>, SeriesConstraint<'_>> {
+
+    let data_series = data_series.trim();
+
+    let length = data_series.chars().count();
+
+    if !(MIN_SERIES_LENGTH..=MAX_SERIES_LENGTH).contains(&length) { return Err(SeriesConstraint::Length(length)); }
+
+    let dot_number = data_series.matches('.').count();
+
+    if !(MIN_DOT_NUMBER..=MAX_DOT_NUMBER).contains(&dot_number) { return Err(SeriesConstraint::DotCount(dot_number)); }
+
+
+    let mut separated_parts = data_series.split('.');
+
+    // the suffix occurs only with the max dot number.
+    let ytl_mode = dot_number == MAX_DOT_NUMBER;
+
+    if ytl_mode {
+        let suffix = separated_parts.next_back().unwrap_or_default();
+
+        if !suffix.eq_ignore_ascii_case("ytl") { return Err(SeriesConstraint::Suffix(suffix)); }
+    }
+
+    let exchange_type = separated_parts.next_back().unwrap_or_default();
+
+    let is_buying = match exchange_type {
+        "a" | "A" => true,
+        "s" | "S" => false,
+        _ => return Err(SeriesConstraint::ExchangeType(exchange_type)),
+    };
+
+    let currency_code = separated_parts.next_back().unwrap_or_default();
+
+    Ok(CurrencySeriesParts { currency_code, is_buying, ytl_mode })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_split_currency_series() {
+        assert_eq!(
+            Ok(CurrencySeriesParts { currency_code: "USD", is_buying: false, ytl_mode: true }),
+            split_currency_series(" TP.DK.USD.S.YTL ")
+        );
+        assert_eq!(
+            Ok(CurrencySeriesParts { currency_code: "eur", is_buying: true, ytl_mode: false }),
+            split_currency_series("TP.DK.eur.a")
+        );
+        assert_eq!(Err(SeriesConstraint::Length(9)), split_currency_series("TP.DK.USD"));
+        assert_eq!(Err(SeriesConstraint::DotCount(2)), split_currency_series("TP.DK.USD-S"));
+        assert_eq!(Err(SeriesConstraint::Suffix("YTX")), split_currency_series("TP.DK.USD.S.YTX"));
+        assert_eq!(Err(SeriesConstraint::ExchangeType("B")), split_currency_series("TP.DK.USD.B"));
+        assert_eq!(
+            "the exchange type \"B\" is neither \"A\" for buying nor \"S\" for selling",
+            SeriesConstraint::ExchangeType("B").to_string()
+        );
+    }
+}
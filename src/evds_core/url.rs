@@ -0,0 +1,141 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+
+/// is the root of all EVDS web service urls.
+pub const EVDS_URL_ROOT: &str = "https://evds2.tcmb.gov.tr/service/evds/";
+
+/// is the canonical order of the url parameters. Unknown parameters are placed after them in alphabetical order.
+///
+/// The series and data group parameters come first since EVDS expects them right after the url root. The api key is
+/// the last parameter so that the url without the key can be used as a key of the cached responses.
+pub const PARAMETER_ORDER: [&str; 11] = [
+    "series",
+    "datagroup",
+    "startDate",
+    "endDate",
+    "mode",
+    "code",
+    "aggregationTypes",
+    "formulas",
+    "frequency",
+    "type",
+    "key",
+];
+
+/// is the name of the api key parameter.
+pub const KEY_PARAMETER: &str = "key";
+
+/// is the name of the return format parameter.
+pub const TYPE_PARAMETER: &str = "type";
+
+/// replaces the api key in the urls built for auditing.
+const MASKED_KEY: &str = "********";
+
+/// are the characters separating the parameters of a query. User inputs containing them could inject parameters.
+const RESERVED_QUERY_CHARACTERS: [char; 3] = ['&', '=', '#'];
+
+
+/// builds the url of the resource placed after the url root, e.g. `"categories/"`, with the parameters in the
+/// canonical order of [`PARAMETER_ORDER`]. The excluded parameters are left out and the value of the api key is
+/// masked when `is_key_masked` is true.
+///
+/// Parameter values are percent encoded except the characters that EVDS uses in series codes, dates and keys, namely
+/// letters, digits, `.`, `-`, `_` and `~`. As a result, the same parameters always produce the same url.
+pub fn build_url(
+    resource: &str,
+    parameters: &[(Cow<str>, String)],
+    excluded_parameters: &[&str],
+    is_key_masked: bool
+) -> String {
+
+    let mut parameters: Vec<&(Cow<str>, String)> = parameters
+        .iter()
+        .filter(|(name, _)| !excluded_parameters.contains(&name.as_ref()))
+        .collect();
+
+    parameters.sort_by(|(former_name, _), (latter_name, _)| {
+        let former_rank = find_rank(former_name);
+        let latter_rank = find_rank(latter_name);
+
+        former_rank.cmp(&latter_rank).then_with(|| former_name.cmp(latter_name))
+    });
+
+    // the parameters are written into a single buffer fitting the url unless some characters are encoded.
+    let query_length: usize = parameters.iter().map(|(name, value)| name.len() + value.len() + 2).sum();
+
+    let mut url = String::with_capacity(EVDS_URL_ROOT.len() + resource.len() + query_length);
+
+    url.push_str(EVDS_URL_ROOT);
+    url.push_str(resource);
+
+    for (index, (name, value)) in parameters.iter().enumerate() {
+        if index > 0 { url.push('&'); }
+
+        encode_into(&mut url, name);
+        url.push('=');
+
+        match is_key_masked && name == KEY_PARAMETER {
+            true => url.push_str(MASKED_KEY),
+            false => encode_into(&mut url, value),
+        }
+    }
+
+    url
+}
+
+/// masks the value of the api key in an already built url, so the url can be stored or shown safely.
+pub fn mask_api_key(url: &str) -> String {
+    url.split('&')
+        .map(|pair| {
+            // the first parameter of a url follows the resource, e.g. "categories/key=...".
+            let name_start = pair.rfind('/').map_or(0, |index| index + 1);
+
+            match pair[name_start..].starts_with("key=") {
+                true => format!("{}{}={}", &pair[..name_start], KEY_PARAMETER, MASKED_KEY),
+                false => pair.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+/// gives the static name of a known parameter, so that only the names of unknown parameters are allocated.
+pub fn intern_name(name: &str) -> Cow<'static, str> {
+    match PARAMETER_ORDER.iter().find(|parameter| **parameter == name) {
+        Some(parameter) => Cow::Borrowed(parameter),
+        None => Cow::Owned(name.to_string()),
+    }
+}
+
+/// checks the user input does not contain any of the characters separating query parameters, namely `&`, `=` and `#`.
+pub fn is_query_value_safe(value: &str) -> bool {
+    !value.contains(&RESERVED_QUERY_CHARACTERS[..])
+}
+
+/// finds the place of the parameter in the canonical order.
+fn find_rank(name: &str) -> usize {
+    match PARAMETER_ORDER.iter().position(|parameter| *parameter == name) {
+        Some(rank) => rank,
+        None => PARAMETER_ORDER.len(),
+    }
+}
+
+/// percent encodes the characters other than letters, digits, `.`, `-`, `_` and `~` into the end of the buffer.
+fn encode_into(buffer: &mut String, text: &str) {
+
+    const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => buffer.push(byte as char),
+            _ => {
+                buffer.push('%');
+                buffer.push(HEX_DIGITS[usize::from(byte >> 4)] as char);
+                buffer.push(HEX_DIGITS[usize::from(byte & 0x0F)] as char);
+            },
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use super::CurrencyCode;
+
+use crate::dataset::{Dataset, Observation};
+use crate::error::ReturnError;
+use crate::traits::ConvertingToRustEnum;
+
+
+/// is the name of the series of a basket index in the responses.
+const BASKET_SERIES_CODE: &str = "CURRENCY_BASKET";
+
+
+/// is a weighted basket of currencies valued in TRY via the selling rates of CBRT, e.g. the basket of 50% USD and 50%
+/// EUR that approximates the currency basket of CBRT.
+#[derive(Debug, Clone)]
+pub(crate) struct CurrencyBasket {
+    /// are the currencies with their weights, which are normalized to sum up to one.
+    components: Vec<(CurrencyCode, f64)>,
+}
+
+impl CurrencyBasket {
+    /// parses a basket written as comma separated currency codes with their weights, e.g. "USD:0.5,EUR:0.5" or
+    /// "usd:50,eur:50". The weights are normalized, so they may be given as ratios or percents.
+    ///
+    /// None is returned when a currency code is unknown or repeated, or a weight is not a positive number.
+    pub(crate) fn parse(basket: &str) -> Option<CurrencyBasket> {
+
+        let mut components: Vec<(CurrencyCode, f64)> = Vec::new();
+
+        for component in basket.split(',') {
+            let (currency_code, weight) = component.split_once(':')?;
+
+            let currency_code = currency_code.trim().convert_strictly().ok()?;
+            let weight = weight.trim().parse::<f64>().ok().filter(|weight| weight.is_finite() && *weight > 0.0)?;
+
+            let is_repeated = components
+                .iter()
+                .any(|(included_code, _)| included_code.to_string() == currency_code.to_string());
+
+            if is_repeated { return None; }
+
+            components.push((currency_code, weight));
+        }
+
+        let total_weight: f64 = components.iter().map(|(_, weight)| weight).sum();
+
+        for (_, weight) in components.iter_mut() { *weight /= total_weight; }
+
+        Some(CurrencyBasket { components })
+    }
+
+    /// generates the selling rate series of the currencies separated by dashes, e.g. "TP.DK.USD.S-TP.DK.EUR.S".
+    pub(crate) fn generate_series(&self) -> String {
+        self.components
+            .iter()
+            .map(|(currency_code, _)| format!("TP.DK.{}.S", currency_code))
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+
+    /// computes the basket at each date of the dataset including the rates of its currencies.
+    ///
+    /// Without a base date, the basket is valued in TRY as the weighted sum of the rates. With a base date, the basket
+    /// is an index which is 100 at the base date and weights the change of each rate since the base date. The basket
+    /// is missing at the dates missing any of the rates, e.g. weekends.
+    ///
+    /// # Error
+    ///
+    /// This function returns `InvalidDate` error when the base date is not a date of the dataset or a rate is missing
+    /// at it, and `UnparsableResponse` when a rate series is not in the dataset.
+    pub(crate) fn compute(&self, dataset: &Dataset, base_date: Option<&str>) -> Result<Dataset, ReturnError> {
+
+        let columns: Vec<usize> = self.components
+            .iter()
+            .map(|(currency_code, _)| {
+                let series_code = format!("TP_DK_{}_S", currency_code);
+
+                dataset.series_codes.iter().position(|code| *code == series_code)
+            })
+            .collect::<Option<Vec<usize>>>()
+            .ok_or(ReturnError::UnparsableResponse)?;
+
+        // the rates are divided by their values at the base date and scaled to 100 to be indexed, or kept as they are to
+        // be summed in TRY.
+        let (base_rates, scale) = match base_date {
+            Some(base_date) => {
+                let base_observation = dataset.find_observation(base_date).ok_or(ReturnError::InvalidDate)?;
+
+                let base_rates = columns
+                    .iter()
+                    .map(|column| base_observation.values[*column].filter(|rate| *rate > 0.0))
+                    .collect::<Option<Vec<f64>>>()
+                    .ok_or(ReturnError::InvalidDate)?;
+
+                (base_rates, 100.0)
+            },
+            None => (vec![1.0; columns.len()], 1.0),
+        };
+
+        let observations = dataset.observations
+            .iter()
+            .map(|observation| {
+                let basket_value = self.components
+                    .iter()
+                    .zip(columns.iter().zip(base_rates.iter()))
+                    .map(|((_, weight), (column, base_rate))| {
+                        observation.values[*column].map(|rate| weight * rate / base_rate)
+                    })
+                    .sum::<Option<f64>>()
+                    .map(|basket_value| basket_value * scale);
+
+                Observation {
+                    date: observation.date.clone(),
+                    values: vec![basket_value],
+                    unix_time: observation.unix_time,
+                }
+            })
+            .collect();
+
+        Ok(Dataset { series_codes: vec![BASKET_SERIES_CODE.to_string()], observations })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compute_currency_basket() {
+        let basket = CurrencyBasket::parse("USD:50, eur:50").unwrap();
+
+        assert_eq!("TP.DK.USD.S-TP.DK.EUR.S", basket.generate_series());
+        assert!(CurrencyBasket::parse("USD:0.5,XYZ:0.5").is_none());
+        assert!(CurrencyBasket::parse("USD:0.5,USD:0.5").is_none());
+        assert!(CurrencyBasket::parse("USD:-1").is_none());
+
+        let dataset = Dataset::from_csv(
+            "Tarih,TP_DK_EUR_S,TP_DK_USD_S\n12-12-2011,2.4,1.8\n13-12-2011,3.0,2.7\n14-12-2011,,1.9"
+        ).unwrap();
+
+        let assert_values = |expected_values: [Option<f64>; 3], basket_dataset: Dataset| {
+            for (expected_value, observation) in expected_values.iter().zip(basket_dataset.observations.iter()) {
+                match (expected_value, observation.values[0]) {
+                    (Some(expected_value), Some(value)) => assert!((expected_value - value).abs() < 1e-9),
+                    (expected_value, value) => assert_eq!(*expected_value, value),
+                }
+            }
+        };
+
+        assert_values([Some(2.1), Some(2.85), None], basket.compute(&dataset, None).unwrap());
+        assert_values([Some(100.0), Some(137.5), None], basket.compute(&dataset, Some("12-12-2011")).unwrap());
+        assert!(matches!(basket.compute(&dataset, Some("15-12-2011")), Err(ReturnError::InvalidDate)));
+    }
+}
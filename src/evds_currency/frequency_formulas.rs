@@ -1,9 +1,11 @@
+use std::fmt;
+
 use super::*;
 
 /// provides aggregation type options to create an element of frequency formulas.
 ///
 /// This enum is used in [`AdvancedProcesses`](crate::evds_currency::frequency_formulas::AdvancedProcesses) for 
-/// [`get_advanced_data`](crate::evds_currency::CurrencySeries::get_advanced_data) function.
+/// [`get_combined_advanced_data`](crate::evds_currency::get_combined_advanced_data) function.
 pub enum AggregationType {
     Average,
     Minimum,
@@ -13,22 +15,29 @@ pub enum AggregationType {
     Cumulative,
 }
 
-impl ToString for AggregationType {
-    fn to_string(&self) -> String {
+impl AggregationType {
+    /// gives the value of the aggregation type in the url without allocating.
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
-            &Self::Average => String::from("avg"),
-            &Self::Minimum => String::from("min"),
-            &Self::Maximum => String::from("max"),
-            &Self::Beginning => String::from("first"),
-            &Self::End => String::from("last"),
-            &Self::Cumulative => String::from("sum"),
+            Self::Average => "avg",
+            Self::Minimum => "min",
+            Self::Maximum => "max",
+            Self::Beginning => "first",
+            Self::End => "last",
+            Self::Cumulative => "sum",
         }
     }
 }
 
+impl fmt::Display for AggregationType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
 impl traits::MakingUrlFormat for AggregationType {
     fn generate_url_format(&self) -> String {
-        format!("aggregationTypes={}", self.to_string())
+        format!("aggregationTypes={}", self.as_str())
     }
 }
 
@@ -36,7 +45,7 @@ impl traits::MakingUrlFormat for AggregationType {
 /// provides formula options to create an element of frequency formulas.
 ///
 /// This enum is used in [`AdvancedProcesses`](crate::evds_currency::frequency_formulas::AdvancedProcesses) for 
-/// [`get_advanced_data`](crate::evds_currency::CurrencySeries::get_advanced_data) function.
+/// [`get_combined_advanced_data`](crate::evds_currency::get_combined_advanced_data) function.
 pub enum Formula {
     Level, 
     PercentageChange, 
@@ -49,25 +58,32 @@ pub enum Formula {
     MovingSum,
 }
 
-impl ToString for Formula {
-    fn to_string(&self) -> String {
+impl Formula {
+    /// gives the value of the formula in the url without allocating.
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
-            &Self::Level => "0".to_string(),
-            &Self::PercentageChange => "1".to_string(),
-            &Self::Difference => "2".to_string(),
-            &Self::YearToYearPercentChange => "3".to_string(),
-            &Self::YearToYearDifferences => "4".to_string(),
-            &Self::PercentageChangeByEndOfPreviousYear => "5".to_string(),
-            &Self::DifferenceByEndOfPreviousYear => "6".to_string(),
-            &Self::MovingAverage => "7".to_string(),
-            &Self::MovingSum => "8".to_string(),
+            Self::Level => "0",
+            Self::PercentageChange => "1",
+            Self::Difference => "2",
+            Self::YearToYearPercentChange => "3",
+            Self::YearToYearDifferences => "4",
+            Self::PercentageChangeByEndOfPreviousYear => "5",
+            Self::DifferenceByEndOfPreviousYear => "6",
+            Self::MovingAverage => "7",
+            Self::MovingSum => "8",
         }
     }
-} 
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
 
 impl traits::MakingUrlFormat for Formula {
     fn generate_url_format(&self) -> String {
-        format!("formulas={}", self.to_string())
+        format!("formulas={}", self.as_str())
     }
 }
 
@@ -75,7 +91,8 @@ impl traits::MakingUrlFormat for Formula {
 /// provides data frequency options to create an element of frequency formulas.
 ///
 /// This struct is used for [`AdvancedProcesses`](crate::evds_currency::frequency_formulas::AdvancedProcesses) for 
-/// [`get_advanced_data`](crate::evds_currency::CurrencySeries::get_advanced_data) function.
+/// [`get_combined_advanced_data`](crate::evds_currency::get_combined_advanced_data) function.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DataFrequency {
     Daily, 
     Business, 
@@ -87,24 +104,31 @@ pub enum DataFrequency {
     Annual,
 }
 
-impl ToString for DataFrequency {
-    fn to_string(&self) -> String {
+impl DataFrequency {
+    /// gives the value of the data frequency in the url without allocating.
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
-            &Self::Daily => "1".to_string(),
-            &Self::Business => "2".to_string(),
-            &Self::WeeklyFriday => "3".to_string(),
-            &Self::TwiceMonthly => "4".to_string(),
-            &Self::Monthly => "5".to_string(),
-            &Self::Quarterly => "6".to_string(),
-            &Self::SemiAnnual => "7".to_string(),
-            &Self::Annual => "8".to_string(),
+            Self::Daily => "1",
+            Self::Business => "2",
+            Self::WeeklyFriday => "3",
+            Self::TwiceMonthly => "4",
+            Self::Monthly => "5",
+            Self::Quarterly => "6",
+            Self::SemiAnnual => "7",
+            Self::Annual => "8",
         }
     }
 }
 
+impl fmt::Display for DataFrequency {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
 impl traits::MakingUrlFormat for DataFrequency {
     fn generate_url_format(&self) -> String {
-        format!("frequency={}", self.to_string())
+        format!("frequency={}", self.as_str())
     }
 }
 
@@ -112,13 +136,15 @@ impl traits::MakingUrlFormat for DataFrequency {
 /// contains the elements of frequency formulas.
 ///
 /// This struct is used as an argument for 
-/// [`get_advanced_data`](crate::evds_currency::CurrencySeries::get_advanced_data) function.
+/// [`get_combined_advanced_data`](crate::evds_currency::get_combined_advanced_data) function.
+#[cfg(feature = "currency")]
 pub struct AdvancedProcesses {
     pub aggregation_type: AggregationType,
     pub formula: Formula,
     pub data_frequency: DataFrequency,
 }
 
+#[cfg(feature = "currency")]
 impl AdvancedProcesses {
     /// creates frequency formulas structure named AdvancedProcess.
     /// 
@@ -144,15 +170,25 @@ impl AdvancedProcesses {
         }
     }
 
-    pub(crate) fn get_aggregation_type_as_url_format(&self) -> String {
-        self.aggregation_type.generate_url_format()
+    /// gives the aggregation type of each of the given number of series, e.g. "aggregationTypes=avg-avg" for two 
+    /// series, since EVDS expects an aggregation type per series.
+    pub(crate) fn get_aggregation_type_as_url_format(&self, series_count: usize) -> String {
+        format!("aggregationTypes={}", repeat_for_series(self.aggregation_type.as_str(), series_count))
     }
 
-    pub(crate) fn get_formula_as_url_format(&self) -> String {
-        self.formula.generate_url_format()
+    /// gives the formula of each of the given number of series, e.g. "formulas=1-1" for two series.
+    pub(crate) fn get_formula_as_url_format(&self, series_count: usize) -> String {
+        format!("formulas={}", repeat_for_series(self.formula.as_str(), series_count))
     }
 
     pub(crate) fn get_data_frequency_as_url_format(&self) -> String {
         self.data_frequency.generate_url_format()
     }
 }
+
+
+/// repeats the text for each series separated by dashes as the series are. A single text is given for no series.
+#[cfg(feature = "currency")]
+fn repeat_for_series(text: &str, series_count: usize) -> String {
+    vec![text; series_count.max(1)].join("-")
+}
@@ -3,21 +3,37 @@
 pub(crate) mod frequency_formulas;
 
 /// provides specific make request function for currency operations.
+#[cfg(feature = "currency")]
 mod currency;
+/// computes the index of a weighted basket of currencies.
+#[cfg(feature = "currency")]
+pub(crate) mod basket;
 
 
+use std::fmt;
+
+#[cfg(feature = "currency")]
 use self::frequency_formulas::*;
 
-use crate::common;
+#[cfg(feature = "currency")]
+use crate::common::{self, url_builder::UrlBuilder};
+#[cfg(all(feature = "currency", feature = "cache"))]
+use crate::dataset::cache::{self, CacheEndpoint};
+#[cfg(feature = "currency")]
+use crate::dataset::sniff::negotiate_response;
 use crate::error::ReturnError;
+#[cfg(feature = "currency")]
 use crate::date::DatePreference;
-use crate::traits::{self, MakingList, MakingUrlFormat, EnumSpecific, ConvertingToRustEnum};
+#[cfg(feature = "currency")]
+use crate::traits::{MakingList, MakingUrlFormat};
+use crate::traits::{self, EnumSpecific, ConvertingToRustEnum};
 
 
 /// contains exchange types, which are selling and buying, to configure currency request.
 /// 
 /// This structure is required for all *evds_currency* functions.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
 pub(crate) struct ExchangeType {
     buying: bool,
     selling: bool,
@@ -35,6 +51,7 @@ impl ExchangeType {
     }
 
     /// creates specified exchange type variable.
+    #[cfg(feature = "currency")]
     pub(crate) fn from(buying: bool, selling: bool) -> ExchangeType {
         ExchangeType {
             buying,
@@ -51,6 +68,7 @@ impl ExchangeType {
     }
 
     /// makes selling type true and buying type false.
+    #[cfg(feature = "currency")]
     pub(crate) fn select_selling_type(&mut self) -> &mut Self {
         self.buying = false;
         self.selling = true;
@@ -59,6 +77,7 @@ impl ExchangeType {
     }
 
     /// makes both type true.
+    #[cfg(feature = "currency")]
     pub(crate) fn select_both_types(&mut self) -> &mut Self {
         self.buying = true;
         self.selling = true;
@@ -66,14 +85,17 @@ impl ExchangeType {
         return self;
     }
 
+    #[cfg(feature = "currency")]
     pub(crate) fn is_selling_type(&self) -> bool {
         self.selling
     }
 
+    #[cfg(any(feature = "currency", feature = "catalog"))]
     pub(crate) fn is_buying_type(&self) -> bool {
         self.buying
     }
 
+    #[cfg(feature = "currency")]
     pub(crate) fn are_both_types(&self) -> bool {
         if self.is_selling_type() && self.is_buying_type() { return true }
         
@@ -81,6 +103,7 @@ impl ExchangeType {
     }
 }
 
+#[cfg(feature = "currency")]
 impl traits::MakingList for ExchangeType {
     fn make_required_list(&self) -> Vec<&str> {
         let mut exchange_type_list = Vec::new();
@@ -98,7 +121,7 @@ impl traits::MakingList for ExchangeType {
 
 
 /// supplies currency code option to the functions making single currency request.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum CurrencyCode {
     Usd,
     Aud,
@@ -121,32 +144,39 @@ pub(crate) enum CurrencyCode {
     Qar,
 }
 
-impl ToString for CurrencyCode {
-    fn to_string(&self) -> String {
+impl CurrencyCode {
+    /// gives the code of the currency used in the series codes without allocating.
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
-            &Self::Usd => String::from("USD"),
-            &Self::Aud => String::from("AUD"),
-            &Self::Dkk => String::from("DKK"),
-            &Self::Eur => String::from("EUR"),
-            &Self::Gbp => String::from("GBP"),
-            &Self::Chf => String::from("CHF"),
-            &Self::Sek => String::from("SEK"),
-            &Self::Cad => String::from("CAD"),
-            &Self::Kwd => String::from("KWD"),
-            &Self::Nok => String::from("NOK"),
-            &Self::Sar => String::from("SAR"),
-            &Self::Jpy => String::from("JPY"),
-            &Self::Bgn => String::from("BGN"),
-            &Self::Ron => String::from("RON"),
-            &Self::Rub => String::from("RUB"),
-            &Self::Irr => String::from("IRR"),
-            &Self::Cny => String::from("CNY"),
-            &Self::Pkr => String::from("PKR"),
-            &Self::Qar => String::from("QAR"),
+            Self::Usd => "USD",
+            Self::Aud => "AUD",
+            Self::Dkk => "DKK",
+            Self::Eur => "EUR",
+            Self::Gbp => "GBP",
+            Self::Chf => "CHF",
+            Self::Sek => "SEK",
+            Self::Cad => "CAD",
+            Self::Kwd => "KWD",
+            Self::Nok => "NOK",
+            Self::Sar => "SAR",
+            Self::Jpy => "JPY",
+            Self::Bgn => "BGN",
+            Self::Ron => "RON",
+            Self::Rub => "RUB",
+            Self::Irr => "IRR",
+            Self::Cny => "CNY",
+            Self::Pkr => "PKR",
+            Self::Qar => "QAR",
         }
     }
 }
 
+impl fmt::Display for CurrencyCode {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
 // This implementation is used for C FFI operations.
 impl EnumSpecific for CurrencyCode {}
 
@@ -177,6 +207,65 @@ impl ConvertingToRustEnum<CurrencyCode> for &str {
             _     => CurrencyCode::Qar,
         }
     }
+
+    /// returns `InvalidEnumValue` error for unknown currency codes instead of `QAR`.
+    fn convert_strictly(&self) -> Result<CurrencyCode, ReturnError> {
+        let currency_code = self.convert();
+
+        if let CurrencyCode::Qar = currency_code {
+            if !self.eq_ignore_ascii_case("qar") { return Err(ReturnError::InvalidEnumValue); }
+        }
+
+        Ok(currency_code)
+    }
+}
+
+
+/// builds the series code of the currency, e.g. "TP.DK.USD.S.YTL", or the codes of both exchange types separated by a
+/// dash when both of them are selected, e.g. "TP.DK.USD.A-TP.DK.USD.S".
+///
+/// # Error
+///
+/// This function returns `EmptyExchangeType` error when neither the buying nor the selling type is selected.
+#[cfg(feature = "currency")]
+pub(crate) fn build_currency_series(
+    currency_code: &CurrencyCode,
+    exchange_type: &ExchangeType,
+    ytl_mode: bool
+) -> Result<String, ReturnError> {
+
+    Ok(build_currency_series_codes(currency_code, exchange_type, ytl_mode)?.join("-"))
+}
+
+/// does the same thing with [`build_currency_series`] but gives the code of each selected exchange type separately, 
+/// the buying one first.
+///
+/// # Error
+///
+/// This function returns `EmptyExchangeType` error when neither the buying nor the selling type is selected.
+#[cfg(feature = "currency")]
+pub(crate) fn build_currency_series_codes(
+    currency_code: &CurrencyCode,
+    exchange_type: &ExchangeType,
+    ytl_mode: bool
+) -> Result<Vec<String>, ReturnError> {
+
+    let exchange_types = exchange_type.make_required_list();
+
+    if exchange_types.is_empty() { return Err(ReturnError::EmptyExchangeType); }
+
+    let series_codes = exchange_types
+        .into_iter()
+        .map(|exchange_type| {
+            <CurrencySeries as MakingUrlFormat>::generate_currency_format_for_combination(
+                currency_code.as_str(),
+                exchange_type,
+                ytl_mode
+            )
+        })
+        .collect();
+
+    Ok(series_codes)
 }
 
 
@@ -210,6 +299,7 @@ impl ConvertingToRustEnum<CurrencyCode> for &str {
 ///         ..Default::default()
 ///     }
 /// ```
+#[cfg(feature = "currency")]
 pub(crate) struct CurrencyCodes {
     pub(crate) usd: bool,
     pub(crate) aud: bool,
@@ -232,6 +322,7 @@ pub(crate) struct CurrencyCodes {
     pub(crate) qar: bool,
 }
 
+#[cfg(feature = "currency")]
 impl Default for CurrencyCodes {
     fn default() -> CurrencyCodes {
         CurrencyCodes {
@@ -258,6 +349,7 @@ impl Default for CurrencyCodes {
     }
 }
 
+#[cfg(feature = "currency")]
 impl CurrencyCodes {
     /// "usd" is used as default currency code. 
     pub(crate) fn new() -> CurrencyCodes {
@@ -329,6 +421,7 @@ impl CurrencyCodes {
     }
 }
 
+#[cfg(feature = "currency")]
 impl traits::MakingList for CurrencyCodes {
     /// makes a list of used currency codes.
     fn make_required_list(&self) -> Vec<&str> {
@@ -360,8 +453,7 @@ impl traits::MakingList for CurrencyCodes {
 
 
 /// supplies reliable and well structured required details about currency and date/s to the functions making single 
-/// currency operations such as [`get_data`](fn@CurrencySeries::get_data) and 
-/// [`get_advanced_data`](fn@CurrencySeries::get_advanced_data).
+/// currency operations such as [`get_data`](fn@CurrencySeries::get_data).
 ///
 /// This struct accepts both Single and Multiple date options.
 ///
@@ -369,6 +461,7 @@ impl traits::MakingList for CurrencyCodes {
 ///
 /// *Use of this struct and its implemented functions seems complicated, however it is safe and makes some required 
 /// error prone operations automatically without any problem.*
+#[cfg(feature = "currency")]
 pub(crate) struct CurrencySeries {
     pub(crate) ytl_mode: bool,
     pub(crate) exchange_type: ExchangeType,
@@ -376,42 +469,13 @@ pub(crate) struct CurrencySeries {
     pub(crate) date_preference: DatePreference,
 }
 
+#[cfg(feature = "currency")]
 impl CurrencySeries {
     /// generates single series or dual series with selling and buying with given data.
     fn generate_series_as_url_format(&self) -> Result<String, ReturnError> {
-        let exchange_types = self.exchange_type.make_required_list();
-        
-        let series_format: String;  
-
-        if exchange_types.is_empty() {
-            return Err(ReturnError::EmptyExchangeType);
-        }
+        let series_codes = build_currency_series(&self.currency_code, &self.exchange_type, self.ytl_mode)?;
 
-        if exchange_types.len() == 2 {
-
-            series_format =
-            <Self as MakingUrlFormat>::generate_two_combined_currencies_format(
-                &<Self as MakingUrlFormat>::generate_currency_format_for_combination(
-                    &self.currency_code.to_string(),
-                    exchange_types[0],
-                    self.ytl_mode
-                ),
-                &<Self as MakingUrlFormat>::generate_currency_format_for_combination(
-                    &self.currency_code.to_string(), 
-                    exchange_types[1],
-                    self.ytl_mode
-                )
-            );
-        }
-        else {
-            series_format = 
-            <Self as MakingUrlFormat>::generate_currency_format(
-                &self.currency_code.to_string(), 
-                exchange_types[0], self.ytl_mode
-            );
-        }
-
-        Ok(series_format)
+        Ok(format!("series={}", series_codes))
     }
 
 
@@ -509,115 +573,60 @@ impl CurrencySeries {
     /// ```
     pub(crate) fn get_data(&self, evds: &common::Evds) -> Result<String, ReturnError> {
         
-        let url_root = "https://evds2.tcmb.gov.tr/service/evds/";
-
         let series_format = self.generate_series_as_url_format()?;
 
-        let url = format!(
-            "{}{}&{}&{}&{}", 
-            url_root, 
-            series_format, 
-            self.date_preference.generate_url_format(), 
-            evds.get_return_format_as_url(), 
-            evds.get_api_key_as_url());
-
-        currency::make_request(&url)
-    }
-
-
-    /// returns data about just one currency with frequency formulas.
-    ///
-    /// Single date or multiple dates can be used for this function.
-    ///
-    /// This function is used as a method of [`CurrencySeries`](struct@CurrencySeries) because of decreasing amount of
-    /// function parameters user entering.
-    ///
-    /// # Error
-    ///  
-    /// This function returns error if internet connection is lost.
-    ///
-    /// # Example
-    ///
-    /// Follow [`Evds`](crate::common::Evds) and 
-    /// [`AdvancedProcesses`](crate::evds_currency::frequency_formulas::AdvancedProcesses) for detailed implementation 
-    /// of *evds* and *advanced_processes*.
-    ///
-    /// ```
-    /// #   use tcmb_evds_c::date::{Date, DatePreference};        
-    /// #   use tcmb_evds_c::evds_currency::{ExchangeType, CurrencyCode};
-    /// #   use tcmb_evds_c::common::{ApiKey, ReturnFormat, Evds};
-    /// #   use tcmb_evds_c::evds_currency::{CurrencySeries, frequency_formulas::*};
-    /// #
-    /// #   let exchange_type = ExchangeType::new();
-    /// #
-    /// #   let currency_code = CurrencyCode::Qar;
-    /// #
-    /// #   let date_result = Date::from("13-12-2011");
-    /// #   let date = 
-    /// #       if let Ok(date) = date_result { date } 
-    /// #       else { return };     
-    /// #   let date_preference = DatePreference::Single(date);
-    /// #   
-    /// #   // Ytl mode adds "YTL" to currency series, when it is true.
-    /// #   let ytl_mode = true;
-    /// #  
-    /// #   let currency_series = CurrencySeries::from(exchange_type, currency_code, date_preference, ytl_mode);
-    /// #
-    /// #   let api_key = 
-    /// #       if let Ok(api_key) = ApiKey::from("users_api_key") { api_key } 
-    /// #       else { return }; 
-    /// #   let evds = Evds::from(api_key, ReturnFormat::Json);
-    /// #
-    /// #   let aggregation_type = AggregationType::Average;
-    /// #   let formula = Formula::Level;
-    /// #   let data_frequency = DataFrequency::Monthly;
-    /// #
-    /// #   let advanced_processes = AdvancedProcesses::from(aggregation_type, formula, data_frequency);
-    /// #
-    ///     // requesting currency data with frequency formulas.
-    ///     let result = currency_series.get_advanced_data(&evds, &advanced_processes);
-    /// 
-    /// 
-    ///     let advanced_currency_data = match result {
-    ///         Ok(response) => response,
-    ///         Err(error) => {
-    ///             println!("{}", error.to_string());
-    ///             return
-    ///         }
-    ///     };
-    /// ```
-    pub(crate) fn get_advanced_data(
-        &self, 
-        evds: &common::Evds, 
-        advanced_processes: &AdvancedProcesses
-    ) -> Result<String, ReturnError> {
-        
-        let url_root = "https://evds2.tcmb.gov.tr/service/evds/";
-
-        if self.exchange_type.are_both_types() {
-            return Err(ReturnError::SingleExchangeTypeExpected)
-        }
+        let url = UrlBuilder::new("")
+            .add_fragment(&series_format)
+            .add_fragment(&self.date_preference.generate_url_format())
+            .add_fragment(evds.get_return_format_as_url())
+            .add_fragment(evds.get_api_key_as_url())
+            .build();
 
-        let series_format = self.generate_series_as_url_format()?;
+        let response = currency::make_request(&url)?;
 
-        let url = format!(
-            "{}{}&{}&{}&{}&{}&{}&{}", 
-            url_root, series_format, 
-            self.date_preference.generate_url_format(), 
-            evds.get_return_format_as_url(), 
-            evds.get_api_key_as_url(), 
-            advanced_processes.get_aggregation_type_as_url_format(), 
-            advanced_processes.get_formula_as_url_format(), 
-            advanced_processes.get_data_frequency_as_url_format()
-        );
-    
-        currency::make_request(&url)
+        Ok(negotiate_response(response, evds.get_return_format()))
     }
 }
 
+#[cfg(feature = "currency")]
 impl traits::MakingUrlFormat for CurrencySeries {}
 
 
+/// returns advanced data about several series codes requested together, e.g. "TP.DK.USD.A" and "TP.DK.EUR.A", where
+/// the aggregation type and the formula are applied to each of the series.
+///
+/// # Error
+///
+/// This function returns `EmptyParameter` error when no series code is given, and error if internet connection is 
+/// lost.
+#[cfg(feature = "currency")]
+pub(crate) fn get_combined_advanced_data(
+    series_codes: &[String],
+    date_preference: &DatePreference,
+    evds: &common::Evds,
+    advanced_processes: &AdvancedProcesses
+) -> Result<String, ReturnError> {
+
+    if series_codes.is_empty() { return Err(ReturnError::EmptyParameter); }
+
+    let url = UrlBuilder::new("")
+        .add_fragment(&format!("series={}", series_codes.join("-")))
+        .add_fragment(&date_preference.generate_url_format())
+        .add_fragment(evds.get_return_format_as_url())
+        .add_fragment(evds.get_api_key_as_url())
+        .add_fragment(&advanced_processes.get_aggregation_type_as_url_format(series_codes.len()))
+        .add_fragment(&advanced_processes.get_formula_as_url_format(series_codes.len()))
+        .add_fragment(&advanced_processes.get_data_frequency_as_url_format());
+
+    #[cfg(feature = "cache")]
+    let response = cache::read_through(&url, CacheEndpoint::Data, currency::make_request)?;
+    #[cfg(not(feature = "cache"))]
+    let response = currency::make_request(&url.build())?;
+
+    Ok(negotiate_response(response, evds.get_return_format()))
+}
+
+
 /// supplies reliable and well structured required details about multiple currencies and date/s to the functions 
 /// making multiple currency operations such as 
 /// [`get_multiple_data`](crate::evds_currency::MultipleCurrencySeries::get_multiple_data).
@@ -626,6 +635,7 @@ impl traits::MakingUrlFormat for CurrencySeries {}
 ///
 /// *Use of this struct and its implemented functions seems complicated, however it is safe and makes some required 
 /// error prone operations automatically without any problem.*
+#[cfg(feature = "currency")]
 pub(crate) struct MultipleCurrencySeries {
     pub(crate) ytl_mode: bool,
     pub(crate) exchange_type: ExchangeType,
@@ -633,13 +643,13 @@ pub(crate) struct MultipleCurrencySeries {
     pub(crate) date_preference: DatePreference,
 }
 
+#[cfg(feature = "currency")]
 impl MultipleCurrencySeries {
+    /// generates the series of the currencies in each selected exchange type, the buying ones first.
     fn generate_multiple_series_as_url_format(&self) -> Result<String, ReturnError> {
         let currency_codes = self.currency_codes.make_required_list();
         let exchange_types = self.exchange_type.make_required_list();
 
-        let series_format: String;
-
         if currency_codes.is_empty() {
             return Err(ReturnError::EmptyCurrencyCodes);
         }
@@ -647,30 +657,21 @@ impl MultipleCurrencySeries {
         if exchange_types.is_empty() {
             return Err(ReturnError::EmptyExchangeType);
         }
-        
-        if exchange_types.len() == 2 {
-            series_format = <Self as MakingUrlFormat>::generate_two_combined_currencies_format(
-                &<Self as MakingUrlFormat>::generate_multiple_currency_format_for_combination(
-                    self.currency_codes.make_required_list(), 
-                    exchange_types[0], 
-                    self.ytl_mode
-                ), 
-                &<Self as MakingUrlFormat>::generate_multiple_currency_format_for_combination(
-                    self.currency_codes.make_required_list(), 
-                    exchange_types[1], 
-                    self.ytl_mode
-                )
-            );
-        }
-        else {
-            series_format = <Self as MakingUrlFormat>::generate_multiple_currency_format(
-                self.currency_codes.make_required_list(), 
-                exchange_types[0], 
-                self.ytl_mode
-            );
-        }
 
-        Ok(series_format)
+        let series_codes: Vec<String> = exchange_types
+            .iter()
+            .flat_map(|exchange_type| {
+                currency_codes.iter().map(move |currency_code| {
+                    <Self as MakingUrlFormat>::generate_currency_format_for_combination(
+                        currency_code,
+                        exchange_type,
+                        self.ytl_mode
+                    )
+                })
+            })
+            .collect();
+
+        Ok(format!("series={}", series_codes.join("-")))
     }
     
     /// creates multiple currency series with detailed information.
@@ -784,26 +785,26 @@ impl MultipleCurrencySeries {
     /// ```
     pub(crate) fn get_multiple_data(&self, evds: &common::Evds) -> Result<String, ReturnError> {
         
-        let url_root = "https://evds2.tcmb.gov.tr/service/evds/";
-
         let series_format = self.generate_multiple_series_as_url_format()?;
 
-        let url = format!(
-            "{}{}&{}&{}&{}", 
-            url_root, series_format,
-            self.date_preference.generate_url_format(),
-            evds.get_return_format_as_url(), 
-            evds.get_api_key_as_url()
-        );
+        let url = UrlBuilder::new("")
+            .add_fragment(&series_format)
+            .add_fragment(&self.date_preference.generate_url_format())
+            .add_fragment(evds.get_return_format_as_url())
+            .add_fragment(evds.get_api_key_as_url())
+            .build();
 
-        currency::make_request(&url)
+        let response = currency::make_request(&url)?;
+
+        Ok(negotiate_response(response, evds.get_return_format()))
     }
 }
 
+#[cfg(feature = "currency")]
 impl traits::MakingUrlFormat for MultipleCurrencySeries {}
 
 
-#[cfg(test)]
+#[cfg(all(test, feature = "currency"))]
 mod test {
     use super::*;
 
@@ -828,4 +829,40 @@ mod test {
             println!("{}", &code);
         }
     }
+
+    #[test]
+    fn should_build_currency_series() {
+        let currency_series = build_currency_series(&CurrencyCode::Usd, &ExchangeType::new(), true);
+
+        assert_eq!(Ok("TP.DK.USD.S.YTL".to_string()), currency_series);
+        assert_eq!(
+            Ok("TP.DK.JPY.A-TP.DK.JPY.S".to_string()),
+            build_currency_series(&CurrencyCode::Jpy, &ExchangeType::from(true, true), false)
+        );
+        assert_eq!(
+            Err(ReturnError::EmptyExchangeType),
+            build_currency_series(&CurrencyCode::Jpy, &ExchangeType::from(false, false), false)
+        );
+
+        let both_types = ExchangeType::from(true, true);
+        let series_codes = build_currency_series_codes(&CurrencyCode::Eur, &both_types, true).unwrap();
+
+        assert_eq!(vec!["TP.DK.EUR.A.YTL", "TP.DK.EUR.S.YTL"], series_codes);
+
+        let advanced_processes = 
+            AdvancedProcesses::from(AggregationType::Average, Formula::Level, DataFrequency::Monthly);
+
+        assert_eq!("aggregationTypes=avg-avg", advanced_processes.get_aggregation_type_as_url_format(2));
+        assert_eq!("formulas=0-0", advanced_processes.get_formula_as_url_format(2));
+        assert_eq!("formulas=0", advanced_processes.get_formula_as_url_format(1));
+
+        let currency_codes = CurrencyCodes { usd: true, eur: true, ..Default::default() };
+        let date_preference = DatePreference::Single(crate::date::Date::from("13-12-2011").unwrap());
+        let multiple_series = MultipleCurrencySeries::from(both_types, currency_codes, date_preference, false);
+
+        assert_eq!(
+            Ok("series=TP.DK.USD.A-TP.DK.EUR.A-TP.DK.USD.S-TP.DK.EUR.S".to_string()),
+            multiple_series.generate_multiple_series_as_url_format()
+        );
+    }
 }
@@ -0,0 +1,49 @@
+use crate::dataset::Dataset;
+use crate::dataset::lazy::LazyDataset;
+use crate::evds_c::common_entities::collect_problem;
+use crate::evds_c::data_series::parse_multiple_series;
+use crate::evds_c::date_entities::parse_dates;
+use crate::evds_c::generate_date_preferences;
+
+
+/// checks the date data as the data requests do and gives whether it is accepted. The date data is also divided via
+/// `parse_dates` on its own, which returns an error for the date data of other formats.
+pub fn check_dates(date_data: &str) -> bool {
+
+    let _ = collect_problem(|| parse_dates(date_data));
+
+    collect_problem(|| generate_date_preferences(date_data)).is_ok()
+}
+
+/// parses the data series separated by dashes, e.g. "TP.DK.USD.A-TP.DK.EUR.A", and gives the number of the series.
+pub fn parse_series(data_series: &str) -> Option<usize> {
+    parse_multiple_series(data_series).ok().map(|series_parts| series_parts.len())
+}
+
+/// parses an EVDS JSON response both at once and lazily, reads the latest value of each series lazily and gives the
+/// number of the observations.
+pub fn parse_json(response: &str) -> Option<usize> {
+
+    let dataset = Dataset::from_json(response);
+
+    if let Ok(lazy_dataset) = LazyDataset::index(response.to_string()) {
+        let series_codes = dataset.as_ref().map(|dataset| dataset.series_codes.clone()).unwrap_or_default();
+
+        for series_code in &series_codes { let _ = lazy_dataset.latest_value(series_code); }
+
+        let _ = lazy_dataset.materialize(&series_codes, 0..lazy_dataset.len());
+    }
+
+    dataset.ok().map(|dataset| dataset.observations.len())
+}
+
+/// parses an EVDS CSV response and gives the number of the observations.
+pub fn parse_csv(response: &str) -> Option<usize> {
+    Dataset::from_csv(response).ok().map(|dataset| dataset.observations.len())
+}
+
+/// parses a response of any return format, recognized by its first character, and gives the number of the
+/// observations.
+pub fn parse_response(response: &str) -> Option<usize> {
+    Dataset::from_response(response).ok().map(|dataset| dataset.observations.len())
+}
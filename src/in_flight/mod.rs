@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use crate::deadline;
+use crate::error::ReturnError;
+
+
+/// is the result shared by the calls coalesced into a request, which is None while the request is made.
+type SharedResult = Mutex<Option<Result<String, ReturnError>>>;
+
+/// is the url of a request together with the annotation of the pre-request hook, since the requests of a url annotated
+/// differently are different requests.
+type FlightKey = (String, Option<String>);
+
+/// is a request being made for a url, which the other calls of the same url wait for.
+#[derive(Default)]
+struct Flight {
+    result: SharedResult,
+    landed: Condvar,
+}
+
+/// is the request of each url and annotation being made at the moment.
+static IN_FLIGHT: Mutex<Option<HashMap<FlightKey, Arc<Flight>>>> = Mutex::new(None);
+
+
+/// completes the flight with an error and removes it when the request panics, so the waiting calls do not wait forever.
+struct FlightGuard {
+    key: FlightKey,
+    flight: Arc<Flight>,
+}
+
+impl Drop for FlightGuard {
+    fn drop(&mut self) {
+
+        let mut result = lock(&self.flight.result);
+
+        if result.is_none() { *result = Some(Err(ReturnError::UnableToRequest)); }
+
+        drop(result);
+
+        if let Some(flights) = lock(&IN_FLIGHT).as_mut() { flights.remove(&self.key); }
+
+        self.flight.landed.notify_all();
+    }
+}
+
+
+/// makes the request of the url unless the same url with the same annotation is already being requested by another
+/// thread, in which case the call waits for that request and shares its response, so that multi-threaded consumers
+/// asking for the same data at once cost a single request.
+///
+/// The calls arriving after the request ends make their own requests. The errors are shared as well. The admission and
+/// the pre-request hook of each call are run by the caller before, so that a call waiting for another one is not
+/// exempted from them.
+///
+/// # Error
+///
+/// This function returns `DeadlineExceeded` error when the deadline of the waiting call passes before the request
+/// ends.
+pub(crate) fn coalesce<F>(url: &str, annotation: Option<&str>, request: F) -> Result<String, ReturnError>
+where
+    F: FnOnce() -> Result<String, ReturnError>
+{
+
+    let key = (url.to_string(), annotation.map(str::to_string));
+
+    let (flight, is_leader) = {
+        let mut flights = lock(&IN_FLIGHT);
+        let flights = flights.get_or_insert_with(HashMap::new);
+
+        match flights.get(&key) {
+            Some(flight) => (flight.clone(), false),
+            None => {
+                let flight = Arc::new(Flight::default());
+
+                flights.insert(key.clone(), flight.clone());
+
+                (flight, true)
+            },
+        }
+    };
+
+    if !is_leader { return wait_for_landing(&flight); }
+
+    let guard = FlightGuard { key, flight };
+
+    let response = request();
+
+    *lock(&guard.flight.result) = Some(response.clone());
+
+    response
+}
+
+/// waits until the request of the flight ends and gives a copy of its result. The wait is bounded by the deadline of
+/// the call.
+fn wait_for_landing(flight: &Flight) -> Result<String, ReturnError> {
+
+    let mut result = lock(&flight.result);
+
+    loop {
+        if let Some(result) = result.as_ref() { return result.clone(); }
+
+        result = match deadline::get_remaining()? {
+            Some(remaining) => match flight.landed.wait_timeout(result, remaining) {
+                Ok((result, _)) => result,
+                Err(poisoned) => poisoned.into_inner().0,
+            },
+            None => match flight.landed.wait(result) {
+                Ok(result) => result,
+                Err(poisoned) => poisoned.into_inner(),
+            },
+        };
+    }
+}
+
+/// locks the mutex by recovering a poisoned lock since a result is either set or not.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+    use std::thread;
+    use std::time::Duration;
+    #[cfg(feature = "export")]
+    use std::time::Instant;
+
+    #[cfg(feature = "export")]
+    use crate::deadline::Deadline;
+
+    #[test]
+    fn should_coalesce_identical_requests() {
+        let request_count = AtomicUsize::new(0);
+        let barrier = Barrier::new(4);
+
+        let responses: Vec<Result<String, ReturnError>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| {
+                    barrier.wait();
+
+                    coalesce("https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S", None, || {
+                        request_count.fetch_add(1, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(200));
+
+                        Ok("response".to_string())
+                    })
+                }))
+                .collect();
+
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+
+        assert_eq!(1, request_count.load(Ordering::SeqCst));
+        assert!(responses.iter().all(|response| response.as_deref().ok() == Some("response")));
+
+        let response = coalesce("https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S", None, || {
+            Err(ReturnError::NotFound)
+        });
+
+        assert!(matches!(response, Err(ReturnError::NotFound)));
+
+        let barrier = Barrier::new(2);
+
+        thread::scope(|scope| {
+            for annotation in [Some("X-Tenant: first"), Some("X-Tenant: second")] {
+                let barrier = &barrier;
+                let request_count = &request_count;
+
+                scope.spawn(move || {
+                    coalesce("https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S", annotation, || {
+                        barrier.wait();
+                        request_count.fetch_add(1, Ordering::SeqCst);
+
+                        Ok("response".to_string())
+                    })
+                });
+            }
+        });
+
+        assert_eq!(3, request_count.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[cfg(feature = "export")]
+    fn should_stop_waiting_at_deadline() {
+        let url = "https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.EUR.S";
+        let barrier = Barrier::new(2);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                coalesce(url, None, || {
+                    barrier.wait();
+                    thread::sleep(Duration::from_millis(500));
+
+                    Ok("response".to_string())
+                })
+            });
+
+            barrier.wait();
+
+            let _call = deadline::inherit_call(Some(Deadline::new(Instant::now(), Duration::from_millis(50))));
+            let start = Instant::now();
+
+            let response = coalesce(url, None, || Ok("own response".to_string()));
+
+            assert!(matches!(response, Err(ReturnError::DeadlineExceeded(_))));
+            assert!(start.elapsed() < Duration::from_millis(400));
+        });
+    }
+}
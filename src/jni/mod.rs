@@ -0,0 +1,391 @@
+use std::convert::TryFrom;
+use std::ptr;
+
+use jni::JNIEnv;
+use jni::objects::{JClass, JString};
+use jni::sys::{jboolean, jbyteArray, jint, jobject, jstring};
+use libc::c_uint;
+
+use crate::evds_c::common_entities::{TcmbEvdsInput, TcmbEvdsResult, TcmbEvdsReturnFormat};
+use crate::evds_c::error_handling::{ReturnErrorC, shield_or};
+use crate::traits::ReadingCEnum;
+use crate::{tcmb_evds_c_get_categories, tcmb_evds_c_get_data, tcmb_evds_c_get_data_group};
+use crate::{tcmb_evds_c_get_series_list, tcmb_evds_c_result_free};
+
+
+/// is the class of the exceptions thrown to Java for the errors of the library.
+const EXCEPTION_CLASS: &str = "com/github/asari555/tcmbevds/EvdsException";
+
+/// is the class thrown for the arguments that cannot be passed to the library, e.g. an unknown return format.
+const ILLEGAL_ARGUMENT_CLASS: &str = "java/lang/IllegalArgumentException";
+
+
+/// copies the Java string in UTF-8 and passes it to the body as an input. A null string or a string that could not be
+/// read is passed as a NULL input, so that the library reports it as a parameter error.
+///
+/// The modified UTF-8 of the JVM is decoded, so the supplementary characters reach the library in standard UTF-8 and
+/// an embedded NUL character is reported by the library instead of cutting the string.
+fn with_input<T, F>(env: &JNIEnv, string: &JString, body: F) -> T
+where
+    F: FnOnce(TcmbEvdsInput) -> T
+{
+
+    let null_input = TcmbEvdsInput { input_ptr: ptr::null(), string_capacity: 0 };
+
+    if string.is_null() { return body(null_input); }
+
+    // the JVM only passes the instances of `java.lang.String` for the string parameters of the native methods.
+    let text: String = match unsafe { env.get_string_unchecked(string) } {
+        Ok(text) => text.into(),
+        Err(_) => return body(null_input),
+    };
+
+    let mut bytes = text.into_bytes();
+    let string_capacity = bytes.len() as _;
+
+    bytes.push(0);
+
+    body(TcmbEvdsInput { input_ptr: bytes.as_ptr() as *const _, string_capacity })
+}
+
+/// creates a Java string of the UTF-8 text, which is encoded in modified UTF-8 for the JVM. Null is returned when the
+/// string could not be created.
+fn new_string(env: &mut JNIEnv, text: &[u8]) -> jobject {
+    match env.new_string(String::from_utf8_lossy(text)) {
+        Ok(string) => string.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// creates a Java byte array of the bytes. Null is returned when the array could not be allocated.
+fn new_byte_array(env: &mut JNIEnv, bytes: &[u8]) -> jobject {
+    match env.byte_array_from_slice(bytes) {
+        Ok(array) => array.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// throws an exception of the class with the message unless an exception is already pending. A failed lookup of the
+/// class leaves `NoClassDefFoundError` pending in the JVM.
+fn throw(env: &mut JNIEnv, class_name: &str, message: &str) {
+
+    if env.exception_check().unwrap_or(true) { return; }
+
+    let _ = env.throw_new(class_name, message);
+}
+
+/// converts the result into a Java object via the conversion, or throws `EvdsException` with the message and the
+/// detail of the error. The result is released in both cases.
+fn convert_result<F>(env: &mut JNIEnv, result: TcmbEvdsResult, convert: F) -> jobject
+where
+    F: FnOnce(&mut JNIEnv, &[u8]) -> jobject
+{
+
+    let (error_type, text, detail) = unsafe { read_result(&result) };
+
+    let object = match error_type {
+        ReturnErrorC::NoError => convert(env, text),
+        _ => {
+            let message = String::from_utf8_lossy(text);
+
+            match detail {
+                Some(detail) => {
+                    throw(env, EXCEPTION_CLASS, &format!("{}\n{}", message, String::from_utf8_lossy(detail)))
+                },
+                None => throw(env, EXCEPTION_CLASS, &message),
+            }
+
+            ptr::null_mut()
+        },
+    };
+
+    unsafe { tcmb_evds_c_result_free(result) };
+
+    object
+}
+
+/// reads the error type, the text and the detail of the result without releasing it.
+unsafe fn read_result(result: &TcmbEvdsResult) -> (ReturnErrorC, &[u8], Option<&[u8]>) {
+
+    let read_bytes = |bytes_ptr: *mut u8, capacity| match bytes_ptr.is_null() {
+        true => None,
+        false => Some(std::slice::from_raw_parts(bytes_ptr as *const u8, capacity as usize)),
+    };
+
+    let text = read_bytes(result.output_ptr, result.string_capacity).unwrap_or_default();
+
+    (result.error_type, text, read_bytes(result.detail_ptr, result.detail_capacity))
+}
+
+/// checks the constant of the return format in `TcmbEvds.java`, whose values are the ones of the enum of the library.
+fn to_return_format(return_format: jint) -> Option<c_uint> {
+
+    let return_format = c_uint::try_from(return_format).ok()?;
+
+    TcmbEvdsReturnFormat::from_value(return_format).map(|_| return_format)
+}
+
+/// makes the request with the converted return format, or throws `IllegalArgumentException` for an unknown one.
+///
+/// The request is shielded, so a panic returns null to Java instead of unwinding into the JVM.
+fn request<R, C>(env: &mut JNIEnv, return_format: jint, make_request: R, convert: C) -> jobject
+where
+    R: FnOnce(&mut JNIEnv, c_uint) -> TcmbEvdsResult,
+    C: FnOnce(&mut JNIEnv, &[u8]) -> jobject,
+{
+
+    shield_or(ptr::null_mut(), || {
+        let return_format = match to_return_format(return_format) {
+            Some(return_format) => return_format,
+            None => {
+                throw(env, ILLEGAL_ARGUMENT_CLASS, &format!("Unknown return format: {}.", return_format));
+
+                return ptr::null_mut();
+            },
+        };
+
+        let result = make_request(env, return_format);
+
+        convert_result(env, result, convert)
+    })
+}
+
+fn get_data(
+    env: &JNIEnv,
+    data_series: &JString,
+    date: &JString,
+    api_key: &JString,
+    return_format: c_uint,
+    ascii_mode: jboolean,
+) -> TcmbEvdsResult {
+    with_input(env, data_series, |data_series| {
+        with_input(env, date, |date| {
+            with_input(env, api_key, |api_key| {
+                tcmb_evds_c_get_data(data_series, date, api_key, return_format, ascii_mode != 0)
+            })
+        })
+    })
+}
+
+
+/// is `String TcmbEvds.getData(String dataSeries, String date, String apiKey, int returnFormat, boolean asciiMode)`.
+#[no_mangle]
+pub extern "system" fn Java_com_github_asari555_tcmbevds_TcmbEvds_getData<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    data_series: JString<'local>,
+    date: JString<'local>,
+    api_key: JString<'local>,
+    return_format: jint,
+    ascii_mode: jboolean,
+) -> jstring {
+    request(
+        &mut env,
+        return_format,
+        |env, return_format| get_data(env, &data_series, &date, &api_key, return_format, ascii_mode),
+        new_string,
+    )
+}
+
+/// is `byte[] TcmbEvds.getDataBytes(String dataSeries, String date, String apiKey, int returnFormat, boolean
+/// asciiMode)` giving the response in UTF-8 without the conversions of Java strings.
+#[no_mangle]
+pub extern "system" fn Java_com_github_asari555_tcmbevds_TcmbEvds_getDataBytes<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    data_series: JString<'local>,
+    date: JString<'local>,
+    api_key: JString<'local>,
+    return_format: jint,
+    ascii_mode: jboolean,
+) -> jbyteArray {
+    request(
+        &mut env,
+        return_format,
+        |env, return_format| get_data(env, &data_series, &date, &api_key, return_format, ascii_mode),
+        new_byte_array,
+    )
+}
+
+/// is `String TcmbEvds.getDataGroup(String dataGroup, String date, String apiKey, int returnFormat, boolean
+/// asciiMode)`.
+#[no_mangle]
+pub extern "system" fn Java_com_github_asari555_tcmbevds_TcmbEvds_getDataGroup<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    data_group: JString<'local>,
+    date: JString<'local>,
+    api_key: JString<'local>,
+    return_format: jint,
+    ascii_mode: jboolean,
+) -> jstring {
+    request(
+        &mut env,
+        return_format,
+        |env, return_format| {
+            with_input(env, &data_group, |data_group| {
+                with_input(env, &date, |date| {
+                    with_input(env, &api_key, |api_key| {
+                        tcmb_evds_c_get_data_group(data_group, date, api_key, return_format, ascii_mode != 0)
+                    })
+                })
+            })
+        },
+        new_string,
+    )
+}
+
+/// is `String TcmbEvds.getCategories(String apiKey, int returnFormat, boolean asciiMode)`.
+#[no_mangle]
+pub extern "system" fn Java_com_github_asari555_tcmbevds_TcmbEvds_getCategories<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    api_key: JString<'local>,
+    return_format: jint,
+    ascii_mode: jboolean,
+) -> jstring {
+    request(
+        &mut env,
+        return_format,
+        |env, return_format| {
+            with_input(env, &api_key, |api_key| tcmb_evds_c_get_categories(api_key, return_format, ascii_mode != 0))
+        },
+        new_string,
+    )
+}
+
+/// is `String TcmbEvds.getSeriesList(String code, String apiKey, int returnFormat, boolean asciiMode)`.
+#[no_mangle]
+pub extern "system" fn Java_com_github_asari555_tcmbevds_TcmbEvds_getSeriesList<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    code: JString<'local>,
+    api_key: JString<'local>,
+    return_format: jint,
+    ascii_mode: jboolean,
+) -> jstring {
+    request(
+        &mut env,
+        return_format,
+        |env, return_format| {
+            with_input(env, &code, |code| {
+                with_input(env, &api_key, |api_key| {
+                    tcmb_evds_c_get_series_list(code, api_key, return_format, ascii_mode != 0)
+                })
+            })
+        },
+        new_string,
+    )
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::ffi::{CStr, CString};
+    use std::mem;
+    use std::os::raw::c_char;
+
+    use jni::sys::{self, JNINativeInterface_, jclass};
+
+    thread_local! {
+        static THROWN_EXCEPTION: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+    }
+
+    // the strings of the fake JVM are C strings, and the classes are their names.
+    unsafe extern "system" fn get_string_utf_chars(
+        _env: *mut sys::JNIEnv,
+        string: jstring,
+        _is_copy: *mut jboolean,
+    ) -> *const c_char {
+        string as *const c_char
+    }
+
+    unsafe extern "system" fn release_string_utf_chars(_env: *mut sys::JNIEnv, _string: jstring, _: *const c_char) {}
+
+    unsafe extern "system" fn exception_check(_env: *mut sys::JNIEnv) -> jboolean {
+        THROWN_EXCEPTION.with(|thrown_exception| thrown_exception.borrow().is_some() as jboolean)
+    }
+
+    unsafe extern "system" fn find_class(_env: *mut sys::JNIEnv, name: *const c_char) -> jclass {
+        CString::from(CStr::from_ptr(name)).into_raw() as jclass
+    }
+
+    unsafe extern "system" fn throw_new(_env: *mut sys::JNIEnv, class: jclass, message: *const c_char) -> jint {
+
+        let class = CStr::from_ptr(class as *const c_char).to_string_lossy().to_string();
+        let message = CStr::from_ptr(message).to_string_lossy().to_string();
+
+        THROWN_EXCEPTION.with(|thrown_exception| *thrown_exception.borrow_mut() = Some((class, message)));
+
+        0
+    }
+
+    unsafe extern "system" fn delete_local_ref(_env: *mut sys::JNIEnv, object: jobject) {
+        drop(CString::from_raw(object as *mut c_char));
+    }
+
+    fn take_thrown_exception() -> Option<(String, String)> {
+        THROWN_EXCEPTION.with(|thrown_exception| thrown_exception.borrow_mut().take())
+    }
+
+    #[test]
+    fn should_throw_errors_to_java() {
+        let mut function_table: JNINativeInterface_ = unsafe { mem::zeroed() };
+
+        function_table.GetStringUTFChars = Some(get_string_utf_chars);
+        function_table.ReleaseStringUTFChars = Some(release_string_utf_chars);
+        function_table.ExceptionCheck = Some(exception_check);
+        function_table.FindClass = Some(find_class);
+        function_table.ThrowNew = Some(throw_new);
+        function_table.DeleteLocalRef = Some(delete_local_ref);
+
+        let mut functions: *const JNINativeInterface_ = &function_table;
+        let raw_env: *mut sys::JNIEnv = &mut functions;
+
+        let data_series = CString::new("").unwrap();
+        let date = CString::new("13-12-2011").unwrap();
+        let api_key = CString::new("users_api_key").unwrap();
+
+        let get_data = |return_format| unsafe {
+            Java_com_github_asari555_tcmbevds_TcmbEvds_getData(
+                JNIEnv::from_raw(raw_env).unwrap(),
+                JClass::from_raw(ptr::null_mut()),
+                JString::from_raw(data_series.as_ptr() as jstring),
+                JString::from_raw(date.as_ptr() as jstring),
+                JString::from_raw(api_key.as_ptr() as jstring),
+                return_format,
+                0,
+            )
+        };
+
+        assert!(get_data(0).is_null());
+
+        let (class, message) = take_thrown_exception().unwrap();
+
+        assert_eq!(EXCEPTION_CLASS, class);
+        assert!(message.starts_with("Error"));
+
+        assert!(get_data(9).is_null());
+        assert_eq!(
+            Some((ILLEGAL_ARGUMENT_CLASS.to_string(), "Unknown return format: 9.".to_string())),
+            take_thrown_exception()
+        );
+
+        // "\u{1F600}" is written as a surrogate pair in the modified UTF-8 of the JVM.
+        let modified_utf8 = CString::new([0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80]).unwrap();
+
+        let text = unsafe {
+            let env = JNIEnv::from_raw(raw_env).unwrap();
+            let string = JString::from_raw(modified_utf8.as_ptr() as jstring);
+
+            with_input(&env, &string, |input| {
+                std::slice::from_raw_parts(input.input_ptr as *const u8, input.string_capacity as usize).to_vec()
+            })
+        };
+
+        assert_eq!("\u{1F600}".as_bytes(), &text[..]);
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{Value, json};
+
+use crate::error::ReturnError;
+
+
+/// is the number of the last characters of a key shown in the metrics and the usage reports.
+const SHOWN_KEY_CHARACTERS: usize = 4;
+
+
+/// specifies when the calls given an empty api key move on to the next key of the pool.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum KeyRotation {
+    /// uses each key in turn, spreading the requests evenly over the keys.
+    RoundRobin,
+    /// keeps using a key until EVDS rate limits a request made with it.
+    OnQuotaError,
+}
+
+/// counts the requests made with a key of the pool and the requests rate limited by EVDS.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct KeyUsage {
+    /// is the key whose characters except the last four are masked, e.g. "****a1b2".
+    pub(crate) masked_key: String,
+    pub(crate) requests: u64,
+    pub(crate) quota_errors: u64,
+}
+
+/// is what the session learned about a key from the requests made with it and its validity checks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct KeyStatus {
+    /// is None until the key is checked via [`tcmb_evds_c_key_status`](crate::tcmb_evds_c_key_status).
+    pub(crate) is_valid: Option<bool>,
+    /// is the time of the last response received with the key in seconds since the Unix epoch.
+    pub(crate) last_success: Option<u64>,
+    pub(crate) rate_limit_count: u64,
+    /// is the time in seconds since the Unix epoch until which EVDS asked the key to wait after a rate limit.
+    pub(crate) rate_limited_until: Option<u64>,
+}
+
+impl KeyStatus {
+    /// tells whether the key should be avoided at the time, namely it is invalid or still rate limited.
+    fn is_unusable(&self, now: u64) -> bool {
+        self.is_valid == Some(false) || self.rate_limited_until.is_some_and(|until| until > now)
+    }
+}
+
+struct PooledKey {
+    key: String,
+    requests: u64,
+    quota_errors: u64,
+}
+
+/// keeps several api keys of a session and picks the key of each call given an empty api key, so that the teams
+/// sharing infrastructure spread their requests over their registered keys.
+///
+/// The status of every key used in the session is kept as well, pooled or not, so that the keys known to be invalid
+/// or rate limited are skipped while another key is usable.
+pub(crate) struct KeyPool {
+    keys: Vec<PooledKey>,
+    rotation: KeyRotation,
+    /// is the index of the key given to the next call.
+    next_key: usize,
+    statuses: BTreeMap<String, KeyStatus>,
+}
+
+impl KeyPool {
+    pub(crate) const fn new() -> KeyPool {
+        KeyPool { keys: Vec::new(), rotation: KeyRotation::RoundRobin, next_key: 0, statuses: BTreeMap::new() }
+    }
+
+    /// replaces the keys of the pool and clears their usage. An empty list of keys disables the pool.
+    pub(crate) fn set_keys(&mut self, keys: Vec<String>, rotation: KeyRotation) {
+        self.keys = keys
+            .into_iter()
+            .map(|key| PooledKey { key, requests: 0, quota_errors: 0 })
+            .collect();
+        self.rotation = rotation;
+        self.next_key = 0;
+    }
+
+    /// gives the key of the next call, skipping the invalid and the rate limited keys unless all keys are so. None is 
+    /// returned when the pool is empty.
+    pub(crate) fn select_key(&mut self, now: u64) -> Option<String> {
+
+        if self.keys.is_empty() { return None; }
+
+        let key_index = (0..self.keys.len())
+            .map(|offset| (self.next_key + offset) % self.keys.len())
+            .find(|index| !self.get_status(&self.keys[*index].key).is_unusable(now))
+            .unwrap_or(self.next_key);
+
+        self.next_key = match self.rotation {
+            KeyRotation::RoundRobin => (key_index + 1) % self.keys.len(),
+            KeyRotation::OnQuotaError => key_index,
+        };
+
+        Some(self.keys[key_index].key.clone())
+    }
+
+    /// counts a request made with the key and updates the status of the key with its response. A rate limited key is 
+    /// skipped by the next call.
+    ///
+    /// Only the keys in the pool are counted, so the calls given their own api keys only update their statuses.
+    pub(crate) fn record_request(&mut self, key: &str, response: &Result<String, ReturnError>, now: u64) {
+
+        let status = self.statuses.entry(key.to_string()).or_default();
+
+        let rate_limit_wait = match response {
+            Ok(_) => {
+                status.last_success = Some(now);
+
+                None
+            },
+            Err(ReturnError::RateLimitedByServer(seconds)) => {
+                status.rate_limit_count += 1;
+                status.rate_limited_until = Some(now + seconds);
+
+                Some(seconds)
+            },
+            Err(_) => None,
+        };
+
+        let index = match self.keys.iter().position(|pooled_key| pooled_key.key == key) {
+            Some(index) => index,
+            None => return,
+        };
+
+        self.keys[index].requests += 1;
+
+        if rate_limit_wait.is_none() { return; }
+
+        self.keys[index].quota_errors += 1;
+
+        if self.next_key == index || self.rotation == KeyRotation::OnQuotaError {
+            self.next_key = (index + 1) % self.keys.len();
+        }
+    }
+
+    /// records the outcome of a validity check of the key.
+    pub(crate) fn record_validity(&mut self, key: &str, is_valid: bool) {
+        self.statuses.entry(key.to_string()).or_default().is_valid = Some(is_valid);
+    }
+
+    /// gives the status of the key, which is empty for a key not used in the session.
+    pub(crate) fn get_status(&self, key: &str) -> KeyStatus {
+        self.statuses.get(key).cloned().unwrap_or_default()
+    }
+
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        self.keys.iter().any(|pooled_key| pooled_key.key == key)
+    }
+
+    pub(crate) fn get_usage(&self) -> Vec<KeyUsage> {
+        self.keys
+            .iter()
+            .map(|pooled_key| KeyUsage {
+                masked_key: mask_key(&pooled_key.key),
+                requests: pooled_key.requests,
+                quota_errors: pooled_key.quota_errors,
+            })
+            .collect()
+    }
+}
+
+static KEY_POOL: Mutex<KeyPool> = Mutex::new(KeyPool::new());
+
+
+/// gives access to the process wide key pool.
+///
+/// A poisoned lock is recovered because a panic cannot leave a key half inserted.
+pub(crate) fn get_key_pool() -> MutexGuard<'static, KeyPool> {
+    match KEY_POOL.lock() {
+        Ok(key_pool) => key_pool,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// counts the request made via the url in the usage of its key if the key is in the pool.
+pub(crate) fn record_response(url: &str, response: &Result<String, ReturnError>) {
+
+    let key = match find_key(url) {
+        Some(key) => key,
+        None => return,
+    };
+
+    get_key_pool().record_request(key, response, now_seconds());
+}
+
+/// gives the current time in seconds since the Unix epoch.
+pub(crate) fn now_seconds() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// stringifies the usage of the keys as a JSON array, e.g. `[{"key":"****a1b2","requests":120,"quotaErrors":1}]`.
+pub(crate) fn usage_to_json(key_usage: &[KeyUsage]) -> String {
+
+    let key_usage: Vec<Value> = key_usage
+        .iter()
+        .map(|usage| json!({ "key": usage.masked_key, "requests": usage.requests, "quotaErrors": usage.quota_errors }))
+        .collect();
+
+    Value::from(key_usage).to_string()
+}
+
+/// stringifies the status of the key as a JSON object, e.g. 
+/// `{"key":"****a1b2","pooled":true,"valid":true,"lastSuccess":1700000000,"rateLimitCount":0,"rateLimitedUntil":null}`.
+pub(crate) fn status_to_json(key: &str, status: &KeyStatus, is_pooled: bool) -> String {
+    json!({
+        "key": mask_key(key),
+        "pooled": is_pooled,
+        "valid": status.is_valid,
+        "lastSuccess": status.last_success,
+        "rateLimitCount": status.rate_limit_count,
+        "rateLimitedUntil": status.rate_limited_until,
+    })
+    .to_string()
+}
+
+/// finds the value of the api key in an already built url.
+fn find_key(url: &str) -> Option<&str> {
+    url.split('&').find_map(|pair| {
+        // the first parameter of a url follows the resource, e.g. "categories/key=...".
+        let name_start = pair.rfind('/').map_or(0, |index| index + 1);
+
+        pair[name_start..].strip_prefix("key=")
+    })
+}
+
+/// masks all characters of the key except the last four.
+fn mask_key(key: &str) -> String {
+
+    let shown_start = key.char_indices().rev().nth(SHOWN_KEY_CHARACTERS - 1).map_or(0, |(index, _)| index);
+
+    format!("****{}", &key[shown_start..])
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_rotate_keys_and_count_usage() {
+        let mut key_pool = KeyPool::new();
+
+        assert_eq!(None, key_pool.select_key(0));
+
+        key_pool.set_keys(vec!["FIRSTKEY".to_string(), "SECONDKEY".to_string()], KeyRotation::RoundRobin);
+
+        assert_eq!(Some("FIRSTKEY"), key_pool.select_key(0).as_deref());
+        assert_eq!(Some("SECONDKEY"), key_pool.select_key(0).as_deref());
+        assert_eq!(Some("FIRSTKEY"), key_pool.select_key(0).as_deref());
+
+        key_pool.set_keys(vec!["FIRSTKEY".to_string(), "SECONDKEY".to_string()], KeyRotation::OnQuotaError);
+
+        key_pool.record_request("FIRSTKEY", &Ok(String::new()), 100);
+        key_pool.record_request("OTHERKEY", &Ok(String::new()), 100);
+
+        assert_eq!(Some("FIRSTKEY"), key_pool.select_key(100).as_deref());
+
+        key_pool.record_request("FIRSTKEY", &Err(ReturnError::RateLimitedByServer(60)), 110);
+
+        assert_eq!(Some("SECONDKEY"), key_pool.select_key(120).as_deref());
+
+        key_pool.record_validity("SECONDKEY", false);
+
+        // every key is unusable, so the next key is given anyway.
+        assert_eq!(Some("SECONDKEY"), key_pool.select_key(120).as_deref());
+        assert_eq!(Some("FIRSTKEY"), key_pool.select_key(170).as_deref());
+        assert_eq!(
+            KeyStatus { is_valid: None, last_success: Some(100), rate_limit_count: 1, rate_limited_until: Some(170) },
+            key_pool.get_status("FIRSTKEY")
+        );
+        assert_eq!(Some(100), key_pool.get_status("OTHERKEY").last_success);
+        assert!(key_pool.contains("SECONDKEY") && !key_pool.contains("OTHERKEY"));
+        assert_eq!(
+            vec![
+                KeyUsage { masked_key: "****TKEY".to_string(), requests: 2, quota_errors: 1 },
+                KeyUsage { masked_key: "****DKEY".to_string(), requests: 0, quota_errors: 0 },
+            ],
+            key_pool.get_usage()
+        );
+        assert_eq!(
+            r#"[{"key":"****TKEY","quotaErrors":1,"requests":2},{"key":"****DKEY","quotaErrors":0,"requests":0}]"#,
+            usage_to_json(&key_pool.get_usage())
+        );
+        assert_eq!(
+            json!({
+                "key": "****TKEY", "pooled": true, "valid": null,
+                "lastSuccess": 100, "rateLimitCount": 1, "rateLimitedUntil": 170,
+            }),
+            serde_json::from_str::<Value>(&status_to_json("FIRSTKEY", &key_pool.get_status("FIRSTKEY"), true)).unwrap()
+        );
+        assert_eq!(Some("ABC"), find_key("https://evds2.tcmb.gov.tr/service/evds/categories/key=ABC&type=json"));
+        assert_eq!("****AB", mask_key("AB"));
+    }
+}
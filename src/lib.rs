@@ -217,6 +217,11 @@ mod common;
 /// # }
 /// ```
 mod date;
+/// parses EVDS JSON responses into datasets made of observations and series columns.
+///
+/// [`Dataset`](crate::dataset::Dataset) is the common parsed representation used by the functions processing the 
+/// response content instead of handing the raw response text to users.
+mod dataset;
 /// contains specified error options that are returned from the functions of 
 /// [`evds_basic`](crate::evds_basic) and [`evds_currency`](crate::evds_currency) to illustrate why the error occurs.
 ///
@@ -227,6 +232,33 @@ mod error;
 /// provides most of the EVDS web services except requesting advanced currency data that means currency data with 
 /// frequency formulas.
 mod evds_basic;
+/// contains the pure logic of the requests, namely the date validation, the currency series parsing and the url 
+/// building, which depends only on `core` and `alloc`.
+///
+/// Firmware and plugin environments making the requests via their own HTTP clients can reuse the checks and the urls 
+/// of the library by vendoring the module into a `no_std` crate, while the rest of the crate layers the errors, the 
+/// spans and the requests on top of it.
+///
+/// # Usage
+///
+/// ```
+/// # use std::borrow::Cow;
+/// # use tcmb_evds_c::evds_core::{date, series, url};
+/// #
+///     assert!(date::is_date_valid("13-12-2011"));
+///
+///     let series_parts = series::split_currency_series("TP.DK.USD.S.YTL").unwrap();
+///
+///     assert_eq!("USD", series_parts.currency_code);
+///
+///     let parameters = [(Cow::Borrowed("series"), "TP.DK.USD.S".to_string())];
+///
+///     assert_eq!(
+///         "https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S",
+///         url::build_url("", &parameters, &[], false)
+///     );
+/// ```
+pub mod evds_core;
 /// provides only currency operations with methods of [`CurrencySeries`] and [`MultipleCurrencySeries`].
 ///
 /// This module built on two main structures and their methods operating currency services. The basic difference between
@@ -242,11 +274,14 @@ mod evds_basic;
 ///
 /// Usage schematic and hierarchy of this module:
 /// 
-/// - [`CurrencySeries`] -> [`get_data`] ( [`Evds`] ) or [`get_advanced_data`] ( [`Evds`], [`AdvancedProcesses`] )
+/// - [`CurrencySeries`] -> [`get_data`] ( [`Evds`] )
 ///
 ///     > `CurrencySeries` requires below struct as a difference:
 ///     > + [`CurrencyCode`]
 ///
+/// - series codes, e.g. of [`build_currency_series_codes`] -> [`get_combined_advanced_data`] ( [`DatePreference`], 
+///   [`Evds`], [`AdvancedProcesses`] )
+///
 /// - [`MultipleCurrencySeries`] -> [`get_multiple_data`] ( [`Evds`])
 ///
 ///     > `MultipleCurrencySeries` requires below struct as a difference:
@@ -262,7 +297,7 @@ mod evds_basic;
 ///
 /// - [`get_data`]
 ///
-/// - [`get_advanced_data`]
+/// - [`get_combined_advanced_data`]
 /// 
 /// - [`get_multiple_data`]
 ///
@@ -286,8 +321,9 @@ mod evds_basic;
 /// [`CurrencyCodes`]: crate::evds_currency::CurrencyCodes
 ///
 /// [`get_data`]: crate::evds_currency::CurrencySeries::get_data
-/// [`get_advanced_data`]: crate::evds_currency::CurrencySeries::get_advanced_data
 /// [`get_multiple_data`]: crate::evds_currency::MultipleCurrencySeries::get_multiple_data
+/// [`build_currency_series_codes`]: crate::evds_currency::build_currency_series_codes
+/// [`get_combined_advanced_data`]: crate::evds_currency::get_combined_advanced_data
 mod evds_currency;
 mod traits;
 /// provides auxiliary enums and structures to FFI to use abilities of the EVDS web services in C language.
@@ -309,22 +345,133 @@ mod traits;
 /// [`common_entities`]: crate::evds_c::common_entities
 /// [`error_handling`]: crate::evds_c::error_handling
 pub mod evds_c;
+/// exposes the operations to Java and Android via JNI when the `jni` feature is on.
+#[cfg(feature = "jni")]
+mod jni;
+/// exposes promise returning functions to Node.js via napi-rs when the `napi` feature is on.
+#[cfg(feature = "napi")]
+mod napi;
+/// exposes the internals measured by the Criterion benchmarks in `benches` when the `bench` feature is on.
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench;
+/// gives the metadata of the build, namely the commit, the features, the target and the HTTP backend.
+mod build_info;
+/// fails the requests fast for a cool-down period after repeated transport failures during EVDS outages.
+mod circuit_breaker;
+/// tracks the pointers handed to C and aborts on double frees and uses after free when the `debug_ffi` feature is on.
+mod debug_ffi;
+/// limits the overall duration of each FFI call including its retries and waits, and the duration of each attempt.
+mod deadline;
+/// exposes the parsers of the inputs and the responses to the cargo-fuzz targets in `fuzz` when the `fuzz` feature is
+/// on.
+#[cfg(feature = "fuzz")]
+#[doc(hidden)]
+pub mod fuzz;
+/// coalesces the requests of the same url made at once by several threads into a single request.
+mod in_flight;
+/// keeps the api keys of the session, rotates the calls among them and counts their usage.
+mod key_pool;
+/// counts the requests, received bytes, retries, cache lookups, errors and latencies of the process.
+mod metrics;
+/// retries the requests rate limited by EVDS with 429 responses after the wait suggested by the server.
+mod rate_limit;
+/// records the responses of EVDS into a directory and replays them to reproduce the calls without EVDS.
+mod recording;
 #[cfg(feature = "async_mode")]
 mod request_async;
 #[cfg(feature = "sync_mode")]
 mod request_sync;
+/// lets the interactive requests preempt the bulk requests of batch operations via a bounded queue of bulk requests.
+mod scheduler;
+/// enters the spans of the operational functions and exports the spans to an OTLP collector when the `tracing` 
+/// feature is on.
+mod spans;
+/// makes the transfers of the requests via the HTTP backend chosen at compile time, namely libcurl or ureq.
+mod transport;
+
+#[cfg(not(any(feature = "curl_http", feature = "pure_rust_http")))]
+compile_error!("An HTTP backend is required, namely the `curl_http` or the `pure_rust_http` feature.");
 
 
+extern crate alloc;
 extern crate libc;
 
 
-use crate::evds_currency::{CurrencySeries, frequency_formulas};
+use crate::evds_currency::frequency_formulas;
+#[cfg(feature = "currency")]
+use crate::evds_currency::{build_currency_series, build_currency_series_codes, get_combined_advanced_data};
+#[cfg(feature = "currency")]
+use crate::evds_currency::basket::CurrencyBasket;
 use crate::evds_c::{common_entities::*, error_handling::*};
+#[cfg(feature = "parse")]
+use crate::evds_c::dataset_entities::{TcmbEvdsDataset, TcmbEvdsJsonBackend, TcmbEvdsLayout};
+use crate::evds_c::handle_entities::TcmbEvdsHandle;
+#[cfg(feature = "parse")]
+use crate::evds_c::hook_entities::{ResponseHook, TcmbEvdsResponseHook, get_response_hook};
+use crate::evds_c::hook_entities::{RequestHook, TcmbEvdsRequestHook, get_request_hook};
+use crate::evds_c::calendar_entities::TcmbEvdsPublicationDay;
+use crate::evds_c::health_entities::{TcmbEvdsCircuitState, TcmbEvdsHealth};
+use crate::evds_c::key_entities::TcmbEvdsKeyRotation;
+use crate::evds_c::metrics_entities::TcmbEvdsMetrics;
+use crate::evds_c::memory_entities::{MEMORY_COUNTERS, TcmbEvdsMemoryStats};
+use crate::evds_c::request_entities::{TcmbEvdsRequest, to_input};
+use crate::evds_c::recording_entities::TcmbEvdsRecordingMode;
+use crate::evds_c::session_entities::{TcmbEvdsDatePolicy, TcmbEvdsSessionDefaults};
+use crate::evds_c::scheduler_entities::TcmbEvdsPriority;
+#[cfg(feature = "catalog")]
+use crate::evds_c::tree_entities::TcmbEvdsCategoryTree;
+use crate::evds_c::settings::SessionDate;
+#[cfg(feature = "parse")]
+use crate::dataset::Dataset;
+#[cfg(feature = "catalog")]
+use crate::dataset::header::HeaderLanguage;
+#[cfg(feature = "parse")]
+use crate::dataset::unit::SeriesUnit;
+#[cfg(feature = "parse")]
+use crate::dataset::json_backend::IS_SIMD_JSON_AVAILABLE;
+#[cfg(feature = "parse")]
+use crate::dataset::checksum::compute_checksum;
+#[cfg(feature = "catalog")]
+use crate::dataset::series_manifest::diff_manifests;
+#[cfg(feature = "cache")]
+use crate::dataset::cache::{CacheEndpoint, get_response_cache};
+#[cfg(feature = "cache")]
+use crate::dataset::store::{ObservationStore, get_observation_store};
+#[cfg(feature = "parse")]
+use crate::dataset::expression::Expression;
+#[cfg(feature = "parse")]
+use crate::dataset::virtual_series::{check_virtual_series_name, get_virtual_series};
 use crate::evds_c::advanced_entities::{TcmbEvdsAggregationType, TcmbEvdsDataFrequency, TcmbEvdsFormula};
-use crate::evds_c::{generate_date_preference, generate_evds, return_response};
-use crate::evds_c::data_series::parse_series;
-use crate::traits::converting_to_rust_enum::ConvertingToRustEnum;
-use libc::c_uint;
+use crate::evds_c::advanced_entities::TcmbEvdsHugeRequestPolicy;
+#[cfg(feature = "currency")]
+use crate::evds_c::currency_entities::{TcmbEvdsCurrencyCode, TcmbEvdsExchangeSide};
+#[cfg(feature = "parse")]
+use crate::evds_c::pipeline_entities::read_post_processors;
+use crate::evds_c::{generate_date_preferences, generate_evds, request_for_each_date_preference};
+use crate::evds_c::{convert_enum, read_enum};
+use crate::evds_c::{post_process_response, return_response};
+use crate::evds_c::{audit_each_date_preference, generate_unverified_evds};
+use crate::evds_c::{check_evds_parameters, check_parameter_text};
+use crate::evds_c::check_date_span;
+#[cfg(any(feature = "currency", feature = "parse", feature = "export"))]
+use crate::evds_c::{DateKind, check_date_kind};
+#[cfg(feature = "currency")]
+use crate::evds_c::data_series::parse_multiple_series;
+#[cfg(feature = "catalog")]
+use crate::evds_c::data_series::describe_series;
+#[cfg(feature = "parse")]
+use crate::evds_c::settings::get_today;
+use crate::date::calendar::{CalendarDate, MAX_UTC_OFFSET_MINUTES, MIN_UTC_OFFSET_MINUTES};
+use crate::date::publication_calendar;
+use crate::traits::{ConvertingToRustEnum, ReadingCEnum};
+use libc::{c_int, c_uint, c_ulong};
+#[cfg(feature = "cache")]
+use std::fs;
+use std::os::raw::c_char;
+use std::ptr;
+use std::ffi::{CString, c_void};
+use std::time::Duration;
 
 
 /// gets data requested via any valid data series from EVDS.
@@ -388,54 +535,474 @@ pub extern "C" fn tcmb_evds_c_get_data(
     data_series: TcmbEvdsInput,
     date: TcmbEvdsInput,
     api_key: TcmbEvdsInput,
-    return_format: TcmbEvdsReturnFormat,
+    return_format: c_uint,
     ascii_mode: bool
 ) -> TcmbEvdsResult {
 
-    let (rust_data_series, data_series_error_state) = data_series.get_input("data_series");
-    let (rust_date, date_error_state) = date.get_input("date");
+    shield_result("tcmb_evds_c_get_data", || {
+        let mut parameter_errors = ParameterErrors::new();
 
+        let rust_data_series = parameter_errors.read_input(&data_series, "data_series");
+        let rust_date = parameter_errors.read_input(&date, "date");
 
-    let parameter_error = ReturnErrorC::ParameterError;
+        if let Some(rust_data_series) = &rust_data_series {
+            parameter_errors.check("data_series", check_parameter_text(rust_data_series));
+        }
 
-    if data_series_error_state {
-        return TcmbEvdsResult::generate_result(rust_data_series, parameter_error);
-    }
-    if date_error_state {
-        return TcmbEvdsResult::generate_result(rust_date, parameter_error);
-    }
+        let date_preferences = match &rust_date {
+            Some(rust_date) => parameter_errors.collect("date", || {
+                check_date_span(generate_date_preferences(rust_date)?, None)
+            }),
+            None => None,
+        };
 
+        let return_format = check_evds_parameters(&mut parameter_errors, &api_key, return_format);
 
-    let date_preference_result = generate_date_preference(&rust_date);
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
 
-    let date_preference = match date_preference_result {
-        Ok(preference) => preference,
-        Err(error_result) => return error_result,
-    };
+        // all of the parameters are checked above.
+        let (rust_data_series, date_preferences) = (rust_data_series.unwrap(), date_preferences.unwrap());
 
 
-    let evds_result = generate_evds(api_key, return_format);
+        let evds_result = generate_evds(api_key, return_format);
 
-    let evds = match evds_result {
-        Ok(evds) => evds,
-        Err(error_result) => return error_result,
-    };
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        // Requesting data from the Tcmb Evds for each date range.
+        let requested_response = 
+        request_for_each_date_preference(date_preferences, &evds, |date_preference| {
+            evds_basic::get_data(
+                &rust_data_series, 
+                &date_preference, 
+                &evds
+            )
+        });
+
+
+        return_response(post_process_response(requested_response, &evds), ascii_mode)
+    })
+}
+
+/// gets data of any valid data series from EVDS like [`tcmb_evds_c_get_data`](crate::tcmb_evds_c_get_data) with the 
+/// headers of the series columns in the given language.
+///
+/// Multi-series responses have raw series codes such as `TP_DK_USD_S` as their headers. `EnglishHeaders` and 
+/// `TurkishHeaders` rewrite them with the descriptive names of the series, e.g. "(USD) US Dollar (Selling)", taken from
+/// the series metadata of EVDS. The names are cached for the process, so each series costs one more request only once.
+/// A series whose name could not be received keeps its code, and `CodeHeaders` makes the same request with 
+/// `tcmb_evds_c_get_data`.
+///
+/// # Error
+///
+/// This function returns the errors of `tcmb_evds_c_get_data`, and `UnsupportedReturnFormat` when the headers are 
+/// translated for the `Xml` return format since the names cannot be XML element names.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult data_result = 
+///         tcmb_evds_c_get_data_with_headers(data_series, date, api_key, Csv, EnglishHeaders, ascii_mode);
+///
+///     fwrite(data_result.output_ptr, data_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(data_result);
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_data_with_headers(
+    data_series: TcmbEvdsInput,
+    date: TcmbEvdsInput,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint,
+    header_language: c_uint,
+    ascii_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_data_with_headers", || {
+        let mut parameter_errors = ParameterErrors::new();
+
+        let rust_data_series = parameter_errors.read_input(&data_series, "data_series");
+        let rust_date = parameter_errors.read_input(&date, "date");
+
+        if let Some(rust_data_series) = &rust_data_series {
+            parameter_errors.check("data_series", check_parameter_text(rust_data_series));
+        }
+
+        let date_preferences = match &rust_date {
+            Some(rust_date) => parameter_errors.collect("date", || {
+                check_date_span(generate_date_preferences(rust_date)?, None)
+            }),
+            None => None,
+        };
+
+        let header_language: TcmbEvdsHeaderLanguage = parameter_errors.read_enum("header_language", header_language);
+        let return_format = check_evds_parameters(&mut parameter_errors, &api_key, return_format);
+
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
+
+        // all of the parameters are checked above.
+        let (rust_data_series, date_preferences) = (rust_data_series.unwrap(), date_preferences.unwrap());
+
+
+        let evds_result = generate_evds(api_key, return_format);
+
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        // Requesting data from the Tcmb Evds for each date range.
+        let mut requested_response = 
+        request_for_each_date_preference(date_preferences, &evds, |date_preference| {
+            evds_basic::get_data(
+                &rust_data_series, 
+                &date_preference, 
+                &evds
+            )
+        });
+
+        if let (Ok(response), Some(language)) = (&requested_response, header_language.to_language()) {
+            requested_response = evds_basic::translate_data_headers(response, &rust_data_series, language, &evds);
+        }
+
+
+        return_response(post_process_response(requested_response, &evds), ascii_mode)
+    })
+}
+
+/// gets exactly the last `n` observations of any valid data series from EVDS as a parsed dataset.
+///
+/// The date window is found automatically. The function starts with a window ending today and widens it until enough 
+/// observations are received, so the same call works for daily, monthly or annual series. Observations without any 
+/// value such as holidays are skipped.
+///
+/// The result contains the observations in the EVDS JSON layout. Additionally, when `dataset` is not NULL, a parsed 
+/// dataset handle is written into it and must be released with `tcmb_evds_c_dataset_free`. The handle is not written 
+/// when an error occurs. The units of the series, e.g. "thousand TRY", are attached to the handle from the series 
+/// metadata of EVDS, which costs one more request for each series, and read via `tcmb_evds_c_dataset_unit`.
+///
+/// # Error
+///
+/// This function returns error when invalid data series, zero `n`, or invalid api key is supplied, there is a bad 
+/// internet connection or the response could not be parsed.
+///
+/// # Safety
+///
+/// The `dataset` must be NULL or a valid pointer to a `TcmbEvdsDataset` pointer.
+///
+/// # Example
+///
+/// ```C
+///
+/// #include "tcmb_evds_c.h"
+///
+///
+/// int main() {
+///
+///     // declaration of required arguments.
+///     TcmbEvdsInput data_series;
+///     TcmbEvdsInput api_key;
+///     TcmbEvdsDataset* dataset = NULL;
+///     bool ascii_mode;
+///     
+///     
+///     // value assignments.
+///     data_series.input_ptr = "TP.DK.USD.S";
+///     data_series.string_capacity = strlen(data_series.input_ptr);
+///     
+///     api_key.input_ptr = "VALID_API_KEY";
+///     api_key.string_capacity = strlen(api_key.input_ptr);
+///     
+///     ascii_mode = false;
+///
+///     
+///     // requesting the last 5 observations.
+///     TcmbEvdsResult last_result = tcmb_evds_c_get_last_n(data_series, 5, api_key, ascii_mode, &dataset);
+///     
+///     
+///     // handling error and printing the result.
+///     printf("\nError: %s", tcmb_evds_c_is_error(last_result) ? "true" : "false");
+///
+///     fwrite(last_result.output_ptr, last_result.string_capacity, 1, stdout);
+///
+///     free(last_result.output_ptr);
+///     tcmb_evds_c_dataset_free(dataset);
+///
+///     return 0;
+/// }
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_get_last_n(
+    data_series: TcmbEvdsInput,
+    n: c_uint,
+    api_key: TcmbEvdsInput,
+    ascii_mode: bool,
+    dataset: *mut *mut TcmbEvdsDataset
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_last_n", || {
+        let mut parameter_errors = ParameterErrors::new();
+
+        let rust_data_series = parameter_errors.read_input(&data_series, "data_series");
+
+        if let Some(rust_data_series) = &rust_data_series {
+            parameter_errors.check("data_series", check_parameter_text(rust_data_series));
+        }
+        if n == 0 {
+            parameter_errors.add(
+                ReturnErrorC::ParameterError,
+                "n",
+                "Error: At least one observation should be requested.".to_string(),
+            );
+        }
+
+        check_evds_parameters(&mut parameter_errors, &api_key, TcmbEvdsReturnFormat::Json as c_uint);
+
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
+
+        // the data series is checked above.
+        let rust_data_series = rust_data_series.unwrap();
+
+
+        // The response is always requested as JSON to be parsed.
+        let evds_result = generate_evds(api_key, TcmbEvdsReturnFormat::Json);
+
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        // Requesting the last observations from the Tcmb Evds.
+        let requested_dataset = evds_basic::get_last_observations(&rust_data_series, n as usize, get_today(), &evds);
+
+        let last_observations = match requested_dataset {
+            Ok(last_observations) => last_observations,
+            Err(return_error) => return handle_return_error(return_error),
+        };
+
+        let response = last_observations.to_json();
+
+        if let Some(dataset) = dataset.as_mut() {
+            let units = evds_basic::get_series_units(&rust_data_series, &last_observations, &evds);
+
+            *dataset = TcmbEvdsDataset::from(last_observations).with_units(units).into_raw();
+        }
+
+
+        return_response(post_process_response(Ok(response), &evds), ascii_mode)
+    })
+}
+
+/// gets a nominal data series from EVDS as a real series, namely divided by a price index fetched together with it and
+/// rebased to the prices of a base period.
+///
+/// The price index is a series code such as `TP.FG.J0`, the consumer price index, and the base period is one of the 
+/// dates of the requested data written like EVDS dates, e.g. "2003-1" for a month. The real series is named after the
+/// nominal series with the `_REAL` suffix, e.g. `TP_KREDI_TOPLAM_REAL`, and has the same value with the nominal series 
+/// at the base period. The nominal series and the price index should have the same frequency.
+///
+/// # Error
+///
+/// This function returns the errors of [`tcmb_evds_c_get_data`](crate::tcmb_evds_c_get_data), and `InvalidDate` when 
+/// the base period is not within the requested dates or the price index has no value at it.
+///
+/// # Example
+///
+/// ```C
+///     data_series.input_ptr = "TP.KREDI.TOPLAM";
+///     data_series.string_capacity = strlen(data_series.input_ptr);
+///
+///     price_index.input_ptr = "TP.FG.J0";
+///     price_index.string_capacity = strlen(price_index.input_ptr);
+///
+///     base_period.input_ptr = "2020-1";
+///     base_period.string_capacity = strlen(base_period.input_ptr);
+///
+///     TcmbEvdsResult real_result = 
+///         tcmb_evds_c_get_real_data(data_series, price_index, base_period, date, api_key, Csv, ascii_mode);
+///
+///     fwrite(real_result.output_ptr, real_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(real_result);
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_real_data(
+    data_series: TcmbEvdsInput,
+    price_index: TcmbEvdsInput,
+    base_period: TcmbEvdsInput,
+    date: TcmbEvdsInput,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_real_data", || {
+        let mut parameter_errors = ParameterErrors::new();
+
+        let rust_data_series = parameter_errors.read_input(&data_series, "data_series");
+        let rust_price_index = parameter_errors.read_input(&price_index, "price_index");
+        let rust_base_period = parameter_errors.read_input(&base_period, "base_period");
+        let rust_date = parameter_errors.read_input(&date, "date");
+
+        if let Some(rust_data_series) = &rust_data_series {
+            parameter_errors.check("data_series", check_parameter_text(rust_data_series));
+        }
+        if let Some(rust_price_index) = &rust_price_index {
+            parameter_errors.check("price_index", check_parameter_text(rust_price_index));
+        }
+        if let Some(rust_base_period) = &rust_base_period {
+            parameter_errors.check("base_period", check_parameter_text(rust_base_period));
+        }
+
+        let date_preferences = match &rust_date {
+            Some(rust_date) => parameter_errors.collect("date", || {
+                check_date_kind(check_date_span(generate_date_preferences(rust_date)?, None)?, DateKind::Range, "date")
+            }),
+            None => None,
+        };
+
+        let return_format = check_evds_parameters(&mut parameter_errors, &api_key, return_format);
+
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
+
+        // all of the parameters are checked above.
+        let (rust_data_series, rust_price_index) = (rust_data_series.unwrap(), rust_price_index.unwrap());
+        let (rust_base_period, date_preferences) = (rust_base_period.unwrap(), date_preferences.unwrap());
+
+
+        let evds_result = generate_evds(api_key, return_format);
+
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        // Requesting the nominal series and the price index from the Tcmb Evds for all date ranges together.
+        let requested_response = evds_basic::get_real_data(
+            &rust_data_series, 
+            &rust_price_index, 
+            &rust_base_period, 
+            &date_preferences, 
+            &evds
+        );
+
+
+        return_response(post_process_response(requested_response, &evds), ascii_mode)
+    })
+}
+
+/// gets the TRY value of a weighted basket of currencies, or its index rebased to 100 at a base date, from the selling 
+/// rates of the currencies in EVDS.
+///
+/// The basket is written as comma separated currency codes with their weights, e.g. "USD:0.5,EUR:0.5" approximating 
+/// the currency basket of CBRT. The weights are normalized, so "USD:50,EUR:50" is the same basket. When the base date 
+/// is empty, the basket is the weighted sum of the rates in TRY. Otherwise, it is an index which is 100 at the base 
+/// date, e.g. "02-01-2023", and weights the change of each rate since then. The result is a single series named 
+/// `CURRENCY_BASKET`, which is missing at the dates missing any of the rates such as weekends.
+///
+/// # Error
+///
+/// This function returns the errors of [`tcmb_evds_c_get_data`](crate::tcmb_evds_c_get_data), `ParameterError` when 
+/// the basket has an unknown or repeated currency code or a weight which is not a positive number, and `InvalidDate` 
+/// when the base date is not within the requested dates or a rate is missing at it.
+///
+/// # Example
+///
+/// ```C
+///     basket.input_ptr = "USD:0.5,EUR:0.5";
+///     basket.string_capacity = strlen(basket.input_ptr);
+///
+///     base_date.input_ptr = "02-01-2023";
+///     base_date.string_capacity = strlen(base_date.input_ptr);
+///
+///     TcmbEvdsResult basket_result = tcmb_evds_c_get_basket_index(basket, base_date, date, api_key, Csv, ascii_mode);
+///
+///     fwrite(basket_result.output_ptr, basket_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(basket_result);
+/// ```
+#[cfg(feature = "currency")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_basket_index(
+    basket: TcmbEvdsInput,
+    base_date: TcmbEvdsInput,
+    date: TcmbEvdsInput,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_basket_index", || {
+        let mut parameter_errors = ParameterErrors::new();
+
+        let rust_basket = parameter_errors.read_input(&basket, "basket");
+        let rust_base_date = parameter_errors.read_input(&base_date, "base_date");
+        let rust_date = parameter_errors.read_input(&date, "date");
+
+        let currency_basket = rust_basket.as_deref().and_then(CurrencyBasket::parse);
+
+        if rust_basket.is_some() && currency_basket.is_none() {
+            let error_message = "Error: The basket must consist of distinct currency codes with positive weights, \
+                e.g. \"USD:0.5,EUR:0.5\".".to_string();
+
+            parameter_errors.add(ReturnErrorC::ParameterError, "basket", error_message);
+        }
+
+        // an empty base date would be the date of the session, so only a given base date is checked.
+        if let Some(rust_base_date) = rust_base_date.as_deref().filter(|base_date| !base_date.trim().is_empty()) {
+            parameter_errors.collect("base_date", || {
+                check_date_kind(generate_date_preferences(rust_base_date)?, DateKind::Single, "base_date")
+            });
+        }
+
+        let date_preferences = match &rust_date {
+            Some(rust_date) => parameter_errors.collect("date", || {
+                check_date_kind(check_date_span(generate_date_preferences(rust_date)?, None)?, DateKind::Range, "date")
+            }),
+            None => None,
+        };
+
+        let return_format = check_evds_parameters(&mut parameter_errors, &api_key, return_format);
+
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
+
+        // all of the parameters are checked above.
+        let (currency_basket, rust_base_date) = (currency_basket.unwrap(), rust_base_date.unwrap());
+        let date_preferences = date_preferences.unwrap();
+
+        let base_date = Some(rust_base_date.trim()).filter(|base_date| !base_date.is_empty());
+
+
+        let evds_result = generate_evds(api_key, return_format);
 
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
 
-    // Requesting data from the Tcmb Evds.
-    let requested_response = 
-    evds_basic::get_data(
-        &rust_data_series, 
-        &date_preference, 
-        &evds
-    );
 
+        // Requesting the rates of the currencies from the Tcmb Evds for all date ranges together.
+        let requested_response = evds_basic::get_basket_index(&currency_basket, base_date, &date_preferences, &evds);
 
-    return_response(requested_response, ascii_mode)
+
+        return_response(post_process_response(requested_response, &evds), ascii_mode)
+    })
 }
 
 /// gets currency data with frequency formulas from EVDS.
 ///
+/// Several currency series can be requested together by separating them with dashes, e.g. "TP.DK.USD.A-TP.DK.EUR.A",
+/// where the aggregation type and the formula are applied to each of the series.
+///
 /// # Error
 ///
 /// This function returns error when invalid currency series, date, aggregation type, formula, data frequency, or api 
@@ -515,84 +1082,205 @@ pub extern "C" fn tcmb_evds_c_get_data(
 ///     return 0;
 /// }
 /// ```
+#[cfg(feature = "currency")]
 #[no_mangle]
 pub extern "C" fn tcmb_evds_c_get_advanced_data(
     currency_series: TcmbEvdsInput, 
     date: TcmbEvdsInput,
-    aggregation_type: TcmbEvdsAggregationType, 
-    formula: TcmbEvdsFormula,
-    data_frequency: TcmbEvdsDataFrequency,
+    aggregation_type: c_uint,
+    formula: c_uint,
+    data_frequency: c_uint,
     api_key: TcmbEvdsInput,
-    return_format: TcmbEvdsReturnFormat,
+    return_format: c_uint,
     ascii_mode: bool
 ) -> TcmbEvdsResult {
-    
-    let (rust_data_series, data_series_error_state) = 
-        currency_series.get_input(
-            "currency_series"
-        );
-    let (rust_date, date_error_state) = date.get_input("date");
-    let rust_aggregation_type = aggregation_type.convert();
-    let rust_formula = formula.convert();
-    let rust_data_frequency = data_frequency.convert();
 
-    let parameter_error = ReturnErrorC::ParameterError;
+    shield_result("tcmb_evds_c_get_advanced_data", || {
+        let mut parameter_errors = ParameterErrors::new();
 
-    if data_series_error_state {
-        return TcmbEvdsResult::generate_result(rust_data_series, parameter_error);
-    }
-    if date_error_state {
-        return TcmbEvdsResult::generate_result(rust_date, parameter_error);
-    }
+        let rust_data_series = parameter_errors.read_input(&currency_series, "currency_series");
+        let rust_date = parameter_errors.read_input(&date, "date");
 
+        let rust_aggregation_type =
+            parameter_errors.check("aggregation_type", convert_enum::<TcmbEvdsAggregationType, _>(aggregation_type));
+        let rust_formula = parameter_errors.check("formula", convert_enum::<TcmbEvdsFormula, _>(formula));
+        let rust_data_frequency =
+            parameter_errors.check("data_frequency", convert_enum::<TcmbEvdsDataFrequency, _>(data_frequency));
 
-    let advanced_processes = 
-        frequency_formulas::AdvancedProcesses::from(
-            rust_aggregation_type, 
-            rust_formula, 
-            rust_data_frequency
-        );
+        let data_series_parts = match &rust_data_series {
+            Some(rust_data_series) => {
+                parameter_errors.check("currency_series", parse_multiple_series(rust_data_series))
+            },
+            None => None,
+        };
 
-    
-    let data_series_parts = parse_series(&rust_data_series);
+        let date_preferences = match &rust_date {
+            Some(rust_date) => parameter_errors.collect("date", || {
+                check_date_span(generate_date_preferences(rust_date)?, rust_data_frequency)
+            }),
+            None => None,
+        };
 
-    if let Err(return_error) = data_series_parts {  return handle_return_error(return_error); };
-    let data_series_parts  = data_series_parts.unwrap();
+        let return_format = check_evds_parameters(&mut parameter_errors, &api_key, return_format);
 
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
 
-    let date_preference_result = generate_date_preference(&rust_date);
 
-    let date_preference = match date_preference_result {
-        Ok(preference) => preference,
-        Err(error_result) => return error_result,
-    };
+        // all of the parameters are checked above.
+        let advanced_processes = 
+            frequency_formulas::AdvancedProcesses::from(
+                rust_aggregation_type.unwrap(), 
+                rust_formula.unwrap(), 
+                rust_data_frequency.unwrap()
+            );
 
+        let (data_series_parts, date_preferences) = (data_series_parts.unwrap(), date_preferences.unwrap());
 
-    let currency_series = CurrencySeries {
-        ytl_mode: data_series_parts.ytl_mode,
-        exchange_type: data_series_parts.exchange_type,
-        currency_code: data_series_parts.currency_code,
-        date_preference
-    };
+        let series_codes = data_series_parts
+            .iter()
+            .map(|series_parts| {
+                build_currency_series(&series_parts.currency_code, &series_parts.exchange_type, series_parts.ytl_mode)
+            })
+            .collect::<Result<Vec<String>, error::ReturnError>>();
 
+        let series_codes = match series_codes {
+            Ok(series_codes) => series_codes,
+            Err(return_error) => return handle_return_error(return_error),
+        };
 
-    let evds_result = generate_evds(api_key, return_format);
 
-    let evds = match evds_result {
-        Ok(evds) => evds,
-        Err(error_result) => return error_result,
-    };
+        let evds_result = generate_evds(api_key, return_format);
 
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
 
-    // Requesting advanced currency data from the Tcmb Evds.
-    let requested_response =
-        currency_series.get_advanced_data(
-            &evds, 
-            &advanced_processes
-        );
+
+        // Requesting advanced currency data from the Tcmb Evds for each date range.
+        let requested_response =
+        request_for_each_date_preference(date_preferences, &evds, |date_preference| {
+            get_combined_advanced_data(
+                &series_codes, 
+                &date_preference, 
+                &evds, 
+                &advanced_processes
+            )
+        });
     
 
-    return_response(requested_response, ascii_mode)
+        return_response(post_process_response(requested_response, &evds), ascii_mode)
+    })
+}
+
+/// gets the data of a currency with frequency formulas from EVDS without writing its series code, e.g. the monthly 
+/// averages of both the buying and the selling rates of US dollar in a single call.
+///
+/// `BothSides` requests the buying and the selling series together in a single request, where the aggregation type 
+/// and the formula are applied to each of them, so the response has a column for each side.
+///
+/// # Error
+///
+/// This function returns error when invalid currency code, exchange side, date, aggregation type, formula, data 
+/// frequency, or api key is supplied or there is a bad internet connection.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult advanced_data_result =
+///         tcmb_evds_c_get_advanced_currency_data(
+///             Usd,
+///             BothSides,
+///             false,
+///             date,
+///             Average,
+///             Level,
+///             Monthly,
+///             api_key,
+///             Json,
+///             false
+///             );
+///
+///     if (!tcmb_evds_c_is_error(advanced_data_result)) { 
+///         fwrite(advanced_data_result.output_ptr, advanced_data_result.string_capacity, 1, stdout);
+///     }
+///
+///     tcmb_evds_c_result_free(advanced_data_result);
+/// ```
+#[cfg(feature = "currency")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_advanced_currency_data(
+    currency_code: c_uint,
+    exchange_side: c_uint,
+    ytl_mode: bool,
+    date: TcmbEvdsInput,
+    aggregation_type: c_uint,
+    formula: c_uint,
+    data_frequency: c_uint,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_advanced_currency_data", || {
+        let mut parameter_errors = ParameterErrors::new();
+
+        let rust_currency_code =
+            parameter_errors.check("currency_code", convert_enum::<TcmbEvdsCurrencyCode, _>(currency_code));
+        let rust_exchange_type =
+            parameter_errors.check("exchange_side", convert_enum::<TcmbEvdsExchangeSide, _>(exchange_side));
+        let rust_date = parameter_errors.read_input(&date, "date");
+
+        let rust_aggregation_type =
+            parameter_errors.check("aggregation_type", convert_enum::<TcmbEvdsAggregationType, _>(aggregation_type));
+        let rust_formula = parameter_errors.check("formula", convert_enum::<TcmbEvdsFormula, _>(formula));
+        let rust_data_frequency =
+            parameter_errors.check("data_frequency", convert_enum::<TcmbEvdsDataFrequency, _>(data_frequency));
+
+        let date_preferences = match &rust_date {
+            Some(rust_date) => parameter_errors.collect("date", || {
+                check_date_span(generate_date_preferences(rust_date)?, rust_data_frequency)
+            }),
+            None => None,
+        };
+
+        let return_format = check_evds_parameters(&mut parameter_errors, &api_key, return_format);
+
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
+
+
+        // all of the parameters are checked above.
+        let advanced_processes = 
+            frequency_formulas::AdvancedProcesses::from(
+                rust_aggregation_type.unwrap(), 
+                rust_formula.unwrap(), 
+                rust_data_frequency.unwrap()
+            );
+
+        let series_codes = 
+            build_currency_series_codes(&rust_currency_code.unwrap(), &rust_exchange_type.unwrap(), ytl_mode);
+
+        let series_codes = match series_codes {
+            Ok(series_codes) => series_codes,
+            Err(return_error) => return handle_return_error(return_error),
+        };
+
+
+        let evds = match generate_evds(api_key, return_format) {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        // Requesting advanced currency data of each side together from the Tcmb Evds for each date range.
+        let requested_response =
+        request_for_each_date_preference(date_preferences.unwrap(), &evds, |date_preference| {
+            get_combined_advanced_data(&series_codes, &date_preference, &evds, &advanced_processes)
+        });
+
+
+        return_response(post_process_response(requested_response, &evds), ascii_mode)
+    })
 }
 
 /// gets all series data related given data group from EVDS.
@@ -661,54 +1349,62 @@ pub extern "C" fn tcmb_evds_c_get_advanced_data(
 ///     return 0;
 /// }
 /// ```
+#[cfg(feature = "catalog")]
 #[no_mangle]
 pub extern "C" fn tcmb_evds_c_get_data_group(
     data_group: TcmbEvdsInput, 
     date: TcmbEvdsInput, 
     api_key: TcmbEvdsInput, 
-    return_format: TcmbEvdsReturnFormat,
+    return_format: c_uint,
     ascii_mode: bool
 ) -> TcmbEvdsResult {
 
-    let (rust_data_group, data_group_error_state) = data_group.get_input("data_group");
-    let (rust_date, date_error_state) = date.get_input("date");
+    shield_result("tcmb_evds_c_get_data_group", || {
+        let mut parameter_errors = ParameterErrors::new();
 
-    let parameter_error = ReturnErrorC::ParameterError;
+        let rust_data_group = parameter_errors.read_input(&data_group, "data_group");
+        let rust_date = parameter_errors.read_input(&date, "date");
 
-    if data_group_error_state {
-        return TcmbEvdsResult::generate_result(rust_data_group, parameter_error);
-    }
-    if date_error_state {
-        return TcmbEvdsResult::generate_result(rust_date, parameter_error);
-    }
+        if let Some(rust_data_group) = &rust_data_group {
+            parameter_errors.check("data_group", check_parameter_text(rust_data_group));
+        }
 
+        let date_preferences = match &rust_date {
+            Some(rust_date) => parameter_errors.collect("date", || {
+                check_date_span(generate_date_preferences(rust_date)?, None)
+            }),
+            None => None,
+        };
 
-    let date_preference_result = generate_date_preference(&rust_date);
+        let return_format = check_evds_parameters(&mut parameter_errors, &api_key, return_format);
 
-    let date_preference = match date_preference_result {
-        Ok(preference) => preference,
-        Err(error_result) => return error_result,
-    };
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
 
+        // all of the parameters are checked above.
+        let (rust_data_group, date_preferences) = (rust_data_group.unwrap(), date_preferences.unwrap());
 
-    let evds_result = generate_evds(api_key, return_format);
 
-    let evds = match evds_result {
-        Ok(evds) => evds,
-        Err(error_result) => return error_result,
-    };
+        let evds_result = generate_evds(api_key, return_format);
 
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
 
-    // Requesting data group from the Tcmb Evds.
-    let requested_response = 
-    evds_basic::get_data_group(
-        &rust_data_group, 
-        &date_preference, 
-        &evds
-    );
 
+        // Requesting data group from the Tcmb Evds for each date range.
+        let requested_response = 
+        request_for_each_date_preference(date_preferences, &evds, |date_preference| {
+            evds_basic::get_data_group(
+                &rust_data_group, 
+                &date_preference, 
+                &evds
+            )
+        });
 
-    return_response(requested_response, ascii_mode)
+
+        return_response(post_process_response(requested_response, &evds), ascii_mode)
+    })
 }
 
 /// gets categories list from EVDS.
@@ -760,45 +1456,125 @@ pub extern "C" fn tcmb_evds_c_get_data_group(
 ///     return 0;
 /// }
 /// ```
+#[cfg(feature = "catalog")]
 #[no_mangle]
 pub extern "C" fn tcmb_evds_c_get_categories(
     api_key: TcmbEvdsInput, 
-    return_format: TcmbEvdsReturnFormat,
+    return_format: c_uint,
     ascii_mode: bool
 ) -> TcmbEvdsResult {
 
-    let evds_result = generate_evds(api_key, return_format);
+    shield_result("tcmb_evds_c_get_categories", || {
+        let return_format: TcmbEvdsReturnFormat = match read_enum(return_format) {
+            Ok(return_format) => return_format,
+            Err(return_error) => return handle_parameter_return_error(return_error, "return_format"),
+        };
 
-    let evds = match evds_result {
-        Ok(evds) => evds,
-        Err(error_result) => return error_result,
-    };
+        let evds_result = generate_evds(api_key, return_format);
 
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
 
-    // Requesting categories data from the Tcmb Evds.
-    let requested_response = evds_basic::get_categories(&evds);
 
+        // Requesting categories data from the Tcmb Evds.
+        let requested_response = evds_basic::get_categories(&evds);
 
-    return_response(requested_response, ascii_mode)
+
+        return_response(requested_response, ascii_mode)
+    })
 }
 
-/// gets data groups from EVDS.
+/// gets the tree of the categories of EVDS holding their data groups, so that user interfaces expand the categories 
+/// without requesting EVDS again.
+///
+/// The categories and all data groups are requested in JSON and written into the result as a JSON array of the 
+/// categories with their data groups in the language, e.g. 
+/// `[{"code":"1","name":"MARKET STATISTICS","groups":[{"code":"bie_pyrepo","name":"...","seriesCount":null}]}]`. The 
+/// same tree is given through the `tree` pointer as a [`TcmbEvdsCategoryTree`] handle when it is not NULL, which must 
+/// be released via [`tcmb_evds_c_tree_free`](crate::evds_c::tree_entities::tcmb_evds_c_tree_free). The series count of 
+/// a data group is known when its series list is requested before via [`tcmb_evds_c_get_series_list`].
 ///
 /// # Error
 ///
-/// This function returns error when invalid mode, code, or api key is supplied or there is a bad internet connection. 
+/// This function returns error when invalid api key is supplied, there is a bad internet connection or the responses
+/// could not be parsed. Nothing is written through the `tree` pointer on error.
+///
+/// # Safety
+///
+/// The `tree` must be NULL or a valid pointer to a `TcmbEvdsCategoryTree` pointer.
 ///
 /// # Example
 ///
 /// ```C
+///     TcmbEvdsCategoryTree* tree = NULL;
 ///
-/// #include "tcmb_evds_c.h"
+///     TcmbEvdsResult tree_result = tcmb_evds_c_get_category_tree(api_key, EnglishHeaders, &tree);
 ///
+///     if (!tcmb_evds_c_is_error(tree_result)) {
+///         long category = tcmb_evds_c_tree_first_category(tree);
 ///
-/// int main() {
+///         printf("%s\n", tcmb_evds_c_tree_node_name(tree, category, EnglishHeaders));
+///     }
 ///
-///     // declaration of required arguments.
-///     unsigned int mode;
+///     tcmb_evds_c_result_free(tree_result);
+///     tcmb_evds_c_tree_free(tree);
+/// ```
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_get_category_tree(
+    api_key: TcmbEvdsInput,
+    language: c_uint,
+    tree: *mut *mut TcmbEvdsCategoryTree
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_get_category_tree", || {
+        let language: TcmbEvdsHeaderLanguage = match read_enum(language) {
+            Ok(language) => language,
+            Err(return_error) => return handle_parameter_return_error(return_error, "language"),
+        };
+
+        // The responses are always requested as JSON to be parsed.
+        let evds_result = generate_evds(api_key, TcmbEvdsReturnFormat::Json);
+
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        let category_tree = match evds_basic::get_category_tree(&evds) {
+            Ok(category_tree) => category_tree,
+            Err(return_error) => return handle_return_error(return_error),
+        };
+
+        let response = category_tree.to_json(language.to_language().unwrap_or(HeaderLanguage::English));
+
+        if let Some(tree) = tree.as_mut() { *tree = TcmbEvdsCategoryTree::from(category_tree).into_raw(); }
+
+
+        return_response(Ok(response), false)
+    })
+}
+
+/// gets data groups from EVDS.
+///
+/// # Error
+///
+/// This function returns error when invalid mode, code, or api key is supplied or there is a bad internet connection. 
+///
+/// # Example
+///
+/// ```C
+///
+/// #include "tcmb_evds_c.h"
+///
+///
+/// int main() {
+///
+///     // declaration of required arguments.
+///     unsigned int mode;
 ///     TcmbEvdsInput code;   
 ///     TcmbEvdsInput api_key;
 ///     TcmbEvdsReturnFormat return_format;
@@ -848,35 +1624,43 @@ pub extern "C" fn tcmb_evds_c_get_categories(
 ///     return 0;
 /// }
 /// ```
+#[cfg(feature = "catalog")]
 #[no_mangle]
 pub extern "C" fn tcmb_evds_c_get_advanced_data_group(
     mode: c_uint,
     code: TcmbEvdsInput,
     api_key: TcmbEvdsInput,
-    return_format: TcmbEvdsReturnFormat,
+    return_format: c_uint,
     ascii_mode: bool
 ) -> TcmbEvdsResult {
 
-    let (rust_code, code_error_state) = code.get_input("code");
+    shield_result("tcmb_evds_c_get_advanced_data_group", || {
+        let (rust_code, code_error_state) = code.get_input("code");
 
-    if code_error_state {
-        return TcmbEvdsResult::generate_result(rust_code, ReturnErrorC::ParameterError);
-    }
+        if code_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_code, ReturnErrorC::ParameterError, "code");
+        }
     
 
-    let evds_result = generate_evds(api_key, return_format);
+        let return_format: TcmbEvdsReturnFormat = match read_enum(return_format) {
+            Ok(return_format) => return_format,
+            Err(return_error) => return handle_parameter_return_error(return_error, "return_format"),
+        };
 
-    let evds = match evds_result {
-        Ok(evds) => evds,
-        Err(error_result) => return error_result,
-    };
+        let evds_result = generate_evds(api_key, return_format);
 
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
 
-    // Requesting advanced data group from the Tcmb Evds.
-    let requested_response = evds_basic::get_advanced_data_group(mode, &rust_code, &evds);
 
+        // Requesting advanced data group from the Tcmb Evds.
+        let requested_response = evds_basic::get_advanced_data_group(mode, &rust_code, &evds);
 
-    return_response(requested_response, ascii_mode)
+
+        return_response(requested_response, ascii_mode)
+    })
 }
 
 /// gets series list from EVDS.
@@ -931,52 +1715,2731 @@ pub extern "C" fn tcmb_evds_c_get_advanced_data_group(
 ///     return 0;
 /// }
 /// ```
+#[cfg(feature = "catalog")]
 #[no_mangle]
 pub extern "C" fn tcmb_evds_c_get_series_list(
     code: evds_c::common_entities::TcmbEvdsInput, 
     api_key: TcmbEvdsInput, 
-    return_format: TcmbEvdsReturnFormat,
+    return_format: c_uint,
     ascii_mode: bool
 ) -> TcmbEvdsResult {
 
-    let (rust_code, code_error_state) = code.get_input("code");
+    shield_result("tcmb_evds_c_get_series_list", || {
+        let (rust_code, code_error_state) = code.get_input("code");
 
-    if code_error_state {
-        return TcmbEvdsResult::generate_result(rust_code,  ReturnErrorC::ParameterError);
-    }
+        if code_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_code, ReturnErrorC::ParameterError, "code");
+        }
    
 
-    let evds_result = generate_evds(api_key, return_format);
+        let return_format: TcmbEvdsReturnFormat = match read_enum(return_format) {
+            Ok(return_format) => return_format,
+            Err(return_error) => return handle_parameter_return_error(return_error, "return_format"),
+        };
 
-    let evds = match evds_result {
-        Ok(evds) => evds,
-        Err(error_result) => return error_result,
-    };
+        let evds_result = generate_evds(api_key, return_format);
+
+        let evds = match evds_result {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
 
 
-    // Requesting series list from the Tcmb Evds.
-    let requested_response = evds_basic::get_series_list(&rust_code, &evds);
+        // Requesting series list from the Tcmb Evds.
+        let requested_response = evds_basic::get_series_list(&rust_code, &evds);
 
     
-    return_response(requested_response, ascii_mode)
+        return_response(requested_response, ascii_mode)
+    })
 }
 
-/// provides users an ability to check whether the result includes error or not. 
+/// gives the manifest of the series of a data group with their metadata as a pretty printed JSON object, so that the
+/// series a pipeline consumes are kept in version control and changes of CBRT to them are caught in the reviews.
+///
+/// Each series of the manifest has its `code`, `name`, `nameEng`, `frequency`, `aggregationMethod`, `startDate`,
+/// `dataSource` and `tag`, and the series are sorted by their codes, e.g.
+/// `{"dataGroup":"bie_dkdovytl","series":[{"code":"TP.DK.USD.A","name":"...","frequency":"IS GUNLUK",...}]}`. Two
+/// manifests are compared via [`tcmb_evds_c_diff_manifests`].
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the data group is invalid, `UnparsableResponse` when the series list
+/// could not be parsed, and error when an invalid api key is supplied.
 ///
 /// # Example
-/// 
+///
 /// ```C
-///     // requesting data.
-///     TcmbEvdsResult series_list = tcmb_evds_c_get_series_list(code, api_key, return_format, ascii_mode);
+///     data_group.input_ptr = "bie_dkdovytl";
+///     data_group.string_capacity = strlen(data_group.input_ptr);
 ///
+///     TcmbEvdsResult manifest = tcmb_evds_c_series_manifest(data_group, api_key);
 ///
-///     // handling error.
-///     printf("\nError: %s", tcmb_evds_c_is_error(series_list) ? "true" : "false");
+///     if (manifest.error_type == NoError) {
+///         FILE* manifest_file = fopen("bie_dkdovytl.json", "w");
+///
+///         fwrite(manifest.output_ptr, manifest.string_capacity, 1, manifest_file);
+///         fclose(manifest_file);
+///     }
+///
+///     tcmb_evds_c_result_free(manifest);
 /// ```
+#[cfg(feature = "catalog")]
 #[no_mangle]
-pub extern "C" fn tcmb_evds_c_is_error(result: TcmbEvdsResult) -> bool {
+pub extern "C" fn tcmb_evds_c_series_manifest(data_group: TcmbEvdsInput, api_key: TcmbEvdsInput) -> TcmbEvdsResult {
 
-    if let ReturnErrorC::NoError = result.error_type { return false; }
-    
-    true
-}
\ No newline at end of file
+    shield_result("tcmb_evds_c_series_manifest", || {
+        let (rust_data_group, data_group_error_state) = data_group.get_input("data_group");
+
+        if data_group_error_state {
+            return TcmbEvdsResult::generate_parameter_result(
+                rust_data_group, 
+                ReturnErrorC::ParameterError, 
+                "data_group"
+            );
+        }
+
+        let evds = match generate_evds(api_key, TcmbEvdsReturnFormat::Json) {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        return_response(evds_basic::get_series_manifest(&rust_data_group, &evds), false)
+    })
+}
+
+/// compares two manifests of [`tcmb_evds_c_series_manifest`] and returns the added and removed series codes and the 
+/// changed fields of the rest as a JSON object, e.g. `{"added":["TP.DK.CHF.A"],"removed":[],"changed":
+/// [{"code":"TP.DK.USD.A","field":"frequency","oldValue":"IS GUNLUK","newValue":"GUNLUK"}]}`.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when either manifest is invalid, and `UnparsableResponse` when either 
+/// manifest could not be parsed.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult manifest_diff = tcmb_evds_c_diff_manifests(committed_manifest, current_manifest);
+///
+///     if (manifest_diff.error_type == NoError) { /* A Process */ };
+///
+///     tcmb_evds_c_result_free(manifest_diff);
+/// ```
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_diff_manifests(
+    old_manifest: TcmbEvdsInput, 
+    new_manifest: TcmbEvdsInput
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_diff_manifests", || {
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        let (rust_old_manifest, old_manifest_error_state) = old_manifest.get_input("old_manifest");
+
+        if old_manifest_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_old_manifest, parameter_error, "old_manifest");
+        }
+
+        let (rust_new_manifest, new_manifest_error_state) = new_manifest.get_input("new_manifest");
+
+        if new_manifest_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_new_manifest, parameter_error, "new_manifest");
+        }
+
+
+        match diff_manifests(&rust_old_manifest, &rust_new_manifest) {
+            Ok(changes) => TcmbEvdsResult::generate_result(changes, ReturnErrorC::NoError),
+            Err(return_error) => handle_return_error(return_error),
+        }
+    })
+}
+
+/// lists the codes of the series known from the series lists received so far, e.g. via 
+/// [`tcmb_evds_c_get_series_list`], that start with the prefix. The list is made without requesting EVDS, so it suits
+/// shell completions and other tools.
+///
+/// The codes are sorted and written one per line in `Csv`, or as an array of strings in `Json` and `Auto`. An empty 
+/// prefix lists all of the known codes.
+///
+/// # Error
+///
+/// This function returns `UnsupportedReturnFormat` error for `Xml` and `ParameterError` when the prefix is invalid.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput prefix;
+///
+///     prefix.input_ptr = "TP.DK.";
+///     prefix.string_capacity = strlen(prefix.input_ptr);
+///
+///     // prints "TP.DK.EUR.A", "TP.DK.EUR.S", "TP.DK.USD.A" and so on after the series list of bie_dkdovytl is 
+///     // received.
+///     TcmbEvdsResult series_codes = tcmb_evds_c_list_series(prefix, Csv);
+///
+///     fwrite(series_codes.output_ptr, series_codes.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(series_codes);
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_list_series(
+    prefix: TcmbEvdsInput,
+    return_format: c_uint
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_list_series", || {
+        let (rust_prefix, prefix_error_state) = prefix.get_input("prefix");
+
+        if prefix_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_prefix, ReturnErrorC::ParameterError, "prefix");
+        }
+
+        let return_format = match read_enum(return_format) {
+            Ok(TcmbEvdsReturnFormat::SessionFormat) => evds_c::settings::get_settings().session_return_format,
+            Ok(return_format) => return_format,
+            Err(return_error) => return handle_parameter_return_error(return_error, "return_format"),
+        };
+
+        let series_codes = dataset::catalog::get_series_catalog().find_codes(&rust_prefix);
+
+        let listed_codes = match return_format.convert() {
+            common::ReturnFormat::Csv => series_codes.join("\n"),
+            common::ReturnFormat::Json | common::ReturnFormat::Auto => {
+                serde_json::Value::from(series_codes).to_string()
+            },
+            common::ReturnFormat::Xml => return handle_return_error(error::ReturnError::UnsupportedReturnFormat),
+        };
+
+        TcmbEvdsResult::generate_result(listed_codes, ReturnErrorC::NoError)
+    })
+}
+
+/// registers a virtual series defined as an expression over real series, e.g. `TP.DK.EUR.S - TP.DK.USD.S`, which is 
+/// requested by its name like a real series via [`tcmb_evds_c_get_data`] and the other data functions.
+///
+/// The expression consists of series codes, numbers, `+`, `-`, `*`, `/`, parentheses and the `log(x)`, `diff(x)` and 
+/// `lag(x, n)` functions of [`tcmb_evds_c_eval`](crate::evds_c::dataset_entities::tcmb_evds_c_eval). When the virtual 
+/// series is requested, the real series are fetched together so that their observations are aligned by date, the expression is 
+/// evaluated at each date, and a dataset of the virtual series is returned under its name. A missing value or a 
+/// division by zero gives a missing value. The name must start with a letter and include only letters, digits, `.` 
+/// and `_`. Defining the same name again replaces its expression.
+///
+/// The result contains the real series of the expression separated by dashes on success.
+///
+/// # Error
+///
+/// This function returns `InvalidExpression` error when the name or the expression is invalid, and `ParameterError`
+/// when one of the inputs cannot be read.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput name = { "SPREAD", 6 };
+///     TcmbEvdsInput expression = { "TP.DK.EUR.S - TP.DK.USD.S", 25 };
+///
+///     tcmb_evds_c_result_free(tcmb_evds_c_define_series(name, expression));
+///
+///     TcmbEvdsResult spread_result = tcmb_evds_c_get_data(name, date, api_key, Csv, false);
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_define_series(name: TcmbEvdsInput, expression: TcmbEvdsInput) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_define_series", || {
+        let mut parameter_errors = ParameterErrors::new();
+
+        let rust_name = parameter_errors.read_input(&name, "name");
+        let rust_expression = parameter_errors.read_input(&expression, "expression");
+
+        if let Some(rust_name) = &rust_name {
+            parameter_errors.check("name", check_virtual_series_name(rust_name.trim()));
+        }
+
+        let parsed_expression = match &rust_expression {
+            Some(rust_expression) => parameter_errors.check("expression", Expression::parse(rust_expression)),
+            None => None,
+        };
+
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
+
+        // all of the parameters are checked above.
+        let (rust_name, parsed_expression) = (rust_name.unwrap(), parsed_expression.unwrap());
+
+        let series_codes = parsed_expression.series_codes().join("-");
+
+        get_virtual_series().define(rust_name.trim().to_string(), parsed_expression);
+
+        TcmbEvdsResult::generate_result(series_codes, ReturnErrorC::NoError)
+    })
+}
+
+/// removes the virtual series registered via [`tcmb_evds_c_define_series`]. False is returned when the name is not 
+/// defined or cannot be read.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput name = { "SPREAD", 6 };
+///
+///     tcmb_evds_c_remove_series(name);
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_remove_series(name: TcmbEvdsInput) -> bool {
+
+    shield_or(false, || {
+        let (rust_name, name_error_state) = name.get_input("name");
+
+        if name_error_state { return false; }
+
+        get_virtual_series().remove(rust_name.trim())
+    })
+}
+
+/// does the same thing with [`tcmb_evds_c_get_data`] and returns the result as an opaque 
+/// [`TcmbEvdsHandle`](crate::evds_c::handle_entities::TcmbEvdsHandle).
+///
+/// The handle is read via the `tcmb_evds_c_handle_*` functions and must be freed via 
+/// [`tcmb_evds_c_handle_free`](crate::evds_c::handle_entities::tcmb_evds_c_handle_free).
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsHandle* data_handle = 
+///         tcmb_evds_c_get_data_handle(data_series, date, api_key, return_format, ascii_mode);
+///
+///     fwrite(tcmb_evds_c_handle_data_ptr(data_handle), tcmb_evds_c_handle_len(data_handle), 1, stdout);
+///
+///     tcmb_evds_c_handle_free(data_handle);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_data_handle(
+    data_series: TcmbEvdsInput,
+    date: TcmbEvdsInput,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> *mut TcmbEvdsHandle {
+
+    let result = tcmb_evds_c_get_data(data_series, date, api_key, return_format, ascii_mode);
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_last_n`] and returns the result as an opaque 
+/// [`TcmbEvdsHandle`](crate::evds_c::handle_entities::TcmbEvdsHandle).
+///
+/// # Safety
+///
+/// The same requirements with [`tcmb_evds_c_get_last_n`] apply to the dataset pointer.
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_get_last_n_handle(
+    data_series: TcmbEvdsInput,
+    n: c_uint,
+    api_key: TcmbEvdsInput,
+    ascii_mode: bool,
+    dataset: *mut *mut TcmbEvdsDataset
+) -> *mut TcmbEvdsHandle {
+
+    let result = tcmb_evds_c_get_last_n(data_series, n, api_key, ascii_mode, dataset);
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_advanced_data`] and returns the result as an opaque 
+/// [`TcmbEvdsHandle`](crate::evds_c::handle_entities::TcmbEvdsHandle).
+#[cfg(feature = "currency")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_advanced_data_handle(
+    currency_series: TcmbEvdsInput, 
+    date: TcmbEvdsInput,
+    aggregation_type: c_uint,
+    formula: c_uint,
+    data_frequency: c_uint,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> *mut TcmbEvdsHandle {
+
+    let result = 
+        tcmb_evds_c_get_advanced_data(
+            currency_series, 
+            date, 
+            aggregation_type, 
+            formula, 
+            data_frequency, 
+            api_key, 
+            return_format, 
+            ascii_mode
+        );
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_data_group`] and returns the result as an opaque 
+/// [`TcmbEvdsHandle`](crate::evds_c::handle_entities::TcmbEvdsHandle).
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_data_group_handle(
+    data_group: TcmbEvdsInput, 
+    date: TcmbEvdsInput, 
+    api_key: TcmbEvdsInput, 
+    return_format: c_uint,
+    ascii_mode: bool
+) -> *mut TcmbEvdsHandle {
+
+    let result = tcmb_evds_c_get_data_group(data_group, date, api_key, return_format, ascii_mode);
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_categories`] and returns the result as an opaque 
+/// [`TcmbEvdsHandle`](crate::evds_c::handle_entities::TcmbEvdsHandle).
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_categories_handle(
+    api_key: TcmbEvdsInput, 
+    return_format: c_uint,
+    ascii_mode: bool
+) -> *mut TcmbEvdsHandle {
+
+    let result = tcmb_evds_c_get_categories(api_key, return_format, ascii_mode);
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_advanced_data_group`] and returns the result as an opaque 
+/// [`TcmbEvdsHandle`](crate::evds_c::handle_entities::TcmbEvdsHandle).
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_advanced_data_group_handle(
+    mode: c_uint,
+    code: TcmbEvdsInput,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint,
+    ascii_mode: bool
+) -> *mut TcmbEvdsHandle {
+
+    let result = tcmb_evds_c_get_advanced_data_group(mode, code, api_key, return_format, ascii_mode);
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// does the same thing with [`tcmb_evds_c_get_series_list`] and returns the result as an opaque 
+/// [`TcmbEvdsHandle`](crate::evds_c::handle_entities::TcmbEvdsHandle).
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_get_series_list_handle(
+    code: TcmbEvdsInput, 
+    api_key: TcmbEvdsInput, 
+    return_format: c_uint,
+    ascii_mode: bool
+) -> *mut TcmbEvdsHandle {
+
+    let result = tcmb_evds_c_get_series_list(code, api_key, return_format, ascii_mode);
+
+    TcmbEvdsHandle::from(result).into_raw()
+}
+
+/// makes the request built via the `tcmb_evds_c_request_set_*` functions of 
+/// [`TcmbEvdsRequest`](crate::evds_c::request_entities::TcmbEvdsRequest). The request is not freed and can be executed 
+/// again, e.g. after changing its dates.
+///
+/// A request with a data series is made via [`tcmb_evds_c_get_data`], or via [`tcmb_evds_c_get_advanced_data`] when 
+/// any of the aggregation type, the formula and the data frequency is set. A request with a data group is made via 
+/// [`tcmb_evds_c_get_data_group`]. The parameters are validated as in those functions.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the request is NULL, has both or neither of a data series and a data 
+/// group, or has frequency formulas with a data group.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsRequest* request = tcmb_evds_c_request_new();
+///
+///     tcmb_evds_c_request_set_series(request, data_series);
+///     tcmb_evds_c_request_set_dates(request, date);
+///     tcmb_evds_c_request_set_api_key(request, api_key);
+///     tcmb_evds_c_request_set_return_format(request, Csv);
+///
+///     TcmbEvdsResult result = tcmb_evds_c_execute(request);
+///
+///     tcmb_evds_c_request_free(request);
+///     tcmb_evds_c_result_free(result);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_execute(request: *const TcmbEvdsRequest) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_execute", || {
+        match request.as_ref() {
+            Some(request) => execute_request(request, &request.dates),
+            None => generate_null_request_result(),
+        }
+    })
+}
+
+/// makes the request built via the `tcmb_evds_c_request_set_*` functions like [`tcmb_evds_c_execute`] for the given 
+/// date range instead of the dates of the request, which are kept as they are.
+///
+/// The request is prepared on the first execution: its api key is verified via EVDS once and not again in the later 
+/// executions, and the connection to EVDS is kept alive between them. Therefore, a periodic poller builds the request 
+/// once and executes it for each new period. An empty end date requests the start date only.
+///
+/// # Error
+///
+/// This function returns the errors of [`tcmb_evds_c_execute`], and `ParameterError` when a date cannot be read.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsRequest* request = tcmb_evds_c_request_new();
+///
+///     tcmb_evds_c_request_set_series(request, data_series);
+///     tcmb_evds_c_request_set_api_key(request, api_key);
+///
+///     while (is_polling) {
+///         TcmbEvdsResult result = tcmb_evds_c_request_with_dates(request, yesterday, today);
+///
+///         /* A Process */
+///
+///         tcmb_evds_c_result_free(result);
+///     }
+///
+///     tcmb_evds_c_request_free(request);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_request_with_dates(
+    request: *const TcmbEvdsRequest,
+    start_date: TcmbEvdsInput,
+    end_date: TcmbEvdsInput
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_request_with_dates", || {
+        let request = match request.as_ref() {
+            Some(request) => request,
+            None => return generate_null_request_result(),
+        };
+
+        let (rust_start_date, start_date_error_state) = start_date.get_input("start_date");
+        let (rust_end_date, end_date_error_state) = end_date.get_input("end_date");
+
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        if start_date_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_start_date, parameter_error, "start_date");
+        }
+        if end_date_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_end_date, parameter_error, "end_date");
+        }
+
+        let dates = match rust_end_date.trim().is_empty() {
+            true => rust_start_date,
+            false => format!("{},{}", rust_start_date.trim(), rust_end_date.trim()),
+        };
+
+        // the dates are read from a valid input, which never contains a NUL character.
+        match CString::new(dates) {
+            Ok(dates) => execute_request(request, &dates),
+            Err(_) => TcmbEvdsResult::generate_parameter_result(
+                "Error: The dates contain a NUL character.".to_string(),
+                parameter_error,
+                "start_date",
+            ),
+        }
+    })
+}
+
+/// runs all client-side checks of the request built via the `tcmb_evds_c_request_set_*` functions without making it, 
+/// so that interactive applications can show complete validation feedback before executing the request.
+///
+/// The data series or the data group, the dates, the enum values and the format of the api key are checked as in 
+/// [`tcmb_evds_c_execute`], except that every problem is reported rather than the first one. The enum values are 
+/// checked strictly even when strict enums are disabled. The checks made via EVDS, e.g. the validity of the api key, 
+/// are not made. A valid request gives an empty result with `NoError`.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the request is NULL. A single problem is returned with its own error 
+/// type, and several problems are returned together as `ParameterError` listing one problem per line as 
+/// `- parameter: message`.
+///
+/// # Safety
+///
+/// The request must be NULL or a valid pointer returned from this library and not freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult validation_result = tcmb_evds_c_validate(request);
+///
+///     if (validation_result.error_type != NoError) {
+///         fwrite(validation_result.output_ptr, validation_result.string_capacity, 1, stderr);
+///     }
+///
+///     tcmb_evds_c_result_free(validation_result);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_validate(request: *const TcmbEvdsRequest) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_validate", || {
+        let request = match request.as_ref() {
+            Some(request) => request,
+            None => return generate_null_request_result(),
+        };
+
+        match request.find_problems().into_result() {
+            Ok(()) => TcmbEvdsResult::generate_result(String::new(), ReturnErrorC::NoError),
+            Err(error_result) => error_result,
+        }
+    })
+}
+
+fn generate_null_request_result() -> TcmbEvdsResult {
+    TcmbEvdsResult::generate_parameter_result(
+        "Error: The given request is NULL.".to_string(),
+        ReturnErrorC::ParameterError,
+        "request",
+    )
+}
+
+/// makes the request for the given dates. The api key of the request is verified via EVDS on the first execution 
+/// only.
+fn execute_request(request: &TcmbEvdsRequest, dates: &CString) -> TcmbEvdsResult {
+
+    let rust_api_key = request.api_key.to_string_lossy();
+
+    if !request.is_api_key_verified() {
+        if let Err(return_error) = common::ApiKey::from(rust_api_key.to_string()) {
+            return handle_parameter_return_error(return_error, "api_key");
+        }
+
+        request.mark_api_key_verified();
+    }
+
+    let request_error = |message: &str| {
+        TcmbEvdsResult::generate_parameter_result(message.to_string(), ReturnErrorC::ParameterError, "request")
+    };
+
+    evds_c::with_verified_api_key(&rust_api_key, || {
+        match (&request.series, &request.data_group) {
+            (Some(series), None) if request.has_frequency_formulas() => {
+                execute_advanced_request(request, series, dates)
+            },
+            (Some(series), None) => {
+                tcmb_evds_c_get_data(
+                    to_input(series),
+                    to_input(dates),
+                    to_input(&request.api_key),
+                    request.return_format,
+                    request.ascii_mode,
+                )
+            },
+            (None, Some(_)) if request.has_frequency_formulas() => {
+                request_error("Error: Frequency formulas are only supported for data series, not for data groups.")
+            },
+            (None, Some(data_group)) => execute_data_group_request(request, data_group, dates),
+            (Some(_), Some(_)) => request_error("Error: The request has both a data series and a data group."),
+            (None, None) => request_error("Error: The request has neither a data series nor a data group."),
+        }
+    })
+}
+
+/// requests the series of the request with its frequency formulas.
+#[cfg(feature = "currency")]
+fn execute_advanced_request(request: &TcmbEvdsRequest, series: &CString, dates: &CString) -> TcmbEvdsResult {
+    tcmb_evds_c_get_advanced_data(
+        to_input(series),
+        to_input(dates),
+        request.aggregation_type.unwrap_or(TcmbEvdsAggregationType::Average as c_uint),
+        request.formula.unwrap_or(TcmbEvdsFormula::Level as c_uint),
+        request.frequency.unwrap_or(TcmbEvdsDataFrequency::Daily as c_uint),
+        to_input(&request.api_key),
+        request.return_format,
+        request.ascii_mode,
+    )
+}
+
+/// returns an error since the frequency formulas are requested via the functions of the `currency` feature.
+#[cfg(not(feature = "currency"))]
+fn execute_advanced_request(_request: &TcmbEvdsRequest, _series: &CString, _dates: &CString) -> TcmbEvdsResult {
+
+    let error_message = "Error: Frequency formulas need the library built with the `currency` feature.".to_string();
+
+    TcmbEvdsResult::generate_parameter_result(error_message, ReturnErrorC::ParameterError, "request")
+}
+
+/// requests the data group of the request.
+#[cfg(feature = "catalog")]
+fn execute_data_group_request(request: &TcmbEvdsRequest, data_group: &CString, dates: &CString) -> TcmbEvdsResult {
+    tcmb_evds_c_get_data_group(
+        to_input(data_group),
+        to_input(dates),
+        to_input(&request.api_key),
+        request.return_format,
+        request.ascii_mode,
+    )
+}
+
+/// returns an error since the data groups are requested via the functions of the `catalog` feature.
+#[cfg(not(feature = "catalog"))]
+fn execute_data_group_request(_request: &TcmbEvdsRequest, _data_group: &CString, _dates: &CString) -> TcmbEvdsResult {
+
+    let error_message = "Error: Data groups need the library built with the `catalog` feature.".to_string();
+
+    TcmbEvdsResult::generate_parameter_result(error_message, ReturnErrorC::ParameterError, "request")
+}
+
+/// releases the output of the result and marks it as released in the memory statistics.
+///
+/// Releasing the output via `free` is still possible, however such results stay counted in 
+/// [`tcmb_evds_c_memory_stats`], so it is required under the budget of [`tcmb_evds_c_set_memory_budget`]. The result
+/// must not be used after this call.
+///
+/// # Safety
+///
+/// The result must be returned from this library and its output must not be freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, return_format, ascii_mode);
+///
+///     fwrite(data_result.output_ptr, data_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(data_result);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_result_free(result: TcmbEvdsResult) {
+
+    shield_or((), || {
+        // moving the result into a handle takes its output back and marks it as released.
+        let handle = TcmbEvdsHandle::from(result).into_raw();
+
+        evds_c::handle_entities::tcmb_evds_c_handle_free(handle);
+    })
+}
+
+/// computes the SHA-256 checksum of the data inside of the result as a lowercase hexadecimal string of 64 characters.
+///
+/// The checksum is computed from the canonical form of the parsed data rather than the response text. Therefore, the 
+/// same observations give the same checksum whether they are fetched in CSV, JSON or XML, and downstream systems can 
+/// deduplicate the data or verify that re-fetched data matches archived data. The given result is not freed.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the result contains an error and `UnparsableResponse` when the data of 
+/// the result could not be parsed, e.g. a categories list.
+///
+/// # Safety
+///
+/// The result must be returned from this library and its output must not be freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult checksum = tcmb_evds_c_result_checksum(data_result);
+///
+///     if (!tcmb_evds_c_is_error(checksum)) { printf("%.*s\n", (int) checksum.string_capacity, checksum.output_ptr); };
+///
+///     tcmb_evds_c_result_free(checksum);
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_result_checksum(result: TcmbEvdsResult) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_result_checksum", || {
+        let output = match result.read_data() {
+            Some(output) => output,
+            None => {
+                let error_message = "Error: The result does not contain any data.".to_string();
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, ReturnErrorC::ParameterError, "result");
+            },
+        };
+
+        let dataset = Dataset::from_response(&output);
+        if let Err(return_error) = dataset { return handle_parameter_return_error(return_error, "result"); }
+
+        let checksum = compute_checksum(&dataset.unwrap());
+
+        TcmbEvdsResult::generate_result(checksum, ReturnErrorC::NoError)
+    })
+}
+
+/// reshapes the data inside of the result into a CSV in the given layout, e.g. a multi-series JSON response into the 
+/// long `Tarih,SERIE_CODE,VALUE` layout that databases usually expect, or a long CSV back into the wide layout of EVDS.
+///
+/// The result may be in any return format and in either layout. Missing values are not written in the long layout. The
+/// given result is not freed.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the result contains an error and `UnparsableResponse` when the data of 
+/// the result could not be parsed.
+///
+/// # Safety
+///
+/// The result must be returned from this library and its output must not be freed yet.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Json, false);
+///
+///     TcmbEvdsResult long_csv = tcmb_evds_c_reshape_result(data_result, LongLayout);
+///
+///     tcmb_evds_c_result_free(long_csv);
+///     tcmb_evds_c_result_free(data_result);
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_reshape_result(result: TcmbEvdsResult, layout: c_uint) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_reshape_result", || {
+        let layout: TcmbEvdsLayout = match read_enum(layout) {
+            Ok(layout) => layout,
+            Err(return_error) => return handle_parameter_return_error(return_error, "layout"),
+        };
+
+        let output = match result.read_data() {
+            Some(output) => output,
+            None => {
+                let error_message = "Error: The result does not contain any data.".to_string();
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, ReturnErrorC::ParameterError, "result");
+            },
+        };
+
+        let dataset = Dataset::from_response(&output);
+        if let Err(return_error) = dataset { return handle_parameter_return_error(return_error, "result"); }
+
+        TcmbEvdsResult::generate_result(layout.write_csv(&dataset.unwrap()), ReturnErrorC::NoError)
+    })
+}
+
+/// gives a snapshot of the process wide counters of the requests sent to EVDS, the received bytes, the errors returned
+/// to C, the cache lookups, the retries and the latency percentiles.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsMetrics metrics = tcmb_evds_c_metrics_snapshot();
+///
+///     printf("requests: %lu, errors: %lu, p50: %.1f ms\n", metrics.requests, metrics.errors, metrics.latency_p50_ms);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_metrics_snapshot() -> TcmbEvdsMetrics {
+
+    shield_or(TcmbEvdsMetrics::zero(), || {
+        TcmbEvdsMetrics::from(&metrics::METRICS.snapshot())
+    })
+}
+
+/// gives the number of the errors of the given type returned to C so far.
+///
+/// # Example
+///
+/// ```C
+///     unsigned long invalid_date_count = tcmb_evds_c_metrics_error_count(InvalidDate);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_metrics_error_count(error_type: ReturnErrorC) -> c_ulong {
+
+    shield_or(0, || {
+        metrics::METRICS.get_error_count(&format!("{:?}", error_type)) as c_ulong
+    })
+}
+
+/// writes the process wide counters in the Prometheus text exposition format to be served from the metrics endpoint of
+/// a service embedding the library.
+///
+/// The counters are named `tcmb_evds_c_requests_total`, `tcmb_evds_c_received_bytes_total`, 
+/// `tcmb_evds_c_retries_total`, `tcmb_evds_c_cache_hits_total`, `tcmb_evds_c_cache_misses_total` and 
+/// `tcmb_evds_c_errors_total` labeled by the error type. The latencies are written as the 
+/// `tcmb_evds_c_request_latency_seconds` summary with the 0.5 and 0.95 quantiles.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult metrics_result = tcmb_evds_c_metrics_prometheus();
+///
+///     fwrite(metrics_result.output_ptr, metrics_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(metrics_result);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_metrics_prometheus() -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_metrics_prometheus", || {
+        TcmbEvdsResult::generate_result(metrics::METRICS.snapshot().to_prometheus(), ReturnErrorC::NoError)
+    })
+}
+
+/// gives the metadata identifying the build of the library as a JSON object, so support requests can tell exactly 
+/// which variant of the library a program runs.
+///
+/// The object includes the version of the crate, the git commit of the sources followed by "-dirty" for uncommitted 
+/// changes, the cargo features, the target triple, the cargo profile, the HTTP backend and the versions of the linked
+/// libcurl and its TLS library together with whether libcurl is linked statically via the `static_curl` feature, e.g.
+/// `{"curl":{"sslVersion":"OpenSSL/3.0.2","static":false,"version":"7.81.0"},"features":["cache","catalog",..],
+/// "gitHash":"d87578a..","httpBackend":"curl","profile":"release","target":"x86_64-unknown-linux-gnu",
+/// "version":"0.1.0"}`. The `curl` member is null when the library is built with the `pure_rust_http` feature. The
+/// commit is 
+/// empty when the library is built outside of a git repository without the `TCMB_EVDS_C_GIT_HASH` environment 
+/// variable. The build time is not included, so the same sources and features always give the same metadata.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult build_info = tcmb_evds_c_build_info();
+///
+///     fprintf(stderr, "tcmb_evds_c build: %s\n", build_info.output_ptr);
+///
+///     tcmb_evds_c_result_free(build_info);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_build_info() -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_build_info", || {
+        TcmbEvdsResult::generate_result(build_info::build_info_json(), ReturnErrorC::NoError)
+    })
+}
+
+/// sets the api keys of the session, separated by commas, which the calls given an empty api key use in turn. An empty
+/// list of keys clears the pool, so the empty api keys are errors as before.
+///
+/// `RoundRobinRotation` spreads the calls evenly over the keys and `QuotaErrorRotation` moves on to the next key only 
+/// after EVDS rate limits a request made with the current one. The requests made with each key and their rate limits 
+/// are counted, and the counts are read via [`tcmb_evds_c_key_pool_usage`] and [`tcmb_evds_c_metrics_prometheus`].
+///
+/// Returns false and keeps the current keys if a key includes one of the characters separating query parameters.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput api_keys;
+///
+///     api_keys.input_ptr = "FIRST_API_KEY,SECOND_API_KEY";
+///     api_keys.string_capacity = strlen(api_keys.input_ptr);
+///
+///     if (!tcmb_evds_c_set_api_keys(api_keys, QuotaErrorRotation)) { /* A Process */ };
+///
+///     api_key.input_ptr = "";
+///     api_key.string_capacity = 0;
+///
+///     // requested with FIRST_API_KEY until it is rate limited.
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Json, false);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_api_keys(api_keys: TcmbEvdsInput, rotation: c_uint) -> bool {
+
+    shield_or(false, || {
+        let (rust_api_keys, api_keys_error_state) = api_keys.get_input("api_keys");
+
+        if api_keys_error_state { return false; }
+
+        let rotation = match TcmbEvdsKeyRotation::from_value(rotation) {
+            Some(rotation) => rotation,
+            None => return false,
+        };
+
+        let keys: Vec<String> = rust_api_keys
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        if keys.iter().any(|key| check_parameter_text(key).is_err()) { return false; }
+
+        key_pool::get_key_pool().set_keys(keys, rotation.to_rotation());
+
+        true
+    })
+}
+
+/// gives the usage of each key of the key pool set via [`tcmb_evds_c_set_api_keys`] as a JSON array, e.g. 
+/// `[{"key":"****a1b2","requests":120,"quotaErrors":1}]`. The keys are masked except their last four characters.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult usage_result = tcmb_evds_c_key_pool_usage();
+///
+///     printf("%s\n", usage_result.output_ptr);
+///
+///     tcmb_evds_c_result_free(usage_result);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_key_pool_usage() -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_key_pool_usage", || {
+        let key_usage = key_pool::get_key_pool().get_usage();
+
+        TcmbEvdsResult::generate_result(key_pool::usage_to_json(&key_usage), ReturnErrorC::NoError)
+    })
+}
+
+/// checks the api key via EVDS with a cheap request of a single observation and gives the status of the key known to 
+/// the session as a JSON object, e.g. 
+/// `{"key":"****a1b2","lastSuccess":1700000000,"pooled":true,"rateLimitCount":0,"rateLimitedUntil":null,"valid":true}`.
+///
+/// `valid` is false for a key rejected by EVDS and also when EVDS could not be reached. `lastSuccess` is the time of 
+/// the last response received with the key, and `rateLimitedUntil` is the time until which EVDS asked the key to wait 
+/// after its last rate limit, both in seconds since the Unix epoch or null. The status is kept for the session, so the 
+/// key pool set via [`tcmb_evds_c_set_api_keys`] skips the keys that are invalid or still rate limited.
+///
+/// # Error
+///
+/// This function returns an error when the api key is empty or includes one of the characters separating query 
+/// parameters.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult status_result = tcmb_evds_c_key_status(api_key);
+///
+///     if (!tcmb_evds_c_is_error(status_result)) { printf("%s\n", status_result.output_ptr); };
+///
+///     tcmb_evds_c_result_free(status_result);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_key_status(api_key: TcmbEvdsInput) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_key_status", || {
+        let (rust_api_key, api_key_error_state) = api_key.get_input("api_key");
+
+        if api_key_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_api_key, ReturnErrorC::ParameterError, "api_key");
+        }
+
+        let rust_api_key = rust_api_key.trim();
+
+        if let Err(return_error) = check_parameter_text(rust_api_key) {
+            return handle_parameter_return_error(return_error, "api_key");
+        }
+
+        let is_valid = common::ApiKey::from(rust_api_key.to_string()).is_ok();
+
+        let mut key_pool = key_pool::get_key_pool();
+
+        key_pool.record_validity(rust_api_key, is_valid);
+
+        let status = key_pool::status_to_json(
+            rust_api_key,
+            &key_pool.get_status(rust_api_key),
+            key_pool.contains(rust_api_key),
+        );
+
+        TcmbEvdsResult::generate_result(status, ReturnErrorC::NoError)
+    })
+}
+
+/// sets the OTLP/HTTP traces endpoint of a collector, e.g. `"http://localhost:4318/v1/traces"`, and starts recording
+/// the spans of the operational functions. An empty endpoint stops recording. Only available when the library is built
+/// with the `tracing` feature.
+///
+/// Each operational function is a span named after the function, and its stages are the child spans `build_url`,
+/// `http`, `parse` and `convert` recorded via the `tracing` crate. The spans are exported in the OTLP JSON encoding by
+/// the batch exporter of OpenTelemetry in the background or via [`tcmb_evds_c_flush_spans`]. Changing or clearing the 
+/// endpoint exports the spans recorded so far to the former endpoint.
+///
+/// Returns false if the endpoint cannot be read or is not a valid url. It is also false in a Rust process that has
+/// already installed a subscriber of the `tracing` crate, whose layers see the spans of the library instead.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput endpoint;
+///
+///     endpoint.input_ptr = "http://localhost:4318/v1/traces";
+///     endpoint.string_capacity = strlen(endpoint.input_ptr);
+///
+///     if (!tcmb_evds_c_set_otlp_endpoint(endpoint)) { /* A Process */ };
+/// ```
+#[cfg(feature = "tracing")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_otlp_endpoint(endpoint: TcmbEvdsInput) -> bool {
+
+    shield_or(false, || {
+        let (rust_endpoint, endpoint_error_state) = endpoint.get_input("endpoint");
+
+        if endpoint_error_state { return false; }
+
+        spans::set_otlp_endpoint(&rust_endpoint)
+    })
+}
+
+/// sets the W3C `traceparent` of the span the calling thread is in, so that the spans of the following calls of the
+/// thread join the distributed trace of the caller. An empty text clears it. Only available when the library is built
+/// with the `tracing` feature.
+///
+/// Returns false and keeps the current parent if the text is not a valid `traceparent`.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput trace_parent;
+///
+///     trace_parent.input_ptr = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+///     trace_parent.string_capacity = strlen(trace_parent.input_ptr);
+///
+///     if (!tcmb_evds_c_set_trace_parent(trace_parent)) { /* A Process */ };
+/// ```
+#[cfg(feature = "tracing")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_trace_parent(trace_parent: TcmbEvdsInput) -> bool {
+
+    shield_or(false, || {
+        let (rust_trace_parent, trace_parent_error_state) = trace_parent.get_input("trace_parent");
+
+        if trace_parent_error_state { return false; }
+
+        spans::set_trace_parent(&rust_trace_parent)
+    })
+}
+
+/// exports the recorded spans to the OTLP endpoint immediately, e.g. before the process exits. Only available when the
+/// library is built with the `tracing` feature.
+///
+/// Returns false if no endpoint is set or the collector does not accept the spans. The spans are not kept for a retry,
+/// and the export waits at most 5 seconds for the collector.
+///
+/// # Example
+///
+/// ```C
+///     if (!tcmb_evds_c_flush_spans()) { /* A Process */ };
+/// ```
+#[cfg(feature = "tracing")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_flush_spans() -> bool {
+    shield_or(false, spans::flush)
+}
+
+/// gives the state of the circuit breaker guarding the requests to EVDS.
+///
+/// After 5 consecutive transport failures, namely requests that could not reach EVDS, the circuit opens and the 
+/// operational functions fail fast with `ServiceUnavailable` error for a cool-down period of 30 seconds instead of 
+/// hammering the endpoint during an outage. Then, the requests probe EVDS again; any response closes the circuit and 
+/// another failure opens it for one more cool-down period.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsHealth health = tcmb_evds_c_health();
+///
+///     if (health.circuit_state != CircuitClosed) { /* A Process */ };
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_health() -> TcmbEvdsHealth {
+
+    let fallback_health = TcmbEvdsHealth { 
+        circuit_state: TcmbEvdsCircuitState::CircuitClosed, 
+        consecutive_failures: 0, 
+        remaining_cool_down_ms: 0,
+    };
+
+    shield_or(fallback_health, || {
+        TcmbEvdsHealth::from(circuit_breaker::get_health())
+    })
+}
+
+/// gives the live allocations that are made for the results, handles and datasets handed to C and not released yet.
+///
+/// Long running programs can compare the statistics over time to detect the missing free calls.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsMemoryStats memory_stats = tcmb_evds_c_memory_stats();
+///
+///     printf("live results: %lu, live bytes: %lu\n", memory_stats.live_results, memory_stats.live_bytes);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_memory_stats() -> TcmbEvdsMemoryStats {
+
+    let fallback_stats = 
+        TcmbEvdsMemoryStats { live_results: 0, live_handles: 0, live_datasets: 0, live_bytes: 0, budget_bytes: 0 };
+
+    shield_or(fallback_stats, || {
+        let memory_budget_bytes = evds_c::settings::get_settings().memory_budget_bytes;
+
+        MEMORY_COUNTERS.generate_stats(memory_budget_bytes)
+    })
+}
+
+/// sets the limit of the live bytes reported by [`tcmb_evds_c_memory_stats`]. Zero, the default, removes the limit.
+///
+/// When the live bytes reach the budget, the operational functions fail fast with `MemoryBudgetExceeded` error without
+/// making any request until enough results, handles or datasets are freed.
+///
+/// Under a budget, the results must be freed via [`tcmb_evds_c_result_free`] rather than `free`. The library cannot see
+/// the results released via `free`, so they stay counted and keep the functions failing once the budget is reached.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_memory_budget(64 * 1024 * 1024);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_memory_budget(budget_bytes: c_ulong) {
+
+    shield_or((), || {
+        evds_c::settings::get_settings().memory_budget_bytes = budget_bytes as usize;
+    })
+}
+
+/// enables the response cache keeping the responses of the last `capacity` requests of [`tcmb_evds_c_get_data`], 
+/// [`tcmb_evds_c_get_advanced_data`], [`tcmb_evds_c_get_data_group`], [`tcmb_evds_c_get_categories`], 
+/// [`tcmb_evds_c_get_advanced_data_group`] and [`tcmb_evds_c_get_series_list`]. Zero, the default, disables the cache 
+/// and drops the cached responses.
+///
+/// The cached responses are keyed by the requests without their api keys, so a cached request is served without 
+/// requesting EVDS even if it is asked with another api key, including an invalid or revoked one. Processes making the
+/// requests of several users should therefore keep the cache disabled or clear it via 
+/// [`tcmb_evds_c_clear_response_cache`] when the users change. The data and data group
+/// responses are served in another return format as well, e.g. a series fetched in CSV is served in JSON by converting
+/// the cached response. Since these responses are parsed to be cached, the data is requested in JSON and serialized 
+/// again in the return format while the cache is enabled, whether the response is cached or not. The date ranges of a 
+/// request separated by semicolons are cached one by one.
+///
+/// The responses of the data endpoints expire after an hour and the responses of the catalog endpoints, namely 
+/// categories, advanced data groups and series lists, after a day by default, which 
+/// [`tcmb_evds_c_set_response_cache_max_age`] and [`tcmb_evds_c_set_catalog_cache_max_age`] change.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_response_cache_capacity(32);
+///
+///     // the second request is served from the cache.
+///     TcmbEvdsResult csv_result = tcmb_evds_c_get_data(data_series, date, api_key, Csv, ascii_mode);
+///     TcmbEvdsResult json_result = tcmb_evds_c_get_data(data_series, date, api_key, Json, ascii_mode);
+/// ```
+#[cfg(feature = "cache")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_response_cache_capacity(capacity: c_uint) {
+
+    shield_or((), || {
+        get_response_cache().set_capacity(capacity as usize);
+    })
+}
+
+/// exports each series of a data group in the given date/s into a directory as CSV files, for users building offline 
+/// archives.
+///
+/// The series of the group are listed from EVDS and fetched in parallel with a rate limit. Each series is written into 
+/// a file named after the series code, e.g. `TP_DK_USD_A.csv`, with the header `Tarih,TP_DK_USD_A`. Additionally, a 
+/// `manifest.json` file lists the code, name, file and observation count of each series. A series that could not be 
+/// fetched does not stop the export; its error is written into the manifest instead. The directory is created if it 
+/// does not exist.
+///
+/// The result contains the manifest.
+///
+/// # Error
+///
+/// This function returns error when invalid data group, date, directory or api key is supplied, there is a bad internet
+/// connection, or the directory could not be written.
+///
+/// # Example
+///
+/// ```C
+///     data_group.input_ptr = "bie_dkdovytl";
+///     data_group.string_capacity = strlen(data_group.input_ptr);
+///
+///     directory.input_ptr = "./exchange_rates";
+///     directory.string_capacity = strlen(directory.input_ptr);
+///
+///     TcmbEvdsResult manifest = tcmb_evds_c_export_data_group(data_group, date, directory, api_key);
+///
+///     if (manifest.error_type == FailedToWriteFile) { /* A Process */ };
+///
+///     tcmb_evds_c_result_free(manifest);
+/// ```
+#[cfg(feature = "export")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_export_data_group(
+    data_group: TcmbEvdsInput,
+    date: TcmbEvdsInput,
+    directory: TcmbEvdsInput,
+    api_key: TcmbEvdsInput
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_export_data_group", || {
+        let (rust_data_group, data_group_error_state) = data_group.get_input("data_group");
+        let (rust_date, date_error_state) = date.get_input("date");
+        let (rust_directory, directory_error_state) = directory.get_input("directory");
+
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        if data_group_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_data_group, parameter_error, "data_group");
+        }
+        if date_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_date, parameter_error, "date");
+        }
+        if directory_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_directory, parameter_error, "directory");
+        }
+
+
+        let date_preferences = match generate_date_preferences(&rust_date) {
+            Ok(preferences) => preferences,
+            Err(error_result) => return error_result,
+        };
+
+        let date_preferences = match check_date_kind(date_preferences, DateKind::Range, "date") {
+            Ok(preferences) => preferences,
+            Err(error_result) => return error_result,
+        };
+
+        let date_preferences = match check_date_span(date_preferences, None) {
+            Ok(preferences) => preferences,
+            Err(error_result) => return error_result,
+        };
+
+        let evds = match generate_evds(api_key, TcmbEvdsReturnFormat::Json) {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        let manifest = 
+            evds_basic::export::export_data_group(&rust_data_group, &date_preferences, &rust_directory, &evds);
+
+
+        return_response(manifest, false)
+    })
+}
+
+/// pre-populates the response cache and the observation store with the series listed in a manifest file, so that a 
+/// service starts with hot data after a deployment.
+///
+/// The manifest lists each series with its `code`, `startDate` and optional `endDate`, either in JSON, e.g. 
+/// `{"series":[{"code":"TP.DK.USD.S","startDate":"01-01-2020","endDate":"31-12-2020"}]}`, or in TOML with a 
+/// `[[series]]` table for each series holding the same keys with string values, e.g. `startDate = "01-01-2020"`. The
+/// series are fetched in parallel with a rate limit and kept by the response cache enabled via 
+/// [`tcmb_evds_c_set_response_cache_capacity`] and the store opened via [`tcmb_evds_c_open_observation_store`], so they
+/// should be set up first. A series that could not be fetched does not
+/// stop the warm-up; the result contains a JSON object with the number of the warmed and failed series and the error 
+/// of each series.
+///
+/// # Error
+///
+/// This function returns `ParameterError` when the manifest could not be read, `UnparsableResponse` when it could not 
+/// be parsed, `InvalidDate` when one of its dates is invalid, and error when an invalid api key is supplied.
+///
+/// # Example
+///
+/// ```C
+///     manifest_path.input_ptr = "/etc/tcmb_evds/warm_up.toml";
+///     manifest_path.string_capacity = strlen(manifest_path.input_ptr);
+///
+///     tcmb_evds_c_set_response_cache_capacity(256);
+///
+///     TcmbEvdsResult warm_up_report = tcmb_evds_c_warm_cache(manifest_path, api_key);
+///
+///     if (warm_up_report.error_type == NoError) { /* A Process */ };
+///
+///     tcmb_evds_c_result_free(warm_up_report);
+/// ```
+#[cfg(feature = "cache")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_warm_cache(manifest_path: TcmbEvdsInput, api_key: TcmbEvdsInput) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_warm_cache", || {
+        let (rust_manifest_path, manifest_path_error_state) = manifest_path.get_input("manifest_path");
+
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        if manifest_path_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_manifest_path, parameter_error, "manifest_path");
+        }
+
+        let manifest = match fs::read_to_string(&rust_manifest_path) {
+            Ok(manifest) => manifest,
+            Err(_) => {
+                let error_message = format!("Error: The manifest \"{}\" could not be read.", rust_manifest_path);
+
+                return TcmbEvdsResult::generate_parameter_result(error_message, parameter_error, "manifest_path");
+            },
+        };
+
+        let evds = match generate_evds(api_key, TcmbEvdsReturnFormat::Json) {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        match evds_basic::warm_up::warm_cache(&manifest, &evds) {
+            Ok(report) => TcmbEvdsResult::generate_result(report, ReturnErrorC::NoError),
+            Err(return_error) => handle_parameter_return_error(return_error, "manifest_path"),
+        }
+    })
+}
+
+/// opens a persistent observation store in the given directory, creating the directory if needed, and keeps the 
+/// historical observations of [`tcmb_evds_c_get_data`] there.
+///
+/// A window that ends before today is requested once and served from the store afterwards, also in later sessions. 
+/// Windows inside of a stored window are served as well for daily series. The observations are kept in append-only 
+/// segment files of each series which are compacted periodically. Since the responses are parsed to be stored, the data
+/// is requested in JSON and converted into CSV or XML while the store is open.
+///
+/// Returns false and keeps the current store if the directory cannot be created.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput directory;
+///
+///     directory.input_ptr = "/var/cache/tcmb_evds";
+///     directory.string_capacity = strlen(directory.input_ptr);
+///
+///     if (!tcmb_evds_c_open_observation_store(directory)) { /* A Process */ };
+/// ```
+#[cfg(feature = "cache")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_open_observation_store(directory: TcmbEvdsInput) -> bool {
+
+    shield_or(false, || {
+        let (rust_directory, directory_error_state) = directory.get_input("directory");
+
+        if directory_error_state { return false; }
+
+        let observation_store = match ObservationStore::open(&rust_directory) {
+            Some(observation_store) => observation_store,
+            None => return false,
+        };
+
+        *get_observation_store() = Some(observation_store);
+
+        true
+    })
+}
+
+/// closes the observation store. The stored observations remain on the disk to be reused later.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_close_observation_store();
+/// ```
+#[cfg(feature = "cache")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_close_observation_store() {
+
+    shield_or((), || {
+        *get_observation_store() = None;
+    })
+}
+
+/// records the request and response pairs of EVDS into the given directory or replays the later calls from there, 
+/// creating the directory if needed.
+///
+/// `RecordResponses` saves each response received from EVDS as a JSON file including the url of the request whose api 
+/// key is masked. `ReplayResponses` serves the calls from those files without requesting EVDS and returns the 
+/// `RecordingNotFound` error for a request that was not recorded. Since the api key is masked, the recordings of a bug 
+/// report can be shared and replayed with any key. `RecordingOff` stops recording and replaying, and ignores the 
+/// directory.
+///
+/// Returns false and keeps the current mode if the directory cannot be created.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput directory;
+///
+///     directory.input_ptr = "/tmp/tcmb_evds_recordings";
+///     directory.string_capacity = strlen(directory.input_ptr);
+///
+///     if (!tcmb_evds_c_set_recording(ReplayResponses, directory)) { /* A Process */ };
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_recording(mode: c_uint, directory: TcmbEvdsInput) -> bool {
+
+    shield_or(false, || {
+        let mode = match TcmbEvdsRecordingMode::from_value(mode) {
+            Some(mode) => mode,
+            None => return false,
+        };
+
+        let recording_mode = match mode.to_mode() {
+            Some(recording_mode) => recording_mode,
+            None => {
+                recording::stop();
+
+                return true;
+            },
+        };
+
+        let (rust_directory, directory_error_state) = directory.get_input("directory");
+
+        if directory_error_state { return false; }
+
+        recording::start(recording_mode, &rust_directory)
+    })
+}
+
+/// registers the hook called with the parsed dataset of each data response before the response is returned, so that 
+/// everything fetched can be archived, e.g. to S3, without wrapping every call. NULL hook removes the current hook.
+///
+/// The hook is called on the thread making the call with the given user data, and the dataset is valid only during the
+/// hook. The hooks of the calls made from multiple threads may run at the same time. The responses that are not data,
+/// e.g. categories, or that could not be parsed do not call the hook.
+///
+/// # Example
+///
+/// ```C
+///     void archive(const TcmbEvdsDataset* dataset, void* user_data) {
+///         TcmbEvdsResult csv = tcmb_evds_c_dataset_to_csv(dataset, LongLayout);
+///
+///         fwrite(csv.output_ptr, csv.string_capacity, 1, (FILE*) user_data);
+///
+///         tcmb_evds_c_result_free(csv);
+///     }
+///
+///     tcmb_evds_c_set_response_hook(archive, archive_file);
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_response_hook(hook: TcmbEvdsResponseHook, user_data: *mut c_void) {
+
+    shield_or((), || {
+        *get_response_hook() = hook.map(|hook| ResponseHook::new(hook, user_data));
+    })
+}
+
+/// registers the hook called before each request is sent to EVDS, so that organizational policies such as "no requests
+/// outside business hours" or per-team quota accounting are applied to every call. NULL hook removes the current hook.
+///
+/// The hook receives the url of the request with its api key masked and the given user data. It returns NULL to let the
+/// request be sent, or a message to veto it, in which case the call returns `RequestVetoed` error with the message. The 
+/// hook may also annotate the request with a header line that is sent with it. The responses served from the response
+/// cache or the recordings are not requested, so they do not call the hook.
+///
+/// # Example
+///
+/// ```C
+///     const char* apply_policy(TcmbEvdsRequestInfo* request, void* user_data) {
+///         if (!is_business_hours()) { return "no requests outside business hours"; }
+///
+///         ((TeamQuota*) user_data)->requests++;
+///         request->annotation = "X-Team: research";
+///
+///         return NULL;
+///     }
+///
+///     tcmb_evds_c_set_request_hook(apply_policy, &team_quota);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_request_hook(hook: TcmbEvdsRequestHook, user_data: *mut c_void) {
+
+    shield_or((), || {
+        *get_request_hook() = hook.map(|hook| RequestHook::new(hook, user_data));
+    })
+}
+
+/// sets the maximum age of the responses of the data endpoints in the response cache in seconds, which is an hour by 
+/// default. An expired response is requested from EVDS again, but it is kept to be served as a stale response via 
+/// [`tcmb_evds_c_set_stale_if_error`]. Zero makes the cached responses never expire.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_response_cache_capacity(32);
+///     tcmb_evds_c_set_response_cache_max_age(15 * 60);
+/// ```
+#[cfg(feature = "cache")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_response_cache_max_age(max_age_seconds: c_uint) {
+
+    shield_or((), || {
+        let max_age = match max_age_seconds {
+            0 => None,
+            max_age_seconds => Some(Duration::from_secs(u64::from(max_age_seconds))),
+        };
+
+        get_response_cache().set_max_age(CacheEndpoint::Data, max_age);
+    })
+}
+
+/// sets the maximum age of the responses of the catalog endpoints, namely categories, advanced data groups and series 
+/// lists, in the response cache in seconds. The catalog rarely changes, so its responses expire after a day by default.
+/// Zero makes the cached responses never expire.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_response_cache_capacity(32);
+///     tcmb_evds_c_set_catalog_cache_max_age(7 * 24 * 60 * 60);
+/// ```
+#[cfg(feature = "cache")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_catalog_cache_max_age(max_age_seconds: c_uint) {
+
+    shield_or((), || {
+        let max_age = match max_age_seconds {
+            0 => None,
+            max_age_seconds => Some(Duration::from_secs(u64::from(max_age_seconds))),
+        };
+
+        get_response_cache().set_max_age(CacheEndpoint::Catalog, max_age);
+    })
+}
+
+/// enables or disables stale responses. Stale responses are disabled by default.
+///
+/// When enabled, a request of the response cache failing due to the network, e.g. during an outage of EVDS, a timeout 
+/// or a rate limit, returns the response cached for the same request instead of the error, even if the response is 
+/// expired. The result of a stale response has no error and has the error of the failed request as its detail, e.g. 
+/// "Stale: Error: Bad internet connection.", which [`tcmb_evds_c_is_stale`] checks. The response cache must be enabled
+/// via [`tcmb_evds_c_set_response_cache_capacity`] to keep the responses.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_response_cache_capacity(32);
+///     tcmb_evds_c_set_stale_if_error(true);
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Json, false);
+///
+///     if (tcmb_evds_c_is_stale(data_result)) { /* Showing a warning on the dashboard */ };
+/// ```
+#[cfg(feature = "cache")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_stale_if_error(stale_if_error: bool) {
+
+    shield_or((), || {
+        evds_c::settings::get_settings().stale_if_error = stale_if_error;
+    })
+}
+
+/// checks whether the result is a stale response served from the response cache after its request failed. The error of
+/// the request is the detail of the result.
+///
+/// # Example
+///
+/// ```C
+///     if (tcmb_evds_c_is_stale(data_result)) { 
+///         printf("%.*s\n", (int) data_result.detail_capacity, data_result.detail_ptr); 
+///     };
+/// ```
+#[cfg(feature = "cache")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_is_stale(result: TcmbEvdsResult) -> bool {
+
+    shield_or(false, || {
+        matches!(result.error_type, ReturnErrorC::NoError) && !result.detail_ptr.is_null()
+    })
+}
+
+/// drops all of the cached responses and keeps the capacity of the response cache.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_clear_response_cache();
+/// ```
+#[cfg(feature = "cache")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_clear_response_cache() {
+
+    shield_or((), || {
+        get_response_cache().clear();
+    })
+}
+
+/// provides users an ability to check whether the result includes error or not. 
+///
+/// # Example
+/// 
+/// ```C
+///     // requesting data.
+///     TcmbEvdsResult series_list = tcmb_evds_c_get_series_list(code, api_key, return_format, ascii_mode);
+///
+///
+///     // handling error.
+///     printf("\nError: %s", tcmb_evds_c_is_error(series_list) ? "true" : "false");
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_is_error(result: TcmbEvdsResult) -> bool {
+
+    shield_or(true, || {
+        if let ReturnErrorC::NoError = result.error_type { return false; }
+    
+        true
+    })
+}
+
+/// checks whether CBRT publishes exchange rates at the given date, e.g. "13-12-2011", so that a daily exchange rate for
+/// the date exists in EVDS.
+///
+/// Weekends, national holidays and religious holidays are not publication days. The religious holidays are embedded 
+/// from 2015 to 2027, so the other weekdays which are not national holidays give `UnknownPublicationDay`. The eves of
+/// the holidays are half days on which exchange rates are still published.
+///
+/// Returns `InvalidPublicationDate` if the date is invalid.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsInput date;
+///
+///     date.input_ptr = "29-10-2018";
+///     date.string_capacity = strlen(date.input_ptr);
+///
+///
+///     // Republic Day, so there is no exchange rate for the date.
+///     if (tcmb_evds_c_is_publication_day(date) == NoPublicationDay) { /* A Process */ };
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_is_publication_day(date: TcmbEvdsInput) -> TcmbEvdsPublicationDay {
+
+    shield_or(TcmbEvdsPublicationDay::InvalidPublicationDate, || {
+        let (rust_date, date_error_state) = date.get_input("date");
+
+        if date_error_state { return TcmbEvdsPublicationDay::InvalidPublicationDate; }
+
+        let is_strict = evds_c::settings::get_settings().strict_date_separators;
+        let rust_date = evds_c::normalize_date_separators(rust_date.trim().to_string(), is_strict);
+
+        match CalendarDate::parse(&rust_date) {
+            Some(date) => TcmbEvdsPublicationDay::from(publication_calendar::is_publication_day(date)),
+            None => TcmbEvdsPublicationDay::InvalidPublicationDate,
+        }
+    })
+}
+
+/// converts a date of EVDS, e.g. "13-12-2011", into the seconds passed since 01-01-1970 00:00 UTC until the start of the
+/// date on Turkish time, which is the `UNIXTIME` of the date in EVDS responses, e.g. 1323727200.
+///
+/// Turkish time is UTC+03:00 since 2016 and it was UTC+02:00 in winter and UTC+03:00 in summer before.
+///
+/// Returns false without writing when the date is invalid or the epoch is NULL.
+///
+/// # Safety
+///
+/// The epoch must be NULL or a valid pointer to a 64 bit integer.
+///
+/// # Example
+///
+/// ```C
+///     int64_t epoch;
+///
+///     if (tcmb_evds_c_date_to_epoch(date, &epoch)) { printf("%lld\n", (long long) epoch); };
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_date_to_epoch(date: TcmbEvdsInput, epoch: *mut i64) -> bool {
+
+    shield_or(false, || {
+        let (rust_date, date_error_state) = date.get_input("date");
+
+        if date_error_state { return false; }
+
+        let is_strict = evds_c::settings::get_settings().strict_date_separators;
+        let rust_date = evds_c::normalize_date_separators(rust_date.trim().to_string(), is_strict);
+
+        match (CalendarDate::parse(&rust_date), epoch.as_mut()) {
+            (Some(date), Some(epoch)) => {
+                *epoch = date.to_epoch_seconds();
+                true
+            },
+            _ => false,
+        }
+    })
+}
+
+/// converts the seconds passed since 01-01-1970 00:00 UTC, e.g. the `UNIXTIME` of EVDS responses, into the date on 
+/// Turkish time in "dd-mm-yyyy" format that EVDS expects, e.g. "13-12-2011" for 1323727200.
+///
+/// Any second of a day gives the same date, so the epochs of other time zones are converted to the days of Turkey.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult date_result = tcmb_evds_c_epoch_to_date(1323727200);
+///
+///     fwrite(date_result.output_ptr, date_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(date_result);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_epoch_to_date(epoch: i64) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_epoch_to_date", || {
+        let date = CalendarDate::from_epoch_seconds(epoch).format();
+
+        TcmbEvdsResult::generate_result(date, ReturnErrorC::NoError)
+    })
+}
+
+/// gives the text of the aggregation type in the urls sent to EVDS, e.g. "last" for `End`, as a NUL terminated string.
+///
+/// The text belongs to the library and must not be freed. An unknown value gives the text of `Average` that the calls
+/// use instead, or NULL when strict enums are enabled via [`tcmb_evds_c_set_strict_enums`].
+///
+/// # Example
+///
+/// ```C
+///     printf("aggregationTypes=%s\n", tcmb_evds_c_aggregation_to_string(End));
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_aggregation_to_string(aggregation_type: c_uint) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        match convert_enum::<TcmbEvdsAggregationType, frequency_formulas::AggregationType>(aggregation_type) {
+            Ok(aggregation_type) => to_static_c_string(aggregation_type.as_str()),
+            Err(_) => ptr::null(),
+        }
+    })
+}
+
+/// gives the text of the formula in the urls sent to EVDS, e.g. "1" for `PercentageChange`, as a NUL terminated 
+/// string.
+///
+/// The text belongs to the library and must not be freed. An unknown value gives the text of `Level` that the calls
+/// use instead, or NULL when strict enums are enabled via [`tcmb_evds_c_set_strict_enums`].
+///
+/// # Example
+///
+/// ```C
+///     printf("formulas=%s\n", tcmb_evds_c_formula_to_string(PercentageChange));
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_formula_to_string(formula: c_uint) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        match convert_enum::<TcmbEvdsFormula, frequency_formulas::Formula>(formula) {
+            Ok(formula) => to_static_c_string(formula.as_str()),
+            Err(_) => ptr::null(),
+        }
+    })
+}
+
+/// gives the text of the data frequency in the urls sent to EVDS, e.g. "5" for `Monthly`, as a NUL terminated string.
+///
+/// The text belongs to the library and must not be freed. An unknown value gives the text of `Daily` that the calls
+/// use instead, or NULL when strict enums are enabled via [`tcmb_evds_c_set_strict_enums`].
+///
+/// # Example
+///
+/// ```C
+///     printf("frequency=%s\n", tcmb_evds_c_frequency_to_string(Monthly));
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_frequency_to_string(data_frequency: c_uint) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        match convert_enum::<TcmbEvdsDataFrequency, frequency_formulas::DataFrequency>(data_frequency) {
+            Ok(data_frequency) => to_static_c_string(data_frequency.as_str()),
+            Err(_) => ptr::null(),
+        }
+    })
+}
+
+/// gives the text of the return format in the urls sent to EVDS, e.g. "json" for `Auto`, as a NUL terminated string.
+///
+/// `SessionFormat` gives the text of the return format of the session defaults set via 
+/// [`tcmb_evds_c_set_session_defaults`]. The text belongs to the library and must not be freed. An unknown value gives
+/// the text of `Json` that the calls use instead, or NULL when strict enums are enabled via 
+/// [`tcmb_evds_c_set_strict_enums`].
+///
+/// # Example
+///
+/// ```C
+///     printf("type=%s\n", tcmb_evds_c_format_to_string(SessionFormat));
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_format_to_string(return_format: c_uint) -> *const c_char {
+
+    shield_or(ptr::null(), || {
+        let return_format = match read_enum(return_format) {
+            Ok(TcmbEvdsReturnFormat::SessionFormat) => evds_c::settings::get_settings().session_return_format,
+            Ok(return_format) => return_format,
+            Err(_) => return ptr::null(),
+        };
+
+        to_static_c_string(return_format.convert().as_str())
+    })
+}
+
+/// builds the code of the currency series in the syntax expected by EVDS, e.g. "TP.DK.USD.S.YTL" for the selling 
+/// series of `Usd` in ytl mode, so that the series given to [`tcmb_evds_c_get_data`] are not mistyped.
+///
+/// `BothSides` gives the buying and the selling series separated by a dash, e.g. "TP.DK.USD.A-TP.DK.USD.S". The
+/// result must be released via [`tcmb_evds_c_result_free`].
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult series_result = tcmb_evds_c_build_currency_series(Eur, BuyingSide, true);
+///
+///     // "TP.DK.EUR.A.YTL"
+///     fwrite(series_result.output_ptr, series_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(series_result);
+/// ```
+#[cfg(feature = "currency")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_build_currency_series(
+    currency_code: c_uint,
+    exchange_side: c_uint,
+    ytl_mode: bool
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_build_currency_series", || {
+        let mut parameter_errors = ParameterErrors::new();
+
+        let rust_currency_code =
+            parameter_errors.check("currency_code", convert_enum::<TcmbEvdsCurrencyCode, _>(currency_code));
+        let rust_exchange_type =
+            parameter_errors.check("exchange_side", convert_enum::<TcmbEvdsExchangeSide, _>(exchange_side));
+
+        if let Err(error_result) = parameter_errors.into_result() { return error_result; }
+
+        // all of the parameters are checked above.
+        match build_currency_series(&rust_currency_code.unwrap(), &rust_exchange_type.unwrap(), ytl_mode) {
+            Ok(currency_series) => TcmbEvdsResult::generate_result(currency_series, ReturnErrorC::NoError),
+            Err(return_error) => handle_return_error(return_error),
+        }
+    })
+}
+
+/// describes how the library reads a series code as a JSON object, which helps to find the cause of the
+/// `InvalidSeries` errors.
+///
+/// A currency series such as "TP.DK.USD.S.YTL" is described by its currency, exchange side and ytl mode, e.g.
+/// `{"code":"TP.DK.USD.S.YTL","currency":"USD","kind":"currency","side":"selling","ytlMode":true}`. The other codes
+/// are described by their data groups, frequencies and first dates kept from the series lists received before, e.g.
+/// `{"code":"TP.FG.J0","dataGroup":"bie_tukfiy4","frequency":"Monthly","kind":"catalog","startDate":"01-01-2003"}`,
+/// where the unknown fields are null. The error of the currency series parser is returned for a code that is neither
+/// a currency series nor a series in a received series list.
+///
+/// # Example
+///
+/// ```C
+///     series_code.input_ptr = "TP.DK.USD.S.YTL";
+///     series_code.string_capacity = strlen(series_code.input_ptr);
+///
+///     TcmbEvdsResult description = tcmb_evds_c_describe_series(series_code);
+///
+///     fwrite(description.output_ptr, description.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(description);
+/// ```
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_describe_series(series_code: TcmbEvdsInput) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_describe_series", || {
+        let (rust_series_code, series_code_error_state) = series_code.get_input("series_code");
+
+        if series_code_error_state {
+            return TcmbEvdsResult::generate_parameter_result(
+                rust_series_code,
+                ReturnErrorC::ParameterError,
+                "series_code"
+            );
+        }
+
+        match describe_series(&rust_series_code, &dataset::catalog::get_series_catalog()) {
+            Ok(description) => TcmbEvdsResult::generate_result(description, ReturnErrorC::NoError),
+            Err(return_error) => handle_parameter_return_error(return_error, "series_code"),
+        }
+    })
+}
+
+/// sets the process wide ascii mode that overrides the `ascii_mode` argument of the operational functions, so that 
+/// wrappers do not have to pass the flag through every layer.
+///
+/// `AsciiModeDefault` is the default and uses the argument of each call. `AsciiModeForceOn` converts every response 
+/// into ASCII characters and `AsciiModeForceOff` never converts them whatever the argument is. An out of range ascii 
+/// mode is ignored.
+///
+/// # Example
+///
+/// ```C
+///     // every response is converted into ASCII characters from now on.
+///     tcmb_evds_c_set_ascii_mode(AsciiModeForceOn);
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, return_format, false);
+///
+///     // each call decides again.
+///     tcmb_evds_c_set_ascii_mode(AsciiModeDefault);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_ascii_mode(ascii_mode: c_uint) {
+
+    shield_or((), || {
+        if let Some(ascii_mode) = TcmbEvdsAsciiMode::from_value(ascii_mode) {
+            evds_c::settings::get_settings().ascii_mode = ascii_mode;
+        }
+    })
+}
+
+/// sets the pipeline of built-in post-processors run in the given order on each data response of the session, so 
+/// that the features shaping the responses are chosen once instead of being passed to every call.
+///
+/// The pipeline runs on the responses of `tcmb_evds_c_get_data`, `tcmb_evds_c_get_data_with_headers`, 
+/// `tcmb_evds_c_get_last_n`, `tcmb_evds_c_get_real_data`, `tcmb_evds_c_get_basket_index`, 
+/// `tcmb_evds_c_get_advanced_data`, `tcmb_evds_c_get_advanced_currency_data` and `tcmb_evds_c_get_data_group` before 
+/// the `ascii_mode` argument is applied. `target_unit` is the unit of `UnitRescalePostProcessor`, e.g. 
+/// "thousand TRY", and `decimal_places` is the number of digits after the dot written by `RoundingPostProcessor`, e.g. 
+/// "1.8530" with 4 decimal places. They are ignored by the other post-processors. The header translation renames the 
+/// series, so it must come after the post-processors reading them, namely the unit rescale. A response whose 
+/// post-processor fails returns the error of the post-processor, e.g. `IncompatibleUnitsError`. The values are kept as
+/// they are received unless a post-processor rewrites them. A count of zero clears the pipeline.
+///
+/// Returns false and keeps the current pipeline if a post-processor is invalid, the target unit of 
+/// `UnitRescalePostProcessor` could not be parsed or the decimal places of `RoundingPostProcessor` are more than 15.
+///
+/// # Safety
+///
+/// The post-processors must be a valid pointer to `count` post-processors unless the count is zero. The 
+/// post-processors are read as integers, so an array of `unsigned int` holding the `TcmbEvdsPostProcessor` options is
+/// expected.
+///
+/// # Example
+///
+/// ```C
+///     unsigned int post_processors[] = 
+///         { UnitRescalePostProcessor, RoundingPostProcessor, EnglishHeadersPostProcessor };
+///
+///     TcmbEvdsInput target_unit;
+///
+///     target_unit.input_ptr = "million TRY";
+///     target_unit.string_capacity = strlen(target_unit.input_ptr);
+///
+///     // the values are in millions of TRY with 2 decimal places and the headers are the English names of the series 
+///     // from now on.
+///     if (!tcmb_evds_c_set_post_processors(post_processors, 3, target_unit, 2)) { /* A Process */ };
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Csv, false);
+///
+///     // the responses are returned as they are received.
+///     tcmb_evds_c_set_post_processors(NULL, 0, target_unit, 0);
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub unsafe extern "C" fn tcmb_evds_c_set_post_processors(
+    post_processors: *const c_uint,
+    count: c_ulong,
+    target_unit: TcmbEvdsInput,
+    decimal_places: c_uint
+) -> bool {
+
+    shield_or(false, || {
+        let c_post_processors = match (count, post_processors.is_null()) {
+            (0, _) => &[][..],
+            (_, true) => return false,
+            (_, false) => std::slice::from_raw_parts(post_processors, count as usize),
+        };
+
+        let (rust_target_unit, target_unit_error_state) = target_unit.get_input("target_unit");
+
+        let target_unit = match target_unit_error_state {
+            true => None,
+            false => SeriesUnit::parse(&rust_target_unit),
+        };
+
+        match read_post_processors(c_post_processors, target_unit.as_ref(), decimal_places) {
+            Some(rust_post_processors) => {
+                evds_c::settings::get_settings().post_processors = rust_post_processors;
+                true
+            },
+            None => false,
+        }
+    })
+}
+
+/// sets the defaults of the session so that the operational functions can be called with empty inputs meaning "use 
+/// the session default" instead of marshalling the same arguments in every call.
+///
+/// A call given an empty date uses the date of the session, which is `date` if it is not empty, the range of 
+/// `trailing_days` ending today if it is not zero, or the range of `date_policy` otherwise, e.g. `YearToDate`. A call 
+/// given `SessionFormat` as the return format uses `return_format`. `ascii_mode` is set as with 
+/// [`tcmb_evds_c_set_ascii_mode`]. An empty date, zero trailing days and `NoDatePolicy` leave the calls without a 
+/// default date, so their empty dates are errors as before.
+///
+/// Returns false and keeps the current defaults if the date is invalid, an enum is out of range or the return format is
+/// `SessionFormat`.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsSessionDefaults defaults;
+///
+///     defaults.date.input_ptr = "";
+///     defaults.date.string_capacity = 0;
+///     defaults.trailing_days = 365;
+///     defaults.date_policy = NoDatePolicy;
+///     defaults.return_format = Json;
+///     defaults.ascii_mode = AsciiModeForceOn;
+///
+///     if (!tcmb_evds_c_set_session_defaults(defaults)) { /* A Process */ };
+///
+///     TcmbEvdsInput empty_date;
+///
+///     empty_date.input_ptr = "";
+///     empty_date.string_capacity = 0;
+///
+///     // the observations of the last year in JSON with ASCII characters.
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, empty_date, api_key, SessionFormat, false);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_session_defaults(defaults: TcmbEvdsSessionDefaults) -> bool {
+
+    shield_or(false, || {
+        let date_policy = TcmbEvdsDatePolicy::from_value(defaults.date_policy);
+        let return_format = TcmbEvdsReturnFormat::from_value(defaults.return_format);
+        let ascii_mode = TcmbEvdsAsciiMode::from_value(defaults.ascii_mode);
+
+        let (date_policy, return_format, ascii_mode) = match (date_policy, return_format, ascii_mode) {
+            (_, Some(TcmbEvdsReturnFormat::SessionFormat), _) => return false,
+            (Some(date_policy), Some(return_format), Some(ascii_mode)) => (date_policy, return_format, ascii_mode),
+            _ => return false,
+        };
+
+        let rust_date = match defaults.date.input_ptr.is_null() {
+            true => String::new(),
+            false => match defaults.date.get_input("date") {
+                (_, true) => return false,
+                (rust_date, false) => rust_date.trim().to_string(),
+            },
+        };
+
+        // the error result of an invalid date is released here since it is not returned to C.
+        let session_date = match (rust_date.is_empty(), defaults.trailing_days) {
+            (false, _) if collect_problem(|| generate_date_preferences(&rust_date)).is_err() => return false,
+            (false, _) => Some(SessionDate::Fixed(rust_date)),
+            (true, 0) => match date_policy {
+                TcmbEvdsDatePolicy::NoDatePolicy => None,
+                TcmbEvdsDatePolicy::LastThirtyDays => Some(SessionDate::Trailing(30)),
+                TcmbEvdsDatePolicy::YearToDate => Some(SessionDate::YearToDate),
+                TcmbEvdsDatePolicy::FullHistory => Some(SessionDate::FullHistory),
+            },
+            (true, trailing_days) => Some(SessionDate::Trailing(trailing_days)),
+        };
+
+        let mut settings = evds_c::settings::get_settings();
+
+        settings.session_date = session_date;
+        settings.session_return_format = return_format;
+        settings.ascii_mode = ascii_mode;
+
+        true
+    })
+}
+
+/// clears the defaults of the session set via [`tcmb_evds_c_set_session_defaults`], so the calls given an empty date 
+/// fail, `SessionFormat` means `Json`, and the `ascii_mode` argument of each call is used.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_clear_session_defaults();
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_clear_session_defaults() {
+
+    shield_or((), || {
+        let mut settings = evds_c::settings::get_settings();
+
+        settings.session_date = None;
+        settings.session_return_format = TcmbEvdsReturnFormat::Json;
+        settings.ascii_mode = TcmbEvdsAsciiMode::AsciiModeDefault;
+    })
+}
+
+/// enables or disables strict enums for all FFI functions. Strict enums are disabled by default.
+///
+/// Out of range enum values, e.g. uninitialized `TcmbEvdsReturnFormat` or `TcmbEvdsFormula` variables, and unknown 
+/// currency codes silently fall back to the default options when strict enums are disabled. When enabled, such values 
+/// make the functions return `InvalidEnumValue` error instead.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_strict_enums(true);
+///
+///     TcmbEvdsReturnFormat return_format;  // uninitialized.
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, return_format, ascii_mode);
+///
+///     // the uninitialized return format is rejected unless it happens to be a valid value.
+///     if (data_result.error_type == InvalidEnumValue) { /* A Process */ };
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_strict_enums(strict_enums: bool) {
+
+    shield_or((), || {
+        evds_c::settings::get_settings().strict_enums = strict_enums;
+    })
+}
+
+/// enables or disables strict dates for all FFI functions. Strict dates are disabled by default.
+///
+/// A single date given to a function needing a date range, e.g. [`tcmb_evds_c_get_real_data`], 
+/// [`tcmb_evds_c_get_basket_index`] and [`tcmb_evds_c_export_data_group`], is used as a range starting and ending at 
+/// it when strict dates are disabled. When enabled, such a date makes the function return `MultipleDateExpected` 
+/// error instead, and a range given for a single date, e.g. the base date of [`tcmb_evds_c_get_basket_index`], returns
+/// `SingleDateExpected` error.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_strict_dates(true);
+///
+///     date.input_ptr = "02-01-2023";
+///     date.string_capacity = strlen(date.input_ptr);
+///
+///     TcmbEvdsResult basket_result = tcmb_evds_c_get_basket_index(basket, base_date, date, api_key, Csv, ascii_mode);
+///
+///     if (basket_result.error_type == MultipleDateExpected) { /* A Process */ };
+///
+///     tcmb_evds_c_result_free(basket_result);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_strict_dates(strict_dates: bool) {
+
+    shield_or((), || {
+        evds_c::settings::get_settings().strict_dates = strict_dates;
+    })
+}
+
+/// enables or disables strict date separators for all FFI functions. Strict date separators are disabled by default.
+///
+/// The dates written with dots or slashes, e.g. "13.12.2011" and "13/12/2011, 13/12/2021", are read as the dates 
+/// written with dashes when strict date separators are disabled. When enabled, only dashes are accepted and such a
+/// date makes the function return `MissingDashInDateData` error.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_strict_date_separators(true);
+///
+///     date.input_ptr = "13.12.2011";
+///     date.string_capacity = strlen(date.input_ptr);
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Csv, ascii_mode);
+///
+///     if (data_result.error_type == MissingDashInDateData) { /* A Process */ };
+///
+///     tcmb_evds_c_result_free(data_result);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_strict_date_separators(strict_date_separators: bool) {
+
+    shield_or((), || {
+        evds_c::settings::get_settings().strict_date_separators = strict_date_separators;
+    })
+}
+
+/// sets the maximum span of the date ranges of the data requests in days, e.g. 3653 for ten years, protecting both 
+/// EVDS and the memory of the application from pulling a daily series for decades by accident. Zero removes the limit,
+/// which is the default.
+///
+/// A longer date range makes the data functions, e.g. [`tcmb_evds_c_get_data`], [`tcmb_evds_c_get_data_group`] and 
+/// [`tcmb_evds_c_export_data_group`], return `DateRangeTooLarge` error without requesting EVDS. A long history is 
+/// still fetched in chunks written as date ranges separated by semicolons, e.g. 
+/// "01-01-2000,31-12-2009;01-01-2010,31-12-2019", each of which is checked separately. The advanced data requested in 
+/// a frequency coarser than business days, e.g. `Monthly`, is not limited.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_max_date_span(3653);
+///
+///     date.input_ptr = "01-01-1970,31-12-2020";
+///     date.string_capacity = strlen(date.input_ptr);
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Csv, ascii_mode);
+///
+///     if (data_result.error_type == DateRangeTooLarge) { /* Fetching in chunks */ };
+///
+///     tcmb_evds_c_result_free(data_result);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_max_date_span(max_span_days: c_uint) {
+
+    shield_or((), || {
+        evds_c::settings::get_settings().max_date_span_days = max_span_days;
+    })
+}
+
+/// sets what happens to the data requests whose estimated number of observations is more than the limit, preventing 
+/// programs from freezing while a huge response is received and parsed. Huge requests are allowed by default.
+///
+/// The observations of a request of [`tcmb_evds_c_get_data`] are estimated from the frequencies of its series and the
+/// span of its date range. The frequencies are taken from the series lists received so far, and the series list of a 
+/// series not received yet is requested once. `RejectHugeRequests` makes a huge request return `HugeRequest` error 
+/// whose message suggests the finest coarser data frequency fitting the limit, e.g. `Monthly` for a daily series, and 
+/// `DowngradeHugeRequests` requests the data in that frequency with the default aggregation method of each series.
+/// An out of range policy is ignored together with the limit.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_huge_request_policy(DowngradeHugeRequests, 100000);
+///
+///     date.input_ptr = "02-01-1950,31-12-2020";
+///     date.string_capacity = strlen(date.input_ptr);
+///
+///     // the daily rates of seventy years are requested monthly instead.
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Csv, ascii_mode);
+///
+///     tcmb_evds_c_result_free(data_result);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_huge_request_policy(policy: c_uint, max_observations: c_ulong) {
+
+    shield_or((), || {
+        let policy = match TcmbEvdsHugeRequestPolicy::from_value(policy) {
+            Some(policy) => policy,
+            None => return,
+        };
+
+        let mut settings = evds_c::settings::get_settings();
+
+        settings.huge_request_policy = policy;
+        settings.max_estimated_observations = max_observations;
+    })
+}
+
+/// enables or disables strict schema validation of JSON data responses. Strict schema validation is disabled by 
+/// default.
+///
+/// When enabled, the JSON responses of `tcmb_evds_c_get_data`, `tcmb_evds_c_get_last_n` and 
+/// `tcmb_evds_c_get_data_group` are checked against the schema of EVDS: an `items` array of objects with a date, a 
+/// matching `totalCount`, and a key for each requested series. A response not matching it returns `UnexpectedSchema` 
+/// error whose message describes the first discrepancy, catching silent changes of the EVDS API early instead of 
+/// parsing partial data. CSV and XML responses are not validated.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_strict_schema(true);
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Json, ascii_mode);
+///
+///     // e.g. "Error: Unexpected response schema, items[0] is missing TP_DK_USD_S."
+///     if (data_result.error_type == UnexpectedSchema) { /* A Process */ };
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_strict_schema(strict_schema: bool) {
+
+    shield_or((), || {
+        evds_c::settings::get_settings().strict_schema = strict_schema;
+    })
+}
+
+/// enables or disables the protection of the CSV written by the library against spreadsheet formula injection, which 
+/// is enabled by default.
+///
+/// When enabled, a text cell beginning with '=', '+', '-', '@', a tab or a carriage return, e.g. a series name of the
+/// headers written by `tcmb_evds_c_get_data_with_headers`, is prefixed with a single quote so that spreadsheet 
+/// applications show it as text instead of running it as a formula. Numbers such as "-1.5" are kept. The protection 
+/// covers the CSV rewritten by the library, namely the translated headers, the post-processed responses, 
+/// `tcmb_evds_c_dataset_to_csv` and `tcmb_evds_c_reshape_result`, while the CSV responses passed as received from EVDS
+/// are not changed.
+///
+/// # Example
+///
+/// ```C
+///     // the CSV is read by a program rather than a spreadsheet application, so the names are kept as they are.
+///     tcmb_evds_c_set_csv_injection_protection(false);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_csv_injection_protection(csv_injection_protection: bool) {
+
+    shield_or((), || {
+        evds_c::settings::get_settings().csv_injection_protection = csv_injection_protection;
+    })
+}
+
+/// sets the parser of the JSON responses, which is `SerdeJsonBackend` by default.
+///
+/// `SimdJsonBackend` parses the responses via simd-json, which uses the SIMD instructions of the CPU and may be faster 
+/// for multi-megabyte responses such as the responses of large data groups, so both are compared by the benchmarks in
+/// `benches`. It is available when the library is built with the `simd_json` feature, e.g. 
+/// `cargo build --release --features simd_json`. Both parsers give the same data.
+///
+/// Returns false and keeps the current parser if the library is built without the parser.
+///
+/// # Example
+///
+/// ```C
+///     if (!tcmb_evds_c_set_json_backend(SimdJsonBackend)) { /* A Process */ };
+/// ```
+#[cfg(feature = "parse")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_json_backend(json_backend: c_uint) -> bool {
+
+    shield_or(false, || {
+        let json_backend = match TcmbEvdsJsonBackend::from_value(json_backend) {
+            Some(json_backend) => json_backend,
+            None => return false,
+        };
+
+        if json_backend == TcmbEvdsJsonBackend::SimdJsonBackend && !IS_SIMD_JSON_AVAILABLE { return false; }
+
+        evds_c::settings::get_settings().json_backend = json_backend;
+
+        true
+    })
+}
+
+/// sets the overall deadline of each operational function call in milliseconds. Zero, the default, removes the 
+/// deadline.
+///
+/// The deadline covers all attempts of the requests made by a call, the retries after transport failures and the waits 
+/// of the rate limited requests. An attempt is cut short when the deadline comes, and a retry or a wait that would end 
+/// after the deadline is not started. In both cases, `DeadlineExceeded` error is returned with the elapsed time, so a 
+/// request handler with a strict latency budget gets its answer in time.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_deadline(2000);
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, Json, ascii_mode);
+///
+///     // e.g. "Error: Deadline of the call is exceeded after 2000 milliseconds."
+///     if (data_result.error_type == DeadlineExceeded) { /* A Process */ };
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_deadline(deadline_ms: c_uint) {
+
+    shield_or((), || {
+        deadline::set_call_deadline(Duration::from_millis(u64::from(deadline_ms)));
+    })
+}
+
+/// sets the timeout of each attempt of a request in milliseconds. Zero, the default, removes the timeout.
+///
+/// An attempt timing out is retried like the other transport failures as long as the deadline set via 
+/// [`tcmb_evds_c_set_deadline`] allows, and an attempt never lasts longer than the time left until the deadline.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_attempt_timeout(500);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_attempt_timeout(timeout_ms: c_uint) {
+
+    shield_or((), || {
+        deadline::set_attempt_timeout(Duration::from_millis(u64::from(timeout_ms)));
+    })
+}
+
+/// chooses how error messages are written into the results of all FFI functions.
+///
+/// `PlainTextErrors` is the default and keeps the standard error messages. `JsonErrors` makes failures populate the 
+/// result with a small JSON object so that scripting consumers already parsing JSON can handle errors uniformly with 
+/// successes:
+///
+/// `{"error":"InvalidDate","param":"date","message":"Error: Invalid date."}`
+///
+/// The `param` field is `null` when the error is not caused by a specific parameter, e.g. a bad internet connection.
+/// When several parameters of a call are invalid, the object is a `ParameterError` with an `errors` array containing 
+/// one such object per parameter. An out of range error format is ignored.
+///
+/// # Example
+///
+/// ```C
+///     // error messages are returned as JSON objects from now on.
+///     tcmb_evds_c_set_error_format(JsonErrors);
+///
+///     TcmbEvdsResult data_result = tcmb_evds_c_get_data(data_series, date, api_key, return_format, ascii_mode);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_error_format(error_format: c_uint) {
+
+    shield_or((), || {
+        if let Some(error_format) = TcmbEvdsErrorFormat::from_value(error_format) {
+            evds_c::settings::get_settings().error_format = error_format;
+        }
+    })
+}
+
+/// overrides the offset from UTC in minutes that is used to find "today", e.g. the end of the date window of 
+/// [`tcmb_evds_c_get_last_n`](crate::tcmb_evds_c_get_last_n).
+///
+/// "Today" is resolved on Turkish time (Europe/Istanbul, UTC+03:00) by default because CBRT publishes on Turkish time. 
+/// Therefore, a server in another time zone does not query a day earlier or later than EVDS. The local time zone of the
+/// machine is never used.
+///
+/// Returns false and keeps the current offset if the offset is not between -720 (UTC-12:00) and 840 (UTC+14:00).
+///
+/// # Example
+///
+/// ```C
+///     // "today" is resolved in UTC from now on.
+///     tcmb_evds_c_set_today_utc_offset(0);
+///
+///     // "today" is resolved on Turkish time again.
+///     tcmb_evds_c_set_today_utc_offset(180);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_today_utc_offset(offset_minutes: c_int) -> bool {
+
+    shield_or(false, || {
+        if !(MIN_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(&offset_minutes) { return false; }
+
+        evds_c::settings::get_settings().today_utc_offset_minutes = offset_minutes;
+
+        true
+    })
+}
+
+/// returns the exact queries that [`tcmb_evds_c_get_data`] would send to EVDS without sending them, so users can verify
+/// nothing unexpected is transmitted with their api key.
+///
+/// Each query is written in a line in the order of the date ranges. The value of the api key is masked in the queries. 
+/// The api key is not validated via EVDS, so the internet connection is not required.
+///
+/// # Error
+///
+/// This function returns error when invalid data series or date is supplied. The data series and the api key must not 
+/// contain `&`, `=` and `#` characters, otherwise `UnsafeQueryCharacter` error is returned.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult audit_result = tcmb_evds_c_audit_data(data_series, date, api_key, return_format);
+///
+///     // prints "https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S&startDate=13-12-2011&endDate=13-12-2011
+///     // &type=csv&key=********".
+///     fwrite(audit_result.output_ptr, audit_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(audit_result);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_audit_data(
+    data_series: TcmbEvdsInput,
+    date: TcmbEvdsInput,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_audit_data", || {
+        let (rust_data_series, data_series_error_state) = data_series.get_input("data_series");
+        let (rust_date, date_error_state) = date.get_input("date");
+
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        if data_series_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_data_series, parameter_error, "data_series");
+        }
+        if date_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_date, parameter_error, "date");
+        }
+
+
+        let date_preferences = match generate_date_preferences(&rust_date) {
+            Ok(preferences) => preferences,
+            Err(error_result) => return error_result,
+        };
+
+        let return_format: TcmbEvdsReturnFormat = match read_enum(return_format) {
+            Ok(return_format) => return_format,
+            Err(return_error) => return handle_parameter_return_error(return_error, "return_format"),
+        };
+
+        let evds = match generate_unverified_evds(api_key, return_format) {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        let audited_urls = audit_each_date_preference(date_preferences, |date_preference| {
+            evds_basic::generate_data_url(&rust_data_series, &date_preference, &evds)
+        });
+
+        if let Err(return_error) = audited_urls { return handle_parameter_return_error(return_error, "data_series"); }
+
+        TcmbEvdsResult::generate_result(audited_urls.unwrap(), ReturnErrorC::NoError)
+    })
+}
+
+/// returns the exact queries that [`tcmb_evds_c_get_data_group`] would send to EVDS without sending them.
+///
+/// The queries are written in the same way with [`tcmb_evds_c_audit_data`].
+///
+/// # Error
+///
+/// This function returns error when invalid data group or date is supplied. The data group and the api key must not 
+/// contain `&`, `=` and `#` characters, otherwise `UnsafeQueryCharacter` error is returned.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult audit_result = tcmb_evds_c_audit_data_group(data_group, date, api_key, return_format);
+///
+///     fwrite(audit_result.output_ptr, audit_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(audit_result);
+/// ```
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_audit_data_group(
+    data_group: TcmbEvdsInput,
+    date: TcmbEvdsInput,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_audit_data_group", || {
+        let (rust_data_group, data_group_error_state) = data_group.get_input("data_group");
+        let (rust_date, date_error_state) = date.get_input("date");
+
+        let parameter_error = ReturnErrorC::ParameterError;
+
+        if data_group_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_data_group, parameter_error, "data_group");
+        }
+        if date_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_date, parameter_error, "date");
+        }
+
+
+        let date_preferences = match generate_date_preferences(&rust_date) {
+            Ok(preferences) => preferences,
+            Err(error_result) => return error_result,
+        };
+
+        let return_format: TcmbEvdsReturnFormat = match read_enum(return_format) {
+            Ok(return_format) => return_format,
+            Err(return_error) => return handle_parameter_return_error(return_error, "return_format"),
+        };
+
+        let evds = match generate_unverified_evds(api_key, return_format) {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        let audited_urls = audit_each_date_preference(date_preferences, |date_preference| {
+            evds_basic::generate_data_group_url(&rust_data_group, &date_preference, &evds)
+        });
+
+        if let Err(return_error) = audited_urls { return handle_parameter_return_error(return_error, "data_group"); }
+
+        TcmbEvdsResult::generate_result(audited_urls.unwrap(), ReturnErrorC::NoError)
+    })
+}
+
+/// returns the exact query that [`tcmb_evds_c_get_series_list`] would send to EVDS without sending it.
+///
+/// The value of the api key is masked in the query and the api key is not validated via EVDS.
+///
+/// # Error
+///
+/// This function returns error when empty code is supplied. The code and the api key must not contain `&`, `=` and `#` 
+/// characters, otherwise `UnsafeQueryCharacter` error is returned.
+///
+/// # Example
+///
+/// ```C
+///     TcmbEvdsResult audit_result = tcmb_evds_c_audit_series_list(code, api_key, return_format);
+///
+///     fwrite(audit_result.output_ptr, audit_result.string_capacity, 1, stdout);
+///
+///     tcmb_evds_c_result_free(audit_result);
+/// ```
+#[cfg(feature = "catalog")]
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_audit_series_list(
+    code: TcmbEvdsInput,
+    api_key: TcmbEvdsInput,
+    return_format: c_uint
+) -> TcmbEvdsResult {
+
+    shield_result("tcmb_evds_c_audit_series_list", || {
+        let (rust_code, code_error_state) = code.get_input("code");
+
+        if code_error_state {
+            return TcmbEvdsResult::generate_parameter_result(rust_code, ReturnErrorC::ParameterError, "code");
+        }
+
+
+        let return_format: TcmbEvdsReturnFormat = match read_enum(return_format) {
+            Ok(return_format) => return_format,
+            Err(return_error) => return handle_parameter_return_error(return_error, "return_format"),
+        };
+
+        let evds = match generate_unverified_evds(api_key, return_format) {
+            Ok(evds) => evds,
+            Err(error_result) => return error_result,
+        };
+
+
+        let audited_url = evds_basic::generate_series_list_url(&rust_code, &evds);
+
+        if let Err(return_error) = audited_url { return handle_parameter_return_error(return_error, "code"); }
+
+        TcmbEvdsResult::generate_result(audited_url.unwrap().build_masked(), ReturnErrorC::NoError)
+    })
+}
+
+/// sets the priority of the requests made by the calling thread. Each thread has `InteractivePriority` by default.
+///
+/// A request of a thread with `BulkPriority` is not started while a request of an interactive thread is being made, so
+/// that the requests waited by users are not stuck behind bulk work such as catalog syncs. The requests of
+/// [`tcmb_evds_c_export_data_group`] and [`tcmb_evds_c_warm_cache`] are always bulk requests. At most 64 bulk requests
+/// wait at once by default, and the others fail fast with `QueueFull` error, which
+/// [`tcmb_evds_c_set_bulk_queue_capacity`] changes. An out of range priority is ignored.
+///
+/// # Example
+///
+/// ```C
+///     // in the thread syncing the catalog.
+///     tcmb_evds_c_set_thread_priority(BulkPriority);
+///
+///     TcmbEvdsResult series_result = tcmb_evds_c_get_series_list(code, api_key, Json, false);
+///
+///     if (series_result.error_type == QueueFull) { /* Retrying later */ };
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_thread_priority(priority: c_uint) {
+
+    shield_or((), || {
+        if let Some(priority) = TcmbEvdsPriority::from_value(priority) {
+            scheduler::set_priority(priority.to_priority());
+        }
+    })
+}
+
+/// sets the number of the bulk requests that may wait for the interactive requests at once, which is 64 by default. 
+/// The bulk requests beyond the capacity fail with `QueueFull` error. Zero makes the bulk requests fail while an 
+/// interactive request is being made.
+///
+/// # Example
+///
+/// ```C
+///     tcmb_evds_c_set_bulk_queue_capacity(16);
+/// ```
+#[no_mangle]
+pub extern "C" fn tcmb_evds_c_set_bulk_queue_capacity(capacity: c_uint) {
+
+    shield_or((), || {
+        scheduler::set_bulk_queue_capacity(capacity as usize);
+    })
+}
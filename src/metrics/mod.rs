@@ -0,0 +1,256 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::key_pool::{KeyUsage, get_key_pool};
+
+
+/// is the number of the latest request latencies the percentiles are computed from.
+const LATENCY_WINDOW: usize = 1024;
+
+/// is a counter of the pooled keys with its name, its help text and how it is read from the usage of a key.
+type KeyCounter = (&'static str, &'static str, fn(&KeyUsage) -> u64);
+
+/// are the process wide counters of the library.
+pub(crate) static METRICS: Metrics = Metrics::new();
+
+
+/// counts the requests sent to EVDS, the received bytes, the retries, the cache lookups and the errors returned to C,
+/// and keeps the latest request latencies.
+pub(crate) struct Metrics {
+    requests: AtomicU64,
+    received_bytes: AtomicU64,
+    retries: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    total_latency_micros: AtomicU64,
+    /// is the count of each error type by its name, e.g. "InvalidDate".
+    errors: Mutex<BTreeMap<String, u64>>,
+    latencies: Mutex<VecDeque<Duration>>,
+}
+
+/// is a copy of the counters at a moment.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MetricsSnapshot {
+    pub(crate) requests: u64,
+    pub(crate) received_bytes: u64,
+    pub(crate) retries: u64,
+    pub(crate) cache_hits: u64,
+    pub(crate) cache_misses: u64,
+    pub(crate) total_latency: Duration,
+    pub(crate) errors: Vec<(String, u64)>,
+    /// is None until a request is made.
+    pub(crate) latency_p50: Option<Duration>,
+    pub(crate) latency_p95: Option<Duration>,
+    /// is the usage of each key of the key pool.
+    pub(crate) key_usage: Vec<KeyUsage>,
+}
+
+impl Metrics {
+    pub(crate) const fn new() -> Metrics {
+        Metrics {
+            requests: AtomicU64::new(0),
+            received_bytes: AtomicU64::new(0),
+            retries: AtomicU64::new(0),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+            total_latency_micros: AtomicU64::new(0),
+            errors: Mutex::new(BTreeMap::new()),
+            latencies: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// records a request sent to EVDS with its latency and the bytes of its response.
+    pub(crate) fn record_request(&self, latency: Duration, received_bytes: usize) {
+
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.received_bytes.fetch_add(received_bytes as u64, Ordering::Relaxed);
+        self.total_latency_micros.fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+
+        let mut latencies = lock(&self.latencies);
+
+        if latencies.len() == LATENCY_WINDOW { latencies.pop_front(); }
+
+        latencies.push_back(latency);
+    }
+
+    /// records a request repeated after a failed attempt or a rate limited response.
+    pub(crate) fn record_retry(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// records a lookup of the response cache or the observation store.
+    #[cfg(feature = "cache")]
+    pub(crate) fn record_cache_lookup(&self, is_hit: bool) {
+        match is_hit {
+            true => self.cache_hits.fetch_add(1, Ordering::Relaxed),
+            false => self.cache_misses.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    /// records an error returned to C by the name of its type.
+    pub(crate) fn record_error(&self, error_name: &str) {
+        *lock(&self.errors).entry(error_name.to_string()).or_insert(0) += 1;
+    }
+
+    pub(crate) fn get_error_count(&self, error_name: &str) -> u64 {
+        lock(&self.errors).get(error_name).copied().unwrap_or(0)
+    }
+
+    pub(crate) fn snapshot(&self) -> MetricsSnapshot {
+
+        let mut latencies: Vec<Duration> = lock(&self.latencies).iter().copied().collect();
+        latencies.sort_unstable();
+
+        MetricsSnapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            received_bytes: self.received_bytes.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.cache_misses.load(Ordering::Relaxed),
+            total_latency: Duration::from_micros(self.total_latency_micros.load(Ordering::Relaxed)),
+            errors: lock(&self.errors).iter().map(|(name, count)| (name.clone(), *count)).collect(),
+            latency_p50: find_percentile(&latencies, 50),
+            latency_p95: find_percentile(&latencies, 95),
+            key_usage: get_key_pool().get_usage(),
+        }
+    }
+}
+
+impl MetricsSnapshot {
+    /// gives the total number of the errors of all types.
+    pub(crate) fn count_errors(&self) -> u64 {
+        self.errors.iter().map(|(_, count)| count).sum()
+    }
+
+    /// stringifies the snapshot in the Prometheus text exposition format. The metric names start with `tcmb_evds_c_`,
+    /// the errors are labeled by their types and the latencies are written as a summary in seconds.
+    pub(crate) fn to_prometheus(&self) -> String {
+
+        let mut text = String::new();
+
+        let counters = [
+            ("requests_total", "Requests sent to EVDS.", self.requests),
+            ("received_bytes_total", "Bytes of the responses received from EVDS.", self.received_bytes),
+            ("retries_total", "Requests repeated after a failed attempt or a rate limit.", self.retries),
+            ("cache_hits_total", "Requests served from the response cache or the observation store.", self.cache_hits),
+            ("cache_misses_total", "Cache lookups that required a request.", self.cache_misses),
+        ];
+
+        for (name, help, value) in counters.iter() {
+            text.push_str(&format!(
+                "# HELP tcmb_evds_c_{0} {1}\n# TYPE tcmb_evds_c_{0} counter\ntcmb_evds_c_{0} {2}\n",
+                name,
+                help,
+                value
+            ));
+        }
+
+        text.push_str("# HELP tcmb_evds_c_errors_total Errors returned to C by type.\n");
+        text.push_str("# TYPE tcmb_evds_c_errors_total counter\n");
+
+        for (name, count) in &self.errors {
+            text.push_str(&format!("tcmb_evds_c_errors_total{{type=\"{}\"}} {}\n", name, count));
+        }
+
+        let key_counters: [KeyCounter; 2] = [
+            ("key_requests_total", "Requests made with each key of the key pool.", |usage| usage.requests),
+            ("key_quota_errors_total", "Rate limited requests of each pooled key.", |usage| usage.quota_errors),
+        ];
+
+        // the counters of the keys are written only while the key pool is used.
+        for (name, help, count) in key_counters.iter().filter(|_| !self.key_usage.is_empty()) {
+            text.push_str(&format!("# HELP tcmb_evds_c_{0} {1}\n# TYPE tcmb_evds_c_{0} counter\n", name, help));
+
+            for usage in &self.key_usage {
+                text.push_str(&format!("tcmb_evds_c_{}{{key=\"{}\"}} {}\n", name, usage.masked_key, count(usage)));
+            }
+        }
+
+        text.push_str("# HELP tcmb_evds_c_request_latency_seconds Latencies of the requests sent to EVDS.\n");
+        text.push_str("# TYPE tcmb_evds_c_request_latency_seconds summary\n");
+
+        for (quantile, latency) in [("0.5", self.latency_p50), ("0.95", self.latency_p95)].iter() {
+            if let Some(latency) = latency {
+                text.push_str(&format!(
+                    "tcmb_evds_c_request_latency_seconds{{quantile=\"{}\"}} {}\n",
+                    quantile,
+                    latency.as_secs_f64()
+                ));
+            }
+        }
+
+        text.push_str(&format!("tcmb_evds_c_request_latency_seconds_sum {}\n", self.total_latency.as_secs_f64()));
+        text.push_str(&format!("tcmb_evds_c_request_latency_seconds_count {}\n", self.requests));
+
+        text
+    }
+}
+
+
+/// finds the percentile of the sorted durations by the nearest rank method.
+fn find_percentile(sorted_durations: &[Duration], percentile: usize) -> Option<Duration> {
+
+    if sorted_durations.is_empty() { return None; }
+
+    let rank = (sorted_durations.len() * percentile).div_ceil(100).max(1);
+
+    Some(sorted_durations[rank - 1])
+}
+
+/// locks the mutex by recovering a poisoned lock since the counters cannot be left half updated.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_count_metrics() {
+        let metrics = Metrics::new();
+
+        assert_eq!(None, metrics.snapshot().latency_p50);
+
+        for milliseconds in 1..=20 {
+            metrics.record_request(Duration::from_millis(milliseconds), 100);
+        }
+
+        metrics.record_retry();
+        #[cfg(feature = "cache")]
+        metrics.record_cache_lookup(true);
+        #[cfg(feature = "cache")]
+        metrics.record_cache_lookup(false);
+        metrics.record_error("InvalidDate");
+        metrics.record_error("InvalidDate");
+        metrics.record_error("NotFound");
+
+        let snapshot = metrics.snapshot();
+
+        assert_eq!((20, 2000, 1), (snapshot.requests, snapshot.received_bytes, snapshot.retries));
+        #[cfg(feature = "cache")]
+        assert_eq!((1, 1), (snapshot.cache_hits, snapshot.cache_misses));
+        assert_eq!((3, 2), (snapshot.count_errors(), metrics.get_error_count("InvalidDate")));
+        assert_eq!(Some(Duration::from_millis(10)), snapshot.latency_p50);
+        assert_eq!(Some(Duration::from_millis(19)), snapshot.latency_p95);
+
+        let prometheus_text = snapshot.to_prometheus();
+
+        assert!(prometheus_text.contains("\ntcmb_evds_c_requests_total 20\n"));
+        assert!(prometheus_text.contains("tcmb_evds_c_errors_total{type=\"InvalidDate\"} 2\n"));
+        assert!(prometheus_text.contains("tcmb_evds_c_request_latency_seconds{quantile=\"0.5\"} 0.01\n"));
+        assert!(prometheus_text.ends_with("tcmb_evds_c_request_latency_seconds_count 20\n"));
+
+        let key_usage = vec![KeyUsage { masked_key: "****a1b2".to_string(), requests: 7, quota_errors: 1 }];
+        let prometheus_text = MetricsSnapshot { key_usage, ..snapshot }.to_prometheus();
+
+        assert!(prometheus_text.contains("tcmb_evds_c_key_requests_total{key=\"****a1b2\"} 7\n"));
+        assert!(prometheus_text.contains("tcmb_evds_c_key_quota_errors_total{key=\"****a1b2\"} 1\n"));
+    }
+}
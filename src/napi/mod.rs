@@ -0,0 +1,207 @@
+use std::ffi::CString;
+use std::os::raw::c_uint;
+
+use napi::bindgen_prelude::AsyncTask;
+use napi::{Env, Error, JsError, Result, Task};
+use napi_derive::napi;
+
+use crate::evds_c::common_entities::{TcmbEvdsInput, TcmbEvdsResult, TcmbEvdsReturnFormat};
+use crate::evds_c::error_handling::ReturnErrorC;
+use crate::{tcmb_evds_c_get_data, tcmb_evds_c_get_data_group, tcmb_evds_c_result_free};
+
+
+/// is the operation made on the thread pool of Node.js.
+#[derive(Debug, PartialEq)]
+enum Operation {
+    Data,
+    DataGroup,
+}
+
+/// is the request of a promise. napi-rs makes it on the thread pool of Node.js via [`Task::compute`] and settles the
+/// promise with its outcome via [`Task::resolve`], namely the response or the error type with its message.
+pub struct Request {
+    operation: Operation,
+    code: String,
+    date: String,
+    api_key: String,
+    return_format: Option<String>,
+    ascii_mode: bool,
+}
+
+impl Request {
+    /// makes the request via the FFI functions, so the session defaults, the response cache and the parsers of the
+    /// library apply to Node.js in the same way with C.
+    fn make(&self) -> std::result::Result<String, (String, String)> {
+
+        let parameter_error = |message: &str| Err((format!("{:?}", ReturnErrorC::ParameterError), message.to_string()));
+
+        let return_format = match to_return_format(self.return_format.as_deref()) {
+            Some(return_format) => return_format,
+            None => return parameter_error("Error: The returnFormat argument must be csv, json, xml or auto."),
+        };
+
+        let to_input = |text: &CString| {
+            TcmbEvdsInput { input_ptr: text.as_ptr(), string_capacity: text.as_bytes().len() as _ }
+        };
+
+        let texts = [&self.code, &self.date, &self.api_key].map(|text| CString::new(text.as_str()));
+
+        let [code, date, api_key] = match texts {
+            [Ok(code), Ok(date), Ok(api_key)] => [code, date, api_key],
+            _ => return parameter_error("Error: The arguments must not contain NUL characters."),
+        };
+
+        let result = match self.operation {
+            Operation::Data => {
+                tcmb_evds_c_get_data(
+                    to_input(&code), to_input(&date), to_input(&api_key), return_format as c_uint, self.ascii_mode
+                )
+            },
+            Operation::DataGroup => {
+                tcmb_evds_c_get_data_group(
+                    to_input(&code), to_input(&date), to_input(&api_key), return_format as c_uint, self.ascii_mode
+                )
+            },
+        };
+
+        read_result(result)
+    }
+}
+
+impl Task for Request {
+    type Output = std::result::Result<String, (String, String)>;
+    type JsValue = String;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(self.make())
+    }
+
+    /// resolves the promise with the response, or rejects it with an error whose `code` is the error type of the
+    /// library, e.g. "InvalidDate", rather than a status of N-API.
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        match output {
+            Ok(response) => Ok(response),
+            Err((code, message)) => Err(Error::from(JsError::from(Error::new(code, message)).into_unknown(env))),
+        }
+    }
+}
+
+
+/// reads the response or the error of the result and releases the result. The detail of an error is appended to its
+/// message.
+fn read_result(result: TcmbEvdsResult) -> std::result::Result<String, (String, String)> {
+
+    let read_text = |text_ptr: *mut u8, capacity| match text_ptr.is_null() {
+        true => String::new(),
+        false => {
+            String::from_utf8_lossy(unsafe { std::slice::from_raw_parts(text_ptr, capacity as usize) }).to_string()
+        },
+    };
+
+    let text = read_text(result.output_ptr, result.string_capacity);
+    let detail = read_text(result.detail_ptr, result.detail_capacity);
+    let error_type = result.error_type;
+
+    unsafe { tcmb_evds_c_result_free(result) };
+
+    match (error_type, detail.is_empty()) {
+        (ReturnErrorC::NoError, _) => Ok(text),
+        (error_type, true) => Err((format!("{:?}", error_type), text)),
+        (error_type, false) => Err((format!("{:?}", error_type), format!("{}\n{}", text, detail))),
+    }
+}
+
+/// converts the name of a return format in JavaScript, e.g. "csv", into the enum of the library. An omitted return
+/// format uses the session format.
+fn to_return_format(name: Option<&str>) -> Option<TcmbEvdsReturnFormat> {
+    match name.map(str::to_lowercase).as_deref() {
+        None => Some(TcmbEvdsReturnFormat::SessionFormat),
+        Some("csv") => Some(TcmbEvdsReturnFormat::Csv),
+        Some("json") => Some(TcmbEvdsReturnFormat::Json),
+        Some("xml") => Some(TcmbEvdsReturnFormat::Xml),
+        Some("auto") => Some(TcmbEvdsReturnFormat::Auto),
+        Some(_) => None,
+    }
+}
+
+
+/// is `getData(dataSeries, date, apiKey, returnFormat?, asciiMode?)` returning a promise of the response.
+#[napi(ts_return_type = "Promise<string>")]
+pub fn get_data(
+    data_series: String,
+    date: String,
+    api_key: String,
+    return_format: Option<String>,
+    ascii_mode: Option<bool>,
+) -> AsyncTask<Request> {
+    AsyncTask::new(Request {
+        operation: Operation::Data,
+        code: data_series,
+        date,
+        api_key,
+        return_format,
+        ascii_mode: ascii_mode.unwrap_or(false),
+    })
+}
+
+/// is `getDataGroup(dataGroup, date, apiKey, returnFormat?, asciiMode?)` returning a promise of the response.
+#[napi(ts_return_type = "Promise<string>")]
+pub fn get_data_group(
+    data_group: String,
+    date: String,
+    api_key: String,
+    return_format: Option<String>,
+    ascii_mode: Option<bool>,
+) -> AsyncTask<Request> {
+    AsyncTask::new(Request {
+        operation: Operation::DataGroup,
+        code: data_group,
+        date,
+        api_key,
+        return_format,
+        ascii_mode: ascii_mode.unwrap_or(false),
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_return_formats() {
+        assert_eq!(Some(TcmbEvdsReturnFormat::SessionFormat), to_return_format(None));
+        assert_eq!(Some(TcmbEvdsReturnFormat::Csv), to_return_format(Some("CSV")));
+        assert_eq!(None, to_return_format(Some("yaml")));
+    }
+
+    #[test]
+    fn should_reject_invalid_requests() {
+        let mut request = Request {
+            operation: Operation::Data,
+            code: String::new(),
+            date: "13-12-2011".to_string(),
+            api_key: "users_api_key".to_string(),
+            return_format: Some("csv".to_string()),
+            ascii_mode: false,
+        };
+
+        assert!(matches!(request.make(), Err((_, message)) if message.starts_with("Error")));
+
+        request.code = "TP.DK.USD.A".to_string();
+        request.return_format = Some("yaml".to_string());
+
+        assert!(matches!(request.make(), Err((code, _)) if code == "ParameterError"));
+    }
+
+    #[test]
+    fn should_create_tasks_without_node() {
+        let arguments = || ("bie_dkdovytl".to_string(), "13-12-2011".to_string(), "users_api_key".to_string());
+
+        let (data_series, date, api_key) = arguments();
+        let _data_task = get_data(data_series, date, api_key, None, None);
+
+        let (data_group, date, api_key) = arguments();
+        let _data_group_task = get_data_group(data_group, date, api_key, Some("csv".to_string()), Some(true));
+    }
+}
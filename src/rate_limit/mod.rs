@@ -0,0 +1,212 @@
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::date::calendar::CalendarDate;
+use crate::deadline;
+use crate::error::ReturnError;
+use crate::metrics::METRICS;
+
+
+/// is the HTTP status code of the responses rejected due to too many requests.
+pub(crate) const TOO_MANY_REQUESTS: u32 = 429;
+
+/// is the number of the retries after a 429 response before `RateLimitedByServer` error is returned.
+const MAX_RATE_LIMIT_RETRIES: u32 = 2;
+
+/// is the longest wait in seconds a request sleeps for. A longer `Retry-After` returns `RateLimitedByServer` error
+/// immediately instead of blocking the caller.
+const MAX_WAIT_SECONDS: u64 = 60;
+
+/// is the first wait in seconds when the server does not send `Retry-After`. It doubles with each retry.
+const INITIAL_BACKOFF_SECONDS: u64 = 1;
+
+const MONTH_NAMES: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// is the instant until which no request is sent since the server rate limited a former request.
+///
+/// It is process wide, so the requests of all threads hold off together rather than each of them hitting the server
+/// again and extending the limit.
+static RATE_LIMITED_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+
+
+/// is the outcome of a single request that may be rate limited by the server.
+pub(crate) enum Attempt {
+    Response(String),
+    /// contains the value of the `Retry-After` header if the server sent it.
+    RateLimited(Option<String>),
+}
+
+
+/// makes the request and retries it while the server responds with 429, waiting as long as `Retry-After` suggests or
+/// backing off exponentially when it is not sent.
+///
+/// # Error
+///
+/// This function returns the errors of the request, `RateLimitedByServer` error with the suggested wait when the
+/// retries are exhausted or the suggested wait is longer than a minute, and `DeadlineExceeded` error when the wait
+/// would end after the deadline of the call.
+pub(crate) fn retry_rate_limited<F>(mut request: F) -> Result<String, ReturnError>
+where
+    F: FnMut() -> Result<Attempt, ReturnError>
+{
+
+    let mut attempt = 0;
+
+    loop {
+        wait_for_rate_limit()?;
+
+        let retry_after = match request()? {
+            Attempt::Response(response) => return Ok(response),
+            Attempt::RateLimited(retry_after) => retry_after,
+        };
+
+        let wait = retry_after
+            .as_deref()
+            .and_then(|retry_after| parse_retry_after(retry_after, SystemTime::now()))
+            .unwrap_or_else(|| Duration::from_secs(INITIAL_BACKOFF_SECONDS << attempt));
+
+        hold_requests(wait);
+
+        if attempt == MAX_RATE_LIMIT_RETRIES { return Err(ReturnError::RateLimitedByServer(round_up_seconds(wait))); }
+
+        deadline::check_wait(wait)?;
+
+        attempt += 1;
+
+        METRICS.record_retry();
+    }
+}
+
+/// sleeps until the rate limit of the server is over.
+///
+/// # Error
+///
+/// This function returns `RateLimitedByServer` error with the remaining wait instead of sleeping when it is longer
+/// than a minute, and `DeadlineExceeded` error when the wait would end after the deadline of the call.
+pub(crate) fn wait_for_rate_limit() -> Result<(), ReturnError> {
+
+    let remaining_wait = match *get_rate_limited_until() {
+        Some(until) => until.saturating_duration_since(Instant::now()),
+        None => return Ok(()),
+    };
+
+    if remaining_wait > Duration::from_secs(MAX_WAIT_SECONDS) {
+        return Err(ReturnError::RateLimitedByServer(round_up_seconds(remaining_wait)));
+    }
+
+    deadline::check_wait(remaining_wait)?;
+
+    thread::sleep(remaining_wait);
+
+    Ok(())
+}
+
+/// finds the value of the `Retry-After` header in a header line received from the server, e.g. "Retry-After: 30".
+pub(crate) fn find_retry_after(header_line: &[u8]) -> Option<String> {
+
+    let header_line = String::from_utf8_lossy(header_line);
+    let (name, value) = header_line.split_once(':')?;
+
+    if !name.trim().eq_ignore_ascii_case("retry-after") { return None; }
+
+    Some(value.trim().to_string())
+}
+
+/// parses the value of `Retry-After` that is either a number of seconds, e.g. "120", or an HTTP date, e.g.
+/// "Wed, 21 Oct 2015 07:28:00 GMT". A date in the past is no wait.
+pub(crate) fn parse_retry_after(retry_after: &str, now: SystemTime) -> Option<Duration> {
+
+    let retry_after = retry_after.trim();
+
+    if let Ok(seconds) = retry_after.parse::<u64>() { return Some(Duration::from_secs(seconds)); }
+
+    let retry_time = parse_http_date(retry_after)?;
+    let now = now.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+
+    Some(Duration::from_secs(retry_time.saturating_sub(now).max(0) as u64))
+}
+
+/// parses an HTTP date in the IMF fixdate format into seconds since 01-01-1970 00:00 UTC.
+fn parse_http_date(http_date: &str) -> Option<i64> {
+
+    let parts: Vec<&str> = http_date.split_whitespace().collect();
+
+    let (day, month, year, time) = match parts.as_slice() {
+        [_, day, month, year, time, "GMT"] => (day, month, year, time),
+        _ => return None,
+    };
+
+    let month = MONTH_NAMES.iter().position(|name| name == month)? as u32 + 1;
+    let date = CalendarDate::parse(&format!("{}-{}-{}", day, month, year))?;
+
+    let time: Vec<i64> = time.split(':').map(|part| part.parse::<i64>().ok()).collect::<Option<_>>()?;
+
+    match time.as_slice() {
+        [hour, minute, second] if *hour < 24 && *minute < 60 && *second < 61 => {
+            Some(date.to_days() * 86_400 + hour * 3_600 + minute * 60 + second)
+        },
+        _ => None,
+    }
+}
+
+/// makes the next requests wait at least for the given duration.
+fn hold_requests(wait: Duration) {
+
+    let until = Instant::now() + wait;
+    let mut rate_limited_until = get_rate_limited_until();
+
+    if rate_limited_until.is_none_or(|current_until| current_until < until) { *rate_limited_until = Some(until); }
+}
+
+fn round_up_seconds(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+/// gives access to the rate limit instant. A poisoned lock is recovered since the instant cannot be left half updated.
+fn get_rate_limited_until() -> MutexGuard<'static, Option<Instant>> {
+    match RATE_LIMITED_UNTIL.lock() {
+        Ok(rate_limited_until) => rate_limited_until,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_retry_after() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_445_412_470);
+
+        assert_eq!(Some(Duration::from_secs(120)), parse_retry_after(" 120 ", now));
+        assert_eq!(Some(Duration::from_secs(10)), parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now));
+        assert_eq!(Some(Duration::from_secs(0)), parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now));
+        assert_eq!(None, parse_retry_after("Wed, 21 Abc 2015 07:28:00 GMT", now));
+        assert_eq!(None, parse_retry_after("soon", now));
+
+        assert_eq!(Some("30".to_string()), find_retry_after(b"retry-after: 30\r\n"));
+        assert_eq!(None, find_retry_after(b"Content-Type: application/json\r\n"));
+    }
+
+    #[test]
+    fn should_retry_rate_limited_requests() {
+        let mut attempts = 0;
+
+        let response = retry_rate_limited(|| {
+            attempts += 1;
+
+            match attempts {
+                1 => Ok(Attempt::RateLimited(Some("0".to_string()))),
+                _ => Ok(Attempt::Response("Tarih,TP_DK_USD_S".to_string())),
+            }
+        });
+
+        assert_eq!(Ok("Tarih,TP_DK_USD_S".to_string()), response);
+
+        let exhausted_response = retry_rate_limited(|| Ok(Attempt::RateLimited(Some("0".to_string()))));
+
+        assert_eq!(Err(ReturnError::RateLimitedByServer(0)), exhausted_response);
+    }
+}
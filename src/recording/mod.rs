@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde_json::{json, Value};
+
+use crate::common::url_builder::mask_api_key;
+use crate::dataset::checksum::sha256;
+use crate::error::ReturnError;
+
+
+/// is the extension of the recording files.
+const RECORDING_EXTENSION: &str = "json";
+
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+
+/// specifies whether the responses are saved into the recordings or served from them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RecordingMode {
+    Record,
+    Replay,
+}
+
+/// keeps the request and response pairs of EVDS in a directory to reproduce the calls without EVDS.
+///
+/// Each recording is a JSON file named after the SHA-256 digest of the url whose api key is masked, and includes the
+/// masked url with the response text. Therefore, the recordings can be shared in bug reports without the api key, and
+/// they are replayed whichever key is given. The recording is best effort: a file that cannot be written never fails
+/// a request.
+struct Recording {
+    mode: RecordingMode,
+    directory: PathBuf,
+}
+
+impl Recording {
+    fn find_path(&self, masked_url: &str) -> PathBuf {
+
+        let digest: String = sha256(masked_url.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+
+        self.directory.join(format!("{}.{}", digest, RECORDING_EXTENSION))
+    }
+}
+
+
+/// starts recording the responses into the directory or replaying them from there, and creates the directory if it
+/// does not exist. False is returned when the directory cannot be created.
+pub(crate) fn start(mode: RecordingMode, directory: &str) -> bool {
+
+    if fs::create_dir_all(directory).is_err() { return false; }
+
+    *get_recording() = Some(Recording { mode, directory: PathBuf::from(directory) });
+
+    true
+}
+
+/// stops recording or replaying. The recordings remain in the directory.
+pub(crate) fn stop() {
+    *get_recording() = None;
+}
+
+/// serves the request from the recordings in replay mode. None is returned when the responses are not replayed.
+///
+/// # Error
+///
+/// This function returns `RecordingNotFound` error when the request has no recording in replay mode.
+pub(crate) fn replay(url: &str) -> Option<Result<String, ReturnError>> {
+
+    let recording = get_recording();
+    let recording = recording.as_ref().filter(|recording| recording.mode == RecordingMode::Replay)?;
+
+    let masked_url = mask_api_key(url);
+
+    Some(read_response(&recording.find_path(&masked_url), &masked_url).ok_or(ReturnError::RecordingNotFound))
+}
+
+/// saves the response of the request in record mode.
+pub(crate) fn record(url: &str, response: &str) {
+
+    let recording = get_recording();
+    let recording = match recording.as_ref().filter(|recording| recording.mode == RecordingMode::Record) {
+        Some(recording) => recording,
+        None => return,
+    };
+
+    let masked_url = mask_api_key(url);
+    let content = json!({ "url": masked_url, "response": response });
+
+    let _ = fs::write(recording.find_path(&masked_url), content.to_string());
+}
+
+/// reads the response of the recording made for the url.
+fn read_response(path: &Path, masked_url: &str) -> Option<String> {
+
+    let content: Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+
+    if content["url"].as_str()? != masked_url { return None; }
+
+    content["response"].as_str().map(str::to_string)
+}
+
+/// gives access to the recording. A poisoned lock is recovered since the recording cannot be left half updated.
+fn get_recording() -> MutexGuard<'static, Option<Recording>> {
+    match RECORDING.lock() {
+        Ok(recording) => recording,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_replay_recorded_response() {
+        let directory = std::env::temp_dir().join(format!("tcmb_evds_c_recording_{}", std::process::id()));
+        let directory = directory.to_string_lossy().to_string();
+
+        let url = "https://evds2.tcmb.gov.tr/service/evds/series=TP.DK.USD.S&type=json&key=users_key";
+
+        assert!(start(RecordingMode::Record, &directory));
+        assert!(replay(url).is_none());
+
+        record(url, "{\"items\":[]}");
+
+        assert!(start(RecordingMode::Replay, &directory));
+        assert_eq!(Some(Ok("{\"items\":[]}".to_string())), replay(&url.replace("users_key", "other_key")));
+        assert_eq!(Some(Err(ReturnError::RecordingNotFound)), replay(&url.replace("USD", "EUR")));
+
+        stop();
+
+        assert!(replay(url).is_none());
+
+        let _ = fs::remove_dir_all(&directory);
+    }
+}
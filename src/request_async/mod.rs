@@ -1,65 +1,104 @@
 #[cfg(feature = "async_mode")]
-use curl::easy::{Easy2, Handler, WriteError};
+use std::cell::RefCell;
+#[cfg(feature = "async_mode")]
+use std::time::Instant;
 
+#[cfg(feature = "async_mode")]
+use crate::common::content_type::check_content_type;
+#[cfg(feature = "async_mode")]
+use crate::common::response_buffer::into_response_text;
 #[cfg(feature = "async_mode")]
 use crate::error::ReturnError;
-
-
-// TESTED
 #[cfg(feature = "async_mode")]
-struct Collector(Vec<u8>);
+use crate::rate_limit::{self, Attempt, TOO_MANY_REQUESTS};
+#[cfg(feature = "async_mode")]
+use crate::circuit_breaker;
+#[cfg(feature = "async_mode")]
+use crate::evds_c::hook_entities::run_request_hook;
+#[cfg(feature = "async_mode")]
+use crate::in_flight;
+#[cfg(feature = "async_mode")]
+use crate::key_pool;
+#[cfg(feature = "async_mode")]
+use crate::metrics::METRICS;
+#[cfg(feature = "async_mode")]
+use crate::recording;
+#[cfg(feature = "async_mode")]
+use crate::scheduler;
+#[cfg(feature = "async_mode")]
+use crate::transport::{self, HttpTransport, Transport};
+
 
 #[cfg(feature = "async_mode")]
-impl Handler for Collector {
-    fn write(&mut self, data: &[u8]) -> Result<usize, WriteError> {
-        self.0.extend_from_slice(data);
-        Ok(data.len())
-    }
+thread_local! {
+    /// is the transport of the thread, which keeps the connection to EVDS alive for the following requests.
+    static TRANSPORT: RefCell<HttpTransport> = RefCell::new(transport::new_transport());
 }
 
 
 /// requests required data from server via given url in async mode.
 ///
-/// This function is fundamental and at the bottom level of the requesting hierarchy.  
+/// This function is fundamental and at the bottom level of the requesting hierarchy. A request rate limited by the 
+/// server with 429 is retried after the wait suggested by its `Retry-After` header. Requests fail fast while the 
+/// circuit breaker is open after repeated transport failures. The responses are saved into or served from the 
+/// recordings while recording or replaying. The requests sent to EVDS are passed to the pre-request hook first, which
+/// may veto them or annotate them with a header. The requests made with the keys of the key pool are counted in their
+/// usage. The calls of the same url and annotation made at once by several threads are coalesced into a single
+/// request after each of them is admitted and passed to the hook. The bulk requests of batch operations wait while an
+/// interactive request is being made. A response whose content type does not match the requested return format, e.g.
+/// an HTML page, is an error.
 #[cfg(feature = "async_mode")]
 pub(crate) fn do_request(url_format: &str) -> Result<String, ReturnError> {
-    let mut handle = Easy2::new(Collector(Vec::new()));
-    
-    if let Err(_) = handle.get(true) {
-        return Err(ReturnError::UnableToRequest)
-    }
-    if let Err(_) = handle.url(url_format) {
-        return Err(ReturnError::UnableToSetUrl);
-    }
+    let _span = tracing::info_span!("http", otel.kind = "client").entered();
 
-    
-    // Applying request is repeated 3 times if the operation does not work properly. In the last turn if the perform()
-    // function ends up with an error, an error is returned from the loop. Otherwise, successful operation breaks the 
-    // loop.
-    let mut perform_result;
+    if let Some(replayed_response) = recording::replay(url_format) { return replayed_response; }
 
-    for element in 0..3 {
-        perform_result = handle.perform();
+    let _admission = scheduler::admit()?;
 
-        if perform_result.is_ok() { break; }
+    let annotation = run_request_hook(url_format)?;
 
-        if element != 2 { continue; }
+    in_flight::coalesce(url_format, annotation.as_deref(), || {
+        let response = circuit_breaker::guard(|| {
+            rate_limit::retry_rate_limited(|| do_single_request(url_format, annotation.as_deref()))
+        });
 
-        return Err(ReturnError::FailedToApplyRequest);
-    }
+        if let Ok(response) = &response { recording::record(url_format, response); }
+
+        key_pool::record_response(url_format, &response);
+
+        response
+    })
+}
 
+/// makes the request via the transport of the thread, whose connections are kept. A new transport is used if the 
+/// transport of the thread is in use.
+#[cfg(feature = "async_mode")]
+fn do_single_request(url_format: &str, annotation: Option<&str>) -> Result<Attempt, ReturnError> {
+    TRANSPORT.with(|thread_transport| match thread_transport.try_borrow_mut() {
+        Ok(mut thread_transport) => perform_request(&mut *thread_transport, url_format, annotation),
+        Err(_) => perform_request(&mut transport::new_transport(), url_format, annotation),
+    })
+}
+
+#[cfg(feature = "async_mode")]
+fn perform_request<T: Transport>(
+    transport: &mut T, 
+    url_format: &str, 
+    annotation: Option<&str>,
+) -> Result<Attempt, ReturnError> {
+    let request_start = Instant::now();
 
-    match handle.response_code() {
-        Ok(number) => {
-            if number != 200 {
-                return Err(ReturnError::RequestDenied)
-            }
-        },
-        Err(_) => return Err(ReturnError::NotFound),
+    let received = transport::get_with_retries(transport, url_format, annotation)?;
+
+    METRICS.record_request(request_start.elapsed(), received.body.len());
+
+    match received.status_code {
+        TOO_MANY_REQUESTS => return Ok(Attempt::RateLimited(received.retry_after)),
+        200 => {},
+        _ => return Err(ReturnError::RequestDenied),
     }
 
-    let contents = handle.get_ref();
-    let response = String::from_utf8_lossy(&contents.0);
-    
-    Ok(response.to_string())
+    check_content_type(url_format, received.content_type.as_deref(), &received.body)?;
+
+    Ok(Attempt::Response(into_response_text(received.body)))
 }
@@ -1,53 +1,110 @@
 #[cfg(feature = "sync_mode")]
-use curl::easy::Easy;
+use std::cell::RefCell;
+#[cfg(feature = "sync_mode")]
+use std::time::Instant;
 
+#[cfg(feature = "sync_mode")]
+use crate::common::content_type::check_content_type;
+#[cfg(feature = "sync_mode")]
+use crate::common::response_buffer::into_response_text;
 #[cfg(feature = "sync_mode")]
 use crate::error::ReturnError;
+#[cfg(feature = "sync_mode")]
+use crate::rate_limit::{self, Attempt, TOO_MANY_REQUESTS};
+#[cfg(feature = "sync_mode")]
+use crate::circuit_breaker;
+#[cfg(feature = "sync_mode")]
+use crate::evds_c::hook_entities::run_request_hook;
+#[cfg(feature = "sync_mode")]
+use crate::in_flight;
+#[cfg(feature = "sync_mode")]
+use crate::key_pool;
+#[cfg(feature = "sync_mode")]
+use crate::metrics::METRICS;
+#[cfg(feature = "sync_mode")]
+use crate::recording;
+#[cfg(feature = "sync_mode")]
+use crate::scheduler;
+#[cfg(feature = "sync_mode")]
+use crate::transport::{self, HttpTransport, Transport};
+
+
+#[cfg(feature = "sync_mode")]
+thread_local! {
+    /// is the transport of the thread, which keeps the connection to EVDS alive for the following requests.
+    static TRANSPORT: RefCell<HttpTransport> = RefCell::new(transport::new_transport());
+}
 
 
 /// requests required data from server via given url in sync mode.
 ///
-/// This function is fundamental and at the bottom level of the requesting hierarchy.  
+/// This function is fundamental and at the bottom level of the requesting hierarchy. A request rate limited by the 
+/// server with 429 is retried after the wait suggested by its `Retry-After` header. Requests fail fast while the 
+/// circuit breaker is open after repeated transport failures. The responses are saved into or served from the 
+/// recordings while recording or replaying. The requests sent to EVDS are passed to the pre-request hook first, which
+/// may veto them or annotate them with a header. The requests made with the keys of the key pool are counted in their
+/// usage. The calls of the same url and annotation made at once by several threads are coalesced into a single
+/// request after each of them is admitted and passed to the hook. The bulk requests of batch operations wait while an
+/// interactive request is being made. A response whose content type does not match the requested return format, e.g.
+/// an HTML page, is an error.
 #[cfg(feature = "sync_mode")]
 pub(crate) fn do_request(url_format: &str) -> Result<String, ReturnError> {
-    let mut buf = Vec::new();
-    let mut handle = Easy::new();
+    let _span = tracing::info_span!("http", otel.kind = "client").entered();
 
-    if let Err(_) = handle.url(url_format) {
-        return Err(ReturnError::UnableToSetUrl);
-    }
+    if let Some(replayed_response) = recording::replay(url_format) { return replayed_response; }
 
-    {
-        let mut transfer = handle.transfer();
-        if let Err(_) = transfer.write_function(|data| {
-            buf.extend_from_slice(data);
-            Ok(data.len())
-        }) {
-            return Err(ReturnError::FailedToSaveReceivedData);
-        }
-
-        
-        // Applying request is repeated 3 times if the operation does not work properly. In the last turn if the 
-        // perform() function ends up with an error, an error is returned from the loop. Otherwise, successful operation 
-        // breaks the loop.
-        let mut perform_result;
-
-        for element in 0..3 {
-            perform_result = transfer.perform();
-
-            if perform_result.is_ok() { break; }
-
-            if element != 2 { continue; }
-            
-            return Err(ReturnError::FailedToApplyRequest);
-        }
-    }
+    let _admission = scheduler::admit()?;
+
+    let annotation = run_request_hook(url_format)?;
+
+    in_flight::coalesce(url_format, annotation.as_deref(), || {
+        let response = circuit_breaker::guard(|| {
+            rate_limit::retry_rate_limited(|| do_single_request(url_format, annotation.as_deref()))
+        });
+
+        if let Ok(response) = &response { recording::record(url_format, response); }
+
+        key_pool::record_response(url_format, &response);
 
-    let response = String::from_utf8_lossy(&buf);
-    
-    if response.is_empty() {
+        response
+    })
+}
+
+/// makes the request via the transport of the thread, whose connections are kept. A new transport is used if the 
+/// transport of the thread is in use.
+#[cfg(feature = "sync_mode")]
+fn do_single_request(url_format: &str, annotation: Option<&str>) -> Result<Attempt, ReturnError> {
+    TRANSPORT.with(|thread_transport| match thread_transport.try_borrow_mut() {
+        Ok(mut thread_transport) => perform_request(&mut *thread_transport, url_format, annotation),
+        Err(_) => perform_request(&mut transport::new_transport(), url_format, annotation),
+    })
+}
+
+#[cfg(feature = "sync_mode")]
+fn perform_request<T: Transport>(
+    transport: &mut T,
+    url_format: &str,
+    annotation: Option<&str>,
+) -> Result<Attempt, ReturnError> {
+    // the benchmarks and the soak test may send the requests to a local mock of EVDS.
+    #[cfg(feature = "bench")]
+    let redirected_url = crate::bench::redirect_to_mock(url_format);
+    #[cfg(feature = "bench")]
+    let url_format: &str = &redirected_url;
+
+    let request_start = Instant::now();
+
+    let received = transport::get_with_retries(transport, url_format, annotation)?;
+
+    METRICS.record_request(request_start.elapsed(), received.body.len());
+
+    if received.status_code == TOO_MANY_REQUESTS { return Ok(Attempt::RateLimited(received.retry_after)); }
+
+    if received.body.is_empty() {
         return Err(ReturnError::NotFound);
     }
 
-    Ok(response.to_string())
+    check_content_type(url_format, received.content_type.as_deref(), &received.body)?;
+
+    Ok(Attempt::Response(into_response_text(received.body)))
 }
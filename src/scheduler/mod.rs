@@ -0,0 +1,217 @@
+use std::cell::Cell;
+use std::sync::{Condvar, Mutex, MutexGuard};
+#[cfg(any(feature = "cache", feature = "export"))]
+use std::thread;
+use std::time::Duration;
+#[cfg(any(feature = "cache", feature = "export"))]
+use std::time::Instant;
+
+use crate::deadline;
+use crate::error::ReturnError;
+
+
+/// is the number of the bulk requests that may wait for the interactive requests at once by default.
+const DEFAULT_BULK_QUEUE_CAPACITY: usize = 64;
+
+/// is the longest time a waiting bulk request sleeps before checking the deadline of its call again.
+const DEADLINE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// is the number of the series fetched at the same time by the batch operations, e.g. exports and cache warm-ups.
+#[cfg(any(feature = "cache", feature = "export"))]
+pub(crate) const MAX_PARALLEL_REQUESTS: usize = 4;
+
+/// is the least time between two requests of a batch operation so that EVDS is not flooded.
+#[cfg(any(feature = "cache", feature = "export"))]
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(250);
+
+static SCHEDULER: Scheduler = Scheduler::new();
+
+thread_local! {
+    /// is the priority of the requests made by the thread.
+    static PRIORITY: Cell<Priority> = const { Cell::new(Priority::Interactive) };
+}
+
+
+/// specifies whether the requests of a thread are made right away or give way to the others.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Priority {
+    /// is the priority of the requests waited by users, e.g. the data shown on a screen.
+    Interactive,
+    /// is the priority of the requests of the batch operations, e.g. exports, cache warm-ups and catalog syncs, which
+    /// wait while an interactive request is being made.
+    Bulk,
+}
+
+/// lets the interactive requests preempt the bulk requests: a bulk request is not started while an interactive request
+/// is being made. The bulk requests waiting are bounded, so that a flood of batch work fails fast instead of piling up.
+pub(crate) struct Scheduler {
+    state: Mutex<SchedulerState>,
+    interactive_finished: Condvar,
+}
+
+struct SchedulerState {
+    interactive_in_flight: usize,
+    bulk_waiting: usize,
+    bulk_queue_capacity: usize,
+}
+
+/// marks an interactive request as finished when dropped.
+pub(crate) struct Admission<'a> {
+    scheduler: &'a Scheduler,
+    is_interactive: bool,
+}
+
+impl Scheduler {
+    pub(crate) const fn new() -> Scheduler {
+        Scheduler {
+            state: Mutex::new(SchedulerState {
+                interactive_in_flight: 0,
+                bulk_waiting: 0,
+                bulk_queue_capacity: DEFAULT_BULK_QUEUE_CAPACITY,
+            }),
+            interactive_finished: Condvar::new(),
+        }
+    }
+
+    /// admits a request of the priority. An interactive request is admitted right away, while a bulk request waits
+    /// until no interactive request is being made.
+    ///
+    /// # Error
+    ///
+    /// This function returns `QueueFull` error when the bulk queue is full, and `DeadlineExceeded` error when the
+    /// deadline of the call passes while waiting.
+    pub(crate) fn admit(&self, priority: Priority) -> Result<Admission<'_>, ReturnError> {
+
+        let mut state = self.lock_state();
+
+        if priority == Priority::Interactive {
+            state.interactive_in_flight += 1;
+
+            return Ok(Admission { scheduler: self, is_interactive: true });
+        }
+
+        if state.interactive_in_flight == 0 { return Ok(Admission { scheduler: self, is_interactive: false }); }
+
+        if state.bulk_waiting >= state.bulk_queue_capacity { return Err(ReturnError::QueueFull); }
+
+        state.bulk_waiting += 1;
+
+        while state.interactive_in_flight > 0 {
+            if let Err(return_error) = deadline::check() {
+                state.bulk_waiting -= 1;
+
+                return Err(return_error);
+            }
+
+            state = match self.interactive_finished.wait_timeout(state, DEADLINE_CHECK_INTERVAL) {
+                Ok((state, _)) => state,
+                Err(poisoned) => poisoned.into_inner().0,
+            };
+        }
+
+        state.bulk_waiting -= 1;
+
+        Ok(Admission { scheduler: self, is_interactive: false })
+    }
+
+    /// changes the number of the bulk requests that may wait at once. Zero makes the bulk requests fail while an 
+    /// interactive request is being made.
+    pub(crate) fn set_bulk_queue_capacity(&self, capacity: usize) {
+        self.lock_state().bulk_queue_capacity = capacity;
+    }
+
+    /// locks the state by recovering a poisoned lock since the counters cannot be left half updated.
+    fn lock_state(&self) -> MutexGuard<'_, SchedulerState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl Drop for Admission<'_> {
+    fn drop(&mut self) {
+
+        if !self.is_interactive { return; }
+
+        self.scheduler.lock_state().interactive_in_flight -= 1;
+        self.scheduler.interactive_finished.notify_all();
+    }
+}
+
+
+/// admits a request of the thread to the process wide scheduler by the priority of the thread.
+///
+/// # Error
+///
+/// This function returns the errors of [`Scheduler::admit`].
+pub(crate) fn admit() -> Result<Admission<'static>, ReturnError> {
+    SCHEDULER.admit(PRIORITY.with(Cell::get))
+}
+
+pub(crate) fn set_bulk_queue_capacity(capacity: usize) {
+    SCHEDULER.set_bulk_queue_capacity(capacity);
+}
+
+/// sets the priority of the requests made by the thread.
+pub(crate) fn set_priority(priority: Priority) {
+    PRIORITY.with(|current_priority| current_priority.set(priority));
+}
+
+
+/// blocks until at least [`MIN_REQUEST_INTERVAL`] passes after the last request of any thread of a batch operation.
+#[cfg(any(feature = "cache", feature = "export"))]
+pub(crate) fn wait_for_turn(last_request: &Mutex<Option<Instant>>) {
+
+    let mut last_request = match last_request.lock() {
+        Ok(last_request) => last_request,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    if let Some(last_instant) = *last_request {
+        let elapsed = last_instant.elapsed();
+
+        if elapsed < MIN_REQUEST_INTERVAL { thread::sleep(MIN_REQUEST_INTERVAL - elapsed); }
+    }
+
+    *last_request = Some(Instant::now());
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    #[test]
+    fn should_let_interactive_requests_preempt_bulk_requests() {
+        let scheduler = Scheduler::new();
+
+        assert!(scheduler.admit(Priority::Bulk).is_ok());
+
+        let interactive_admission = scheduler.admit(Priority::Interactive).unwrap();
+        let is_interactive_finished = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            let bulk_request = scope.spawn(|| {
+                let _admission = scheduler.admit(Priority::Bulk).unwrap();
+
+                is_interactive_finished.load(Ordering::SeqCst)
+            });
+
+            thread::sleep(Duration::from_millis(50));
+
+            is_interactive_finished.store(true, Ordering::SeqCst);
+            drop(interactive_admission);
+
+            assert!(bulk_request.join().unwrap());
+        });
+
+        scheduler.set_bulk_queue_capacity(0);
+
+        let _interactive_admission = scheduler.admit(Priority::Interactive).unwrap();
+
+        assert!(matches!(scheduler.admit(Priority::Bulk), Err(ReturnError::QueueFull)));
+    }
+}
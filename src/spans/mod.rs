@@ -0,0 +1,304 @@
+#[cfg(feature = "tracing")]
+use std::cell::RefCell;
+#[cfg(feature = "tracing")]
+use std::collections::HashMap;
+#[cfg(feature = "tracing")]
+use std::convert::TryFrom;
+#[cfg(feature = "tracing")]
+use std::sync::{Mutex, MutexGuard, OnceLock};
+#[cfg(feature = "tracing")]
+use std::time::Duration;
+
+#[cfg(feature = "tracing")]
+use async_trait::async_trait;
+#[cfg(feature = "tracing")]
+use opentelemetry::Context;
+#[cfg(feature = "tracing")]
+use opentelemetry::propagation::TextMapPropagator;
+#[cfg(feature = "tracing")]
+use opentelemetry::trace::{TraceContextExt, TracerProvider};
+#[cfg(feature = "tracing")]
+use opentelemetry_http::{Bytes, HttpClient, HttpError, Request, Response};
+#[cfg(feature = "tracing")]
+use opentelemetry_otlp::{Protocol, SpanExporter, WithExportConfig, WithHttpConfig};
+#[cfg(feature = "tracing")]
+use opentelemetry_sdk::Resource;
+#[cfg(feature = "tracing")]
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+#[cfg(feature = "tracing")]
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+#[cfg(feature = "tracing")]
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+#[cfg(feature = "tracing")]
+use tracing_subscriber::{Registry, reload};
+#[cfg(feature = "tracing")]
+use tracing_subscriber::layer::SubscriberExt;
+#[cfg(feature = "tracing")]
+use crate::transport::{self, Transport};
+use tracing::span::EnteredSpan;
+
+
+/// is the longest an export to the collector may take.
+#[cfg(feature = "tracing")]
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(feature = "tracing")]
+const SERVICE_NAME: &str = "tcmb_evds_c";
+
+/// is the layer exporting the spans of the `tracing` crate to the collector.
+#[cfg(feature = "tracing")]
+type ExportLayer = OpenTelemetryLayer<Registry, SdkTracer>;
+
+/// is the handle swapping the export layer of the subscriber installed by the library, or None when another
+/// subscriber was installed by the process before.
+#[cfg(feature = "tracing")]
+static EXPORT_LAYER_HANDLE: OnceLock<Option<reload::Handle<Option<ExportLayer>, Registry>>> = OnceLock::new();
+
+/// is the provider batching the spans and exporting them to the endpoint set last. No span is exported while it is
+/// not set.
+#[cfg(feature = "tracing")]
+static TRACER_PROVIDER: Mutex<Option<SdkTracerProvider>> = Mutex::new(None);
+
+#[cfg(feature = "tracing")]
+thread_local! {
+    /// is the context of the caller the spans of the operational functions of the thread are parented to, given as a
+    /// W3C `traceparent`.
+    static PARENT_CONTEXT: RefCell<Option<Context>> = const { RefCell::new(None) };
+}
+
+
+/// posts the spans to the collector via a transport of the HTTP backend rather than via the request functions, so
+/// the export is not traced, counted in the metrics or guarded by the circuit breaker of EVDS.
+#[cfg(feature = "tracing")]
+#[derive(Debug)]
+struct TransportClient;
+
+#[cfg(feature = "tracing")]
+#[async_trait]
+impl HttpClient for TransportClient {
+    async fn send_bytes(&self, request: Request<Bytes>) -> Result<Response<Bytes>, HttpError> {
+
+        let mut header_lines = Vec::new();
+
+        for (name, value) in request.headers() {
+            header_lines.push(format!("{}: {}", name, value.to_str()?));
+        }
+
+        let received = transport::new_transport()
+            .perform_post(&request.uri().to_string(), &header_lines, request.body(), EXPORT_TIMEOUT)
+            .map_err(|transfer_failure| transfer_failure.into_error())?;
+
+        let status = u16::try_from(received.status_code)?;
+
+        Ok(Response::builder().status(status).body(Bytes::from(received.body))?)
+    }
+}
+
+
+/// enters the span of an operational function named after the function, e.g. "tcmb_evds_c_get_data". The stages of
+/// the request pipeline such as "build_url", "http", "parse" and "convert" are its child spans entered via the macros
+/// of the `tracing` crate.
+///
+/// The span is a child of the `traceparent` set by the caller when the thread is not in a span yet.
+pub(crate) fn enter_function(function_name: &str) -> EnteredSpan {
+
+    let span = tracing::info_span!("ffi", otel.name = function_name);
+
+    #[cfg(feature = "tracing")]
+    if tracing::Span::current().is_none() {
+        if let Some(parent_context) = PARENT_CONTEXT.with(|parent_context| parent_context.borrow().clone()) {
+            let _ = span.set_parent(parent_context);
+        }
+    }
+
+    span.entered()
+}
+
+/// sets the OTLP/HTTP traces endpoint the spans are exported to in the OTLP JSON encoding. An empty endpoint stops the
+/// export after exporting the spans recorded so far.
+///
+/// false is returned when the endpoint is not a valid url or the process has already installed another subscriber of
+/// the `tracing` crate, whose layers should export the spans instead.
+#[cfg(feature = "tracing")]
+pub(crate) fn set_otlp_endpoint(endpoint: &str) -> bool {
+
+    let endpoint = endpoint.trim();
+
+    let export_layer_handle = match EXPORT_LAYER_HANDLE.get_or_init(install_subscriber) {
+        Some(export_layer_handle) => export_layer_handle,
+        None => return false,
+    };
+
+    let tracer_provider = match endpoint.is_empty() {
+        true => None,
+        false => match build_tracer_provider(endpoint) {
+            Some(tracer_provider) => Some(tracer_provider),
+            None => return false,
+        },
+    };
+
+    let export_layer = tracer_provider
+        .as_ref()
+        .map(|tracer_provider| tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME)));
+
+    if export_layer_handle.reload(export_layer).is_err() { return false; }
+
+    let former_tracer_provider = std::mem::replace(&mut *lock(&TRACER_PROVIDER), tracer_provider);
+
+    if let Some(former_tracer_provider) = former_tracer_provider { let _ = former_tracer_provider.shutdown(); }
+
+    true
+}
+
+/// sets the W3C `traceparent` of the caller, e.g. "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01", so that
+/// the spans of the thread join the distributed trace of the caller. An empty text clears it.
+///
+/// false is returned when the text is not a valid `traceparent`.
+#[cfg(feature = "tracing")]
+pub(crate) fn set_trace_parent(trace_parent: &str) -> bool {
+
+    let trace_parent = trace_parent.trim();
+
+    let parent_context = match trace_parent.is_empty() {
+        true => None,
+        false => match parse_trace_parent(trace_parent) {
+            Some(parent_context) => Some(parent_context),
+            None => return false,
+        },
+    };
+
+    PARENT_CONTEXT.with(|context| *context.borrow_mut() = parent_context);
+
+    true
+}
+
+/// exports the spans batched so far to the collector.
+///
+/// false is returned when no endpoint is set or the collector does not accept the spans.
+#[cfg(feature = "tracing")]
+pub(crate) fn flush() -> bool {
+    match lock(&TRACER_PROVIDER).as_ref() {
+        Some(tracer_provider) => tracer_provider.force_flush().is_ok(),
+        None => false,
+    }
+}
+
+/// parses a W3C `traceparent` into the remote context of the caller via the propagator of OpenTelemetry. Uppercase and
+/// all-zero ids are invalid.
+#[cfg(feature = "tracing")]
+pub(crate) fn parse_trace_parent(trace_parent: &str) -> Option<Context> {
+
+    let carrier = HashMap::from([("traceparent".to_string(), trace_parent.to_string())]);
+
+    let parent_context = TraceContextPropagator::new().extract(&carrier);
+
+    match parent_context.span().span_context().is_valid() {
+        true => Some(parent_context),
+        false => None,
+    }
+}
+
+/// installs the subscriber of the library whose export layer is set via [`set_otlp_endpoint`]. None is returned when
+/// the process has already installed another subscriber.
+#[cfg(feature = "tracing")]
+fn install_subscriber() -> Option<reload::Handle<Option<ExportLayer>, Registry>> {
+
+    let (export_layer, export_layer_handle) = reload::Layer::new(None);
+
+    tracing::subscriber::set_global_default(Registry::default().with(export_layer)).ok()?;
+
+    Some(export_layer_handle)
+}
+
+/// builds the provider batching the spans of the library and exporting them to the endpoint in the background.
+#[cfg(feature = "tracing")]
+fn build_tracer_provider(endpoint: &str) -> Option<SdkTracerProvider> {
+
+    let span_exporter = SpanExporter::builder()
+        .with_http()
+        .with_protocol(Protocol::HttpJson)
+        .with_endpoint(endpoint)
+        .with_timeout(EXPORT_TIMEOUT)
+        .with_http_client(TransportClient)
+        .build()
+        .ok()?;
+
+    let tracer_provider = SdkTracerProvider::builder()
+        .with_batch_exporter(span_exporter)
+        .with_resource(Resource::builder_empty().with_service_name(SERVICE_NAME).build())
+        .build();
+
+    Some(tracer_provider)
+}
+
+/// locks the mutex by recovering a poisoned lock since the provider cannot be left half updated.
+#[cfg(feature = "tracing")]
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::Arc;
+
+    use opentelemetry::trace::{SpanId, TraceId};
+    use opentelemetry_sdk::error::OTelSdkResult;
+    use opentelemetry_sdk::trace::SpanData;
+
+    use super::*;
+
+    /// keeps the exported spans to be checked by the tests.
+    #[derive(Debug, Clone, Default)]
+    struct KeptSpans(Arc<Mutex<Vec<SpanData>>>);
+
+    impl opentelemetry_sdk::trace::SpanExporter for KeptSpans {
+        fn export(&self, batch: Vec<SpanData>) -> impl std::future::Future<Output = OTelSdkResult> + Send {
+            lock(&self.0).extend(batch);
+
+            async { Ok(()) }
+        }
+    }
+
+    #[test]
+    fn should_parse_trace_parent() {
+        let parent_context = parse_trace_parent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+        let span_context = parent_context.span().span_context().clone();
+
+        assert_eq!(TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(), span_context.trace_id());
+        assert_eq!(SpanId::from_hex("00f067aa0ba902b7").unwrap(), span_context.span_id());
+        assert!(span_context.is_remote());
+
+        assert!(parse_trace_parent("00-00000000000000000000000000000000-00f067aa0ba902b7-01").is_none());
+        assert!(parse_trace_parent("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01").is_none());
+        assert!(parse_trace_parent("4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7").is_none());
+    }
+
+    #[test]
+    fn should_export_function_spans_under_trace_parent() {
+        let kept_spans = KeptSpans::default();
+        let tracer_provider = SdkTracerProvider::builder().with_simple_exporter(kept_spans.clone()).build();
+        let export_layer = tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME));
+
+        assert!(set_trace_parent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"));
+
+        tracing::subscriber::with_default(Registry::default().with(export_layer), || {
+            let _function_span = enter_function("tcmb_evds_c_get_data");
+            let _parse_span = tracing::info_span!("parse").entered();
+        });
+
+        assert!(set_trace_parent(""));
+
+        let exported_spans = lock(&kept_spans.0);
+        let [parse_span, function_span] = exported_spans.as_slice() else { panic!("two spans are expected") };
+
+        assert_eq!("parse", parse_span.name);
+        assert_eq!("tcmb_evds_c_get_data", function_span.name);
+        assert_eq!(function_span.span_context.span_id(), parse_span.parent_span_id);
+        assert_eq!(SpanId::from_hex("00f067aa0ba902b7").unwrap(), function_span.parent_span_id);
+        assert_eq!(TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(), parse_span.span_context.trace_id());
+    }
+}
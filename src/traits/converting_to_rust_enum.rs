@@ -1,6 +1,37 @@
+use libc::c_uint;
+
 use super::enum_specific::EnumSpecific;
 
+use crate::error::ReturnError;
+
 pub(crate) trait ConvertingToRustEnum<T: EnumSpecific> {
     /// converts implemented type to an enum type declared with T.
     fn convert(&self) -> T;
+
+    /// converts implemented type to an enum type declared with T without falling back to a default option.
+    ///
+    /// # Error
+    ///
+    /// This function returns `InvalidEnumValue` error when the value does not correspond to any option.
+    fn convert_strictly(&self) -> Result<T, ReturnError> {
+        Ok(self.convert())
+    }
+}
+
+/// reads a fieldless `#[repr(C)]` enum received from C as an integer.
+///
+/// C callers may pass uninitialized or out of range integers as enums, and holding such a value as a Rust enum is 
+/// undefined behavior. The FFI functions therefore receive the enums as `c_uint` values, which are checked against the 
+/// options before any enum is created.
+pub(crate) trait ReadingCEnum: Sized + Copy + 'static {
+    /// lists the options in the order of their values, starting from zero.
+    const OPTIONS: &'static [Self];
+
+    /// is the option used for an out of range value unless strict enums are enabled.
+    const FALLBACK: Self;
+
+    /// gives the option of the value, or None when the value does not correspond to any option.
+    fn from_value(value: c_uint) -> Option<Self> {
+        Self::OPTIONS.get(value as usize).copied()
+    }
 }
@@ -1,4 +1,5 @@
 pub(crate) mod having_date_validation;
+#[cfg(feature = "currency")]
 pub(crate) mod making_list;
 pub(crate) mod making_url_format;
 pub(crate) mod converting_to_rust_enum;
@@ -6,6 +7,7 @@ pub(crate) mod enum_specific;
 pub(crate) mod checking_string_character;
 
 pub(crate) use self::having_date_validation::*;
+#[cfg(feature = "currency")]
 pub(crate) use self::making_list::*;
 pub(crate) use self::making_url_format::*;
 pub(crate) use self::converting_to_rust_enum::*;
@@ -0,0 +1,170 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use curl::easy::{Easy, List};
+
+use super::{HttpTransport, Received, TransferFailure, Transport};
+use crate::common::content_type::find_content_type;
+use crate::common::response_buffer::{find_content_length, reserve_response};
+use crate::error::ReturnError;
+use crate::rate_limit;
+
+
+/// creates a handle of libcurl, which keeps its connections alive for the following transfers.
+pub(crate) fn new_transport() -> HttpTransport {
+    Easy::new()
+}
+
+
+impl Transport for Easy {
+    /// gets the url after resetting the options of the handle, so the connections of the handle are kept.
+    fn perform_get(
+        &mut self,
+        url: &str,
+        header_line: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<Received, TransferFailure> {
+
+        self.reset();
+
+        if self.url(url).is_err() { return Err(TransferFailure::Setup(ReturnError::UnableToSetUrl)); }
+
+        if let Some(header_line) = header_line {
+            set_headers(self, &[header_line.to_string()])?;
+        }
+        if let Some(timeout) = timeout {
+            if self.timeout(timeout).is_err() { return Err(TransferFailure::Setup(ReturnError::UnableToRequest)); }
+        }
+
+        receive(self)
+    }
+
+    #[cfg(feature = "tracing")]
+    fn perform_post(
+        &mut self,
+        url: &str,
+        header_lines: &[String],
+        body: &[u8],
+        timeout: Duration,
+    ) -> Result<Received, TransferFailure> {
+
+        self.reset();
+
+        if self.url(url).is_err() { return Err(TransferFailure::Setup(ReturnError::UnableToSetUrl)); }
+
+        if self.post(true).is_err() || self.post_fields_copy(body).is_err() || self.timeout(timeout).is_err() {
+            return Err(TransferFailure::Setup(ReturnError::UnableToRequest));
+        }
+
+        set_headers(self, header_lines)?;
+
+        receive(self)
+    }
+}
+
+
+/// sets the header lines of the request, e.g. "Content-Type: application/json".
+fn set_headers(handle: &mut Easy, header_lines: &[String]) -> Result<(), TransferFailure> {
+
+    let mut headers = List::new();
+
+    for header_line in header_lines {
+        if headers.append(header_line).is_err() { return Err(TransferFailure::Setup(ReturnError::UnableToRequest)); }
+    }
+
+    handle.http_headers(headers).map_err(|_| TransferFailure::Setup(ReturnError::UnableToRequest))
+}
+
+/// performs the transfer of the handle and receives the response into a buffer reserved from its `Content-Length`
+/// header.
+fn receive(handle: &mut Easy) -> Result<Received, TransferFailure> {
+    let mut body = Vec::new();
+    let mut retry_after = None;
+    let mut content_type = None;
+    let content_length = Cell::new(None);
+
+    {
+        let mut transfer = handle.transfer();
+        if transfer.write_function(|data| {
+            reserve_response(&mut body, content_length.get());
+            body.extend_from_slice(data);
+            Ok(data.len())
+        }).is_err() {
+            return Err(TransferFailure::Setup(ReturnError::FailedToSaveReceivedData));
+        }
+        let header_result = transfer.header_function(|header_line| {
+            if let Some(value) = rate_limit::find_retry_after(header_line) { retry_after = Some(value); }
+            if let Some(length) = find_content_length(header_line) { content_length.set(Some(length)); }
+            if let Some(media_type) = find_content_type(header_line) { content_type = Some(media_type); }
+            true
+        });
+
+        if header_result.is_err() {
+            return Err(TransferFailure::Setup(ReturnError::FailedToSaveReceivedData));
+        }
+
+        transfer.perform().map_err(|transfer_error| TransferFailure::Transfer(to_return_error(&transfer_error)))?;
+    }
+
+    let status_code = handle.response_code()
+        .map_err(|transfer_error| TransferFailure::Transfer(to_return_error(&transfer_error)))?;
+
+    Ok(Received { status_code, body, retry_after, content_type })
+}
+
+/// maps a failed transfer of curl to the error of its cause and keeps the code and the message of curl as the
+/// detail of the error.
+fn to_return_error(transfer_error: &curl::Error) -> ReturnError {
+
+    let mut detail = format!("curl error {}: {}", transfer_error.code(), transfer_error.description());
+
+    if let Some(extra_description) = transfer_error.extra_description() {
+        detail = format!("{} ({})", detail, extra_description);
+    }
+
+    if transfer_error.is_couldnt_resolve_host() || transfer_error.is_couldnt_resolve_proxy() {
+        return ReturnError::DnsFailure(detail);
+    }
+
+    let is_tls_error = transfer_error.is_ssl_connect_error()
+        || transfer_error.is_peer_failed_verification()
+        || transfer_error.is_ssl_certproblem()
+        || transfer_error.is_ssl_cipher()
+        || transfer_error.is_ssl_cacert_badfile()
+        || transfer_error.is_ssl_issuer_error();
+
+    if is_tls_error { return ReturnError::TlsError(detail); }
+
+    if transfer_error.is_operation_timedout() { return ReturnError::Timeout(detail); }
+
+    ReturnError::FailedToApplyRequest(detail)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_transfer_errors() {
+        let dns_failure = to_return_error(&curl::Error::new(6));
+
+        assert!(matches!(dns_failure, ReturnError::DnsFailure(_)));
+        assert!(dns_failure.get_detail().is_some_and(|detail| detail.starts_with("curl error 6: ")));
+
+        let timeout = to_return_error(&curl::Error::new(28));
+
+        assert!(matches!(timeout, ReturnError::Timeout(_)));
+        assert!(matches!(to_return_error(&curl::Error::new(60)), ReturnError::TlsError(_)));
+        assert!(matches!(to_return_error(&curl::Error::new(56)), ReturnError::FailedToApplyRequest(_)));
+    }
+
+    #[test]
+    fn should_fail_transfers_to_closed_ports() {
+        let mut handle = new_transport();
+
+        let failure = handle.perform_get("http://127.0.0.1:9", None, Some(Duration::from_secs(5)));
+
+        assert!(matches!(failure, Err(TransferFailure::Transfer(ReturnError::FailedToApplyRequest(_)))));
+    }
+}
@@ -0,0 +1,160 @@
+/// makes the transfers via libcurl, which is the default HTTP backend.
+#[cfg(not(feature = "pure_rust_http"))]
+mod curl_transport;
+/// makes the transfers via ureq and rustls when the `pure_rust_http` feature is on.
+#[cfg(feature = "pure_rust_http")]
+mod ureq_transport;
+
+use std::time::Duration;
+
+#[cfg(not(feature = "pure_rust_http"))]
+pub(crate) use self::curl_transport::new_transport;
+#[cfg(feature = "pure_rust_http")]
+pub(crate) use self::ureq_transport::new_transport;
+
+use crate::deadline;
+use crate::error::ReturnError;
+use crate::metrics::METRICS;
+
+
+/// is the HTTP backend the library is built with, namely "curl" or "ureq".
+#[cfg(not(feature = "pure_rust_http"))]
+pub(crate) const BACKEND_NAME: &str = "curl";
+#[cfg(feature = "pure_rust_http")]
+pub(crate) const BACKEND_NAME: &str = "ureq";
+
+/// is the transport of the HTTP backend chosen at compile time, namely a handle of libcurl or an agent of ureq when
+/// the `pure_rust_http` feature is on.
+#[cfg(not(feature = "pure_rust_http"))]
+pub(crate) type HttpTransport = curl::easy::Easy;
+#[cfg(feature = "pure_rust_http")]
+pub(crate) type HttpTransport = ureq::Agent;
+
+
+/// is a response received by a transport regardless of its status code.
+#[derive(Debug)]
+pub(crate) struct Received {
+    /// is the HTTP status code of the response, e.g. 200.
+    pub(crate) status_code: u32,
+    pub(crate) body: Vec<u8>,
+    /// is the value of the `Retry-After` header, if any.
+    pub(crate) retry_after: Option<String>,
+    /// is the media type of the `Content-Type` header, if any.
+    pub(crate) content_type: Option<String>,
+}
+
+/// is the failure of a transfer.
+#[derive(Debug)]
+pub(crate) enum TransferFailure {
+    /// is a request that cannot be made, e.g. due to an invalid url, which is not retried.
+    Setup(ReturnError),
+    /// is a transfer failed on the way, e.g. due to a timeout, which is retried.
+    Transfer(ReturnError),
+}
+
+#[cfg(feature = "tracing")]
+impl TransferFailure {
+    /// gives the error of the failure regardless of whether it is retried.
+    pub(crate) fn into_error(self) -> ReturnError {
+        match self {
+            TransferFailure::Setup(error) | TransferFailure::Transfer(error) => error,
+        }
+    }
+}
+
+
+/// makes single transfers via an HTTP backend. The request pipeline, namely the retries, the rate limits, the
+/// circuit breaker and the metrics, is built on it, so that the backends differ only in how the bytes are moved.
+pub(crate) trait Transport {
+    /// gets the url with the optional header line, e.g. "X-Request-Id: 42", within the optional timeout.
+    fn perform_get(
+        &mut self,
+        url: &str,
+        header_line: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<Received, TransferFailure>;
+
+    /// posts the body to the url with the header lines within the timeout, e.g. the spans exported to a collector.
+    #[cfg(feature = "tracing")]
+    fn perform_post(
+        &mut self,
+        url: &str,
+        header_lines: &[String],
+        body: &[u8],
+        timeout: Duration,
+    ) -> Result<Received, TransferFailure>;
+}
+
+
+/// gets the url via the transport up to 3 times while its transfer fails. Each attempt is limited by the attempt
+/// timeout and the time left until the deadline of the call, and the error of the last attempt is returned.
+pub(crate) fn get_with_retries<T: Transport>(
+    transport: &mut T,
+    url: &str,
+    header_line: Option<&str>,
+) -> Result<Received, ReturnError> {
+
+    let mut retries = 0;
+
+    loop {
+        let timeout = deadline::get_attempt_timeout()?;
+
+        let transfer_error = match transport.perform_get(url, header_line, timeout) {
+            Ok(received) => return Ok(received),
+            Err(TransferFailure::Setup(setup_error)) => return Err(setup_error),
+            Err(TransferFailure::Transfer(transfer_error)) => transfer_error,
+        };
+
+        deadline::check()?;
+
+        if retries == 2 { return Err(transfer_error); }
+
+        retries += 1;
+        METRICS.record_retry();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// fails its transfers with the given failures in order and receives an empty response afterwards.
+    struct FailingTransport(Vec<TransferFailure>, usize);
+
+    impl Transport for FailingTransport {
+        fn perform_get(&mut self, _: &str, _: Option<&str>, _: Option<Duration>) -> Result<Received, TransferFailure> {
+            self.1 += 1;
+
+            match self.0.is_empty() {
+                true => Ok(Received { status_code: 200, body: Vec::new(), retry_after: None, content_type: None }),
+                false => Err(self.0.remove(0)),
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        fn perform_post(&mut self, _: &str, _: &[String], _: &[u8], _: Duration) -> Result<Received, TransferFailure> {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn should_retry_failed_transfers() {
+        let timeout = || TransferFailure::Transfer(ReturnError::Timeout("timed out".to_string()));
+
+        let mut transport = FailingTransport(vec![timeout(), timeout()], 0);
+
+        assert_eq!(200, get_with_retries(&mut transport, "https://evds2.tcmb.gov.tr", None).unwrap().status_code);
+        assert_eq!(3, transport.1);
+
+        let mut transport = FailingTransport(vec![timeout(), timeout(), timeout()], 0);
+
+        assert!(matches!(get_with_retries(&mut transport, "url", None), Err(ReturnError::Timeout(_))));
+        assert_eq!(3, transport.1);
+
+        let mut transport = FailingTransport(vec![TransferFailure::Setup(ReturnError::UnableToSetUrl)], 0);
+
+        assert!(matches!(get_with_retries(&mut transport, "url", None), Err(ReturnError::UnableToSetUrl)));
+        assert_eq!(1, transport.1);
+    }
+}
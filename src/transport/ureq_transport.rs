@@ -0,0 +1,161 @@
+use std::error::Error;
+use std::io::{self, Read};
+use std::time::Duration;
+
+use ureq::{Agent, AgentBuilder, ErrorKind, Request, Response};
+
+use super::{HttpTransport, Received, TransferFailure, Transport};
+use crate::common::content_type::find_content_type;
+use crate::common::response_buffer::{find_content_length, reserve_response};
+use crate::error::ReturnError;
+use crate::rate_limit;
+
+
+/// creates an agent of ureq, which keeps its connections alive for the following transfers. The redirects are not
+/// followed, in the same way with libcurl.
+pub(crate) fn new_transport() -> HttpTransport {
+    AgentBuilder::new().redirects(0).build()
+}
+
+
+impl Transport for Agent {
+    fn perform_get(
+        &mut self,
+        url: &str,
+        header_line: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<Received, TransferFailure> {
+
+        let mut request = set_headers(self.get(url), header_line.into_iter())?;
+
+        if let Some(timeout) = timeout { request = request.timeout(timeout); }
+
+        receive(request.call())
+    }
+
+    #[cfg(feature = "tracing")]
+    fn perform_post(
+        &mut self,
+        url: &str,
+        header_lines: &[String],
+        body: &[u8],
+        timeout: Duration,
+    ) -> Result<Received, TransferFailure> {
+
+        let request = set_headers(self.post(url), header_lines.iter().map(String::as_str))?.timeout(timeout);
+
+        receive(request.send_bytes(body))
+    }
+}
+
+
+/// sets the header lines of the request, e.g. "Content-Type: application/json".
+fn set_headers<'a>(
+    mut request: Request,
+    header_lines: impl Iterator<Item = &'a str>,
+) -> Result<Request, TransferFailure> {
+
+    for header_line in header_lines {
+        let (name, value) = match header_line.split_once(':') {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => return Err(TransferFailure::Setup(ReturnError::UnableToRequest)),
+        };
+
+        request = request.set(name, value);
+    }
+
+    Ok(request)
+}
+
+/// receives the response of a request regardless of its status code into a buffer reserved from its
+/// `Content-Length` header. The headers are read via the same parsers with the header lines of libcurl.
+fn receive(result: Result<Response, ureq::Error>) -> Result<Received, TransferFailure> {
+
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(ureq::Error::Transport(transport_error)) => return Err(to_transfer_failure(&transport_error)),
+    };
+
+    let mut retry_after = None;
+    let mut content_type = None;
+    let mut content_length = None;
+
+    for name in response.headers_names() {
+        for value in response.all(&name) {
+            let header_line = format!("{}: {}", name, value);
+
+            if let Some(value) = rate_limit::find_retry_after(header_line.as_bytes()) { retry_after = Some(value); }
+            if let Some(length) = find_content_length(header_line.as_bytes()) { content_length = Some(length); }
+            if let Some(media_type) = find_content_type(header_line.as_bytes()) { content_type = Some(media_type); }
+        }
+    }
+
+    let status_code = u32::from(response.status());
+
+    let mut body = Vec::new();
+    reserve_response(&mut body, content_length);
+
+    if let Err(read_error) = response.into_reader().read_to_end(&mut body) {
+        return Err(TransferFailure::Transfer(to_return_error(&read_error, format!("ureq error: {}", read_error))));
+    }
+
+    Ok(Received { status_code, body, retry_after, content_type })
+}
+
+/// maps a failed transfer of ureq to the error of its cause and keeps the message of ureq as the detail of the error.
+/// The requests which cannot be made, e.g. due to an invalid url, are not retried.
+fn to_transfer_failure(transport_error: &ureq::Transport) -> TransferFailure {
+
+    let detail = format!("ureq error: {}", transport_error);
+
+    match transport_error.kind() {
+        ErrorKind::InvalidUrl | ErrorKind::UnknownScheme => TransferFailure::Setup(ReturnError::UnableToSetUrl),
+        ErrorKind::Dns => TransferFailure::Transfer(ReturnError::DnsFailure(detail)),
+        _ => match transport_error.source().and_then(|source| source.downcast_ref::<io::Error>()) {
+            Some(io_error) => TransferFailure::Transfer(to_return_error(io_error, detail)),
+            None => TransferFailure::Transfer(ReturnError::FailedToApplyRequest(detail)),
+        },
+    }
+}
+
+/// maps an IO error of a transfer to a timeout, a TLS error of rustls or a failed request.
+fn to_return_error(io_error: &io::Error, detail: String) -> ReturnError {
+
+    if matches!(io_error.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock) {
+        return ReturnError::Timeout(detail);
+    }
+
+    if io_error.get_ref().is_some_and(|inner_error| inner_error.is::<ureq::rustls::Error>()) {
+        return ReturnError::TlsError(detail);
+    }
+
+    ReturnError::FailedToApplyRequest(detail)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_transfer_errors() {
+        let timeout = io::Error::new(io::ErrorKind::TimedOut, "timed out reading response");
+
+        assert!(matches!(to_return_error(&timeout, String::new()), ReturnError::Timeout(_)));
+
+        let tls_error = io::Error::new(io::ErrorKind::InvalidData, ureq::rustls::Error::DecryptError);
+
+        assert!(matches!(to_return_error(&tls_error, String::new()), ReturnError::TlsError(_)));
+
+        let mut agent = new_transport();
+
+        assert!(matches!(
+            agent.perform_get("evds2.tcmb.gov.tr", None, None),
+            Err(TransferFailure::Setup(ReturnError::UnableToSetUrl))
+        ));
+        assert!(matches!(
+            agent.perform_get("http://127.0.0.1:9", None, Some(Duration::from_secs(5))),
+            Err(TransferFailure::Transfer(ReturnError::FailedToApplyRequest(detail))) if detail.starts_with("ureq")
+        ));
+    }
+}
@@ -0,0 +1,15 @@
+// swift-tools-version:5.5
+import PackageDescription
+
+// wraps the library built via `cargo build --release` in the parent directory. The header and the module map are
+// generated into `target/` by the build, and the library is found via `-Xlinker -L../target/release`.
+let package = Package(
+    name: "TcmbEvds",
+    products: [
+        .library(name: "TcmbEvds", targets: ["TcmbEvds"]),
+    ],
+    targets: [
+        .systemLibrary(name: "CTcmbEvds"),
+        .target(name: "TcmbEvds", dependencies: ["CTcmbEvds"]),
+    ]
+)
@@ -0,0 +1,132 @@
+import CTcmbEvds
+
+/// is an error returned by the library with its type, message and the detail of the underlying curl error if any.
+public struct EvdsError: Error, CustomStringConvertible {
+    public let type: ReturnErrorC
+    public let message: String
+    public let detail: String?
+
+    public var description: String { detail.map { "\(message) (\($0))" } ?? message }
+}
+
+/// makes the EVDS requests of an api key via the C functions of the library.
+///
+/// The results are copied into Swift strings and released via `tcmb_evds_c_result_free` before returning, so users
+/// do not manage the memory of the library.
+public struct Evds {
+    public let apiKey: String
+
+    public init(apiKey: String) {
+        self.apiKey = apiKey
+    }
+
+    /// gets the data of the data series, e.g. "TP.DK.USD.S", on the date or in the date range, e.g. "13-12-2011".
+    public func data(
+        series: String,
+        date: String,
+        format: TcmbEvdsReturnFormat = Json,
+        asciiMode: Bool = false
+    ) throws -> String {
+        try withInputs(series, date, apiKey) { series, date, apiKey in
+            tcmb_evds_c_get_data(
+                data_series: series, date: date, api_key: apiKey, return_format: format.rawValue, ascii_mode: asciiMode
+            )
+        }
+    }
+
+    /// gets the data of the data group, e.g. "bie_dkdovytl", on the date or in the date range.
+    public func dataGroup(
+        _ dataGroup: String,
+        date: String,
+        format: TcmbEvdsReturnFormat = Json,
+        asciiMode: Bool = false
+    ) throws -> String {
+        try withInputs(dataGroup, date, apiKey) { dataGroup, date, apiKey in
+            tcmb_evds_c_get_data_group(
+                data_group: dataGroup,
+                date: date,
+                api_key: apiKey,
+                return_format: format.rawValue,
+                ascii_mode: asciiMode
+            )
+        }
+    }
+
+    /// gets the categories of EVDS.
+    public func categories(format: TcmbEvdsReturnFormat = Json, asciiMode: Bool = false) throws -> String {
+        try withInputs(apiKey, "", "") { apiKey, _, _ in
+            tcmb_evds_c_get_categories(api_key: apiKey, return_format: format.rawValue, ascii_mode: asciiMode)
+        }
+    }
+
+    /// gets the series of the data group. The codes of the series are also listed by `knownSeries(prefix:)`.
+    public func seriesList(
+        code: String,
+        format: TcmbEvdsReturnFormat = Json,
+        asciiMode: Bool = false
+    ) throws -> String {
+        try withInputs(code, apiKey, "") { code, apiKey, _ in
+            tcmb_evds_c_get_series_list(
+                code: code, api_key: apiKey, return_format: format.rawValue, ascii_mode: asciiMode
+            )
+        }
+    }
+
+    /// lists the codes of the series received so far that start with the prefix without requesting EVDS.
+    public static func knownSeries(prefix: String = "") throws -> [String] {
+        let codes = try withInputs(prefix, "", "") { prefix, _, _ in
+            tcmb_evds_c_list_series(prefix: prefix, return_format: Csv.rawValue)
+        }
+
+        return codes.split(separator: "\n").map(String.init)
+    }
+}
+
+/// passes the texts to the body as inputs that are valid during the call.
+private func withInputs(
+    _ first: String,
+    _ second: String,
+    _ third: String,
+    _ body: (TcmbEvdsInput, TcmbEvdsInput, TcmbEvdsInput) -> TcmbEvdsResult
+) throws -> String {
+    let result = first.withCString { firstPointer in
+        second.withCString { secondPointer in
+            third.withCString { thirdPointer in
+                body(
+                    makeInput(firstPointer, first),
+                    makeInput(secondPointer, second),
+                    makeInput(thirdPointer, third)
+                )
+            }
+        }
+    }
+
+    return try readResult(result)
+}
+
+private func makeInput(_ pointer: UnsafePointer<CChar>, _ text: String) -> TcmbEvdsInput {
+    TcmbEvdsInput(input_ptr: pointer, string_capacity: UInt(text.utf8.count))
+}
+
+/// copies the text of the result and releases the result.
+private func readResult(_ result: TcmbEvdsResult) throws -> String {
+    defer { tcmb_evds_c_result_free(result: result) }
+
+    let text = readText(result.output_ptr, result.string_capacity) ?? ""
+
+    guard result.error_type == NoError else {
+        throw EvdsError(
+            type: result.error_type,
+            message: text,
+            detail: readText(result.detail_ptr, result.detail_capacity)
+        )
+    }
+
+    return text
+}
+
+private func readText(_ pointer: UnsafeMutablePointer<UInt8>?, _ capacity: UInt) -> String? {
+    guard let pointer = pointer else { return nil }
+
+    return String(decoding: UnsafeBufferPointer(start: pointer, count: Int(capacity)), as: UTF8.self)
+}
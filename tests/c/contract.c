@@ -0,0 +1,899 @@
+/*
+ * is the contract test of the C interface of tcmb_evds_c, run by tests/c_contract.rs against the local mock of EVDS.
+ *
+ * Every function declared in tcmb_evds_c.h is called with valid and invalid inputs, and the error codes, the returned
+ * values and the semantics of the returned buffers are checked as C programs rely on them. The requests are sent to
+ * the mock given in the TCMB_EVDS_C_MOCK_URL_ROOT environment variable, and the files are written into the directory
+ * given as the first argument.
+ */
+
+#include <stdio.h>
+#include <string.h>
+#include <wchar.h>
+
+#include "tcmb_evds_c.h"
+
+
+#define CHECK(condition) check((condition), #condition, __LINE__)
+
+#define SERIES "TP.DK.USD.S"
+#define DATES "01-01-2000,31-12-2011"
+#define API_KEY "CONTRACTTESTKEY"
+
+static int failure_count = 0;
+
+static const TcmbEvdsInput NULL_INPUT = { NULL, 0 };
+
+static const char *output_directory = ".";
+
+
+static void check(bool condition, const char *expression, int line) {
+    if (!condition) {
+        fprintf(stderr, "contract.c:%d: check failed: %s\n", line, expression);
+        failure_count++;
+    }
+}
+
+/* checks the NUL terminated text returned by the library equals the expected one. */
+static bool is_text(const char *text, const char *expected_text) {
+    return text != NULL && strcmp(text, expected_text) == 0;
+}
+
+static TcmbEvdsInput input(const char *text) {
+    TcmbEvdsInput text_input;
+
+    text_input.input_ptr = text;
+    text_input.string_capacity = strlen(text);
+
+    return text_input;
+}
+
+/* checks the buffers of the result are consistent with each other as documented for TcmbEvdsResult. */
+static void check_buffers(TcmbEvdsResult result, int line) {
+    check(result.output_ptr != NULL, "the output is not NULL", line);
+    check(result.string_capacity <= result.buffer_capacity, "the string fits into the buffer", line);
+    check(tcmb_evds_c_is_error(result) == (result.error_type != NoError), "the error is reported", line);
+
+    if (result.output_ptr != NULL) {
+        check(memchr(result.output_ptr, '\0', result.string_capacity) == NULL, "the text has no NUL character", line);
+    }
+
+    if (result.detail_ptr == NULL) {
+        check(result.detail_capacity == 0, "a missing detail is empty", line);
+    }
+
+    if (result.error_type != NoError) {
+        check(result.string_capacity > 0, "the error has a message", line);
+    }
+}
+
+/* checks the error type and the buffers of the result, and then releases it. */
+static void expect_result(TcmbEvdsResult result, ReturnErrorC expected_error, int line) {
+    check_buffers(result, line);
+
+    if (result.error_type != expected_error) {
+        fprintf(stderr, "contract.c:%d: expected the error %d, got %d: %.*s %.*s\n",
+                line, (int) expected_error, (int) result.error_type, (int) result.string_capacity, result.output_ptr,
+                (int) result.detail_capacity, result.detail_ptr != NULL ? (const char *) result.detail_ptr : "");
+        failure_count++;
+    }
+
+    tcmb_evds_c_result_free(result);
+}
+
+/* checks the result is an error of any type, and then releases it. */
+static void expect_any_error(TcmbEvdsResult result, int line) {
+    check_buffers(result, line);
+    check(result.error_type != NoError, "the result is an error", line);
+
+    tcmb_evds_c_result_free(result);
+}
+
+/* checks the buffers of the result of a response whose content the mock does not follow, and then releases it. */
+static void expect_any_result(TcmbEvdsResult result, int line) {
+    check_buffers(result, line);
+
+    tcmb_evds_c_result_free(result);
+}
+
+#define EXPECT(result, expected_error) expect_result((result), (expected_error), __LINE__)
+#define EXPECT_ERROR(result) expect_any_error((result), __LINE__)
+#define EXPECT_ANY(result) expect_any_result((result), __LINE__)
+
+/* checks the text of a successful result equals the expected one, and then releases it. */
+static void expect_text(TcmbEvdsResult result, const char *expected_text, int line) {
+    size_t expected_length = strlen(expected_text);
+
+    check_buffers(result, line);
+    check(result.error_type == NoError, "the result is not an error", line);
+    check(result.string_capacity == expected_length, "the text has the expected length", line);
+
+    if (result.string_capacity == expected_length) {
+        check(memcmp(result.output_ptr, expected_text, expected_length) == 0, "the text is the expected one", line);
+    }
+
+    tcmb_evds_c_result_free(result);
+}
+
+#define EXPECT_TEXT(result, expected_text) expect_text((result), (expected_text), __LINE__)
+
+/* checks the handle is not NULL and its accessors agree with each other, and then releases it. */
+static void expect_handle(TcmbEvdsHandle *handle, ReturnErrorC expected_error, int line) {
+    check(handle != NULL, "the handle is not NULL", line);
+    check(tcmb_evds_c_handle_error(handle) == expected_error, "the handle has the expected error", line);
+    check(tcmb_evds_c_handle_data_ptr(handle) != NULL, "the data of the handle is not NULL", line);
+    check(strlen(tcmb_evds_c_handle_data_ptr(handle)) == tcmb_evds_c_handle_len(handle), "the length is kept", line);
+
+    tcmb_evds_c_handle_free(handle);
+}
+
+#define EXPECT_HANDLE(handle, expected_error) expect_handle((handle), (expected_error), __LINE__)
+
+
+static void check_results(void) {
+    TcmbEvdsResult data_result = tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Csv, false);
+
+    check_buffers(data_result, __LINE__);
+    CHECK(data_result.error_type == NoError);
+    CHECK(strncmp((const char *) data_result.output_ptr, "Tarih,TP_DK_USD_S", 17) == 0);
+#if defined(TCMB_EVDS_C_CACHE)
+    CHECK(!tcmb_evds_c_is_stale(data_result));
+#endif
+
+#if defined(TCMB_EVDS_C_PARSE)
+    EXPECT(tcmb_evds_c_result_checksum(data_result), NoError);
+    EXPECT(tcmb_evds_c_reshape_result(data_result, LongLayout), NoError);
+#endif
+
+    tcmb_evds_c_result_free(data_result);
+
+    TcmbEvdsResult error_result = tcmb_evds_c_get_data(input(SERIES), input("32-13-2011"), input(API_KEY), Csv, false);
+
+    CHECK(tcmb_evds_c_is_error(error_result));
+
+#if defined(TCMB_EVDS_C_CACHE)
+    CHECK(!tcmb_evds_c_is_stale(error_result));
+#endif
+
+#if defined(TCMB_EVDS_C_PARSE)
+    EXPECT(tcmb_evds_c_result_checksum(error_result), ParameterError);
+    EXPECT(tcmb_evds_c_reshape_result(error_result, WideLayout), ParameterError);
+#endif
+    EXPECT(error_result, InvalidDate);
+
+    /* the results are released by value, so an empty result is released without any effect. */
+    TcmbEvdsResult empty_result = { NULL, 0, NoError, NULL, 0, 0 };
+
+    tcmb_evds_c_result_free(empty_result);
+}
+
+static void check_data_requests(void) {
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Json, false), NoError);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input("01-01-2000"), input(API_KEY), Csv, true), NoError);
+    EXPECT(tcmb_evds_c_get_data(NULL_INPUT, input(DATES), input(API_KEY), Csv, false), ParameterError);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), NULL_INPUT, input(API_KEY), Csv, false), ParameterError);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), NULL_INPUT, Csv, false), ParameterError);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input("01-01-2000,32-12-2011"), input(API_KEY), Csv, false),
+           InvalidDate);
+
+    /* a capacity longer than the string is rejected instead of reading beyond the string. */
+    TcmbEvdsInput long_capacity = { SERIES, sizeof(SERIES) + 8 };
+
+    EXPECT(tcmb_evds_c_get_data(long_capacity, input(DATES), input(API_KEY), Csv, false), ParameterError);
+
+#if defined(TCMB_EVDS_C_PARSE)
+    EXPECT(tcmb_evds_c_get_data_with_headers(input(SERIES), input(DATES), input(API_KEY), Csv, EnglishHeaders, false),
+           NoError);
+    EXPECT(tcmb_evds_c_get_data_with_headers(NULL_INPUT, input(DATES), input(API_KEY), Csv, CodeHeaders, false),
+           ParameterError);
+
+    TcmbEvdsDataset *dataset = NULL;
+
+    EXPECT(tcmb_evds_c_get_last_n(input(SERIES), 5, input(API_KEY), false, &dataset), NoError);
+    CHECK(dataset != NULL);
+    CHECK(tcmb_evds_c_dataset_observation_count(dataset) == 5);
+
+    tcmb_evds_c_dataset_free(dataset);
+
+    /* the dataset is optional, so the last observations are returned as a text only. */
+    EXPECT(tcmb_evds_c_get_last_n(input(SERIES), 5, input(API_KEY), false, NULL), NoError);
+
+    dataset = NULL;
+
+    EXPECT(tcmb_evds_c_get_last_n(NULL_INPUT, 5, input(API_KEY), false, &dataset), ParameterError);
+    CHECK(dataset == NULL);
+
+    EXPECT_ANY(tcmb_evds_c_get_real_data(
+        input(SERIES), input("TP.FG.J0"), input("2003"), input(DATES), input(API_KEY), Csv, false));
+    EXPECT_ERROR(tcmb_evds_c_get_real_data(
+        input(SERIES), NULL_INPUT, input("2003"), input(DATES), input(API_KEY), Csv, false));
+#endif
+
+#if defined(TCMB_EVDS_C_CURRENCY)
+    EXPECT_ANY(tcmb_evds_c_get_basket_index(
+        input("USD:0.5,EUR:0.5"), input("01-01-2000"), input(DATES), input(API_KEY), Csv, false));
+    EXPECT_ERROR(tcmb_evds_c_get_basket_index(
+        NULL_INPUT, input("01-01-2000"), input(DATES), input(API_KEY), Csv, false));
+
+    EXPECT(tcmb_evds_c_get_advanced_data(
+        input(SERIES), input(DATES), Average, Level, Monthly, input(API_KEY), Csv, false), NoError);
+    EXPECT(tcmb_evds_c_get_advanced_data(
+        NULL_INPUT, input(DATES), Average, Level, Monthly, input(API_KEY), Csv, false), ParameterError);
+
+    EXPECT(tcmb_evds_c_get_advanced_currency_data(
+        Usd, SellingSide, false, input(DATES), End, Level, Monthly, input(API_KEY), Csv, false), NoError);
+    EXPECT(tcmb_evds_c_get_advanced_currency_data(
+        Usd, SellingSide, false, input("32-13-2011"), End, Level, Monthly, input(API_KEY), Csv, false), InvalidDate);
+
+    EXPECT_TEXT(tcmb_evds_c_build_currency_series(Usd, SellingSide, false), "TP.DK.USD.S");
+    EXPECT_TEXT(tcmb_evds_c_build_currency_series(Eur, BuyingSide, true), "TP.DK.EUR.A.YTL");
+#endif
+}
+
+static void check_catalog_requests(void) {
+#if defined(TCMB_EVDS_C_CATALOG)
+    EXPECT(tcmb_evds_c_get_data_group(input("bie_dkdovytl"), input(DATES), input(API_KEY), Csv, false), NoError);
+    EXPECT(tcmb_evds_c_get_data_group(NULL_INPUT, input(DATES), input(API_KEY), Csv, false), ParameterError);
+
+    EXPECT(tcmb_evds_c_get_categories(input(API_KEY), Json, false), NoError);
+    EXPECT(tcmb_evds_c_get_categories(NULL_INPUT, Json, false), ParameterError);
+
+    /* the mock answers with observations instead of categories, so the tree cannot be built. */
+    TcmbEvdsCategoryTree *tree = NULL;
+
+    EXPECT_ANY(tcmb_evds_c_get_category_tree(input(API_KEY), EnglishHeaders, &tree));
+
+    CHECK(tcmb_evds_c_tree_first_category(tree) == tcmb_evds_c_tree_first_category(tree));
+
+    tcmb_evds_c_tree_free(tree);
+
+    EXPECT_ERROR(tcmb_evds_c_get_category_tree(NULL_INPUT, EnglishHeaders, NULL));
+
+    CHECK(tcmb_evds_c_tree_first_category(NULL) == -1);
+    CHECK(tcmb_evds_c_tree_first_child(NULL, 0) == -1);
+    CHECK(tcmb_evds_c_tree_next_sibling(NULL, 0) == -1);
+    CHECK(tcmb_evds_c_tree_node_code(NULL, 0) == NULL);
+    CHECK(tcmb_evds_c_tree_node_name(NULL, 0, EnglishHeaders) == NULL);
+    CHECK(tcmb_evds_c_tree_series_count(NULL, 0) == -1);
+
+    tcmb_evds_c_tree_free(NULL);
+
+    EXPECT(tcmb_evds_c_get_advanced_data_group(2, input("bie_dkdovytl"), input(API_KEY), Json, false), NoError);
+    EXPECT(tcmb_evds_c_get_advanced_data_group(2, NULL_INPUT, input(API_KEY), Json, false), ParameterError);
+
+    EXPECT(tcmb_evds_c_get_series_list(input("bie_dkdovytl"), input(API_KEY), Json, false), NoError);
+    EXPECT(tcmb_evds_c_get_series_list(NULL_INPUT, input(API_KEY), Json, false), ParameterError);
+
+    EXPECT_ANY(tcmb_evds_c_series_manifest(input("bie_dkdovytl"), input(API_KEY)));
+    EXPECT_ERROR(tcmb_evds_c_series_manifest(NULL_INPUT, input(API_KEY)));
+
+    EXPECT_ERROR(tcmb_evds_c_diff_manifests(input("{}"), NULL_INPUT));
+    EXPECT_ERROR(tcmb_evds_c_diff_manifests(input("not a manifest"), input("not a manifest")));
+
+    EXPECT(tcmb_evds_c_describe_series(input("TP.DK.USD.S.YTL")), NoError);
+    EXPECT(tcmb_evds_c_describe_series(NULL_INPUT), ParameterError);
+
+    EXPECT_ANY(tcmb_evds_c_audit_data_group(input("bie_dkdovytl"), input(DATES), input(API_KEY), Json));
+    EXPECT_ANY(tcmb_evds_c_audit_series_list(input("bie_dkdovytl"), input(API_KEY), Json));
+#endif
+
+#if defined(TCMB_EVDS_C_PARSE)
+    EXPECT(tcmb_evds_c_define_series(input("SPREAD"), input("TP.DK.EUR.S - TP.DK.USD.S")), NoError);
+    EXPECT_ERROR(tcmb_evds_c_define_series(input("BROKEN"), input("TP.DK.EUR.S -")));
+    EXPECT_ERROR(tcmb_evds_c_define_series(NULL_INPUT, input("TP.DK.EUR.S")));
+
+    CHECK(tcmb_evds_c_remove_series(input("SPREAD")));
+    CHECK(!tcmb_evds_c_remove_series(input("SPREAD")));
+    CHECK(!tcmb_evds_c_remove_series(NULL_INPUT));
+
+    EXPECT_ANY(tcmb_evds_c_list_series(input("TP.DK."), Csv));
+    EXPECT(tcmb_evds_c_list_series(NULL_INPUT, Csv), ParameterError);
+#endif
+
+    EXPECT_ANY(tcmb_evds_c_audit_data(input(SERIES), input(DATES), input(API_KEY), Csv));
+    EXPECT_ERROR(tcmb_evds_c_audit_data(NULL_INPUT, input(DATES), input(API_KEY), Csv));
+}
+
+static void check_handles(void) {
+    TcmbEvdsHandle *handle = tcmb_evds_c_get_data_handle(input(SERIES), input(DATES), input(API_KEY), Json, false);
+    unsigned long total_count = 0;
+
+    CHECK(tcmb_evds_c_handle_error(handle) == NoError);
+    CHECK(tcmb_evds_c_handle_detail(handle) == NULL);
+    CHECK(tcmb_evds_c_handle_total_count(handle, &total_count));
+    CHECK(total_count == 10);
+    CHECK(tcmb_evds_c_handle_row_count(handle) == 10);
+    CHECK(tcmb_evds_c_handle_null_count(handle) == 0);
+    CHECK(is_text(tcmb_evds_c_handle_first_date(handle), "01-01-2000"));
+    CHECK(is_text(tcmb_evds_c_handle_last_date(handle), "10-01-2000"));
+
+    EXPECT_HANDLE(handle, NoError);
+
+    EXPECT_HANDLE(tcmb_evds_c_get_data_handle(NULL_INPUT, input(DATES), input(API_KEY), Json, false), ParameterError);
+
+    /* the accessors of a NULL handle give empty values. */
+    CHECK(tcmb_evds_c_handle_data_ptr(NULL) == NULL);
+    CHECK(tcmb_evds_c_handle_len(NULL) == 0);
+    CHECK(tcmb_evds_c_handle_error(NULL) == InternalError);
+    CHECK(tcmb_evds_c_handle_detail(NULL) == NULL);
+    CHECK(!tcmb_evds_c_handle_total_count(NULL, &total_count));
+    CHECK(tcmb_evds_c_handle_row_count(NULL) == 0);
+    CHECK(tcmb_evds_c_handle_null_count(NULL) == 0);
+    CHECK(tcmb_evds_c_handle_first_date(NULL) == NULL);
+    CHECK(tcmb_evds_c_handle_last_date(NULL) == NULL);
+
+    tcmb_evds_c_handle_free(NULL);
+
+#if defined(TCMB_EVDS_C_PARSE)
+    TcmbEvdsDataset *dataset = NULL;
+
+    EXPECT_HANDLE(tcmb_evds_c_get_last_n_handle(input(SERIES), 3, input(API_KEY), false, &dataset), NoError);
+    CHECK(tcmb_evds_c_dataset_observation_count(dataset) == 3);
+
+    tcmb_evds_c_dataset_free(dataset);
+#endif
+
+#if defined(TCMB_EVDS_C_CURRENCY)
+    EXPECT_HANDLE(tcmb_evds_c_get_advanced_data_handle(
+        input(SERIES), input(DATES), Average, Level, Monthly, input(API_KEY), Csv, false), NoError);
+#endif
+
+#if defined(TCMB_EVDS_C_CATALOG)
+    EXPECT_HANDLE(
+        tcmb_evds_c_get_data_group_handle(input("bie_dkdovytl"), input(DATES), input(API_KEY), Csv, false), NoError);
+    EXPECT_HANDLE(tcmb_evds_c_get_categories_handle(input(API_KEY), Json, false), NoError);
+    EXPECT_HANDLE(
+        tcmb_evds_c_get_advanced_data_group_handle(2, input("bie_dkdovytl"), input(API_KEY), Json, false), NoError);
+    EXPECT_HANDLE(tcmb_evds_c_get_series_list_handle(input("bie_dkdovytl"), input(API_KEY), Json, false), NoError);
+    EXPECT_HANDLE(tcmb_evds_c_get_series_list_handle(NULL_INPUT, input(API_KEY), Json, false), ParameterError);
+#endif
+}
+
+static void check_request_builder(void) {
+    TcmbEvdsRequest *request = tcmb_evds_c_request_new();
+
+    CHECK(request != NULL);
+
+    EXPECT(tcmb_evds_c_validate(request), ParameterError);
+
+    CHECK(tcmb_evds_c_request_set_series(request, input(SERIES)));
+    CHECK(tcmb_evds_c_request_set_dates(request, input(DATES)));
+    CHECK(tcmb_evds_c_request_set_api_key(request, input(API_KEY)));
+    CHECK(tcmb_evds_c_request_set_return_format(request, Csv));
+    CHECK(tcmb_evds_c_request_set_ascii_mode(request, false));
+
+    EXPECT(tcmb_evds_c_validate(request), NoError);
+    EXPECT(tcmb_evds_c_execute(request), NoError);
+    EXPECT(tcmb_evds_c_request_with_dates(request, input("01-01-2000"), input("31-12-2000")), NoError);
+    EXPECT(tcmb_evds_c_request_with_dates(request, input("32-13-2000"), input("31-12-2000")), InvalidDate);
+
+    CHECK(tcmb_evds_c_request_set_aggregation_type(request, Average));
+    CHECK(tcmb_evds_c_request_set_formula(request, Level));
+    CHECK(tcmb_evds_c_request_set_frequency(request, Monthly));
+
+    EXPECT_ANY(tcmb_evds_c_execute(request));
+
+    CHECK(!tcmb_evds_c_request_set_series(request, NULL_INPUT));
+
+    tcmb_evds_c_request_free(request);
+
+    request = tcmb_evds_c_request_new();
+
+    CHECK(tcmb_evds_c_request_set_data_group(request, input("bie_dkdovytl")));
+    CHECK(tcmb_evds_c_request_set_dates(request, input(DATES)));
+    CHECK(tcmb_evds_c_request_set_api_key(request, input(API_KEY)));
+
+    EXPECT_ANY(tcmb_evds_c_execute(request));
+
+    tcmb_evds_c_request_free(request);
+
+    /* every function of the builder rejects a NULL request. */
+    CHECK(!tcmb_evds_c_request_set_series(NULL, input(SERIES)));
+    CHECK(!tcmb_evds_c_request_set_data_group(NULL, input("bie_dkdovytl")));
+    CHECK(!tcmb_evds_c_request_set_dates(NULL, input(DATES)));
+    CHECK(!tcmb_evds_c_request_set_api_key(NULL, input(API_KEY)));
+    CHECK(!tcmb_evds_c_request_set_return_format(NULL, Csv));
+    CHECK(!tcmb_evds_c_request_set_ascii_mode(NULL, true));
+    CHECK(!tcmb_evds_c_request_set_aggregation_type(NULL, Average));
+    CHECK(!tcmb_evds_c_request_set_formula(NULL, Level));
+    CHECK(!tcmb_evds_c_request_set_frequency(NULL, Monthly));
+
+    EXPECT(tcmb_evds_c_execute(NULL), ParameterError);
+    EXPECT(tcmb_evds_c_request_with_dates(NULL, input("01-01-2000"), input("31-12-2000")), ParameterError);
+    EXPECT(tcmb_evds_c_validate(NULL), ParameterError);
+
+    tcmb_evds_c_request_free(NULL);
+}
+
+static void check_datasets(void) {
+#if defined(TCMB_EVDS_C_PARSE)
+    TcmbEvdsDataset *dataset = NULL;
+
+    EXPECT(tcmb_evds_c_get_last_n(input(SERIES), 10, input(API_KEY), false, &dataset), NoError);
+
+    CHECK(tcmb_evds_c_dataset_observation_count(dataset) == 10);
+    CHECK(tcmb_evds_c_dataset_series_count(dataset) == 1);
+    CHECK(tcmb_evds_c_dataset_series_code(dataset, 0) != NULL);
+    CHECK(tcmb_evds_c_dataset_series_code(dataset, 1) == NULL);
+    CHECK(is_text(tcmb_evds_c_dataset_date(dataset, 0), "01-01-2000"));
+    CHECK(tcmb_evds_c_dataset_date(dataset, 10) == NULL);
+
+    double value = -1.0;
+    int64_t unix_time = 0;
+    unsigned long column = 99;
+
+    CHECK(tcmb_evds_c_dataset_value(dataset, 1, 0, &value) && value == 1.0001);
+    CHECK(!tcmb_evds_c_dataset_value(dataset, 10, 0, &value));
+    CHECK(!tcmb_evds_c_dataset_value(dataset, 0, 0, NULL));
+    CHECK(tcmb_evds_c_dataset_unix_time(dataset, 0, &unix_time) && unix_time == 946677600);
+    CHECK(!tcmb_evds_c_dataset_unix_time(dataset, 10, &unix_time));
+
+    /* the mock does not give the units of the series. */
+    CHECK(tcmb_evds_c_dataset_unit(dataset, 0) == NULL);
+    CHECK(tcmb_evds_c_dataset_unit(dataset, 1) == NULL);
+
+    CHECK(tcmb_evds_c_dataset_find_column(dataset, input(SERIES), &column) && column == 0);
+    CHECK(!tcmb_evds_c_dataset_find_column(dataset, input("TP.DK.EUR.S"), &column));
+    CHECK(!tcmb_evds_c_dataset_find_column(dataset, NULL_INPUT, &column));
+
+    double buffer[16];
+
+    CHECK(tcmb_evds_c_dataset_column_len(dataset, 0) == 10);
+    CHECK(tcmb_evds_c_dataset_column_len(dataset, 1) == 0);
+    CHECK(tcmb_evds_c_dataset_copy_column(dataset, 0, buffer, 16) == 10);
+    CHECK(tcmb_evds_c_dataset_copy_column(dataset, 0, buffer, 4) == 4);
+    CHECK(tcmb_evds_c_dataset_copy_column(dataset, 0, NULL, 16) == 0);
+
+    EXPECT_ERROR(tcmb_evds_c_rescale(dataset, input("million TRY")));
+    EXPECT_ERROR(tcmb_evds_c_rescale(dataset, NULL_INPUT));
+
+    CHECK(tcmb_evds_c_dataset_round(dataset, 2));
+
+    EXPECT(tcmb_evds_c_detect_outliers(dataset, ZScore, 3.0), NoError);
+    EXPECT(tcmb_evds_c_dataset_to_csv(dataset, WideLayout), NoError);
+    EXPECT(tcmb_evds_c_dataset_to_csv(dataset, LongLayout), NoError);
+    EXPECT(tcmb_evds_c_diff(dataset, dataset), NoError);
+    EXPECT(tcmb_evds_c_eval(input("TP.DK.USD.S * 2"), dataset), NoError);
+    EXPECT(tcmb_evds_c_eval(input("TP.DK.USD.S *"), dataset), InvalidExpression);
+    EXPECT_ERROR(tcmb_evds_c_eval(NULL_INPUT, dataset));
+
+    TcmbEvdsFrequencyInfo frequency_info = tcmb_evds_c_infer_frequency(dataset);
+
+    CHECK(frequency_info.observation_count == 10);
+
+#if defined(TCMB_EVDS_C_SEASONAL)
+    EXPECT_ANY(tcmb_evds_c_seasonally_adjust(dataset));
+#endif
+
+    const TcmbEvdsDataset *chunks[] = { dataset, dataset };
+    TcmbEvdsDataset *merged = NULL;
+
+    EXPECT(tcmb_evds_c_merge(chunks, 2, &merged), NoError);
+    CHECK(tcmb_evds_c_dataset_observation_count(merged) == 10);
+
+    tcmb_evds_c_dataset_free(merged);
+
+    merged = NULL;
+
+    EXPECT(tcmb_evds_c_merge(NULL, 2, &merged), ParameterError);
+    CHECK(merged == NULL);
+
+    TcmbEvdsIterator *iterator = tcmb_evds_c_iter_new(dataset);
+    const char *date = NULL;
+    unsigned long row_count = 0;
+
+    CHECK(iterator != NULL);
+
+    while (tcmb_evds_c_iter_next(iterator, &date, &value)) {
+        CHECK(date != NULL);
+        row_count++;
+    }
+
+    CHECK(row_count == 10);
+    CHECK(!tcmb_evds_c_iter_next(iterator, &date, &value));
+
+    tcmb_evds_c_iter_free(iterator);
+
+    tcmb_evds_c_dataset_free(dataset);
+
+    /* the functions of the datasets treat NULL as an empty dataset. */
+    CHECK(tcmb_evds_c_dataset_observation_count(NULL) == 0);
+    CHECK(tcmb_evds_c_dataset_series_count(NULL) == 0);
+    CHECK(tcmb_evds_c_dataset_series_code(NULL, 0) == NULL);
+    CHECK(tcmb_evds_c_dataset_date(NULL, 0) == NULL);
+    CHECK(!tcmb_evds_c_dataset_value(NULL, 0, 0, &value));
+    CHECK(!tcmb_evds_c_dataset_unix_time(NULL, 0, &unix_time));
+    CHECK(tcmb_evds_c_dataset_unit(NULL, 0) == NULL);
+    CHECK(!tcmb_evds_c_dataset_find_column(NULL, input(SERIES), &column));
+    CHECK(tcmb_evds_c_dataset_column_len(NULL, 0) == 0);
+    CHECK(tcmb_evds_c_dataset_copy_column(NULL, 0, buffer, 16) == 0);
+    CHECK(!tcmb_evds_c_dataset_round(NULL, 2));
+    CHECK(!tcmb_evds_c_infer_frequency(NULL).is_inferred);
+    CHECK(tcmb_evds_c_iter_new(NULL) == NULL);
+    CHECK(!tcmb_evds_c_iter_next(NULL, &date, &value));
+
+    EXPECT(tcmb_evds_c_rescale(NULL, input("million TRY")), ParameterError);
+    EXPECT(tcmb_evds_c_detect_outliers(NULL, ZScore, 3.0), ParameterError);
+    EXPECT(tcmb_evds_c_dataset_to_csv(NULL, WideLayout), ParameterError);
+    EXPECT(tcmb_evds_c_diff(NULL, NULL), ParameterError);
+
+    tcmb_evds_c_iter_free(NULL);
+#endif
+
+    tcmb_evds_c_dataset_free(NULL);
+}
+
+static void check_lazy_datasets(void) {
+#if defined(TCMB_EVDS_C_PARSE)
+    TcmbEvdsResult json_result = tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Json, false);
+    TcmbEvdsLazyDataset *lazy_dataset = tcmb_evds_c_lazy_dataset_from_result(json_result);
+    double value = -1.0;
+
+    CHECK(lazy_dataset != NULL);
+    CHECK(tcmb_evds_c_lazy_dataset_observation_count(lazy_dataset) == 10);
+    CHECK(tcmb_evds_c_lazy_dataset_value(lazy_dataset, 2, input(SERIES), &value) && value == 2.0002);
+    CHECK(!tcmb_evds_c_lazy_dataset_value(lazy_dataset, 10, input(SERIES), &value));
+    CHECK(tcmb_evds_c_lazy_dataset_latest_value(lazy_dataset, input(SERIES), &value) && value == 9.0009);
+    CHECK(!tcmb_evds_c_lazy_dataset_latest_value(lazy_dataset, input("TP.DK.EUR.S"), &value));
+
+    TcmbEvdsDataset *dataset = tcmb_evds_c_lazy_dataset_materialize(lazy_dataset, input(SERIES), 2, 3);
+
+    CHECK(tcmb_evds_c_dataset_observation_count(dataset) == 3);
+
+    tcmb_evds_c_dataset_free(dataset);
+
+    /* the lazy dataset keeps its own copy of the response, so the result is released separately. */
+    tcmb_evds_c_lazy_dataset_free(lazy_dataset);
+    tcmb_evds_c_result_free(json_result);
+
+    TcmbEvdsResult error_result = tcmb_evds_c_get_data(NULL_INPUT, input(DATES), input(API_KEY), Json, false);
+
+    CHECK(tcmb_evds_c_lazy_dataset_from_result(error_result) == NULL);
+
+    tcmb_evds_c_result_free(error_result);
+
+    CHECK(tcmb_evds_c_lazy_dataset_observation_count(NULL) == 0);
+    CHECK(!tcmb_evds_c_lazy_dataset_value(NULL, 0, input(SERIES), &value));
+    CHECK(!tcmb_evds_c_lazy_dataset_latest_value(NULL, input(SERIES), &value));
+    CHECK(tcmb_evds_c_lazy_dataset_materialize(NULL, input(SERIES), 0, 1) == NULL);
+
+    tcmb_evds_c_lazy_dataset_free(NULL);
+#endif
+}
+
+static void check_wide_inputs(void) {
+#if defined(TCMB_EVDS_C_PARSE)
+    TcmbEvdsWideInput series = { L"" SERIES, wcslen(L"" SERIES) };
+    TcmbEvdsWideInput dates = { L"" DATES, wcslen(L"" DATES) };
+    TcmbEvdsWideInput api_key = { L"" API_KEY, wcslen(L"" API_KEY) };
+    TcmbEvdsWideInput group = { L"bie_dkdovytl", wcslen(L"bie_dkdovytl") };
+    TcmbEvdsWideInput null_input = { NULL, 0 };
+
+    EXPECT(tcmb_evds_c_get_data_w(series, dates, api_key, Csv, false), NoError);
+    EXPECT(tcmb_evds_c_get_data_w(null_input, dates, api_key, Csv, false), ParameterError);
+
+#if defined(TCMB_EVDS_C_CURRENCY)
+    EXPECT(tcmb_evds_c_get_advanced_data_w(series, dates, Average, Level, Monthly, api_key, Csv, false), NoError);
+#endif
+
+#if defined(TCMB_EVDS_C_CATALOG)
+    EXPECT(tcmb_evds_c_get_data_group_w(group, dates, api_key, Csv, false), NoError);
+    EXPECT(tcmb_evds_c_get_categories_w(api_key, Json, false), NoError);
+    EXPECT(tcmb_evds_c_get_series_list_w(group, api_key, Json, false), NoError);
+    EXPECT(tcmb_evds_c_get_series_list_w(null_input, api_key, Json, false), ParameterError);
+#else
+    (void) group;
+#endif
+#endif
+}
+
+static void check_dates_and_enums(void) {
+    int64_t epoch = 0;
+
+    CHECK(tcmb_evds_c_date_to_epoch(input("13-12-2011"), &epoch) && epoch == 1323727200);
+    CHECK(!tcmb_evds_c_date_to_epoch(input("32-13-2011"), &epoch));
+    CHECK(!tcmb_evds_c_date_to_epoch(input("13-12-2011"), NULL));
+    CHECK(!tcmb_evds_c_date_to_epoch(NULL_INPUT, &epoch));
+
+    EXPECT_TEXT(tcmb_evds_c_epoch_to_date(1323727200), "13-12-2011");
+
+    CHECK(tcmb_evds_c_is_publication_day(input("29-10-2018")) == NoPublicationDay);
+    CHECK(tcmb_evds_c_is_publication_day(input("30-10-2018")) == PublicationDay);
+    CHECK(tcmb_evds_c_is_publication_day(input("31-08-2011")) == UnknownPublicationDay);
+    CHECK(tcmb_evds_c_is_publication_day(NULL_INPUT) == InvalidPublicationDate);
+
+    CHECK(is_text(tcmb_evds_c_aggregation_to_string(Average), "avg"));
+    CHECK(is_text(tcmb_evds_c_formula_to_string(Level), "0"));
+    CHECK(is_text(tcmb_evds_c_frequency_to_string(Monthly), "5"));
+    CHECK(is_text(tcmb_evds_c_format_to_string(Csv), "csv"));
+
+    /* the values outside the C enums are rejected when strict enums are enabled for untrusted values. */
+    tcmb_evds_c_set_strict_enums(true);
+
+    CHECK(tcmb_evds_c_aggregation_to_string((TcmbEvdsAggregationType) 99) == NULL);
+    CHECK(tcmb_evds_c_formula_to_string((TcmbEvdsFormula) 99) == NULL);
+    CHECK(tcmb_evds_c_frequency_to_string((TcmbEvdsDataFrequency) 99) == NULL);
+    CHECK(tcmb_evds_c_format_to_string((TcmbEvdsReturnFormat) 99) == NULL);
+
+    tcmb_evds_c_set_strict_enums(false);
+}
+
+static void check_settings(void) {
+    TcmbEvdsResult data_result;
+
+    tcmb_evds_c_set_strict_enums(true);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), (TcmbEvdsReturnFormat) 99, false),
+           InvalidEnumValue);
+    tcmb_evds_c_set_strict_enums(false);
+
+    tcmb_evds_c_set_strict_dates(true);
+    tcmb_evds_c_set_strict_dates(false);
+
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input("01.01.2000,31/12/2011"), input(API_KEY), Csv, false), NoError);
+    tcmb_evds_c_set_strict_date_separators(true);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input("01.01.2000,31/12/2011"), input(API_KEY), Csv, false),
+           MissingDashInDateData);
+    tcmb_evds_c_set_strict_date_separators(false);
+
+    tcmb_evds_c_set_max_date_span(30);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Csv, false), DateRangeTooLarge);
+    tcmb_evds_c_set_max_date_span(0);
+
+    tcmb_evds_c_set_huge_request_policy(RejectHugeRequests, 1);
+    EXPECT_ANY(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Csv, false));
+    tcmb_evds_c_set_huge_request_policy(AllowHugeRequests, 0);
+
+    tcmb_evds_c_set_ascii_mode(AsciiModeForceOn);
+    tcmb_evds_c_set_ascii_mode(AsciiModeDefault);
+
+    tcmb_evds_c_set_error_format(JsonErrors);
+    data_result = tcmb_evds_c_get_data(NULL_INPUT, input(DATES), input(API_KEY), Csv, false);
+    CHECK(data_result.string_capacity > 0 && data_result.output_ptr[0] == '{');
+    EXPECT(data_result, ParameterError);
+    tcmb_evds_c_set_error_format(PlainTextErrors);
+
+    CHECK(tcmb_evds_c_set_today_utc_offset(0));
+    CHECK(!tcmb_evds_c_set_today_utc_offset(900));
+    CHECK(tcmb_evds_c_set_today_utc_offset(180));
+
+    tcmb_evds_c_set_deadline(60000);
+    tcmb_evds_c_set_attempt_timeout(30000);
+    tcmb_evds_c_set_deadline(0);
+    tcmb_evds_c_set_attempt_timeout(0);
+
+    tcmb_evds_c_set_thread_priority(BulkPriority);
+    tcmb_evds_c_set_bulk_queue_capacity(4);
+    tcmb_evds_c_set_thread_priority(InteractivePriority);
+
+    TcmbEvdsSessionDefaults defaults;
+
+    defaults.date = input(DATES);
+    defaults.trailing_days = 0;
+    defaults.date_policy = NoDatePolicy;
+    defaults.return_format = Csv;
+    defaults.ascii_mode = AsciiModeDefault;
+
+    CHECK(tcmb_evds_c_set_session_defaults(defaults));
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(""), input(API_KEY), SessionFormat, false), NoError);
+
+    defaults.date = input("32-13-2011");
+
+    CHECK(!tcmb_evds_c_set_session_defaults(defaults));
+
+    tcmb_evds_c_clear_session_defaults();
+
+#if defined(TCMB_EVDS_C_PARSE)
+    unsigned int post_processors[] = { AsciiPostProcessor, RoundingPostProcessor };
+
+    CHECK(tcmb_evds_c_set_post_processors(post_processors, 2, input(""), 2));
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Csv, false), NoError);
+    CHECK(!tcmb_evds_c_set_post_processors(NULL, 2, input(""), 2));
+    CHECK(tcmb_evds_c_set_post_processors(NULL, 0, input(""), 0));
+
+    tcmb_evds_c_set_strict_schema(true);
+    tcmb_evds_c_set_strict_schema(false);
+
+    tcmb_evds_c_set_csv_injection_protection(true);
+    tcmb_evds_c_set_csv_injection_protection(false);
+
+    CHECK(tcmb_evds_c_set_json_backend(SerdeJsonBackend));
+#endif
+
+    CHECK(tcmb_evds_c_set_api_keys(input("FIRST_API_KEY,SECOND_API_KEY"), RoundRobinRotation));
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(""), Csv, false), NoError);
+    EXPECT(tcmb_evds_c_key_pool_usage(), NoError);
+    CHECK(!tcmb_evds_c_set_api_keys(NULL_INPUT, RoundRobinRotation));
+    CHECK(tcmb_evds_c_set_api_keys(input(""), RoundRobinRotation));
+
+    EXPECT_ANY(tcmb_evds_c_key_status(input(API_KEY)));
+    EXPECT_ERROR(tcmb_evds_c_key_status(input("")));
+
+#if defined(TCMB_EVDS_C_TRACING)
+    CHECK(tcmb_evds_c_set_trace_parent(input("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01")));
+    CHECK(!tcmb_evds_c_set_trace_parent(input("not a trace parent")));
+    CHECK(!tcmb_evds_c_set_otlp_endpoint(NULL_INPUT));
+    CHECK(tcmb_evds_c_flush_spans());
+#endif
+}
+
+static void check_cache(void) {
+#if defined(TCMB_EVDS_C_CACHE)
+    tcmb_evds_c_set_response_cache_capacity(8);
+    tcmb_evds_c_set_response_cache_max_age(60);
+#if defined(TCMB_EVDS_C_CATALOG)
+    tcmb_evds_c_set_catalog_cache_max_age(60);
+#endif
+    tcmb_evds_c_set_stale_if_error(true);
+
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Json, false), NoError);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Json, false), NoError);
+
+    TcmbEvdsMetrics metrics = tcmb_evds_c_metrics_snapshot();
+
+    CHECK(metrics.cache_hits > 0);
+
+    tcmb_evds_c_clear_response_cache();
+    tcmb_evds_c_set_stale_if_error(false);
+    tcmb_evds_c_set_response_cache_capacity(0);
+
+    char store_directory[1024];
+
+    snprintf(store_directory, sizeof(store_directory), "%s/observation_store", output_directory);
+
+    CHECK(tcmb_evds_c_open_observation_store(input(store_directory)));
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Json, false), NoError);
+
+    tcmb_evds_c_close_observation_store();
+
+    CHECK(!tcmb_evds_c_open_observation_store(NULL_INPUT));
+
+    EXPECT_ERROR(tcmb_evds_c_warm_cache(input("missing_manifest.json"), input(API_KEY)));
+    EXPECT_ERROR(tcmb_evds_c_warm_cache(NULL_INPUT, input(API_KEY)));
+#endif
+
+    /* the live result is larger than the budget, so the next request fails until the result is released. */
+    TcmbEvdsResult live_result = tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Json, false);
+
+    tcmb_evds_c_set_memory_budget(64);
+
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Json, false), MemoryBudgetExceeded);
+
+    tcmb_evds_c_set_memory_budget(0);
+
+    EXPECT(live_result, NoError);
+}
+
+static void check_exports(void) {
+#if defined(TCMB_EVDS_C_EXPORT)
+    EXPECT_ANY(tcmb_evds_c_export_data_group(
+        input("bie_dkdovytl"), input(DATES), input(output_directory), input(API_KEY)));
+    EXPECT_ERROR(tcmb_evds_c_export_data_group(
+        input("bie_dkdovytl"), input(DATES), NULL_INPUT, input(API_KEY)));
+#endif
+}
+
+static int request_hook_call_count = 0;
+
+static const char *count_request(TcmbEvdsRequestInfo *request, void *user_data) {
+    (void) user_data;
+
+    if (request != NULL && request->url != NULL) { request_hook_call_count++; }
+
+    return NULL;
+}
+
+/* vetoes the requests of the observations, so that the api key is still verified via the mock. */
+static const char *veto_request(TcmbEvdsRequestInfo *request, void *user_data) {
+    if (request != NULL && request->url != NULL && strstr(request->url, "startDate=01-01-2000") != NULL) {
+        return (const char *) user_data;
+    }
+
+    return NULL;
+}
+
+#if defined(TCMB_EVDS_C_PARSE)
+static int response_hook_call_count = 0;
+
+static void count_response(const TcmbEvdsDataset *dataset, void *user_data) {
+    (void) user_data;
+
+    if (dataset != NULL) { response_hook_call_count++; }
+}
+#endif
+
+static void check_hooks_and_recordings(void) {
+    tcmb_evds_c_set_request_hook(count_request, NULL);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Csv, false), NoError);
+    CHECK(request_hook_call_count > 0);
+
+    tcmb_evds_c_set_request_hook(veto_request, "vetoed by the contract test");
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Csv, false), RequestVetoed);
+
+    tcmb_evds_c_set_request_hook(NULL, NULL);
+
+#if defined(TCMB_EVDS_C_PARSE)
+    tcmb_evds_c_set_response_hook(count_response, NULL);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Json, false), NoError);
+    CHECK(response_hook_call_count == 1);
+
+    tcmb_evds_c_set_response_hook(NULL, NULL);
+#endif
+
+    char recording_directory[1024];
+
+    snprintf(recording_directory, sizeof(recording_directory), "%s/recordings", output_directory);
+
+    CHECK(tcmb_evds_c_set_recording(RecordResponses, input(recording_directory)));
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Csv, false), NoError);
+
+    CHECK(tcmb_evds_c_set_recording(ReplayResponses, input(recording_directory)));
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input(DATES), input(API_KEY), Csv, false), NoError);
+    EXPECT(tcmb_evds_c_get_data(input(SERIES), input("01-01-2001"), input(API_KEY), Csv, false), RecordingNotFound);
+
+    CHECK(!tcmb_evds_c_set_recording(RecordResponses, NULL_INPUT));
+    CHECK(tcmb_evds_c_set_recording(RecordingOff, input("")));
+}
+
+static void check_metrics_and_health(void) {
+    TcmbEvdsMetrics metrics = tcmb_evds_c_metrics_snapshot();
+
+    CHECK(metrics.requests > 0);
+    CHECK(metrics.errors > 0);
+    CHECK(tcmb_evds_c_metrics_error_count(InvalidDate) > 0);
+    CHECK(tcmb_evds_c_metrics_error_count(TlsError) == 0);
+
+    EXPECT(tcmb_evds_c_metrics_prometheus(), NoError);
+
+    TcmbEvdsResult build_info = tcmb_evds_c_build_info();
+
+    CHECK(build_info.string_capacity > 0 && build_info.output_ptr[0] == '{');
+    EXPECT(build_info, NoError);
+
+    TcmbEvdsHealth health = tcmb_evds_c_health();
+
+    CHECK(health.circuit_state == CircuitClosed);
+    CHECK(health.consecutive_failures == 0);
+}
+
+static void check_memory_stats(void) {
+    TcmbEvdsMemoryStats memory_stats = tcmb_evds_c_memory_stats();
+
+    CHECK(memory_stats.live_results == 0);
+    CHECK(memory_stats.live_handles == 0);
+    CHECK(memory_stats.live_datasets == 0);
+    CHECK(memory_stats.live_bytes == 0);
+    CHECK(memory_stats.budget_bytes == 0);
+}
+
+
+int main(int argc, char **argv) {
+    if (argc > 1) { output_directory = argv[1]; }
+
+    check_results();
+    check_data_requests();
+    check_catalog_requests();
+    check_handles();
+    check_request_builder();
+    check_datasets();
+    check_lazy_datasets();
+    check_wide_inputs();
+    check_dates_and_enums();
+    check_settings();
+    check_cache();
+    check_exports();
+    check_hooks_and_recordings();
+    check_metrics_and_health();
+
+    /* every result, handle and dataset above is released, so nothing is left alive. */
+    check_memory_stats();
+
+    if (failure_count > 0) {
+        fprintf(stderr, "%d checks of the contract failed.\n", failure_count);
+
+        return 1;
+    }
+
+    printf("The contract of tcmb_evds_c.h holds.\n");
+
+    return 0;
+}
@@ -0,0 +1,123 @@
+//! runs the C program in `tests/c/contract.c` against the local mock of EVDS to check the contract of the C interface,
+//! e.g. `cargo test --features c_contract --test c_contract`.
+//!
+//! The program is compiled with the C compiler in the `CC` environment variable, or `cc`, against the generated header
+//! and the built library, so the changes breaking the C programs of the users fail the test.
+
+#![cfg(unix)]
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tcmb_evds_c::bench::MOCK_URL_ROOT_VARIABLE;
+use tcmb_evds_c::bench::mock_server::start_mock_server;
+
+
+/// is the number of the observations of the mock responses, which the checks of the program rely on.
+const OBSERVATION_COUNT: usize = 10;
+
+
+/// gives the directory of the library built for the test, which is the "deps" directory containing the test binary.
+/// The library in its parent directory, e.g. "target/debug", may be built with other features.
+fn find_library_directory() -> PathBuf {
+    env::current_exe().unwrap().parent().unwrap().to_path_buf()
+}
+
+/// gives the directory of the header written by the build script, which is the target directory.
+fn find_header_directory() -> PathBuf {
+    match env::var_os("CARGO_TARGET_DIR") {
+        Some(target_directory) => PathBuf::from(target_directory),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("target"),
+    }
+}
+
+/// gives the names of the functions declared in the header.
+fn read_declared_functions(header: &str) -> Vec<String> {
+
+    let mut functions: Vec<String> = header
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .flat_map(|line| line.match_indices("tcmb_evds_c_").map(move |(start, _)| &line[start..]))
+        .filter_map(|declaration| {
+            let name_end = declaration.find(|character: char| !character.is_ascii_alphanumeric() && character != '_')?;
+
+            match declaration[name_end..].starts_with('(') {
+                true => Some(declaration[..name_end].to_string()),
+                false => None,
+            }
+        })
+        .collect();
+
+    functions.sort();
+    functions.dedup();
+
+    functions
+}
+
+#[test]
+fn should_call_every_declared_function() {
+    let header = fs::read_to_string(find_header_directory().join("tcmb_evds_c.h")).unwrap();
+    let program = fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/c/contract.c")).unwrap();
+
+    let declared_functions = read_declared_functions(&header);
+
+    assert!(declared_functions.contains(&"tcmb_evds_c_get_data".to_string()));
+
+    let uncalled_functions: Vec<&String> = declared_functions
+        .iter()
+        .filter(|function| !program.contains(&format!("{}(", function)))
+        .collect();
+
+    assert!(uncalled_functions.is_empty(), "tests/c/contract.c does not call {:?}", uncalled_functions);
+}
+
+#[test]
+fn should_keep_contract_in_c() {
+    let library_directory = find_library_directory();
+    let output_directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("c_contract");
+    let program = output_directory.join("contract");
+
+    // the files of the previous runs are removed, so the recordings and the stores start empty.
+    let _ = fs::remove_dir_all(&output_directory);
+    fs::create_dir_all(&output_directory).unwrap();
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+
+    let compilation = Command::new(compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror", "-o"])
+        .arg(&program)
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/c/contract.c"))
+        .arg("-I")
+        .arg(find_header_directory())
+        .arg("-L")
+        .arg(&library_directory)
+        .arg(format!("-Wl,-rpath,{}", library_directory.display()))
+        .arg("-ltcmb_evds_c")
+        .output()
+        .unwrap();
+
+    assert!(
+        compilation.status.success(),
+        "tests/c/contract.c is not compiled:\n{}",
+        String::from_utf8_lossy(&compilation.stderr)
+    );
+
+    let address = start_mock_server(OBSERVATION_COUNT);
+
+    // cargo adds the parent directory to the library path of the tests, which would load the library built there.
+    let run = Command::new(&program)
+        .arg(&output_directory)
+        .env("LD_LIBRARY_PATH", &library_directory)
+        .env(MOCK_URL_ROOT_VARIABLE, format!("http://{}/", address))
+        .output()
+        .unwrap();
+
+    assert!(
+        run.status.success(),
+        "the contract is broken:\n{}{}",
+        String::from_utf8_lossy(&run.stdout),
+        String::from_utf8_lossy(&run.stderr)
+    );
+}